@@ -0,0 +1,354 @@
+//! DuckDB catalog reader
+//!
+//! Reads tables, views, columns, constraints and indexes from DuckDB's
+//! `duckdb_tables()`, `duckdb_views()`, `duckdb_columns()`,
+//! `duckdb_constraints()` and `duckdb_indexes()` table functions.
+
+use async_trait::async_trait;
+use std::path::Path;
+use std::sync::Mutex;
+
+use super::{CatalogColumn, CatalogForeignKey, CatalogIndex, CatalogReader, CatalogTable};
+use crate::database::{DatabaseError, DatabaseResult};
+
+/// Separator used to aggregate list values into strings
+const LIST_SEPARATOR: char = '\u{1f}';
+
+/// Reads the catalog of a DuckDB database
+pub struct DuckDbCatalogReader {
+    connection: Mutex<duckdb::Connection>,
+}
+
+impl DuckDbCatalogReader {
+    /// Open a DuckDB database file read-only
+    pub fn open(db_path: impl AsRef<Path>) -> DatabaseResult<Self> {
+        let config = duckdb::Config::default()
+            .access_mode(duckdb::AccessMode::ReadOnly)
+            .map_err(|e| DatabaseError::ConnectionFailed(format!("Invalid config: {}", e)))?;
+        let connection =
+            duckdb::Connection::open_with_flags(db_path.as_ref(), config).map_err(|e| {
+                DatabaseError::ConnectionFailed(format!("Failed to open DuckDB: {}", e))
+            })?;
+        Ok(Self::from_connection(connection))
+    }
+
+    /// Read the catalog through an existing connection
+    pub fn from_connection(connection: duckdb::Connection) -> Self {
+        Self {
+            connection: Mutex::new(connection),
+        }
+    }
+
+    fn lock(&self) -> DatabaseResult<std::sync::MutexGuard<'_, duckdb::Connection>> {
+        self.connection
+            .lock()
+            .map_err(|e| DatabaseError::ConnectionFailed(format!("Lock error: {}", e)))
+    }
+
+    fn read_tables(conn: &duckdb::Connection) -> DatabaseResult<Vec<CatalogTable>> {
+        let sql = "SELECT schema_name, table_name, 'BASE TABLE', comment
+                   FROM duckdb_tables()
+                   WHERE database_name = current_database() AND NOT internal
+                   UNION ALL
+                   SELECT schema_name, view_name, 'VIEW', comment
+                   FROM duckdb_views()
+                   WHERE database_name = current_database() AND NOT internal
+                   ORDER BY 1, 2";
+        query(conn, sql, |row| {
+            Ok(CatalogTable {
+                schema: row.get(0)?,
+                name: row.get(1)?,
+                table_type: row.get(2)?,
+                comment: row.get::<_, Option<String>>(3)?.filter(|c| !c.is_empty()),
+                ..Default::default()
+            })
+        })
+    }
+
+    fn read_columns(
+        conn: &duckdb::Connection,
+    ) -> DatabaseResult<Vec<(String, String, CatalogColumn)>> {
+        let sql = "SELECT schema_name, table_name, column_name, data_type, is_nullable,
+                          column_default, comment
+                   FROM duckdb_columns()
+                   WHERE database_name = current_database() AND NOT internal
+                   ORDER BY schema_name, table_name, column_index";
+        query(conn, sql, |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                CatalogColumn {
+                    name: row.get(2)?,
+                    data_type: row.get(3)?,
+                    nullable: row.get(4)?,
+                    default: row.get(5)?,
+                    comment: row.get::<_, Option<String>>(6)?.filter(|c| !c.is_empty()),
+                    profile: None,
+                },
+            ))
+        })
+    }
+
+    fn read_constraints(
+        conn: &duckdb::Connection,
+        tables: &mut [CatalogTable],
+    ) -> DatabaseResult<()> {
+        let sql = "SELECT schema_name, table_name, constraint_type, constraint_name,
+                          array_to_string(constraint_column_names, chr(31)),
+                          referenced_table,
+                          array_to_string(referenced_column_names, chr(31))
+                   FROM duckdb_constraints()
+                   WHERE database_name = current_database()
+                     AND constraint_type IN ('PRIMARY KEY', 'FOREIGN KEY', 'UNIQUE')
+                   ORDER BY schema_name, table_name, constraint_index";
+        let rows = query(conn, sql, |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, Option<String>>(3)?,
+                row.get::<_, Option<String>>(4)?,
+                row.get::<_, Option<String>>(5)?,
+                row.get::<_, Option<String>>(6)?,
+            ))
+        })?;
+
+        for (schema, table, kind, name, columns, referenced_table, referenced_columns) in rows {
+            let Some(table) = tables
+                .iter_mut()
+                .find(|t| t.schema == schema && t.name == table)
+            else {
+                continue;
+            };
+            let columns = split_list(columns.as_deref());
+            match kind.as_str() {
+                "PRIMARY KEY" => table.primary_key = columns,
+                "UNIQUE" => table.indexes.push(CatalogIndex {
+                    name: name.unwrap_or_else(|| format!("{}_unique", columns.join("_"))),
+                    columns,
+                    unique: true,
+                }),
+                _ => {
+                    let Some(referenced_table) = referenced_table else {
+                        continue;
+                    };
+                    table.foreign_keys.push(CatalogForeignKey {
+                        name,
+                        columns,
+                        referenced_schema: None,
+                        referenced_table,
+                        referenced_columns: split_list(referenced_columns.as_deref()),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn read_indexes(conn: &duckdb::Connection, tables: &mut [CatalogTable]) -> DatabaseResult<()> {
+        let sql = "SELECT schema_name, table_name, index_name, is_unique,
+                          CAST(expressions AS VARCHAR)
+                   FROM duckdb_indexes()
+                   WHERE database_name = current_database() AND NOT is_primary
+                   ORDER BY schema_name, table_name, index_name";
+        let rows = query(conn, sql, |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, bool>(3)?,
+                row.get::<_, Option<String>>(4)?,
+            ))
+        })?;
+
+        for (schema, table, name, unique, expressions) in rows {
+            if let Some(table) = tables
+                .iter_mut()
+                .find(|t| t.schema == schema && t.name == table)
+            {
+                table.indexes.push(CatalogIndex {
+                    name,
+                    columns: parse_expressions(expressions.as_deref().unwrap_or_default()),
+                    unique,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)]
+impl CatalogReader for DuckDbCatalogReader {
+    fn engine(&self) -> &'static str {
+        "duckdb"
+    }
+
+    async fn read_catalog(&self) -> DatabaseResult<Vec<CatalogTable>> {
+        let conn = self.lock()?;
+        let mut tables = Self::read_tables(&conn)?;
+        for (schema, table, column) in Self::read_columns(&conn)? {
+            if let Some(table) = tables
+                .iter_mut()
+                .find(|t| t.schema == schema && t.name == table)
+            {
+                table.columns.push(column);
+            }
+        }
+        Self::read_constraints(&conn, &mut tables)?;
+        Self::read_indexes(&conn, &mut tables)?;
+        Ok(tables)
+    }
+
+    async fn query_counts(&self, sql: &str) -> DatabaseResult<Vec<u64>> {
+        let conn = self.lock()?;
+        let mut stmt = conn
+            .prepare(sql)
+            .map_err(|e| DatabaseError::QueryFailed(format!("Prepare failed: {}", e)))?;
+        let mut rows = stmt
+            .query([])
+            .map_err(|e| DatabaseError::QueryFailed(format!("Query failed: {}", e)))?;
+        let row = rows
+            .next()
+            .map_err(|e| DatabaseError::QueryFailed(format!("Query failed: {}", e)))?
+            .ok_or_else(|| DatabaseError::QueryFailed("Count query returned no rows".into()))?;
+
+        (0..row.as_ref().column_count())
+            .map(|i| {
+                row.get::<_, i64>(i)
+                    .map(|count| count as u64)
+                    .map_err(|e| DatabaseError::QueryFailed(format!("Invalid count: {}", e)))
+            })
+            .collect()
+    }
+}
+
+/// Run a catalog query and map every row
+fn query<T>(
+    conn: &duckdb::Connection,
+    sql: &str,
+    map: impl FnMut(&duckdb::Row<'_>) -> duckdb::Result<T>,
+) -> DatabaseResult<Vec<T>> {
+    let mut stmt = conn
+        .prepare(sql)
+        .map_err(|e| DatabaseError::QueryFailed(format!("Prepare failed: {}", e)))?;
+    stmt.query_map([], map)
+        .and_then(|rows| rows.collect())
+        .map_err(|e| DatabaseError::QueryFailed(format!("Catalog query failed: {}", e)))
+}
+
+/// Split a list aggregated with [`LIST_SEPARATOR`]
+fn split_list(value: Option<&str>) -> Vec<String> {
+    value
+        .filter(|v| !v.is_empty())
+        .map(|v| v.split(LIST_SEPARATOR).map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+/// Column names from an index expression list such as `[customer_id, "Order Date"]`
+fn parse_expressions(expressions: &str) -> Vec<String> {
+    expressions
+        .trim()
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .split(',')
+        .map(str::trim)
+        .filter(|e| !e.is_empty())
+        .map(|e| {
+            let e = e.trim_matches('\'');
+            if e.len() >= 2 && e.starts_with('"') && e.ends_with('"') {
+                e[1..e.len() - 1].replace("\"\"", "\"")
+            } else if e.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                e.to_string()
+            } else {
+                "<expression>".to_string()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::introspect::{
+        DISTINCT_COUNT_PROPERTY, IntrospectOptions, NULL_RATIO_PROPERTY, introspect,
+    };
+
+    fn reader() -> DuckDbCatalogReader {
+        let conn = duckdb::Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE customers (
+                 id INTEGER PRIMARY KEY,
+                 email VARCHAR NOT NULL UNIQUE,
+                 nickname VARCHAR
+             );
+             COMMENT ON TABLE customers IS 'Registered customers';
+             COMMENT ON COLUMN customers.email IS 'Login email';
+             CREATE TABLE orders (
+                 id INTEGER PRIMARY KEY,
+                 customer_id INTEGER NOT NULL REFERENCES customers (id),
+                 total DECIMAL(10,2) DEFAULT 0
+             );
+             CREATE INDEX orders_customer_idx ON orders (customer_id);
+             CREATE VIEW big_orders AS SELECT * FROM orders WHERE total > 100;
+             INSERT INTO customers VALUES (1, 'a@example.com', NULL), (2, 'b@example.com', 'bee');
+             INSERT INTO orders VALUES (1, 1, 10), (2, 1, 200), (3, 2, 10);",
+        )
+        .unwrap();
+        DuckDbCatalogReader::from_connection(conn)
+    }
+
+    #[tokio::test]
+    async fn test_introspect_duckdb() {
+        let model = introspect(&reader(), &IntrospectOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(model.tables.len(), 2);
+        assert_eq!(model.relationships.len(), 1);
+
+        let customers = model.tables.iter().find(|t| t.name == "customers").unwrap();
+        assert_eq!(customers.schema_name.as_deref(), Some("main"));
+        assert_eq!(
+            customers.odcl_metadata["description"]["purpose"],
+            "Registered customers"
+        );
+        let email = &customers.columns[1];
+        assert!(email.unique && !email.nullable);
+        assert_eq!(email.description, "Login email");
+
+        let orders = model.tables.iter().find(|t| t.name == "orders").unwrap();
+        assert!(orders.columns[0].primary_key);
+        assert_eq!(orders.columns[2].data_type, "DECIMAL(10,2)");
+        assert_eq!(orders.columns[1].relationships[0].to, "customers.id");
+        let indexes = orders.odcl_metadata["indexes"].as_array().unwrap();
+        assert!(
+            indexes
+                .iter()
+                .any(|i| i["name"] == "orders_customer_idx" && i["columns"][0] == "customer_id")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_introspect_duckdb_profile() {
+        let options = IntrospectOptions::default()
+            .with_views(true)
+            .with_profile(true);
+        let model = introspect(&reader(), &options).await.unwrap();
+        assert_eq!(model.tables.len(), 3);
+        assert!(model.warnings.is_empty());
+
+        let customers = model.tables.iter().find(|t| t.name == "customers").unwrap();
+        assert_eq!(customers.odcl_metadata["rowCount"], 2);
+        let nickname = &customers.columns[2].custom_properties;
+        assert_eq!(nickname[NULL_RATIO_PROPERTY], 0.5);
+        assert_eq!(nickname[DISTINCT_COUNT_PROPERTY], 1);
+
+        // Views are imported but not profiled
+        let view = model
+            .tables
+            .iter()
+            .find(|t| t.name == "big_orders")
+            .unwrap();
+        assert_eq!(view.odcl_metadata["tableType"], "VIEW");
+        assert!(view.columns[0].custom_properties.is_empty());
+    }
+}
@@ -0,0 +1,584 @@
+//! Reverse engineering of data models from live databases
+//!
+//! A [`CatalogReader`] reads the system catalogs of a database (DuckDB's
+//! `duckdb_*()` functions, SQLite's `pragma` functions, PostgreSQL's
+//! `pg_catalog`) into [`CatalogTable`]s. [`introspect`] filters them, optionally
+//! profiles their columns, and builds tables with primary keys, comments and
+//! indexes plus foreign key relationships between them.
+//!
+//! Profiling runs one `COUNT` query per table. Row counts, null counts, null
+//! ratios and distinct counts are stored in each column's custom properties.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use data_modelling_core::database::introspect::{DuckDbCatalogReader, IntrospectOptions, introspect};
+//!
+//! let reader = DuckDbCatalogReader::open("warehouse.duckdb")?;
+//! let model = introspect(&reader, &IntrospectOptions::default().with_profile(true)).await?;
+//! for table in &model.tables {
+//!     println!("{} ({} columns)", table.name, table.columns.len());
+//! }
+//! ```
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::{info, warn};
+
+use super::{DatabaseError, DatabaseResult};
+use crate::models::enums::{DatabaseType, EndpointCardinality, RelationshipType};
+use crate::models::{
+    Column, ForeignKey, ForeignKeyDetails, PropertyRelationship, Relationship, Table,
+};
+
+#[cfg(feature = "duckdb-backend")]
+pub mod duckdb;
+#[cfg(feature = "postgres-backend")]
+pub mod postgres;
+#[cfg(feature = "sqlite-introspect")]
+pub mod sqlite;
+
+#[cfg(feature = "duckdb-backend")]
+pub use self::duckdb::DuckDbCatalogReader;
+#[cfg(feature = "postgres-backend")]
+pub use self::postgres::PostgresCatalogReader;
+#[cfg(feature = "sqlite-introspect")]
+pub use self::sqlite::SqliteCatalogReader;
+
+/// Column custom property holding the table's row count when profiled
+pub const ROW_COUNT_PROPERTY: &str = "rowCount";
+/// Column custom property holding the number of null values
+pub const NULL_COUNT_PROPERTY: &str = "nullCount";
+/// Column custom property holding the fraction of null values
+pub const NULL_RATIO_PROPERTY: &str = "nullRatio";
+/// Column custom property holding the number of distinct non-null values
+pub const DISTINCT_COUNT_PROPERTY: &str = "distinctCount";
+
+/// Table read from a database catalog
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CatalogTable {
+    /// Schema name
+    pub schema: String,
+    /// Table name
+    pub name: String,
+    /// Table type (`BASE TABLE`, `VIEW`, `MATERIALIZED VIEW`, ...)
+    pub table_type: String,
+    /// Table comment
+    pub comment: Option<String>,
+    /// Columns in ordinal order
+    pub columns: Vec<CatalogColumn>,
+    /// Primary key columns in key order
+    pub primary_key: Vec<String>,
+    /// Foreign key constraints
+    pub foreign_keys: Vec<CatalogForeignKey>,
+    /// Indexes and unique constraints, excluding the primary key
+    pub indexes: Vec<CatalogIndex>,
+    /// Row count, when profiled
+    pub row_count: Option<u64>,
+}
+
+impl CatalogTable {
+    /// Whether the table is a view rather than stored data
+    pub fn is_view(&self) -> bool {
+        self.table_type.to_uppercase().contains("VIEW")
+    }
+
+    /// Schema-qualified, quoted table name for queries
+    pub fn qualified_name(&self) -> String {
+        format!(
+            "{}.{}",
+            quote_identifier(&self.schema),
+            quote_identifier(&self.name)
+        )
+    }
+}
+
+/// Column read from a database catalog
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CatalogColumn {
+    /// Column name
+    pub name: String,
+    /// Native data type as reported by the database
+    pub data_type: String,
+    /// Whether the column accepts nulls
+    pub nullable: bool,
+    /// Default value expression
+    pub default: Option<String>,
+    /// Column comment
+    pub comment: Option<String>,
+    /// Profiling statistics, when profiled
+    pub profile: Option<ColumnProfile>,
+}
+
+/// Foreign key constraint read from a database catalog
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CatalogForeignKey {
+    /// Constraint name
+    pub name: Option<String>,
+    /// Referencing columns
+    pub columns: Vec<String>,
+    /// Referenced schema (the referencing table's schema when not reported)
+    pub referenced_schema: Option<String>,
+    /// Referenced table
+    pub referenced_table: String,
+    /// Referenced columns (the referenced primary key when empty)
+    pub referenced_columns: Vec<String>,
+}
+
+/// Index or unique constraint read from a database catalog
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CatalogIndex {
+    /// Index name
+    pub name: String,
+    /// Indexed columns (expressions are reported as `<expression>`)
+    pub columns: Vec<String>,
+    /// Whether the index enforces uniqueness
+    pub unique: bool,
+}
+
+/// Profiling statistics of a column
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnProfile {
+    /// Number of null values
+    pub null_count: u64,
+    /// Number of distinct non-null values, when the type supports counting them
+    pub distinct_count: Option<u64>,
+}
+
+/// Options for reading a database catalog
+#[derive(Debug, Clone, Default)]
+pub struct IntrospectOptions {
+    /// Schemas to include (all user schemas when empty)
+    pub schemas: Vec<String>,
+    /// Tables to include, by name or `schema.name` (all tables when empty)
+    pub tables: Vec<String>,
+    /// Whether to include views
+    pub include_views: bool,
+    /// Whether to collect row counts, null ratios and distinct counts
+    pub profile: bool,
+}
+
+impl IntrospectOptions {
+    /// Restrict the import to these schemas
+    pub fn with_schemas(mut self, schemas: Vec<String>) -> Self {
+        self.schemas = schemas;
+        self
+    }
+
+    /// Restrict the import to these tables (`name` or `schema.name`)
+    pub fn with_tables(mut self, tables: Vec<String>) -> Self {
+        self.tables = tables;
+        self
+    }
+
+    /// Include views alongside tables
+    pub fn with_views(mut self, include_views: bool) -> Self {
+        self.include_views = include_views;
+        self
+    }
+
+    /// Collect profiling statistics for every table
+    pub fn with_profile(mut self, profile: bool) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Whether a catalog table passes the schema, table and view filters
+    fn includes(&self, table: &CatalogTable) -> bool {
+        (self.include_views || !table.is_view())
+            && (self.schemas.is_empty() || self.schemas.contains(&table.schema))
+            && (self.tables.is_empty()
+                || self
+                    .tables
+                    .iter()
+                    .any(|t| *t == table.name || *t == format!("{}.{}", table.schema, table.name)))
+    }
+}
+
+/// Tables and relationships reverse-engineered from a database
+#[derive(Debug, Clone, Default)]
+pub struct IntrospectedModel {
+    /// Tables with columns, keys, comments and indexes
+    pub tables: Vec<Table>,
+    /// Foreign key relationships between the tables
+    pub relationships: Vec<Relationship>,
+    /// Problems that did not stop the import (e.g. tables that could not be profiled)
+    pub warnings: Vec<String>,
+}
+
+/// Reads table metadata from a database's system catalogs
+#[async_trait(?Send)]
+pub trait CatalogReader {
+    /// Database engine name (`duckdb`, `sqlite`, `postgres`)
+    fn engine(&self) -> &'static str;
+
+    /// Database type recorded on imported tables, where one exists
+    fn database_type(&self) -> Option<DatabaseType> {
+        None
+    }
+
+    /// Read all user tables and views with their columns, keys and indexes
+    async fn read_catalog(&self) -> DatabaseResult<Vec<CatalogTable>>;
+
+    /// Run a query returning a single row of counts
+    async fn query_counts(&self, sql: &str) -> DatabaseResult<Vec<u64>>;
+}
+
+/// Reverse-engineer a model from a database
+pub async fn introspect(
+    reader: &dyn CatalogReader,
+    options: &IntrospectOptions,
+) -> DatabaseResult<IntrospectedModel> {
+    let mut catalog: Vec<CatalogTable> = reader
+        .read_catalog()
+        .await?
+        .into_iter()
+        .filter(|t| options.includes(t))
+        .collect();
+
+    let mut warnings = Vec::new();
+    if options.profile {
+        for table in catalog.iter_mut().filter(|t| !t.is_view()) {
+            if let Err(e) = profile_table(reader, table).await {
+                warn!(table = %table.name, error = %e, "Failed to profile table");
+                warnings.push(format!("Failed to profile {}: {}", table.name, e));
+            }
+        }
+    }
+
+    let mut model = build_model(reader.engine(), reader.database_type(), &catalog);
+    model.warnings.splice(0..0, warnings);
+
+    info!(
+        engine = reader.engine(),
+        tables = model.tables.len(),
+        relationships = model.relationships.len(),
+        "Introspected database"
+    );
+    Ok(model)
+}
+
+/// Collect row, null and distinct counts for a table
+///
+/// Distinct counts are dropped for the whole table if the database cannot
+/// count distinct values of one of its column types.
+async fn profile_table(reader: &dyn CatalogReader, table: &mut CatalogTable) -> DatabaseResult<()> {
+    let counts = match reader.query_counts(&profile_sql(table, true)).await {
+        Ok(counts) => counts,
+        Err(_) => reader.query_counts(&profile_sql(table, false)).await?,
+    };
+    let with_distinct = counts.len() == 1 + table.columns.len() * 2;
+    if counts.len() != 1 + table.columns.len() && !with_distinct {
+        return Err(DatabaseError::QueryFailed(format!(
+            "Unexpected profile result with {} values",
+            counts.len()
+        )));
+    }
+
+    let row_count = counts[0];
+    table.row_count = Some(row_count);
+    let step = if with_distinct { 2 } else { 1 };
+    for (i, column) in table.columns.iter_mut().enumerate() {
+        let non_null = counts[1 + i * step];
+        column.profile = Some(ColumnProfile {
+            null_count: row_count.saturating_sub(non_null),
+            distinct_count: with_distinct.then(|| counts[2 + i * step]),
+        });
+    }
+    Ok(())
+}
+
+/// Query counting rows plus non-null (and distinct) values of every column
+fn profile_sql(table: &CatalogTable, distinct: bool) -> String {
+    let mut expressions = vec!["COUNT(*)".to_string()];
+    for column in &table.columns {
+        let name = quote_identifier(&column.name);
+        expressions.push(format!("COUNT({})", name));
+        if distinct {
+            expressions.push(format!("COUNT(DISTINCT {})", name));
+        }
+    }
+    format!(
+        "SELECT {} FROM {}",
+        expressions.join(", "),
+        table.qualified_name()
+    )
+}
+
+/// Quote an identifier with double quotes
+pub(crate) fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Build tables and relationships from catalog tables
+pub fn build_model(
+    engine: &str,
+    database_type: Option<DatabaseType>,
+    catalog: &[CatalogTable],
+) -> IntrospectedModel {
+    let mut tables: Vec<Table> = catalog
+        .iter()
+        .map(|t| catalog_table_to_table(engine, database_type, t))
+        .collect();
+
+    let mut relationships = Vec::new();
+    let mut warnings = Vec::new();
+    for (source_index, source) in catalog.iter().enumerate() {
+        for fk in &source.foreign_keys {
+            let target_schema = fk.referenced_schema.as_deref().unwrap_or(&source.schema);
+            let Some(target_index) = catalog
+                .iter()
+                .position(|t| t.schema == target_schema && t.name == fk.referenced_table)
+            else {
+                warnings.push(format!(
+                    "Foreign key {} on {} references {}.{}, which was not imported",
+                    fk.name.as_deref().unwrap_or("(unnamed)"),
+                    source.name,
+                    target_schema,
+                    fk.referenced_table
+                ));
+                continue;
+            };
+
+            let target = &catalog[target_index];
+            let target_columns = if fk.referenced_columns.is_empty() {
+                target.primary_key.clone()
+            } else {
+                fk.referenced_columns.clone()
+            };
+            let (Some(source_column), Some(target_column)) =
+                (fk.columns.first(), target_columns.first())
+            else {
+                continue;
+            };
+
+            let target_id = tables[target_index].id;
+            let source_table = &mut tables[source_index];
+            let mut source_optional = false;
+            for (column_name, target_column) in fk.columns.iter().zip(&target_columns) {
+                if let Some(column) = source_table
+                    .columns
+                    .iter_mut()
+                    .find(|c| c.name == *column_name)
+                {
+                    source_optional |= column.nullable;
+                    column.foreign_key = Some(ForeignKey {
+                        table_id: target_id.to_string(),
+                        column_name: target_column.clone(),
+                    });
+                    column.relationships.push(PropertyRelationship {
+                        relationship_type: "foreignKey".to_string(),
+                        to: format!("{}.{}", target.name, target_column),
+                    });
+                }
+            }
+
+            let mut relationship = Relationship::new(source_table.id, target_id);
+            relationship.relationship_type = Some(RelationshipType::ForeignKey);
+            relationship.foreign_key_details = Some(ForeignKeyDetails {
+                source_column: source_column.clone(),
+                target_column: target_column.clone(),
+            });
+            relationship.source_optional = Some(source_optional);
+            relationship.target_optional = Some(false);
+            relationship.source_cardinality = Some(EndpointCardinality::ZeroOrMany);
+            relationship.target_cardinality = Some(if source_optional {
+                EndpointCardinality::ZeroOrOne
+            } else {
+                EndpointCardinality::ExactlyOne
+            });
+            let mut notes = match fk.name {
+                Some(ref name) => format!("Foreign key {}", name),
+                None => "Foreign key".to_string(),
+            };
+            if fk.columns.len() > 1 {
+                notes.push_str(&format!(
+                    " ({}) -> ({})",
+                    fk.columns.join(", "),
+                    target_columns.join(", ")
+                ));
+            }
+            relationship.notes = Some(notes);
+            relationships.push(relationship);
+        }
+    }
+
+    IntrospectedModel {
+        tables,
+        relationships,
+        warnings,
+    }
+}
+
+/// Convert a catalog table into a table model
+fn catalog_table_to_table(
+    engine: &str,
+    database_type: Option<DatabaseType>,
+    catalog_table: &CatalogTable,
+) -> Table {
+    let columns = catalog_table
+        .columns
+        .iter()
+        .map(|c| {
+            let key_position = catalog_table.primary_key.iter().position(|k| *k == c.name);
+            let unique = catalog_table
+                .indexes
+                .iter()
+                .any(|i| i.unique && i.columns.len() == 1 && i.columns[0] == c.name)
+                || (catalog_table.primary_key.len() == 1 && key_position.is_some());
+
+            let mut column = Column {
+                name: c.name.clone(),
+                data_type: c.data_type.to_uppercase(),
+                physical_type: Some(c.data_type.clone()),
+                nullable: c.nullable && key_position.is_none(),
+                primary_key: key_position.is_some(),
+                primary_key_position: key_position.map(|p| p as i32 + 1),
+                unique,
+                description: c.comment.clone().unwrap_or_default(),
+                default_value: c.default.as_ref().map(|d| json!(d)),
+                ..Default::default()
+            };
+            if let (Some(profile), Some(row_count)) = (c.profile, catalog_table.row_count) {
+                let properties = &mut column.custom_properties;
+                properties.insert(ROW_COUNT_PROPERTY.to_string(), json!(row_count));
+                properties.insert(NULL_COUNT_PROPERTY.to_string(), json!(profile.null_count));
+                let ratio = if row_count == 0 {
+                    0.0
+                } else {
+                    profile.null_count as f64 / row_count as f64
+                };
+                properties.insert(NULL_RATIO_PROPERTY.to_string(), json!(ratio));
+                if let Some(distinct) = profile.distinct_count {
+                    properties.insert(DISTINCT_COUNT_PROPERTY.to_string(), json!(distinct));
+                }
+            }
+            column
+        })
+        .collect();
+
+    let mut table = Table::new(catalog_table.name.clone(), columns);
+    table.schema_name = Some(catalog_table.schema.clone());
+    table.database_type = database_type;
+
+    let metadata = &mut table.odcl_metadata;
+    metadata.insert("databaseEngine".to_string(), json!(engine));
+    metadata.insert("tableType".to_string(), json!(catalog_table.table_type));
+    if let Some(ref comment) = catalog_table.comment {
+        metadata.insert("description".to_string(), json!({ "purpose": comment }));
+    }
+    if !catalog_table.indexes.is_empty() {
+        metadata.insert("indexes".to_string(), json!(catalog_table.indexes));
+    }
+    if let Some(row_count) = catalog_table.row_count {
+        metadata.insert(ROW_COUNT_PROPERTY.to_string(), json!(row_count));
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn catalog() -> Vec<CatalogTable> {
+        vec![
+            CatalogTable {
+                schema: "main".to_string(),
+                name: "customers".to_string(),
+                table_type: "BASE TABLE".to_string(),
+                comment: Some("Registered customers".to_string()),
+                columns: vec![CatalogColumn {
+                    name: "id".to_string(),
+                    data_type: "INTEGER".to_string(),
+                    ..Default::default()
+                }],
+                primary_key: vec!["id".to_string()],
+                ..Default::default()
+            },
+            CatalogTable {
+                schema: "main".to_string(),
+                name: "orders".to_string(),
+                table_type: "BASE TABLE".to_string(),
+                columns: vec![
+                    CatalogColumn {
+                        name: "id".to_string(),
+                        data_type: "INTEGER".to_string(),
+                        ..Default::default()
+                    },
+                    CatalogColumn {
+                        name: "customer_id".to_string(),
+                        data_type: "integer".to_string(),
+                        nullable: true,
+                        ..Default::default()
+                    },
+                ],
+                primary_key: vec!["id".to_string()],
+                foreign_keys: vec![CatalogForeignKey {
+                    name: Some("orders_customer_fk".to_string()),
+                    columns: vec!["customer_id".to_string()],
+                    referenced_table: "customers".to_string(),
+                    ..Default::default()
+                }],
+                indexes: vec![CatalogIndex {
+                    name: "orders_customer_idx".to_string(),
+                    columns: vec!["customer_id".to_string()],
+                    unique: false,
+                }],
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn test_build_model_resolves_foreign_keys() {
+        let model = build_model("duckdb", None, &catalog());
+        assert!(model.warnings.is_empty());
+        assert_eq!(model.relationships.len(), 1);
+
+        let customers = &model.tables[0];
+        let orders = &model.tables[1];
+        let relationship = &model.relationships[0];
+        assert_eq!(relationship.source_table_id, orders.id);
+        assert_eq!(relationship.target_table_id, customers.id);
+        let details = relationship.foreign_key_details.as_ref().unwrap();
+        assert_eq!(details.target_column, "id");
+        assert_eq!(
+            relationship.target_cardinality,
+            Some(EndpointCardinality::ZeroOrOne)
+        );
+
+        let customer_id = &orders.columns[1];
+        assert_eq!(customer_id.data_type, "INTEGER");
+        assert_eq!(customer_id.relationships[0].to, "customers.id");
+        assert!(customers.columns[0].primary_key && customers.columns[0].unique);
+        assert_eq!(
+            orders.odcl_metadata["indexes"][0]["name"],
+            "orders_customer_idx"
+        );
+        assert_eq!(
+            customers.odcl_metadata["description"]["purpose"],
+            "Registered customers"
+        );
+    }
+
+    #[test]
+    fn test_options_filter_tables() {
+        let options = IntrospectOptions::default().with_tables(vec!["main.orders".to_string()]);
+        let included: Vec<_> = catalog()
+            .into_iter()
+            .filter(|t| options.includes(t))
+            .collect();
+        assert_eq!(included.len(), 1);
+
+        // References to tables outside the import are reported
+        let model = build_model("duckdb", None, &included);
+        assert!(model.relationships.is_empty());
+        assert_eq!(model.warnings.len(), 1);
+    }
+}
@@ -0,0 +1,256 @@
+//! PostgreSQL catalog reader
+//!
+//! Reads tables, views, columns, constraints and indexes from `pg_catalog`,
+//! including table and column comments. System schemas and partitions of
+//! partitioned tables are skipped.
+
+use async_trait::async_trait;
+
+use super::{CatalogColumn, CatalogForeignKey, CatalogIndex, CatalogReader, CatalogTable};
+use crate::database::{DatabaseError, DatabaseResult};
+use crate::models::enums::DatabaseType;
+
+const TABLES_SQL: &str = "
+    SELECT c.oid::bigint, n.nspname, c.relname,
+           CASE c.relkind
+               WHEN 'v' THEN 'VIEW'
+               WHEN 'm' THEN 'MATERIALIZED VIEW'
+               WHEN 'f' THEN 'FOREIGN TABLE'
+               ELSE 'BASE TABLE'
+           END,
+           obj_description(c.oid, 'pg_class')
+    FROM pg_class c
+    JOIN pg_namespace n ON n.oid = c.relnamespace
+    WHERE c.relkind IN ('r', 'p', 'v', 'm', 'f')
+      AND NOT c.relispartition
+      AND n.nspname NOT IN ('pg_catalog', 'information_schema')
+      AND n.nspname NOT LIKE 'pg_toast%'
+      AND n.nspname NOT LIKE 'pg_temp%'
+    ORDER BY n.nspname, c.relname";
+
+const COLUMNS_SQL: &str = "
+    SELECT a.attname, format_type(a.atttypid, a.atttypmod), NOT a.attnotnull,
+           pg_get_expr(d.adbin, d.adrelid), col_description(a.attrelid, a.attnum)
+    FROM pg_attribute a
+    LEFT JOIN pg_attrdef d ON d.adrelid = a.attrelid AND d.adnum = a.attnum
+    WHERE a.attrelid = $1::bigint::oid AND a.attnum > 0 AND NOT a.attisdropped
+    ORDER BY a.attnum";
+
+const CONSTRAINTS_SQL: &str = "
+    SELECT con.contype::text, con.conname,
+           ARRAY(SELECT a.attname::text
+                 FROM unnest(con.conkey) WITH ORDINALITY k(attnum, ord)
+                 JOIN pg_attribute a ON a.attrelid = con.conrelid AND a.attnum = k.attnum
+                 ORDER BY k.ord),
+           rn.nspname, rc.relname,
+           ARRAY(SELECT a.attname::text
+                 FROM unnest(con.confkey) WITH ORDINALITY k(attnum, ord)
+                 JOIN pg_attribute a ON a.attrelid = con.confrelid AND a.attnum = k.attnum
+                 ORDER BY k.ord)
+    FROM pg_constraint con
+    LEFT JOIN pg_class rc ON rc.oid = con.confrelid
+    LEFT JOIN pg_namespace rn ON rn.oid = rc.relnamespace
+    WHERE con.conrelid = $1::bigint::oid AND con.contype IN ('p', 'f', 'u')
+    ORDER BY con.contype, con.conname";
+
+const INDEXES_SQL: &str = "
+    SELECT ic.relname, i.indisunique,
+           ARRAY(SELECT COALESCE(a.attname::text, '<expression>')
+                 FROM unnest(i.indkey::int2[]) WITH ORDINALITY k(attnum, ord)
+                 LEFT JOIN pg_attribute a ON a.attrelid = i.indrelid AND a.attnum = k.attnum
+                 WHERE k.ord <= i.indnkeyatts
+                 ORDER BY k.ord)
+    FROM pg_index i
+    JOIN pg_class ic ON ic.oid = i.indexrelid
+    WHERE i.indrelid = $1::bigint::oid AND NOT i.indisprimary
+    ORDER BY ic.relname";
+
+/// Reads the catalog of a PostgreSQL database
+pub struct PostgresCatalogReader {
+    client: tokio_postgres::Client,
+}
+
+impl PostgresCatalogReader {
+    /// Connect to a PostgreSQL database
+    pub async fn connect(connection_string: &str) -> DatabaseResult<Self> {
+        let (client, connection) =
+            tokio_postgres::connect(connection_string, tokio_postgres::NoTls)
+                .await
+                .map_err(|e| {
+                    DatabaseError::ConnectionFailed(format!(
+                        "Failed to connect to PostgreSQL: {}",
+                        e
+                    ))
+                })?;
+
+        // Spawn connection handler
+        tokio::spawn(async move {
+            if let Err(e) = connection.await {
+                eprintln!("PostgreSQL connection error: {}", e);
+            }
+        });
+
+        Ok(Self::from_client(client))
+    }
+
+    /// Read the catalog through an existing client
+    pub fn from_client(client: tokio_postgres::Client) -> Self {
+        Self { client }
+    }
+
+    async fn query(&self, sql: &str, oid: i64) -> DatabaseResult<Vec<tokio_postgres::Row>> {
+        self.client
+            .query(sql, &[&oid])
+            .await
+            .map_err(|e| DatabaseError::QueryFailed(format!("Catalog query failed: {}", e)))
+    }
+
+    async fn read_table(&self, oid: i64, table: &mut CatalogTable) -> DatabaseResult<()> {
+        for row in self.query(COLUMNS_SQL, oid).await? {
+            table.columns.push(CatalogColumn {
+                name: row.get(0),
+                data_type: row.get(1),
+                nullable: row.get(2),
+                default: row.get(3),
+                comment: row.get(4),
+                profile: None,
+            });
+        }
+
+        for row in self.query(CONSTRAINTS_SQL, oid).await? {
+            let kind: String = row.get(0);
+            let name: String = row.get(1);
+            let columns: Vec<String> = row.get(2);
+            match kind.as_str() {
+                "p" => table.primary_key = columns,
+                "u" => table.indexes.push(CatalogIndex {
+                    name,
+                    columns,
+                    unique: true,
+                }),
+                _ => table.foreign_keys.push(CatalogForeignKey {
+                    name: Some(name),
+                    columns,
+                    referenced_schema: row.get(3),
+                    referenced_table: row.get::<_, Option<String>>(4).unwrap_or_default(),
+                    referenced_columns: row.get(5),
+                }),
+            }
+        }
+
+        for row in self.query(INDEXES_SQL, oid).await? {
+            let name: String = row.get(0);
+            // Unique constraints are backed by an index of the same name
+            if table.indexes.iter().any(|i| i.name == name) {
+                continue;
+            }
+            table.indexes.push(CatalogIndex {
+                name,
+                unique: row.get(1),
+                columns: row.get(2),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)]
+impl CatalogReader for PostgresCatalogReader {
+    fn engine(&self) -> &'static str {
+        "postgres"
+    }
+
+    fn database_type(&self) -> Option<DatabaseType> {
+        Some(DatabaseType::Postgres)
+    }
+
+    async fn read_catalog(&self) -> DatabaseResult<Vec<CatalogTable>> {
+        let rows = self
+            .client
+            .query(TABLES_SQL, &[])
+            .await
+            .map_err(|e| DatabaseError::QueryFailed(format!("Catalog query failed: {}", e)))?;
+
+        let mut tables = Vec::with_capacity(rows.len());
+        for row in rows {
+            let oid: i64 = row.get(0);
+            let mut table = CatalogTable {
+                schema: row.get(1),
+                name: row.get(2),
+                table_type: row.get(3),
+                comment: row.get(4),
+                ..Default::default()
+            };
+            self.read_table(oid, &mut table).await?;
+            tables.push(table);
+        }
+        Ok(tables)
+    }
+
+    async fn query_counts(&self, sql: &str) -> DatabaseResult<Vec<u64>> {
+        let row = self
+            .client
+            .query_one(sql, &[])
+            .await
+            .map_err(|e| DatabaseError::QueryFailed(format!("Query failed: {}", e)))?;
+        (0..row.len())
+            .map(|i| {
+                row.try_get::<_, i64>(i)
+                    .map(|count| count as u64)
+                    .map_err(|e| DatabaseError::QueryFailed(format!("Invalid count: {}", e)))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::config::ENV_POSTGRES_URL;
+    use crate::database::introspect::{IntrospectOptions, introspect};
+
+    #[tokio::test]
+    async fn test_introspect_postgres() {
+        // Requires a local PostgreSQL database
+        let Ok(url) = std::env::var(ENV_POSTGRES_URL) else {
+            return;
+        };
+        let reader = PostgresCatalogReader::connect(&url).await.unwrap();
+        let schema = format!("introspect_{}", uuid::Uuid::new_v4().simple());
+        reader
+            .client
+            .batch_execute(&format!(
+                "CREATE SCHEMA {schema};
+                 CREATE TABLE {schema}.customers (id serial PRIMARY KEY, email text UNIQUE);
+                 COMMENT ON TABLE {schema}.customers IS 'Registered customers';
+                 CREATE TABLE {schema}.orders (
+                     id bigint PRIMARY KEY,
+                     customer_id integer NOT NULL REFERENCES {schema}.customers (id),
+                     total numeric(10,2)
+                 );
+                 INSERT INTO {schema}.customers (email) VALUES ('a@example.com');"
+            ))
+            .await
+            .unwrap();
+
+        let options = IntrospectOptions::default()
+            .with_schemas(vec![schema.clone()])
+            .with_profile(true);
+        let result = introspect(&reader, &options).await;
+        reader
+            .client
+            .batch_execute(&format!("DROP SCHEMA {schema} CASCADE"))
+            .await
+            .unwrap();
+
+        let model = result.unwrap();
+        assert_eq!(model.tables.len(), 2);
+        assert_eq!(model.relationships.len(), 1);
+        let customers = model.tables.iter().find(|t| t.name == "customers").unwrap();
+        assert_eq!(customers.database_type, Some(DatabaseType::Postgres));
+        assert!(customers.columns[1].unique);
+        assert_eq!(customers.odcl_metadata["rowCount"], 1);
+        let orders = model.tables.iter().find(|t| t.name == "orders").unwrap();
+        assert_eq!(orders.columns[2].data_type, "NUMERIC(10,2)");
+    }
+}
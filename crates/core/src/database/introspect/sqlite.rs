@@ -0,0 +1,275 @@
+//! SQLite catalog reader
+//!
+//! Reads tables and views from `sqlite_master` and their columns, foreign keys
+//! and indexes from the `pragma_table_xinfo`, `pragma_foreign_key_list`,
+//! `pragma_index_list` and `pragma_index_info` table-valued functions.
+//!
+//! SQLite has no comments or schemas; tables are reported in the `main` schema.
+
+use async_trait::async_trait;
+use std::path::Path;
+use std::sync::Mutex;
+
+use super::{CatalogColumn, CatalogForeignKey, CatalogIndex, CatalogReader, CatalogTable};
+use crate::database::{DatabaseError, DatabaseResult};
+
+/// Reads the catalog of a SQLite database
+pub struct SqliteCatalogReader {
+    connection: Mutex<rusqlite::Connection>,
+}
+
+impl SqliteCatalogReader {
+    /// Open a SQLite database file read-only
+    pub fn open(db_path: impl AsRef<Path>) -> DatabaseResult<Self> {
+        let connection = rusqlite::Connection::open_with_flags(
+            db_path.as_ref(),
+            rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )
+        .map_err(|e| DatabaseError::ConnectionFailed(format!("Failed to open SQLite: {}", e)))?;
+        Ok(Self::from_connection(connection))
+    }
+
+    /// Read the catalog through an existing connection
+    pub fn from_connection(connection: rusqlite::Connection) -> Self {
+        Self {
+            connection: Mutex::new(connection),
+        }
+    }
+
+    fn lock(&self) -> DatabaseResult<std::sync::MutexGuard<'_, rusqlite::Connection>> {
+        self.connection
+            .lock()
+            .map_err(|e| DatabaseError::ConnectionFailed(format!("Lock error: {}", e)))
+    }
+
+    fn read_table(conn: &rusqlite::Connection, table: &mut CatalogTable) -> DatabaseResult<()> {
+        // Columns; pk holds the 1-based primary key position
+        let columns = query(
+            conn,
+            "SELECT name, type, \"notnull\", dflt_value, pk
+             FROM pragma_table_xinfo(?1) WHERE hidden = 0 ORDER BY cid",
+            &table.name,
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, bool>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    row.get::<_, i64>(4)?,
+                ))
+            },
+        )?;
+        let mut primary_key: Vec<(i64, String)> = Vec::new();
+        for (name, data_type, not_null, default, pk) in columns {
+            if pk > 0 {
+                primary_key.push((pk, name.clone()));
+            }
+            table.columns.push(CatalogColumn {
+                name,
+                // Columns declared without a type have BLOB affinity
+                data_type: if data_type.is_empty() {
+                    "BLOB".to_string()
+                } else {
+                    data_type
+                },
+                nullable: !not_null && pk == 0,
+                default,
+                comment: None,
+                profile: None,
+            });
+        }
+        primary_key.sort();
+        table.primary_key = primary_key.into_iter().map(|(_, name)| name).collect();
+
+        // Foreign keys; rows of one constraint share an id and are ordered by seq
+        let references = query(
+            conn,
+            "SELECT id, \"table\", \"from\", \"to\"
+             FROM pragma_foreign_key_list(?1) ORDER BY id, seq",
+            &table.name,
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                ))
+            },
+        )?;
+        let mut current_id = None;
+        for (id, referenced_table, from, to) in references {
+            if current_id != Some(id) {
+                current_id = Some(id);
+                table.foreign_keys.push(CatalogForeignKey {
+                    name: None,
+                    columns: Vec::new(),
+                    referenced_schema: None,
+                    referenced_table,
+                    referenced_columns: Vec::new(),
+                });
+            }
+            if let Some(fk) = table.foreign_keys.last_mut() {
+                fk.columns.push(from);
+                // A NULL target references the primary key of the parent table
+                if let Some(to) = to {
+                    fk.referenced_columns.push(to);
+                }
+            }
+        }
+
+        // Indexes, excluding the automatic primary key index
+        let indexes = query(
+            conn,
+            "SELECT name, \"unique\" FROM pragma_index_list(?1)
+             WHERE origin <> 'pk' ORDER BY name",
+            &table.name,
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, bool>(1)?)),
+        )?;
+        for (name, unique) in indexes {
+            let columns = query(
+                conn,
+                "SELECT name FROM pragma_index_info(?1) ORDER BY seqno",
+                &name,
+                |row| row.get::<_, Option<String>>(0),
+            )?
+            .into_iter()
+            .map(|c| c.unwrap_or_else(|| "<expression>".to_string()))
+            .collect();
+            table.indexes.push(CatalogIndex {
+                name,
+                columns,
+                unique,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)]
+impl CatalogReader for SqliteCatalogReader {
+    fn engine(&self) -> &'static str {
+        "sqlite"
+    }
+
+    async fn read_catalog(&self) -> DatabaseResult<Vec<CatalogTable>> {
+        let conn = self.lock()?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT name, type FROM sqlite_master
+                 WHERE type IN ('table', 'view') AND name NOT LIKE 'sqlite_%'
+                 ORDER BY name",
+            )
+            .map_err(|e| DatabaseError::QueryFailed(format!("Prepare failed: {}", e)))?;
+        let mut tables: Vec<CatalogTable> = stmt
+            .query_map([], |row| {
+                let table_type: String = row.get(1)?;
+                Ok(CatalogTable {
+                    schema: "main".to_string(),
+                    name: row.get(0)?,
+                    table_type: if table_type == "view" {
+                        "VIEW".to_string()
+                    } else {
+                        "BASE TABLE".to_string()
+                    },
+                    ..Default::default()
+                })
+            })
+            .and_then(|rows| rows.collect())
+            .map_err(|e| DatabaseError::QueryFailed(format!("Catalog query failed: {}", e)))?;
+
+        for table in &mut tables {
+            Self::read_table(&conn, table)?;
+        }
+        Ok(tables)
+    }
+
+    async fn query_counts(&self, sql: &str) -> DatabaseResult<Vec<u64>> {
+        let conn = self.lock()?;
+        let mut stmt = conn
+            .prepare(sql)
+            .map_err(|e| DatabaseError::QueryFailed(format!("Prepare failed: {}", e)))?;
+        let column_count = stmt.column_count();
+        stmt.query_row([], |row| {
+            (0..column_count)
+                .map(|i| row.get::<_, i64>(i).map(|count| count as u64))
+                .collect()
+        })
+        .map_err(|e| DatabaseError::QueryFailed(format!("Query failed: {}", e)))
+    }
+}
+
+/// Run a pragma query with one argument and map every row
+fn query<T>(
+    conn: &rusqlite::Connection,
+    sql: &str,
+    argument: &str,
+    map: impl FnMut(&rusqlite::Row<'_>) -> rusqlite::Result<T>,
+) -> DatabaseResult<Vec<T>> {
+    let mut stmt = conn
+        .prepare(sql)
+        .map_err(|e| DatabaseError::QueryFailed(format!("Prepare failed: {}", e)))?;
+    stmt.query_map([argument], map)
+        .and_then(|rows| rows.collect())
+        .map_err(|e| DatabaseError::QueryFailed(format!("Catalog query failed: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::introspect::{IntrospectOptions, NULL_COUNT_PROPERTY, introspect};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_introspect_sqlite() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("shop.db");
+        let conn = rusqlite::Connection::open(&path).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE customers (id INTEGER PRIMARY KEY, email TEXT NOT NULL UNIQUE, notes);
+             CREATE TABLE order_lines (
+                 order_id INTEGER NOT NULL,
+                 line INTEGER NOT NULL,
+                 customer_id INTEGER REFERENCES customers,
+                 quantity INTEGER DEFAULT 1,
+                 PRIMARY KEY (order_id, line)
+             );
+             CREATE INDEX order_lines_customer ON order_lines (customer_id);
+             INSERT INTO customers VALUES (1, 'a@example.com', NULL);
+             INSERT INTO order_lines VALUES (1, 1, 1, 2), (1, 2, NULL, 1);",
+        )
+        .unwrap();
+        drop(conn);
+
+        let reader = SqliteCatalogReader::open(&path).unwrap();
+        let options = IntrospectOptions::default().with_profile(true);
+        let model = introspect(&reader, &options).await.unwrap();
+        assert_eq!(model.tables.len(), 2);
+        assert!(model.warnings.is_empty());
+
+        let customers = model.tables.iter().find(|t| t.name == "customers").unwrap();
+        assert_eq!(customers.columns[2].data_type, "BLOB");
+        assert!(customers.columns[1].unique);
+
+        let lines = model
+            .tables
+            .iter()
+            .find(|t| t.name == "order_lines")
+            .unwrap();
+        assert_eq!(lines.columns[1].primary_key_position, Some(2));
+        assert!(!lines.columns[0].nullable);
+        assert_eq!(lines.columns[3].default_value, Some(serde_json::json!("1")));
+        assert_eq!(lines.columns[2].custom_properties[NULL_COUNT_PROPERTY], 1);
+
+        // The foreign key without target columns resolves to the primary key
+        let relationship = &model.relationships[0];
+        assert_eq!(relationship.target_table_id, customers.id);
+        assert_eq!(
+            relationship
+                .foreign_key_details
+                .as_ref()
+                .unwrap()
+                .target_column,
+            "id"
+        );
+    }
+}
@@ -20,6 +20,7 @@ pub mod duckdb;
 pub mod postgres;
 
 pub mod config;
+pub mod introspect;
 pub mod schema;
 pub mod sync;
 
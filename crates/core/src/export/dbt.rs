@@ -0,0 +1,763 @@
+//! dbt exporter for generating project fragments from data contracts.
+//!
+//! Produces:
+//! - `sources.yml` describing raw tables as a dbt source
+//! - `schema.yml` documenting modelled tables
+//! - generic column tests derived from `primary_key`, `nullable`, `unique`,
+//!   `enum_values` and ODCS quality rules
+//! - singular SQL tests for quality rules that carry a `query`
+//!
+//! Range and exclusion checks use the `dbt_utils` package, which must be listed
+//! in the project's `packages.yml`.
+
+use super::{ExportError, ExportResult};
+use crate::models::odcs::QualityRule;
+use crate::models::{Column, Table};
+use serde::Serialize;
+use serde_yaml::{Mapping, Value};
+use std::collections::BTreeMap;
+
+/// dbt properties file version.
+const DBT_PROPERTIES_VERSION: u8 = 2;
+
+/// A generated dbt project fragment.
+///
+/// File paths returned by [`DbtProject::files`] are relative to the dbt
+/// project root.
+#[derive(Debug, Clone, Default)]
+pub struct DbtProject {
+    /// Contents of `models/sources.yml`, if source tables were exported
+    pub sources: Option<String>,
+    /// Contents of `models/schema.yml`, if model tables were exported
+    pub schema: Option<String>,
+    /// SQL models keyed by model name (without the `.sql` extension)
+    pub models: BTreeMap<String, String>,
+    /// Singular tests keyed by test name (without the `.sql` extension)
+    pub tests: BTreeMap<String, String>,
+    /// Quality rules that could not be expressed as dbt tests
+    pub warnings: Vec<String>,
+}
+
+impl DbtProject {
+    /// Add a SQL model, e.g. a staging model generated from a schema mapping.
+    pub fn add_model(&mut self, name: impl Into<String>, sql: impl Into<String>) {
+        self.models.insert(name.into(), sql.into());
+    }
+
+    /// Relative paths and contents of every file in the fragment.
+    pub fn files(&self) -> Vec<(String, &str)> {
+        let mut files = Vec::new();
+        if let Some(sources) = &self.sources {
+            files.push(("models/sources.yml".to_string(), sources.as_str()));
+        }
+        if let Some(schema) = &self.schema {
+            files.push(("models/schema.yml".to_string(), schema.as_str()));
+        }
+        for (name, sql) in &self.models {
+            files.push((format!("models/{}.sql", name), sql.as_str()));
+        }
+        for (name, sql) in &self.tests {
+            files.push((format!("tests/{}.sql", name), sql.as_str()));
+        }
+        files
+    }
+}
+
+#[derive(Serialize)]
+struct SourcesFile {
+    version: u8,
+    sources: Vec<SourceDef>,
+}
+
+#[derive(Serialize)]
+struct SourceDef {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    database: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    schema: Option<String>,
+    tables: Vec<ModelDef>,
+}
+
+#[derive(Serialize)]
+struct SchemaFile {
+    version: u8,
+    models: Vec<ModelDef>,
+}
+
+#[derive(Serialize)]
+struct ModelDef {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    data_tests: Vec<Value>,
+    columns: Vec<ColumnDef>,
+}
+
+#[derive(Serialize)]
+struct ColumnDef {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data_type: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    data_tests: Vec<Value>,
+}
+
+/// Catalog and schema of a source table.
+type Location = (Option<String>, Option<String>);
+
+/// Group source tables by catalog and schema, in order of first appearance.
+///
+/// A single group is named `source_name`; otherwise each group name is
+/// suffixed with its catalog and schema.
+fn source_groups<'a>(
+    tables: &'a [Table],
+    source_name: &str,
+) -> Vec<(String, Location, Vec<&'a Table>)> {
+    let mut groups: Vec<(Location, Vec<&Table>)> = Vec::new();
+    for table in tables {
+        let location = (table.catalog_name.clone(), table.schema_name.clone());
+        match groups.iter_mut().find(|(l, _)| *l == location) {
+            Some((_, group)) => group.push(table),
+            None => groups.push((location, vec![table])),
+        }
+    }
+
+    let single = groups.len() == 1;
+    let mut used = std::collections::HashSet::new();
+    groups
+        .into_iter()
+        .map(|(location, tables)| {
+            let mut name = source_name.to_string();
+            if !single {
+                for part in [&location.0, &location.1].into_iter().flatten() {
+                    name.push('_');
+                    name.push_str(part);
+                }
+            }
+            let base = name.clone();
+            let mut suffix = 2;
+            while !used.insert(name.clone()) {
+                name = format!("{}_{}", base, suffix);
+                suffix += 1;
+            }
+            (name, location, tables)
+        })
+        .collect()
+}
+
+/// Where the tests of a table are attached in the dbt project.
+enum Relation<'a> {
+    Source(&'a str),
+    Model,
+}
+
+impl Relation<'_> {
+    fn jinja(&self, table: &str) -> String {
+        match self {
+            Relation::Source(source) => format!("{{{{ source('{}', '{}') }}}}", source, table),
+            Relation::Model => format!("{{{{ ref('{}') }}}}", table),
+        }
+    }
+
+    /// Prefix of singular test names for a table. Source tables include the
+    /// source name, so same-named tables of different schemas keep their tests.
+    fn test_prefix(&self, table: &str) -> String {
+        match self {
+            Relation::Source(source) => format!("{}_{}", source, table),
+            Relation::Model => table.to_string(),
+        }
+    }
+}
+
+/// Exporter for dbt project fragments.
+pub struct DbtExporter;
+
+impl DbtExporter {
+    /// Export tables as dbt model documentation (`schema.yml`).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use data_modelling_core::export::dbt::DbtExporter;
+    /// use data_modelling_core::models::{Table, Column};
+    ///
+    /// let mut id = Column::new("id".to_string(), "INTEGER".to_string());
+    /// id.primary_key = true;
+    /// let tables = vec![Table::new("customers".to_string(), vec![id])];
+    ///
+    /// let result = DbtExporter.export(&tables).unwrap();
+    /// assert_eq!(result.format, "dbt");
+    /// assert!(result.content.contains("not_null"));
+    /// ```
+    pub fn export(&self, tables: &[Table]) -> Result<ExportResult, ExportError> {
+        let project = Self::export_project(&[], tables, "raw")?;
+        Ok(ExportResult {
+            content: project.schema.unwrap_or_default(),
+            format: "dbt".to_string(),
+        })
+    }
+
+    /// Export a project fragment.
+    ///
+    /// # Arguments
+    ///
+    /// * `sources` - Raw tables, written to `sources.yml` under `source_name`
+    /// * `models` - Modelled tables, written to `schema.yml`
+    /// * `source_name` - dbt source name used in `{{ source(...) }}`
+    ///
+    /// Source tables are grouped by catalog and schema into one dbt source
+    /// each. With more than one group, each source is named
+    /// `<source_name>_<catalog>_<schema>` from the parts that are set.
+    pub fn export_project(
+        sources: &[Table],
+        models: &[Table],
+        source_name: &str,
+    ) -> Result<DbtProject, ExportError> {
+        let mut project = DbtProject::default();
+
+        if !sources.is_empty() {
+            let mut source_defs = Vec::new();
+            for (name, (database, schema), tables) in source_groups(sources, source_name) {
+                let relation = Relation::Source(&name);
+                let tables = tables
+                    .iter()
+                    .map(|t| Self::model_def(t, &relation, &mut project))
+                    .collect();
+                source_defs.push(SourceDef {
+                    name: name.clone(),
+                    database,
+                    schema,
+                    tables,
+                });
+            }
+            let file = SourcesFile {
+                version: DBT_PROPERTIES_VERSION,
+                sources: source_defs,
+            };
+            project.sources = Some(
+                serde_yaml::to_string(&file)
+                    .map_err(|e| ExportError::SerializationError(e.to_string()))?,
+            );
+        }
+
+        if !models.is_empty() {
+            let models = models
+                .iter()
+                .map(|t| Self::model_def(t, &Relation::Model, &mut project))
+                .collect();
+            let file = SchemaFile {
+                version: DBT_PROPERTIES_VERSION,
+                models,
+            };
+            project.schema = Some(
+                serde_yaml::to_string(&file)
+                    .map_err(|e| ExportError::SerializationError(e.to_string()))?,
+            );
+        }
+
+        Ok(project)
+    }
+
+    fn model_def(table: &Table, relation: &Relation, project: &mut DbtProject) -> ModelDef {
+        let primary_keys = primary_key_columns(table);
+        let mut data_tests = Vec::new();
+        if primary_keys.len() > 1 {
+            data_tests.push(test_with_args(
+                "dbt_utils.unique_combination_of_columns",
+                [(
+                    "combination_of_columns",
+                    Value::Sequence(primary_keys.iter().map(|c| Value::from(*c)).collect()),
+                )],
+            ));
+        }
+
+        let test_prefix = relation.test_prefix(&table.name);
+        for (index, rule) in parse_rules(&table.quality).iter().enumerate() {
+            let test_name = format!("assert_{}_rule_{}", test_prefix, index + 1);
+            Self::singular_test(rule, table, None, relation, test_name, project);
+        }
+
+        let columns = table
+            .columns
+            .iter()
+            .map(|column| {
+                let mut data_tests = column_tests(column, primary_keys.len() == 1);
+                for (index, rule) in parse_rules(&column.quality).iter().enumerate() {
+                    if rule.query.is_some() {
+                        let test_name = format!(
+                            "assert_{}_{}_rule_{}",
+                            test_prefix,
+                            column_name(column),
+                            index + 1
+                        );
+                        Self::singular_test(
+                            rule,
+                            table,
+                            Some(column),
+                            relation,
+                            test_name,
+                            project,
+                        );
+                    } else if !rule_tests(rule, column, &mut data_tests) {
+                        project.warnings.push(format!(
+                            "{}.{}: quality rule '{}' has no dbt equivalent",
+                            table.name,
+                            column.name,
+                            rule_label(rule)
+                        ));
+                    }
+                }
+                ColumnDef {
+                    name: column_name(column).to_string(),
+                    description: non_empty(&column.description),
+                    data_type: column
+                        .physical_type
+                        .clone()
+                        .or_else(|| non_empty(&column.data_type)),
+                    data_tests,
+                }
+            })
+            .collect();
+
+        ModelDef {
+            name: table.name.clone(),
+            description: table_description(table),
+            data_tests,
+            columns,
+        }
+    }
+
+    /// Emit a singular test that returns a row when the rule's query result
+    /// violates its threshold.
+    fn singular_test(
+        rule: &QualityRule,
+        table: &Table,
+        column: Option<&Column>,
+        relation: &Relation,
+        test_name: String,
+        project: &mut DbtProject,
+    ) {
+        let scope = match column {
+            Some(c) => format!("{}.{}", table.name, c.name),
+            None => table.name.clone(),
+        };
+        let Some(query) = rule.query.as_deref() else {
+            project.warnings.push(format!(
+                "{}: quality rule '{}' has no dbt equivalent",
+                scope,
+                rule_label(rule)
+            ));
+            return;
+        };
+        let conditions = rule_conditions(rule);
+        if conditions.is_empty() {
+            project.warnings.push(format!(
+                "{}: quality rule '{}' has a query but no threshold",
+                scope,
+                rule_label(rule)
+            ));
+            return;
+        }
+
+        let relation = relation.jinja(&table.name);
+        let column = column.map(column_name).unwrap_or_default();
+        let query = query
+            .trim()
+            .trim_end_matches(';')
+            .replace("${table}", &relation)
+            .replace("{object}", &relation)
+            .replace("${column}", column)
+            .replace("{property}", column);
+
+        let mut sql = String::new();
+        if let Some(description) = &rule.description {
+            sql.push_str(&format!("-- {}\n", description));
+        }
+        sql.push_str(&format!("-- Quality rule on {}\n", scope));
+        sql.push_str("with rule as (\n    select (\n");
+        for line in query.lines() {
+            sql.push_str(&format!("        {}\n", line));
+        }
+        sql.push_str("    ) as observed\n)\n\n");
+        sql.push_str("select observed\nfrom rule\n");
+        sql.push_str(&format!("where not ({})\n", conditions.join(" and ")));
+
+        project.tests.insert(test_name, sql);
+    }
+}
+
+fn column_name(column: &Column) -> &str {
+    column.physical_name.as_deref().unwrap_or(&column.name)
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
+
+fn primary_key_columns(table: &Table) -> Vec<&str> {
+    let mut keys: Vec<&Column> = table.columns.iter().filter(|c| c.primary_key).collect();
+    keys.sort_by_key(|c| c.primary_key_position.unwrap_or(i32::MAX));
+    keys.into_iter().map(column_name).collect()
+}
+
+fn parse_rules(
+    quality: &[std::collections::HashMap<String, serde_json::Value>],
+) -> Vec<QualityRule> {
+    quality
+        .iter()
+        .filter_map(|q| serde_json::to_value(q).ok())
+        .filter_map(|v| serde_json::from_value(v).ok())
+        .collect()
+}
+
+fn rule_label(rule: &QualityRule) -> String {
+    rule.metric
+        .clone()
+        .or_else(|| rule.description.clone())
+        .or_else(|| rule.rule_type.clone())
+        .unwrap_or_else(|| "unnamed".to_string())
+}
+
+/// Generic tests implied by the column's own constraints.
+fn column_tests(column: &Column, single_primary_key: bool) -> Vec<Value> {
+    let mut tests = Vec::new();
+    if column.primary_key || !column.nullable {
+        tests.push(Value::from("not_null"));
+    }
+    if column.unique || (column.primary_key && single_primary_key) {
+        tests.push(Value::from("unique"));
+    }
+    if !column.enum_values.is_empty() {
+        tests.push(test_with_args(
+            "accepted_values",
+            [(
+                "values",
+                Value::Sequence(
+                    column
+                        .enum_values
+                        .iter()
+                        .map(|v| Value::from(v.as_str()))
+                        .collect(),
+                ),
+            )],
+        ));
+    }
+    tests
+}
+
+/// Translate a query-less quality rule into generic tests.
+///
+/// Returns `false` when nothing in the rule could be expressed.
+fn rule_tests(rule: &QualityRule, column: &Column, tests: &mut Vec<Value>) -> bool {
+    let before = tests.len();
+    let zero = serde_json::json!(0);
+
+    match rule.metric.as_deref() {
+        Some("nullValues") if rule.must_be.as_ref() == Some(&zero) => {
+            push_unique(tests, Value::from("not_null"));
+        }
+        Some("duplicateValues") if rule.must_be.as_ref() == Some(&zero) => {
+            push_unique(tests, Value::from("unique"));
+        }
+        _ => {}
+    }
+
+    if let Some(values) = &rule.must_be_in
+        && column.enum_values.is_empty()
+    {
+        tests.push(test_with_args(
+            "accepted_values",
+            [("values", json_values(values))],
+        ));
+    }
+    if let Some(values) = &rule.must_not_be_in {
+        tests.push(test_with_args(
+            "dbt_utils.not_accepted_values",
+            [("values", json_values(values))],
+        ));
+    }
+
+    // accepted_range takes one inclusivity flag, so mixed bounds become two tests
+    let lower = rule
+        .must_be_greater_than_or_equal
+        .as_ref()
+        .map(|v| (v, true))
+        .or_else(|| rule.must_be_greater_than.as_ref().map(|v| (v, false)));
+    let upper = rule
+        .must_be_less_than_or_equal
+        .as_ref()
+        .map(|v| (v, true))
+        .or_else(|| rule.must_be_less_than.as_ref().map(|v| (v, false)));
+    match (lower, upper) {
+        (Some((min, lo_inc)), Some((max, hi_inc))) if lo_inc == hi_inc => {
+            tests.push(accepted_range(Some(min), Some(max), lo_inc));
+        }
+        (lower, upper) => {
+            if let Some((min, inclusive)) = lower {
+                tests.push(accepted_range(Some(min), None, inclusive));
+            }
+            if let Some((max, inclusive)) = upper {
+                tests.push(accepted_range(None, Some(max), inclusive));
+            }
+        }
+    }
+
+    tests.len() > before || rule.must_be_in.is_some()
+}
+
+/// SQL conditions on `observed` that must hold for the rule to pass.
+fn rule_conditions(rule: &QualityRule) -> Vec<String> {
+    [
+        (&rule.must_be, "="),
+        (&rule.must_not_be, "<>"),
+        (&rule.must_be_greater_than, ">"),
+        (&rule.must_be_greater_than_or_equal, ">="),
+        (&rule.must_be_less_than, "<"),
+        (&rule.must_be_less_than_or_equal, "<="),
+    ]
+    .into_iter()
+    .filter_map(|(value, op)| {
+        value
+            .as_ref()
+            .map(|v| format!("observed {} {}", op, json_to_sql(v)))
+    })
+    .collect()
+}
+
+fn accepted_range(
+    min: Option<&serde_json::Value>,
+    max: Option<&serde_json::Value>,
+    inclusive: bool,
+) -> Value {
+    let mut args = Vec::new();
+    if let Some(min) = min {
+        args.push(("min_value", json_to_yaml(min)));
+    }
+    if let Some(max) = max {
+        args.push(("max_value", json_to_yaml(max)));
+    }
+    if !inclusive {
+        args.push(("inclusive", Value::Bool(false)));
+    }
+    test_with_args("dbt_utils.accepted_range", args)
+}
+
+/// Table description: the ODCS schema description, falling back to the notes.
+fn table_description(table: &Table) -> Option<String> {
+    table
+        .odcl_metadata
+        .get("schemaDescription")
+        .and_then(|v| v.as_str())
+        .filter(|d| !d.is_empty())
+        .or_else(|| table.notes.as_deref().filter(|n| !n.is_empty()))
+        .map(str::to_string)
+}
+
+fn test_with_args<'a>(name: &str, args: impl IntoIterator<Item = (&'a str, Value)>) -> Value {
+    let mut arguments = Mapping::new();
+    for (key, value) in args {
+        arguments.insert(Value::from(key), value);
+    }
+    let mut test = Mapping::new();
+    test.insert(Value::from(name), Value::Mapping(arguments));
+    Value::Mapping(test)
+}
+
+fn push_unique(tests: &mut Vec<Value>, test: Value) {
+    if !tests.contains(&test) {
+        tests.push(test);
+    }
+}
+
+fn json_values(values: &[serde_json::Value]) -> Value {
+    Value::Sequence(values.iter().map(json_to_yaml).collect())
+}
+
+fn json_to_yaml(value: &serde_json::Value) -> Value {
+    serde_yaml::to_value(value).unwrap_or(Value::Null)
+}
+
+fn json_to_sql(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => "null".to_string(),
+        serde_json::Value::String(s) => format!("'{}'", s.replace('\'', "''")),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn rule(value: serde_json::Value) -> HashMap<String, serde_json::Value> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn orders() -> Table {
+        let mut id = Column::new("order_id".to_string(), "BIGINT".to_string());
+        id.primary_key = true;
+        id.nullable = false;
+        id.description = "Order identifier".to_string();
+
+        let mut status = Column::new("status".to_string(), "VARCHAR".to_string());
+        status.enum_values = vec!["open".to_string(), "shipped".to_string()];
+
+        let mut amount = Column::new("amount".to_string(), "DECIMAL".to_string());
+        amount.quality = vec![
+            rule(serde_json::json!({"mustBeGreaterThanOrEqual": 0, "mustBeLessThan": 10000})),
+            rule(serde_json::json!({
+                "type": "sql",
+                "description": "No negative totals",
+                "query": "SELECT COUNT(*) FROM ${table} WHERE ${column} < 0",
+                "mustBe": 0
+            })),
+        ];
+
+        Table::new("orders".to_string(), vec![id, status, amount])
+    }
+
+    #[test]
+    fn test_schema_tests_from_constraints_and_rules() {
+        let project = DbtExporter::export_project(&[], &[orders()], "raw").unwrap();
+        let schema: serde_yaml::Value =
+            serde_yaml::from_str(project.schema.as_deref().unwrap()).unwrap();
+        let columns = &schema["models"][0]["columns"];
+
+        assert_eq!(schema["version"], Value::from(2));
+        assert_eq!(columns[0]["description"], Value::from("Order identifier"));
+        assert_eq!(columns[0]["data_tests"][0], Value::from("not_null"));
+        assert_eq!(columns[0]["data_tests"][1], Value::from("unique"));
+        assert_eq!(
+            columns[1]["data_tests"][0]["accepted_values"]["values"][1],
+            Value::from("shipped")
+        );
+
+        // Mixed inclusive/exclusive bounds are split into two range tests
+        let amount_tests = columns[2]["data_tests"].as_sequence().unwrap();
+        assert_eq!(amount_tests.len(), 2);
+        assert_eq!(
+            amount_tests[1]["dbt_utils.accepted_range"]["inclusive"],
+            Value::Bool(false)
+        );
+
+        let test = &project.tests["assert_orders_amount_rule_2"];
+        assert!(test.contains("FROM {{ ref('orders') }} WHERE amount < 0"));
+        assert!(test.contains("where not (observed = 0)"));
+        assert!(project.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_sources_and_files() {
+        let mut raw = orders();
+        raw.schema_name = Some("landing".to_string());
+        let mut project = DbtExporter::export_project(&[raw], &[], "shop").unwrap();
+        project.add_model("stg_shop__orders", "select 1");
+
+        let sources: serde_yaml::Value =
+            serde_yaml::from_str(project.sources.as_deref().unwrap()).unwrap();
+        assert_eq!(sources["sources"][0]["name"], Value::from("shop"));
+        assert_eq!(sources["sources"][0]["schema"], Value::from("landing"));
+        assert_eq!(
+            sources["sources"][0]["tables"][0]["name"],
+            Value::from("orders")
+        );
+        assert!(
+            project.tests["assert_shop_orders_amount_rule_2"]
+                .contains("{{ source('shop', 'orders') }}")
+        );
+
+        let paths: Vec<String> = project.files().into_iter().map(|(p, _)| p).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "models/sources.yml",
+                "models/stg_shop__orders.sql",
+                "tests/assert_shop_orders_amount_rule_2.sql"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sources_grouped_by_schema() {
+        let mut orders = orders();
+        orders.schema_name = Some("sales".to_string());
+        let mut customers = Table::new(
+            "customers".to_string(),
+            vec![Column::new("id".to_string(), "BIGINT".to_string())],
+        );
+        customers.schema_name = Some("crm".to_string());
+        let mut refunds = Table::new(
+            "refunds".to_string(),
+            vec![Column::new("id".to_string(), "BIGINT".to_string())],
+        );
+        refunds.schema_name = Some("sales".to_string());
+
+        let project =
+            DbtExporter::export_project(&[orders, customers, refunds], &[], "raw").unwrap();
+        let sources: serde_yaml::Value =
+            serde_yaml::from_str(project.sources.as_deref().unwrap()).unwrap();
+        let sources = sources["sources"].as_sequence().unwrap();
+
+        assert_eq!(sources.len(), 2);
+        assert_eq!(sources[0]["name"], Value::from("raw_sales"));
+        assert_eq!(sources[0]["schema"], Value::from("sales"));
+        assert_eq!(sources[0]["tables"][1]["name"], Value::from("refunds"));
+        assert_eq!(sources[1]["name"], Value::from("raw_crm"));
+        assert_eq!(sources[1]["tables"][0]["name"], Value::from("customers"));
+        assert!(
+            project.tests["assert_raw_sales_orders_amount_rule_2"]
+                .contains("{{ source('raw_sales', 'orders') }}")
+        );
+    }
+
+    #[test]
+    fn test_same_table_in_two_schemas() {
+        let mut sales = orders();
+        sales.schema_name = Some("sales".to_string());
+        let mut archive = orders();
+        archive.schema_name = Some("archive".to_string());
+
+        let project = DbtExporter::export_project(&[sales, archive], &[], "raw").unwrap();
+        assert!(
+            project.tests["assert_raw_sales_orders_amount_rule_2"]
+                .contains("{{ source('raw_sales', 'orders') }}")
+        );
+        assert!(
+            project.tests["assert_raw_archive_orders_amount_rule_2"]
+                .contains("{{ source('raw_archive', 'orders') }}")
+        );
+    }
+
+    #[test]
+    fn test_model_description() {
+        let mut table = orders();
+        table.notes = Some("Notes".to_string());
+        table.odcl_metadata.insert(
+            "schemaDescription".to_string(),
+            serde_json::json!("Customer orders"),
+        );
+        let project = DbtExporter::export_project(&[], &[table.clone()], "raw").unwrap();
+        let schema: serde_yaml::Value =
+            serde_yaml::from_str(project.schema.as_deref().unwrap()).unwrap();
+        assert_eq!(
+            schema["models"][0]["description"],
+            Value::from("Customer orders")
+        );
+
+        // Notes are used when the schema has no description
+        table.odcl_metadata.remove("schemaDescription");
+        let project = DbtExporter::export_project(&[], &[table], "raw").unwrap();
+        let schema: serde_yaml::Value =
+            serde_yaml::from_str(project.schema.as_deref().unwrap()).unwrap();
+        assert_eq!(schema["models"][0]["description"], Value::from("Notes"));
+    }
+}
@@ -225,7 +225,7 @@ impl JSONSchemaExporter {
     }
 
     /// Map SQL/ODCL data types to JSON Schema types and formats.
-    fn map_data_type_to_json_schema(data_type: &str) -> (String, Option<String>) {
+    pub(crate) fn map_data_type_to_json_schema(data_type: &str) -> (String, Option<String>) {
         let dt_lower = data_type.to_lowercase();
 
         match dt_lower.as_str() {
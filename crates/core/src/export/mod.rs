@@ -11,11 +11,13 @@
 //! - Decision (MADR-compliant decision records)
 //! - Knowledge (Knowledge Base articles)
 //! - Markdown (for GitHub readability)
+//! - dbt (sources, model documentation and tests)
 
 pub mod avro;
 #[cfg(feature = "bpmn")]
 pub mod bpmn;
 pub mod cads;
+pub mod dbt;
 pub mod decision;
 #[cfg(feature = "dmn")]
 pub mod dmn;
@@ -81,6 +83,7 @@ pub use avro::AvroExporter;
 #[cfg(feature = "bpmn")]
 pub use bpmn::BPMNExporter;
 pub use cads::CADSExporter;
+pub use dbt::{DbtExporter, DbtProject};
 pub use decision::DecisionExporter;
 #[cfg(feature = "dmn")]
 pub use dmn::DMNExporter;
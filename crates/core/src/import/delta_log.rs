@@ -0,0 +1,565 @@
+//! Delta Lake transaction log importer
+//!
+//! Replays a table's `_delta_log` to find its current metadata: the latest
+//! checkpoint named by `_last_checkpoint` (or the latest checkpoint file) is
+//! read first, followed by the JSON commits after it. The schema of the last
+//! `metaData` action becomes the table's columns, its partition columns are
+//! marked as partitioned, and the table configuration and protocol versions
+//! are captured in the table's `odcs_metadata`.
+
+use crate::import::lakehouse_shared::{
+    LakehouseField, LakehouseType, decimal_type_name, fields_to_columns, mark_partition_columns,
+    table_result,
+};
+use crate::import::{ImportError, ImportResult};
+use arrow::json::ArrayWriter;
+use parquet::arrow::ProjectionMask;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// Transaction log directory of a Delta table
+const DELTA_LOG_DIR: &str = "_delta_log";
+
+/// Column metadata key holding a column's physical name under column mapping
+const PHYSICAL_NAME_KEY: &str = "delta.columnMapping.physicalName";
+
+/// Table state replayed from the transaction log
+#[derive(Default)]
+struct LogState {
+    version: Option<u64>,
+    checkpoint_version: Option<u64>,
+    metadata: Option<Value>,
+    protocol: Option<Value>,
+}
+
+impl LogState {
+    fn apply(&mut self, action: &Value) {
+        if let Some(metadata) = action.get("metaData").filter(|v| !v.is_null()) {
+            self.metadata = Some(metadata.clone());
+        }
+        if let Some(protocol) = action.get("protocol").filter(|v| !v.is_null()) {
+            self.protocol = Some(protocol.clone());
+        }
+    }
+}
+
+/// Importer for Delta Lake table metadata.
+#[derive(Default)]
+pub struct DeltaLogImporter;
+
+impl DeltaLogImporter {
+    /// Create a new Delta Lake importer.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use data_modelling_core::import::delta_log::DeltaLogImporter;
+    ///
+    /// let importer = DeltaLogImporter::new();
+    /// ```
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Import a Delta table from its directory or its `_delta_log` directory.
+    ///
+    /// The table is named after its `metaData` name, or else its directory.
+    pub fn import_path(&self, path: &Path) -> Result<ImportResult, ImportError> {
+        let (table_dir, log_dir) = if path.file_name().is_some_and(|n| n == DELTA_LOG_DIR) {
+            (path.parent().unwrap_or(path), path.to_path_buf())
+        } else {
+            (path, path.join(DELTA_LOG_DIR))
+        };
+        if !log_dir.is_dir() {
+            return Err(ImportError::IoError(format!(
+                "No Delta transaction log found at {}",
+                log_dir.display()
+            )));
+        }
+
+        let state = Self::replay(&log_dir)?;
+        let metadata = state.metadata.as_ref().ok_or_else(|| {
+            ImportError::ValidationError(format!(
+                "Delta transaction log {} has no metaData action",
+                log_dir.display()
+            ))
+        })?;
+
+        let name = metadata
+            .get("name")
+            .and_then(|v| v.as_str())
+            .filter(|n| !n.is_empty())
+            .map(|n| n.to_string())
+            .or_else(|| {
+                table_dir
+                    .canonicalize()
+                    .ok()
+                    .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+            })
+            .unwrap_or_else(|| "delta_table".to_string());
+
+        let schema_string = metadata
+            .get("schemaString")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| {
+                ImportError::ValidationError("Delta metaData has no schemaString".to_string())
+            })?;
+        let schema: Value = serde_json::from_str(schema_string)
+            .map_err(|e| ImportError::ParseError(format!("Failed to parse Delta schema: {}", e)))?;
+
+        let fields = Self::struct_fields(&schema)?;
+        let mut columns = fields_to_columns(&fields);
+
+        let partition_columns: Vec<String> = metadata
+            .get("partitionColumns")
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|v| v.as_str().map(|s| s.to_string()))
+            .collect();
+        let partitions: Vec<(String, Option<String>)> = partition_columns
+            .iter()
+            .map(|c| (c.clone(), None))
+            .collect();
+        let warnings: Vec<String> = mark_partition_columns(&mut columns, &partitions)
+            .into_iter()
+            .map(|c| format!("Partition column '{}' not found", c))
+            .collect();
+
+        let mut odcs_metadata = HashMap::new();
+        odcs_metadata.insert("format".to_string(), json!("delta"));
+        if let Some(version) = state.version {
+            odcs_metadata.insert("tableVersion".to_string(), json!(version));
+        }
+        if let Some(version) = state.checkpoint_version {
+            odcs_metadata.insert("checkpointVersion".to_string(), json!(version));
+        }
+        if let Some(id) = metadata.get("id").filter(|v| !v.is_null()) {
+            odcs_metadata.insert("tableId".to_string(), id.clone());
+        }
+        if let Some(created) = metadata.get("createdTime").filter(|v| !v.is_null()) {
+            odcs_metadata.insert("createdTime".to_string(), created.clone());
+        }
+        if !partition_columns.is_empty() {
+            odcs_metadata.insert("partitionColumns".to_string(), json!(partition_columns));
+        }
+        if let Some(configuration) = metadata
+            .get("configuration")
+            .filter(|v| v.as_object().is_some_and(|c| !c.is_empty()))
+        {
+            odcs_metadata.insert("tableProperties".to_string(), configuration.clone());
+        }
+        if let Some(ref protocol) = state.protocol {
+            odcs_metadata.insert("protocol".to_string(), protocol.clone());
+        }
+
+        let description = metadata
+            .get("description")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+
+        info!(
+            "Parsed Delta transaction log: {} with {} columns",
+            name,
+            columns.len()
+        );
+        table_result(&name, description, columns, odcs_metadata, warnings)
+    }
+
+    /// Replay the latest checkpoint and the commits after it
+    fn replay(log_dir: &Path) -> Result<LogState, ImportError> {
+        let entries: Vec<PathBuf> = std::fs::read_dir(log_dir)
+            .map_err(|e| {
+                ImportError::IoError(format!("Failed to read {}: {}", log_dir.display(), e))
+            })?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .collect();
+
+        let mut state = LogState {
+            checkpoint_version: Self::last_checkpoint(log_dir).or_else(|| {
+                entries
+                    .iter()
+                    .filter(|p| Self::is_checkpoint_file(p))
+                    .filter_map(|p| Self::file_version(p))
+                    .max()
+            }),
+            ..Default::default()
+        };
+
+        if let Some(version) = state.checkpoint_version {
+            let mut parts: Vec<&PathBuf> = entries
+                .iter()
+                .filter(|p| Self::is_checkpoint_file(p) && Self::file_version(p) == Some(version))
+                .collect();
+            parts.sort();
+            if parts.is_empty() {
+                return Err(ImportError::IoError(format!(
+                    "Checkpoint {} not found in {}",
+                    version,
+                    log_dir.display()
+                )));
+            }
+            for part in parts {
+                for action in Self::checkpoint_actions(part)? {
+                    state.apply(&action);
+                }
+            }
+            state.version = Some(version);
+        }
+
+        let mut commits: Vec<(u64, &PathBuf)> = entries
+            .iter()
+            .filter(|p| p.extension().is_some_and(|e| e == "json"))
+            .filter_map(|p| {
+                // Commits are named `<20-digit version>.json`
+                let stem = p.file_stem()?.to_str()?;
+                if !stem.chars().all(|c| c.is_ascii_digit()) {
+                    return None;
+                }
+                Some((stem.parse::<u64>().ok()?, p))
+            })
+            .filter(|(version, _)| state.checkpoint_version.is_none_or(|c| *version > c))
+            .collect();
+        commits.sort();
+
+        for (version, path) in commits {
+            let content = std::fs::read_to_string(path).map_err(|e| {
+                ImportError::IoError(format!("Failed to read {}: {}", path.display(), e))
+            })?;
+            for line in content.lines().filter(|l| !l.trim().is_empty()) {
+                let action: Value = serde_json::from_str(line).map_err(|e| {
+                    ImportError::ParseError(format!("Invalid action in {}: {}", path.display(), e))
+                })?;
+                state.apply(&action);
+            }
+            state.version = Some(version);
+        }
+
+        Ok(state)
+    }
+
+    /// Checkpoint version recorded in `_last_checkpoint`
+    fn last_checkpoint(log_dir: &Path) -> Option<u64> {
+        let content = std::fs::read_to_string(log_dir.join("_last_checkpoint")).ok()?;
+        let value: Value = serde_json::from_str(&content).ok()?;
+        value.get("version")?.as_u64()
+    }
+
+    /// Whether a file is a Parquet checkpoint (single-part, multi-part or V2)
+    fn is_checkpoint_file(path: &Path) -> bool {
+        path.file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.contains(".checkpoint") && n.ends_with(".parquet"))
+    }
+
+    /// Version prefix of a log file name
+    fn file_version(path: &Path) -> Option<u64> {
+        let name = path.file_name()?.to_str()?;
+        name.split('.').next()?.parse().ok()
+    }
+
+    /// Read the `metaData` and `protocol` actions of a checkpoint file
+    fn checkpoint_actions(path: &Path) -> Result<Vec<Value>, ImportError> {
+        let parquet_error = |e: &dyn std::fmt::Display| {
+            ImportError::ParseError(format!(
+                "Failed to read checkpoint {}: {}",
+                path.display(),
+                e
+            ))
+        };
+
+        let file = File::open(path).map_err(|e| {
+            ImportError::IoError(format!("Failed to open {}: {}", path.display(), e))
+        })?;
+        let builder =
+            ParquetRecordBatchReaderBuilder::try_new(file).map_err(|e| parquet_error(&e))?;
+
+        // Only the metaData and protocol columns are needed
+        let roots: Vec<usize> = builder
+            .parquet_schema()
+            .root_schema()
+            .get_fields()
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| matches!(f.name(), "metaData" | "protocol"))
+            .map(|(i, _)| i)
+            .collect();
+        let mask = ProjectionMask::roots(builder.parquet_schema(), roots);
+        let reader = builder
+            .with_projection(mask)
+            .build()
+            .map_err(|e| parquet_error(&e))?;
+
+        let mut writer = ArrayWriter::new(Vec::new());
+        for batch in reader {
+            let batch = batch.map_err(|e| parquet_error(&e))?;
+            writer.write(&batch).map_err(|e| parquet_error(&e))?;
+        }
+        writer.finish().map_err(|e| parquet_error(&e))?;
+
+        let rows = writer.into_inner();
+        if rows.is_empty() {
+            return Ok(Vec::new());
+        }
+        serde_json::from_slice(&rows).map_err(|e| parquet_error(&e))
+    }
+
+    /// Convert the fields of a Spark struct type
+    fn struct_fields(struct_type: &Value) -> Result<Vec<LakehouseField>, ImportError> {
+        let fields = struct_type
+            .get("fields")
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| ImportError::ParseError("Struct type missing fields".to_string()))?;
+
+        let mut result = Vec::new();
+        for field in fields {
+            let name = field
+                .get("name")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| ImportError::ParseError("Field missing name".to_string()))?;
+            let field_type = field
+                .get("type")
+                .ok_or_else(|| ImportError::ParseError(format!("Field '{}' missing type", name)))?;
+            let nullable = field
+                .get("nullable")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+
+            let mut lakehouse_field =
+                LakehouseField::new(name, Self::spark_type(field_type, name)?, nullable);
+            lakehouse_field.physical_type = field_type.as_str().map(|s| s.to_string());
+
+            let metadata = field.get("metadata");
+            let metadata_str = |key: &str| {
+                metadata
+                    .and_then(|m| m.get(key))
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string())
+            };
+            lakehouse_field.description = metadata_str("comment").unwrap_or_default();
+            lakehouse_field.physical_name = metadata_str(PHYSICAL_NAME_KEY);
+            if let Some(expression) = metadata_str("delta.generationExpression") {
+                lakehouse_field
+                    .custom_properties
+                    .insert("generationExpression".to_string(), json!(expression));
+            }
+            result.push(lakehouse_field);
+        }
+        Ok(result)
+    }
+
+    /// Convert a Spark type
+    fn spark_type(field_type: &Value, name: &str) -> Result<LakehouseType, ImportError> {
+        if let Some(type_name) = field_type.as_str() {
+            return Ok(LakehouseType::Primitive(Self::primitive_type(type_name)));
+        }
+
+        match field_type.get("type").and_then(|v| v.as_str()) {
+            Some("struct") => Ok(LakehouseType::Struct(Self::struct_fields(field_type)?)),
+            Some("array") => {
+                let element = field_type.get("elementType").ok_or_else(|| {
+                    ImportError::ParseError(format!("Array '{}' missing elementType", name))
+                })?;
+                Ok(LakehouseType::List(Box::new(Self::spark_type(
+                    element, name,
+                )?)))
+            }
+            Some("map") => {
+                let (Some(key), Some(value)) =
+                    (field_type.get("keyType"), field_type.get("valueType"))
+                else {
+                    return Err(ImportError::ParseError(format!(
+                        "Map '{}' missing keyType or valueType",
+                        name
+                    )));
+                };
+                Ok(LakehouseType::Map(
+                    Box::new(Self::spark_type(key, name)?),
+                    Box::new(Self::spark_type(value, name)?),
+                ))
+            }
+            other => Err(ImportError::ParseError(format!(
+                "Unsupported Delta type {:?} for '{}'",
+                other, name
+            ))),
+        }
+    }
+
+    /// Map a Spark primitive type to a SQL type name
+    fn primitive_type(type_name: &str) -> String {
+        match type_name {
+            "boolean" => "BOOLEAN".to_string(),
+            "byte" => "TINYINT".to_string(),
+            "short" => "SMALLINT".to_string(),
+            "integer" => "INT".to_string(),
+            "long" => "BIGINT".to_string(),
+            "float" => "FLOAT".to_string(),
+            "double" => "DOUBLE".to_string(),
+            "date" => "DATE".to_string(),
+            "timestamp" => "TIMESTAMP".to_string(),
+            "timestamp_ntz" => "TIMESTAMP_NTZ".to_string(),
+            "string" => "STRING".to_string(),
+            "binary" => "BINARY".to_string(),
+            other => decimal_type_name(other).unwrap_or_else(|| other.to_uppercase()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{ArrayRef, StringArray, StructArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::record_batch::RecordBatch;
+    use parquet::arrow::ArrowWriter;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn schema_string(extra_field: bool) -> String {
+        let mut fields = vec![
+            json!({"name": "order_id", "type": "long", "nullable": false,
+                   "metadata": {"comment": "Order key"}}),
+            json!({"name": "amount", "type": "decimal(10,2)", "nullable": true, "metadata": {}}),
+            json!({"name": "order_date", "type": "date", "nullable": true, "metadata": {}}),
+            json!({"name": "lines", "nullable": true, "metadata": {}, "type": {
+            "type": "array", "containsNull": true, "elementType": {
+                "type": "struct", "fields": [
+                    {"name": "sku", "type": "string", "nullable": false, "metadata": {}}
+                ]}}}),
+        ];
+        if extra_field {
+            fields.push(
+                json!({"name": "status", "type": "string", "nullable": true, "metadata": {}}),
+            );
+        }
+        json!({"type": "struct", "fields": fields}).to_string()
+    }
+
+    fn metadata_action(extra_field: bool) -> Value {
+        json!({"metaData": {
+            "id": "5b1e0a4c-1111-2222-3333-444455556666",
+            "format": {"provider": "parquet", "options": {}},
+            "schemaString": schema_string(extra_field),
+            "partitionColumns": ["order_date"],
+            "configuration": {"delta.appendOnly": "true"},
+            "createdTime": 1700000000000u64
+        }})
+    }
+
+    fn write_commit(log_dir: &Path, version: u64, actions: &[Value]) {
+        let lines: Vec<String> = actions.iter().map(|a| a.to_string()).collect();
+        std::fs::write(
+            log_dir.join(format!("{:020}.json", version)),
+            lines.join("\n"),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_import_delta_commits() {
+        let temp = TempDir::new().unwrap();
+        let log_dir = temp.path().join("orders").join(DELTA_LOG_DIR);
+        std::fs::create_dir_all(&log_dir).unwrap();
+        write_commit(
+            &log_dir,
+            0,
+            &[
+                json!({"protocol": {"minReaderVersion": 1, "minWriterVersion": 2}}),
+                metadata_action(false),
+            ],
+        );
+        write_commit(
+            &log_dir,
+            1,
+            &[json!({"add": {"path": "order_date=2024-01-02/part-0.parquet"}})],
+        );
+        write_commit(&log_dir, 2, &[metadata_action(true)]);
+
+        let result = DeltaLogImporter::new()
+            .import_path(&temp.path().join("orders"))
+            .unwrap();
+        assert!(result.errors.is_empty());
+        let table = &result.tables[0];
+        assert_eq!(table.name.as_deref(), Some("orders"));
+
+        let column = |name: &str| table.columns.iter().find(|c| c.name == name).unwrap();
+        assert_eq!(column("order_id").data_type, "BIGINT");
+        assert!(!column("order_id").nullable);
+        assert_eq!(column("order_id").description.as_deref(), Some("Order key"));
+        assert_eq!(column("amount").data_type, "DECIMAL(10,2)");
+        assert_eq!(column("lines").data_type, "ARRAY<OBJECT>");
+        assert_eq!(column("lines.[].sku").data_type, "STRING");
+        assert_eq!(column("status").data_type, "STRING");
+        assert!(column("order_date").partitioned);
+        assert_eq!(column("order_date").partition_key_position, Some(1));
+
+        assert_eq!(table.odcs_metadata["tableVersion"], 2);
+        assert_eq!(
+            table.odcs_metadata["tableProperties"]["delta.appendOnly"],
+            "true"
+        );
+        assert_eq!(table.odcs_metadata["protocol"]["minWriterVersion"], 2);
+    }
+
+    #[test]
+    fn test_import_delta_checkpoint() {
+        let temp = TempDir::new().unwrap();
+        let log_dir = temp.path().join("orders").join(DELTA_LOG_DIR);
+        std::fs::create_dir_all(&log_dir).unwrap();
+
+        // Checkpoint at version 3 holding the original metadata
+        let metadata_fields = vec![
+            Field::new("id", DataType::Utf8, true),
+            Field::new("schemaString", DataType::Utf8, true),
+        ];
+        let metadata = StructArray::new(
+            metadata_fields.clone().into(),
+            vec![
+                Arc::new(StringArray::from(vec![None, Some("orders-id")])) as ArrayRef,
+                Arc::new(StringArray::from(vec![None, Some(schema_string(false))])),
+            ],
+            Some(vec![false, true].into()),
+        );
+        let add_fields = vec![Field::new("path", DataType::Utf8, true)];
+        let add = StructArray::new(
+            add_fields.clone().into(),
+            vec![Arc::new(StringArray::from(vec![Some("part-0.parquet"), None])) as ArrayRef],
+            Some(vec![true, false].into()),
+        );
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("add", DataType::Struct(add_fields.into()), true),
+            Field::new("metaData", DataType::Struct(metadata_fields.into()), true),
+        ]));
+        let batch =
+            RecordBatch::try_new(schema.clone(), vec![Arc::new(add), Arc::new(metadata)]).unwrap();
+        let file = File::create(log_dir.join(format!("{:020}.checkpoint.parquet", 3))).unwrap();
+        let mut writer = ArrowWriter::try_new(file, schema, None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        std::fs::write(
+            log_dir.join("_last_checkpoint"),
+            r#"{"version": 3, "size": 2}"#,
+        )
+        .unwrap();
+
+        // Commits up to the checkpoint are ignored, later ones are applied
+        write_commit(
+            &log_dir,
+            2,
+            &[json!({"metaData": {"schemaString": "not json"}})],
+        );
+        write_commit(&log_dir, 4, &[json!({"add": {"path": "part-1.parquet"}})]);
+
+        let result = DeltaLogImporter::new().import_path(&log_dir).unwrap();
+        let table = &result.tables[0];
+        assert_eq!(table.name.as_deref(), Some("orders"));
+        assert_eq!(table.columns.len(), 5);
+        assert_eq!(table.odcs_metadata["checkpointVersion"], 3);
+        assert_eq!(table.odcs_metadata["tableVersion"], 4);
+        assert_eq!(table.odcs_metadata["tableId"], "orders-id");
+    }
+}
@@ -0,0 +1,548 @@
+//! Apache Iceberg table metadata importer
+//!
+//! Reads an Iceberg `metadata.json` file and produces a table from its current
+//! schema. Field nullability follows `required`, identity partitions mark
+//! their source columns as partitioned and other transforms are kept in the
+//! `partitionTransform` custom property. Table properties, the partition spec
+//! and the current snapshot summary are captured in the table's
+//! `odcs_metadata`.
+//!
+//! Tables created from ODCS contracts record their property ids as
+//! `odcs.column.<id>` table properties; these are restored as column ids.
+
+use crate::import::lakehouse_shared::{
+    LakehouseField, LakehouseType, decimal_type_name, fields_to_columns, mark_partition_columns,
+    table_result,
+};
+use crate::import::{ImportError, ImportResult};
+use serde_json::{Map, Value, json};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// Table property prefix mapping ODCS property ids to Iceberg field paths
+const COLUMN_ID_PROPERTY_PREFIX: &str = "odcs.column.";
+
+/// Importer for Iceberg table metadata files.
+#[derive(Default)]
+pub struct IcebergMetadataImporter;
+
+impl IcebergMetadataImporter {
+    /// Create a new Iceberg metadata importer.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use data_modelling_core::import::iceberg_metadata::IcebergMetadataImporter;
+    ///
+    /// let importer = IcebergMetadataImporter::new();
+    /// ```
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Import an Iceberg table from a local path.
+    ///
+    /// The path may be a `*.metadata.json` file, a table's `metadata`
+    /// directory or the table directory. For directories the version named by
+    /// `version-hint.text` is used, or else the latest metadata file.
+    pub fn import_path(&self, path: &Path) -> Result<ImportResult, ImportError> {
+        let metadata_file = Self::metadata_file(path)?;
+        let content = std::fs::read_to_string(&metadata_file).map_err(|e| {
+            ImportError::IoError(format!("Failed to read {}: {}", metadata_file.display(), e))
+        })?;
+
+        // The table directory holds the metadata directory
+        let table_dir = metadata_file
+            .parent()
+            .filter(|p| p.file_name().is_some_and(|n| n == "metadata"))
+            .and_then(|p| p.parent());
+        let fallback_name = table_dir
+            .and_then(|p| p.file_name())
+            .map(|n| n.to_string_lossy().to_string());
+
+        self.import_with_name(&content, fallback_name.as_deref())
+    }
+
+    /// Import Iceberg table metadata JSON content.
+    ///
+    /// The table is named after the last segment of its `location`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use data_modelling_core::import::iceberg_metadata::IcebergMetadataImporter;
+    ///
+    /// let metadata = r#"{
+    ///   "format-version": 2,
+    ///   "location": "s3://warehouse/sales/orders",
+    ///   "current-schema-id": 0,
+    ///   "schemas": [{
+    ///     "schema-id": 0,
+    ///     "type": "struct",
+    ///     "fields": [{"id": 1, "name": "order_id", "required": true, "type": "long"}]
+    ///   }]
+    /// }"#;
+    /// let result = IcebergMetadataImporter::new().import(metadata).unwrap();
+    /// assert_eq!(result.tables[0].name.as_deref(), Some("orders"));
+    /// ```
+    pub fn import(&self, metadata_json: &str) -> Result<ImportResult, ImportError> {
+        self.import_with_name(metadata_json, None)
+    }
+
+    fn import_with_name(
+        &self,
+        metadata_json: &str,
+        fallback_name: Option<&str>,
+    ) -> Result<ImportResult, ImportError> {
+        let metadata: Value = serde_json::from_str(metadata_json).map_err(|e| {
+            ImportError::ParseError(format!("Failed to parse Iceberg metadata: {}", e))
+        })?;
+
+        let name = metadata
+            .get("location")
+            .and_then(|v| v.as_str())
+            .and_then(|l| l.trim_end_matches('/').rsplit('/').next())
+            .filter(|n| !n.is_empty())
+            .or(fallback_name)
+            .unwrap_or("iceberg_table")
+            .to_string();
+
+        let schema = Self::current_schema(&metadata)?;
+        let properties: Map<String, Value> = metadata
+            .get("properties")
+            .and_then(|v| v.as_object())
+            .cloned()
+            .unwrap_or_default();
+        let property_ids: HashMap<&str, &str> = properties
+            .iter()
+            .filter_map(|(key, path)| {
+                let id = key.strip_prefix(COLUMN_ID_PROPERTY_PREFIX)?;
+                Some((path.as_str()?, id))
+            })
+            .collect();
+
+        let fields = Self::struct_fields(schema, "", &property_ids)?;
+        let mut columns = fields_to_columns(&fields);
+        let mut warnings = Vec::new();
+
+        // Partition columns of the default spec
+        let spec = Self::default_partition_spec(&metadata);
+        let field_names = Self::field_names(schema);
+        let mut partitions = Vec::new();
+        let mut spec_json = Vec::new();
+        for field in spec {
+            let source_id = field.get("source-id").and_then(|v| v.as_i64());
+            let transform = field
+                .get("transform")
+                .and_then(|v| v.as_str())
+                .unwrap_or("identity");
+            let Some(column) = source_id.and_then(|id| field_names.get(&id)) else {
+                warnings.push(format!(
+                    "Partition field {} has an unknown source column",
+                    field.get("name").unwrap_or(&Value::Null)
+                ));
+                continue;
+            };
+            if transform == "void" {
+                continue;
+            }
+            partitions.push((
+                column.clone(),
+                (transform != "identity").then(|| transform.to_string()),
+            ));
+            spec_json.push(json!({
+                "name": field.get("name"),
+                "column": column,
+                "transform": transform,
+            }));
+        }
+        for column in mark_partition_columns(&mut columns, &partitions) {
+            warnings.push(format!("Partition column '{}' not found", column));
+        }
+
+        let mut odcs_metadata = HashMap::new();
+        odcs_metadata.insert("format".to_string(), json!("iceberg"));
+        for (key, target) in [
+            ("format-version", "formatVersion"),
+            ("table-uuid", "tableUuid"),
+            ("location", "location"),
+            ("current-snapshot-id", "currentSnapshotId"),
+            ("last-updated-ms", "lastUpdatedMs"),
+        ] {
+            if let Some(value) = metadata.get(key).filter(|v| !v.is_null()) {
+                odcs_metadata.insert(target.to_string(), value.clone());
+            }
+        }
+        if !spec_json.is_empty() {
+            odcs_metadata.insert("partitionSpec".to_string(), Value::Array(spec_json));
+        }
+        if !properties.is_empty() {
+            odcs_metadata.insert(
+                "tableProperties".to_string(),
+                Value::Object(properties.clone()),
+            );
+        }
+        if let Some(summary) = Self::current_snapshot_summary(&metadata) {
+            odcs_metadata.insert("snapshotSummary".to_string(), summary.clone());
+        }
+
+        let description = properties
+            .get("comment")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+
+        info!(
+            "Parsed Iceberg metadata: {} with {} columns",
+            name,
+            columns.len()
+        );
+        table_result(&name, description, columns, odcs_metadata, warnings)
+    }
+
+    /// Resolve the metadata file for a file, metadata directory or table directory
+    fn metadata_file(path: &Path) -> Result<PathBuf, ImportError> {
+        if path.is_file() {
+            return Ok(path.to_path_buf());
+        }
+        let metadata_dir = if path.join("metadata").is_dir() {
+            path.join("metadata")
+        } else {
+            path.to_path_buf()
+        };
+
+        if let Ok(hint) = std::fs::read_to_string(metadata_dir.join("version-hint.text")) {
+            let candidate = metadata_dir.join(format!("v{}.metadata.json", hint.trim()));
+            if candidate.is_file() {
+                return Ok(candidate);
+            }
+        }
+
+        let entries = std::fs::read_dir(&metadata_dir).map_err(|e| {
+            ImportError::IoError(format!("Failed to read {}: {}", metadata_dir.display(), e))
+        })?;
+        entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter_map(|path| {
+                let file_name = path.file_name()?.to_str()?;
+                let stem = file_name.strip_suffix(".metadata.json")?;
+                // `00003-<uuid>.metadata.json` or `v3.metadata.json`
+                let digits: String = stem
+                    .trim_start_matches('v')
+                    .chars()
+                    .take_while(|c| c.is_ascii_digit())
+                    .collect();
+                Some((digits.parse::<u64>().unwrap_or(0), path))
+            })
+            .max()
+            .map(|(_, path)| path)
+            .ok_or_else(|| {
+                ImportError::IoError(format!(
+                    "No Iceberg metadata files found in {}",
+                    metadata_dir.display()
+                ))
+            })
+    }
+
+    /// Current schema, from `schemas` (format v2) or `schema` (format v1)
+    fn current_schema(metadata: &Value) -> Result<&Value, ImportError> {
+        let current_id = metadata.get("current-schema-id").and_then(|v| v.as_i64());
+        let schemas = metadata.get("schemas").and_then(|v| v.as_array());
+
+        let schema = match (schemas, current_id) {
+            (Some(schemas), Some(id)) => schemas
+                .iter()
+                .find(|s| s.get("schema-id").and_then(|v| v.as_i64()) == Some(id)),
+            (Some(schemas), None) => schemas.last(),
+            _ => None,
+        };
+        schema.or_else(|| metadata.get("schema")).ok_or_else(|| {
+            ImportError::ValidationError("Iceberg metadata has no current schema".to_string())
+        })
+    }
+
+    /// Fields of the default partition spec
+    fn default_partition_spec(metadata: &Value) -> Vec<&Value> {
+        let default_id = metadata.get("default-spec-id").and_then(|v| v.as_i64());
+        let specs = metadata.get("partition-specs").and_then(|v| v.as_array());
+
+        let fields = match (specs, default_id) {
+            (Some(specs), Some(id)) => specs
+                .iter()
+                .find(|s| s.get("spec-id").and_then(|v| v.as_i64()) == Some(id))
+                .and_then(|s| s.get("fields")),
+            _ => metadata.get("partition-spec"),
+        };
+        fields
+            .and_then(|f| f.as_array())
+            .map(|f| f.iter().collect())
+            .unwrap_or_default()
+    }
+
+    /// Summary of the current snapshot
+    fn current_snapshot_summary(metadata: &Value) -> Option<&Value> {
+        let current = metadata.get("current-snapshot-id")?.as_i64()?;
+        metadata
+            .get("snapshots")?
+            .as_array()?
+            .iter()
+            .find(|s| s.get("snapshot-id").and_then(|v| v.as_i64()) == Some(current))?
+            .get("summary")
+    }
+
+    /// Column names of top-level and struct fields by field id
+    fn field_names(schema: &Value) -> HashMap<i64, String> {
+        fn collect(fields: &Value, prefix: &str, out: &mut HashMap<i64, String>) {
+            for field in fields.as_array().into_iter().flatten() {
+                let (Some(id), Some(name)) = (
+                    field.get("id").and_then(|v| v.as_i64()),
+                    field.get("name").and_then(|v| v.as_str()),
+                ) else {
+                    continue;
+                };
+                let path = format!("{}{}", prefix, name);
+                if let Some(children) = field.get("type").and_then(|t| t.get("fields")) {
+                    collect(children, &format!("{}.", path), out);
+                }
+                out.insert(id, path);
+            }
+        }
+
+        let mut names = HashMap::new();
+        if let Some(fields) = schema.get("fields") {
+            collect(fields, "", &mut names);
+        }
+        names
+    }
+
+    /// Convert the fields of a struct type
+    ///
+    /// `path` is the Iceberg field path of the struct, used to look up ODCS
+    /// property ids; list elements appear in it as `element`.
+    fn struct_fields(
+        struct_type: &Value,
+        path: &str,
+        property_ids: &HashMap<&str, &str>,
+    ) -> Result<Vec<LakehouseField>, ImportError> {
+        let fields = struct_type
+            .get("fields")
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| ImportError::ParseError("Struct type missing fields".to_string()))?;
+
+        let mut result = Vec::new();
+        for field in fields {
+            let name = field
+                .get("name")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| ImportError::ParseError("Field missing name".to_string()))?;
+            let field_path = if path.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}.{}", path, name)
+            };
+            let field_type = field
+                .get("type")
+                .ok_or_else(|| ImportError::ParseError(format!("Field '{}' missing type", name)))?;
+            let required = field
+                .get("required")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+
+            let mut lakehouse_field = LakehouseField::new(
+                name,
+                Self::iceberg_type(field_type, &field_path, property_ids)?,
+                !required,
+            );
+            lakehouse_field.physical_type = field_type.as_str().map(|s| s.to_string());
+            lakehouse_field.description = field
+                .get("doc")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string();
+            lakehouse_field.id = property_ids
+                .get(field_path.as_str())
+                .map(|id| id.to_string());
+            if let Some(id) = field.get("id") {
+                lakehouse_field
+                    .custom_properties
+                    .insert("icebergFieldId".to_string(), id.clone());
+            }
+            result.push(lakehouse_field);
+        }
+        Ok(result)
+    }
+
+    /// Convert an Iceberg type
+    fn iceberg_type(
+        field_type: &Value,
+        path: &str,
+        property_ids: &HashMap<&str, &str>,
+    ) -> Result<LakehouseType, ImportError> {
+        if let Some(name) = field_type.as_str() {
+            return Ok(LakehouseType::Primitive(Self::primitive_type(name)));
+        }
+
+        match field_type.get("type").and_then(|v| v.as_str()) {
+            Some("struct") => Ok(LakehouseType::Struct(Self::struct_fields(
+                field_type,
+                path,
+                property_ids,
+            )?)),
+            Some("list") => {
+                let element = field_type.get("element").ok_or_else(|| {
+                    ImportError::ParseError(format!("List '{}' missing element", path))
+                })?;
+                let element_path = format!("{}.element", path);
+                Ok(LakehouseType::List(Box::new(Self::iceberg_type(
+                    element,
+                    &element_path,
+                    property_ids,
+                )?)))
+            }
+            Some("map") => {
+                let (Some(key), Some(value)) = (field_type.get("key"), field_type.get("value"))
+                else {
+                    return Err(ImportError::ParseError(format!(
+                        "Map '{}' missing key or value",
+                        path
+                    )));
+                };
+                Ok(LakehouseType::Map(
+                    Box::new(Self::iceberg_type(
+                        key,
+                        &format!("{}.key", path),
+                        property_ids,
+                    )?),
+                    Box::new(Self::iceberg_type(
+                        value,
+                        &format!("{}.value", path),
+                        property_ids,
+                    )?),
+                ))
+            }
+            other => Err(ImportError::ParseError(format!(
+                "Unsupported Iceberg type {:?} for '{}'",
+                other, path
+            ))),
+        }
+    }
+
+    /// Map an Iceberg primitive type to a SQL type name
+    fn primitive_type(name: &str) -> String {
+        match name {
+            "boolean" => "BOOLEAN".to_string(),
+            "int" => "INT".to_string(),
+            "long" => "BIGINT".to_string(),
+            "float" => "FLOAT".to_string(),
+            "double" => "DOUBLE".to_string(),
+            "date" => "DATE".to_string(),
+            "time" => "TIME".to_string(),
+            "timestamp" | "timestamp_ns" => "TIMESTAMP_NTZ".to_string(),
+            "timestamptz" | "timestamptz_ns" => "TIMESTAMP".to_string(),
+            "string" => "STRING".to_string(),
+            "uuid" => "UUID".to_string(),
+            "binary" => "BINARY".to_string(),
+            other if other.starts_with("fixed[") => "BINARY".to_string(),
+            other => decimal_type_name(other).unwrap_or_else(|| other.to_uppercase()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const METADATA: &str = r#"{
+      "format-version": 2,
+      "table-uuid": "9c12d441-03fe-4693-9a96-a0705ddf69c1",
+      "location": "s3://warehouse/sales/orders",
+      "last-updated-ms": 1700000000000,
+      "current-schema-id": 1,
+      "schemas": [
+        {"schema-id": 0, "type": "struct", "fields": [
+          {"id": 1, "name": "order_id", "required": true, "type": "int"}
+        ]},
+        {"schema-id": 1, "type": "struct", "fields": [
+          {"id": 1, "name": "order_id", "required": true, "type": "long", "doc": "Order key"},
+          {"id": 2, "name": "amount", "required": false, "type": "decimal(12, 2)"},
+          {"id": 3, "name": "created_at", "required": true, "type": "timestamptz"},
+          {"id": 4, "name": "customer", "required": false, "type": {
+            "type": "struct", "fields": [
+              {"id": 5, "name": "name", "required": false, "type": "string"}
+            ]}},
+          {"id": 6, "name": "lines", "required": false, "type": {
+            "type": "list", "element-id": 7, "element-required": true, "element": {
+              "type": "struct", "fields": [
+                {"id": 8, "name": "sku", "required": true, "type": "string"}
+              ]}}},
+          {"id": 9, "name": "region", "required": true, "type": "string"}
+        ]}
+      ],
+      "default-spec-id": 0,
+      "partition-specs": [{"spec-id": 0, "fields": [
+        {"source-id": 9, "field-id": 1000, "name": "region", "transform": "identity"},
+        {"source-id": 3, "field-id": 1001, "name": "created_at_day", "transform": "day"}
+      ]}],
+      "properties": {
+        "comment": "Customer orders",
+        "write.format.default": "parquet",
+        "odcs.column.sku_id": "lines.element.sku"
+      },
+      "current-snapshot-id": 42,
+      "snapshots": [{"snapshot-id": 42, "summary": {"total-records": "10"}}]
+    }"#;
+
+    #[test]
+    fn test_import_iceberg_metadata() {
+        let result = IcebergMetadataImporter::new().import(METADATA).unwrap();
+        assert!(result.errors.is_empty());
+        let table = &result.tables[0];
+        assert_eq!(table.name.as_deref(), Some("orders"));
+        assert_eq!(
+            table.description,
+            Some(json!({ "purpose": "Customer orders" }))
+        );
+
+        let column = |name: &str| table.columns.iter().find(|c| c.name == name).unwrap();
+        assert_eq!(column("order_id").data_type, "BIGINT");
+        assert!(!column("order_id").nullable);
+        assert_eq!(column("order_id").description.as_deref(), Some("Order key"));
+        assert_eq!(column("amount").data_type, "DECIMAL(12,2)");
+        assert_eq!(column("customer").data_type, "OBJECT");
+        assert_eq!(column("customer.name").data_type, "STRING");
+        assert_eq!(column("lines").data_type, "ARRAY<OBJECT>");
+        assert_eq!(column("lines.[].sku").id.as_deref(), Some("sku_id"));
+
+        assert!(column("region").partitioned);
+        assert_eq!(column("region").partition_key_position, Some(1));
+        let created_at = column("created_at");
+        assert_eq!(created_at.partition_key_position, Some(2));
+        assert_eq!(created_at.custom_properties["partitionTransform"], "day");
+
+        assert_eq!(table.odcs_metadata["format"], "iceberg");
+        assert_eq!(
+            table.odcs_metadata["tableProperties"]["write.format.default"],
+            "parquet"
+        );
+        assert_eq!(
+            table.odcs_metadata["snapshotSummary"]["total-records"],
+            "10"
+        );
+    }
+
+    #[test]
+    fn test_import_path_uses_latest_metadata() {
+        let temp = TempDir::new().unwrap();
+        let metadata_dir = temp.path().join("orders").join("metadata");
+        std::fs::create_dir_all(&metadata_dir).unwrap();
+        let old = METADATA.replace(r#""current-schema-id": 1"#, r#""current-schema-id": 0"#);
+        std::fs::write(metadata_dir.join("00000-a.metadata.json"), old).unwrap();
+        std::fs::write(metadata_dir.join("00001-b.metadata.json"), METADATA).unwrap();
+
+        let result = IcebergMetadataImporter::new()
+            .import_path(&temp.path().join("orders"))
+            .unwrap();
+        assert_eq!(result.tables[0].columns.len(), 8);
+    }
+}
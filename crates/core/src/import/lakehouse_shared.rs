@@ -0,0 +1,204 @@
+//! Shared schema handling for the lakehouse table importers
+//!
+//! The Parquet, Iceberg and Delta Lake importers convert their native schemas
+//! into [`LakehouseField`]s, which are flattened into columns the same way the
+//! ODCS importer flattens nested properties: struct children use dot notation
+//! (`address.city`) and fields of list elements use array notation
+//! (`items.[].sku`).
+
+use crate::import::odcs_shared::column_to_column_data;
+use crate::import::{ImportError, ImportResult, TableData};
+use crate::models::{Column, Table};
+use crate::validation::input::{validate_column_name, validate_table_name};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use tracing::warn;
+
+/// Custom column property holding a non-identity partition transform
+///
+/// Matches the property read when creating Iceberg tables from contracts.
+pub(crate) const PARTITION_TRANSFORM_PROPERTY: &str = "partitionTransform";
+
+/// Field of a lakehouse table schema
+#[derive(Debug, Clone)]
+pub(crate) struct LakehouseField {
+    pub name: String,
+    pub field_type: LakehouseType,
+    pub nullable: bool,
+    pub description: String,
+    pub physical_type: Option<String>,
+    pub physical_name: Option<String>,
+    pub id: Option<String>,
+    pub custom_properties: HashMap<String, Value>,
+}
+
+/// Type of a lakehouse schema field
+#[derive(Debug, Clone)]
+pub(crate) enum LakehouseType {
+    /// Scalar type, as a SQL type name (`BIGINT`, `DECIMAL(10,2)`, ...)
+    Primitive(String),
+    Struct(Vec<LakehouseField>),
+    List(Box<LakehouseType>),
+    Map(Box<LakehouseType>, Box<LakehouseType>),
+}
+
+impl LakehouseField {
+    pub fn new(name: impl Into<String>, field_type: LakehouseType, nullable: bool) -> Self {
+        Self {
+            name: name.into(),
+            field_type,
+            nullable,
+            description: String::new(),
+            physical_type: None,
+            physical_name: None,
+            id: None,
+            custom_properties: HashMap::new(),
+        }
+    }
+}
+
+impl LakehouseType {
+    /// Type name used in column data types, e.g. `ARRAY<STRUCT<id: BIGINT>>`
+    pub fn type_name(&self) -> String {
+        match self {
+            LakehouseType::Primitive(name) => name.clone(),
+            LakehouseType::Struct(fields) => format!(
+                "STRUCT<{}>",
+                fields
+                    .iter()
+                    .map(|f| format!("{}: {}", f.name, f.field_type.type_name()))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            LakehouseType::List(element) => format!("ARRAY<{}>", element.type_name()),
+            LakehouseType::Map(key, value) => {
+                format!("MAP<{}, {}>", key.type_name(), value.type_name())
+            }
+        }
+    }
+}
+
+/// Flatten schema fields into columns
+pub(crate) fn fields_to_columns(fields: &[LakehouseField]) -> Vec<Column> {
+    let mut columns = Vec::new();
+    for field in fields {
+        push_columns(field, "", &mut columns);
+    }
+    columns
+}
+
+fn push_columns(field: &LakehouseField, prefix: &str, columns: &mut Vec<Column>) {
+    if prefix.is_empty()
+        && let Err(e) = validate_column_name(&field.name)
+    {
+        warn!("Column name validation warning for '{}': {}", field.name, e);
+    }
+
+    let name = format!("{}{}", prefix, field.name);
+    let data_type = match field.field_type {
+        LakehouseType::Struct(_) => "OBJECT".to_string(),
+        LakehouseType::List(ref element) if matches!(**element, LakehouseType::Struct(_)) => {
+            "ARRAY<OBJECT>".to_string()
+        }
+        ref other => other.type_name(),
+    };
+
+    columns.push(Column {
+        id: field.id.clone(),
+        name: name.clone(),
+        data_type,
+        physical_type: field.physical_type.clone(),
+        physical_name: field.physical_name.clone(),
+        nullable: field.nullable,
+        description: field.description.clone(),
+        custom_properties: field.custom_properties.clone(),
+        ..Default::default()
+    });
+
+    match field.field_type {
+        LakehouseType::Struct(ref children) => {
+            for child in children {
+                push_columns(child, &format!("{}.", name), columns);
+            }
+        }
+        LakehouseType::List(ref element) => {
+            if let LakehouseType::Struct(ref children) = **element {
+                for child in children {
+                    push_columns(child, &format!("{}.[].", name), columns);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Mark partition columns in partition key order
+///
+/// `partitions` holds `(column, transform)` pairs; identity partitions have no
+/// transform. Returns the partition columns that do not exist.
+pub(crate) fn mark_partition_columns(
+    columns: &mut [Column],
+    partitions: &[(String, Option<String>)],
+) -> Vec<String> {
+    let mut missing = Vec::new();
+    for (position, (name, transform)) in partitions.iter().enumerate() {
+        let Some(column) = columns.iter_mut().find(|c| c.name == *name) else {
+            missing.push(name.clone());
+            continue;
+        };
+        column.partitioned = true;
+        column.partition_key_position = Some(position as i32 + 1);
+        if let Some(transform) = transform {
+            column
+                .custom_properties
+                .insert(PARTITION_TRANSFORM_PROPERTY.to_string(), json!(transform));
+        }
+    }
+    missing
+}
+
+/// Build a single-table import result
+pub(crate) fn table_result(
+    name: &str,
+    description: Option<String>,
+    columns: Vec<Column>,
+    metadata: HashMap<String, Value>,
+    warnings: Vec<String>,
+) -> Result<ImportResult, ImportError> {
+    if columns.is_empty() {
+        return Err(ImportError::ValidationError(format!(
+            "Table '{}' has no columns",
+            name
+        )));
+    }
+    if let Err(e) = validate_table_name(name) {
+        warn!("Table name validation warning for '{}': {}", name, e);
+    }
+
+    let table = TableData {
+        table_index: 0,
+        id: Some(Table::generate_id(name, None, None, None).to_string()),
+        name: Some(name.to_string()),
+        description: description.map(|purpose| json!({ "purpose": purpose })),
+        columns: columns.iter().map(column_to_column_data).collect(),
+        odcs_metadata: metadata,
+        ..Default::default()
+    };
+
+    Ok(ImportResult {
+        tables: vec![table],
+        tables_requiring_name: Vec::new(),
+        errors: warnings.into_iter().map(ImportError::ParseError).collect(),
+        ai_suggestions: None,
+    })
+}
+
+/// Decimal type name from a `decimal(p,s)` type string
+pub(crate) fn decimal_type_name(type_name: &str) -> Option<String> {
+    let params = type_name
+        .trim()
+        .strip_prefix("decimal(")?
+        .strip_suffix(')')?
+        .replace(' ', "");
+    Some(format!("DECIMAL({})", params))
+}
@@ -6,6 +6,7 @@
 //! - JSON Schema
 //! - AVRO
 //! - Protobuf
+//! - Lakehouse tables (Iceberg metadata; Parquet footers and Delta Lake logs with `lakehouse-import`)
 //! - Decision (MADR-compliant decision records)
 //! - Knowledge (Knowledge Base articles)
 
@@ -14,16 +15,22 @@ pub mod avro;
 pub mod bpmn;
 pub mod cads;
 pub mod decision;
+#[cfg(feature = "lakehouse-import")]
+pub mod delta_log;
 #[cfg(feature = "dmn")]
 pub mod dmn;
+pub mod iceberg_metadata;
 pub mod json_schema;
 pub mod knowledge;
+mod lakehouse_shared;
 pub mod odcl;
 pub mod odcs;
 pub mod odcs_shared;
 pub mod odps;
 #[cfg(feature = "openapi")]
 pub mod openapi;
+#[cfg(feature = "lakehouse-import")]
+pub mod parquet_file;
 pub mod protobuf;
 pub mod sql;
 
@@ -346,12 +353,17 @@ impl Default for ColumnData {
 pub use avro::AvroImporter;
 pub use cads::CADSImporter;
 pub use decision::DecisionImporter;
+#[cfg(feature = "lakehouse-import")]
+pub use delta_log::DeltaLogImporter;
+pub use iceberg_metadata::IcebergMetadataImporter;
 pub use json_schema::JSONSchemaImporter;
 pub use knowledge::KnowledgeImporter;
 pub use odcl::ODCLImporter;
 pub use odcs::ODCSImporter;
 pub use odcs_shared::ParserError;
 pub use odps::ODPSImporter;
+#[cfg(feature = "lakehouse-import")]
+pub use parquet_file::ParquetImporter;
 pub use protobuf::ProtobufImporter;
 pub use sql::SQLImporter;
 
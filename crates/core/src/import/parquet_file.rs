@@ -0,0 +1,428 @@
+//! Parquet file footer importer
+//!
+//! Reads the schema from a Parquet file footer, without reading any data, and
+//! produces a table with its columns. Types come from the Arrow schema stored
+//! in the footer (or derived from the Parquet schema), nullability from the
+//! field repetition, and the Parquet physical type of each scalar column is
+//! kept as its `physicalType`.
+//!
+//! Hive-style `key=value` directories between the table directory and the file
+//! are imported as partition columns. Row counts, the writer and the footer's
+//! key-value metadata are captured in the table's `odcs_metadata`.
+
+use crate::import::lakehouse_shared::{
+    LakehouseField, LakehouseType, fields_to_columns, mark_partition_columns, table_result,
+};
+use crate::import::{ImportError, ImportResult};
+use crate::models::Column;
+use arrow::datatypes::{DataType, Field};
+use parquet::basic::LogicalType;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::schema::types::Type as ParquetType;
+use serde_json::{Map, Value, json};
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// Footer metadata key holding the serialized Arrow schema
+const ARROW_SCHEMA_KEY: &str = "ARROW:schema";
+
+/// Value Hive uses for null partition values
+const HIVE_DEFAULT_PARTITION: &str = "__HIVE_DEFAULT_PARTITION__";
+
+/// Importer for Parquet file schemas.
+#[derive(Default)]
+pub struct ParquetImporter;
+
+impl ParquetImporter {
+    /// Create a new Parquet importer.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use data_modelling_core::import::parquet_file::ParquetImporter;
+    ///
+    /// let importer = ParquetImporter::new();
+    /// ```
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Import the schema of a Parquet file or a directory of Parquet files.
+    ///
+    /// For a directory the first data file (in path order) is read, the table
+    /// is named after the directory and `key=value` subdirectories become
+    /// partition columns. For a file inside `key=value` directories the table
+    /// is named after the directory above them, otherwise after the file.
+    pub fn import_path(&self, path: &Path) -> Result<ImportResult, ImportError> {
+        let (file, table_dir) = if path.is_dir() {
+            let file = Self::first_data_file(path)?.ok_or_else(|| {
+                ImportError::IoError(format!("No Parquet files found in {}", path.display()))
+            })?;
+            (file, Some(path.to_path_buf()))
+        } else {
+            let table_dir = path
+                .ancestors()
+                .skip(1)
+                .find(|dir| Self::partition_segment(dir).is_none())
+                .filter(|dir| Some(*dir) != path.parent())
+                .map(Path::to_path_buf);
+            (path.to_path_buf(), table_dir)
+        };
+
+        let name = match table_dir {
+            Some(ref dir) => dir.file_name(),
+            None => file.file_stem(),
+        }
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "parquet_table".to_string());
+
+        // Partition directories between the table directory and the file
+        let partitions: Vec<(String, String)> = match table_dir {
+            Some(ref dir) => file
+                .parent()
+                .and_then(|parent| parent.strip_prefix(dir).ok())
+                .map(|relative| {
+                    relative
+                        .iter()
+                        .filter_map(|segment| Self::partition_segment(Path::new(segment)))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            None => Vec::new(),
+        };
+
+        self.import_file(&file, &name, &partitions)
+    }
+
+    fn import_file(
+        &self,
+        path: &Path,
+        name: &str,
+        partitions: &[(String, String)],
+    ) -> Result<ImportResult, ImportError> {
+        let file = File::open(path).map_err(|e| {
+            ImportError::IoError(format!("Failed to open {}: {}", path.display(), e))
+        })?;
+        let reader = SerializedFileReader::new(file).map_err(|e| {
+            ImportError::ParseError(format!(
+                "Failed to read Parquet footer of {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        let metadata = reader.metadata();
+        let file_metadata = metadata.file_metadata();
+
+        let arrow_schema = parquet::arrow::parquet_to_arrow_schema(
+            file_metadata.schema_descr(),
+            file_metadata.key_value_metadata(),
+        )
+        .map_err(|e| ImportError::ParseError(format!("Unsupported Parquet schema: {}", e)))?;
+
+        let root = file_metadata.schema_descr().root_schema();
+        let fields: Vec<LakehouseField> = arrow_schema
+            .fields()
+            .iter()
+            .map(|field| Self::arrow_field(field, Self::child(root, field.name())))
+            .collect();
+        let mut columns = fields_to_columns(&fields);
+
+        // Hive partition values are not stored in the files
+        for (key, value) in partitions {
+            if !columns.iter().any(|c| c.name == *key) {
+                columns.push(Column {
+                    name: key.clone(),
+                    data_type: Self::partition_value_type(value).to_string(),
+                    nullable: value == HIVE_DEFAULT_PARTITION,
+                    ..Default::default()
+                });
+            }
+        }
+        let partition_columns: Vec<(String, Option<String>)> = partitions
+            .iter()
+            .map(|(key, _)| (key.clone(), None))
+            .collect();
+        mark_partition_columns(&mut columns, &partition_columns);
+
+        let mut odcs_metadata = HashMap::new();
+        odcs_metadata.insert("format".to_string(), json!("parquet"));
+        odcs_metadata.insert("numRows".to_string(), json!(file_metadata.num_rows()));
+        odcs_metadata.insert("numRowGroups".to_string(), json!(metadata.num_row_groups()));
+        if let Some(created_by) = file_metadata.created_by() {
+            odcs_metadata.insert("createdBy".to_string(), json!(created_by));
+        }
+        let key_values: Map<String, Value> = file_metadata
+            .key_value_metadata()
+            .into_iter()
+            .flatten()
+            .filter(|kv| kv.key != ARROW_SCHEMA_KEY)
+            .map(|kv| (kv.key.clone(), json!(kv.value)))
+            .collect();
+        if !key_values.is_empty() {
+            odcs_metadata.insert("parquetMetadata".to_string(), Value::Object(key_values));
+        }
+
+        info!(
+            "Parsed Parquet footer: {} with {} columns",
+            name,
+            columns.len()
+        );
+        table_result(name, None, columns, odcs_metadata, Vec::new())
+    }
+
+    /// First Parquet data file below a directory, skipping `_` and `.` entries
+    fn first_data_file(dir: &Path) -> Result<Option<PathBuf>, ImportError> {
+        let mut entries: Vec<PathBuf> = std::fs::read_dir(dir)
+            .map_err(|e| ImportError::IoError(format!("Failed to read {}: {}", dir.display(), e)))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| !n.starts_with('_') && !n.starts_with('.'))
+            })
+            .collect();
+        entries.sort();
+
+        for entry in entries {
+            if entry.is_dir() {
+                if let Some(file) = Self::first_data_file(&entry)? {
+                    return Ok(Some(file));
+                }
+            } else if entry.extension().is_some_and(|e| e == "parquet") {
+                return Ok(Some(entry));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Key and value of a Hive-style `key=value` path segment
+    fn partition_segment(path: &Path) -> Option<(String, String)> {
+        let segment = path.file_name()?.to_str()?;
+        let (key, value) = segment.split_once('=')?;
+        (!key.is_empty()).then(|| (key.to_string(), value.to_string()))
+    }
+
+    /// Type of a partition column inferred from a partition value
+    fn partition_value_type(value: &str) -> &'static str {
+        if value.parse::<i64>().is_ok() {
+            "BIGINT"
+        } else if chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok() {
+            "DATE"
+        } else {
+            "STRING"
+        }
+    }
+
+    /// Child of a Parquet group type by name
+    fn child<'a>(group: &'a ParquetType, name: &str) -> Option<&'a ParquetType> {
+        if !group.is_group() {
+            return None;
+        }
+        group
+            .get_fields()
+            .iter()
+            .find(|f| f.name() == name)
+            .map(|f| f.as_ref())
+    }
+
+    /// Convert an Arrow field, with its Parquet type where it maps one to one
+    fn arrow_field(field: &Field, parquet_type: Option<&ParquetType>) -> LakehouseField {
+        let primitive = parquet_type.filter(|t| t.is_primitive());
+        let is_uuid = primitive
+            .is_some_and(|t| matches!(t.get_basic_info().logical_type(), Some(LogicalType::Uuid)));
+
+        let field_type = if is_uuid {
+            LakehouseType::Primitive("UUID".to_string())
+        } else {
+            Self::arrow_type(field.data_type(), parquet_type)
+        };
+        let mut lakehouse_field =
+            LakehouseField::new(field.name(), field_type, field.is_nullable());
+        lakehouse_field.physical_type = primitive.map(|t| t.get_physical_type().to_string());
+        lakehouse_field
+    }
+
+    /// Convert an Arrow data type
+    fn arrow_type(data_type: &DataType, parquet_type: Option<&ParquetType>) -> LakehouseType {
+        match data_type {
+            DataType::Struct(fields) => LakehouseType::Struct(
+                fields
+                    .iter()
+                    .map(|f| {
+                        Self::arrow_field(f, parquet_type.and_then(|t| Self::child(t, f.name())))
+                    })
+                    .collect(),
+            ),
+            DataType::List(element)
+            | DataType::LargeList(element)
+            | DataType::ListView(element)
+            | DataType::LargeListView(element)
+            | DataType::FixedSizeList(element, _) => {
+                LakehouseType::List(Box::new(Self::arrow_type(element.data_type(), None)))
+            }
+            DataType::Map(entries, _) => match entries.data_type() {
+                DataType::Struct(kv) if kv.len() == 2 => LakehouseType::Map(
+                    Box::new(Self::arrow_type(kv[0].data_type(), None)),
+                    Box::new(Self::arrow_type(kv[1].data_type(), None)),
+                ),
+                _ => LakehouseType::Primitive("MAP".to_string()),
+            },
+            DataType::Dictionary(_, value) => Self::arrow_type(value, parquet_type),
+            other => LakehouseType::Primitive(Self::primitive_type(other)),
+        }
+    }
+
+    /// Map a scalar Arrow type to a SQL type name
+    fn primitive_type(data_type: &DataType) -> String {
+        match data_type {
+            DataType::Boolean => "BOOLEAN".to_string(),
+            DataType::Int8 => "TINYINT".to_string(),
+            DataType::Int16 | DataType::UInt8 => "SMALLINT".to_string(),
+            DataType::Int32 | DataType::UInt16 => "INT".to_string(),
+            DataType::Int64 | DataType::UInt32 | DataType::UInt64 => "BIGINT".to_string(),
+            DataType::Float16 | DataType::Float32 => "FLOAT".to_string(),
+            DataType::Float64 => "DOUBLE".to_string(),
+            DataType::Decimal128(precision, scale) | DataType::Decimal256(precision, scale) => {
+                format!("DECIMAL({},{})", precision, scale)
+            }
+            DataType::Date32 | DataType::Date64 => "DATE".to_string(),
+            DataType::Time32(_) | DataType::Time64(_) => "TIME".to_string(),
+            DataType::Timestamp(_, Some(_)) => "TIMESTAMP".to_string(),
+            DataType::Timestamp(_, None) => "TIMESTAMP_NTZ".to_string(),
+            DataType::Duration(_) | DataType::Interval(_) => "INTERVAL".to_string(),
+            DataType::Binary
+            | DataType::LargeBinary
+            | DataType::BinaryView
+            | DataType::FixedSizeBinary(_) => "BINARY".to_string(),
+            _ => "STRING".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{ArrayRef, Int64Array, StringArray, StructArray, TimestampMicrosecondArray};
+    use arrow::datatypes::{Schema, TimeUnit};
+    use arrow::record_batch::RecordBatch;
+    use parquet::arrow::ArrowWriter;
+    use parquet::file::properties::WriterProperties;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn write_orders(path: &Path) {
+        let customer_fields = vec![Field::new("name", DataType::Utf8, true)];
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("order_id", DataType::Int64, false),
+            Field::new(
+                "created_at",
+                DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+                true,
+            ),
+            Field::new(
+                "customer",
+                DataType::Struct(customer_fields.clone().into()),
+                true,
+            ),
+        ]));
+        let customer = StructArray::new(
+            customer_fields.into(),
+            vec![Arc::new(StringArray::from(vec![Some("Ada")])) as ArrayRef],
+            None,
+        );
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int64Array::from(vec![1])),
+                Arc::new(TimestampMicrosecondArray::from(vec![0]).with_timezone("UTC")),
+                Arc::new(customer),
+            ],
+        )
+        .unwrap();
+
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let properties = WriterProperties::builder()
+            .set_key_value_metadata(Some(vec![parquet::file::metadata::KeyValue::new(
+                "writer.job".to_string(),
+                "nightly".to_string(),
+            )]))
+            .build();
+        let mut writer =
+            ArrowWriter::try_new(File::create(path).unwrap(), schema, Some(properties)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+    }
+
+    #[test]
+    fn test_import_parquet_file() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("orders.parquet");
+        write_orders(&path);
+
+        let result = ParquetImporter::new().import_path(&path).unwrap();
+        let table = &result.tables[0];
+        assert_eq!(table.name.as_deref(), Some("orders"));
+
+        let column = |name: &str| table.columns.iter().find(|c| c.name == name).unwrap();
+        assert_eq!(column("order_id").data_type, "BIGINT");
+        assert_eq!(column("order_id").physical_type.as_deref(), Some("INT64"));
+        assert!(!column("order_id").nullable);
+        assert_eq!(column("created_at").data_type, "TIMESTAMP");
+        assert_eq!(column("customer").data_type, "OBJECT");
+        assert_eq!(column("customer.name").data_type, "STRING");
+        assert_eq!(
+            column("customer.name").physical_type.as_deref(),
+            Some("BYTE_ARRAY")
+        );
+
+        assert_eq!(table.odcs_metadata["numRows"], 1);
+        assert_eq!(
+            table.odcs_metadata["parquetMetadata"]["writer.job"],
+            "nightly"
+        );
+        assert!(
+            !table.odcs_metadata["parquetMetadata"]
+                .as_object()
+                .unwrap()
+                .contains_key(ARROW_SCHEMA_KEY)
+        );
+    }
+
+    #[test]
+    fn test_import_hive_partitioned_directory() {
+        let temp = TempDir::new().unwrap();
+        let table_dir = temp.path().join("orders");
+        write_orders(
+            &table_dir
+                .join("order_date=2024-01-02")
+                .join("region=emea")
+                .join("part-0.parquet"),
+        );
+
+        let result = ParquetImporter::new().import_path(&table_dir).unwrap();
+        let table = &result.tables[0];
+        assert_eq!(table.name.as_deref(), Some("orders"));
+
+        let order_date = table
+            .columns
+            .iter()
+            .find(|c| c.name == "order_date")
+            .unwrap();
+        assert_eq!(order_date.data_type, "DATE");
+        assert_eq!(order_date.partition_key_position, Some(1));
+        let region = table.columns.iter().find(|c| c.name == "region").unwrap();
+        assert_eq!(region.data_type, "STRING");
+        assert_eq!(region.partition_key_position, Some(2));
+
+        // A file in the same layout names the table after the directory above its partitions
+        let file = table_dir
+            .join("order_date=2024-01-02")
+            .join("region=emea")
+            .join("part-0.parquet");
+        let result = ParquetImporter::new().import_path(&file).unwrap();
+        assert_eq!(result.tables[0].name.as_deref(), Some("orders"));
+        assert_eq!(result.tables[0].columns.len(), 6);
+    }
+}
@@ -30,6 +30,88 @@ pub struct InferenceConfig {
 
     /// Minimum confidence threshold for format detection (0.0 - 1.0)
     pub format_confidence_threshold: f64,
+
+    /// Maximum distinct values for a string field to be treated as an enumeration
+    /// (0 = disable enum detection)
+    #[serde(default = "default_max_enum_values")]
+    pub max_enum_values: usize,
+
+    /// Detect candidate primary keys and collect value sketches for foreign key discovery
+    #[serde(default = "default_true")]
+    pub detect_keys: bool,
+
+    /// Maximum number of fields in a composite candidate key (1 = single fields only)
+    #[serde(default = "default_max_key_columns")]
+    pub max_key_columns: usize,
+
+    /// Number of records retained for composite key detection
+    #[serde(default = "default_key_sample_size")]
+    pub key_sample_size: usize,
+
+    /// Detect discriminated unions (records whose shape depends on a field such as `type`)
+    #[serde(default = "default_true")]
+    pub detect_variants: bool,
+
+    /// Field that discriminates record variants (overrides automatic detection)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub discriminator_field: Option<String>,
+
+    /// Top-level field names considered as discriminators during automatic detection
+    #[serde(default = "default_discriminator_candidates")]
+    pub discriminator_candidates: Vec<String>,
+
+    /// Maximum number of distinct discriminator values before a field is rejected
+    #[serde(default = "default_max_variants")]
+    pub max_variants: usize,
+
+    /// Similarity (0.0 - 1.0) at or above which variant schemas are merged together
+    #[serde(default = "default_variant_similarity_threshold")]
+    pub variant_similarity_threshold: f64,
+}
+
+fn default_max_enum_values() -> usize {
+    10
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_max_key_columns() -> usize {
+    2
+}
+
+fn default_key_sample_size() -> usize {
+    10_000
+}
+
+fn default_discriminator_candidates() -> Vec<String> {
+    [
+        "type",
+        "kind",
+        "_type",
+        "@type",
+        "event",
+        "event_type",
+        "eventType",
+        "record_type",
+        "recordType",
+        "object_type",
+        "objectType",
+        "message_type",
+        "messageType",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect()
+}
+
+fn default_max_variants() -> usize {
+    20
+}
+
+fn default_variant_similarity_threshold() -> f64 {
+    0.8
 }
 
 impl Default for InferenceConfig {
@@ -43,6 +125,15 @@ impl Default for InferenceConfig {
             max_examples: 5,
             assume_nullable: false,
             format_confidence_threshold: 0.9,
+            max_enum_values: default_max_enum_values(),
+            detect_keys: true,
+            max_key_columns: default_max_key_columns(),
+            key_sample_size: default_key_sample_size(),
+            detect_variants: true,
+            discriminator_field: None,
+            discriminator_candidates: default_discriminator_candidates(),
+            max_variants: default_max_variants(),
+            variant_similarity_threshold: default_variant_similarity_threshold(),
         }
     }
 }
@@ -114,6 +205,60 @@ impl InferenceConfigBuilder {
         self
     }
 
+    /// Set the maximum number of distinct values for enum detection (0 = disabled)
+    pub fn max_enum_values(mut self, max: usize) -> Self {
+        self.config.max_enum_values = max;
+        self
+    }
+
+    /// Enable or disable candidate key detection
+    pub fn detect_keys(mut self, detect: bool) -> Self {
+        self.config.detect_keys = detect;
+        self
+    }
+
+    /// Set the maximum number of fields in a composite candidate key
+    pub fn max_key_columns(mut self, max: usize) -> Self {
+        self.config.max_key_columns = max.max(1);
+        self
+    }
+
+    /// Set the number of records retained for composite key detection
+    pub fn key_sample_size(mut self, size: usize) -> Self {
+        self.config.key_sample_size = size;
+        self
+    }
+
+    /// Enable or disable discriminated union detection
+    pub fn detect_variants(mut self, detect: bool) -> Self {
+        self.config.detect_variants = detect;
+        self
+    }
+
+    /// Set the discriminator field explicitly
+    pub fn discriminator_field(mut self, field: impl Into<String>) -> Self {
+        self.config.discriminator_field = Some(field.into());
+        self
+    }
+
+    /// Set the field names considered during automatic discriminator detection
+    pub fn discriminator_candidates(mut self, candidates: Vec<String>) -> Self {
+        self.config.discriminator_candidates = candidates;
+        self
+    }
+
+    /// Set the maximum number of distinct discriminator values
+    pub fn max_variants(mut self, max: usize) -> Self {
+        self.config.max_variants = max;
+        self
+    }
+
+    /// Set the similarity threshold for merging variant schemas
+    pub fn variant_similarity_threshold(mut self, threshold: f64) -> Self {
+        self.config.variant_similarity_threshold = threshold.clamp(0.0, 1.0);
+        self
+    }
+
     /// Build the configuration
     pub fn build(self) -> InferenceConfig {
         self.config
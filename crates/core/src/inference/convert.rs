@@ -0,0 +1,535 @@
+//! Direct conversion of inferred schemas into ODCS tables
+//!
+//! Converts an [`InferredSchema`] into the SDK [`Table`] model without a JSON
+//! Schema round-trip, so the statistics gathered during inference are kept:
+//!
+//! - Example values become `Column::examples`
+//! - Detected string formats become `logicalTypeOptions.format`
+//! - Numeric min/max become quality rules
+//! - Optional and null-bearing fields become nullable
+//! - Low-cardinality string fields become `enum_values`
+//! - The preferred candidate key becomes the primary key
+//! - Discriminated-union variants become separate tables (schema objects)
+
+use std::collections::HashMap;
+
+use serde_json::{Value, json};
+
+use super::formats::Format;
+use super::types::{FieldStats, InferredField, InferredSchema, InferredType};
+use crate::models::odcs::ODCSContract;
+use crate::models::{Column, LogicalTypeOptions, Table};
+
+impl InferredSchema {
+    /// Convert the inferred schema into a [`Table`]
+    ///
+    /// Nested objects are flattened to dot-notation columns (`address.city`) and
+    /// fields of array items use the `[]` segment (`items.[].sku`), matching the
+    /// layout the ODCS converters use for nested properties.
+    pub fn to_table(&self, name: &str) -> Table {
+        let mut columns = Vec::new();
+
+        if let InferredType::Object { properties } = &self.root {
+            for (field_name, field) in properties {
+                self.push_field_columns(
+                    field_name,
+                    &format!("$.{}", field_name),
+                    field,
+                    Some(self.record_count),
+                    &mut columns,
+                );
+            }
+        }
+
+        for (index, column) in columns.iter_mut().enumerate() {
+            column.column_order = index as i32;
+        }
+
+        self.apply_candidate_keys(&mut columns);
+
+        let mut table = Table::new(name.to_string(), columns);
+
+        if let Some(ref description) = self.description {
+            table
+                .odcl_metadata
+                .insert("schemaDescription".to_string(), json!(description));
+        }
+
+        let mut custom_properties = vec![json!({
+            "property": "inferredRecordCount",
+            "value": self.record_count
+        })];
+        if let Some(ref partition) = self.partition {
+            custom_properties.push(json!({
+                "property": "inferredFromPartition",
+                "value": partition
+            }));
+        }
+        table
+            .odcl_metadata
+            .insert("customProperties".to_string(), json!(custom_properties));
+
+        table
+    }
+
+    /// Convert the inferred schema into an ODCS contract
+    ///
+    /// Produces one schema object, or one per variant when a discriminator was
+    /// detected.
+    pub fn to_odcs_contract(&self, name: &str) -> ODCSContract {
+        if self.variants.is_empty() {
+            return ODCSContract::from_table(&self.to_table(name));
+        }
+
+        contract_from_tables(name, &self.to_variant_tables(name))
+    }
+
+    /// Convert each detected variant into its own [`Table`]
+    ///
+    /// Variant tables are named `{name}_{value}` and record the discriminator
+    /// field and values as custom properties. Without a discriminator this
+    /// returns the single table from [`to_table`](Self::to_table).
+    pub fn to_variant_tables(&self, name: &str) -> Vec<Table> {
+        let Some(ref discriminator) = self.discriminator else {
+            return vec![self.to_table(name)];
+        };
+        if self.variants.is_empty() {
+            return vec![self.to_table(name)];
+        }
+
+        self.variants
+            .iter()
+            .map(|variant| {
+                let suffix = variant_table_suffix(&variant.discriminator_values);
+                let mut table = variant.schema.to_table(&format!("{}_{}", name, suffix));
+
+                if let Some(column) = table.columns.iter_mut().find(|c| &c.name == discriminator) {
+                    column.nullable = false;
+                    column.enum_values = variant.discriminator_values.clone();
+                }
+
+                if let Some(Value::Array(properties)) =
+                    table.odcl_metadata.get_mut("customProperties")
+                {
+                    properties.push(json!({
+                        "property": "discriminator",
+                        "value": discriminator
+                    }));
+                    properties.push(json!({
+                        "property": "discriminatorValues",
+                        "value": variant.discriminator_values
+                    }));
+                }
+                table
+            })
+            .collect()
+    }
+
+    /// Mark the preferred candidate key as primary key and single-field keys as unique
+    fn apply_candidate_keys(&self, columns: &mut [Column]) {
+        for key in &self.candidate_keys {
+            if !key.is_composite()
+                && let Some(column) = columns.iter_mut().find(|c| c.name == key.fields[0])
+            {
+                column.unique = true;
+            }
+        }
+
+        let Some(primary) = self.candidate_keys.first() else {
+            return;
+        };
+        for (position, field) in primary.fields.iter().enumerate() {
+            if let Some(column) = columns.iter_mut().find(|c| &c.name == field) {
+                column.primary_key = true;
+                column.nullable = false;
+                if primary.is_composite() {
+                    column.primary_key_position = Some(position as i32 + 1);
+                }
+            }
+        }
+    }
+
+    /// Append the column for a field and, recursively, its nested fields
+    fn push_field_columns(
+        &self,
+        column_name: &str,
+        stats_path: &str,
+        field: &InferredField,
+        parent_occurrences: Option<usize>,
+        columns: &mut Vec<Column>,
+    ) {
+        let stats = self.field_stats.get(stats_path);
+        columns.push(build_column(column_name, field, stats, parent_occurrences));
+
+        let occurrences = stats.map(|s| s.occurrences);
+        match &field.field_type {
+            InferredType::Object { properties } => {
+                for (child_name, child) in properties {
+                    self.push_field_columns(
+                        &format!("{}.{}", column_name, child_name),
+                        &format!("{}.{}", stats_path, child_name),
+                        child,
+                        occurrences,
+                        columns,
+                    );
+                }
+            }
+            InferredType::Array { items } => {
+                if let InferredType::Object { properties } = items.as_ref() {
+                    let items_path = format!("{}[]", stats_path);
+                    let items_occurrences =
+                        self.field_stats.get(&items_path).map(|s| s.occurrences);
+                    for (child_name, child) in properties {
+                        self.push_field_columns(
+                            &format!("{}.[].{}", column_name, child_name),
+                            &format!("{}.{}", items_path, child_name),
+                            child,
+                            items_occurrences,
+                            columns,
+                        );
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Build an ODCS contract named `name` with one schema object per table
+///
+/// The version defaults to `1.0.0` and the status to `draft` unless the
+/// tables carry contract metadata.
+pub fn contract_from_tables(name: &str, tables: &[Table]) -> ODCSContract {
+    let mut contract = ODCSContract::from_tables(tables);
+    contract.name = name.to_string();
+    if contract.version.is_empty() {
+        contract.version = "1.0.0".to_string();
+    }
+    if contract.status.is_none() {
+        contract.status = Some("draft".to_string());
+    }
+    contract
+}
+
+/// Copy field descriptions from a JSON Schema onto columns without one
+///
+/// Used to carry LLM-refined descriptions over to tables converted from the
+/// inferred schema. Variant tables from
+/// [`to_variant_tables`](InferredSchema::to_variant_tables) are matched to the
+/// schema's `oneOf` entries in order.
+pub fn apply_schema_descriptions(tables: &mut [Table], json_schema: &Value) {
+    if tables.len() > 1 {
+        let variants = json_schema["oneOf"]
+            .as_array()
+            .map(|v| v.as_slice())
+            .unwrap_or_default();
+        for (table, variant_schema) in tables.iter_mut().zip(variants) {
+            apply_table_descriptions(table, variant_schema);
+        }
+    } else if let Some(table) = tables.first_mut() {
+        apply_table_descriptions(table, json_schema);
+    }
+}
+
+fn apply_table_descriptions(table: &mut Table, json_schema: &Value) {
+    fn collect(schema: &Value, prefix: &str, out: &mut HashMap<String, String>) {
+        let Some(properties) = schema.get("properties").and_then(|p| p.as_object()) else {
+            return;
+        };
+        for (name, prop) in properties {
+            let path = if prefix.is_empty() {
+                name.clone()
+            } else {
+                format!("{}.{}", prefix, name)
+            };
+            if let Some(desc) = prop.get("description").and_then(|d| d.as_str()) {
+                out.insert(path.clone(), desc.to_string());
+            }
+            collect(prop, &path, out);
+            if let Some(items) = prop.get("items") {
+                collect(items, &format!("{}.[]", path), out);
+            }
+        }
+    }
+
+    let mut descriptions = HashMap::new();
+    collect(json_schema, "", &mut descriptions);
+
+    for column in &mut table.columns {
+        if column.description.is_empty()
+            && let Some(desc) = descriptions.get(&column.name)
+        {
+            column.description = desc.clone();
+        }
+    }
+}
+
+/// Table name suffix for a variant (`["order.created"]` -> `order_created`)
+fn variant_table_suffix(values: &[String]) -> String {
+    let suffix: String = values
+        .join("_")
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let suffix = suffix.trim_matches('_');
+    if suffix.is_empty() {
+        "variant".to_string()
+    } else {
+        suffix.to_string()
+    }
+}
+
+/// Column name for an inference field path (`$.items[].sku` -> `items.[].sku`)
+pub(crate) fn column_name_for_path(path: &str) -> String {
+    path.strip_prefix("$.")
+        .unwrap_or(path)
+        .replace("[].", ".[].")
+        .trim_end_matches("[]")
+        .to_string()
+}
+
+/// Build a single column from an inferred field and its statistics
+fn build_column(
+    name: &str,
+    field: &InferredField,
+    stats: Option<&FieldStats>,
+    parent_occurrences: Option<usize>,
+) -> Column {
+    let mut column = Column {
+        name: name.to_string(),
+        data_type: inferred_type_to_data_type(&field.field_type),
+        description: field.description.clone().unwrap_or_default(),
+        examples: field.examples.clone(),
+        nullable: is_nullable(field, stats, parent_occurrences),
+        ..Default::default()
+    };
+
+    if let InferredType::String {
+        format: Some(format),
+    } = &field.field_type
+        && !matches!(
+            format,
+            Format::Date | Format::DateTime | Format::Time | Format::None
+        )
+    {
+        column.logical_type_options = Some(LogicalTypeOptions {
+            format: Some(format.to_string()),
+            ..Default::default()
+        });
+    }
+
+    if let InferredType::Mixed { types } = &field.field_type {
+        let type_names: Vec<&str> = types.iter().map(|t| t.type_name()).collect();
+        column
+            .custom_properties
+            .insert("inferredTypes".to_string(), json!(type_names));
+    }
+
+    if let Some(stats) = stats {
+        let is_integer = matches!(field.field_type, InferredType::Integer);
+        if matches!(
+            field.field_type,
+            InferredType::Integer | InferredType::Number
+        ) {
+            if let Some(min) = stats.min {
+                column.quality.push(range_rule(
+                    "MIN",
+                    "mustBeGreaterThanOrEqual",
+                    min,
+                    is_integer,
+                ));
+            }
+            if let Some(max) = stats.max {
+                column
+                    .quality
+                    .push(range_rule("MAX", "mustBeLessThanOrEqual", max, is_integer));
+            }
+        }
+
+        if matches!(field.field_type, InferredType::String { .. }) {
+            column.enum_values = stats.enum_values.clone();
+        }
+    }
+
+    column
+}
+
+/// Whether a field should be nullable given how often it was present and null
+fn is_nullable(
+    field: &InferredField,
+    stats: Option<&FieldStats>,
+    parent_occurrences: Option<usize>,
+) -> bool {
+    if field.nullable || !field.required {
+        return true;
+    }
+    match (stats, parent_occurrences) {
+        (Some(stats), Some(parent)) => stats.null_count > 0 || stats.occurrences < parent,
+        (Some(stats), None) => stats.null_count > 0,
+        _ => false,
+    }
+}
+
+/// Map an inferred type to an SDK column data type
+fn inferred_type_to_data_type(inferred: &InferredType) -> String {
+    match inferred {
+        InferredType::Boolean => "BOOLEAN".to_string(),
+        InferredType::Integer => "BIGINT".to_string(),
+        InferredType::Number => "DOUBLE".to_string(),
+        InferredType::String { format } => match format {
+            Some(Format::Date) => "DATE".to_string(),
+            Some(Format::DateTime) => "TIMESTAMP".to_string(),
+            Some(Format::Time) => "TIME".to_string(),
+            _ => "STRING".to_string(),
+        },
+        InferredType::Array { items } => format!("ARRAY<{}>", inferred_type_to_data_type(items)),
+        InferredType::Object { .. } => "STRUCT".to_string(),
+        InferredType::Null | InferredType::Unknown | InferredType::Mixed { .. } => {
+            "STRING".to_string()
+        }
+    }
+}
+
+/// Build a quality rule asserting an observed numeric bound
+fn range_rule(
+    aggregate: &str,
+    operator: &str,
+    bound: f64,
+    is_integer: bool,
+) -> HashMap<String, Value> {
+    let value = if is_integer && bound.fract() == 0.0 {
+        json!(bound as i64)
+    } else {
+        json!(bound)
+    };
+
+    let mut rule = HashMap::new();
+    rule.insert("type".to_string(), json!("sql"));
+    rule.insert(
+        "description".to_string(),
+        json!(format!(
+            "Observed {} value during inference",
+            aggregate.to_lowercase()
+        )),
+    );
+    rule.insert(
+        "query".to_string(),
+        json!(format!("SELECT {}(${{column}}) FROM ${{table}}", aggregate)),
+    );
+    rule.insert(operator.to_string(), value);
+    rule
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::inference::{InferenceConfig, SchemaInferrer};
+
+    fn infer(records: &[&str]) -> crate::inference::InferredSchema {
+        let mut inferrer = SchemaInferrer::with_config(InferenceConfig::default());
+        for record in records {
+            inferrer.add_json(record).unwrap();
+        }
+        inferrer.finalize().unwrap()
+    }
+
+    #[test]
+    fn test_to_table_carries_field_metadata() {
+        let schema = infer(&[
+            r#"{"id": 1, "email": "a@example.com", "status": "active", "score": 1.5}"#,
+            r#"{"id": 2, "email": "b@example.com", "status": "inactive", "score": 9.5}"#,
+            r#"{"id": 3, "email": "c@example.com", "status": "active"}"#,
+            r#"{"id": 4, "email": "d@example.com", "status": "inactive", "score": null}"#,
+        ]);
+
+        let table = schema.to_table("users");
+        assert_eq!(table.name, "users");
+
+        let id = table.columns.iter().find(|c| c.name == "id").unwrap();
+        assert_eq!(id.data_type, "BIGINT");
+        assert!(!id.nullable);
+        assert!(!id.examples.is_empty());
+        assert_eq!(id.quality.len(), 2);
+        assert_eq!(id.quality[0]["mustBeGreaterThanOrEqual"], 1);
+        assert_eq!(id.quality[1]["mustBeLessThanOrEqual"], 4);
+
+        let email = table.columns.iter().find(|c| c.name == "email").unwrap();
+        assert_eq!(
+            email
+                .logical_type_options
+                .as_ref()
+                .unwrap()
+                .format
+                .as_deref(),
+            Some("email")
+        );
+        assert!(email.enum_values.is_empty());
+
+        assert!(id.primary_key);
+        assert!(id.unique);
+
+        let status = table.columns.iter().find(|c| c.name == "status").unwrap();
+        assert_eq!(status.enum_values, vec!["active", "inactive"]);
+        assert!(!status.primary_key);
+
+        let score = table.columns.iter().find(|c| c.name == "score").unwrap();
+        assert_eq!(score.data_type, "DOUBLE");
+        assert!(score.nullable);
+    }
+
+    #[test]
+    fn test_to_table_flattens_nested_fields() {
+        let schema = infer(&[
+            r#"{"user": {"name": "Alice", "age": 30}, "items": [{"sku": "A1"}], "tags": ["x"]}"#,
+            r#"{"user": {"name": "Bob"}, "items": [{"sku": "B2", "qty": 2}], "tags": []}"#,
+        ]);
+
+        let table = schema.to_table("orders");
+        let names: Vec<&str> = table.columns.iter().map(|c| c.name.as_str()).collect();
+        assert!(names.contains(&"user"));
+        assert!(names.contains(&"user.name"));
+        assert!(names.contains(&"items.[].sku"));
+
+        let age = table.columns.iter().find(|c| c.name == "user.age").unwrap();
+        assert!(age.nullable);
+        let tags = table.columns.iter().find(|c| c.name == "tags").unwrap();
+        assert_eq!(tags.data_type, "ARRAY<STRING>");
+
+        let contract = schema.to_odcs_contract("orders");
+        assert_eq!(contract.schema.len(), 1);
+        let user = contract.schema[0]
+            .properties
+            .iter()
+            .find(|p| p.name == "user")
+            .unwrap();
+        assert_eq!(user.logical_type, "object");
+        assert_eq!(user.properties.len(), 2);
+    }
+
+    #[test]
+    fn test_variants_become_separate_tables() {
+        let schema = infer(&[
+            r#"{"type": "page.view", "id": 1, "url": "/home"}"#,
+            r#"{"type": "page.view", "id": 2, "url": "/about"}"#,
+            r#"{"type": "purchase", "id": 3, "amount": 9.5, "currency": "EUR"}"#,
+        ]);
+
+        let tables = schema.to_variant_tables("events");
+        let names: Vec<&str> = tables.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["events_page_view", "events_purchase"]);
+
+        let discriminator = tables[0].columns.iter().find(|c| c.name == "type").unwrap();
+        assert_eq!(discriminator.enum_values, vec!["page.view"]);
+        assert!(tables[1].columns.iter().any(|c| c.name == "amount"));
+        assert!(!tables[1].columns.iter().any(|c| c.name == "url"));
+
+        let contract = schema.to_odcs_contract("events");
+        assert_eq!(contract.name, "events");
+        assert_eq!(contract.schema.len(), 2);
+    }
+}
@@ -0,0 +1,242 @@
+//! Distinct value counting
+//!
+//! Counts distinct values exactly while the set is small and switches to a
+//! HyperLogLog sketch once it grows past [`EXACT_LIMIT`] values. Counters can
+//! be merged, which makes them usable across partitions and for inclusion
+//! (foreign key) checks between fields.
+
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Number of distinct hashes kept exactly before switching to a sketch
+pub const EXACT_LIMIT: usize = 1024;
+
+/// HyperLogLog precision (2^12 registers, ~1.6% standard error)
+const PRECISION: u32 = 12;
+const REGISTER_COUNT: usize = 1 << PRECISION;
+
+/// Relative standard error of the sketch estimate
+pub const SKETCH_ERROR: f64 = 1.04 / 64.0;
+
+/// Distinct value counter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "mode")]
+pub enum DistinctCounter {
+    /// Exact set of value hashes
+    Exact { hashes: BTreeSet<u64> },
+    /// HyperLogLog registers
+    Sketch { registers: Vec<u8> },
+}
+
+impl DistinctCounter {
+    /// Create an empty counter
+    pub fn new() -> Self {
+        DistinctCounter::Exact {
+            hashes: BTreeSet::new(),
+        }
+    }
+
+    /// Add a JSON value
+    pub fn insert_value(&mut self, value: &Value) {
+        self.insert_hash(hash_value(value));
+    }
+
+    /// Add a pre-computed value hash
+    pub fn insert_hash(&mut self, hash: u64) {
+        match self {
+            DistinctCounter::Exact { hashes } => {
+                hashes.insert(hash);
+                if hashes.len() > EXACT_LIMIT {
+                    let mut registers = vec![0u8; REGISTER_COUNT];
+                    for h in hashes.iter() {
+                        update_register(&mut registers, *h);
+                    }
+                    *self = DistinctCounter::Sketch { registers };
+                }
+            }
+            DistinctCounter::Sketch { registers } => update_register(registers, hash),
+        }
+    }
+
+    /// Whether the count is exact (no sketch approximation)
+    pub fn is_exact(&self) -> bool {
+        matches!(self, DistinctCounter::Exact { .. })
+    }
+
+    /// Number of distinct values (exact or estimated)
+    pub fn count(&self) -> usize {
+        match self {
+            DistinctCounter::Exact { hashes } => hashes.len(),
+            DistinctCounter::Sketch { registers } => estimate(registers),
+        }
+    }
+
+    /// Merge another counter into this one
+    pub fn merge(&mut self, other: &DistinctCounter) {
+        match other {
+            DistinctCounter::Exact { hashes } => {
+                for h in hashes {
+                    self.insert_hash(*h);
+                }
+            }
+            DistinctCounter::Sketch { registers: theirs } => {
+                if let DistinctCounter::Exact { hashes } = self {
+                    let mut registers = vec![0u8; REGISTER_COUNT];
+                    for h in hashes.iter() {
+                        update_register(&mut registers, *h);
+                    }
+                    *self = DistinctCounter::Sketch { registers };
+                }
+                if let DistinctCounter::Sketch { registers } = self {
+                    for (mine, theirs) in registers.iter_mut().zip(theirs) {
+                        *mine = (*mine).max(*theirs);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Whether every value counted here also appears in `other`
+    ///
+    /// Exact when both counters are exact. Otherwise the union is estimated and
+    /// compared with `other`, allowing for the sketch error.
+    pub fn is_subset_of(&self, other: &DistinctCounter) -> bool {
+        if let (
+            DistinctCounter::Exact { hashes: mine },
+            DistinctCounter::Exact { hashes: theirs },
+        ) = (self, other)
+        {
+            return mine.is_subset(theirs);
+        }
+
+        let mut union = other.clone();
+        union.merge(self);
+        union.count() as f64 <= other.count() as f64 * (1.0 + 3.0 * SKETCH_ERROR)
+    }
+}
+
+impl Default for DistinctCounter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Stable 64-bit hash of a JSON value
+///
+/// Uses FNV-1a over the canonical JSON text followed by a SplitMix64 finalizer,
+/// so hashes are stable across runs and can be persisted.
+pub fn hash_value(value: &Value) -> u64 {
+    let text = match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    hash_bytes(text.as_bytes())
+}
+
+/// Stable 64-bit hash of a byte string
+pub fn hash_bytes(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    mix(hash)
+}
+
+/// Combine hashes of several values into one (order-sensitive)
+pub fn combine_hashes(hashes: &[u64]) -> u64 {
+    hashes
+        .iter()
+        .fold(0x9e37_79b9_7f4a_7c15u64, |acc, h| mix(acc ^ h))
+}
+
+fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+fn update_register(registers: &mut [u8], hash: u64) {
+    let index = (hash >> (64 - PRECISION)) as usize;
+    let rest = hash << PRECISION;
+    let rank = (rest.leading_zeros() + 1).min(64 - PRECISION + 1) as u8;
+    if registers[index] < rank {
+        registers[index] = rank;
+    }
+}
+
+fn estimate(registers: &[u8]) -> usize {
+    let m = registers.len() as f64;
+    let alpha = 0.7213 / (1.0 + 1.079 / m);
+    let sum: f64 = registers.iter().map(|r| 2f64.powi(-i32::from(*r))).sum();
+    let raw = alpha * m * m / sum;
+
+    let zeros = registers.iter().filter(|r| **r == 0).count();
+    if raw <= 2.5 * m && zeros > 0 {
+        (m * (m / zeros as f64).ln()).round() as usize
+    } else {
+        raw.round() as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_exact_counting() {
+        let mut counter = DistinctCounter::new();
+        for value in [json!(1), json!(2), json!(2), json!("2")] {
+            counter.insert_value(&value);
+        }
+        // "2" and 2 share the same canonical text
+        assert_eq!(counter.count(), 2);
+        assert!(counter.is_exact());
+    }
+
+    #[test]
+    fn test_sketch_estimate_within_error() {
+        let mut counter = DistinctCounter::new();
+        for i in 0..50_000 {
+            counter.insert_value(&json!(i));
+        }
+        assert!(!counter.is_exact());
+        let estimate = counter.count() as f64;
+        assert!((estimate - 50_000.0).abs() / 50_000.0 < 0.05);
+    }
+
+    #[test]
+    fn test_merge_and_subset() {
+        let mut ids = DistinctCounter::new();
+        let mut refs = DistinctCounter::new();
+        for i in 0..5_000 {
+            ids.insert_value(&json!(i));
+            if i % 3 == 0 {
+                refs.insert_value(&json!(i));
+            }
+        }
+        assert!(refs.is_subset_of(&ids));
+
+        let mut outside = DistinctCounter::new();
+        for i in 10_000..12_000 {
+            outside.insert_value(&json!(i));
+        }
+        assert!(!outside.is_subset_of(&ids));
+
+        let small: DistinctCounter = {
+            let mut c = DistinctCounter::new();
+            c.insert_value(&json!(1));
+            c.insert_value(&json!(99_999));
+            c
+        };
+        let mut exact_ids = DistinctCounter::new();
+        exact_ids.insert_value(&json!(1));
+        assert!(!small.is_subset_of(&exact_ids));
+
+        ids.merge(&outside);
+        assert!(outside.is_subset_of(&ids));
+    }
+}
@@ -2,16 +2,19 @@
 //!
 //! Provides both single-threaded and parallel schema inference capabilities.
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::sync::{Arc, Mutex};
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use super::config::InferenceConfig;
+use super::distinct::{DistinctCounter, hash_value};
 use super::error::InferenceError;
 use super::formats::{Format, detect_format};
+use super::keys::find_candidate_keys;
 use super::types::{FieldStats, InferredField, InferredSchema, InferredType};
+use super::variants::VariantTracker;
 
 /// Statistics from schema inference
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -32,10 +35,19 @@ pub struct InferenceStats {
 /// Schema inference engine
 ///
 /// Analyzes JSON records and builds a schema definition.
+///
+/// The accumulated state is serialisable and mergeable: persist an inferrer
+/// with serde, fold new batches into it later (or [`merge`](Self::merge) the
+/// partial inferrers of several workers) and re-finalise with
+/// [`snapshot`](Self::snapshot) without re-reading earlier records.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SchemaInferrer {
     config: InferenceConfig,
-    /// Field type tracking: path -> list of observed types
-    field_types: HashMap<String, Vec<InferredType>>,
+    /// Field type tracking: path -> merge of all observed types
+    field_types: HashMap<String, InferredType>,
+    /// Observed type names per field with their counts
+    field_type_counts: HashMap<String, BTreeMap<String, usize>>,
     /// Field occurrence counts
     field_occurrences: HashMap<String, usize>,
     /// Null occurrences per field
@@ -44,6 +56,19 @@ pub struct SchemaInferrer {
     field_examples: HashMap<String, Vec<Value>>,
     /// Numeric stats per field
     field_numeric_stats: HashMap<String, NumericStats>,
+    /// Distinct string values per field (bounded by `max_enum_values`)
+    field_string_values: HashMap<String, BTreeSet<String>>,
+    /// Non-null string occurrences per field
+    field_string_counts: HashMap<String, usize>,
+    /// Fields whose distinct string values exceeded `max_enum_values`
+    high_cardinality_fields: HashSet<String>,
+    /// Distinct value counters per scalar field
+    field_distinct: HashMap<String, DistinctCounter>,
+    /// Top-level scalar value hashes per sampled record (composite key detection)
+    key_rows: Vec<BTreeMap<String, u64>>,
+    /// Per-value sub-inferrers for candidate discriminator fields
+    #[serde(default)]
+    variants: Option<VariantTracker>,
     /// Total records processed
     record_count: usize,
     /// Records skipped
@@ -52,7 +77,7 @@ pub struct SchemaInferrer {
     max_depth_seen: usize,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 struct NumericStats {
     min: f64,
     max: f64,
@@ -73,6 +98,20 @@ impl NumericStats {
         self.count += 1;
     }
 
+    fn merge(&mut self, other: &NumericStats) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            *self = other.clone();
+            return;
+        }
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.sum += other.sum;
+        self.count += other.count;
+    }
+
     fn avg(&self) -> Option<f64> {
         if self.count > 0 {
             Some(self.sum / self.count as f64)
@@ -91,12 +130,19 @@ impl SchemaInferrer {
     /// Create a new schema inferrer with custom configuration
     pub fn with_config(config: InferenceConfig) -> Self {
         Self {
+            variants: VariantTracker::new(&config),
             config,
             field_types: HashMap::new(),
+            field_type_counts: HashMap::new(),
             field_occurrences: HashMap::new(),
             field_nulls: HashMap::new(),
             field_examples: HashMap::new(),
             field_numeric_stats: HashMap::new(),
+            field_string_values: HashMap::new(),
+            field_string_counts: HashMap::new(),
+            high_cardinality_fields: HashSet::new(),
+            field_distinct: HashMap::new(),
+            key_rows: Vec::new(),
             record_count: 0,
             skipped_count: 0,
             max_depth_seen: 0,
@@ -138,6 +184,23 @@ impl SchemaInferrer {
         self.record_count += 1;
         self.analyze_value(value, "$", 0)?;
 
+        if let Some(tracker) = &mut self.variants {
+            tracker.observe(value)?;
+        }
+
+        if self.config.detect_keys
+            && self.config.max_key_columns > 1
+            && self.key_rows.len() < self.config.key_sample_size
+            && let Some(obj) = value.as_object()
+        {
+            let row = obj
+                .iter()
+                .filter(|(_, v)| is_key_value(v))
+                .map(|(k, v)| (k.clone(), hash_value(v)))
+                .collect();
+            self.key_rows.push(row);
+        }
+
         Ok(())
     }
 
@@ -149,13 +212,13 @@ impl SchemaInferrer {
         Ok(())
     }
 
-    /// Analyze a JSON value at a given path
+    /// Analyze a JSON value at a given path, returning its inferred type
     fn analyze_value(
         &mut self,
         value: &Value,
         path: &str,
         depth: usize,
-    ) -> Result<(), InferenceError> {
+    ) -> Result<InferredType, InferenceError> {
         if depth > self.config.max_depth {
             return Err(InferenceError::MaxDepthExceeded {
                 depth,
@@ -170,10 +233,7 @@ impl SchemaInferrer {
 
         // Infer and track type
         let inferred_type = self.infer_type(value, path, depth)?;
-        self.field_types
-            .entry(path.to_string())
-            .or_default()
-            .push(inferred_type);
+        self.record_type(path, inferred_type.clone());
 
         // Track nulls
         if value.is_null() {
@@ -196,7 +256,80 @@ impl SchemaInferrer {
                 .add(n);
         }
 
-        Ok(())
+        // Track distinct string values for enum detection
+        if let Value::String(s) = value {
+            self.track_string_value(path, s);
+        }
+
+        // Track distinct scalar values
+        if is_key_value(value) {
+            self.field_distinct
+                .entry(path.to_string())
+                .or_default()
+                .insert_value(value);
+        }
+
+        Ok(inferred_type)
+    }
+
+    /// Merge an observed type into the field's type and count it
+    fn record_type(&mut self, path: &str, inferred_type: InferredType) {
+        *self
+            .field_type_counts
+            .entry(path.to_string())
+            .or_default()
+            .entry(inferred_type.type_name().to_string())
+            .or_insert(0) += 1;
+
+        let merged = self
+            .field_types
+            .entry(path.to_string())
+            .or_insert(InferredType::Unknown);
+        let mut updated =
+            std::mem::replace(merged, InferredType::Unknown).merge_with(inferred_type);
+        cap_examples(&mut updated, self.config.max_examples);
+        *merged = updated;
+    }
+
+    /// Record a string value for low-cardinality (enum) detection
+    fn track_string_value(&mut self, path: &str, value: &str) {
+        if self.config.max_enum_values == 0 {
+            return;
+        }
+
+        *self
+            .field_string_counts
+            .entry(path.to_string())
+            .or_insert(0) += 1;
+
+        if self.high_cardinality_fields.contains(path) {
+            return;
+        }
+
+        let values = self
+            .field_string_values
+            .entry(path.to_string())
+            .or_default();
+        values.insert(value.to_string());
+        if values.len() > self.config.max_enum_values {
+            self.field_string_values.remove(path);
+            self.high_cardinality_fields.insert(path.to_string());
+        }
+    }
+
+    /// Enumeration candidates for a field
+    ///
+    /// A string field is an enumeration candidate when its distinct values stay
+    /// within `max_enum_values` and each value is seen at least twice on average.
+    fn enum_candidates(&self, path: &str) -> Vec<String> {
+        let Some(values) = self.field_string_values.get(path) else {
+            return Vec::new();
+        };
+        let count = self.field_string_counts.get(path).copied().unwrap_or(0);
+        if values.is_empty() || count < values.len() * 2 {
+            return Vec::new();
+        }
+        values.iter().cloned().collect()
     }
 
     /// Infer the type of a JSON value
@@ -240,8 +373,7 @@ impl SchemaInferrer {
                     let item_path = format!("{}[]", path);
 
                     for item in arr {
-                        self.analyze_value(item, &item_path, depth + 1)?;
-                        let t = self.infer_type(item, &item_path, depth + 1)?;
+                        let t = self.analyze_value(item, &item_path, depth + 1)?;
                         item_type = item_type.merge_with(t);
                     }
 
@@ -255,9 +387,7 @@ impl SchemaInferrer {
 
                 for (key, val) in obj {
                     let field_path = format!("{}.{}", path, key);
-                    self.analyze_value(val, &field_path, depth + 1)?;
-
-                    let field_type = self.infer_type(val, &field_path, depth + 1)?;
+                    let field_type = self.analyze_value(val, &field_path, depth + 1)?;
                     let nullable = val.is_null();
 
                     let mut field = InferredField::new(field_type);
@@ -277,7 +407,7 @@ impl SchemaInferrer {
     }
 
     /// Finalize inference and generate schema
-    pub fn finalize(self) -> Result<InferredSchema, InferenceError> {
+    pub fn finalize(mut self) -> Result<InferredSchema, InferenceError> {
         if self.record_count == 0 {
             return Err(InferenceError::NoRecords);
         }
@@ -296,14 +426,49 @@ impl SchemaInferrer {
                 FieldStats {
                     occurrences: *occurrences,
                     null_count,
-                    distinct_count: self.field_examples.get(path).map(|e| e.len()),
+                    distinct_count: self.field_distinct.get(path).map(|d| d.count()),
                     min: numeric.map(|n| n.min),
                     max: numeric.map(|n| n.max),
                     avg: numeric.and_then(|n| n.avg()),
+                    enum_values: self.enum_candidates(path),
                 },
             );
         }
 
+        let mut candidate_keys = Vec::new();
+        let mut value_sketches = HashMap::new();
+        if self.config.detect_keys {
+            let mut eligible = Vec::new();
+            for (path, counter) in &self.field_distinct {
+                if !self.is_key_eligible(path) {
+                    continue;
+                }
+                value_sketches.insert(path.clone(), counter.clone());
+
+                if let Some(name) = path.strip_prefix("$.")
+                    && !name.contains('.')
+                    && !name.contains('[')
+                    && let Some(stats) = field_stats.get(path)
+                {
+                    eligible.push((name.to_string(), stats, counter));
+                }
+            }
+            candidate_keys = find_candidate_keys(
+                self.record_count,
+                &eligible,
+                &self.key_rows,
+                self.config.max_key_columns,
+            );
+        }
+
+        let (discriminator, variants) = match self.variants.take() {
+            Some(tracker) => match tracker.finalize()? {
+                Some((field, variants)) => (Some(field), variants),
+                None => (None, Vec::new()),
+            },
+            None => (None, Vec::new()),
+        };
+
         Ok(InferredSchema {
             name: None,
             description: None,
@@ -311,15 +476,31 @@ impl SchemaInferrer {
             record_count: self.record_count,
             partition: None,
             field_stats,
+            candidate_keys,
+            value_sketches,
+            discriminator,
+            variants,
         })
     }
 
+    /// Whether a field can take part in a key: integers and non-temporal strings
+    fn is_key_eligible(&self, path: &str) -> bool {
+        match self.field_types.get(path) {
+            Some(InferredType::Integer) => true,
+            Some(InferredType::String { format }) => !matches!(
+                format,
+                Some(Format::Date) | Some(Format::DateTime) | Some(Format::Time)
+            ),
+            _ => false,
+        }
+    }
+
     /// Build the root type from collected data
     fn build_root_type(&self) -> Result<InferredType, InferenceError> {
         // Find all top-level fields ($.fieldname)
         let mut properties = BTreeMap::new();
 
-        for (path, types) in &self.field_types {
+        for (path, merged_type) in &self.field_types {
             // Only process direct children of root
             if !path.starts_with("$.") || path.matches('.').count() != 1 {
                 continue;
@@ -330,11 +511,7 @@ impl SchemaInferrer {
                 continue;
             }
 
-            // Merge all observed types
-            let mut primary_type = InferredType::Unknown;
-            for t in types {
-                primary_type = primary_type.merge_with(t.clone());
-            }
+            let mut primary_type = merged_type.clone();
 
             // If we have object types, recursively build nested structure
             if matches!(primary_type, InferredType::Object { .. }) {
@@ -372,7 +549,7 @@ impl SchemaInferrer {
         let prefix = format!("{}.", parent_path);
         let mut properties = BTreeMap::new();
 
-        for (path, types) in &self.field_types {
+        for (path, merged_type) in &self.field_types {
             if !path.starts_with(&prefix) {
                 continue;
             }
@@ -383,11 +560,7 @@ impl SchemaInferrer {
                 continue;
             }
 
-            // Merge all observed types
-            let mut primary_type = InferredType::Unknown;
-            for t in types {
-                primary_type = primary_type.merge_with(t.clone());
-            }
+            let mut primary_type = merged_type.clone();
 
             // Recursively build if object
             if matches!(primary_type, InferredType::Object { .. }) {
@@ -413,15 +586,14 @@ impl SchemaInferrer {
 
     /// Get current inference statistics
     pub fn stats(&self) -> InferenceStats {
-        let mut type_distribution = HashMap::new();
-
-        for (path, types) in &self.field_types {
-            let mut dist: HashMap<String, usize> = HashMap::new();
-            for t in types {
-                *dist.entry(t.type_name().to_string()).or_insert(0) += 1;
-            }
-            type_distribution.insert(path.clone(), dist);
-        }
+        let type_distribution = self
+            .field_type_counts
+            .iter()
+            .map(|(path, counts)| {
+                let dist = counts.iter().map(|(k, v)| (k.clone(), *v)).collect();
+                (path.clone(), dist)
+            })
+            .collect();
 
         InferenceStats {
             records_processed: self.record_count,
@@ -436,6 +608,104 @@ impl SchemaInferrer {
     pub fn record_count(&self) -> usize {
         self.record_count
     }
+
+    /// Get the inference configuration
+    pub fn config(&self) -> &InferenceConfig {
+        &self.config
+    }
+
+    /// Generate a schema from the current state without consuming the inferrer
+    ///
+    /// Further records can be added afterwards and the schema re-generated.
+    pub fn snapshot(&self) -> Result<InferredSchema, InferenceError> {
+        self.clone().finalize()
+    }
+
+    /// Fold another inferrer's accumulated state into this one
+    ///
+    /// Counts and numeric statistics are summed, types merged and distinct
+    /// counters combined. Examples, enum candidates and key samples stay
+    /// bounded by this inferrer's configuration.
+    pub fn merge(&mut self, other: SchemaInferrer) {
+        for (path, counts) in other.field_type_counts {
+            let existing = self.field_type_counts.entry(path).or_default();
+            for (type_name, count) in counts {
+                *existing.entry(type_name).or_insert(0) += count;
+            }
+        }
+        for (path, inferred_type) in other.field_types {
+            let merged = self
+                .field_types
+                .entry(path)
+                .or_insert(InferredType::Unknown);
+            let mut updated =
+                std::mem::replace(merged, InferredType::Unknown).merge_with(inferred_type);
+            cap_examples(&mut updated, self.config.max_examples);
+            *merged = updated;
+        }
+
+        add_counts(&mut self.field_occurrences, other.field_occurrences);
+        add_counts(&mut self.field_nulls, other.field_nulls);
+        add_counts(&mut self.field_string_counts, other.field_string_counts);
+
+        for (path, examples) in other.field_examples {
+            let existing = self.field_examples.entry(path).or_default();
+            for example in examples {
+                if existing.len() >= self.config.max_examples {
+                    break;
+                }
+                if !existing.contains(&example) {
+                    existing.push(example);
+                }
+            }
+        }
+
+        for (path, stats) in other.field_numeric_stats {
+            self.field_numeric_stats
+                .entry(path)
+                .or_default()
+                .merge(&stats);
+        }
+
+        self.high_cardinality_fields
+            .extend(other.high_cardinality_fields);
+        for (path, values) in other.field_string_values {
+            self.field_string_values
+                .entry(path)
+                .or_default()
+                .extend(values);
+        }
+        let max_enum_values = self.config.max_enum_values;
+        let high_cardinality = &mut self.high_cardinality_fields;
+        self.field_string_values.retain(|path, values| {
+            if values.len() > max_enum_values {
+                high_cardinality.insert(path.clone());
+            }
+            !high_cardinality.contains(path)
+        });
+
+        for (path, counter) in other.field_distinct {
+            self.field_distinct
+                .entry(path)
+                .and_modify(|existing| existing.merge(&counter))
+                .or_insert(counter);
+        }
+
+        let free_rows = self
+            .config
+            .key_sample_size
+            .saturating_sub(self.key_rows.len());
+        self.key_rows
+            .extend(other.key_rows.into_iter().take(free_rows));
+
+        if let (Some(tracker), Some(other_tracker)) = (&mut self.variants, other.variants) {
+            tracker.merge(other_tracker);
+        }
+
+        self.record_count += other.record_count;
+        self.skipped_count += other.skipped_count;
+        self.max_depth_seen = self.max_depth_seen.max(other.max_depth_seen);
+    }
 }
 
 impl Default for SchemaInferrer {
@@ -444,6 +714,40 @@ impl Default for SchemaInferrer {
     }
 }
 
+/// Add per-field counts from another inferrer
+fn add_counts(counts: &mut HashMap<String, usize>, other: HashMap<String, usize>) {
+    for (path, count) in other {
+        *counts.entry(path).or_insert(0) += count;
+    }
+}
+
+/// Bound the examples held inside a merged type
+///
+/// Fields of array items only keep examples in their merged type, which would
+/// otherwise grow with every distinct value seen.
+fn cap_examples(inferred: &mut InferredType, max_examples: usize) {
+    match inferred {
+        InferredType::Object { properties } => {
+            for field in properties.values_mut() {
+                field.examples.truncate(max_examples);
+                cap_examples(&mut field.field_type, max_examples);
+            }
+        }
+        InferredType::Array { items } => cap_examples(items, max_examples),
+        InferredType::Mixed { types } => {
+            for t in types {
+                cap_examples(t, max_examples);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Scalar values that are counted for distinctness (no nulls, objects or arrays)
+fn is_key_value(value: &Value) -> bool {
+    matches!(value, Value::Bool(_) | Value::Number(_) | Value::String(_))
+}
+
 fn value_type_name(value: &Value) -> &'static str {
     match value {
         Value::Null => "null",
@@ -471,8 +775,13 @@ impl ParallelSchemaInferrer {
 
     /// Create a new parallel schema inferrer with custom configuration
     pub fn with_config(config: InferenceConfig) -> Self {
+        Self::from_inferrer(SchemaInferrer::with_config(config))
+    }
+
+    /// Wrap an existing inferrer, e.g. one restored from a saved state
+    pub fn from_inferrer(inferrer: SchemaInferrer) -> Self {
         Self {
-            inner: Arc::new(Mutex::new(SchemaInferrer::with_config(config))),
+            inner: Arc::new(Mutex::new(inferrer)),
         }
     }
 
@@ -501,16 +810,39 @@ impl ParallelSchemaInferrer {
             .stats())
     }
 
+    /// Fold a partial inferrer's state into this one (thread-safe)
+    pub fn merge(&self, other: SchemaInferrer) -> Result<(), InferenceError> {
+        self.inner
+            .lock()
+            .map_err(|_| InferenceError::LockError)?
+            .merge(other);
+        Ok(())
+    }
+
+    /// Generate a schema from the current state without consuming the inferrer (thread-safe)
+    pub fn snapshot(&self) -> Result<InferredSchema, InferenceError> {
+        self.inner
+            .lock()
+            .map_err(|_| InferenceError::LockError)?
+            .snapshot()
+    }
+
+    /// Unwrap the accumulated inferrer state
+    ///
+    /// All threads must have finished adding records before calling this method.
+    pub fn into_inferrer(self) -> Result<SchemaInferrer, InferenceError> {
+        Arc::try_unwrap(self.inner)
+            .map_err(|_| InferenceError::LockError)?
+            .into_inner()
+            .map_err(|_| InferenceError::LockError)
+    }
+
     /// Finalize inference and generate schema
     ///
     /// This consumes the parallel inferrer. All threads must have finished
     /// adding records before calling this method.
     pub fn finalize(self) -> Result<InferredSchema, InferenceError> {
-        let inner = Arc::try_unwrap(self.inner)
-            .map_err(|_| InferenceError::LockError)?
-            .into_inner()
-            .map_err(|_| InferenceError::LockError)?;
-        inner.finalize()
+        self.into_inferrer()?.finalize()
     }
 }
 
@@ -762,6 +1094,100 @@ mod tests {
         assert!(stats.fields_discovered > 0);
     }
 
+    #[test]
+    fn test_nested_field_stats_counted_once() {
+        let mut inferrer = SchemaInferrer::new();
+
+        inferrer
+            .add_json(r#"{"user": {"address": {"city": "Paris"}}, "items": [{"sku": "A"}]}"#)
+            .unwrap();
+        inferrer
+            .add_json(r#"{"user": {"address": {"city": "Rome"}}, "items": [{"sku": "B"}]}"#)
+            .unwrap();
+
+        let schema = inferrer.finalize().unwrap();
+        assert_eq!(schema.field_stats["$.user"].occurrences, 2);
+        assert_eq!(schema.field_stats["$.user.address.city"].occurrences, 2);
+        assert_eq!(schema.field_stats["$.items[].sku"].occurrences, 2);
+    }
+
+    #[test]
+    fn test_enum_candidates() {
+        let config = InferenceConfig::builder().max_enum_values(3).build();
+        let mut inferrer = SchemaInferrer::with_config(config);
+
+        for (i, status) in ["new", "paid", "new", "paid", "new"].iter().enumerate() {
+            inferrer
+                .add_json(&format!(r#"{{"status": "{}", "ref": "r{}"}}"#, status, i))
+                .unwrap();
+        }
+
+        let schema = inferrer.finalize().unwrap();
+        assert_eq!(
+            schema.field_stats["$.status"].enum_values,
+            vec!["new", "paid"]
+        );
+        assert!(schema.field_stats["$.ref"].enum_values.is_empty());
+    }
+
+    #[test]
+    fn test_merge_matches_single_pass() {
+        let records: Vec<String> = (0..20)
+            .map(|i| {
+                if i % 2 == 0 {
+                    format!(r#"{{"id": {}, "status": "new", "score": {}.5}}"#, i, i)
+                } else {
+                    format!(r#"{{"id": {}, "status": "paid", "note": null}}"#, i)
+                }
+            })
+            .collect();
+
+        let mut single = SchemaInferrer::new();
+        single.add_json_batch(&records).unwrap();
+        let expected = single.finalize().unwrap();
+
+        let mut first = SchemaInferrer::new();
+        first.add_json_batch(&records[..7]).unwrap();
+        let mut second = SchemaInferrer::new();
+        second.add_json_batch(&records[7..]).unwrap();
+        first.merge(second);
+        let merged = first.finalize().unwrap();
+
+        assert_eq!(merged.record_count, 20);
+        assert_eq!(merged.root, expected.root);
+        assert_eq!(merged.candidate_keys, expected.candidate_keys);
+        let score = &merged.field_stats["$.score"];
+        assert_eq!(score.min, Some(0.5));
+        assert_eq!(score.max, Some(18.5));
+        assert_eq!(score.occurrences, 10);
+        assert_eq!(merged.field_stats["$.id"].distinct_count, Some(20));
+        assert_eq!(
+            merged.field_stats["$.status"].enum_values,
+            vec!["new", "paid"]
+        );
+    }
+
+    #[test]
+    fn test_state_round_trip_and_snapshot() {
+        let mut inferrer = SchemaInferrer::new();
+        inferrer.add_json(r#"{"type": "a", "x": 1}"#).unwrap();
+        inferrer.add_json(r#"{"type": "b", "y": "z"}"#).unwrap();
+
+        let state = serde_json::to_string(&inferrer).unwrap();
+        let mut restored: SchemaInferrer = serde_json::from_str(&state).unwrap();
+        assert_eq!(restored.record_count(), 2);
+        assert_eq!(restored.snapshot().unwrap().variants.len(), 2);
+
+        restored.add_json(r#"{"type": "a", "x": 2}"#).unwrap();
+        let schema = restored.snapshot().unwrap();
+        assert_eq!(schema.record_count, 3);
+        assert_eq!(schema.variants[0].schema.record_count, 2);
+
+        let parallel = ParallelSchemaInferrer::from_inferrer(restored);
+        parallel.merge(inferrer).unwrap();
+        assert_eq!(parallel.snapshot().unwrap().record_count, 5);
+    }
+
     #[test]
     fn test_sample_size_limit() {
         let config = InferenceConfig::builder().sample_size(2).build();
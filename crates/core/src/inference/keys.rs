@@ -0,0 +1,458 @@
+//! Candidate key and foreign key discovery
+//!
+//! Candidate primary keys are sets of top-level fields that are present,
+//! non-null and unique in every sampled record. Single-field keys use the
+//! per-field [`DistinctCounter`]; composite keys are checked exactly against a
+//! bounded sample of records.
+//!
+//! Foreign keys are inclusion dependencies: every value of a field in one
+//! schema also appears in a single-field candidate key of another schema.
+
+use std::collections::{BTreeMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::convert::column_name_for_path;
+use super::distinct::{DistinctCounter, SKETCH_ERROR, combine_hashes};
+use super::types::{CandidateKey, FieldStats, InferredSchema};
+use crate::models::enums::{EndpointCardinality, RelationshipType};
+use crate::models::relationship::ForeignKeyDetails;
+use crate::models::{Relationship, Table};
+
+/// Maximum number of fields considered when searching for composite keys
+const MAX_COMPOSITE_FIELDS: usize = 24;
+
+/// Minimum distinct source values for a foreign key without a matching name
+const MIN_UNNAMED_DISTINCT: usize = 20;
+
+/// A discovered foreign key (inclusion dependency) between two schemas
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForeignKeyCandidate {
+    /// Name of the referencing schema
+    pub source_schema: String,
+    /// Field path in the referencing schema (e.g. `$.customer_id`)
+    pub source_field: String,
+    /// Name of the referenced schema
+    pub target_schema: String,
+    /// Candidate key field path in the referenced schema (e.g. `$.id`)
+    pub target_field: String,
+    /// Whether the field names suggest the reference (`customer_id` -> `customers.id`)
+    pub name_match: bool,
+    /// Whether inclusion was estimated from a sketch rather than checked exactly
+    pub estimated: bool,
+}
+
+impl ForeignKeyCandidate {
+    /// Build a foreign key relationship between the matching tables
+    ///
+    /// Tables are matched by name against the source and target schema names.
+    /// Returns `None` when either table is missing.
+    pub fn to_relationship(&self, tables: &[Table]) -> Option<Relationship> {
+        let source = tables.iter().find(|t| t.name == self.source_schema)?;
+        let target = tables.iter().find(|t| t.name == self.target_schema)?;
+
+        let source_column = column_name_for_path(&self.source_field);
+        let target_column = column_name_for_path(&self.target_field);
+        let source_optional = source
+            .columns
+            .iter()
+            .find(|c| c.name == source_column)
+            .map(|c| c.nullable)
+            .unwrap_or(true);
+
+        let mut relationship = Relationship::new(source.id, target.id);
+        relationship.relationship_type = Some(RelationshipType::ForeignKey);
+        relationship.foreign_key_details = Some(ForeignKeyDetails {
+            source_column,
+            target_column,
+        });
+        relationship.source_optional = Some(source_optional);
+        relationship.target_optional = Some(false);
+        relationship.source_cardinality = Some(EndpointCardinality::ZeroOrMany);
+        relationship.target_cardinality = Some(if source_optional {
+            EndpointCardinality::ZeroOrOne
+        } else {
+            EndpointCardinality::ExactlyOne
+        });
+        relationship.notes = Some(if self.estimated {
+            "Inferred foreign key (estimated inclusion)".to_string()
+        } else {
+            "Inferred foreign key".to_string()
+        });
+        Some(relationship)
+    }
+}
+
+/// Discover foreign keys between named inferred schemas
+///
+/// A field references a single-field candidate key of another schema when all
+/// of its values appear in the key. Fields whose names do not point at the
+/// referenced schema need at least 20 distinct values to be reported, which
+/// filters out small integer columns that are trivially included in an id range.
+pub fn discover_foreign_keys(schemas: &[(&str, &InferredSchema)]) -> Vec<ForeignKeyCandidate> {
+    let mut candidates = Vec::new();
+
+    for (target_name, target) in schemas {
+        for key in target.candidate_keys.iter().filter(|k| !k.is_composite()) {
+            let target_field = format!("$.{}", key.fields[0]);
+            let Some(target_counter) = target.value_sketches.get(&target_field) else {
+                continue;
+            };
+
+            for (source_name, source) in schemas {
+                if source_name == target_name {
+                    continue;
+                }
+
+                let mut fields: Vec<_> = source.value_sketches.iter().collect();
+                fields.sort_by(|a, b| a.0.cmp(b.0));
+
+                for (source_field, source_counter) in fields {
+                    let distinct = source_counter.count();
+                    if distinct == 0 || !source_counter.is_subset_of(target_counter) {
+                        continue;
+                    }
+
+                    let name_match = names_match(source_field, target_name, &key.fields[0]);
+                    if !name_match && distinct < MIN_UNNAMED_DISTINCT {
+                        continue;
+                    }
+
+                    candidates.push(ForeignKeyCandidate {
+                        source_schema: source_name.to_string(),
+                        source_field: source_field.clone(),
+                        target_schema: target_name.to_string(),
+                        target_field: target_field.clone(),
+                        name_match,
+                        estimated: !(source_counter.is_exact() && target_counter.is_exact()),
+                    });
+                }
+            }
+        }
+    }
+
+    candidates.sort_by(|a, b| {
+        b.name_match
+            .cmp(&a.name_match)
+            .then(a.estimated.cmp(&b.estimated))
+            .then(a.source_schema.cmp(&b.source_schema))
+            .then(a.source_field.cmp(&b.source_field))
+    });
+    candidates
+}
+
+/// Build relationships for discovered foreign keys
+///
+/// Candidates whose tables are not present are skipped.
+pub fn foreign_key_relationships(
+    candidates: &[ForeignKeyCandidate],
+    tables: &[Table],
+) -> Vec<Relationship> {
+    candidates
+        .iter()
+        .filter_map(|c| c.to_relationship(tables))
+        .collect()
+}
+
+/// Find candidate keys from the statistics gathered by the inferrer
+///
+/// `fields` holds the key-eligible top-level fields with their statistics and
+/// distinct counters. `key_rows` holds per-record value hashes of those fields
+/// for composite key checks.
+pub(crate) fn find_candidate_keys(
+    record_count: usize,
+    fields: &[(String, &FieldStats, &DistinctCounter)],
+    key_rows: &[BTreeMap<String, u64>],
+    max_key_columns: usize,
+) -> Vec<CandidateKey> {
+    let mut keys = Vec::new();
+    let mut composite_fields = Vec::new();
+
+    for (name, stats, counter) in fields {
+        if stats.occurrences != record_count || stats.null_count > 0 {
+            continue;
+        }
+
+        let distinct = counter.count();
+        let unique = if counter.is_exact() {
+            distinct == record_count
+        } else {
+            distinct as f64 >= record_count as f64 * (1.0 - 3.0 * SKETCH_ERROR)
+        };
+
+        if unique {
+            keys.push(CandidateKey {
+                fields: vec![name.clone()],
+                estimated: !counter.is_exact(),
+            });
+        } else if distinct > 1 {
+            composite_fields.push((name.clone(), distinct));
+        }
+    }
+
+    if max_key_columns > 1 && !key_rows.is_empty() {
+        composite_fields.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        composite_fields.truncate(MAX_COMPOSITE_FIELDS);
+        let mut names: Vec<String> = composite_fields.into_iter().map(|(n, _)| n).collect();
+        names.sort();
+
+        let sampled = key_rows.len() < record_count;
+        let mut found: Vec<Vec<String>> = Vec::new();
+        for size in 2..=max_key_columns.min(names.len()) {
+            for combo in combinations(&names, size) {
+                if found
+                    .iter()
+                    .any(|key| key.iter().all(|field| combo.contains(field)))
+                {
+                    continue;
+                }
+                if is_unique_combination(&combo, key_rows) {
+                    found.push(combo.clone());
+                    keys.push(CandidateKey {
+                        fields: combo,
+                        estimated: sampled,
+                    });
+                }
+            }
+        }
+    }
+
+    rank_keys(&mut keys);
+    keys
+}
+
+/// Recompute candidate keys for a merged schema
+///
+/// Single-field keys are kept when they are keys in every input and the merged
+/// distinct counter still covers every occurrence. Composite keys are kept when
+/// they are keys in every input, but are marked as estimated because
+/// uniqueness across inputs cannot be verified.
+pub(crate) fn merge_candidate_keys(
+    inputs: &[Vec<CandidateKey>],
+    merged: &InferredSchema,
+) -> Vec<CandidateKey> {
+    let Some(first) = inputs.first() else {
+        return Vec::new();
+    };
+
+    let mut keys = Vec::new();
+    for key in first {
+        if !inputs
+            .iter()
+            .all(|keys| keys.iter().any(|k| k.fields == key.fields))
+        {
+            continue;
+        }
+
+        if key.is_composite() {
+            keys.push(CandidateKey {
+                fields: key.fields.clone(),
+                estimated: true,
+            });
+            continue;
+        }
+
+        let path = format!("$.{}", key.fields[0]);
+        let (Some(stats), Some(counter)) = (
+            merged.field_stats.get(&path),
+            merged.value_sketches.get(&path),
+        ) else {
+            continue;
+        };
+        // The key was complete in every input, so compare against its own occurrences
+        let occurrences = stats.occurrences;
+        let stats = [(key.fields[0].clone(), stats, counter)];
+        keys.extend(find_candidate_keys(occurrences, &stats, &[], 1));
+    }
+
+    rank_keys(&mut keys);
+    keys
+}
+
+/// Order keys so the most likely primary key comes first
+fn rank_keys(keys: &mut [CandidateKey]) {
+    keys.sort_by(|a, b| {
+        a.fields
+            .len()
+            .cmp(&b.fields.len())
+            .then(key_name_score(a).cmp(&key_name_score(b)))
+            .then(a.estimated.cmp(&b.estimated))
+            .then(a.fields.cmp(&b.fields))
+    });
+}
+
+/// Lower scores for id-like field names
+fn key_name_score(key: &CandidateKey) -> usize {
+    key.fields
+        .iter()
+        .map(|field| {
+            let name = normalize(field);
+            if name == "id" {
+                0
+            } else if name.ends_with("id") || name.ends_with("key") {
+                1
+            } else {
+                2
+            }
+        })
+        .sum()
+}
+
+fn is_unique_combination(fields: &[String], key_rows: &[BTreeMap<String, u64>]) -> bool {
+    let mut seen = HashSet::with_capacity(key_rows.len());
+    for row in key_rows {
+        let mut hashes = Vec::with_capacity(fields.len());
+        for field in fields {
+            match row.get(field) {
+                Some(hash) => hashes.push(*hash),
+                None => return false,
+            }
+        }
+        if !seen.insert(combine_hashes(&hashes)) {
+            return false;
+        }
+    }
+    true
+}
+
+fn combinations(names: &[String], size: usize) -> Vec<Vec<String>> {
+    if size == 0 {
+        return vec![Vec::new()];
+    }
+    let mut result = Vec::new();
+    for (i, name) in names.iter().enumerate() {
+        for mut rest in combinations(&names[i + 1..], size - 1) {
+            rest.insert(0, name.clone());
+            result.push(rest);
+        }
+    }
+    result
+}
+
+/// Whether a source field name refers to the target schema's key
+///
+/// Matches `id` -> `id`, `customer_id` -> `customers.id` and
+/// `customerId` -> `customer.id`.
+fn names_match(source_path: &str, target_schema: &str, target_field: &str) -> bool {
+    let leaf = source_path
+        .rsplit('.')
+        .next()
+        .unwrap_or(source_path)
+        .trim_end_matches("[]");
+    let source = normalize(leaf);
+    let field = normalize(target_field);
+    if source == field && field != "id" {
+        return true;
+    }
+
+    let schema = normalize(target_schema);
+    let singular = schema.strip_suffix('s').unwrap_or(&schema);
+    let mut prefixes = vec![schema.clone(), singular.to_string()];
+    prefixes.dedup();
+    prefixes
+        .iter()
+        .any(|prefix| source == format!("{}{}", prefix, field))
+}
+
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inference::{InferenceConfig, SchemaInferrer};
+
+    fn infer(records: &[String]) -> InferredSchema {
+        let mut inferrer = SchemaInferrer::with_config(InferenceConfig::default());
+        for record in records {
+            inferrer.add_json(record).unwrap();
+        }
+        inferrer.finalize().unwrap()
+    }
+
+    #[test]
+    fn test_single_and_composite_candidate_keys() {
+        let records: Vec<String> = (0..6)
+            .map(|i| {
+                format!(
+                    r#"{{"id": {}, "order": {}, "line": {}, "note": "n", "opt": {}}}"#,
+                    i,
+                    i / 2,
+                    i % 2,
+                    if i == 0 {
+                        "null".to_string()
+                    } else {
+                        i.to_string()
+                    }
+                )
+            })
+            .collect();
+        let schema = infer(&records);
+
+        assert_eq!(schema.field_stats["$.id"].distinct_count, Some(6));
+        assert_eq!(schema.candidate_keys[0].fields, vec!["id"]);
+        assert!(!schema.candidate_keys[0].estimated);
+        assert!(
+            schema
+                .candidate_keys
+                .iter()
+                .any(|k| k.fields == vec!["line", "order"])
+        );
+        // Nullable fields are never keys
+        assert!(
+            !schema
+                .candidate_keys
+                .iter()
+                .any(|k| k.fields.contains(&"opt".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_discover_foreign_keys() {
+        let customers: Vec<String> = (0..30)
+            .map(|i| format!(r#"{{"id": {}, "name": "c{}"}}"#, i, i))
+            .collect();
+        let orders: Vec<String> = (0..40)
+            .map(|i| {
+                format!(
+                    r#"{{"order_id": {}, "customer_id": {}, "qty": {}}}"#,
+                    1000 + i,
+                    i % 25,
+                    i % 3
+                )
+            })
+            .collect();
+        let customers = infer(&customers);
+        let orders = infer(&orders);
+
+        let fks = discover_foreign_keys(&[("customers", &customers), ("orders", &orders)]);
+        assert_eq!(fks.len(), 1);
+        assert_eq!(fks[0].source_schema, "orders");
+        assert_eq!(fks[0].source_field, "$.customer_id");
+        assert_eq!(fks[0].target_field, "$.id");
+        assert!(fks[0].name_match);
+
+        let tables = vec![customers.to_table("customers"), orders.to_table("orders")];
+        let relationships = foreign_key_relationships(&fks, &tables);
+        assert_eq!(relationships.len(), 1);
+        assert_eq!(relationships[0].source_table_id, tables[1].id);
+        assert_eq!(relationships[0].target_table_id, tables[0].id);
+        let details = relationships[0].foreign_key_details.as_ref().unwrap();
+        assert_eq!(details.source_column, "customer_id");
+        assert_eq!(details.target_column, "id");
+    }
+
+    #[test]
+    fn test_names_match() {
+        assert!(names_match("$.customer_id", "customers", "id"));
+        assert!(names_match("$.customerId", "customer", "id"));
+        assert!(names_match("$.items[].sku", "products", "sku"));
+        assert!(!names_match("$.id", "customers", "id"));
+        assert!(!names_match("$.qty", "customers", "id"));
+    }
+}
@@ -5,7 +5,9 @@
 
 use std::collections::BTreeMap;
 
+use super::keys::merge_candidate_keys;
 use super::types::{InferredField, InferredSchema, InferredType};
+use super::variants::merge_variants;
 
 /// Merge multiple schemas into a single unified schema
 ///
@@ -39,8 +41,23 @@ pub fn merge_schemas(schemas: Vec<InferredSchema>) -> InferredSchema {
 
     result.root = merged_root;
 
+    let input_keys: Vec<_> = schemas.iter().map(|s| s.candidate_keys.clone()).collect();
+
+    if let Some((discriminator, variants)) = merge_variants(&schemas) {
+        result.discriminator = Some(discriminator);
+        result.variants = variants;
+    }
+
     // Merge field stats
     for schema in schemas {
+        for (key, counter) in schema.value_sketches {
+            result
+                .value_sketches
+                .entry(key)
+                .and_modify(|existing| existing.merge(&counter))
+                .or_insert(counter);
+        }
+
         for (key, stats) in schema.field_stats {
             result
                 .field_stats
@@ -48,17 +65,37 @@ pub fn merge_schemas(schemas: Vec<InferredSchema>) -> InferredSchema {
                 .and_modify(|existing| {
                     existing.occurrences += stats.occurrences;
                     existing.null_count += stats.null_count;
+                    // Distinct counts cannot be added; recomputed from sketches below
+                    existing.distinct_count = None;
                     if let (Some(min), Some(other_min)) = (&mut existing.min, stats.min) {
                         *min = min.min(other_min);
                     }
                     if let (Some(max), Some(other_max)) = (&mut existing.max, stats.max) {
                         *max = max.max(other_max);
                     }
+                    // Only keep enum candidates that are low-cardinality in every schema
+                    if existing.enum_values.is_empty() || stats.enum_values.is_empty() {
+                        existing.enum_values.clear();
+                    } else {
+                        for value in &stats.enum_values {
+                            if !existing.enum_values.contains(value) {
+                                existing.enum_values.push(value.clone());
+                            }
+                        }
+                        existing.enum_values.sort();
+                    }
                 })
                 .or_insert(stats);
         }
     }
 
+    for (key, counter) in &result.value_sketches {
+        if let Some(stats) = result.field_stats.get_mut(key) {
+            stats.distinct_count = Some(counter.count());
+        }
+    }
+    result.candidate_keys = merge_candidate_keys(&input_keys, &result);
+
     result
 }
 
@@ -173,11 +210,21 @@ fn types_compatible(a: &InferredType, b: &InferredType) -> bool {
 ///
 /// Returns groups of schema indices that should be merged together.
 pub fn group_similar_schemas(schemas: &[InferredSchema], threshold: f64) -> Vec<Vec<usize>> {
-    if schemas.is_empty() {
+    group_by_similarity(schemas, threshold, schema_similarity)
+}
+
+/// Group items greedily: each ungrouped item collects every later item whose
+/// similarity to it meets the threshold
+pub(crate) fn group_by_similarity<T>(
+    items: &[T],
+    threshold: f64,
+    similarity: impl Fn(&T, &T) -> f64,
+) -> Vec<Vec<usize>> {
+    if items.is_empty() {
         return Vec::new();
     }
 
-    let n = schemas.len();
+    let n = items.len();
     let mut visited = vec![false; n];
     let mut groups = Vec::new();
 
@@ -194,7 +241,7 @@ pub fn group_similar_schemas(schemas: &[InferredSchema], threshold: f64) -> Vec<
                 continue;
             }
 
-            if schema_similarity(&schemas[i], &schemas[j]) >= threshold {
+            if similarity(&items[i], &items[j]) >= threshold {
                 group.push(j);
                 visited[j] = true;
             }
@@ -222,6 +269,7 @@ mod tests {
             record_count: 1,
             partition: None,
             field_stats: std::collections::HashMap::new(),
+            ..Default::default()
         }
     }
 
@@ -279,6 +327,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_merge_candidate_keys() {
+        use crate::inference::SchemaInferrer;
+
+        let infer = |ids: std::ops::Range<i32>| {
+            let mut inferrer = SchemaInferrer::new();
+            for id in ids {
+                inferrer.add_json(&format!(r#"{{"id": {}}}"#, id)).unwrap();
+            }
+            inferrer.finalize().unwrap()
+        };
+
+        let merged = merge_schemas(vec![infer(0..5), infer(5..10)]);
+        assert_eq!(merged.field_stats["$.id"].distinct_count, Some(10));
+        assert_eq!(merged.candidate_keys[0].fields, vec!["id"]);
+
+        let overlapping = merge_schemas(vec![infer(0..5), infer(3..8)]);
+        assert_eq!(overlapping.field_stats["$.id"].distinct_count, Some(8));
+        assert!(overlapping.candidate_keys.is_empty());
+    }
+
     #[test]
     fn test_schema_similarity_identical() {
         let s1 = make_object_schema(&[
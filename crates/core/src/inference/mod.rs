@@ -10,6 +10,10 @@
 //! - **Schema merging** - Combine schemas to find the minimum common schema
 //! - **Nullability tracking** - Track optional vs required fields
 //! - **Example collection** - Gather sample values for documentation
+//! - **ODCS conversion** - Turn inferred schemas directly into tables and contracts
+//! - **Key discovery** - Find candidate primary keys and cross-schema foreign keys
+//! - **Incremental state** - Serialise and merge inferrer state to fold in new batches
+//! - **Variant detection** - Split discriminated unions (e.g. by `$.type`) into sub-schemas
 //!
 //! ## Example
 //!
@@ -28,18 +32,27 @@
 //! ```
 
 mod config;
+mod convert;
+mod distinct;
 mod error;
 mod formats;
 mod inferrer;
+mod keys;
 mod merge;
 mod types;
+mod variants;
 
 pub use config::{InferenceConfig, InferenceConfigBuilder};
+pub use convert::{apply_schema_descriptions, contract_from_tables};
+pub use distinct::DistinctCounter;
 pub use error::InferenceError;
 pub use formats::{Format, detect_format};
 pub use inferrer::{InferenceStats, ParallelSchemaInferrer, SchemaInferrer};
+pub use keys::{ForeignKeyCandidate, discover_foreign_keys, foreign_key_relationships};
 pub use merge::{group_similar_schemas, merge_schemas};
-pub use types::{InferredField, InferredSchema, InferredType};
+pub use types::{
+    CandidateKey, FieldStats, InferredField, InferredSchema, InferredType, SchemaVariant,
+};
 
 // Re-export parallel inference functions when staging feature is enabled
 #[cfg(feature = "staging")]
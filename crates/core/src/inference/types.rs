@@ -7,6 +7,7 @@ use std::collections::{BTreeMap, HashMap};
 
 use serde::{Deserialize, Serialize};
 
+use super::distinct::DistinctCounter;
 use super::formats::Format;
 
 /// Inferred JSON type
@@ -242,6 +243,46 @@ pub struct InferredSchema {
     /// Field statistics
     #[serde(skip_serializing_if = "HashMap::is_empty")]
     pub field_stats: HashMap<String, FieldStats>,
+    /// Candidate primary keys, most likely first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub candidate_keys: Vec<CandidateKey>,
+    /// Distinct value counters for key-eligible fields (used for foreign key discovery)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub value_sketches: HashMap<String, DistinctCounter>,
+    /// Top-level field whose value selects the record shape (discriminated union)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub discriminator: Option<String>,
+    /// Per-variant sub-schemas when a discriminator was detected
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variants: Vec<SchemaVariant>,
+}
+
+/// One shape of a discriminated union
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaVariant {
+    /// Discriminator values that select this variant
+    pub discriminator_values: Vec<String>,
+    /// Schema inferred from the records of this variant
+    pub schema: InferredSchema,
+}
+
+/// A set of top-level fields that is non-null and unique over the sample
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CandidateKey {
+    /// Field names making up the key, in key order
+    pub fields: Vec<String>,
+    /// Whether uniqueness was estimated from a sketch rather than checked exactly
+    #[serde(default)]
+    pub estimated: bool,
+}
+
+impl CandidateKey {
+    /// Whether the key has more than one field
+    pub fn is_composite(&self) -> bool {
+        self.fields.len() > 1
+    }
 }
 
 /// Statistics for a field
@@ -263,6 +304,9 @@ pub struct FieldStats {
     /// Average value (for numbers)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub avg: Option<f64>,
+    /// Observed values of a low-cardinality string field (enumeration candidates)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub enum_values: Vec<String>,
 }
 
 impl InferredSchema {
@@ -275,10 +319,18 @@ impl InferredSchema {
             record_count: 0,
             partition: None,
             field_stats: HashMap::new(),
+            candidate_keys: Vec::new(),
+            value_sketches: HashMap::new(),
+            discriminator: None,
+            variants: Vec::new(),
         }
     }
 
     /// Convert to JSON Schema format
+    ///
+    /// When a discriminator was detected the root becomes a `oneOf` over the
+    /// variant schemas, each pinning the discriminator with `const` (or `enum`
+    /// when several values share a shape).
     pub fn to_json_schema(&self) -> serde_json::Value {
         let mut schema = serde_json::json!({
             "$schema": "https://json-schema.org/draft/2020-12/schema"
@@ -291,11 +343,55 @@ impl InferredSchema {
             schema["description"] = serde_json::Value::String(desc.clone());
         }
 
-        self.type_to_json_schema(&self.root, &mut schema);
+        match (&self.discriminator, self.variants.is_empty()) {
+            (Some(discriminator), false) => {
+                self.variants_to_json_schema(discriminator, &mut schema)
+            }
+            _ => self.type_to_json_schema(&self.root, &mut schema),
+        }
 
         schema
     }
 
+    fn variants_to_json_schema(&self, discriminator: &str, schema: &mut serde_json::Value) {
+        let mut all_values = Vec::new();
+        let mut one_of = Vec::new();
+
+        for variant in &self.variants {
+            let mut sub_schema = serde_json::json!({
+                "title": variant.discriminator_values.join(" | ")
+            });
+            variant
+                .schema
+                .type_to_json_schema(&variant.schema.root, &mut sub_schema);
+
+            sub_schema["properties"][discriminator] = match variant.discriminator_values.as_slice()
+            {
+                [value] => serde_json::json!({ "type": "string", "const": value }),
+                values => serde_json::json!({ "type": "string", "enum": values }),
+            };
+            let required = sub_schema["required"]
+                .as_array()
+                .cloned()
+                .unwrap_or_default();
+            if !required.iter().any(|r| r == discriminator) {
+                let mut required = required;
+                required.insert(0, serde_json::json!(discriminator));
+                sub_schema["required"] = serde_json::Value::Array(required);
+            }
+
+            all_values.extend(variant.discriminator_values.iter().cloned());
+            one_of.push(sub_schema);
+        }
+
+        schema["type"] = serde_json::json!("object");
+        schema["properties"] = serde_json::json!({
+            discriminator: { "type": "string", "enum": all_values }
+        });
+        schema["required"] = serde_json::json!([discriminator]);
+        schema["oneOf"] = serde_json::Value::Array(one_of);
+    }
+
     fn type_to_json_schema(&self, inferred: &InferredType, schema: &mut serde_json::Value) {
         match inferred {
             InferredType::Null => {
@@ -436,6 +532,7 @@ mod tests {
             record_count: 10,
             partition: None,
             field_stats: HashMap::new(),
+            ..Default::default()
         };
 
         let json_schema = schema.to_json_schema();
@@ -444,4 +541,43 @@ mod tests {
         assert!(json_schema["properties"]["name"].is_object());
         assert!(json_schema["properties"]["age"].is_object());
     }
+
+    #[test]
+    fn test_variants_to_json_schema() {
+        let variant = |values: &[&str], field: &str| {
+            let mut properties = BTreeMap::new();
+            properties.insert(
+                "type".to_string(),
+                InferredField::new(InferredType::String { format: None }),
+            );
+            properties.insert(field.to_string(), InferredField::new(InferredType::Integer));
+            SchemaVariant {
+                discriminator_values: values.iter().map(|v| v.to_string()).collect(),
+                schema: InferredSchema {
+                    root: InferredType::Object { properties },
+                    ..Default::default()
+                },
+            }
+        };
+
+        let schema = InferredSchema {
+            discriminator: Some("type".to_string()),
+            variants: vec![
+                variant(&["click"], "x"),
+                variant(&["view", "impression"], "ms"),
+            ],
+            ..Default::default()
+        };
+
+        let json_schema = schema.to_json_schema();
+        assert_eq!(json_schema["required"], serde_json::json!(["type"]));
+        let one_of = json_schema["oneOf"].as_array().unwrap();
+        assert_eq!(one_of.len(), 2);
+        assert_eq!(one_of[0]["properties"]["type"]["const"], "click");
+        assert!(one_of[0]["properties"]["x"].is_object());
+        assert_eq!(
+            one_of[1]["properties"]["type"]["enum"],
+            serde_json::json!(["view", "impression"])
+        );
+    }
 }
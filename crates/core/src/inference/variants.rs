@@ -0,0 +1,337 @@
+//! Discriminated union detection
+//!
+//! Event streams often mix several record shapes in one partition, with a
+//! top-level field such as `type` deciding which shape applies. Merging them
+//! into one schema turns every shape-specific field into an optional or
+//! `Mixed` field.
+//!
+//! While records are added, each candidate discriminator field routes the
+//! record into a sub-inferrer keyed by the field's value. At finalize the
+//! per-value schemas are grouped by field-path similarity; the first candidate
+//! whose values split the records into at least two dissimilar shapes becomes
+//! the discriminator and each group becomes a [`SchemaVariant`].
+
+use std::collections::{BTreeMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::config::InferenceConfig;
+use super::error::InferenceError;
+use super::inferrer::SchemaInferrer;
+use super::merge::{group_by_similarity, merge_schemas};
+use super::types::{InferredSchema, SchemaVariant};
+
+/// Records routed by the values of one candidate discriminator field
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Candidate {
+    field: String,
+    inferrers: BTreeMap<String, SchemaInferrer>,
+    rejected: bool,
+}
+
+/// Tracks candidate discriminator fields while records are added
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct VariantTracker {
+    candidates: Vec<Candidate>,
+    /// Configuration for the per-value sub-inferrers
+    variant_config: InferenceConfig,
+    explicit: bool,
+    max_variants: usize,
+    similarity_threshold: f64,
+}
+
+impl VariantTracker {
+    /// Create a tracker for the configured discriminator, or `None` when disabled
+    pub(crate) fn new(config: &InferenceConfig) -> Option<Self> {
+        if !config.detect_variants {
+            return None;
+        }
+
+        let fields = match &config.discriminator_field {
+            Some(field) => vec![field.clone()],
+            None => config.discriminator_candidates.clone(),
+        };
+        if fields.is_empty() {
+            return None;
+        }
+
+        let mut variant_config = config.clone();
+        variant_config.detect_variants = false;
+        variant_config.sample_size = 0;
+
+        Some(Self {
+            candidates: fields
+                .into_iter()
+                .map(|field| Candidate {
+                    field,
+                    inferrers: BTreeMap::new(),
+                    rejected: false,
+                })
+                .collect(),
+            variant_config,
+            explicit: config.discriminator_field.is_some(),
+            max_variants: config.max_variants,
+            similarity_threshold: config.variant_similarity_threshold,
+        })
+    }
+
+    /// Route a record to the sub-inferrer of each candidate field's value
+    ///
+    /// A candidate is rejected once a record lacks a string value for it or
+    /// its distinct values exceed `max_variants`.
+    pub(crate) fn observe(&mut self, record: &Value) -> Result<(), InferenceError> {
+        for candidate in &mut self.candidates {
+            if candidate.rejected {
+                continue;
+            }
+
+            let Some(Value::String(value)) = record.get(&candidate.field) else {
+                candidate.reject();
+                continue;
+            };
+            if !candidate.inferrers.contains_key(value)
+                && candidate.inferrers.len() >= self.max_variants
+            {
+                candidate.reject();
+                continue;
+            }
+
+            candidate
+                .inferrers
+                .entry(value.clone())
+                .or_insert_with(|| SchemaInferrer::with_config(self.variant_config.clone()))
+                .add_value(record)?;
+        }
+        Ok(())
+    }
+
+    /// Fold another tracker's per-value inferrers into this one
+    pub(crate) fn merge(&mut self, other: VariantTracker) {
+        for other_candidate in other.candidates {
+            let Some(candidate) = self
+                .candidates
+                .iter_mut()
+                .find(|c| c.field == other_candidate.field)
+            else {
+                continue;
+            };
+            if candidate.rejected {
+                continue;
+            }
+            if other_candidate.rejected {
+                candidate.reject();
+                continue;
+            }
+
+            for (value, inferrer) in other_candidate.inferrers {
+                if let Some(existing) = candidate.inferrers.get_mut(&value) {
+                    existing.merge(inferrer);
+                } else if candidate.inferrers.len() >= self.max_variants {
+                    candidate.reject();
+                    break;
+                } else {
+                    candidate.inferrers.insert(value, inferrer);
+                }
+            }
+        }
+    }
+
+    /// Pick the discriminator and build its variants
+    ///
+    /// Returns `None` when no candidate splits the records into at least two
+    /// distinct shapes. An explicitly configured discriminator keeps one
+    /// variant per value instead of grouping similar values together.
+    pub(crate) fn finalize(self) -> Result<Option<(String, Vec<SchemaVariant>)>, InferenceError> {
+        for candidate in self.candidates {
+            if candidate.rejected || candidate.inferrers.len() < 2 {
+                continue;
+            }
+
+            let discriminator_path = format!("$.{}", candidate.field);
+            let mut values = Vec::new();
+            let mut schemas = Vec::new();
+            for (value, inferrer) in candidate.inferrers {
+                let mut schema = inferrer.finalize()?;
+                schema.name = Some(value.clone());
+                values.push(value);
+                schemas.push(schema);
+            }
+
+            let groups = if self.explicit {
+                (0..schemas.len()).map(|i| vec![i]).collect()
+            } else {
+                let paths: Vec<HashSet<&str>> = schemas
+                    .iter()
+                    .map(|s| {
+                        s.field_stats
+                            .keys()
+                            .map(String::as_str)
+                            .filter(|p| *p != discriminator_path)
+                            .collect()
+                    })
+                    .collect();
+                group_by_similarity(&paths, self.similarity_threshold, path_similarity)
+            };
+            if groups.len() < 2 {
+                continue;
+            }
+
+            let mut schemas: Vec<Option<InferredSchema>> = schemas.into_iter().map(Some).collect();
+            let variants = groups
+                .into_iter()
+                .map(|group| {
+                    let discriminator_values = group.iter().map(|&i| values[i].clone()).collect();
+                    let members = group.iter().filter_map(|&i| schemas[i].take()).collect();
+                    let mut schema = merge_schemas(members);
+                    schema.name = None;
+                    SchemaVariant {
+                        discriminator_values,
+                        schema,
+                    }
+                })
+                .collect();
+
+            return Ok(Some((candidate.field, variants)));
+        }
+
+        Ok(None)
+    }
+}
+
+impl Candidate {
+    fn reject(&mut self) {
+        self.rejected = true;
+        self.inferrers.clear();
+    }
+}
+
+/// Jaccard similarity of two sets of field paths
+fn path_similarity(a: &HashSet<&str>, b: &HashSet<&str>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 1.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+/// Merge the variants of schemas that share a discriminator
+///
+/// Variants with overlapping discriminator values are merged into one.
+/// Returns `None` unless every schema has the same discriminator.
+pub(crate) fn merge_variants(schemas: &[InferredSchema]) -> Option<(String, Vec<SchemaVariant>)> {
+    let discriminator = schemas.first()?.discriminator.clone()?;
+    if schemas
+        .iter()
+        .any(|s| s.discriminator.as_deref() != Some(discriminator.as_str()))
+    {
+        return None;
+    }
+
+    let mut merged: Vec<SchemaVariant> = Vec::new();
+    for variant in schemas.iter().flat_map(|s| s.variants.iter()) {
+        let existing = merged.iter_mut().find(|m| {
+            m.discriminator_values
+                .iter()
+                .any(|v| variant.discriminator_values.contains(v))
+        });
+        match existing {
+            Some(existing) => {
+                for value in &variant.discriminator_values {
+                    if !existing.discriminator_values.contains(value) {
+                        existing.discriminator_values.push(value.clone());
+                    }
+                }
+                let schema = std::mem::take(&mut existing.schema);
+                existing.schema = merge_schemas(vec![schema, variant.schema.clone()]);
+            }
+            None => merged.push(variant.clone()),
+        }
+    }
+
+    Some((discriminator, merged))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::inference::{InferenceConfig, InferredType, SchemaInferrer};
+
+    fn infer(config: InferenceConfig, records: &[&str]) -> crate::inference::InferredSchema {
+        let mut inferrer = SchemaInferrer::with_config(config);
+        for record in records {
+            inferrer.add_json(record).unwrap();
+        }
+        inferrer.finalize().unwrap()
+    }
+
+    const EVENTS: &[&str] = &[
+        r#"{"type": "click", "id": 1, "x": 10, "y": 20}"#,
+        r#"{"type": "click", "id": 2, "x": 15, "y": 25}"#,
+        r#"{"type": "purchase", "id": 3, "order": {"sku": "A1", "amount": 9.99}}"#,
+        r#"{"type": "refund", "id": 4, "order": {"sku": "B2", "amount": 5.0}}"#,
+    ];
+
+    #[test]
+    fn test_detects_discriminator_and_groups_similar_values() {
+        let schema = infer(InferenceConfig::default(), EVENTS);
+
+        assert_eq!(schema.discriminator.as_deref(), Some("type"));
+        assert_eq!(schema.variants.len(), 2);
+        assert_eq!(schema.variants[0].discriminator_values, vec!["click"]);
+        assert_eq!(
+            schema.variants[1].discriminator_values,
+            vec!["purchase", "refund"]
+        );
+
+        let click = &schema.variants[0].schema;
+        assert_eq!(click.record_count, 2);
+        if let InferredType::Object { properties } = &click.root {
+            assert!(properties["x"].required);
+            assert!(!properties.contains_key("order"));
+        } else {
+            panic!("Expected object type");
+        }
+    }
+
+    #[test]
+    fn test_same_shape_values_are_not_variants() {
+        let schema = infer(
+            InferenceConfig::default(),
+            &[
+                r#"{"type": "a", "id": 1}"#,
+                r#"{"type": "b", "id": 2}"#,
+                r#"{"type": "c", "id": 3}"#,
+            ],
+        );
+        assert!(schema.discriminator.is_none());
+        assert!(schema.variants.is_empty());
+    }
+
+    #[test]
+    fn test_explicit_discriminator_and_limits() {
+        let config = InferenceConfig::builder()
+            .discriminator_field("type")
+            .build();
+        let schema = infer(config, EVENTS);
+        assert_eq!(schema.variants.len(), 3);
+
+        let config = InferenceConfig::builder().max_variants(2).build();
+        assert!(infer(config, EVENTS).variants.is_empty());
+
+        let config = InferenceConfig::builder().detect_variants(false).build();
+        assert!(infer(config, EVENTS).discriminator.is_none());
+    }
+
+    #[test]
+    fn test_merge_schemas_merges_variants() {
+        let merged = crate::inference::merge_schemas(vec![
+            infer(InferenceConfig::default(), &EVENTS[..3]),
+            infer(InferenceConfig::default(), &EVENTS[1..]),
+        ]);
+        assert_eq!(merged.discriminator.as_deref(), Some("type"));
+        assert_eq!(merged.variants.len(), 2);
+        assert_eq!(merged.variants[0].schema.record_count, 3);
+    }
+}
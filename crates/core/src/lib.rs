@@ -24,6 +24,7 @@ pub mod llm;
 pub mod mapping;
 pub mod model;
 pub mod models;
+pub mod pii;
 #[cfg(feature = "pipeline")]
 pub mod pipeline;
 #[cfg(any(feature = "staging", feature = "staging-postgres"))]
@@ -0,0 +1,597 @@
+//! Deterministic LLM response cache
+//!
+//! LLM calls are slow and, even at low temperature, not reproducible. The
+//! [`CachingLlmClient`] wraps any [`LlmClient`] and stores each response under
+//! a key derived from the model name, temperature and a hash of the prompt, so
+//! a later run with the same inputs gets the same output without calling the
+//! model. Embeddings are cached per text under the embedding model name, so
+//! documentation retrieval ranks chunks the same way when replaying.
+//!
+//! Responses are kept in a [`ResponseStore`]: [`FileResponseStore`] writes one
+//! JSON file per response (suitable for committing recordings next to CI
+//! fixtures) and, with the `staging` feature, [`StagingResponseStore`] keeps
+//! them in the staging DuckDB.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use data_modelling_core::llm::{CacheMode, CachingLlmClient, OllamaClient};
+//!
+//! // Record responses locally...
+//! let client = CachingLlmClient::with_file_store(
+//!     OllamaClient::new("http://localhost:11434", "llama3.2"),
+//!     "tests/llm-recordings",
+//!     CacheMode::Record,
+//! );
+//!
+//! // ...and replay them in CI without a model
+//! let config = RefinementConfig::with_ollama("llama3.2")
+//!     .with_cache("tests/llm-recordings", CacheMode::Replay);
+//! let client = create_client(&config)?;
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::client::LlmClient;
+use super::error::{LlmError, LlmResult};
+
+/// How the cache interacts with the wrapped client
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheMode {
+    /// Serve cached responses and record new ones on a miss
+    #[default]
+    ReadWrite,
+    /// Always call the model and overwrite cached responses
+    Record,
+    /// Only serve cached responses; a miss is an error and the model is never called
+    Replay,
+}
+
+impl FromStr for CacheMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "readwrite" | "read-write" | "read_write" => Ok(Self::ReadWrite),
+            "record" => Ok(Self::Record),
+            "replay" => Ok(Self::Replay),
+            _ => Err(format!(
+                "Invalid cache mode: {}. Use readwrite, record or replay",
+                s
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for CacheMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ReadWrite => write!(f, "readwrite"),
+            Self::Record => write!(f, "record"),
+            Self::Replay => write!(f, "replay"),
+        }
+    }
+}
+
+/// Where and how to cache LLM responses
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LlmCacheConfig {
+    /// Cache directory, or a `.duckdb` staging database (requires `staging` feature)
+    pub path: PathBuf,
+    /// Cache mode
+    #[serde(default)]
+    pub mode: CacheMode,
+}
+
+impl LlmCacheConfig {
+    /// Create a cache configuration
+    pub fn new(path: impl Into<PathBuf>, mode: CacheMode) -> Self {
+        Self {
+            path: path.into(),
+            mode,
+        }
+    }
+
+    /// Open the store this configuration points at
+    pub fn open_store(&self) -> LlmResult<Box<dyn ResponseStore>> {
+        if self.path.extension().and_then(|e| e.to_str()) == Some("duckdb") {
+            #[cfg(feature = "staging")]
+            {
+                return Ok(Box::new(StagingResponseStore::open(&self.path)?));
+            }
+            #[cfg(not(feature = "staging"))]
+            {
+                return Err(LlmError::FeatureNotAvailable(
+                    "DuckDB LLM cache".to_string(),
+                    "staging".to_string(),
+                ));
+            }
+        }
+        Ok(Box::new(FileResponseStore::new(&self.path)))
+    }
+}
+
+/// A cached LLM response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedResponse {
+    /// Cache key (see [`cache_key`])
+    pub key: String,
+    /// Model that produced the response
+    pub model: String,
+    /// Sampling temperature, if the client reports one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    /// SHA-256 of the prompt
+    pub prompt_hash: String,
+    /// Response text
+    pub response: String,
+    /// When the response was recorded
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Persistent storage for cached responses
+pub trait ResponseStore: Send + Sync {
+    /// Look up a response by key
+    fn get(&self, key: &str) -> LlmResult<Option<CachedResponse>>;
+
+    /// Store a response, replacing any existing entry with the same key
+    fn put(&self, entry: &CachedResponse) -> LlmResult<()>;
+}
+
+/// Hex-encoded SHA-256 of `data`
+fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Compute the cache key for a model, temperature and prompt
+///
+/// Returns `(key, prompt_hash)`.
+pub fn cache_key(model: &str, temperature: Option<f32>, prompt: &str) -> (String, String) {
+    let prompt_hash = sha256_hex(prompt.as_bytes());
+    let temperature = temperature.map(|t| format!("{:.3}", t)).unwrap_or_default();
+    let key = sha256_hex(format!("{}\n{}\n{}", model, temperature, prompt_hash).as_bytes());
+    (key, prompt_hash)
+}
+
+/// Compute the cache key for the embedding of one text
+///
+/// Returns `(key, text_hash)`. Embedding keys never collide with completion
+/// keys because they hash a different prefix.
+pub fn embedding_cache_key(model: &str, text: &str) -> (String, String) {
+    let text_hash = sha256_hex(text.as_bytes());
+    let key = sha256_hex(format!("embedding\n{}\n{}", model, text_hash).as_bytes());
+    (key, text_hash)
+}
+
+/// Stores each response as `<key>.json` in a directory
+#[derive(Debug, Clone)]
+pub struct FileResponseStore {
+    dir: PathBuf,
+}
+
+impl FileResponseStore {
+    /// Create a store in `dir` (created on first write)
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Get the cache directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+}
+
+impl ResponseStore for FileResponseStore {
+    fn get(&self, key: &str) -> LlmResult<Option<CachedResponse>> {
+        let path = self.entry_path(key);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(&path)?;
+        Ok(Some(serde_json::from_str(&content)?))
+    }
+
+    fn put(&self, entry: &CachedResponse) -> LlmResult<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        let content = serde_json::to_string_pretty(entry)?;
+        std::fs::write(self.entry_path(&entry.key), content)?;
+        Ok(())
+    }
+}
+
+/// Stores responses in the `llm_responses` table of a staging database
+#[cfg(feature = "staging")]
+pub struct StagingResponseStore {
+    db: std::sync::Mutex<crate::staging::StagingDb>,
+}
+
+#[cfg(feature = "staging")]
+impl StagingResponseStore {
+    /// Wrap an open staging database, creating the cache table if needed
+    pub fn new(db: crate::staging::StagingDb) -> LlmResult<Self> {
+        db.init()
+            .map_err(|e| LlmError::IoError(format!("Failed to initialise LLM cache: {}", e)))?;
+        Ok(Self {
+            db: std::sync::Mutex::new(db),
+        })
+    }
+
+    /// Open (or create) a staging database and use it as the store
+    pub fn open(path: &Path) -> LlmResult<Self> {
+        let db = crate::staging::StagingDb::open(&path.display().to_string())
+            .map_err(|e| LlmError::IoError(format!("Failed to open LLM cache: {}", e)))?;
+        Self::new(db)
+    }
+}
+
+#[cfg(feature = "staging")]
+impl ResponseStore for StagingResponseStore {
+    fn get(&self, key: &str) -> LlmResult<Option<CachedResponse>> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|_| LlmError::IoError("LLM cache lock poisoned".to_string()))?;
+        db.get_llm_response(key)
+            .map_err(|e| LlmError::IoError(e.to_string()))
+    }
+
+    fn put(&self, entry: &CachedResponse) -> LlmResult<()> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|_| LlmError::IoError("LLM cache lock poisoned".to_string()))?;
+        db.put_llm_response(entry)
+            .map_err(|e| LlmError::IoError(e.to_string()))
+    }
+}
+
+/// Cache hit/miss counters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheStats {
+    /// Responses served from the cache
+    pub hits: usize,
+    /// Responses produced by the model and recorded
+    pub recorded: usize,
+    /// Replay lookups that found nothing
+    pub misses: usize,
+}
+
+/// An [`LlmClient`] that caches responses of the wrapped client
+pub struct CachingLlmClient<C: LlmClient> {
+    inner: C,
+    store: Box<dyn ResponseStore>,
+    mode: CacheMode,
+    embedding_model: Option<String>,
+    hits: AtomicUsize,
+    recorded: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+impl<C: LlmClient> CachingLlmClient<C> {
+    /// Wrap a client with a response store
+    pub fn new(inner: C, store: Box<dyn ResponseStore>, mode: CacheMode) -> Self {
+        Self {
+            inner,
+            store,
+            mode,
+            embedding_model: None,
+            hits: AtomicUsize::new(0),
+            recorded: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+        }
+    }
+
+    /// Wrap a client with a [`FileResponseStore`] in `dir`
+    pub fn with_file_store(inner: C, dir: impl Into<PathBuf>, mode: CacheMode) -> Self {
+        Self::new(inner, Box::new(FileResponseStore::new(dir)), mode)
+    }
+
+    /// Set the embedding model name used in embedding cache keys
+    ///
+    /// Defaults to the completion model name.
+    pub fn with_embedding_model(mut self, model: impl Into<String>) -> Self {
+        self.embedding_model = Some(model.into());
+        self
+    }
+
+    /// Get the cache mode
+    pub fn mode(&self) -> CacheMode {
+        self.mode
+    }
+
+    /// Get the hit/miss counters
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            recorded: self.recorded.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Get the wrapped client
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+}
+
+#[async_trait]
+impl<C: LlmClient> LlmClient for CachingLlmClient<C> {
+    async fn complete(&self, prompt: &str) -> LlmResult<String> {
+        let model = self.inner.model_name();
+        let temperature = self.inner.temperature();
+        let (key, prompt_hash) = cache_key(model, temperature, prompt);
+
+        if self.mode != CacheMode::Record {
+            if let Some(entry) = self.store.get(&key)? {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return Ok(entry.response);
+            }
+            if self.mode == CacheMode::Replay {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                return Err(LlmError::CacheMiss(format!(
+                    "no recorded response for model '{}' and prompt {}",
+                    model, prompt_hash
+                )));
+            }
+        }
+
+        let response = self.inner.complete(prompt).await?;
+        self.store.put(&CachedResponse {
+            key,
+            model: model.to_string(),
+            temperature,
+            prompt_hash,
+            response: response.clone(),
+            recorded_at: Utc::now(),
+        })?;
+        self.recorded.fetch_add(1, Ordering::Relaxed);
+
+        Ok(response)
+    }
+
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+
+    fn max_tokens(&self) -> usize {
+        self.inner.max_tokens()
+    }
+
+    fn temperature(&self) -> Option<f32> {
+        self.inner.temperature()
+    }
+
+    async fn embed(&self, texts: &[String]) -> LlmResult<Vec<Vec<f32>>> {
+        let model = self
+            .embedding_model
+            .as_deref()
+            .unwrap_or_else(|| self.inner.model_name());
+        let keys: Vec<(String, String)> = texts
+            .iter()
+            .map(|text| embedding_cache_key(model, text))
+            .collect();
+
+        let mut vectors: Vec<Option<Vec<f32>>> = vec![None; texts.len()];
+        if self.mode != CacheMode::Record {
+            for (vector, (key, _)) in vectors.iter_mut().zip(&keys) {
+                if let Some(entry) = self.store.get(key)? {
+                    *vector = Some(serde_json::from_str(&entry.response)?);
+                    self.hits.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+
+        let missing: Vec<usize> = (0..texts.len()).filter(|&i| vectors[i].is_none()).collect();
+        if !missing.is_empty() {
+            if self.mode == CacheMode::Replay {
+                self.misses.fetch_add(missing.len(), Ordering::Relaxed);
+                return Err(LlmError::CacheMiss(format!(
+                    "no recorded embedding for model '{}' and text {}",
+                    model, keys[missing[0]].1
+                )));
+            }
+
+            let missing_texts: Vec<String> = missing.iter().map(|&i| texts[i].clone()).collect();
+            let embedded = self.inner.embed(&missing_texts).await?;
+            if embedded.len() != missing.len() {
+                return Err(LlmError::InvalidResponse(format!(
+                    "Expected {} embeddings, got {}",
+                    missing.len(),
+                    embedded.len()
+                )));
+            }
+            for (&i, vector) in missing.iter().zip(embedded) {
+                let (key, text_hash) = keys[i].clone();
+                self.store.put(&CachedResponse {
+                    key,
+                    model: model.to_string(),
+                    temperature: None,
+                    prompt_hash: text_hash,
+                    response: serde_json::to_string(&vector)?,
+                    recorded_at: Utc::now(),
+                })?;
+                self.recorded.fetch_add(1, Ordering::Relaxed);
+                vectors[i] = Some(vector);
+            }
+        }
+
+        Ok(vectors.into_iter().flatten().collect())
+    }
+
+    async fn is_ready(&self) -> bool {
+        // Replay never reaches the model
+        self.mode == CacheMode::Replay || self.inner.is_ready().await
+    }
+}
+
+/// Stand-in for a model that is not available, used in replay mode
+///
+/// Reports the model name and temperature the recordings were made with so
+/// cache keys match, but fails every completion.
+pub(crate) struct UnavailableClient {
+    pub(crate) model: String,
+    pub(crate) temperature: Option<f32>,
+    pub(crate) max_tokens: usize,
+}
+
+#[async_trait]
+impl LlmClient for UnavailableClient {
+    async fn complete(&self, _prompt: &str) -> LlmResult<String> {
+        Err(LlmError::ConfigError(format!(
+            "Model '{}' is not available in replay mode",
+            self.model
+        )))
+    }
+
+    fn model_name(&self) -> &str {
+        &self.model
+    }
+
+    fn max_tokens(&self) -> usize {
+        self.max_tokens
+    }
+
+    fn temperature(&self) -> Option<f32> {
+        self.temperature
+    }
+
+    async fn is_ready(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::MockLlmClient;
+
+    #[test]
+    fn test_cache_key() {
+        let (key, hash) = cache_key("llama3.2", Some(0.1), "prompt");
+        assert_eq!(key.len(), 64);
+        assert_eq!(
+            cache_key("llama3.2", Some(0.1), "prompt"),
+            (key.clone(), hash)
+        );
+        assert_ne!(cache_key("mistral", Some(0.1), "prompt").0, key);
+        assert_ne!(cache_key("llama3.2", Some(0.2), "prompt").0, key);
+        assert_ne!(cache_key("llama3.2", Some(0.1), "other").0, key);
+    }
+
+    #[test]
+    fn test_cache_mode_parse() {
+        assert_eq!("replay".parse::<CacheMode>().unwrap(), CacheMode::Replay);
+        assert_eq!(
+            "read-write".parse::<CacheMode>().unwrap(),
+            CacheMode::ReadWrite
+        );
+        assert!("invalid".parse::<CacheMode>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_record_then_replay() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let client = CachingLlmClient::with_file_store(
+            MockLlmClient::new("recorded"),
+            dir.path(),
+            CacheMode::ReadWrite,
+        );
+        assert_eq!(client.complete("prompt").await.unwrap(), "recorded");
+        assert_eq!(client.complete("prompt").await.unwrap(), "recorded");
+        assert_eq!(
+            client.stats(),
+            CacheStats {
+                hits: 1,
+                recorded: 1,
+                misses: 0
+            }
+        );
+
+        // Replay serves the recording even though the model now fails
+        let client = CachingLlmClient::with_file_store(
+            MockLlmClient::failing(),
+            dir.path(),
+            CacheMode::Replay,
+        );
+        assert!(client.is_ready().await);
+        assert_eq!(client.complete("prompt").await.unwrap(), "recorded");
+        assert!(matches!(
+            client.complete("new prompt").await,
+            Err(LlmError::CacheMiss(_))
+        ));
+        assert_eq!(client.stats().misses, 1);
+
+        // Record overwrites existing entries
+        let client = CachingLlmClient::with_file_store(
+            MockLlmClient::new("re-recorded"),
+            dir.path(),
+            CacheMode::Record,
+        );
+        assert_eq!(client.complete("prompt").await.unwrap(), "re-recorded");
+        let client = CachingLlmClient::with_file_store(
+            MockLlmClient::failing(),
+            dir.path(),
+            CacheMode::Replay,
+        );
+        assert_eq!(client.complete("prompt").await.unwrap(), "re-recorded");
+    }
+
+    #[tokio::test]
+    async fn test_create_client_replay_without_model() {
+        let dir = tempfile::tempdir().unwrap();
+        let (key, prompt_hash) = cache_key("qwen2.5", Some(0.1), "prompt");
+        FileResponseStore::new(dir.path())
+            .put(&CachedResponse {
+                key,
+                model: "qwen2.5".to_string(),
+                temperature: Some(0.1),
+                prompt_hash,
+                response: "recorded".to_string(),
+                recorded_at: Utc::now(),
+            })
+            .unwrap();
+
+        // The model file does not exist, which is fine when replaying
+        let config = crate::llm::RefinementConfig::with_local_model("/models/qwen2.5.gguf")
+            .with_temperature(0.1)
+            .with_cache(dir.path(), CacheMode::Replay);
+        let client = crate::llm::create_client(&config).unwrap();
+        assert!(client.is_ready().await);
+        assert_eq!(client.complete("prompt").await.unwrap(), "recorded");
+
+        let config = config.with_cache(dir.path(), CacheMode::ReadWrite);
+        assert!(crate::llm::create_client(&config).is_err());
+    }
+
+    #[cfg(feature = "staging")]
+    #[tokio::test]
+    async fn test_staging_store() {
+        let store =
+            StagingResponseStore::new(crate::staging::StagingDb::memory().unwrap()).unwrap();
+        let client = CachingLlmClient::new(
+            MockLlmClient::new("from duckdb"),
+            Box::new(store),
+            CacheMode::ReadWrite,
+        );
+        client.complete("prompt").await.unwrap();
+        assert_eq!(client.complete("prompt").await.unwrap(), "from duckdb");
+        assert_eq!(client.stats().hits, 1);
+    }
+}
@@ -2,12 +2,16 @@
 //!
 //! This module defines the `LlmClient` trait for interacting with LLMs,
 //! along with implementations for Ollama (online) and llama.cpp (offline).
+//! [`create_client`] builds the client selected by a [`RefinementConfig`].
 
 use async_trait::async_trait;
 
-#[cfg(test)]
-use super::error::LlmError;
-use super::error::LlmResult;
+use super::cache::{CacheMode, CachingLlmClient, UnavailableClient};
+use super::config::{LlmMode, RefinementConfig};
+use super::error::{LlmError, LlmResult};
+use super::llamacpp::LlamaCppClient;
+use super::ollama::OllamaClient;
+use super::openai::{DEFAULT_API_KEY_ENV, OpenAiClient};
 
 /// Trait for LLM client implementations
 ///
@@ -31,10 +35,136 @@ pub trait LlmClient: Send + Sync {
     /// Get the maximum context size in tokens
     fn max_tokens(&self) -> usize;
 
+    /// Get the sampling temperature, if the client has a fixed one
+    ///
+    /// Used as part of the response cache key.
+    fn temperature(&self) -> Option<f32> {
+        None
+    }
+
+    /// Embed texts as vectors for semantic retrieval
+    ///
+    /// Returns one vector per input text. Backends without an embeddings
+    /// endpoint return an error and retrieval falls back to keyword ranking.
+    async fn embed(&self, texts: &[String]) -> LlmResult<Vec<Vec<f32>>> {
+        let _ = texts;
+        Err(LlmError::ModelError(format!(
+            "Model '{}' does not provide embeddings",
+            self.model_name()
+        )))
+    }
+
     /// Check if the client is ready and connected
     async fn is_ready(&self) -> bool;
 }
 
+#[async_trait]
+impl<T: LlmClient + ?Sized> LlmClient for Box<T> {
+    async fn complete(&self, prompt: &str) -> LlmResult<String> {
+        (**self).complete(prompt).await
+    }
+
+    fn model_name(&self) -> &str {
+        (**self).model_name()
+    }
+
+    fn max_tokens(&self) -> usize {
+        (**self).max_tokens()
+    }
+
+    fn temperature(&self) -> Option<f32> {
+        (**self).temperature()
+    }
+
+    async fn embed(&self, texts: &[String]) -> LlmResult<Vec<Vec<f32>>> {
+        (**self).embed(texts).await
+    }
+
+    async fn is_ready(&self) -> bool {
+        (**self).is_ready().await
+    }
+}
+
+/// Create the client selected by `config.llm_mode`
+///
+/// The client inherits the config's timeout, context size and temperature.
+/// OpenAI-compatible clients read their API key from `api_key_env`
+/// (default `OPENAI_API_KEY`) and request JSON output, since every prompt in
+/// this crate expects a JSON response.
+///
+/// With a [`cache`](RefinementConfig::cache) configured the client is wrapped
+/// in a [`CachingLlmClient`]. In replay mode no model is created at all, so
+/// replay works without the model file or server being available.
+pub fn create_client(config: &RefinementConfig) -> LlmResult<Box<dyn LlmClient>> {
+    let Some(cache) = &config.cache else {
+        return create_model_client(config);
+    };
+
+    let inner: Box<dyn LlmClient> = if cache.mode == CacheMode::Replay {
+        let model = config.llm_mode.model_name().ok_or_else(|| {
+            LlmError::ConfigError("LLM mode is 'none'; nothing to replay".to_string())
+        })?;
+        Box::new(UnavailableClient {
+            model: model.to_string(),
+            temperature: Some(config.temperature.clamp(0.0, 2.0)),
+            max_tokens: config.max_context_tokens,
+        })
+    } else {
+        create_model_client(config)?
+    };
+
+    let mut client = CachingLlmClient::new(inner, cache.open_store()?, cache.mode);
+    if let Some(ref model) = config.retrieval.embedding_model {
+        client = client.with_embedding_model(model);
+    }
+    Ok(Box::new(client))
+}
+
+/// Create the uncached client for `config.llm_mode`
+fn create_model_client(config: &RefinementConfig) -> LlmResult<Box<dyn LlmClient>> {
+    let embedding_model = config.retrieval.embedding_model.as_deref();
+    match &config.llm_mode {
+        LlmMode::None => Err(LlmError::ConfigError(
+            "LLM mode is 'none'; no client to create".to_string(),
+        )),
+        LlmMode::Online { url, model } => {
+            let mut client = OllamaClient::new(url, model)
+                .with_timeout(config.timeout_seconds)
+                .with_max_context(config.max_context_tokens)
+                .with_temperature(config.temperature);
+            if let Some(embedding_model) = embedding_model {
+                client = client.with_embedding_model(embedding_model);
+            }
+            Ok(Box::new(client))
+        }
+        LlmMode::Offline {
+            model_path,
+            gpu_layers,
+        } => Ok(Box::new(
+            LlamaCppClient::new(model_path)?
+                .with_gpu_layers(*gpu_layers)
+                .with_context_size(config.max_context_tokens)
+                .with_temperature(config.temperature),
+        )),
+        LlmMode::OpenAi {
+            base_url,
+            model,
+            api_key_env,
+        } => {
+            let mut client = OpenAiClient::new(base_url, model)
+                .with_api_key_env(api_key_env.as_deref().unwrap_or(DEFAULT_API_KEY_ENV))
+                .with_json_mode()
+                .with_timeout(config.timeout_seconds)
+                .with_max_context(config.max_context_tokens)
+                .with_temperature(config.temperature);
+            if let Some(embedding_model) = embedding_model {
+                client = client.with_embedding_model(embedding_model);
+            }
+            Ok(Box::new(client))
+        }
+    }
+}
+
 /// Response from an LLM completion request
 #[derive(Debug, Clone)]
 pub struct CompletionResponse {
@@ -153,6 +283,21 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_boxed_client_and_create_client() {
+        let client: Box<dyn LlmClient> = Box::new(MockLlmClient::new("Boxed"));
+        assert_eq!(client.complete("Prompt").await.unwrap(), "Boxed");
+        assert_eq!(client.model_name(), "mock-model");
+
+        let config = RefinementConfig::with_openai("http://localhost:8000/v1", "qwen2.5")
+            .with_max_context_tokens(8192);
+        let client = create_client(&config).unwrap();
+        assert_eq!(client.model_name(), "qwen2.5");
+        assert_eq!(client.max_tokens(), 8192);
+
+        assert!(create_client(&RefinementConfig::default()).is_err());
+    }
+
     #[test]
     fn test_completion_response() {
         let response = CompletionResponse::new("Generated text")
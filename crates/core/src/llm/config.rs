@@ -8,12 +8,16 @@ use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
 
+use super::cache::{CacheMode, LlmCacheConfig};
+use super::retrieval::RetrievalConfig;
+
 /// LLM operation mode
 ///
 /// Determines how the LLM is accessed for schema refinement:
 /// - `None`: No LLM refinement (inference only)
 /// - `Online`: Connect to an Ollama API server
 /// - `Offline`: Use embedded llama.cpp with local model file
+/// - `OpenAi`: Connect to an OpenAI-compatible chat completions server
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(tag = "mode", rename_all = "snake_case")]
 pub enum LlmMode {
@@ -36,6 +40,18 @@ pub enum LlmMode {
         #[serde(default)]
         gpu_layers: u32,
     },
+
+    /// Online mode using an OpenAI-compatible `/v1/chat/completions` API
+    #[serde(rename = "openai")]
+    OpenAi {
+        /// Base URL including the API version (e.g., "http://localhost:8000/v1")
+        base_url: String,
+        /// Model name as known to the server
+        model: String,
+        /// Environment variable holding the API key (default: OPENAI_API_KEY)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        api_key_env: Option<String>,
+    },
 }
 
 impl Default for LlmMode {
@@ -77,6 +93,15 @@ impl LlmMode {
         }
     }
 
+    /// Create an OpenAI-compatible mode configuration
+    pub fn openai(base_url: impl Into<String>, model: impl Into<String>) -> Self {
+        LlmMode::OpenAi {
+            base_url: base_url.into(),
+            model: model.into(),
+            api_key_env: None,
+        }
+    }
+
     /// Check if LLM refinement is enabled
     pub fn is_enabled(&self) -> bool {
         !matches!(self, LlmMode::None)
@@ -91,6 +116,27 @@ impl LlmMode {
     pub fn is_offline(&self) -> bool {
         matches!(self, LlmMode::Offline { .. })
     }
+
+    /// Check if using an OpenAI-compatible server
+    pub fn is_openai(&self) -> bool {
+        matches!(self, LlmMode::OpenAi { .. })
+    }
+
+    /// Get the model name, as reported by the client for this mode
+    ///
+    /// Offline models are named after the GGUF file stem.
+    pub fn model_name(&self) -> Option<&str> {
+        match self {
+            LlmMode::None => None,
+            LlmMode::Online { model, .. } | LlmMode::OpenAi { model, .. } => Some(model),
+            LlmMode::Offline { model_path, .. } => Some(
+                model_path
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("unknown"),
+            ),
+        }
+    }
 }
 
 /// Configuration for schema refinement using LLM
@@ -132,6 +178,14 @@ pub struct RefinementConfig {
     /// Enable verbose logging of prompts and responses
     #[serde(default)]
     pub verbose: bool,
+
+    /// Response cache (see [`CachingLlmClient`](super::CachingLlmClient))
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache: Option<LlmCacheConfig>,
+
+    /// Selection of relevant documentation for large documents
+    #[serde(default)]
+    pub retrieval: RetrievalConfig,
 }
 
 fn default_max_context_tokens() -> usize {
@@ -171,6 +225,8 @@ impl Default for RefinementConfig {
             include_samples: default_include_samples(),
             max_samples: default_max_samples(),
             verbose: false,
+            cache: None,
+            retrieval: RetrievalConfig::default(),
         }
     }
 }
@@ -192,6 +248,14 @@ impl RefinementConfig {
         }
     }
 
+    /// Create a new refinement config with an OpenAI-compatible server
+    pub fn with_openai(base_url: impl Into<String>, model: impl Into<String>) -> Self {
+        Self {
+            llm_mode: LlmMode::openai(base_url, model),
+            ..Default::default()
+        }
+    }
+
     /// Set documentation from file path
     pub fn with_documentation_file(mut self, path: impl Into<PathBuf>) -> Self {
         self.documentation_path = Some(path.into());
@@ -234,6 +298,18 @@ impl RefinementConfig {
         self
     }
 
+    /// Cache responses in `path` (a directory, or a `.duckdb` staging database)
+    pub fn with_cache(mut self, path: impl Into<PathBuf>, mode: CacheMode) -> Self {
+        self.cache = Some(LlmCacheConfig::new(path, mode));
+        self
+    }
+
+    /// Set how relevant documentation is selected
+    pub fn with_retrieval(mut self, retrieval: RetrievalConfig) -> Self {
+        self.retrieval = retrieval;
+        self
+    }
+
     /// Check if LLM refinement is enabled
     pub fn is_enabled(&self) -> bool {
         self.llm_mode.is_enabled()
@@ -325,6 +401,23 @@ mod tests {
         assert_eq!(mode, parsed);
     }
 
+    #[test]
+    fn test_llm_mode_openai() {
+        let mode = LlmMode::openai("http://localhost:8000/v1", "qwen2.5");
+        assert!(mode.is_enabled());
+        assert!(mode.is_openai());
+        assert!(!mode.is_online());
+
+        let json = serde_json::to_string(&mode).unwrap();
+        assert!(json.contains(r#""mode":"openai""#));
+        assert!(!json.contains("api_key_env"));
+        let parsed: LlmMode = serde_json::from_str(&json).unwrap();
+        assert_eq!(mode, parsed);
+
+        let config = RefinementConfig::with_openai("http://localhost:8000/v1", "qwen2.5");
+        assert!(config.llm_mode.is_openai());
+    }
+
     #[test]
     fn test_refinement_config_default() {
         let config = RefinementConfig::default();
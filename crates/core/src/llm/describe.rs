@@ -0,0 +1,685 @@
+//! LLM-generated documentation for ODCS tables
+//!
+//! [`TableDocumenter`] proposes descriptions and business names for columns,
+//! and descriptions for tables, where they are missing. Prompts include the
+//! sibling columns and their example values, knowledge articles linked to the
+//! table and any configured documentation.
+//!
+//! The result is a [`DocumentationPatch`] listing each proposed text, which
+//! can be reviewed or edited (it serializes to YAML and Markdown) and then
+//! applied with [`DocumentationPatch::apply`]. Tables are never modified by
+//! the documenter itself, and responses that rename columns or change their
+//! types are rejected by [`validate_refinement`].
+//!
+//! # Example
+//!
+//! ```ignore
+//! use data_modelling_core::llm::{OllamaClient, RefinementConfig, TableDocumenter};
+//!
+//! let config = RefinementConfig::with_ollama("llama3.2")
+//!     .with_documentation_file("docs/orders.md");
+//! let documenter = TableDocumenter::new(OllamaClient::new("http://localhost:11434", "llama3.2"), config)
+//!     .with_knowledge(articles);
+//!
+//! let patch = documenter.document_table(&table).await?;
+//! println!("{}", patch.to_markdown());
+//!
+//! // After review
+//! patch.apply(&mut table);
+//! ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+
+use super::client::LlmClient;
+use super::config::RefinementConfig;
+use super::docs::{load_documentation, truncate_documentation};
+use super::error::{LlmError, LlmResult};
+use super::prompt::{TABLE_DOCUMENTATION_PROMPT, parse_llm_response};
+use super::retrieval::fit_documentation;
+use super::validation::validate_refinement;
+use crate::models::{KnowledgeArticle, Table};
+
+/// Number of example values per column included in the prompt
+const MAX_EXAMPLES: usize = 3;
+
+/// A documentation field that can be proposed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DocField {
+    /// Table or column description
+    Description,
+    /// Column business name
+    BusinessName,
+}
+
+impl std::fmt::Display for DocField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DocField::Description => write!(f, "description"),
+            DocField::BusinessName => write!(f, "businessName"),
+        }
+    }
+}
+
+/// A single proposed piece of documentation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocSuggestion {
+    /// Table name
+    pub table: String,
+    /// Column name (`None` for the table itself)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<String>,
+    /// Field the text is proposed for
+    pub field: DocField,
+    /// Proposed text
+    pub proposed: String,
+}
+
+impl DocSuggestion {
+    /// Human-readable location, e.g. `orders.customer_id.description`
+    pub fn target(&self) -> String {
+        match &self.column {
+            Some(column) => format!("{}.{}.{}", self.table, column, self.field),
+            None => format!("{}.{}", self.table, self.field),
+        }
+    }
+}
+
+/// Reviewable set of documentation suggestions
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentationPatch {
+    /// Model that produced the suggestions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Proposed documentation
+    #[serde(default)]
+    pub suggestions: Vec<DocSuggestion>,
+    /// Tables or items that could not be documented
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+impl DocumentationPatch {
+    /// Create an empty patch
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check if the patch has no suggestions
+    pub fn is_empty(&self) -> bool {
+        self.suggestions.is_empty()
+    }
+
+    /// Append the suggestions and warnings of another patch
+    pub fn extend(&mut self, other: DocumentationPatch) {
+        if self.model.is_none() {
+            self.model = other.model;
+        }
+        self.suggestions.extend(other.suggestions);
+        self.warnings.extend(other.warnings);
+    }
+
+    /// Parse a patch from YAML (or JSON)
+    pub fn from_yaml(content: &str) -> Result<Self, String> {
+        serde_yaml::from_str(content).map_err(|e| format!("Invalid documentation patch: {}", e))
+    }
+
+    /// Serialize the patch to YAML
+    pub fn to_yaml(&self) -> Result<String, String> {
+        serde_yaml::to_string(self).map_err(|e| e.to_string())
+    }
+
+    /// Render the patch for review
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::from("# Documentation Suggestions\n\n");
+        if let Some(ref model) = self.model {
+            md.push_str(&format!("Generated by `{}`.\n\n", model));
+        }
+
+        if self.suggestions.is_empty() {
+            md.push_str("No suggestions.\n");
+        } else {
+            md.push_str("| Target | Proposed |\n|--------|----------|\n");
+            for suggestion in &self.suggestions {
+                md.push_str(&format!(
+                    "| `{}` | {} |\n",
+                    suggestion.target(),
+                    suggestion.proposed.replace('|', "\\|").replace('\n', " ")
+                ));
+            }
+        }
+
+        if !self.warnings.is_empty() {
+            md.push_str("\n## Warnings\n\n");
+            for warning in &self.warnings {
+                md.push_str(&format!("- {}\n", warning));
+            }
+        }
+
+        md
+    }
+
+    /// Apply the suggestions for `table`
+    ///
+    /// Only fills fields that are still missing, so documentation written
+    /// since the patch was generated is never overwritten. Returns the number
+    /// of suggestions applied.
+    pub fn apply(&self, table: &mut Table) -> usize {
+        let mut applied = 0;
+        let table_name = table.name.clone();
+
+        for suggestion in self.suggestions.iter().filter(|s| s.table == table_name) {
+            match (&suggestion.column, suggestion.field) {
+                (None, DocField::Description) => {
+                    if table_description(table).is_none() {
+                        set_table_description(table, &suggestion.proposed);
+                        applied += 1;
+                    }
+                }
+                (None, DocField::BusinessName) => {}
+                (Some(name), field) => {
+                    let Some(column) = table.columns.iter_mut().find(|c| &c.name == name) else {
+                        continue;
+                    };
+                    match field {
+                        DocField::Description if column.description.trim().is_empty() => {
+                            column.description = suggestion.proposed.clone();
+                            applied += 1;
+                        }
+                        DocField::BusinessName if is_blank(&column.business_name) => {
+                            column.business_name = Some(suggestion.proposed.clone());
+                            applied += 1;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        if applied > 0 {
+            table.updated_at = chrono::Utc::now();
+        }
+        applied
+    }
+}
+
+/// Get a table's description
+///
+/// ODCS keeps a schema's own description in `odcl_metadata["schemaDescription"]`;
+/// `odcl_metadata["description"]` holds the contract description.
+pub fn table_description(table: &Table) -> Option<&str> {
+    let description = table.odcl_metadata.get("schemaDescription")?.as_str()?;
+    (!description.trim().is_empty()).then_some(description)
+}
+
+/// Set a table's description as its ODCS schema description
+fn set_table_description(table: &mut Table, text: &str) {
+    table
+        .odcl_metadata
+        .insert("schemaDescription".to_string(), json!(text));
+}
+
+fn is_blank(value: &Option<String>) -> bool {
+    value.as_deref().is_none_or(|v| v.trim().is_empty())
+}
+
+/// Proposes missing table and column documentation using an LLM
+pub struct TableDocumenter<C: LlmClient> {
+    client: C,
+    config: RefinementConfig,
+    articles: Vec<KnowledgeArticle>,
+    business_names: bool,
+    table_descriptions: bool,
+}
+
+impl<C: LlmClient> TableDocumenter<C> {
+    /// Create a new documenter
+    pub fn new(client: C, config: RefinementConfig) -> Self {
+        Self {
+            client,
+            config,
+            articles: Vec::new(),
+            business_names: true,
+            table_descriptions: true,
+        }
+    }
+
+    /// Provide knowledge articles; those linked to a table are added to its prompt
+    pub fn with_knowledge(mut self, articles: Vec<KnowledgeArticle>) -> Self {
+        self.articles = articles;
+        self
+    }
+
+    /// Propose business names for columns (default: true)
+    pub fn with_business_names(mut self, enabled: bool) -> Self {
+        self.business_names = enabled;
+        self
+    }
+
+    /// Propose table descriptions (default: true)
+    pub fn with_table_descriptions(mut self, enabled: bool) -> Self {
+        self.table_descriptions = enabled;
+        self
+    }
+
+    /// List the documentation missing from a table as `(column, field)` pairs
+    pub fn missing(&self, table: &Table) -> Vec<(Option<String>, DocField)> {
+        let mut missing = Vec::new();
+        if self.table_descriptions && table_description(table).is_none() {
+            missing.push((None, DocField::Description));
+        }
+        for column in &table.columns {
+            if column.description.trim().is_empty() {
+                missing.push((Some(column.name.clone()), DocField::Description));
+            }
+            if self.business_names && is_blank(&column.business_name) {
+                missing.push((Some(column.name.clone()), DocField::BusinessName));
+            }
+        }
+        missing
+    }
+
+    /// Propose documentation for everything missing from a table
+    pub async fn document_table(&self, table: &Table) -> LlmResult<DocumentationPatch> {
+        let mut patch = DocumentationPatch {
+            model: Some(self.client.model_name().to_string()),
+            ..Default::default()
+        };
+
+        let missing = self.missing(table);
+        if missing.is_empty() {
+            return Ok(patch);
+        }
+
+        let prompt = self.build_prompt(table, &missing).await?;
+        if self.config.verbose {
+            tracing::debug!("Documentation prompt:\n{}", prompt);
+        }
+
+        let mut last_error = None;
+        for attempt in 0..=self.config.max_retries {
+            match self.try_document(&prompt, table, &missing).await {
+                Ok(suggestions) => {
+                    let documented = suggestions.len();
+                    patch.suggestions = suggestions;
+                    if documented < missing.len() {
+                        patch.warnings.push(format!(
+                            "{}: {} of {} missing items could not be documented",
+                            table.name,
+                            missing.len() - documented,
+                            missing.len()
+                        ));
+                    }
+                    return Ok(patch);
+                }
+                Err(e) => {
+                    if attempt < self.config.max_retries {
+                        tracing::warn!("Documentation attempt {} failed: {}", attempt + 1, e);
+                    }
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or(LlmError::MaxRetriesExceeded(self.config.max_retries)))
+    }
+
+    /// Propose documentation for several tables
+    ///
+    /// A table that cannot be documented is recorded as a warning rather than
+    /// failing the whole patch.
+    pub async fn document_tables(&self, tables: &[Table]) -> DocumentationPatch {
+        let mut patch = DocumentationPatch {
+            model: Some(self.client.model_name().to_string()),
+            ..Default::default()
+        };
+        for table in tables {
+            match self.document_table(table).await {
+                Ok(table_patch) => patch.extend(table_patch),
+                Err(e) => patch.warnings.push(format!("{}: {}", table.name, e)),
+            }
+        }
+        patch
+    }
+
+    /// Call the model once and turn a valid response into suggestions
+    async fn try_document(
+        &self,
+        prompt: &str,
+        table: &Table,
+        missing: &[(Option<String>, DocField)],
+    ) -> LlmResult<Vec<DocSuggestion>> {
+        let response = self.client.complete(prompt).await?;
+        if self.config.verbose {
+            tracing::debug!("LLM response:\n{}", response);
+        }
+
+        let parsed = parse_llm_response(&response).map_err(LlmError::ParseError)?;
+        let columns = parsed
+            .schema
+            .get("columns")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+
+        // Guard against renamed, dropped or retyped columns
+        let mut refined = Map::new();
+        for column in &columns {
+            let name = column
+                .get("name")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| LlmError::ParseError("Column without a name".to_string()))?;
+            if !table.columns.iter().any(|c| c.name == name) {
+                return Err(LlmError::ValidationError(format!(
+                    "Unknown column '{}' in response",
+                    name
+                )));
+            }
+            let mut property = Map::new();
+            if let Some(data_type) = column.get("dataType").and_then(|v| v.as_str()) {
+                property.insert("type".to_string(), json!(data_type.to_lowercase()));
+            }
+            refined.insert(name.to_string(), Value::Object(property));
+        }
+        validate_refinement(
+            &columns_schema(table),
+            &json!({ "type": "object", "properties": refined }),
+        )
+        .to_result()?;
+
+        let text = |value: Option<&Value>| {
+            value
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+        };
+
+        let mut suggestions = Vec::new();
+        for (column_name, field) in missing {
+            let proposed = match column_name {
+                None => text(parsed.schema.get("description")),
+                Some(name) => columns
+                    .iter()
+                    .find(|c| c.get("name").and_then(|v| v.as_str()) == Some(name))
+                    .and_then(|c| {
+                        text(c.get(match field {
+                            DocField::Description => "description",
+                            DocField::BusinessName => "businessName",
+                        }))
+                    }),
+            };
+            if let Some(proposed) = proposed {
+                suggestions.push(DocSuggestion {
+                    table: table.name.clone(),
+                    column: column_name.clone(),
+                    field: *field,
+                    proposed,
+                });
+            }
+        }
+
+        Ok(suggestions)
+    }
+
+    /// Build the prompt for a table
+    async fn build_prompt(
+        &self,
+        table: &Table,
+        missing: &[(Option<String>, DocField)],
+    ) -> LlmResult<String> {
+        let table_json = serde_json::to_string_pretty(&table_context(table))?;
+
+        let missing_text = missing
+            .iter()
+            .map(|(column, field)| match column {
+                Some(column) => format!("- column `{}`: {}", column, field),
+                None => format!("- table `{}`: {}", table.name, field),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        // Documentation and knowledge each get a quarter of the context window
+        let budget_tokens = self.config.max_context_tokens / 4;
+
+        // Linked articles are included whole; other articles and the
+        // documentation only contribute chunks about this table's columns
+        let (linked, unlinked): (Vec<&KnowledgeArticle>, Vec<&KnowledgeArticle>) =
+            self.articles.iter().partition(|a| is_linked(a, table));
+
+        let documentation = self.load_documentation_context()?.unwrap_or_default();
+        let mut fields: Vec<String> = table.columns.iter().map(|c| c.name.clone()).collect();
+        fields.push(table.name.clone());
+        let relevant = fit_documentation(
+            &self.client,
+            &self.config.retrieval,
+            &documentation,
+            &unlinked,
+            &fields,
+            budget_tokens,
+        )
+        .await;
+        let documentation_section = if relevant.trim().is_empty() {
+            String::new()
+        } else {
+            format!("## Documentation Context\n```\n{}\n```\n", relevant)
+        };
+
+        let knowledge_section = if linked.is_empty() {
+            String::new()
+        } else {
+            let text = linked
+                .iter()
+                .map(|a| format!("### {}\n{}\n\n{}", a.title, a.summary, a.content))
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            format!(
+                "## Knowledge Articles\n{}\n",
+                truncate_documentation(&text, budget_tokens)
+            )
+        };
+
+        Ok(TABLE_DOCUMENTATION_PROMPT
+            .replace("{table}", &table_json)
+            .replace("{missing}", &missing_text)
+            .replace("{documentation_section}", &documentation_section)
+            .replace("{knowledge_section}", &knowledge_section))
+    }
+
+    /// Load documentation from configured sources
+    fn load_documentation_context(&self) -> LlmResult<Option<String>> {
+        if let Some(text) = &self.config.documentation_text {
+            return Ok(Some(text.clone()));
+        }
+        if let Some(path) = &self.config.documentation_path {
+            return Ok(Some(load_documentation(path)?));
+        }
+        Ok(None)
+    }
+}
+
+/// Check if a knowledge article is linked to a table
+fn is_linked(article: &KnowledgeArticle, table: &Table) -> bool {
+    article.linked_assets.iter().any(|asset| {
+        asset.asset_id == table.id || asset.asset_name.eq_ignore_ascii_case(&table.name)
+    })
+}
+
+/// Table summary given to the model: names, types, existing text and examples
+fn table_context(table: &Table) -> Value {
+    let columns: Vec<Value> = table
+        .columns
+        .iter()
+        .map(|column| {
+            let mut obj = Map::new();
+            obj.insert("name".to_string(), json!(column.name));
+            obj.insert("dataType".to_string(), json!(column.data_type));
+            if !column.description.trim().is_empty() {
+                obj.insert("description".to_string(), json!(column.description));
+            }
+            if let Some(ref business_name) = column.business_name {
+                obj.insert("businessName".to_string(), json!(business_name));
+            }
+            if column.primary_key {
+                obj.insert("primaryKey".to_string(), json!(true));
+            }
+            if !column.examples.is_empty() {
+                let examples: Vec<&Value> = column.examples.iter().take(MAX_EXAMPLES).collect();
+                obj.insert("examples".to_string(), json!(examples));
+            }
+            Value::Object(obj)
+        })
+        .collect();
+
+    let mut context = Map::new();
+    context.insert("name".to_string(), json!(table.name));
+    if let Some(description) = table_description(table) {
+        context.insert("description".to_string(), json!(description));
+    }
+    context.insert("columns".to_string(), Value::Array(columns));
+    Value::Object(context)
+}
+
+/// Column names and types as a JSON Schema, for [`validate_refinement`]
+///
+/// Types are lowercased since models echo them back in varying case.
+fn columns_schema(table: &Table) -> Value {
+    let properties: Map<String, Value> = table
+        .columns
+        .iter()
+        .map(|c| {
+            (
+                c.name.clone(),
+                json!({ "type": c.data_type.to_lowercase() }),
+            )
+        })
+        .collect();
+    json!({ "type": "object", "properties": properties })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::MockLlmClient;
+    use crate::models::{AssetLink, Column};
+
+    fn orders() -> Table {
+        let mut status = Column::new("status".to_string(), "string".to_string());
+        status.examples = vec![json!("shipped"), json!("pending")];
+        let mut id = Column::new("order_id".to_string(), "string".to_string());
+        id.description = "Unique order identifier".to_string();
+        id.business_name = Some("Order ID".to_string());
+        Table::new("orders".to_string(), vec![id, status])
+    }
+
+    fn config() -> RefinementConfig {
+        RefinementConfig::with_ollama("llama3.2").with_max_retries(0)
+    }
+
+    #[test]
+    fn test_missing() {
+        let documenter = TableDocumenter::new(MockLlmClient::new(""), config());
+        let missing = documenter.missing(&orders());
+        assert_eq!(
+            missing,
+            vec![
+                (None, DocField::Description),
+                (Some("status".to_string()), DocField::Description),
+                (Some("status".to_string()), DocField::BusinessName),
+            ]
+        );
+
+        let documenter = documenter
+            .with_business_names(false)
+            .with_table_descriptions(false);
+        assert_eq!(documenter.missing(&orders()).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_document_table_and_apply() {
+        let response = r#"{"description": "Customer orders", "columns": [
+            {"name": "order_id", "dataType": "string", "description": "ignored", "businessName": "Ignored"},
+            {"name": "status", "dataType": "string", "description": "Fulfilment status of the order", "businessName": "Order Status"}
+        ]}"#;
+        let documenter = TableDocumenter::new(MockLlmClient::new(response), config());
+        let mut table = orders();
+
+        let patch = documenter.document_table(&table).await.unwrap();
+        assert_eq!(patch.suggestions.len(), 3);
+        assert!(patch.warnings.is_empty());
+        assert_eq!(patch.suggestions[1].target(), "orders.status.description");
+
+        // The patch is reviewable and round-trips through YAML
+        let patch = DocumentationPatch::from_yaml(&patch.to_yaml().unwrap()).unwrap();
+        assert!(patch.to_markdown().contains("Order Status"));
+
+        assert_eq!(patch.apply(&mut table), 3);
+        assert_eq!(table_description(&table), Some("Customer orders"));
+        assert_eq!(table.columns[0].description, "Unique order identifier");
+        assert_eq!(
+            table.columns[1].business_name.as_deref(),
+            Some("Order Status")
+        );
+
+        // Applying again changes nothing
+        assert_eq!(patch.apply(&mut table), 0);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_renamed_or_retyped_columns() {
+        let table = orders();
+
+        let renamed = r#"{"columns": [{"name": "order_id", "dataType": "string"}, {"name": "order_status", "dataType": "string"}]}"#;
+        let documenter = TableDocumenter::new(MockLlmClient::new(renamed), config());
+        assert!(matches!(
+            documenter.document_table(&table).await,
+            Err(LlmError::ValidationError(_))
+        ));
+
+        let retyped = r#"{"columns": [{"name": "order_id", "dataType": "integer"}, {"name": "status", "dataType": "string"}]}"#;
+        let documenter = TableDocumenter::new(MockLlmClient::new(retyped), config());
+        assert!(documenter.document_table(&table).await.is_err());
+
+        let patch = documenter
+            .document_tables(std::slice::from_ref(&table))
+            .await;
+        assert!(patch.is_empty());
+        assert_eq!(patch.warnings.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_prompt_includes_linked_knowledge() {
+        let table = orders();
+        let mut article = KnowledgeArticle::new(
+            1,
+            "Order lifecycle",
+            "How orders move through fulfilment",
+            "An order is pending until it is shipped.",
+            "data-team",
+        );
+        article.linked_assets = vec![AssetLink::new("odcs", table.id, "orders")];
+        let unrelated = KnowledgeArticle::new(2, "Payroll", "Salaries", "Paid monthly.", "hr");
+        let glossary = KnowledgeArticle::new(
+            3,
+            "Glossary",
+            "Common terms",
+            "The status of an order is pending or shipped.",
+            "data-team",
+        );
+
+        let documenter = TableDocumenter::new(MockLlmClient::new(""), config())
+            .with_knowledge(vec![article, unrelated, glossary]);
+        let missing = documenter.missing(&table);
+        let prompt = documenter.build_prompt(&table, &missing).await.unwrap();
+
+        assert!(prompt.contains("Order lifecycle"));
+        assert!(!prompt.contains("Payroll"));
+        // Unlinked articles contribute only chunks that mention the columns
+        assert!(prompt.contains("KB-0003 Glossary"));
+        assert!(prompt.contains("\"shipped\""));
+        assert!(prompt.contains("- column `status`: businessName"));
+    }
+}
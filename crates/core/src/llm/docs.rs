@@ -16,6 +16,8 @@ pub enum DocFormat {
     Markdown,
     /// Word document (.docx) - basic text extraction
     Word,
+    /// PDF document (.pdf) - basic text extraction
+    Pdf,
     /// Unknown format
     Unknown,
 }
@@ -27,6 +29,7 @@ impl DocFormat {
             Some("txt") => DocFormat::PlainText,
             Some("md") | Some("markdown") => DocFormat::Markdown,
             Some("docx") => DocFormat::Word,
+            Some("pdf") => DocFormat::Pdf,
             _ => DocFormat::Unknown,
         }
     }
@@ -43,6 +46,7 @@ impl DocFormat {
 /// - `.txt` - Plain text
 /// - `.md` - Markdown (treated as text)
 /// - `.docx` - Word documents (basic text extraction)
+/// - `.pdf` - PDF documents (basic text extraction)
 ///
 /// # Arguments
 /// * `path` - Path to the documentation file
@@ -62,6 +66,7 @@ pub fn load_documentation(path: &Path) -> LlmResult<String> {
     match format {
         DocFormat::PlainText | DocFormat::Markdown => load_text_file(path),
         DocFormat::Word => load_docx_file(path),
+        DocFormat::Pdf => load_pdf_file(path),
         DocFormat::Unknown => {
             // Try to load as text anyway
             load_text_file(path).map_err(|_| {
@@ -166,6 +171,227 @@ fn extract_text_from_docx_xml(xml: &str) -> String {
     output.trim().to_string()
 }
 
+/// Load and extract text from a PDF document
+///
+/// Text is taken from the text-showing operators of each content stream.
+/// Scanned PDFs and fonts with custom encodings yield little or no text.
+fn load_pdf_file(path: &Path) -> LlmResult<String> {
+    let data = std::fs::read(path).map_err(|e| {
+        LlmError::DocumentationError(format!("Failed to read {}: {}", path.display(), e))
+    })?;
+    if !data.starts_with(b"%PDF") {
+        return Err(LlmError::DocumentationError(format!(
+            "Not a PDF file: {}",
+            path.display()
+        )));
+    }
+
+    let text = extract_text_from_pdf(&data);
+    if text.is_empty() {
+        return Err(LlmError::DocumentationError(format!(
+            "No extractable text in {}",
+            path.display()
+        )));
+    }
+    Ok(text)
+}
+
+/// Extract plain text from the content streams of a PDF
+fn extract_text_from_pdf(data: &[u8]) -> String {
+    let mut output = String::new();
+    let mut pos = 0;
+
+    while let Some(offset) = find_bytes(&data[pos..], b"stream") {
+        let header = &data[pos..pos + offset];
+        let mut body_start = pos + offset + b"stream".len();
+        if data.get(body_start) == Some(&b'\r') {
+            body_start += 1;
+        }
+        if data.get(body_start) == Some(&b'\n') {
+            body_start += 1;
+        }
+        let Some(length) = find_bytes(&data[body_start..], b"endstream") else {
+            break;
+        };
+        let raw = &data[body_start..body_start + length];
+        pos = body_start + length + b"endstream".len();
+
+        // Only the dictionary of this object matters, not earlier objects
+        let dict = match find_last_bytes(header, b"obj") {
+            Some(i) => &header[i..],
+            None => header,
+        };
+        // Skip images and embedded font programs
+        if find_bytes(dict, b"/Image").is_some() || find_bytes(dict, b"/Length1").is_some() {
+            continue;
+        }
+
+        if find_bytes(dict, b"/FlateDecode").is_some() {
+            let mut inflated = Vec::new();
+            let mut decoder = flate2::read::ZlibDecoder::new(raw);
+            if std::io::Read::read_to_end(&mut decoder, &mut inflated).is_err()
+                && inflated.is_empty()
+            {
+                continue;
+            }
+            extract_text_from_pdf_content(&inflated, &mut output);
+        } else if find_bytes(dict, b"/Filter").is_none() {
+            extract_text_from_pdf_content(raw, &mut output);
+        }
+    }
+
+    // Tidy line breaks left by positioning operators
+    let mut result = String::new();
+    let mut blank_lines = 0;
+    for line in output.lines().map(str::trim) {
+        if line.is_empty() {
+            blank_lines += 1;
+            continue;
+        }
+        if !result.is_empty() {
+            result.push_str(if blank_lines > 0 { "\n\n" } else { "\n" });
+        }
+        result.push_str(line);
+        blank_lines = 0;
+    }
+    result
+}
+
+/// Append the text shown by a PDF content stream to `output`
+///
+/// Handles literal strings shown with `Tj`, `TJ`, `'` and `"`; large negative
+/// kerning inside `TJ` arrays is treated as a word space.
+fn extract_text_from_pdf_content(content: &[u8], output: &mut String) {
+    let mut operands = String::new();
+    let mut in_array = false;
+    let mut i = 0;
+
+    while i < content.len() {
+        match content[i] {
+            b'(' => {
+                let (text, next) = read_pdf_string(content, i + 1);
+                operands.push_str(&text);
+                i = next;
+            }
+            b'%' => {
+                while i < content.len() && content[i] != b'\n' && content[i] != b'\r' {
+                    i += 1;
+                }
+            }
+            b'[' => {
+                in_array = true;
+                i += 1;
+            }
+            b']' => {
+                in_array = false;
+                i += 1;
+            }
+            b'<' => {
+                // Hex strings and dictionaries are skipped
+                while i < content.len() && content[i] != b'>' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            b'-' | b'.' | b'0'..=b'9' => {
+                let start = i;
+                while i < content.len() && matches!(content[i], b'-' | b'.' | b'0'..=b'9') {
+                    i += 1;
+                }
+                if in_array
+                    && let Ok(number) = std::str::from_utf8(&content[start..i])
+                        .unwrap_or_default()
+                        .parse::<f64>()
+                    && number < -200.0
+                {
+                    operands.push(' ');
+                }
+            }
+            c if c.is_ascii_alphabetic() || c == b'\'' || c == b'"' => {
+                let start = i;
+                while i < content.len()
+                    && (content[i].is_ascii_alphabetic()
+                        || matches!(content[i], b'*' | b'\'' | b'"'))
+                {
+                    i += 1;
+                }
+                match &content[start..i] {
+                    b"Tj" | b"TJ" => output.push_str(&operands),
+                    b"'" | b"\"" => {
+                        output.push('\n');
+                        output.push_str(&operands);
+                    }
+                    b"T*" | b"Td" | b"TD" => output.push('\n'),
+                    b"ET" => output.push_str("\n\n"),
+                    _ => {}
+                }
+                operands.clear();
+            }
+            _ => i += 1,
+        }
+    }
+}
+
+/// Read a PDF literal string starting after its opening parenthesis
+///
+/// Returns the decoded text and the position after the closing parenthesis.
+fn read_pdf_string(content: &[u8], mut i: usize) -> (String, usize) {
+    let mut text = String::new();
+    let mut depth = 1;
+
+    while i < content.len() {
+        let c = content[i];
+        i += 1;
+        match c {
+            b'\\' if i < content.len() => {
+                let escaped = content[i];
+                i += 1;
+                match escaped {
+                    b'n' => text.push('\n'),
+                    b'r' => text.push('\r'),
+                    b't' => text.push('\t'),
+                    b'b' | b'f' | b'\n' | b'\r' => {}
+                    b'0'..=b'7' => {
+                        let mut value = (escaped - b'0') as u32;
+                        for _ in 0..2 {
+                            if i < content.len() && (b'0'..=b'7').contains(&content[i]) {
+                                value = value * 8 + (content[i] - b'0') as u32;
+                                i += 1;
+                            }
+                        }
+                        text.push(char::from_u32(value).unwrap_or(' '));
+                    }
+                    other => text.push(other as char),
+                }
+            }
+            b'(' => {
+                depth += 1;
+                text.push('(');
+            }
+            b')' => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+                text.push(')');
+            }
+            other => text.push(other as char),
+        }
+    }
+
+    (text, i)
+}
+
+/// Find the first occurrence of `needle` in `haystack`
+fn find_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+/// Find the last occurrence of `needle` in `haystack`
+fn find_last_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).rposition(|w| w == needle)
+}
+
 /// Load documentation from multiple files
 ///
 /// Concatenates content from multiple files with separators
@@ -270,8 +496,9 @@ mod tests {
             DocFormat::Markdown
         );
         assert_eq!(DocFormat::from_path(Path::new("doc.docx")), DocFormat::Word);
+        assert_eq!(DocFormat::from_path(Path::new("doc.pdf")), DocFormat::Pdf);
         assert_eq!(
-            DocFormat::from_path(Path::new("doc.pdf")),
+            DocFormat::from_path(Path::new("doc.html")),
             DocFormat::Unknown
         );
     }
@@ -281,6 +508,7 @@ mod tests {
         assert!(DocFormat::PlainText.is_supported());
         assert!(DocFormat::Markdown.is_supported());
         assert!(DocFormat::Word.is_supported());
+        assert!(DocFormat::Pdf.is_supported());
         assert!(!DocFormat::Unknown.is_supported());
     }
 
@@ -357,4 +585,23 @@ mod tests {
         assert!(result.contains("World"));
         assert!(result.contains("Second paragraph"));
     }
+
+    #[test]
+    fn test_extract_text_from_pdf() {
+        use std::io::Write;
+
+        let content = b"BT /F1 12 Tf 72 720 Td (customer_id: unique \\(internal\\) key) Tj T* [(order)-250(date)] TJ ET";
+        let mut encoder =
+            flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(content).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let mut pdf =
+            b"%PDF-1.4\n4 0 obj\n<< /Length 10 /Filter /FlateDecode >>\nstream\n".to_vec();
+        pdf.extend_from_slice(&compressed);
+        pdf.extend_from_slice(b"\nendstream\nendobj\n%%EOF\n");
+
+        let text = extract_text_from_pdf(&pdf);
+        assert_eq!(text, "customer_id: unique (internal) key\norder date");
+    }
 }
@@ -60,6 +60,10 @@ pub enum LlmError {
     #[error("Context exceeds maximum tokens ({max}): {actual} tokens")]
     ContextTooLarge { max: usize, actual: usize },
 
+    /// Replay cache has no response for a prompt
+    #[error("LLM cache miss in replay mode: {0}")]
+    CacheMiss(String),
+
     /// Feature not available
     #[error("LLM feature not available: {0}. Enable with --features {1}")]
     FeatureNotAvailable(String, String),
@@ -132,6 +136,12 @@ impl LlmError {
                     Hint: Rebuild with --features {flag}"
                 )
             }
+            LlmError::CacheMiss(msg) => {
+                format!(
+                    "LLM cache miss in replay mode: {msg}\n\n\
+                    Hint: Re-record responses with --llm-cache-mode record"
+                )
+            }
             LlmError::MaxRetriesExceeded(retries) => {
                 format!(
                     "Failed after {retries} retries.\n\n\
@@ -361,6 +361,10 @@ impl LlmClient for LlamaCppClient {
         self.context_size
     }
 
+    fn temperature(&self) -> Option<f32> {
+        Some(self.temperature)
+    }
+
     async fn is_ready(&self) -> bool {
         self.ensure_loaded().is_ok()
     }
@@ -402,6 +406,10 @@ impl LlmClient for LlamaCppClient {
         self.context_size
     }
 
+    fn temperature(&self) -> Option<f32> {
+        Some(self.temperature)
+    }
+
     async fn is_ready(&self) -> bool {
         false
     }
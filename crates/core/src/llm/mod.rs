@@ -7,7 +7,15 @@
 //!
 //! - **Online Mode**: Connect to Ollama API for LLM inference (requires `llm-online` feature)
 //! - **Offline Mode**: Use embedded llama.cpp for local inference (requires `llm-offline` feature)
+//! - **OpenAI-compatible Mode**: Connect to vLLM, llama-server, LM Studio, LocalAI or any other
+//!   `/v1/chat/completions` server, with JSON mode and streaming (requires `llm-online` feature)
+//! - **Response Cache**: Record responses keyed on model, temperature and prompt hash and
+//!   replay them without a model, for reproducible runs and CI
+//! - **Table Documentation**: Propose missing ODCS column/table descriptions and business
+//!   names as a reviewable patch
 //! - **Documentation Context**: Load documentation to provide context for refinement
+//! - **Retrieval**: Chunk documentation (Markdown, docx, PDF, knowledge articles) and select
+//!   the chunks relevant to each field with BM25 and optional backend embeddings
 //! - **Validation**: Ensure refined schemas maintain compatibility with originals
 //!
 //! # Example
@@ -41,31 +49,46 @@
 //!
 //! # Feature Flags
 //!
-//! - `llm-online`: Enable Ollama client for online inference
+//! - `llm-online`: Enable Ollama and OpenAI-compatible clients for online inference
 //! - `llm-offline`: Enable llama.cpp client for offline inference
 //!
 //! Without either feature, the LLM module provides configuration types and
 //! validation, but actual inference will return feature-not-available errors.
 
+pub mod cache;
 pub mod client;
 pub mod config;
+pub mod describe;
 pub mod docs;
 pub mod error;
 pub mod llamacpp;
 pub mod ollama;
+pub mod openai;
 pub mod prompt;
 pub mod refine;
+pub mod retrieval;
 pub mod validation;
 
 // Re-export main types
-pub use client::{CompletionResponse, LlmClient};
+#[cfg(feature = "staging")]
+pub use cache::StagingResponseStore;
+pub use cache::{
+    CacheMode, CacheStats, CachedResponse, CachingLlmClient, FileResponseStore, LlmCacheConfig,
+    ResponseStore, cache_key, embedding_cache_key,
+};
+pub use client::{CompletionResponse, LlmClient, create_client};
 pub use config::{LlmMode, RefinementConfig};
+pub use describe::{
+    DocField, DocSuggestion, DocumentationPatch, TableDocumenter, table_description,
+};
 pub use docs::{DocFormat, load_documentation};
 pub use error::{LlmError, LlmResult};
 pub use llamacpp::LlamaCppClient;
 pub use ollama::OllamaClient;
+pub use openai::{OpenAiClient, ResponseFormat};
 pub use prompt::{PromptContext, estimate_tokens, parse_llm_response};
 pub use refine::{RefinementBuilder, RefinementResult, SchemaRefiner, refine_schema};
+pub use retrieval::{DocChunk, DocumentIndex, RetrievalConfig, chunk_document};
 pub use validation::{ValidationError, ValidationResult, validate_refinement};
 
 #[cfg(test)]
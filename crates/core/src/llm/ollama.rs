@@ -33,6 +33,8 @@ pub struct OllamaClient {
     max_context_tokens: usize,
     /// Temperature for sampling
     temperature: f32,
+    /// Model used for embeddings (defaults to the completion model)
+    embedding_model: Option<String>,
     /// HTTP client
     #[cfg(feature = "llm-online")]
     client: reqwest::Client,
@@ -72,6 +74,21 @@ struct GenerateResponse {
     eval_count: Option<usize>,
 }
 
+/// Request body for Ollama embed endpoint
+#[derive(Debug, Serialize)]
+#[allow(dead_code)]
+struct EmbedRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+/// Response from Ollama embed endpoint
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct EmbedResponse {
+    embeddings: Vec<Vec<f32>>,
+}
+
 /// Response from Ollama tags endpoint (list models)
 #[derive(Debug, Deserialize)]
 #[allow(dead_code)]
@@ -102,6 +119,7 @@ impl OllamaClient {
             timeout_seconds: 120,
             max_context_tokens: 4096,
             temperature: 0.1,
+            embedding_model: None,
             client: reqwest::Client::new(),
         }
     }
@@ -115,6 +133,7 @@ impl OllamaClient {
             timeout_seconds: 120,
             max_context_tokens: 4096,
             temperature: 0.1,
+            embedding_model: None,
         }
     }
 
@@ -136,6 +155,12 @@ impl OllamaClient {
         self
     }
 
+    /// Set the model used for embeddings (e.g., "nomic-embed-text")
+    pub fn with_embedding_model(mut self, model: impl Into<String>) -> Self {
+        self.embedding_model = Some(model.into());
+        self
+    }
+
     /// Get the base URL
     pub fn base_url(&self) -> &str {
         &self.base_url
@@ -262,6 +287,46 @@ impl LlmClient for OllamaClient {
         Ok(gen_response.response)
     }
 
+    async fn embed(&self, texts: &[String]) -> LlmResult<Vec<Vec<f32>>> {
+        let url = format!("{}/api/embed", self.base_url);
+        let request = EmbedRequest {
+            model: self.embedding_model.as_deref().unwrap_or(&self.model),
+            input: texts,
+        };
+
+        let response = self
+            .client
+            .post(&url)
+            .json(&request)
+            .timeout(std::time::Duration::from_secs(self.timeout_seconds))
+            .send()
+            .await
+            .map_err(|e| LlmError::ConnectionError(e.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(LlmError::ModelError(format!(
+                "Ollama embed error (HTTP {}): {}",
+                status, error_text
+            )));
+        }
+
+        let embed_response: EmbedResponse = response
+            .json()
+            .await
+            .map_err(|e| LlmError::ParseError(e.to_string()))?;
+        if embed_response.embeddings.len() != texts.len() {
+            return Err(LlmError::InvalidResponse(format!(
+                "Expected {} embeddings, got {}",
+                texts.len(),
+                embed_response.embeddings.len()
+            )));
+        }
+
+        Ok(embed_response.embeddings)
+    }
+
     fn model_name(&self) -> &str {
         &self.model
     }
@@ -270,6 +335,10 @@ impl LlmClient for OllamaClient {
         self.max_context_tokens
     }
 
+    fn temperature(&self) -> Option<f32> {
+        Some(self.temperature)
+    }
+
     async fn is_ready(&self) -> bool {
         self.list_models().await.is_ok()
     }
@@ -293,6 +362,10 @@ impl LlmClient for OllamaClient {
         self.max_context_tokens
     }
 
+    fn temperature(&self) -> Option<f32> {
+        Some(self.temperature)
+    }
+
     async fn is_ready(&self) -> bool {
         false
     }
@@ -367,6 +440,14 @@ mod tests {
         assert_eq!(response.eval_count, Some(100));
     }
 
+    #[test]
+    fn test_embed_response_deserialize() {
+        let json = r#"{"model": "nomic-embed-text", "embeddings": [[0.1, 0.2], [0.3, 0.4]]}"#;
+        let response: EmbedResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.embeddings.len(), 2);
+        assert!((response.embeddings[1][0] - 0.3).abs() < f32::EPSILON);
+    }
+
     #[test]
     fn test_generate_response_minimal() {
         let json = r#"{"response": "Text", "done": true}"#;
@@ -0,0 +1,850 @@
+//! OpenAI-compatible chat completions client
+//!
+//! This module provides an HTTP client for servers that implement the OpenAI
+//! `/v1/chat/completions` protocol, such as vLLM, llama-server, LM Studio and
+//! LocalAI, as well as hosted OpenAI-compatible APIs.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use data_modelling_core::llm::openai::OpenAiClient;
+//!
+//! let client = OpenAiClient::new("http://localhost:8000/v1", "qwen2.5-coder")
+//!     .with_api_key_env("OPENAI_API_KEY")
+//!     .with_json_mode()
+//!     .with_timeout(60);
+//!
+//! let response = client.complete("Analyze this schema...").await?;
+//! ```
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::client::LlmClient;
+use super::error::{LlmError, LlmResult};
+
+/// Default environment variable holding the API key
+pub const DEFAULT_API_KEY_ENV: &str = "OPENAI_API_KEY";
+
+/// Output format requested from the server
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResponseFormat {
+    /// Free-form text
+    Text,
+    /// Any valid JSON object (`response_format: {"type": "json_object"}`)
+    JsonObject,
+    /// JSON matching a schema (`response_format: {"type": "json_schema"}`)
+    JsonSchema {
+        /// Schema name sent to the server
+        name: String,
+        /// JSON Schema the response must match
+        schema: Value,
+        /// Whether the server should enforce the schema strictly
+        strict: bool,
+    },
+}
+
+/// OpenAI-compatible chat completions client
+#[derive(Debug, Clone)]
+pub struct OpenAiClient {
+    /// Base URL including the API version (e.g., "http://localhost:8000/v1")
+    base_url: String,
+    /// Model name to use
+    model: String,
+    /// Bearer token sent in the `Authorization` header
+    api_key: Option<String>,
+    /// System message prepended to every request
+    system_prompt: Option<String>,
+    /// Request timeout in seconds
+    timeout_seconds: u64,
+    /// Maximum context tokens
+    max_context_tokens: usize,
+    /// Maximum tokens to generate
+    max_completion_tokens: Option<usize>,
+    /// Temperature for sampling
+    temperature: f32,
+    /// Requested output format
+    response_format: ResponseFormat,
+    /// Stream the response as server-sent events
+    stream: bool,
+    /// Model used for embeddings (defaults to the completion model)
+    embedding_model: Option<String>,
+    /// HTTP client
+    #[cfg(feature = "llm-online")]
+    client: reqwest::Client,
+}
+
+/// Request body for the chat completions endpoint
+#[derive(Debug, Serialize)]
+#[allow(dead_code)]
+struct ChatRequest<'a> {
+    model: &'a str,
+    messages: Vec<ChatMessage<'a>>,
+    temperature: f32,
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<Value>,
+}
+
+/// A single chat message
+#[derive(Debug, Serialize)]
+#[allow(dead_code)]
+struct ChatMessage<'a> {
+    role: &'a str,
+    content: &'a str,
+}
+
+/// Response from the chat completions endpoint
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct ChatResponse {
+    choices: Vec<ChatChoice>,
+    #[serde(default)]
+    usage: Option<Usage>,
+}
+
+/// A completion choice
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct ChatChoice {
+    message: ResponseMessage,
+    #[serde(default)]
+    finish_reason: Option<String>,
+}
+
+/// Message returned in a completion choice
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct ResponseMessage {
+    #[serde(default)]
+    content: Option<String>,
+}
+
+/// Token usage reported by the server
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct Usage {
+    #[serde(default)]
+    prompt_tokens: usize,
+    #[serde(default)]
+    completion_tokens: usize,
+}
+
+/// A streamed completion chunk
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct ChatChunk {
+    choices: Vec<ChunkChoice>,
+}
+
+/// A choice within a streamed chunk
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct ChunkChoice {
+    delta: ChunkDelta,
+}
+
+/// Incremental content within a streamed chunk
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct ChunkDelta {
+    #[serde(default)]
+    content: Option<String>,
+}
+
+/// Request body for the embeddings endpoint
+#[derive(Debug, Serialize)]
+#[allow(dead_code)]
+struct EmbeddingsRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+/// Response from the embeddings endpoint
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct EmbeddingsResponse {
+    data: Vec<EmbeddingEntry>,
+}
+
+/// A single embedding, tagged with the index of its input
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct EmbeddingEntry {
+    embedding: Vec<f32>,
+    #[serde(default)]
+    index: usize,
+}
+
+/// Response from the models endpoint
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct ModelsResponse {
+    data: Vec<ModelEntry>,
+}
+
+/// Model entry from the models endpoint
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct ModelEntry {
+    id: String,
+}
+
+impl OpenAiClient {
+    /// Create a new OpenAI-compatible client
+    ///
+    /// # Arguments
+    /// * `base_url` - Base URL including the API version (e.g., "http://localhost:8000/v1")
+    /// * `model` - Model name as known to the server
+    pub fn new(base_url: impl Into<String>, model: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            model: model.into(),
+            api_key: None,
+            system_prompt: None,
+            timeout_seconds: 120,
+            max_context_tokens: 4096,
+            max_completion_tokens: None,
+            temperature: 0.1,
+            response_format: ResponseFormat::Text,
+            stream: false,
+            embedding_model: None,
+            #[cfg(feature = "llm-online")]
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Set the API key
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Read the API key from an environment variable, if it is set
+    pub fn with_api_key_env(mut self, var: &str) -> Self {
+        if let Ok(key) = std::env::var(var)
+            && !key.is_empty()
+        {
+            self.api_key = Some(key);
+        }
+        self
+    }
+
+    /// Set the model used for embeddings (e.g., "bge-small-en")
+    pub fn with_embedding_model(mut self, model: impl Into<String>) -> Self {
+        self.embedding_model = Some(model.into());
+        self
+    }
+
+    /// Set a system message sent before every prompt
+    pub fn with_system_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.system_prompt = Some(prompt.into());
+        self
+    }
+
+    /// Set the request timeout
+    pub fn with_timeout(mut self, seconds: u64) -> Self {
+        self.timeout_seconds = seconds;
+        self
+    }
+
+    /// Set the maximum context tokens
+    pub fn with_max_context(mut self, tokens: usize) -> Self {
+        self.max_context_tokens = tokens;
+        self
+    }
+
+    /// Set the maximum number of tokens to generate
+    pub fn with_max_completion_tokens(mut self, tokens: usize) -> Self {
+        self.max_completion_tokens = Some(tokens);
+        self
+    }
+
+    /// Set the temperature for sampling
+    pub fn with_temperature(mut self, temperature: f32) -> Self {
+        self.temperature = temperature.clamp(0.0, 2.0);
+        self
+    }
+
+    /// Ask the server to return a JSON object
+    pub fn with_json_mode(mut self) -> Self {
+        self.response_format = ResponseFormat::JsonObject;
+        self
+    }
+
+    /// Ask the server to return JSON matching `schema` (structured output)
+    pub fn with_json_schema(mut self, name: impl Into<String>, schema: Value) -> Self {
+        self.response_format = ResponseFormat::JsonSchema {
+            name: name.into(),
+            schema,
+            strict: true,
+        };
+        self
+    }
+
+    /// Stream responses as server-sent events
+    ///
+    /// `complete` still returns the full text; use
+    /// [`complete_streaming`](Self::complete_streaming) to observe the deltas.
+    pub fn with_streaming(mut self, stream: bool) -> Self {
+        self.stream = stream;
+        self
+    }
+
+    /// Get the base URL
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Check whether an API key is configured
+    pub fn has_api_key(&self) -> bool {
+        self.api_key.is_some()
+    }
+
+    /// Build the request body for a prompt
+    fn build_request<'a>(&'a self, prompt: &'a str, stream: bool) -> ChatRequest<'a> {
+        let mut messages = Vec::new();
+        if let Some(ref system) = self.system_prompt {
+            messages.push(ChatMessage {
+                role: "system",
+                content: system,
+            });
+        }
+        messages.push(ChatMessage {
+            role: "user",
+            content: prompt,
+        });
+
+        let response_format = match &self.response_format {
+            ResponseFormat::Text => None,
+            ResponseFormat::JsonObject => Some(serde_json::json!({"type": "json_object"})),
+            ResponseFormat::JsonSchema {
+                name,
+                schema,
+                strict,
+            } => Some(serde_json::json!({
+                "type": "json_schema",
+                "json_schema": {"name": name, "schema": schema, "strict": strict}
+            })),
+        };
+
+        ChatRequest {
+            model: &self.model,
+            messages,
+            temperature: self.temperature,
+            stream,
+            max_tokens: self.max_completion_tokens,
+            response_format,
+        }
+    }
+
+    /// List models served by the endpoint
+    #[cfg(feature = "llm-online")]
+    pub async fn list_models(&self) -> LlmResult<Vec<String>> {
+        let url = format!("{}/models", self.base_url);
+
+        let mut request = self
+            .client
+            .get(&url)
+            .timeout(std::time::Duration::from_secs(10));
+        if let Some(ref key) = self.api_key {
+            request = request.bearer_auth(key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| LlmError::ConnectionError(e.to_string()))?;
+
+        if !response.status().is_success() {
+            return Err(LlmError::ConnectionError(format!(
+                "Failed to list models: HTTP {}",
+                response.status()
+            )));
+        }
+
+        let models: ModelsResponse = response
+            .json()
+            .await
+            .map_err(|e| LlmError::ParseError(e.to_string()))?;
+
+        Ok(models.data.into_iter().map(|m| m.id).collect())
+    }
+
+    /// List models (stub for when feature is disabled)
+    #[cfg(not(feature = "llm-online"))]
+    pub async fn list_models(&self) -> LlmResult<Vec<String>> {
+        Err(LlmError::FeatureNotAvailable(
+            "Online LLM".to_string(),
+            "llm-online".to_string(),
+        ))
+    }
+
+    /// Send a chat completion request and return the raw response
+    #[cfg(feature = "llm-online")]
+    async fn send(&self, prompt: &str, stream: bool) -> LlmResult<reqwest::Response> {
+        self.post("chat/completions", &self.build_request(prompt, stream))
+            .await
+    }
+
+    /// POST a JSON body to an endpoint below the base URL
+    #[cfg(feature = "llm-online")]
+    async fn post<B: Serialize>(&self, endpoint: &str, body: &B) -> LlmResult<reqwest::Response> {
+        let url = format!("{}/{}", self.base_url, endpoint);
+
+        tracing::debug!("Sending request to OpenAI-compatible endpoint: {}", url);
+
+        let mut request = self
+            .client
+            .post(&url)
+            .json(body)
+            .timeout(std::time::Duration::from_secs(self.timeout_seconds));
+        if let Some(ref key) = self.api_key {
+            request = request.bearer_auth(key);
+        }
+
+        let response = request.send().await.map_err(|e| {
+            if e.is_timeout() {
+                LlmError::Timeout(self.timeout_seconds)
+            } else if e.is_connect() {
+                LlmError::ConnectionError(format!("Failed to connect to {}: {}", self.base_url, e))
+            } else {
+                LlmError::ConnectionError(e.to_string())
+            }
+        })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(60);
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(match status.as_u16() {
+                429 => LlmError::RateLimited(retry_after),
+                401 | 403 => LlmError::ConfigError(format!(
+                    "Authentication failed (HTTP {}): {}",
+                    status, error_text
+                )),
+                404 => LlmError::ModelError(format!(
+                    "Model or endpoint not found (HTTP {}): {}",
+                    status, error_text
+                )),
+                _ => LlmError::ConnectionError(format!(
+                    "{} API error (HTTP {}): {}",
+                    endpoint, status, error_text
+                )),
+            });
+        }
+
+        Ok(response)
+    }
+
+    /// Generate a completion, calling `on_delta` with each streamed fragment
+    ///
+    /// Returns the concatenated response text.
+    #[cfg(feature = "llm-online")]
+    pub async fn complete_streaming<F>(&self, prompt: &str, mut on_delta: F) -> LlmResult<String>
+    where
+        F: FnMut(&str) + Send,
+    {
+        let mut response = self.send(prompt, true).await?;
+
+        let mut text = String::new();
+        let mut lines = SseLineBuffer::default();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| LlmError::ConnectionError(e.to_string()))?
+        {
+            for line in lines.push(&chunk) {
+                match parse_sse_line(&line)? {
+                    SseEvent::Delta(delta) => {
+                        on_delta(&delta);
+                        text.push_str(&delta);
+                    }
+                    SseEvent::Done => return Ok(text),
+                    SseEvent::Skip => {}
+                }
+            }
+        }
+
+        if let SseEvent::Delta(delta) = parse_sse_line(&lines.finish())? {
+            on_delta(&delta);
+            text.push_str(&delta);
+        }
+        Ok(text)
+    }
+
+    /// Generate a streamed completion (stub for when feature is disabled)
+    #[cfg(not(feature = "llm-online"))]
+    pub async fn complete_streaming<F>(&self, _prompt: &str, _on_delta: F) -> LlmResult<String>
+    where
+        F: FnMut(&str) + Send,
+    {
+        Err(LlmError::FeatureNotAvailable(
+            "Online LLM".to_string(),
+            "llm-online".to_string(),
+        ))
+    }
+}
+
+/// A parsed server-sent event line
+#[derive(Debug, PartialEq)]
+#[allow(dead_code)]
+enum SseEvent {
+    /// Content fragment
+    Delta(String),
+    /// End of stream
+    Done,
+    /// Comment, keep-alive or chunk without content
+    Skip,
+}
+
+/// Splits a streamed response body into lines
+///
+/// Bytes are buffered until a newline arrives, so that a multibyte character
+/// split across two network chunks is decoded in one piece.
+#[derive(Debug, Default)]
+#[allow(dead_code)]
+struct SseLineBuffer {
+    buffer: Vec<u8>,
+}
+
+#[allow(dead_code)]
+impl SseLineBuffer {
+    /// Add a chunk and return the lines it completes
+    fn push(&mut self, chunk: &[u8]) -> Vec<String> {
+        self.buffer.extend_from_slice(chunk);
+        let mut lines = Vec::new();
+        while let Some(newline) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=newline).collect();
+            lines.push(String::from_utf8_lossy(&line).into_owned());
+        }
+        lines
+    }
+
+    /// The trailing bytes after the last newline
+    fn finish(self) -> String {
+        String::from_utf8_lossy(&self.buffer).into_owned()
+    }
+}
+
+/// Parse one line of a chat completions event stream
+#[allow(dead_code)]
+fn parse_sse_line(line: &str) -> LlmResult<SseEvent> {
+    let Some(data) = line.trim().strip_prefix("data:") else {
+        return Ok(SseEvent::Skip);
+    };
+    let data = data.trim();
+    if data == "[DONE]" {
+        return Ok(SseEvent::Done);
+    }
+
+    let chunk: ChatChunk = serde_json::from_str(data)?;
+    Ok(chunk
+        .choices
+        .into_iter()
+        .find_map(|c| c.delta.content)
+        .filter(|c| !c.is_empty())
+        .map_or(SseEvent::Skip, SseEvent::Delta))
+}
+
+#[cfg(feature = "llm-online")]
+#[async_trait]
+impl LlmClient for OpenAiClient {
+    async fn complete(&self, prompt: &str) -> LlmResult<String> {
+        if self.stream {
+            return self.complete_streaming(prompt, |_| {}).await;
+        }
+
+        let response = self.send(prompt, false).await?;
+        let chat: ChatResponse = response
+            .json()
+            .await
+            .map_err(|e| LlmError::ParseError(e.to_string()))?;
+
+        if let Some(ref usage) = chat.usage {
+            tracing::debug!(
+                "Chat completion used {} prompt tokens, {} completion tokens",
+                usage.prompt_tokens,
+                usage.completion_tokens
+            );
+        }
+
+        chat.choices
+            .into_iter()
+            .next()
+            .and_then(|c| c.message.content)
+            .ok_or_else(|| LlmError::InvalidResponse("Response contained no content".to_string()))
+    }
+
+    async fn embed(&self, texts: &[String]) -> LlmResult<Vec<Vec<f32>>> {
+        let request = EmbeddingsRequest {
+            model: self.embedding_model.as_deref().unwrap_or(&self.model),
+            input: texts,
+        };
+        let response: EmbeddingsResponse = self
+            .post("embeddings", &request)
+            .await?
+            .json()
+            .await
+            .map_err(|e| LlmError::ParseError(e.to_string()))?;
+
+        if response.data.len() != texts.len() {
+            return Err(LlmError::InvalidResponse(format!(
+                "Expected {} embeddings, got {}",
+                texts.len(),
+                response.data.len()
+            )));
+        }
+        let mut data = response.data;
+        data.sort_by_key(|e| e.index);
+        Ok(data.into_iter().map(|e| e.embedding).collect())
+    }
+
+    fn model_name(&self) -> &str {
+        &self.model
+    }
+
+    fn max_tokens(&self) -> usize {
+        self.max_context_tokens
+    }
+
+    fn temperature(&self) -> Option<f32> {
+        Some(self.temperature)
+    }
+
+    async fn is_ready(&self) -> bool {
+        self.list_models().await.is_ok()
+    }
+}
+
+#[cfg(not(feature = "llm-online"))]
+#[async_trait]
+impl LlmClient for OpenAiClient {
+    async fn complete(&self, _prompt: &str) -> LlmResult<String> {
+        Err(LlmError::FeatureNotAvailable(
+            "Online LLM".to_string(),
+            "llm-online".to_string(),
+        ))
+    }
+
+    fn model_name(&self) -> &str {
+        &self.model
+    }
+
+    fn max_tokens(&self) -> usize {
+        self.max_context_tokens
+    }
+
+    fn temperature(&self) -> Option<f32> {
+        Some(self.temperature)
+    }
+
+    async fn is_ready(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_openai_client_new() {
+        let client = OpenAiClient::new("http://localhost:8000/v1/", "qwen2.5");
+        assert_eq!(client.base_url(), "http://localhost:8000/v1");
+        assert_eq!(client.model_name(), "qwen2.5");
+        assert_eq!(client.max_tokens(), 4096);
+        assert!(!client.has_api_key());
+    }
+
+    #[test]
+    fn test_build_request_json_modes() {
+        let client = OpenAiClient::new("http://localhost:8000/v1", "m")
+            .with_system_prompt("You are a data modeller")
+            .with_max_completion_tokens(256);
+        let json = serde_json::to_value(client.build_request("Hi", false)).unwrap();
+        assert_eq!(json["messages"][0]["role"], "system");
+        assert_eq!(json["messages"][1]["content"], "Hi");
+        assert_eq!(json["max_tokens"], 256);
+        assert!(json.get("response_format").is_none());
+
+        let client = client.with_json_mode();
+        let json = serde_json::to_value(client.build_request("Hi", false)).unwrap();
+        assert_eq!(json["response_format"]["type"], "json_object");
+
+        let schema = serde_json::json!({"type": "object"});
+        let client = client.with_json_schema("schema", schema.clone());
+        let json = serde_json::to_value(client.build_request("Hi", true)).unwrap();
+        assert_eq!(json["response_format"]["type"], "json_schema");
+        assert_eq!(json["response_format"]["json_schema"]["schema"], schema);
+        assert_eq!(json["stream"], true);
+    }
+
+    #[test]
+    fn test_parse_sse_line() {
+        let line = r#"data: {"choices":[{"delta":{"content":"Hel"}}]}"#;
+        assert_eq!(
+            parse_sse_line(line).unwrap(),
+            SseEvent::Delta("Hel".to_string())
+        );
+        assert_eq!(parse_sse_line("data: [DONE]").unwrap(), SseEvent::Done);
+        assert_eq!(parse_sse_line(": keep-alive").unwrap(), SseEvent::Skip);
+        assert_eq!(
+            parse_sse_line(r#"data: {"choices":[{"delta":{"role":"assistant"}}]}"#).unwrap(),
+            SseEvent::Skip
+        );
+        assert!(parse_sse_line("data: {not json").is_err());
+    }
+
+    #[test]
+    fn test_sse_line_buffer_split_multibyte() {
+        let body = "data: {\"choices\":[{\"delta\":{\"content\":\"Größe\"}}]}\ndata: [DONE]";
+        let bytes = body.as_bytes();
+        // Split inside the two-byte encoding of 'ö'
+        let split = body.find('ö').unwrap() + 1;
+
+        let mut lines = SseLineBuffer::default();
+        assert!(lines.push(&bytes[..split]).is_empty());
+        let complete = lines.push(&bytes[split..]);
+        assert_eq!(complete.len(), 1);
+        assert_eq!(
+            parse_sse_line(&complete[0]).unwrap(),
+            SseEvent::Delta("Größe".to_string())
+        );
+        assert_eq!(lines.finish(), "data: [DONE]");
+    }
+
+    #[cfg(feature = "llm-online")]
+    mod server {
+        use super::*;
+        use std::io::{BufRead, BufReader, Read, Write};
+        use std::net::TcpListener;
+        use std::sync::mpsc;
+
+        /// Serve one canned HTTP response and hand back the request it received
+        fn mock_server(
+            content_type: &'static str,
+            body: String,
+        ) -> (String, mpsc::Receiver<String>) {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}/v1", listener.local_addr().unwrap());
+            let (tx, rx) = mpsc::channel();
+
+            std::thread::spawn(move || {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut head = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = len.trim().parse().unwrap();
+                    }
+                    if line == "\r\n" {
+                        break;
+                    }
+                    head.push_str(&line);
+                }
+                let mut request_body = vec![0; content_length];
+                reader.read_exact(&mut request_body).unwrap();
+                tx.send(format!(
+                    "{}\n{}",
+                    head,
+                    String::from_utf8_lossy(&request_body)
+                ))
+                .unwrap();
+
+                let mut stream = stream;
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    content_type,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            });
+
+            (url, rx)
+        }
+
+        #[tokio::test]
+        async fn test_complete_against_mock_server() {
+            let body = serde_json::json!({
+                "choices": [{"message": {"role": "assistant", "content": "{\"ok\": true}"}}],
+                "usage": {"prompt_tokens": 5, "completion_tokens": 3}
+            })
+            .to_string();
+            let (url, requests) = mock_server("application/json", body);
+
+            let client = OpenAiClient::new(url, "local-model")
+                .with_api_key("secret")
+                .with_json_mode();
+            let response = client.complete("Describe the schema").await.unwrap();
+            assert_eq!(response, "{\"ok\": true}");
+
+            let request = requests.recv().unwrap();
+            assert!(request.starts_with("POST /v1/chat/completions"));
+            assert!(
+                request
+                    .to_lowercase()
+                    .contains("authorization: bearer secret")
+            );
+            assert!(request.contains("\"json_object\""));
+            assert!(request.contains("Describe the schema"));
+        }
+
+        #[tokio::test]
+        async fn test_embed_against_mock_server() {
+            let body = serde_json::json!({
+                "data": [
+                    {"index": 1, "embedding": [0.0, 1.0]},
+                    {"index": 0, "embedding": [1.0, 0.0]}
+                ]
+            })
+            .to_string();
+            let (url, requests) = mock_server("application/json", body);
+
+            let client = OpenAiClient::new(url, "local-model").with_embedding_model("embedder");
+            let vectors = client
+                .embed(&["first".to_string(), "second".to_string()])
+                .await
+                .unwrap();
+            assert_eq!(vectors, vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+
+            let request = requests.recv().unwrap();
+            assert!(request.starts_with("POST /v1/embeddings"));
+            assert!(request.contains("\"embedder\""));
+        }
+
+        #[tokio::test]
+        async fn test_streaming_against_mock_server() {
+            let body = [
+                r#"data: {"choices":[{"delta":{"role":"assistant"}}]}"#,
+                r#"data: {"choices":[{"delta":{"content":"Hello"}}]}"#,
+                r#"data: {"choices":[{"delta":{"content":", world"}}]}"#,
+                "data: [DONE]",
+            ]
+            .join("\n\n");
+            let (url, _requests) = mock_server("text/event-stream", body);
+
+            let client = OpenAiClient::new(url, "local-model").with_streaming(true);
+            let mut deltas = Vec::new();
+            let text = client
+                .complete_streaming("Hi", |d| deltas.push(d.to_string()))
+                .await
+                .unwrap();
+            assert_eq!(text, "Hello, world");
+            assert_eq!(deltas, vec!["Hello", ", world"]);
+        }
+    }
+}
@@ -44,6 +44,32 @@ Sample values: {sample_values}
 
 Return only the description text, no JSON or formatting."#;
 
+/// Prompt template for documenting the columns of a table
+pub const TABLE_DOCUMENTATION_PROMPT: &str = r#"You are a data documentation expert. Your task is to write missing documentation for a table in a data contract.
+
+## Rules
+1. NEVER rename columns or change their data types - return every column with its name and dataType exactly as given
+2. Only write the items listed under "Missing Documentation"; existing text is kept as is
+3. Descriptions are 1-2 sentences of plain business language
+4. Business names are short Title Case labels (e.g., "Customer Email")
+5. Use sibling columns, example values, documentation and knowledge articles for context, but do not invent facts they do not support
+
+## Table
+```json
+{table}
+```
+
+## Missing Documentation
+{missing}
+
+{documentation_section}
+
+{knowledge_section}
+
+## Output
+Return ONLY a valid JSON object of this form, with null for anything you cannot describe:
+{"description": "table description", "columns": [{"name": "column", "dataType": "type", "description": "column description", "businessName": "Business Name"}]}"#;
+
 /// Context for building a refinement prompt
 #[derive(Debug, Clone, Default)]
 pub struct PromptContext {
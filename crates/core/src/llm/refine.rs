@@ -6,11 +6,13 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use super::cache::CacheMode;
 use super::client::LlmClient;
 use super::config::RefinementConfig;
 use super::docs::load_documentation;
 use super::error::{LlmError, LlmResult};
 use super::prompt::{PromptContext, parse_llm_response};
+use super::retrieval::{fit_documentation, schema_field_names};
 use super::validation::{ValidationResult, validate_refinement};
 
 /// Result of a schema refinement operation
@@ -105,6 +107,16 @@ impl<C: LlmClient> SchemaRefiner<C> {
             PromptContext::new(&schema_json).with_max_tokens(self.config.max_context_tokens);
 
         if let Some(doc) = documentation {
+            // Large documentation is narrowed to the chunks about these fields
+            let doc = fit_documentation(
+                &self.client,
+                &self.config.retrieval,
+                &doc,
+                &[],
+                &schema_field_names(schema),
+                self.config.max_context_tokens / 4,
+            )
+            .await;
             context = context.with_documentation(doc);
         }
 
@@ -228,6 +240,15 @@ pub async fn refine_schema<C: LlmClient>(
     let mut context = PromptContext::new(&schema_json).with_max_tokens(config.max_context_tokens);
 
     if let Some(doc) = documentation {
+        let doc = fit_documentation(
+            client,
+            &config.retrieval,
+            &doc,
+            &[],
+            &schema_field_names(schema),
+            config.max_context_tokens / 4,
+        )
+        .await;
         context = context.with_documentation(doc);
     }
 
@@ -308,6 +329,12 @@ impl RefinementBuilder {
         self
     }
 
+    /// Set the LLM mode to an OpenAI-compatible server
+    pub fn with_openai(mut self, base_url: impl Into<String>, model: impl Into<String>) -> Self {
+        self.config.llm_mode = super::config::LlmMode::openai(base_url, model);
+        self
+    }
+
     /// Set the LLM mode to offline (llama.cpp)
     pub fn with_local_model(mut self, model_path: impl Into<std::path::PathBuf>) -> Self {
         self.config.llm_mode = super::config::LlmMode::Offline {
@@ -359,6 +386,18 @@ impl RefinementBuilder {
         self
     }
 
+    /// Cache responses in `path` (a directory, or a `.duckdb` staging database)
+    pub fn with_cache(mut self, path: impl Into<std::path::PathBuf>, mode: CacheMode) -> Self {
+        self.config = self.config.with_cache(path, mode);
+        self
+    }
+
+    /// Set how relevant documentation is selected
+    pub fn with_retrieval(mut self, retrieval: super::retrieval::RetrievalConfig) -> Self {
+        self.config = self.config.with_retrieval(retrieval);
+        self
+    }
+
     /// Build the configuration
     pub fn build(self) -> RefinementConfig {
         self.config
@@ -0,0 +1,813 @@
+//! Retrieval of relevant documentation for LLM prompts
+//!
+//! Documentation for a large schema rarely fits in a prompt, and cutting it
+//! at a token limit drops whatever comes last. A [`DocumentIndex`] instead
+//! splits documentation files and knowledge base articles into chunks, ranks
+//! them against each field with BM25 and, when the backend provides them,
+//! embeddings, and assembles the best chunks per field within a token budget.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use data_modelling_core::llm::{DocumentIndex, RetrievalConfig};
+//!
+//! let mut index = DocumentIndex::new(RetrievalConfig::default());
+//! index.add_file(Path::new("docs/data-dictionary.md"))?;
+//! for article in &articles {
+//!     index.add_article(article);
+//! }
+//!
+//! // Optional: rank with embeddings from the configured backend as well
+//! index.embed_chunks(&client).await?;
+//!
+//! let fields = vec!["customer_id".to_string(), "order_date".to_string()];
+//! let context = index.context_for_fields_with(&client, &fields, 1024).await;
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::client::LlmClient;
+use super::docs::{load_documentation, truncate_documentation};
+use super::error::LlmResult;
+use super::prompt::estimate_tokens;
+use crate::models::knowledge::KnowledgeArticle;
+
+/// BM25 term frequency saturation
+const BM25_K1: f64 = 1.2;
+/// BM25 document length normalisation
+const BM25_B: f64 = 0.75;
+/// Rank offset for reciprocal rank fusion of BM25 and embedding rankings
+const RRF_K: f64 = 60.0;
+/// Number of texts sent per embeddings request
+const EMBED_BATCH_SIZE: usize = 32;
+
+/// Words that carry no meaning for matching fields to documentation
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "in", "is", "it", "of", "on",
+    "or", "that", "the", "this", "to", "was", "with",
+];
+
+/// Settings for documentation retrieval
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetrievalConfig {
+    /// Select relevant chunks when documentation exceeds its share of the
+    /// prompt; when disabled, documentation is truncated instead
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Target chunk size in tokens
+    #[serde(default = "default_chunk_tokens")]
+    pub chunk_tokens: usize,
+
+    /// Number of chunks selected per field
+    #[serde(default = "default_top_k")]
+    pub top_k: usize,
+
+    /// Also rank chunks by embedding similarity using the LLM backend
+    #[serde(default)]
+    pub embeddings: bool,
+
+    /// Embedding model, if different from the completion model
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding_model: Option<String>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_chunk_tokens() -> usize {
+    256
+}
+
+fn default_top_k() -> usize {
+    3
+}
+
+impl Default for RetrievalConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            chunk_tokens: default_chunk_tokens(),
+            top_k: default_top_k(),
+            embeddings: false,
+            embedding_model: None,
+        }
+    }
+}
+
+impl RetrievalConfig {
+    /// Set the number of chunks selected per field
+    pub fn with_top_k(mut self, top_k: usize) -> Self {
+        self.top_k = top_k.max(1);
+        self
+    }
+
+    /// Set the target chunk size in tokens
+    pub fn with_chunk_tokens(mut self, tokens: usize) -> Self {
+        self.chunk_tokens = tokens.max(16);
+        self
+    }
+
+    /// Rank with embeddings, optionally from a dedicated embedding model
+    pub fn with_embeddings(mut self, model: Option<String>) -> Self {
+        self.embeddings = true;
+        self.embedding_model = model;
+        self
+    }
+}
+
+/// A chunk of documentation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DocChunk {
+    /// File path or article reference the chunk came from
+    pub source: String,
+    /// Nearest Markdown heading above the chunk
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heading: Option<String>,
+    /// Chunk text
+    pub text: String,
+}
+
+impl DocChunk {
+    /// Format the chunk for a prompt, labelled with its source
+    pub fn to_prompt_text(&self) -> String {
+        match &self.heading {
+            Some(heading) => format!("--- {} / {} ---\n{}", self.source, heading, self.text),
+            None => format!("--- {} ---\n{}", self.source, self.text),
+        }
+    }
+}
+
+/// Split documentation into chunks of roughly `chunk_tokens` tokens
+///
+/// Markdown headings start a new chunk and label the chunks below them.
+/// Paragraphs are kept together where they fit; longer ones are split at
+/// line and then word boundaries.
+pub fn chunk_document(source: &str, text: &str, chunk_tokens: usize) -> Vec<DocChunk> {
+    let max_chars = chunk_tokens.max(16) * 4;
+    let mut chunks = Vec::new();
+    let mut heading: Option<String> = None;
+    let mut current = String::new();
+
+    let flush = |current: &mut String, heading: &Option<String>, chunks: &mut Vec<DocChunk>| {
+        let text = current.trim();
+        if !text.is_empty() {
+            chunks.push(DocChunk {
+                source: source.to_string(),
+                heading: heading.clone(),
+                text: text.to_string(),
+            });
+        }
+        current.clear();
+    };
+
+    for paragraph in split_paragraphs(text) {
+        let mut body = paragraph.as_str();
+        if let Some(title) = markdown_heading(&paragraph) {
+            flush(&mut current, &heading, &mut chunks);
+            heading = Some(title);
+            body = paragraph
+                .split_once('\n')
+                .map(|(_, rest)| rest.trim())
+                .unwrap_or_default();
+        }
+        if body.is_empty() {
+            continue;
+        }
+
+        for piece in split_to_size(body, max_chars) {
+            if !current.is_empty() && current.len() + piece.len() + 2 > max_chars {
+                flush(&mut current, &heading, &mut chunks);
+            }
+            if !current.is_empty() {
+                current.push_str("\n\n");
+            }
+            current.push_str(&piece);
+        }
+    }
+    flush(&mut current, &heading, &mut chunks);
+
+    chunks
+}
+
+/// Split text into blank-line separated paragraphs, keeping headings on their own
+fn split_paragraphs(text: &str) -> Vec<String> {
+    let mut paragraphs = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+
+    for line in text.lines() {
+        let trimmed = line.trim_end();
+        if trimmed.trim().is_empty() {
+            if !current.is_empty() {
+                paragraphs.push(current.join("\n"));
+                current.clear();
+            }
+            continue;
+        }
+        if trimmed.starts_with('#') && !current.is_empty() {
+            paragraphs.push(current.join("\n"));
+            current.clear();
+        }
+        current.push(trimmed);
+    }
+    if !current.is_empty() {
+        paragraphs.push(current.join("\n"));
+    }
+
+    paragraphs
+}
+
+/// Heading text if the paragraph starts with a Markdown heading
+fn markdown_heading(paragraph: &str) -> Option<String> {
+    let first = paragraph.lines().next()?;
+    let title = first.trim_start_matches('#');
+    if title.len() == first.len() || !title.starts_with(' ') {
+        return None;
+    }
+    Some(title.trim().to_string())
+}
+
+/// Split a paragraph into pieces of at most `max_chars`, at line then word boundaries
+fn split_to_size(paragraph: &str, max_chars: usize) -> Vec<String> {
+    if paragraph.len() <= max_chars {
+        return vec![paragraph.to_string()];
+    }
+
+    let mut pieces = Vec::new();
+    let mut current = String::new();
+    for line in paragraph.lines() {
+        for word in line.split_whitespace() {
+            if !current.is_empty() && current.len() + word.len() + 1 > max_chars {
+                pieces.push(std::mem::take(&mut current));
+            }
+            if !current.is_empty() {
+                current.push(' ');
+            }
+            current.push_str(word);
+        }
+        if current.len() > max_chars / 2 {
+            pieces.push(std::mem::take(&mut current));
+        } else if !current.is_empty() {
+            current.push('\n');
+        }
+    }
+    let rest = current.trim_end();
+    if !rest.is_empty() {
+        pieces.push(rest.to_string());
+    }
+
+    pieces
+}
+
+/// Split text into lowercase search terms
+///
+/// Splits on punctuation as well as `snake_case` and `camelCase` boundaries,
+/// so a field called `customerEmail` matches documentation about the
+/// "customer email".
+pub fn tokenize(text: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    let mut current = String::new();
+    let mut previous_lower = false;
+
+    let mut push = |current: &mut String| {
+        if current.len() > 1 && !STOPWORDS.contains(&current.as_str()) {
+            terms.push(current.clone());
+        }
+        current.clear();
+    };
+
+    for c in text.chars() {
+        if !c.is_alphanumeric() {
+            push(&mut current);
+            previous_lower = false;
+            continue;
+        }
+        if c.is_uppercase() && previous_lower {
+            push(&mut current);
+        }
+        previous_lower = c.is_lowercase() || c.is_ascii_digit();
+        current.extend(c.to_lowercase());
+    }
+    push(&mut current);
+
+    terms
+}
+
+/// Search index over documentation chunks
+#[derive(Debug, Clone, Default)]
+pub struct DocumentIndex {
+    config: RetrievalConfig,
+    chunks: Vec<DocChunk>,
+    /// Term frequencies per chunk
+    term_freqs: Vec<HashMap<String, usize>>,
+    /// Number of terms per chunk
+    lengths: Vec<usize>,
+    /// Number of chunks containing each term
+    doc_freqs: HashMap<String, usize>,
+    /// Chunk embeddings, once computed
+    embeddings: Option<Vec<Vec<f32>>>,
+}
+
+impl DocumentIndex {
+    /// Create an empty index
+    pub fn new(config: RetrievalConfig) -> Self {
+        Self {
+            config,
+            ..Default::default()
+        }
+    }
+
+    /// Get the retrieval settings
+    pub fn config(&self) -> &RetrievalConfig {
+        &self.config
+    }
+
+    /// Chunk and index documentation text
+    pub fn add_text(&mut self, source: &str, text: &str) {
+        for chunk in chunk_document(source, text, self.config.chunk_tokens) {
+            self.add_chunk(chunk);
+        }
+    }
+
+    /// Load, chunk and index a documentation file (text, Markdown, docx or PDF)
+    pub fn add_file(&mut self, path: &Path) -> LlmResult<()> {
+        let text = load_documentation(path)?;
+        self.add_text(&path.display().to_string(), &text);
+        Ok(())
+    }
+
+    /// Chunk and index a knowledge base article
+    pub fn add_article(&mut self, article: &KnowledgeArticle) {
+        let source = format!("KB-{:04} {}", article.number, article.title);
+        let text = format!("{}\n\n{}", article.summary, article.content);
+        self.add_text(&source, &text);
+    }
+
+    /// Index a single chunk
+    pub fn add_chunk(&mut self, chunk: DocChunk) {
+        let mut searchable = chunk.text.clone();
+        if let Some(heading) = &chunk.heading {
+            searchable = format!("{}\n{}", heading, searchable);
+        }
+
+        let mut freqs: HashMap<String, usize> = HashMap::new();
+        let terms = tokenize(&searchable);
+        for term in &terms {
+            *freqs.entry(term.clone()).or_default() += 1;
+        }
+        for term in freqs.keys() {
+            *self.doc_freqs.entry(term.clone()).or_default() += 1;
+        }
+
+        self.lengths.push(terms.len());
+        self.term_freqs.push(freqs);
+        self.chunks.push(chunk);
+        // Embeddings no longer cover every chunk
+        self.embeddings = None;
+    }
+
+    /// Get the indexed chunks
+    pub fn chunks(&self) -> &[DocChunk] {
+        &self.chunks
+    }
+
+    /// Number of indexed chunks
+    pub fn len(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Check if the index has no chunks
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    /// Check if chunk embeddings have been computed
+    pub fn has_embeddings(&self) -> bool {
+        self.embeddings.is_some()
+    }
+
+    /// Rank chunks against a query with BM25
+    ///
+    /// Returns up to `k` chunks with a positive score, best first.
+    pub fn search(&self, query: &str, k: usize) -> Vec<(&DocChunk, f64)> {
+        self.bm25_ranking(query)
+            .into_iter()
+            .take(k)
+            .map(|(i, score)| (&self.chunks[i], score))
+            .collect()
+    }
+
+    /// Compute embeddings for every chunk using the client's backend
+    pub async fn embed_chunks<C: LlmClient + ?Sized>(&mut self, client: &C) -> LlmResult<()> {
+        let mut embeddings = Vec::with_capacity(self.chunks.len());
+        for batch in self.chunks.chunks(EMBED_BATCH_SIZE) {
+            let texts: Vec<String> = batch.iter().map(|c| c.to_prompt_text()).collect();
+            embeddings.extend(client.embed(&texts).await?);
+        }
+        self.embeddings = Some(embeddings);
+        Ok(())
+    }
+
+    /// Select documentation for a set of fields within a token budget, using BM25
+    ///
+    /// Each field query contributes its `top_k` best chunks; the best chunk of
+    /// every field is included before any field's second best.
+    pub fn context_for_fields(&self, fields: &[String], max_tokens: usize) -> String {
+        let rankings: Vec<Vec<usize>> = fields
+            .iter()
+            .map(|f| self.bm25_ranking(f).into_iter().map(|(i, _)| i).collect())
+            .collect();
+        self.assemble(&rankings, max_tokens)
+    }
+
+    /// Select documentation for a set of fields, also ranking by embeddings
+    ///
+    /// BM25 and embedding rankings are combined with reciprocal rank fusion.
+    /// Without chunk embeddings, or if the query embeddings fail, this is
+    /// the same as [`context_for_fields`](Self::context_for_fields).
+    pub async fn context_for_fields_with<C: LlmClient + ?Sized>(
+        &self,
+        client: &C,
+        fields: &[String],
+        max_tokens: usize,
+    ) -> String {
+        let Some(embeddings) = &self.embeddings else {
+            return self.context_for_fields(fields, max_tokens);
+        };
+        let queries = match client.embed(fields).await {
+            Ok(queries) => queries,
+            Err(e) => {
+                tracing::warn!("Falling back to keyword retrieval: {}", e);
+                return self.context_for_fields(fields, max_tokens);
+            }
+        };
+
+        let rankings: Vec<Vec<usize>> = fields
+            .iter()
+            .zip(&queries)
+            .map(|(field, query)| {
+                let mut similarity: Vec<(usize, f64)> = embeddings
+                    .iter()
+                    .enumerate()
+                    .map(|(i, e)| (i, cosine_similarity(query, e)))
+                    .collect();
+                similarity.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+                let mut fused: HashMap<usize, f64> = HashMap::new();
+                for (rank, (i, _)) in self.bm25_ranking(field).into_iter().enumerate() {
+                    *fused.entry(i).or_default() += 1.0 / (RRF_K + rank as f64 + 1.0);
+                }
+                for (rank, (i, _)) in similarity.into_iter().enumerate() {
+                    *fused.entry(i).or_default() += 1.0 / (RRF_K + rank as f64 + 1.0);
+                }
+
+                let mut fused: Vec<(usize, f64)> = fused.into_iter().collect();
+                fused.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+                fused.into_iter().map(|(i, _)| i).collect()
+            })
+            .collect();
+
+        self.assemble(&rankings, max_tokens)
+    }
+
+    /// Chunk indices with a positive BM25 score for the query, best first
+    fn bm25_ranking(&self, query: &str) -> Vec<(usize, f64)> {
+        let terms: HashSet<String> = tokenize(query).into_iter().collect();
+        if terms.is_empty() || self.chunks.is_empty() {
+            return Vec::new();
+        }
+
+        let n = self.chunks.len() as f64;
+        let average_length = self.lengths.iter().sum::<usize>() as f64 / n;
+
+        let mut scores: Vec<(usize, f64)> = self
+            .term_freqs
+            .iter()
+            .enumerate()
+            .filter_map(|(i, freqs)| {
+                let length = self.lengths[i] as f64;
+                let score: f64 = terms
+                    .iter()
+                    .filter_map(|term| {
+                        let tf = *freqs.get(term)? as f64;
+                        let df = self.doc_freqs[term] as f64;
+                        let idf = ((n - df + 0.5) / (df + 0.5) + 1.0).ln();
+                        let norm = BM25_K1 * (1.0 - BM25_B + BM25_B * length / average_length);
+                        Some(idf * tf * (BM25_K1 + 1.0) / (tf + norm))
+                    })
+                    .sum();
+                (score > 0.0).then_some((i, score))
+            })
+            .collect();
+        scores.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        scores
+    }
+
+    /// Take the top chunks of each ranking, round-robin, until the budget is used
+    fn assemble(&self, rankings: &[Vec<usize>], max_tokens: usize) -> String {
+        let mut selected: Vec<usize> = Vec::new();
+        for rank in 0..self.config.top_k {
+            for ranking in rankings {
+                if let Some(&i) = ranking.get(rank)
+                    && !selected.contains(&i)
+                {
+                    selected.push(i);
+                }
+            }
+        }
+
+        let mut used = 0;
+        let mut parts = Vec::new();
+        for i in selected {
+            let text = self.chunks[i].to_prompt_text();
+            let tokens = estimate_tokens(&text);
+            if used + tokens > max_tokens {
+                continue;
+            }
+            used += tokens;
+            parts.push(text);
+        }
+        parts.join("\n\n")
+    }
+}
+
+/// Cosine similarity of two vectors (0.0 if either is empty or zero)
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f64 {
+    let (mut dot, mut norm_a, mut norm_b) = (0.0f64, 0.0f64, 0.0f64);
+    for (x, y) in a.iter().zip(b) {
+        dot += (*x as f64) * (*y as f64);
+        norm_a += (*x as f64) * (*x as f64);
+        norm_b += (*y as f64) * (*y as f64);
+    }
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a.sqrt() * norm_b.sqrt())
+}
+
+/// Collect the property names of a JSON schema, including nested ones
+pub fn schema_field_names(schema: &Value) -> Vec<String> {
+    fn collect(schema: &Value, names: &mut Vec<String>) {
+        if let Some(properties) = schema.get("properties").and_then(|p| p.as_object()) {
+            for (name, property) in properties {
+                if !names.contains(name) {
+                    names.push(name.clone());
+                }
+                collect(property, names);
+            }
+        }
+        if let Some(items) = schema.get("items") {
+            collect(items, names);
+        }
+    }
+
+    let mut names = Vec::new();
+    collect(schema, &mut names);
+    names
+}
+
+/// Fit documentation into `max_tokens` for a prompt about `fields`
+///
+/// Documentation that fits is kept whole. Otherwise, with retrieval enabled,
+/// it is chunked and the chunks most relevant to the fields are selected,
+/// falling back to truncation when nothing matches. Chunks of the `extra`
+/// knowledge articles that match the fields fill any remaining budget.
+pub(crate) async fn fit_documentation<C: LlmClient + ?Sized>(
+    client: &C,
+    config: &RetrievalConfig,
+    documentation: &str,
+    extra: &[&KnowledgeArticle],
+    fields: &[String],
+    max_tokens: usize,
+) -> String {
+    let fits = estimate_tokens(documentation) <= max_tokens;
+    if fits && extra.is_empty() {
+        return documentation.to_string();
+    }
+    if !config.enabled {
+        return truncate_documentation(documentation, max_tokens);
+    }
+
+    let mut index = DocumentIndex::new(config.clone());
+    if !fits {
+        index.add_text("documentation", documentation);
+    }
+    for article in extra {
+        index.add_article(article);
+    }
+    if config.embeddings
+        && let Err(e) = index.embed_chunks(client).await
+    {
+        tracing::warn!(
+            "Could not embed documentation, using keyword retrieval: {}",
+            e
+        );
+    }
+
+    let budget = if fits {
+        max_tokens - estimate_tokens(documentation)
+    } else {
+        max_tokens
+    };
+    let context = index.context_for_fields_with(client, fields, budget).await;
+
+    match (fits, context.trim().is_empty()) {
+        (true, true) => documentation.to_string(),
+        (true, false) if documentation.trim().is_empty() => context,
+        (true, false) => format!("{}\n\n{}", documentation.trim_end(), context),
+        (false, true) => truncate_documentation(documentation, max_tokens),
+        (false, false) => context,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+
+    use crate::llm::MockLlmClient;
+    use crate::llm::error::LlmError;
+
+    const DICTIONARY: &str = "# Data Dictionary\n\nThis document describes the sales schema.\n\n## Customers\n\nThe customer_id column is the surrogate key of a customer.\nEmail addresses are validated on signup.\n\n## Orders\n\nThe order_date is the calendar date on which the order was placed, in UTC.\n\n## Shipping\n\nShipments leave the warehouse within two days.";
+
+    /// Embeds texts by counting groups of related words, to make similarity predictable
+    struct KeywordEmbedder;
+
+    #[async_trait]
+    impl LlmClient for KeywordEmbedder {
+        async fn complete(&self, _prompt: &str) -> LlmResult<String> {
+            Err(LlmError::ConfigError("not used".to_string()))
+        }
+
+        fn model_name(&self) -> &str {
+            "keyword-embedder"
+        }
+
+        fn max_tokens(&self) -> usize {
+            4096
+        }
+
+        async fn embed(&self, texts: &[String]) -> LlmResult<Vec<Vec<f32>>> {
+            Ok(texts
+                .iter()
+                .map(|t| {
+                    let t = t.to_lowercase();
+                    [
+                        &["customer"][..],
+                        &["order"][..],
+                        &["warehouse", "shipment", "dispatch"][..],
+                    ]
+                    .iter()
+                    .map(|group| group.iter().map(|k| t.matches(k).count() as f32).sum())
+                    .collect()
+                })
+                .collect())
+        }
+
+        async fn is_ready(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(tokenize("customerEmail"), vec!["customer", "email"]);
+        assert_eq!(tokenize("order_date"), vec!["order", "date"]);
+        assert_eq!(tokenize("The ID of a customer"), vec!["id", "customer"]);
+    }
+
+    #[test]
+    fn test_chunk_document_headings() {
+        let chunks = chunk_document("dictionary.md", DICTIONARY, 256);
+        assert_eq!(chunks.len(), 4);
+        assert_eq!(chunks[1].heading.as_deref(), Some("Customers"));
+        assert!(chunks[1].text.starts_with("The customer_id column"));
+        assert!(chunks.iter().all(|c| c.source == "dictionary.md"));
+
+        let long = "word ".repeat(500);
+        let chunks = chunk_document("long.txt", &long, 64);
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|c| c.text.len() <= 64 * 4));
+    }
+
+    #[test]
+    fn test_search_and_context_for_fields() {
+        let mut index = DocumentIndex::new(RetrievalConfig::default().with_top_k(1));
+        index.add_text("dictionary.md", DICTIONARY);
+
+        let hits = index.search("orderDate", 3);
+        assert_eq!(hits[0].0.heading.as_deref(), Some("Orders"));
+        assert!(index.search("unrelated", 3).is_empty());
+
+        let fields = vec!["customer_id".to_string(), "order_date".to_string()];
+        let context = index.context_for_fields(&fields, 1000);
+        assert!(context.contains("surrogate key"));
+        assert!(context.contains("calendar date"));
+        assert!(!context.contains("warehouse"));
+
+        // A tight budget keeps only what fits
+        let context = index.context_for_fields(&fields, 40);
+        assert!(context.contains("surrogate key"));
+        assert!(!context.contains("calendar date"));
+    }
+
+    #[tokio::test]
+    async fn test_context_with_embeddings() {
+        let mut index = DocumentIndex::new(RetrievalConfig::default().with_top_k(1));
+        index.add_text("dictionary.md", DICTIONARY);
+        index.embed_chunks(&KeywordEmbedder).await.unwrap();
+        assert!(index.has_embeddings());
+
+        // No keyword overlap with "dispatch", but the embedding matches the shipping chunk
+        let fields = vec!["dispatch".to_string()];
+        let context = index
+            .context_for_fields_with(&KeywordEmbedder, &fields, 1000)
+            .await;
+        assert!(context.contains("Shipments leave"));
+
+        // Clients without embeddings fall back to BM25
+        let fields = vec!["order_date".to_string()];
+        let context = index
+            .context_for_fields_with(&MockLlmClient::new(""), &fields, 1000)
+            .await;
+        assert!(context.contains("calendar date"));
+    }
+
+    #[tokio::test]
+    async fn test_fit_documentation_replays_embeddings() {
+        use crate::llm::{CacheMode, CachingLlmClient};
+
+        let dir = tempfile::tempdir().unwrap();
+        let config = RetrievalConfig::default()
+            .with_top_k(1)
+            .with_embeddings(Some("embedder".to_string()));
+        let large = format!(
+            "{}\n\n{}",
+            DICTIONARY,
+            "Unrelated filler text.\n\n".repeat(200)
+        );
+        let fields = vec!["dispatch".to_string()];
+
+        let recording =
+            CachingLlmClient::with_file_store(KeywordEmbedder, dir.path(), CacheMode::Record)
+                .with_embedding_model("embedder");
+        let recorded = fit_documentation(&recording, &config, &large, &[], &fields, 200).await;
+        assert!(recorded.contains("Shipments leave"));
+
+        // Replay ranks with the recorded embeddings although the model has none
+        let replaying = CachingLlmClient::with_file_store(
+            MockLlmClient::failing(),
+            dir.path(),
+            CacheMode::Replay,
+        )
+        .with_embedding_model("embedder");
+        let replayed = fit_documentation(&replaying, &config, &large, &[], &fields, 200).await;
+        assert_eq!(replayed, recorded);
+        assert_eq!(replaying.stats().misses, 0);
+    }
+
+    #[tokio::test]
+    async fn test_fit_documentation() {
+        let client = MockLlmClient::new("");
+        let config = RetrievalConfig::default();
+        let fields = vec!["order_date".to_string()];
+
+        let small = fit_documentation(&client, &config, DICTIONARY, &[], &fields, 1000).await;
+        assert_eq!(small, DICTIONARY);
+
+        let large = format!(
+            "{}\n\n{}",
+            DICTIONARY,
+            "Unrelated filler text.\n\n".repeat(200)
+        );
+        let fitted = fit_documentation(&client, &config, &large, &[], &fields, 200).await;
+        assert!(fitted.contains("calendar date"));
+        assert!(estimate_tokens(&fitted) <= 200);
+    }
+
+    #[test]
+    fn test_schema_field_names() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "id": {"type": "string"},
+                "address": {
+                    "type": "object",
+                    "properties": {"postcode": {"type": "string"}}
+                },
+                "lines": {
+                    "type": "array",
+                    "items": {"type": "object", "properties": {"sku": {"type": "string"}}}
+                }
+            }
+        });
+        let mut names = schema_field_names(&schema);
+        names.sort();
+        assert_eq!(names, vec!["address", "id", "lines", "postcode", "sku"]);
+    }
+}
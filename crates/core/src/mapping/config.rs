@@ -101,6 +101,8 @@ pub enum TransformFormat {
     Python,
     /// PySpark transformation
     Spark,
+    /// dbt staging model (SQL with Jinja source/ref)
+    Dbt,
 }
 
 impl Default for TransformFormat {
@@ -116,6 +118,7 @@ impl std::fmt::Display for TransformFormat {
             TransformFormat::Jq => write!(f, "jq"),
             TransformFormat::Python => write!(f, "python"),
             TransformFormat::Spark => write!(f, "spark"),
+            TransformFormat::Dbt => write!(f, "dbt"),
         }
     }
 }
@@ -129,6 +132,7 @@ impl std::str::FromStr for TransformFormat {
             "jq" => Ok(TransformFormat::Jq),
             "python" | "py" => Ok(TransformFormat::Python),
             "spark" | "pyspark" => Ok(TransformFormat::Spark),
+            "dbt" => Ok(TransformFormat::Dbt),
             _ => Err(format!("Unknown transform format: {}", s)),
         }
     }
@@ -184,6 +188,10 @@ mod tests {
             "spark".parse::<TransformFormat>().unwrap(),
             TransformFormat::Spark
         );
+        assert_eq!(
+            "dbt".parse::<TransformFormat>().unwrap(),
+            TransformFormat::Dbt
+        );
         assert!("invalid".parse::<TransformFormat>().is_err());
     }
 }
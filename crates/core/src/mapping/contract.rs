@@ -0,0 +1,616 @@
+//! Contract-aware matching between ODCS tables
+//!
+//! [`SchemaMatcher`] only sees JSON Schema property names and types. When
+//! both sides are ODCS contracts, columns carry more evidence: business
+//! names, authoritative definitions, glossary terms, physical names and
+//! classifications. [`ContractMatcher`] matches on that evidence first and
+//! falls back to name-based matching for the remaining columns.
+//!
+//! The resulting lineage can be written into the target contract with
+//! [`apply_lineage`], which fills `transformSourceObjects` and
+//! `transformLogic` on the mapped target columns.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use super::config::MappingConfig;
+use super::error::MappingResult;
+use super::generator::transform_to_sql;
+use super::matcher::{SchemaMatcher, calculate_compatibility_score, can_coerce, types_compatible};
+use super::types::{
+    FieldMapping, MappingStats, MatchMethod, SchemaMapping, TransformMapping, TransformType,
+};
+use crate::export::JSONSchemaExporter;
+use crate::models::{Column, Table};
+
+/// Authoritative definition types that point at a business glossary
+const GLOSSARY_DEFINITION_TYPES: &[&str] = &["businessdefinition", "glossary", "glossaryterm"];
+
+/// Column tag keys that name a glossary term
+const GLOSSARY_TAG_KEYS: &[&str] = &["glossary", "glossaryterm", "term"];
+
+/// Evidence that a source column corresponds to a target column
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MatchSignal {
+    /// Both columns reference the same authoritative definition
+    AuthoritativeDefinition { url: String },
+    /// Both columns are linked to the same glossary term
+    GlossaryTerm { term: String },
+    /// Both columns have the same business name
+    BusinessName { name: String },
+    /// Both columns have the same physical name
+    PhysicalName { name: String },
+}
+
+impl MatchSignal {
+    /// Confidence contributed by this signal
+    pub fn weight(&self) -> f64 {
+        match self {
+            MatchSignal::AuthoritativeDefinition { .. } => 1.0,
+            MatchSignal::GlossaryTerm { .. } => 0.95,
+            MatchSignal::BusinessName { .. } => 0.9,
+            MatchSignal::PhysicalName { .. } => 0.9,
+        }
+    }
+}
+
+impl std::fmt::Display for MatchSignal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MatchSignal::AuthoritativeDefinition { url } => {
+                write!(f, "shared authoritative definition {}", url)
+            }
+            MatchSignal::GlossaryTerm { term } => write!(f, "glossary term '{}'", term),
+            MatchSignal::BusinessName { name } => write!(f, "business name '{}'", name),
+            MatchSignal::PhysicalName { name } => write!(f, "physical name '{}'", name),
+        }
+    }
+}
+
+/// Signals that led to a contract-based column match
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MatchEvidence {
+    /// Source column name
+    pub source: String,
+    /// Target column name
+    pub target: String,
+    /// Signals supporting the match
+    pub signals: Vec<MatchSignal>,
+}
+
+/// Result of matching two ODCS tables
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractMapping {
+    /// Column mapping between the tables
+    pub mapping: SchemaMapping,
+    /// Contract evidence for matches made on metadata rather than names
+    pub evidence: Vec<MatchEvidence>,
+    /// Mapped columns whose classifications differ
+    pub warnings: Vec<String>,
+}
+
+/// Matches ODCS tables using contract metadata as well as column names
+pub struct ContractMatcher {
+    config: MappingConfig,
+}
+
+impl ContractMatcher {
+    /// Create a matcher with default configuration
+    pub fn new() -> Self {
+        Self {
+            config: MappingConfig::default(),
+        }
+    }
+
+    /// Create a matcher with custom configuration
+    pub fn with_config(config: MappingConfig) -> Self {
+        Self { config }
+    }
+
+    /// Match the columns of a source table to the columns of a target table
+    pub fn match_tables(&self, source: &Table, target: &Table) -> MappingResult<ContractMapping> {
+        // Phase 1: contract signals, strongest pairs first
+        let mut candidates = Vec::new();
+        for (si, source_col) in source.columns.iter().enumerate() {
+            for (ti, target_col) in target.columns.iter().enumerate() {
+                let signals = contract_signals(source_col, target_col);
+                if let Some(score) = signals.iter().map(MatchSignal::weight).reduce(f64::max) {
+                    candidates.push((score, si, ti, signals));
+                }
+            }
+        }
+        candidates.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)).then(a.2.cmp(&b.2)));
+
+        let mut mapping = SchemaMapping::empty();
+        let mut evidence = Vec::new();
+        let mut used_sources: HashSet<usize> = HashSet::new();
+        let mut used_targets: HashSet<usize> = HashSet::new();
+
+        for (score, si, ti, signals) in candidates {
+            if used_sources.contains(&si) || used_targets.contains(&ti) {
+                continue;
+            }
+            used_sources.insert(si);
+            used_targets.insert(ti);
+
+            let source_col = &source.columns[si];
+            let target_col = &target.columns[ti];
+            push_match(
+                &mut mapping,
+                source_col,
+                target_col,
+                score,
+                self.config.suggest_type_coercions,
+            );
+            evidence.push(MatchEvidence {
+                source: source_col.name.clone(),
+                target: target_col.name.clone(),
+                signals,
+            });
+        }
+
+        // Phase 2: name-based matching for the remaining columns
+        let remaining_source = subset(source, &used_sources);
+        let remaining_target = subset(target, &used_targets);
+        let by_name = SchemaMatcher::with_config(self.config.clone()).match_schemas(
+            &JSONSchemaExporter::export_table(&remaining_source),
+            &JSONSchemaExporter::export_table(&remaining_target),
+        )?;
+
+        mapping.direct_mappings.extend(by_name.direct_mappings);
+        mapping.transformations.extend(by_name.transformations);
+        mapping.gaps = by_name.gaps;
+        mapping
+            .gaps
+            .sort_by(|a, b| a.target_path.cmp(&b.target_path));
+        mapping.extras = by_name.extras;
+        mapping.extras.sort();
+
+        mapping.stats = MappingStats {
+            source_fields: source.columns.len(),
+            target_fields: target.columns.len(),
+            direct_mapped: mapping.direct_mappings.len(),
+            transform_mapped: mapping.transformations.len(),
+            gaps_count: mapping.gaps.len(),
+            required_gaps: mapping.gaps.iter().filter(|g| g.required).count(),
+            extras_count: mapping.extras.len(),
+        };
+        mapping.compatibility_score = calculate_compatibility_score(&mapping);
+
+        let warnings = classification_warnings(&mapping, source, target);
+
+        Ok(ContractMapping {
+            mapping,
+            evidence,
+            warnings,
+        })
+    }
+}
+
+impl Default for ContractMatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Write mapping lineage into the target table's columns
+///
+/// Each mapped target column gets the qualified source columns in
+/// `transform_source_objects` and a SQL expression in `transform_logic`.
+/// Transformations without a `transform_description` also get one. Returns
+/// the number of target columns updated.
+pub fn apply_lineage(mapping: &SchemaMapping, source: &Table, target: &mut Table) -> usize {
+    let source_object = qualified_table_name(source);
+    let physical = |name: &str| {
+        source
+            .columns
+            .iter()
+            .find(|c| c.name == name)
+            .and_then(|c| c.physical_name.clone())
+            .unwrap_or_else(|| name.to_string())
+    };
+    let mut updated = 0;
+
+    for direct in &mapping.direct_mappings {
+        let source_name = physical(&direct.source_path);
+        if let Some(column) = target
+            .columns
+            .iter_mut()
+            .find(|c| c.name == direct.target_path)
+        {
+            column.transform_source_objects = vec![format!("{}.{}", source_object, source_name)];
+            column.transform_logic = Some(transform_to_sql(&TransformType::Rename, &[source_name]));
+            updated += 1;
+        }
+    }
+
+    for transform in &mapping.transformations {
+        let source_names: Vec<String> =
+            transform.source_paths.iter().map(|p| physical(p)).collect();
+        let targets: Vec<&String> = match &transform.transform_type {
+            TransformType::Split { target_paths, .. } if !target_paths.is_empty() => {
+                target_paths.iter().collect()
+            }
+            _ => vec![&transform.target_path],
+        };
+        let logic = if source_names.is_empty()
+            && !matches!(transform.transform_type, TransformType::Default { .. })
+        {
+            None
+        } else {
+            Some(transform_to_sql(&transform.transform_type, &source_names))
+        };
+
+        for target_path in targets {
+            if let Some(column) = target.columns.iter_mut().find(|c| &c.name == target_path) {
+                column.transform_source_objects = source_names
+                    .iter()
+                    .map(|name| format!("{}.{}", source_object, name))
+                    .collect();
+                column.transform_logic = logic.clone();
+                if column.transform_description.is_none() {
+                    column.transform_description = Some(transform.description.clone());
+                }
+                updated += 1;
+            }
+        }
+    }
+
+    updated
+}
+
+/// Collect the contract signals shared by two columns
+fn contract_signals(source: &Column, target: &Column) -> Vec<MatchSignal> {
+    let mut signals = Vec::new();
+
+    let target_urls: HashSet<String> = target
+        .authoritative_definitions
+        .iter()
+        .map(|d| normalize_url(&d.url))
+        .collect();
+    for definition in &source.authoritative_definitions {
+        let url = normalize_url(&definition.url);
+        if target_urls.contains(&url) {
+            signals.push(MatchSignal::AuthoritativeDefinition { url });
+        }
+    }
+
+    let target_terms = glossary_terms(target);
+    let mut source_terms: Vec<String> = glossary_terms(source)
+        .into_iter()
+        .filter(|t| target_terms.contains(t))
+        .collect();
+    source_terms.sort();
+    signals.extend(
+        source_terms
+            .into_iter()
+            .map(|term| MatchSignal::GlossaryTerm { term }),
+    );
+
+    if let (Some(a), Some(b)) = (&source.business_name, &target.business_name)
+        && !a.trim().is_empty()
+        && normalize_name(a) == normalize_name(b)
+    {
+        signals.push(MatchSignal::BusinessName { name: b.clone() });
+    }
+
+    // Physical names only count when at least one side declares one,
+    // otherwise this would duplicate plain name matching
+    if source.physical_name.is_some() || target.physical_name.is_some() {
+        let a = source.physical_name.as_deref().unwrap_or(&source.name);
+        let b = target.physical_name.as_deref().unwrap_or(&target.name);
+        if a.eq_ignore_ascii_case(b) {
+            signals.push(MatchSignal::PhysicalName {
+                name: b.to_string(),
+            });
+        }
+    }
+
+    signals
+}
+
+/// Glossary terms linked to a column through tags or authoritative definitions
+fn glossary_terms(column: &Column) -> HashSet<String> {
+    let mut terms = HashSet::new();
+
+    for tag in &column.tags {
+        if let Some((key, value)) = tag.split_once(':')
+            && GLOSSARY_TAG_KEYS.contains(&key.trim().to_lowercase().as_str())
+            && !value.trim().is_empty()
+        {
+            terms.insert(normalize_name(value));
+        }
+    }
+
+    for definition in &column.authoritative_definitions {
+        if GLOSSARY_DEFINITION_TYPES.contains(&definition.definition_type.to_lowercase().as_str())
+            && let Some(term) = definition
+                .url
+                .trim_end_matches('/')
+                .rsplit(['/', '#', '='])
+                .next()
+                .filter(|t| !t.is_empty())
+        {
+            terms.insert(normalize_name(term));
+        }
+    }
+
+    terms
+}
+
+/// Add a contract match as a direct mapping or, for coercible types, a cast
+fn push_match(
+    mapping: &mut SchemaMapping,
+    source: &Column,
+    target: &Column,
+    confidence: f64,
+    suggest_coercions: bool,
+) {
+    let source_type = json_type(&source.data_type);
+    let target_type = json_type(&target.data_type);
+    let compatible = types_compatible(&source_type, &target_type);
+
+    if !compatible && suggest_coercions && can_coerce(&source_type, &target_type) {
+        mapping.transformations.push(
+            TransformMapping::new(
+                vec![source.name.clone()],
+                target.name.clone(),
+                TransformType::TypeCast {
+                    from_type: source_type,
+                    to_type: target_type,
+                },
+            )
+            .with_confidence(confidence * 0.9),
+        );
+    } else {
+        mapping.direct_mappings.push(
+            FieldMapping::new(source.name.clone(), target.name.clone())
+                .with_confidence(confidence)
+                .with_type_compatible(compatible)
+                .with_match_method(MatchMethod::Semantic),
+        );
+    }
+}
+
+/// Report mapped columns whose classifications differ
+fn classification_warnings(mapping: &SchemaMapping, source: &Table, target: &Table) -> Vec<String> {
+    let classification = |table: &Table, name: &str| {
+        table
+            .columns
+            .iter()
+            .find(|c| c.name == name)
+            .and_then(|c| c.classification.clone())
+    };
+
+    let pairs = mapping
+        .direct_mappings
+        .iter()
+        .map(|m| (m.source_path.as_str(), m.target_path.as_str()))
+        .chain(mapping.transformations.iter().flat_map(|t| {
+            t.source_paths
+                .iter()
+                .map(move |s| (s.as_str(), t.target_path.as_str()))
+        }));
+
+    let mut warnings = Vec::new();
+    for (source_name, target_name) in pairs {
+        let from = classification(source, source_name);
+        let to = classification(target, target_name);
+        if let (Some(from), Some(to)) = (&from, &to)
+            && !from.eq_ignore_ascii_case(to)
+        {
+            warnings.push(format!(
+                "{} -> {}: classification changes from '{}' to '{}'",
+                source_name, target_name, from, to
+            ));
+        } else if let (Some(from), None) = (&from, &to) {
+            warnings.push(format!(
+                "{} -> {}: '{}' source mapped to an unclassified column",
+                source_name, target_name, from
+            ));
+        }
+    }
+    warnings
+}
+
+/// A copy of the table without the given columns
+fn subset(table: &Table, exclude: &HashSet<usize>) -> Table {
+    let mut copy = table.clone();
+    copy.columns = table
+        .columns
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !exclude.contains(i))
+        .map(|(_, c)| c.clone())
+        .collect();
+    copy
+}
+
+fn qualified_table_name(table: &Table) -> String {
+    [&table.catalog_name, &table.schema_name]
+        .into_iter()
+        .flatten()
+        .map(String::as_str)
+        .chain(std::iter::once(table.name.as_str()))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// JSON Schema type for a column data type, as used by the JSON Schema export
+fn json_type(data_type: &str) -> String {
+    JSONSchemaExporter::map_data_type_to_json_schema(data_type).0
+}
+
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn normalize_url(url: &str) -> String {
+    url.trim().trim_end_matches('/').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::column::AuthoritativeDefinition;
+
+    fn column(name: &str, data_type: &str) -> Column {
+        Column::new(name.to_string(), data_type.to_string())
+    }
+
+    fn source_table() -> Table {
+        let mut cust_no = column("cust_no", "string");
+        cust_no
+            .authoritative_definitions
+            .push(AuthoritativeDefinition {
+                definition_type: "businessDefinition".to_string(),
+                url: "https://glossary.example.com/terms/customer-id".to_string(),
+            });
+        let mut mail = column("mail", "string");
+        mail.business_name = Some("Email Address".to_string());
+        mail.classification = Some("confidential".to_string());
+        let mut amt = column("amt", "string");
+        amt.tags.push("glossary:Order Amount".to_string());
+        let mut created = column("created", "timestamp");
+        created.physical_name = Some("CREATED_TS".to_string());
+
+        let mut table = Table::new(
+            "orders_raw".to_string(),
+            vec![cust_no, mail, amt, created, column("status", "string")],
+        );
+        table.schema_name = Some("raw".to_string());
+        table
+    }
+
+    fn target_table() -> Table {
+        let mut customer_id = column("customer_id", "string");
+        customer_id
+            .authoritative_definitions
+            .push(AuthoritativeDefinition {
+                definition_type: "businessDefinition".to_string(),
+                url: "https://glossary.example.com/terms/customer-id/".to_string(),
+            });
+        let mut email = column("email", "string");
+        email.business_name = Some("email address".to_string());
+        email.classification = Some("public".to_string());
+        let mut total = column("order_total", "decimal");
+        total
+            .authoritative_definitions
+            .push(AuthoritativeDefinition {
+                definition_type: "glossaryTerm".to_string(),
+                url: "https://wiki.example.com/glossary#OrderAmount".to_string(),
+            });
+        let mut created_at = column("created_at", "timestamp");
+        created_at.physical_name = Some("created_ts".to_string());
+
+        Table::new(
+            "fact_orders".to_string(),
+            vec![
+                customer_id,
+                email,
+                total,
+                created_at,
+                column("status", "string"),
+                column("channel", "string"),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_contract_signals_drive_matching() {
+        let result = ContractMatcher::new()
+            .match_tables(&source_table(), &target_table())
+            .unwrap();
+        let mapping = &result.mapping;
+
+        let target_of = |source: &str| {
+            mapping
+                .direct_mappings
+                .iter()
+                .find(|m| m.source_path == source)
+                .map(|m| m.target_path.as_str())
+                .or_else(|| {
+                    mapping
+                        .transformations
+                        .iter()
+                        .find(|t| t.source_paths == [source])
+                        .map(|t| t.target_path.as_str())
+                })
+        };
+        assert_eq!(target_of("cust_no"), Some("customer_id"));
+        assert_eq!(target_of("mail"), Some("email"));
+        assert_eq!(target_of("amt"), Some("order_total"));
+        assert_eq!(target_of("created"), Some("created_at"));
+        assert_eq!(target_of("status"), Some("status"));
+
+        // string -> decimal needs a cast
+        assert!(
+            mapping
+                .transformations
+                .iter()
+                .any(|t| t.target_path == "order_total"
+                    && matches!(t.transform_type, TransformType::TypeCast { .. }))
+        );
+
+        assert_eq!(result.evidence.len(), 4);
+        assert!(result.evidence.iter().any(|e| e.target == "order_total"
+            && e.signals
+                == vec![MatchSignal::GlossaryTerm {
+                    term: "orderamount".to_string()
+                }]));
+
+        assert_eq!(mapping.gaps.len(), 1);
+        assert_eq!(mapping.gaps[0].target_path, "channel");
+        assert!(mapping.extras.is_empty());
+
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].contains("'confidential' to 'public'"));
+    }
+
+    #[test]
+    fn test_apply_lineage() {
+        let source = source_table();
+        let mut target = target_table();
+        let result = ContractMatcher::new()
+            .match_tables(&source, &target)
+            .unwrap();
+
+        let updated = apply_lineage(&result.mapping, &source, &mut target);
+        assert_eq!(updated, 5);
+
+        let created_at = target
+            .columns
+            .iter()
+            .find(|c| c.name == "created_at")
+            .unwrap();
+        assert_eq!(
+            created_at.transform_source_objects,
+            vec!["raw.orders_raw.CREATED_TS"]
+        );
+        assert_eq!(
+            created_at.transform_logic.as_deref(),
+            Some("\"CREATED_TS\"")
+        );
+
+        let total = target
+            .columns
+            .iter()
+            .find(|c| c.name == "order_total")
+            .unwrap();
+        assert_eq!(total.transform_source_objects, vec!["raw.orders_raw.amt"]);
+        assert_eq!(
+            total.transform_logic.as_deref(),
+            Some("CAST(\"amt\" AS DOUBLE)")
+        );
+        assert!(total.transform_description.is_some());
+
+        let channel = target.columns.iter().find(|c| c.name == "channel").unwrap();
+        assert!(channel.transform_source_objects.is_empty());
+        assert!(channel.transform_logic.is_none());
+    }
+}
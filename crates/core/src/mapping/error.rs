@@ -34,6 +34,14 @@ pub enum MappingError {
     #[error("Failed to generate transformation: {0}")]
     TransformGenerationError(String),
 
+    /// Invalid mapping specification
+    #[error("Invalid mapping specification: {0}")]
+    InvalidSpec(String),
+
+    /// Mapping execution failed
+    #[error("Mapping execution failed: {0}")]
+    ExecutionError(String),
+
     /// IO error
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
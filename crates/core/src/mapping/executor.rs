@@ -0,0 +1,1431 @@
+//! In-process execution of schema mappings
+//!
+//! Applies a [`SchemaMapping`] to JSON records so a mapping can be tested
+//! before a transformation script is generated. Output records are
+//! validated against the target schema and failures are counted per field.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::Write as _;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+#[cfg(feature = "staging")]
+use super::error::{MappingError, MappingResult};
+use super::path::{
+    ARRAY_MARKER, array_depth, array_prefix, array_segments, child_path, element_path,
+    is_array_path, is_elementwise,
+};
+use super::types::{SchemaMapping, TransformMapping, TransformType};
+
+/// Maximum number of individual errors kept in an execution report
+const MAX_REPORTED_ERRORS: usize = 100;
+
+/// Kind of failure recorded for a field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureKind {
+    /// The transformation could not be applied to the source value
+    Transform,
+    /// The output value does not satisfy the target schema
+    Validation,
+}
+
+impl std::fmt::Display for FailureKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FailureKind::Transform => write!(f, "transform"),
+            FailureKind::Validation => write!(f, "validation"),
+        }
+    }
+}
+
+/// A single failure for one record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordError {
+    /// Index of the record in the input
+    pub record: usize,
+    /// Target field path
+    pub field: String,
+    /// Kind of failure
+    pub kind: FailureKind,
+    /// Failure message
+    pub message: String,
+}
+
+/// Failure counts for a single target field
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldFailures {
+    /// Records where the transformation failed
+    pub transform: usize,
+    /// Records where the output failed validation
+    pub validation: usize,
+}
+
+impl FieldFailures {
+    /// Total failures for the field
+    pub fn total(&self) -> usize {
+        self.transform + self.validation
+    }
+}
+
+/// Summary of a mapping execution
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExecutionReport {
+    /// Records processed
+    pub records_processed: usize,
+    /// Records that mapped and validated without failures
+    pub records_valid: usize,
+    /// Records with at least one failure
+    pub records_failed: usize,
+    /// Output records produced (differs from `records_processed` when
+    /// arrays are exploded or rows are collected)
+    #[serde(default)]
+    pub records_emitted: usize,
+    /// Failure counts keyed by target field path
+    pub fields: BTreeMap<String, FieldFailures>,
+    /// First failures encountered (capped)
+    pub errors: Vec<RecordError>,
+    /// Transformations that could not be executed in-process
+    pub unsupported: Vec<String>,
+}
+
+impl ExecutionReport {
+    /// Check whether every record mapped and validated cleanly
+    pub fn is_success(&self) -> bool {
+        self.records_failed == 0
+    }
+
+    /// Total failures recorded for a target field
+    pub fn failure_count(&self, field: &str) -> usize {
+        self.fields
+            .get(field)
+            .map(FieldFailures::total)
+            .unwrap_or(0)
+    }
+
+    fn record_failure(&mut self, record: usize, field: &str, kind: FailureKind, message: String) {
+        let counts = self.fields.entry(field.to_string()).or_default();
+        match kind {
+            FailureKind::Transform => counts.transform += 1,
+            FailureKind::Validation => counts.validation += 1,
+        }
+        if self.errors.len() < MAX_REPORTED_ERRORS {
+            self.errors.push(RecordError {
+                record,
+                field: field.to_string(),
+                kind,
+                message,
+            });
+        }
+    }
+}
+
+/// Mapped records together with their execution report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionResult {
+    /// Records in target shape
+    pub records: Vec<Value>,
+    /// Execution summary
+    pub report: ExecutionReport,
+}
+
+/// Executes a [`SchemaMapping`] against JSON records
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use data_modelling_core::mapping::{MappingExecutor, map_schemas};
+///
+/// let mapping = map_schemas(&source_schema, &target_schema)?;
+/// let result = MappingExecutor::new(mapping)
+///     .with_target_schema(target_schema)
+///     .execute(&records);
+/// println!("{} of {} records valid", result.report.records_valid, result.report.records_processed);
+/// ```
+pub struct MappingExecutor {
+    mapping: SchemaMapping,
+    target_schema: Option<Value>,
+    patterns: HashMap<String, Regex>,
+    apply_gap_defaults: bool,
+}
+
+impl MappingExecutor {
+    /// Create an executor for a mapping
+    pub fn new(mapping: SchemaMapping) -> Self {
+        Self {
+            mapping,
+            target_schema: None,
+            patterns: HashMap::new(),
+            apply_gap_defaults: true,
+        }
+    }
+
+    /// Validate output records against a target JSON Schema
+    pub fn with_target_schema(mut self, schema: Value) -> Self {
+        self.patterns.clear();
+        collect_patterns(&schema, &mut self.patterns);
+        self.target_schema = Some(schema);
+        self
+    }
+
+    /// Fill unmapped target fields with the gap's suggested default
+    pub fn with_gap_defaults(mut self, enabled: bool) -> Self {
+        self.apply_gap_defaults = enabled;
+        self
+    }
+
+    /// Get the mapping being executed
+    pub fn mapping(&self) -> &SchemaMapping {
+        &self.mapping
+    }
+
+    /// Map a single record into output rows, returning any failures
+    ///
+    /// A record yields one row, or one row per array element when the mapping
+    /// explodes an array. Collect transforms aggregate across records and are
+    /// only applied by [`MappingExecutor::execute`]; here each row carries a
+    /// single collected element. Failures are `(target_path, kind, message)`
+    /// tuples.
+    pub fn execute_record(
+        &self,
+        record: &Value,
+    ) -> (Vec<Value>, Vec<(String, FailureKind, String)>) {
+        let mut rows = Vec::new();
+        let mut failures = Vec::new();
+        for elements in explode_contexts(record, self.explode_driver().as_deref()) {
+            let (mut row, mut row_failures) = self.map_row(record, &elements);
+            self.validate_row(&mut row, &mut row_failures);
+            rows.push(row);
+            failures.extend(row_failures);
+        }
+        (rows, failures)
+    }
+
+    /// Map a batch of records and build an execution report
+    pub fn execute(&self, records: &[Value]) -> ExecutionResult {
+        let mut report = ExecutionReport {
+            unsupported: self.unsupported(),
+            ..Default::default()
+        };
+
+        let driver = self.explode_driver();
+        let mut rows = Vec::new();
+        for (index, record) in records.iter().enumerate() {
+            for elements in explode_contexts(record, driver.as_deref()) {
+                let (value, failures) = self.map_row(record, &elements);
+                rows.push(MappedRow {
+                    value,
+                    records: vec![index],
+                    failures,
+                });
+            }
+        }
+
+        let collected = self.collected_arrays();
+        if !collected.is_empty() {
+            rows = collect_rows(rows, &collected);
+        }
+
+        let mut failed_records = BTreeSet::new();
+        for row in &mut rows {
+            self.validate_row(&mut row.value, &mut row.failures);
+            if row.failures.is_empty() {
+                continue;
+            }
+            failed_records.extend(row.records.iter().copied());
+            for (field, kind, message) in row.failures.drain(..) {
+                report.record_failure(row.records[0], &field, kind, message);
+            }
+        }
+
+        report.records_processed = records.len();
+        report.records_failed = failed_records.len();
+        report.records_valid = records.len() - failed_records.len();
+        report.records_emitted = rows.len();
+
+        ExecutionResult {
+            records: rows.into_iter().map(|r| r.value).collect(),
+            report,
+        }
+    }
+
+    /// Map one record (and exploded array elements) into a target row
+    fn map_row(&self, record: &Value, elements: &Elements) -> (Value, Vec<Failure>) {
+        let mut output = Value::Object(Map::new());
+        let mut failures = Vec::new();
+
+        for mapping in &self.mapping.direct_mappings {
+            let (source, target) = (&mapping.source_path, &mapping.target_path);
+            if is_array_path(source) || is_array_path(target) {
+                if array_depth(source) == array_depth(target) {
+                    copy_elementwise(
+                        record,
+                        &array_segments(source),
+                        &mut output,
+                        &array_segments(target),
+                    );
+                }
+            } else if let Some(value) = get_path(record, source) {
+                set_path(&mut output, target, value.clone());
+            }
+        }
+
+        for transform in &self.mapping.transformations {
+            let result = if is_elementwise(transform) {
+                apply_elementwise(transform, record, &mut output)
+            } else {
+                apply_transform(transform, record, elements).map(|values| {
+                    for (path, value) in values {
+                        if let Some(value) = value {
+                            set_path(&mut output, &path, value);
+                        }
+                    }
+                })
+            };
+            if let Err(TransformFailure::Failed(message)) = result {
+                failures.push((
+                    transform.target_path.clone(),
+                    FailureKind::Transform,
+                    message,
+                ));
+            }
+        }
+
+        if self.apply_gap_defaults {
+            for gap in &self.mapping.gaps {
+                if let Some(default) = &gap.suggested_default
+                    && !is_array_path(&gap.target_path)
+                    && get_path(&output, &gap.target_path).is_none()
+                {
+                    set_path(&mut output, &gap.target_path, default.clone());
+                }
+            }
+        }
+
+        (output, failures)
+    }
+
+    /// Validate a row against the target schema, adding validation failures
+    fn validate_row(&self, row: &mut Value, failures: &mut Vec<Failure>) {
+        let Some(schema) = &self.target_schema else {
+            return;
+        };
+        let failed: HashSet<String> = failures.iter().map(|(f, _, _)| f.clone()).collect();
+        let mut violations = Vec::new();
+        self.validate(schema, row, "", &mut violations);
+        for (path, message) in violations {
+            // A field whose transform failed is already counted
+            if !failed.contains(&path) {
+                failures.push((path, FailureKind::Validation, message));
+            }
+        }
+    }
+
+    /// Innermost exploded array; every exploded array must enclose it
+    fn explode_driver(&self) -> Option<String> {
+        self.explode_paths()
+            .max_by_key(|p| array_depth(p))
+            .map(str::to_string)
+    }
+
+    fn explode_paths(&self) -> impl Iterator<Item = &str> {
+        self.mapping
+            .transformations
+            .iter()
+            .filter_map(|t| match &t.transform_type {
+                TransformType::Explode { array_path } => Some(array_path.as_str()),
+                _ => None,
+            })
+    }
+
+    /// Top-level target arrays filled by collect transforms
+    fn collected_arrays(&self) -> Vec<String> {
+        let mut arrays: Vec<String> = self
+            .mapping
+            .transformations
+            .iter()
+            .filter_map(|t| match &t.transform_type {
+                TransformType::Collect { array_path } if array_depth(array_path) == 1 => {
+                    Some(array_path.trim_end_matches(ARRAY_MARKER).to_string())
+                }
+                _ => None,
+            })
+            .collect();
+        arrays.sort();
+        arrays.dedup();
+        arrays
+    }
+
+    /// Mapping entries that cannot run in-process
+    fn unsupported(&self) -> Vec<String> {
+        let mut unsupported = Vec::new();
+        let driver = self.explode_driver().unwrap_or_default();
+
+        for mapping in &self.mapping.direct_mappings {
+            if array_depth(&mapping.source_path) != array_depth(&mapping.target_path) {
+                unsupported.push(format!(
+                    "{}: array nesting differs from {} (use explode or collect)",
+                    mapping.target_path, mapping.source_path
+                ));
+            }
+        }
+        for transform in &self.mapping.transformations {
+            let reason = match &transform.transform_type {
+                TransformType::Explode { array_path } if !driver.starts_with(array_path) => {
+                    Some(format!("explodes {} as well as {}", array_path, driver))
+                }
+                TransformType::Collect { array_path } if array_depth(array_path) != 1 => {
+                    Some(format!("collect into nested array {}", array_path))
+                }
+                _ if !is_elementwise(transform)
+                    && !matches!(
+                        transform.transform_type,
+                        TransformType::Explode { .. } | TransformType::Collect { .. }
+                    )
+                    && (transform.source_paths.iter().any(|p| is_array_path(p))
+                        || is_array_path(&transform.target_path)) =>
+                {
+                    Some("array paths are only supported one level deep".to_string())
+                }
+                _ => check_supported(transform).err().map(|f| match f {
+                    TransformFailure::Unsupported(reason) | TransformFailure::Failed(reason) => {
+                        reason
+                    }
+                }),
+            };
+            if let Some(reason) = reason {
+                unsupported.push(format!("{}: {}", transform.target_path, reason));
+            }
+        }
+        unsupported
+    }
+
+    /// Map records from a staging database partition
+    ///
+    /// A `limit` of 0 maps every record in the partition.
+    #[cfg(feature = "staging")]
+    pub fn execute_partition(
+        &self,
+        db: &crate::staging::StagingDb,
+        partition: Option<&str>,
+        limit: usize,
+    ) -> MappingResult<ExecutionResult> {
+        let rows = db
+            .get_records_after(0, limit, partition)
+            .map_err(|e| MappingError::ExecutionError(e.to_string()))?;
+        let records = rows
+            .into_iter()
+            .map(|(id, raw)| {
+                serde_json::from_str(&raw).map_err(|e| {
+                    MappingError::ExecutionError(format!(
+                        "Invalid JSON in staged record {}: {}",
+                        id, e
+                    ))
+                })
+            })
+            .collect::<MappingResult<Vec<Value>>>()?;
+        Ok(self.execute(&records))
+    }
+
+    /// Validate a value against a schema, collecting `(path, message)` violations
+    fn validate(&self, schema: &Value, value: &Value, path: &str, out: &mut Vec<(String, String)>) {
+        let field = if path.is_empty() { "$" } else { path };
+
+        if let Some(expected) = schema.get("type")
+            && !matches_type(expected, value)
+        {
+            out.push((
+                field.to_string(),
+                format!("expected type {}, got {}", expected, json_type(value)),
+            ));
+            return;
+        }
+
+        if let Some(options) = schema.get("enum").and_then(|e| e.as_array())
+            && !options.contains(value)
+        {
+            out.push((field.to_string(), format!("value {} not in enum", value)));
+        }
+
+        match value {
+            Value::String(s) => {
+                let len = s.chars().count() as u64;
+                if let Some(min) = schema.get("minLength").and_then(|v| v.as_u64())
+                    && len < min
+                {
+                    out.push((field.to_string(), format!("shorter than minLength {}", min)));
+                }
+                if let Some(max) = schema.get("maxLength").and_then(|v| v.as_u64())
+                    && len > max
+                {
+                    out.push((field.to_string(), format!("longer than maxLength {}", max)));
+                }
+                if let Some(pattern) = schema.get("pattern").and_then(|p| p.as_str())
+                    && let Some(re) = self.patterns.get(pattern)
+                    && !re.is_match(s)
+                {
+                    out.push((
+                        field.to_string(),
+                        format!("does not match pattern {}", pattern),
+                    ));
+                }
+                if let Some(format) = schema.get("format").and_then(|f| f.as_str())
+                    && !matches_format(format, s)
+                {
+                    out.push((field.to_string(), format!("not a valid {}", format)));
+                }
+            }
+            Value::Number(n) => {
+                let n = n.as_f64().unwrap_or(0.0);
+                if let Some(min) = schema.get("minimum").and_then(|v| v.as_f64())
+                    && n < min
+                {
+                    out.push((field.to_string(), format!("less than minimum {}", min)));
+                }
+                if let Some(max) = schema.get("maximum").and_then(|v| v.as_f64())
+                    && n > max
+                {
+                    out.push((field.to_string(), format!("greater than maximum {}", max)));
+                }
+            }
+            Value::Object(obj) => {
+                if let Some(required) = schema.get("required").and_then(|r| r.as_array()) {
+                    for name in required.iter().filter_map(|r| r.as_str()) {
+                        if !obj.contains_key(name) {
+                            out.push((
+                                child_path(path, name),
+                                "required field missing".to_string(),
+                            ));
+                        }
+                    }
+                }
+                if let Some(props) = schema.get("properties").and_then(|p| p.as_object()) {
+                    for (name, prop_schema) in props {
+                        if let Some(child) = obj.get(name) {
+                            self.validate(prop_schema, child, &child_path(path, name), out);
+                        }
+                    }
+                }
+            }
+            Value::Array(items) => {
+                if let Some(item_schema) = schema.get("items") {
+                    let item_path = format!("{}[]", path);
+                    for item in items {
+                        self.validate(item_schema, item, &item_path, out);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Why a transformation produced no value
+enum TransformFailure {
+    /// The transformation cannot be evaluated in-process
+    Unsupported(String),
+    /// The transformation failed for this record
+    Failed(String),
+}
+
+/// Output of a transformation: target paths and their values (None = no value)
+type TransformOutput = Vec<(String, Option<Value>)>;
+
+/// A failure for one target field: `(target_path, kind, message)`
+type Failure = (String, FailureKind, String);
+
+/// Current element of each exploded array, keyed by array path (e.g. `items[]`)
+type Elements<'a> = HashMap<String, &'a Value>;
+
+/// An output row with the input records it was built from
+struct MappedRow {
+    value: Value,
+    records: Vec<usize>,
+    failures: Vec<Failure>,
+}
+
+/// Enumerate the element combinations of an exploded array path
+///
+/// Without a driver the record maps to a single row. A record whose array is
+/// missing or empty yields no rows.
+fn explode_contexts<'a>(record: &'a Value, driver: Option<&str>) -> Vec<Elements<'a>> {
+    let Some(driver) = driver else {
+        return vec![Elements::new()];
+    };
+    let segments = array_segments(driver);
+    let mut contexts: Vec<(&Value, Elements)> = vec![(record, Elements::new())];
+    let mut prefix = String::new();
+    for segment in &segments[..segments.len() - 1] {
+        prefix = format!("{}{}", child_path(&prefix, segment), ARRAY_MARKER);
+        let mut next = Vec::new();
+        for (current, elements) in contexts {
+            if let Some(Value::Array(items)) = get_path(current, segment) {
+                for item in items {
+                    let mut elements = elements.clone();
+                    elements.insert(prefix.clone(), item);
+                    next.push((item, elements));
+                }
+            }
+        }
+        contexts = next;
+    }
+    contexts.into_iter().map(|(_, elements)| elements).collect()
+}
+
+/// Merge rows that differ only in their collected arrays
+///
+/// Rows are grouped by every other target field, like SQL `GROUP BY ALL`.
+fn collect_rows(rows: Vec<MappedRow>, collected: &[String]) -> Vec<MappedRow> {
+    let mut groups: Vec<MappedRow> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    for mut row in rows {
+        let mut arrays = Vec::new();
+        for path in collected {
+            arrays.push(take_path(&mut row.value, path));
+        }
+        let key = row.value.to_string();
+        let group = match index.get(&key) {
+            Some(&i) => {
+                let group = &mut groups[i];
+                group.records.extend(row.records);
+                group.failures.extend(row.failures);
+                group
+            }
+            None => {
+                index.insert(key, groups.len());
+                groups.push(row);
+                groups.last_mut().expect("just pushed")
+            }
+        };
+        for (path, array) in collected.iter().zip(arrays) {
+            let slot = path_mut(&mut group.value, path);
+            if !slot.is_array() {
+                *slot = Value::Array(Vec::new());
+            }
+            if let (Value::Array(items), Some(Value::Array(new))) = (slot, array) {
+                items.extend(new);
+            }
+        }
+    }
+    groups
+}
+
+/// Apply a transform to each element of a source array, writing the results
+/// into the same positions of the target array
+fn apply_elementwise(
+    transform: &TransformMapping,
+    record: &Value,
+    output: &mut Value,
+) -> Result<(), TransformFailure> {
+    let source_array = array_prefix(&transform.source_paths[0])
+        .unwrap_or_default()
+        .trim_end_matches(ARRAY_MARKER);
+    let target_array = array_prefix(&transform.target_path)
+        .unwrap_or_default()
+        .trim_end_matches(ARRAY_MARKER);
+    let Some(Value::Array(items)) = get_path(record, source_array) else {
+        return Ok(());
+    };
+
+    let mut element_transform = transform.clone();
+    element_transform.source_paths = transform
+        .source_paths
+        .iter()
+        .map(|p| element_path(p).to_string())
+        .collect();
+    element_transform.target_path = element_path(&transform.target_path).to_string();
+    if let TransformType::Split { target_paths, .. } = &mut element_transform.transform_type {
+        for path in target_paths.iter_mut() {
+            *path = element_path(path).to_string();
+        }
+    }
+
+    let mut failure = None;
+    for (i, item) in items.iter().enumerate() {
+        match apply_transform(&element_transform, item, &Elements::new()) {
+            Ok(values) => {
+                for (path, value) in values {
+                    if let Some(value) = value {
+                        set_path(array_slot(output, target_array, i), &path, value);
+                    }
+                }
+            }
+            Err(e) => {
+                failure.get_or_insert(e);
+            }
+        }
+    }
+    failure.map_or(Ok(()), Err)
+}
+
+/// Copy values between arrays of equal nesting, element by element
+fn copy_elementwise(
+    source: &Value,
+    source_segments: &[&str],
+    target: &mut Value,
+    target_segments: &[&str],
+) {
+    let (Some((source_head, source_rest)), Some((target_head, target_rest))) =
+        (source_segments.split_first(), target_segments.split_first())
+    else {
+        return;
+    };
+    if source_rest.is_empty() {
+        if let Some(value) = get_path(source, source_head) {
+            set_path(target, target_head, value.clone());
+        }
+        return;
+    }
+    if let Some(Value::Array(items)) = get_path(source, source_head) {
+        for (i, item) in items.iter().enumerate() {
+            copy_elementwise(
+                item,
+                source_rest,
+                array_slot(target, target_head, i),
+                target_rest,
+            );
+        }
+    }
+}
+
+fn check_supported(transform: &TransformMapping) -> Result<(), TransformFailure> {
+    match &transform.transform_type {
+        TransformType::Custom { expression } => {
+            custom_function(expression).map(|_| ()).ok_or_else(|| {
+                TransformFailure::Unsupported(format!("custom expression '{}'", expression))
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+fn apply_transform(
+    transform: &TransformMapping,
+    record: &Value,
+    elements: &Elements,
+) -> Result<TransformOutput, TransformFailure> {
+    check_supported(transform)?;
+    let target = transform.target_path.clone();
+    let first = transform
+        .source_paths
+        .first()
+        .and_then(|p| get_path(record, p))
+        .filter(|v| !v.is_null());
+
+    match &transform.transform_type {
+        TransformType::Rename => Ok(vec![(target, first.cloned())]),
+        TransformType::TypeCast { to_type, .. } => {
+            let value = first.map(|v| cast_value(v, to_type)).transpose()?;
+            Ok(vec![(target, value)])
+        }
+        TransformType::Merge { separator } => {
+            let separator = separator.as_deref().unwrap_or(" ");
+            let parts: Vec<String> = transform
+                .source_paths
+                .iter()
+                .filter_map(|p| get_path(record, p))
+                .filter(|v| !v.is_null())
+                .map(value_to_string)
+                .collect();
+            let value = (!parts.is_empty()).then(|| Value::String(parts.join(separator)));
+            Ok(vec![(target, value)])
+        }
+        TransformType::Split {
+            delimiter,
+            target_paths,
+        } => {
+            let Some(source) = first else {
+                return Ok(vec![(target, None)]);
+            };
+            let text = value_to_string(source);
+            let parts: Vec<&str> = if delimiter.is_empty() {
+                vec![text.as_str()]
+            } else {
+                text.split(delimiter.as_str()).collect()
+            };
+            if target_paths.is_empty() {
+                let array = parts.iter().map(|p| Value::String(p.to_string())).collect();
+                return Ok(vec![(target, Some(Value::Array(array)))]);
+            }
+            if parts.len() > target_paths.len() {
+                return Err(TransformFailure::Failed(format!(
+                    "split produced {} parts for {} target fields",
+                    parts.len(),
+                    target_paths.len()
+                )));
+            }
+            Ok(target_paths
+                .iter()
+                .enumerate()
+                .map(|(i, path)| {
+                    (
+                        path.clone(),
+                        parts.get(i).map(|p| Value::String(p.to_string())),
+                    )
+                })
+                .collect())
+        }
+        TransformType::FormatChange {
+            from_format,
+            to_format,
+        } => {
+            let value = first
+                .map(|v| change_format(v, from_format, to_format))
+                .transpose()
+                .map_err(TransformFailure::Failed)?;
+            Ok(vec![(target, value)])
+        }
+        TransformType::Extract { json_path } => {
+            let value = first
+                .and_then(|v| {
+                    let parsed = match v {
+                        Value::String(s) => serde_json::from_str(s).ok(),
+                        other => Some(other.clone()),
+                    };
+                    parsed.and_then(|p| eval_json_path(&p, json_path))
+                })
+                .or_else(|| eval_json_path(record, json_path))
+                .filter(|v| !v.is_null());
+            Ok(vec![(target, value)])
+        }
+        TransformType::Default { value } => Ok(vec![(
+            target,
+            Some(first.cloned().unwrap_or_else(|| value.clone())),
+        )]),
+        TransformType::Explode { array_path } => {
+            let value = transform
+                .source_paths
+                .first()
+                .zip(elements.get(array_path))
+                .and_then(|(path, element)| get_path(element, element_path(path)))
+                .filter(|v| !v.is_null())
+                .cloned();
+            Ok(vec![(target, value)])
+        }
+        TransformType::Collect { .. } => Ok(vec![(target, first.cloned())]),
+        TransformType::Custom { expression } => {
+            let func = custom_function(expression).expect("checked by check_supported");
+            let value = first.map(|v| match v {
+                Value::String(s) => Value::String(func(s)),
+                other => other.clone(),
+            });
+            Ok(vec![(target, value)])
+        }
+    }
+}
+
+/// Resolve the small set of custom expressions that can run in-process
+fn custom_function(expression: &str) -> Option<fn(&str) -> String> {
+    let name = expression
+        .split('(')
+        .next()
+        .unwrap_or(expression)
+        .trim()
+        .to_lowercase();
+    match name.as_str() {
+        "upper" | "uppercase" => Some(|s| s.to_uppercase()),
+        "lower" | "lowercase" => Some(|s| s.to_lowercase()),
+        "trim" => Some(|s| s.trim().to_string()),
+        _ => None,
+    }
+}
+
+fn cast_value(value: &Value, to_type: &str) -> Result<Value, TransformFailure> {
+    let fail = || {
+        TransformFailure::Failed(format!(
+            "cannot cast {} to {}",
+            value,
+            to_type.to_lowercase()
+        ))
+    };
+    match to_type.to_lowercase().as_str() {
+        "string" | "varchar" | "text" | "str" => Ok(Value::String(value_to_string(value))),
+        "integer" | "int" | "bigint" | "long" | "smallint" | "int32" | "int64" => match value {
+            Value::Number(n) if n.is_i64() || n.is_u64() => Ok(value.clone()),
+            Value::Number(n) => n
+                .as_f64()
+                .filter(|f| f.fract() == 0.0)
+                .map(|f| Value::from(f as i64))
+                .ok_or_else(fail),
+            Value::String(s) => s.trim().parse::<i64>().map(Value::from).map_err(|_| fail()),
+            Value::Bool(b) => Ok(Value::from(*b as i64)),
+            _ => Err(fail()),
+        },
+        "number" | "float" | "double" | "decimal" | "numeric" | "real" => match value {
+            Value::Number(_) => Ok(value.clone()),
+            Value::String(s) => s
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .and_then(serde_json::Number::from_f64)
+                .map(Value::Number)
+                .ok_or_else(fail),
+            _ => Err(fail()),
+        },
+        "boolean" | "bool" => match value {
+            Value::Bool(_) => Ok(value.clone()),
+            Value::Number(n) => match n.as_i64() {
+                Some(0) => Ok(Value::Bool(false)),
+                Some(1) => Ok(Value::Bool(true)),
+                _ => Err(fail()),
+            },
+            Value::String(s) => match s.trim().to_lowercase().as_str() {
+                "true" | "t" | "yes" | "y" | "1" => Ok(Value::Bool(true)),
+                "false" | "f" | "no" | "n" | "0" => Ok(Value::Bool(false)),
+                _ => Err(fail()),
+            },
+            _ => Err(fail()),
+        },
+        "array" | "object" => {
+            let expected_array = to_type.eq_ignore_ascii_case("array");
+            let parsed = match value {
+                Value::String(s) => serde_json::from_str(s).map_err(|_| fail())?,
+                other => other.clone(),
+            };
+            if parsed.is_array() == expected_array && (expected_array || parsed.is_object()) {
+                Ok(parsed)
+            } else {
+                Err(fail())
+            }
+        }
+        other => Err(TransformFailure::Unsupported(format!("cast to {}", other))),
+    }
+}
+
+/// A parsed point in time, keeping track of how much information it carries
+enum Moment {
+    Zoned(DateTime<Utc>),
+    Local(NaiveDateTime),
+    Day(NaiveDate),
+}
+
+fn change_format(value: &Value, from: &str, to: &str) -> Result<Value, String> {
+    let moment = parse_moment(value, from)?;
+    let naive = match &moment {
+        Moment::Zoned(dt) => dt.naive_utc(),
+        Moment::Local(dt) => *dt,
+        Moment::Day(d) => d.and_hms_opt(0, 0, 0).unwrap_or_default(),
+    };
+
+    match to.to_lowercase().as_str() {
+        "iso8601" | "iso" | "rfc3339" | "date-time" => Ok(Value::String(match moment {
+            Moment::Zoned(dt) => dt.to_rfc3339(),
+            Moment::Local(dt) => dt.format("%Y-%m-%dT%H:%M:%S").to_string(),
+            Moment::Day(d) => d.format("%Y-%m-%d").to_string(),
+        })),
+        "date" => Ok(Value::String(naive.format("%Y-%m-%d").to_string())),
+        "epoch" | "unix" => Ok(Value::from(naive.and_utc().timestamp())),
+        "epoch_ms" | "unix_ms" => Ok(Value::from(naive.and_utc().timestamp_millis())),
+        _ => {
+            let mut out = String::new();
+            write!(out, "{}", naive.format(to))
+                .map_err(|_| format!("invalid target format '{}'", to))?;
+            Ok(Value::String(out))
+        }
+    }
+}
+
+fn parse_moment(value: &Value, from: &str) -> Result<Moment, String> {
+    let from_lower = from.to_lowercase();
+    if let Some(n) = value.as_i64() {
+        let dt = if from_lower == "epoch_ms" || from_lower == "unix_ms" {
+            DateTime::from_timestamp_millis(n)
+        } else {
+            DateTime::from_timestamp(n, 0)
+        };
+        return dt
+            .map(Moment::Zoned)
+            .ok_or_else(|| format!("timestamp {} out of range", n));
+    }
+
+    let text = value
+        .as_str()
+        .ok_or_else(|| format!("cannot parse {} as a date", value))?
+        .trim();
+
+    match from_lower.as_str() {
+        "auto" | "iso8601" | "iso" | "rfc3339" | "date-time" | "date" | "" => {
+            if let Ok(dt) = DateTime::parse_from_rfc3339(text) {
+                return Ok(Moment::Zoned(dt.with_timezone(&Utc)));
+            }
+            for format in ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f"] {
+                if let Ok(dt) = NaiveDateTime::parse_from_str(text, format) {
+                    return Ok(Moment::Local(dt));
+                }
+            }
+            NaiveDate::parse_from_str(text, "%Y-%m-%d")
+                .map(Moment::Day)
+                .map_err(|_| format!("'{}' is not an ISO 8601 date", text))
+        }
+        "epoch" | "unix" | "epoch_ms" | "unix_ms" => {
+            let n: i64 = text
+                .parse()
+                .map_err(|_| format!("'{}' is not a timestamp", text))?;
+            parse_moment(&Value::from(n), from)
+        }
+        _ => {
+            if let Ok(dt) = DateTime::parse_from_str(text, from) {
+                return Ok(Moment::Zoned(dt.with_timezone(&Utc)));
+            }
+            if let Ok(dt) = NaiveDateTime::parse_from_str(text, from) {
+                return Ok(Moment::Local(dt));
+            }
+            NaiveDate::parse_from_str(text, from)
+                .map(Moment::Day)
+                .map_err(|_| format!("'{}' does not match format '{}'", text, from))
+        }
+    }
+}
+
+/// Evaluate a simple JSON path such as `$.address.lines[0]`
+fn eval_json_path(value: &Value, path: &str) -> Option<Value> {
+    let path = path.trim();
+    let path = path.strip_prefix('$').unwrap_or(path);
+    let mut current = value;
+
+    for segment in path.split('.').filter(|s| !s.is_empty()) {
+        let (name, indexes) = match segment.find('[') {
+            Some(pos) => (&segment[..pos], &segment[pos..]),
+            None => (segment, ""),
+        };
+        if !name.is_empty() {
+            current = current.get(name)?;
+        }
+        for index in indexes.split('[').filter(|s| !s.is_empty()) {
+            let index: usize = index.trim_end_matches(']').parse().ok()?;
+            current = current.get(index)?;
+        }
+    }
+
+    Some(current.clone())
+}
+
+/// Get a value at a dotted path (an empty path is the value itself)
+fn get_path<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    if path.is_empty() {
+        return Some(value);
+    }
+    if let Some(v) = value.get(path) {
+        return Some(v);
+    }
+    path.split('.')
+        .try_fold(value, |current, key| current.get(key))
+}
+
+/// Set a value at a dotted path, creating intermediate objects
+fn set_path(target: &mut Value, path: &str, value: Value) {
+    *path_mut(target, path) = value;
+}
+
+/// Get a mutable slot at a dotted path, creating intermediate objects
+///
+/// A `name[]` segment descends into the first element of the array,
+/// creating it if needed.
+fn path_mut<'a>(target: &'a mut Value, path: &str) -> &'a mut Value {
+    if path.is_empty() {
+        return target;
+    }
+    let mut current = target;
+    for key in path.split('.') {
+        let (name, array) = match key.strip_suffix(ARRAY_MARKER) {
+            Some(name) => (name, true),
+            None => (key, false),
+        };
+        if !current.is_object() {
+            *current = Value::Object(Map::new());
+        }
+        let Value::Object(obj) = current else {
+            unreachable!()
+        };
+        current = obj.entry(name.to_string()).or_insert(Value::Null);
+        if array {
+            if !current.is_array() {
+                *current = Value::Array(Vec::new());
+            }
+            let Value::Array(items) = current else {
+                unreachable!()
+            };
+            if items.is_empty() {
+                items.push(Value::Null);
+            }
+            current = &mut items[0];
+        }
+    }
+    current
+}
+
+/// Get the element slot at `index` of the array at `path`, growing it as needed
+fn array_slot<'a>(target: &'a mut Value, path: &str, index: usize) -> &'a mut Value {
+    let slot = path_mut(target, path);
+    if !slot.is_array() {
+        *slot = Value::Array(Vec::new());
+    }
+    let Value::Array(items) = slot else {
+        unreachable!()
+    };
+    if items.len() <= index {
+        items.resize(index + 1, Value::Null);
+    }
+    &mut items[index]
+}
+
+/// Remove and return the value at a dotted path
+fn take_path(target: &mut Value, path: &str) -> Option<Value> {
+    let (parent, key) = match path.rsplit_once('.') {
+        Some((parent, key)) => (get_path_mut(target, parent)?, key),
+        None => (target, path),
+    };
+    parent.as_object_mut()?.remove(key)
+}
+
+fn get_path_mut<'a>(value: &'a mut Value, path: &str) -> Option<&'a mut Value> {
+    path.split('.')
+        .try_fold(value, |current, key| current.get_mut(key))
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn matches_type(expected: &Value, value: &Value) -> bool {
+    let matches_one = |t: &str| match t {
+        "number" => value.is_number(),
+        "integer" => match value {
+            Value::Number(n) => {
+                n.is_i64() || n.is_u64() || n.as_f64().is_some_and(|f| f.fract() == 0.0)
+            }
+            _ => false,
+        },
+        other => json_type(value) == other,
+    };
+    match expected {
+        Value::String(t) => matches_one(t),
+        Value::Array(types) => types.iter().filter_map(|t| t.as_str()).any(matches_one),
+        _ => true,
+    }
+}
+
+fn matches_format(format: &str, value: &str) -> bool {
+    match format {
+        "date" => NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok(),
+        "date-time" => DateTime::parse_from_rfc3339(value).is_ok(),
+        "email" => value
+            .split_once('@')
+            .is_some_and(|(local, domain)| !local.is_empty() && domain.contains('.')),
+        "uuid" => uuid::Uuid::parse_str(value).is_ok(),
+        "uri" => value
+            .split_once("://")
+            .is_some_and(|(scheme, rest)| !scheme.is_empty() && !rest.is_empty()),
+        _ => true,
+    }
+}
+
+/// Pre-compile every `pattern` keyword in a schema
+fn collect_patterns(schema: &Value, patterns: &mut HashMap<String, Regex>) {
+    match schema {
+        Value::Object(obj) => {
+            if let Some(pattern) = obj.get("pattern").and_then(|p| p.as_str())
+                && !patterns.contains_key(pattern)
+                && let Ok(re) = Regex::new(pattern)
+            {
+                patterns.insert(pattern.to_string(), re);
+            }
+            for child in obj.values() {
+                collect_patterns(child, patterns);
+            }
+        }
+        Value::Array(items) => {
+            for child in items {
+                collect_patterns(child, patterns);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mapping::{FieldGap, FieldMapping};
+    use serde_json::json;
+
+    fn transform(sources: &[&str], target: &str, t: TransformType) -> TransformMapping {
+        TransformMapping::new(sources.iter().map(|s| s.to_string()).collect(), target, t)
+    }
+
+    #[test]
+    fn test_direct_and_transform_mappings() {
+        let mut mapping = SchemaMapping::empty();
+        mapping
+            .direct_mappings
+            .push(FieldMapping::new("customer.id", "id"));
+        mapping.transformations.push(transform(
+            &["age"],
+            "age",
+            TransformType::TypeCast {
+                from_type: "string".to_string(),
+                to_type: "integer".to_string(),
+            },
+        ));
+        mapping.transformations.push(transform(
+            &["first", "last"],
+            "name",
+            TransformType::Merge { separator: None },
+        ));
+        mapping.transformations.push(transform(
+            &["created"],
+            "created_date",
+            TransformType::FormatChange {
+                from_format: "%d/%m/%Y".to_string(),
+                to_format: "date".to_string(),
+            },
+        ));
+        mapping.transformations.push(transform(
+            &["payload"],
+            "city",
+            TransformType::Extract {
+                json_path: "$.address.city".to_string(),
+            },
+        ));
+        mapping.transformations.push(transform(
+            &["status"],
+            "status",
+            TransformType::Default {
+                value: json!("active"),
+            },
+        ));
+        mapping.transformations.push(transform(
+            &["full_code"],
+            "code",
+            TransformType::Split {
+                delimiter: "-".to_string(),
+                target_paths: vec!["code.prefix".to_string(), "code.number".to_string()],
+            },
+        ));
+        mapping
+            .gaps
+            .push(FieldGap::new("source_system", "string", true).with_default(json!("crm")));
+
+        let record = json!({
+            "customer": {"id": 7},
+            "age": "42",
+            "first": "Ada",
+            "last": "Lovelace",
+            "created": "10/12/1815",
+            "payload": "{\"address\": {\"city\": \"London\"}}",
+            "full_code": "UK-001"
+        });
+
+        let result = MappingExecutor::new(mapping).execute(&[record]);
+        assert!(result.report.is_success());
+        assert_eq!(
+            result.records[0],
+            json!({
+                "id": 7,
+                "age": 42,
+                "name": "Ada Lovelace",
+                "created_date": "1815-12-10",
+                "city": "London",
+                "status": "active",
+                "code": {"prefix": "UK", "number": "001"},
+                "source_system": "crm"
+            })
+        );
+    }
+
+    #[test]
+    fn test_failures_counted_per_field() {
+        let mut mapping = SchemaMapping::empty();
+        mapping
+            .direct_mappings
+            .push(FieldMapping::new("email", "email"));
+        mapping.transformations.push(transform(
+            &["qty"],
+            "quantity",
+            TransformType::TypeCast {
+                from_type: "string".to_string(),
+                to_type: "integer".to_string(),
+            },
+        ));
+        let target = json!({
+            "type": "object",
+            "required": ["email", "quantity"],
+            "properties": {
+                "email": {"type": "string", "format": "email"},
+                "quantity": {"type": "integer", "minimum": 1}
+            }
+        });
+
+        let records = vec![
+            json!({"email": "a@example.com", "qty": "3"}),
+            json!({"email": "not-an-email", "qty": "three"}),
+            json!({"email": "b@example.com", "qty": "0"}),
+        ];
+
+        let result = MappingExecutor::new(mapping)
+            .with_target_schema(target)
+            .execute(&records);
+        let report = &result.report;
+
+        assert_eq!(report.records_processed, 3);
+        assert_eq!(report.records_valid, 1);
+        assert_eq!(report.records_failed, 2);
+        assert_eq!(report.fields["email"].validation, 1);
+        // The failed cast is not double counted as a missing required field
+        assert_eq!(report.fields["quantity"].transform, 1);
+        assert_eq!(report.fields["quantity"].validation, 1);
+        assert_eq!(report.failure_count("quantity"), 2);
+        assert_eq!(report.errors.len(), 3);
+        assert_eq!(report.errors[0].record, 1);
+    }
+
+    #[test]
+    fn test_explode_and_elementwise_arrays() {
+        let mut mapping = SchemaMapping::empty();
+        mapping
+            .direct_mappings
+            .push(FieldMapping::new("order_id", "order_id"));
+        mapping.transformations.push(transform(
+            &["items[].sku"],
+            "sku",
+            TransformType::Explode {
+                array_path: "items[]".to_string(),
+            },
+        ));
+        mapping.transformations.push(transform(
+            &["items[].qty"],
+            "quantity",
+            TransformType::Explode {
+                array_path: "items[]".to_string(),
+            },
+        ));
+        let record = json!({
+            "order_id": 1,
+            "items": [{"sku": "A", "qty": 2}, {"sku": "B", "qty": 1}]
+        });
+
+        let result = MappingExecutor::new(mapping).execute(std::slice::from_ref(&record));
+        assert_eq!(result.report.records_processed, 1);
+        assert_eq!(result.report.records_emitted, 2);
+        assert_eq!(
+            result.records,
+            vec![
+                json!({"order_id": 1, "sku": "A", "quantity": 2}),
+                json!({"order_id": 1, "sku": "B", "quantity": 1}),
+            ]
+        );
+
+        let mut mapping = SchemaMapping::empty();
+        mapping
+            .direct_mappings
+            .push(FieldMapping::new("items[].sku", "lines[].product"));
+        mapping.transformations.push(transform(
+            &["items[].qty"],
+            "lines[].quantity",
+            TransformType::TypeCast {
+                from_type: "integer".to_string(),
+                to_type: "string".to_string(),
+            },
+        ));
+        let result = MappingExecutor::new(mapping).execute(&[record]);
+        assert_eq!(
+            result.records,
+            vec![json!({"lines": [
+                {"product": "A", "quantity": "2"},
+                {"product": "B", "quantity": "1"}
+            ]})]
+        );
+    }
+
+    #[test]
+    fn test_collect_groups_rows() {
+        let mut mapping = SchemaMapping::empty();
+        mapping
+            .direct_mappings
+            .push(FieldMapping::new("order_id", "order_id"));
+        mapping.transformations.push(transform(
+            &["sku"],
+            "items[].sku",
+            TransformType::Collect {
+                array_path: "items[]".to_string(),
+            },
+        ));
+        let records = vec![
+            json!({"order_id": 1, "sku": "A"}),
+            json!({"order_id": 2, "sku": "C"}),
+            json!({"order_id": 1, "sku": "B"}),
+        ];
+
+        let result = MappingExecutor::new(mapping).execute(&records);
+        assert_eq!(result.report.records_processed, 3);
+        assert_eq!(result.report.records_emitted, 2);
+        assert_eq!(
+            result.records,
+            vec![
+                json!({"order_id": 1, "items": [{"sku": "A"}, {"sku": "B"}]}),
+                json!({"order_id": 2, "items": [{"sku": "C"}]}),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unsupported_custom_expression_reported() {
+        let mut mapping = SchemaMapping::empty();
+        mapping.transformations.push(transform(
+            &["name"],
+            "name",
+            TransformType::Custom {
+                expression: "upper(name)".to_string(),
+            },
+        ));
+        mapping.transformations.push(transform(
+            &["price"],
+            "price_eur",
+            TransformType::Custom {
+                expression: "price * fx_rate".to_string(),
+            },
+        ));
+
+        let result = MappingExecutor::new(mapping).execute(&[json!({"name": "ada", "price": 3})]);
+        assert_eq!(result.records[0], json!({"name": "ADA"}));
+        assert_eq!(result.report.unsupported.len(), 1);
+        assert!(result.report.unsupported[0].starts_with("price_eur"));
+        assert!(result.report.is_success());
+    }
+
+    #[test]
+    fn test_format_change_epoch_and_iso() {
+        let value = change_format(&json!(0), "epoch", "iso8601").unwrap();
+        assert_eq!(value, json!("1970-01-01T00:00:00+00:00"));
+
+        let value = change_format(&json!("2024-03-01T12:00:00Z"), "auto", "epoch").unwrap();
+        assert_eq!(value, json!(1709294400));
+
+        assert!(change_format(&json!("yesterday"), "auto", "date").is_err());
+    }
+
+    #[test]
+    fn test_eval_json_path() {
+        let value = json!({"lines": [{"sku": "A"}, {"sku": "B"}]});
+        assert_eq!(eval_json_path(&value, "$.lines[1].sku"), Some(json!("B")));
+        assert_eq!(eval_json_path(&value, "$.missing"), None);
+    }
+}
@@ -2,7 +2,11 @@
 
 use super::config::TransformFormat;
 use super::error::MappingResult;
-use super::types::{SchemaMapping, TransformType};
+use super::path::{
+    ARRAY_MARKER, array_depth, array_prefix, array_segments, child_path, element_path,
+    is_array_path, is_elementwise,
+};
+use super::types::{FieldMapping, SchemaMapping, TransformMapping, TransformType};
 
 /// Generate transformation script from a schema mapping
 pub fn generate_transform(
@@ -16,6 +20,7 @@ pub fn generate_transform(
         TransformFormat::Jq => generate_jq(mapping),
         TransformFormat::Python => generate_python(mapping, source_table, target_table),
         TransformFormat::Spark => generate_spark(mapping, source_table, target_table),
+        TransformFormat::Dbt => generate_dbt(mapping, source_table, target_table),
     }
 }
 
@@ -45,75 +50,37 @@ fn generate_sql(
 
     lines.push(format!("INSERT INTO {} (", target_table));
 
-    // Collect all target columns
-    let mut columns: Vec<String> = mapping
-        .direct_mappings
+    let plan = ArrayPlan::new(mapping);
+    let items = sql_select_items(mapping, &plan);
+
+    let columns: Vec<String> = items
         .iter()
-        .map(|m| format!("    {}", escape_identifier(&m.target_path)))
+        .map(|item| format!("    {}", item.target))
         .collect();
-
-    columns.extend(
-        mapping
-            .transformations
-            .iter()
-            .map(|t| format!("    {}", escape_identifier(&t.target_path))),
-    );
-
-    // Add columns for gaps with defaults
-    for gap in &mapping.gaps {
-        if gap.suggested_default.is_some() {
-            columns.push(format!("    {}", escape_identifier(&gap.target_path)));
-        }
-    }
-
     lines.push(columns.join(",\n"));
     lines.push(")".to_string());
     lines.push("SELECT".to_string());
 
-    // Generate SELECT expressions
-    let mut select_exprs: Vec<String> = Vec::new();
-
-    // Direct mappings
-    for m in &mapping.direct_mappings {
-        let comment = format!(
-            "-- {} match, confidence: {:.0}%",
-            m.match_method,
-            m.confidence * 100.0
-        );
-        select_exprs.push(format!(
-            "    {} AS {} {}",
-            escape_identifier(&m.source_path),
-            escape_identifier(&m.target_path),
-            comment
+    // Separators go before the trailing comments so they are not commented out
+    let last = items.len().saturating_sub(1);
+    for (i, item) in items.iter().enumerate() {
+        let separator = if i < last { "," } else { "" };
+        lines.push(format!(
+            "    {} AS {}{} -- {}",
+            item.expr, item.target, separator, item.comment
         ));
     }
 
-    // Transformations
-    for t in &mapping.transformations {
-        let expr = transform_to_sql(&t.transform_type, &t.source_paths);
-        let comment = format!("-- {}", t.description);
-        select_exprs.push(format!(
-            "    {} AS {} {}",
-            expr,
-            escape_identifier(&t.target_path),
-            comment
-        ));
+    let mut from = vec![format!("FROM {}", source_table)];
+    from.extend(sql_unnest_joins(&plan));
+    if !plan.collect.is_empty() {
+        from.push("GROUP BY ALL".to_string());
     }
-
-    // Gap defaults
-    for gap in &mapping.gaps {
-        if let Some(ref default) = gap.suggested_default {
-            let default_sql = value_to_sql(default);
-            select_exprs.push(format!(
-                "    {} AS {} -- default for missing field",
-                default_sql,
-                escape_identifier(&gap.target_path)
-            ));
-        }
+    if let Some(last) = from.last_mut() {
+        last.push(';');
     }
-
-    lines.push(select_exprs.join(",\n"));
-    lines.push(format!("FROM {};", source_table));
+    lines.extend(from);
+    lines.extend(plan.unsupported_notes("--"));
 
     // Add notes about unmapped extras
     if !mapping.extras.is_empty() {
@@ -147,6 +114,86 @@ fn generate_sql(
     Ok(lines.join("\n"))
 }
 
+/// Generate a dbt staging model
+///
+/// A `source_table` of the form `source_name.table` is read through
+/// `{{ source(...) }}`; a bare name is treated as an upstream model and read
+/// through `{{ ref(...) }}`.
+fn generate_dbt(
+    mapping: &SchemaMapping,
+    source_table: &str,
+    target_table: &str,
+) -> MappingResult<String> {
+    let mut lines = Vec::new();
+
+    lines.push(format!(
+        "-- Staging model for {} (source: {})",
+        target_table, source_table
+    ));
+    lines.push(format!(
+        "-- Direct mappings: {}, Transformations: {}, Coverage: {:.1}%",
+        mapping.direct_mappings.len(),
+        mapping.transformations.len(),
+        mapping.compatibility_score * 100.0
+    ));
+    lines.push(String::new());
+    lines.push("{{ config(materialized='view') }}".to_string());
+    lines.push(String::new());
+
+    let relation = match source_table.split_once('.') {
+        Some((source, table)) => format!(
+            "{{{{ source('{}', '{}') }}}}",
+            escape_jinja_string(source),
+            escape_jinja_string(table)
+        ),
+        None => format!("{{{{ ref('{}') }}}}", escape_jinja_string(source_table)),
+    };
+
+    lines.push("with source as (".to_string());
+    lines.push(format!("    select * from {}", relation));
+    lines.push("),".to_string());
+    lines.push(String::new());
+    lines.push("renamed as (".to_string());
+    lines.push("    select".to_string());
+
+    let plan = ArrayPlan::new(mapping);
+    let mut select_exprs: Vec<String> = sql_select_items(mapping, &plan)
+        .into_iter()
+        .map(|item| format!("        {} as {}", item.expr, item.target))
+        .collect();
+    if select_exprs.is_empty() {
+        select_exprs.push("        *".to_string());
+    }
+
+    lines.push(select_exprs.join(",\n"));
+    lines.push("    from source".to_string());
+    for join in sql_unnest_joins(&plan) {
+        lines.push(format!("    {}", join));
+    }
+    if !plan.collect.is_empty() {
+        lines.push("    group by all".to_string());
+    }
+    lines.push(")".to_string());
+    lines.push(String::new());
+    lines.push("select * from renamed".to_string());
+
+    let required_gaps: Vec<_> = mapping
+        .gaps
+        .iter()
+        .filter(|g| g.required && g.suggested_default.is_none())
+        .collect();
+    if !required_gaps.is_empty() {
+        lines.push(String::new());
+        lines.push("-- WARNING: The following required target fields have no mapping:".to_string());
+        for gap in required_gaps {
+            lines.push(format!("--   {} ({})", gap.target_path, gap.target_type));
+        }
+    }
+    lines.extend(plan.unsupported_notes("--"));
+
+    Ok(lines.join("\n"))
+}
+
 /// Generate JQ transformation
 fn generate_jq(mapping: &SchemaMapping) -> MappingResult<String> {
     let mut lines = Vec::new();
@@ -156,32 +203,80 @@ fn generate_jq(mapping: &SchemaMapping) -> MappingResult<String> {
         "# Coverage: {:.1}%",
         mapping.compatibility_score * 100.0
     ));
+    let plan = ArrayPlan::new(mapping);
+    if !plan.collect.is_empty() {
+        lines.push("# Collects rows into arrays: run with `jq -s` over all records".to_string());
+    }
     lines.push(String::new());
+    if !plan.collect.is_empty() {
+        lines.push("map(".to_string());
+    }
+
+    // Bind each exploded element, emitting one object per element
+    for array_path in &plan.explode {
+        let container = array_container(array_path);
+        let source = match array_prefix(container) {
+            Some(parent) => format!("${}.{}", element_alias(parent), element_path(container)),
+            None => path_to_jq(container),
+        };
+        lines.push(format!("{}[] as ${} |", source, element_alias(array_path)));
+    }
     lines.push("{".to_string());
 
     let mut assignments: Vec<String> = Vec::new();
 
     // Direct mappings
     for m in &mapping.direct_mappings {
+        if plan.handles_direct(m) {
+            continue;
+        }
         let source_jq = path_to_jq(&m.source_path);
         assignments.push(format!("  \"{}\": {}", m.target_path, source_jq));
     }
 
     // Transformations
     for t in &mapping.transformations {
-        let expr = transform_to_jq(&t.transform_type, &t.source_paths);
+        if plan.handles_transform(t) {
+            continue;
+        }
+        let expr = match (&t.transform_type, t.source_paths.first()) {
+            (TransformType::Explode { array_path }, Some(source)) => jq_element_path(
+                &format!("${}", element_alias(array_path)),
+                element_path(source),
+            ),
+            (other, _) => transform_to_jq(other, &t.source_paths),
+        };
         assignments.push(format!(
-            "  \"{}\": {} # {}",
-            t.target_path, expr, t.description
+            "  # {}\n  \"{}\": {}",
+            t.description, t.target_path, expr
         ));
     }
 
+    // Element-wise array mappings
+    for group in &plan.elementwise {
+        let element = group_fields(group, |p| jq_element_path("", p), jq_transform_expr);
+        assignments.push(format!(
+            "  \"{}\": [{}[] | {}]",
+            group.target,
+            path_to_jq(&group.source),
+            jq_object(&element)
+        ));
+    }
+
+    // Collected arrays hold a single element until rows are grouped below
+    for group in &plan.collect {
+        let element = group_fields(group, path_to_jq, jq_transform_expr);
+        assignments.push(format!("  \"{}\": {}", group.target, jq_object(&element)));
+    }
+
     // Gap defaults
     for gap in &mapping.gaps {
-        if let Some(ref default) = gap.suggested_default {
+        if let Some(ref default) = gap.suggested_default
+            && !is_array_path(&gap.target_path)
+        {
             let default_jq = serde_json::to_string(default).unwrap_or_else(|_| "null".to_string());
             assignments.push(format!(
-                "  \"{}\": {} # default",
+                "  # default\n  \"{}\": {}",
                 gap.target_path, default_jq
             ));
         }
@@ -190,6 +285,21 @@ fn generate_jq(mapping: &SchemaMapping) -> MappingResult<String> {
     lines.push(assignments.join(",\n"));
     lines.push("}".to_string());
 
+    if !plan.collect.is_empty() {
+        let collected = plan.collected();
+        let keys: Vec<String> = collected.iter().map(|c| format!(".[\"{}\"]", c)).collect();
+        let merged: Vec<String> = collected
+            .iter()
+            .map(|c| format!("\"{}\": map(.[\"{}\"])", c, c))
+            .collect();
+        lines.push(")".to_string());
+        lines.push(format!("| group_by(del({}))", keys.join(", ")));
+        lines.push(format!("| map(.[0] + {{{}}})", merged.join(", ")));
+    }
+    for note in plan.unsupported_notes("#") {
+        lines.push(note);
+    }
+
     Ok(lines.join("\n"))
 }
 
@@ -200,6 +310,19 @@ fn generate_python(
     target_table: &str,
 ) -> MappingResult<String> {
     let mut lines = Vec::new();
+    let plan = ArrayPlan::new(mapping);
+    let direct: Vec<_> = mapping
+        .direct_mappings
+        .iter()
+        .filter(|m| !plan.handles_direct(m))
+        .collect();
+    let transformations: Vec<_> = mapping
+        .transformations
+        .iter()
+        .filter(|t| {
+            !plan.handles_transform(t) && !matches!(t.transform_type, TransformType::Explode { .. })
+        })
+        .collect();
 
     lines.push("\"\"\"".to_string());
     lines.push("Schema mapping transformation".to_string());
@@ -223,9 +346,9 @@ fn generate_python(
     lines.push(String::new());
 
     // Direct mappings
-    if !mapping.direct_mappings.is_empty() {
+    if !direct.is_empty() {
         lines.push("    # Direct mappings".to_string());
-        for m in &mapping.direct_mappings {
+        for m in &direct {
             let source_access = path_to_python_access(&m.source_path);
             lines.push(format!(
                 "    target[\"{}\"] = {}  # {} match",
@@ -236,9 +359,9 @@ fn generate_python(
     }
 
     // Transformations
-    if !mapping.transformations.is_empty() {
+    if !transformations.is_empty() {
         lines.push("    # Transformations".to_string());
-        for t in &mapping.transformations {
+        for t in &transformations {
             let expr = transform_to_python(&t.transform_type, &t.source_paths);
             lines.push(format!(
                 "    target[\"{}\"] = {}  # {}",
@@ -252,7 +375,7 @@ fn generate_python(
     let gaps_with_defaults: Vec<_> = mapping
         .gaps
         .iter()
-        .filter(|g| g.suggested_default.is_some())
+        .filter(|g| g.suggested_default.is_some() && !is_array_path(&g.target_path))
         .collect();
     if !gaps_with_defaults.is_empty() {
         lines.push("    # Default values for missing fields".to_string());
@@ -277,6 +400,16 @@ fn generate_python(
     lines.push("    \"\"\"Transform a batch of records.\"\"\"".to_string());
     lines.push("    return [transform_record(r) for r in records]".to_string());
 
+    let skipped = mapping.direct_mappings.len() - direct.len() + mapping.transformations.len()
+        - transformations.len();
+    if skipped > 0 {
+        lines.push(String::new());
+        lines.push(format!(
+            "# Note: {} array mapping(s) are not generated; use the SQL, jq or spark format",
+            skipped
+        ));
+    }
+
     Ok(lines.join("\n"))
 }
 
@@ -300,8 +433,27 @@ fn generate_spark(
     ));
     lines.push("\"\"\"".to_string());
     lines.push(String::new());
+    let plan = ArrayPlan::new(mapping);
+    let mut functions = vec!["col", "lit", "concat", "concat_ws", "split"];
+    if !plan.explode.is_empty() {
+        functions.push("explode");
+    }
+    if !plan.elementwise.is_empty() {
+        // Aliased so it is not shadowed by the generated `transform` function
+        functions.push("transform as transform_array");
+    }
+    if !plan.elementwise.is_empty() || !plan.collect.is_empty() {
+        functions.push("struct");
+    }
+    if !plan.collect.is_empty() {
+        functions.push("collect_list");
+    }
+
     lines.push("from pyspark.sql import DataFrame".to_string());
-    lines.push("from pyspark.sql.functions import col, lit, concat, concat_ws, split".to_string());
+    lines.push(format!(
+        "from pyspark.sql.functions import {}",
+        functions.join(", ")
+    ));
     lines.push(
         "from pyspark.sql.types import StringType, IntegerType, DoubleType, BooleanType"
             .to_string(),
@@ -310,12 +462,33 @@ fn generate_spark(
     lines.push(String::new());
     lines.push("def transform(df: DataFrame) -> DataFrame:".to_string());
     lines.push("    \"\"\"Transform source DataFrame to target schema.\"\"\"".to_string());
-    lines.push("    return df.select(".to_string());
+
+    // One row per exploded element
+    for array_path in &plan.explode {
+        let container = array_container(array_path);
+        let source = match array_prefix(container) {
+            Some(parent) => format!("{}.{}", element_alias(parent), element_path(container)),
+            None => container.to_string(),
+        };
+        lines.push(format!(
+            "    df = df.withColumn(\"{}\", explode({}))",
+            element_alias(array_path),
+            path_to_spark_col(&source)
+        ));
+    }
+    if plan.explode.is_empty() && plan.collect.is_empty() {
+        lines.push("    return df.select(".to_string());
+    } else {
+        lines.push("    df = df.select(".to_string());
+    }
 
     let mut select_exprs: Vec<String> = Vec::new();
 
     // Direct mappings
     for m in &mapping.direct_mappings {
+        if plan.handles_direct(m) {
+            continue;
+        }
         let source_col = path_to_spark_col(&m.source_path);
         select_exprs.push(format!(
             "        {}.alias(\"{}\"),  # {}",
@@ -325,16 +498,48 @@ fn generate_spark(
 
     // Transformations
     for t in &mapping.transformations {
-        let expr = transform_to_spark(&t.transform_type, &t.source_paths);
+        if plan.handles_transform(t) {
+            continue;
+        }
+        let expr = match (&t.transform_type, t.source_paths.first()) {
+            (TransformType::Explode { array_path }, Some(source)) => path_to_spark_col(
+                &child_path(&element_alias(array_path), element_path(source)),
+            ),
+            (other, _) => transform_to_spark(other, &t.source_paths),
+        };
         select_exprs.push(format!(
             "        {}.alias(\"{}\"),  # {}",
             expr, t.target_path, t.description
         ));
     }
 
+    // Element-wise array mappings
+    for group in &plan.elementwise {
+        let element = group_fields(group, spark_element_field, spark_transform_expr);
+        select_exprs.push(format!(
+            "        transform_array({}, lambda x: {}).alias(\"{}\"),  # element-wise from {}[]",
+            path_to_spark_col(&group.source),
+            spark_struct(&element),
+            group.target,
+            group.source
+        ));
+    }
+
+    // Collected arrays hold a single element until rows are grouped below
+    for group in &plan.collect {
+        let element = group_fields(group, path_to_spark_col, spark_transform_expr);
+        select_exprs.push(format!(
+            "        {}.alias(\"{}\"),  # collected from grouped rows",
+            spark_struct(&element),
+            group.target
+        ));
+    }
+
     // Gap defaults
     for gap in &mapping.gaps {
-        if let Some(ref default) = gap.suggested_default {
+        if let Some(ref default) = gap.suggested_default
+            && !is_array_path(&gap.target_path)
+        {
             let default_spark = value_to_spark_lit(default);
             select_exprs.push(format!(
                 "        {}.alias(\"{}\"),  # default",
@@ -353,11 +558,458 @@ fn generate_spark(
     lines.push(select_exprs.join("\n"));
     lines.push("    )".to_string());
 
+    if !plan.collect.is_empty() {
+        let collected: Vec<String> = plan
+            .collected()
+            .iter()
+            .map(|c| format!("\"{}\"", c))
+            .collect();
+        let aggregates: Vec<String> = plan
+            .collected()
+            .iter()
+            .map(|c| format!("collect_list(\"{}\").alias(\"{}\")", c, c))
+            .collect();
+        lines.push(format!("    collected = [{}]", collected.join(", ")));
+        lines.push("    keys = [c for c in df.columns if c not in collected]".to_string());
+        lines.push(format!(
+            "    return df.groupBy(*keys).agg({})",
+            aggregates.join(", ")
+        ));
+    } else if !plan.explode.is_empty() {
+        lines.push("    return df".to_string());
+    }
+    for note in plan.unsupported_notes("#") {
+        lines.push(note);
+    }
+
     Ok(lines.join("\n"))
 }
 
+// Array handling
+
+/// How a mapping's array paths are realised in generated code
+struct ArrayPlan<'a> {
+    /// Exploded arrays, outermost first (e.g. `a[]` then `a[].b[]`)
+    explode: Vec<String>,
+    /// Element-wise mappings between differently named arrays
+    elementwise: Vec<ArrayGroup<'a>>,
+    /// Fields aggregated into target arrays
+    collect: Vec<ArrayGroup<'a>>,
+    /// Mappings with array paths the generators cannot express
+    unsupported: Vec<String>,
+}
+
+/// Fields written into one target array
+struct ArrayGroup<'a> {
+    /// Source array (e.g. `items`); empty for collects
+    source: String,
+    /// Target array (e.g. `lines`)
+    target: String,
+    fields: Vec<ArrayField<'a>>,
+}
+
+/// A field inside an array group
+struct ArrayField<'a> {
+    /// Source paths, relative to the source element for element-wise groups
+    sources: Vec<String>,
+    /// Target path relative to the target element
+    target: String,
+    transform: Option<&'a TransformType>,
+}
+
+/// Nested field expressions, used to build struct/object literals
+enum Nested {
+    Leaf(String),
+    Group(Vec<(String, Nested)>),
+}
+
+impl<'a> ArrayPlan<'a> {
+    fn new(mapping: &'a SchemaMapping) -> Self {
+        let mut plan = ArrayPlan {
+            explode: Vec::new(),
+            elementwise: Vec::new(),
+            collect: Vec::new(),
+            unsupported: Vec::new(),
+        };
+        let whole_arrays: Vec<(&str, &str)> = mapping
+            .direct_mappings
+            .iter()
+            .map(|m| (m.source_path.as_str(), m.target_path.as_str()))
+            .collect();
+
+        for m in &mapping.direct_mappings {
+            if !is_array_path(&m.source_path) && !is_array_path(&m.target_path) {
+                continue;
+            }
+            if array_depth(&m.source_path) != 1 || array_depth(&m.target_path) != 1 {
+                plan.unsupported
+                    .push(format!("{} <- {}", m.target_path, m.source_path));
+                continue;
+            }
+            let source = array_container(&m.source_path);
+            let target = array_container(&m.target_path);
+            // The whole array is already copied
+            if whole_arrays.contains(&(source, target)) {
+                continue;
+            }
+            push_field(
+                &mut plan.elementwise,
+                source,
+                target,
+                ArrayField {
+                    sources: vec![element_path(&m.source_path).to_string()],
+                    target: element_path(&m.target_path).to_string(),
+                    transform: None,
+                },
+            );
+        }
+
+        for t in &mapping.transformations {
+            match &t.transform_type {
+                // Nothing to read from the exploded element
+                TransformType::Explode { .. } if t.source_paths.is_empty() => {
+                    plan.unsupported.push(format!(
+                        "{} ({}; no source path)",
+                        t.target_path, t.description
+                    ));
+                }
+                TransformType::Explode { array_path } => {
+                    let mut prefix = String::new();
+                    for segment in array_segments(array_path).iter().rev().skip(1).rev() {
+                        prefix = format!("{}{}", child_path(&prefix, segment), ARRAY_MARKER);
+                        if !plan.explode.contains(&prefix) {
+                            plan.explode.push(prefix.clone());
+                        }
+                    }
+                }
+                TransformType::Collect { array_path }
+                    if array_depth(array_path) == 1
+                        && !t.source_paths.iter().any(|p| is_array_path(p)) =>
+                {
+                    push_field(
+                        &mut plan.collect,
+                        "",
+                        array_container(array_path),
+                        ArrayField {
+                            sources: t.source_paths.clone(),
+                            target: element_path(&t.target_path).to_string(),
+                            transform: None,
+                        },
+                    );
+                }
+                _ if is_elementwise(t) => {
+                    push_field(
+                        &mut plan.elementwise,
+                        array_container(&t.source_paths[0]),
+                        array_container(&t.target_path),
+                        ArrayField {
+                            sources: t
+                                .source_paths
+                                .iter()
+                                .map(|p| element_path(p).to_string())
+                                .collect(),
+                            target: element_path(&t.target_path).to_string(),
+                            transform: Some(&t.transform_type),
+                        },
+                    );
+                }
+                TransformType::Collect { .. } => {
+                    plan.unsupported
+                        .push(format!("{} ({})", t.target_path, t.description));
+                }
+                _ if is_array_path(&t.target_path)
+                    || t.source_paths.iter().any(|p| is_array_path(p)) =>
+                {
+                    plan.unsupported
+                        .push(format!("{} ({})", t.target_path, t.description));
+                }
+                _ => {}
+            }
+        }
+        plan.explode.sort_by_key(|p| array_depth(p));
+        plan
+    }
+
+    /// Whether a direct mapping is generated by the plan rather than inline
+    fn handles_direct(&self, m: &FieldMapping) -> bool {
+        is_array_path(&m.source_path) || is_array_path(&m.target_path)
+    }
+
+    /// Whether a transformation is generated by the plan rather than inline
+    ///
+    /// Explode transforms without a source path are only listed as unsupported.
+    fn handles_transform(&self, t: &TransformMapping) -> bool {
+        matches!(t.transform_type, TransformType::Collect { .. })
+            || (matches!(t.transform_type, TransformType::Explode { .. })
+                && t.source_paths.is_empty())
+            || (!matches!(t.transform_type, TransformType::Explode { .. })
+                && (is_array_path(&t.target_path)
+                    || t.source_paths.iter().any(|p| is_array_path(p))))
+    }
+
+    /// Target arrays built by collect groups
+    fn collected(&self) -> Vec<&str> {
+        self.collect.iter().map(|g| g.target.as_str()).collect()
+    }
+
+    /// Comment lines listing mappings that were not generated
+    fn unsupported_notes(&self, comment: &str) -> Vec<String> {
+        if self.unsupported.is_empty() {
+            return Vec::new();
+        }
+        let mut lines = vec![
+            String::new(),
+            format!(
+                "{} Note: The following array mappings are not generated (nested arrays or missing source paths):",
+                comment
+            ),
+        ];
+        for entry in &self.unsupported {
+            lines.push(format!("{}   {}", comment, entry));
+        }
+        lines
+    }
+}
+
+fn push_field<'a>(
+    groups: &mut Vec<ArrayGroup<'a>>,
+    source: &str,
+    target: &str,
+    field: ArrayField<'a>,
+) {
+    match groups
+        .iter_mut()
+        .find(|g| g.source == source && g.target == target)
+    {
+        Some(group) => group.fields.push(field),
+        None => groups.push(ArrayGroup {
+            source: source.to_string(),
+            target: target.to_string(),
+            fields: vec![field],
+        }),
+    }
+}
+
+/// Path of the array holding the innermost element (`items[].sku` gives `items`)
+fn array_container(path: &str) -> &str {
+    array_prefix(path)
+        .unwrap_or(path)
+        .trim_end_matches(ARRAY_MARKER)
+}
+
+/// Variable name bound to the elements of an exploded array
+fn element_alias(array_path: &str) -> String {
+    let container = array_container(array_path);
+    let name = container.rsplit(['.', ']']).next().unwrap_or(container);
+    format!("{}_item", name.trim_start_matches('.'))
+}
+
+/// Group `(relative path, expression)` pairs into nested fields
+fn nest(fields: &[(String, String)]) -> Nested {
+    if let [(path, expr)] = fields
+        && path.is_empty()
+    {
+        return Nested::Leaf(expr.clone());
+    }
+    let mut groups: Vec<(String, Vec<(String, String)>)> = Vec::new();
+    for (path, expr) in fields {
+        let (head, rest) = path.split_once('.').unwrap_or((path, ""));
+        let entry = (rest.to_string(), expr.clone());
+        match groups.iter_mut().find(|(key, _)| key == head) {
+            Some((_, children)) => children.push(entry),
+            None => groups.push((head.to_string(), vec![entry])),
+        }
+    }
+    Nested::Group(
+        groups
+            .into_iter()
+            .map(|(key, children)| (key, nest(&children)))
+            .collect(),
+    )
+}
+
+/// Render element fields with a language-specific path and transform renderer
+fn group_fields(
+    group: &ArrayGroup,
+    path: impl Fn(&str) -> String,
+    transform: impl Fn(&TransformType, &[String]) -> String,
+) -> Nested {
+    let fields: Vec<(String, String)> = group
+        .fields
+        .iter()
+        .map(|f| {
+            let cols: Vec<String> = f.sources.iter().map(|s| path(s)).collect();
+            let expr = match f.transform {
+                Some(t) => transform(t, &cols),
+                None => cols[0].clone(),
+            };
+            (f.target.clone(), expr)
+        })
+        .collect();
+    nest(&fields)
+}
+
+/// Quote each segment of a dotted path for SQL, optionally under a base
+fn sql_column(base: Option<&str>, path: &str) -> String {
+    let mut parts: Vec<String> = base.map(str::to_string).into_iter().collect();
+    parts.extend(
+        path.split('.')
+            .filter(|p| !p.is_empty())
+            .map(|p| format!("\"{}\"", p)),
+    );
+    parts.join(".")
+}
+
+fn sql_struct(nested: &Nested) -> String {
+    match nested {
+        Nested::Leaf(expr) => expr.clone(),
+        Nested::Group(fields) => {
+            let parts: Vec<String> = fields
+                .iter()
+                .map(|(key, value)| format!("'{}': {}", key.replace('\'', "''"), sql_struct(value)))
+                .collect();
+            format!("{{{}}}", parts.join(", "))
+        }
+    }
+}
+
+fn jq_object(nested: &Nested) -> String {
+    match nested {
+        Nested::Leaf(expr) => expr.clone(),
+        Nested::Group(fields) => {
+            let parts: Vec<String> = fields
+                .iter()
+                .map(|(key, value)| format!("\"{}\": {}", key, jq_object(value)))
+                .collect();
+            format!("{{{}}}", parts.join(", "))
+        }
+    }
+}
+
+fn spark_struct(nested: &Nested) -> String {
+    match nested {
+        Nested::Leaf(expr) => expr.clone(),
+        Nested::Group(fields) => {
+            let parts: Vec<String> = fields
+                .iter()
+                .map(|(key, value)| format!("{}.alias(\"{}\")", spark_struct(value), key))
+                .collect();
+            format!("struct({})", parts.join(", "))
+        }
+    }
+}
+
+/// A generated SQL select item: expression, target column and comment
+struct SqlItem {
+    expr: String,
+    target: String,
+    comment: String,
+}
+
+/// Select items shared by the SQL and dbt generators
+fn sql_select_items(mapping: &SchemaMapping, plan: &ArrayPlan) -> Vec<SqlItem> {
+    let mut items = Vec::new();
+
+    for m in &mapping.direct_mappings {
+        if plan.handles_direct(m) {
+            continue;
+        }
+        items.push(SqlItem {
+            expr: escape_identifier(&m.source_path),
+            target: escape_identifier(&m.target_path),
+            comment: format!(
+                "{} match, confidence: {:.0}%",
+                m.match_method,
+                m.confidence * 100.0
+            ),
+        });
+    }
+
+    for t in &mapping.transformations {
+        if plan.handles_transform(t) {
+            continue;
+        }
+        let expr = match (&t.transform_type, t.source_paths.first()) {
+            (TransformType::Explode { array_path }, Some(source)) => sql_column(
+                Some(&format!("\"{}\"", element_alias(array_path))),
+                element_path(source),
+            ),
+            (other, _) => transform_to_sql(other, &t.source_paths),
+        };
+        items.push(SqlItem {
+            expr,
+            target: escape_identifier(&t.target_path),
+            comment: t.description.clone(),
+        });
+    }
+
+    for group in &plan.elementwise {
+        let element = group_fields(group, |p| sql_column(Some("x"), p), sql_transform_expr);
+        items.push(SqlItem {
+            expr: format!(
+                "LIST_TRANSFORM({}, x -> {})",
+                sql_column(None, &group.source),
+                sql_struct(&element)
+            ),
+            target: escape_identifier(&group.target),
+            comment: format!("element-wise from {}[]", group.source),
+        });
+    }
+
+    for group in &plan.collect {
+        let element = group_fields(group, |p| sql_column(None, p), sql_transform_expr);
+        items.push(SqlItem {
+            expr: format!("ARRAY_AGG({})", sql_struct(&element)),
+            target: escape_identifier(&group.target),
+            comment: "collected from grouped rows".to_string(),
+        });
+    }
+
+    for gap in &mapping.gaps {
+        if let Some(ref default) = gap.suggested_default
+            && !is_array_path(&gap.target_path)
+        {
+            items.push(SqlItem {
+                expr: value_to_sql(default),
+                target: escape_identifier(&gap.target_path),
+                comment: "default for missing field".to_string(),
+            });
+        }
+    }
+
+    items
+}
+
+/// `CROSS JOIN LATERAL` clauses that unnest the exploded arrays
+fn sql_unnest_joins(plan: &ArrayPlan) -> Vec<String> {
+    plan.explode
+        .iter()
+        .map(|array_path| {
+            let container = array_container(array_path);
+            let source = match array_prefix(container) {
+                Some(parent) => sql_column(
+                    Some(&format!("\"{}\"", element_alias(parent))),
+                    element_path(container),
+                ),
+                None => sql_column(None, container),
+            };
+            let alias = element_alias(array_path);
+            format!(
+                "CROSS JOIN LATERAL (SELECT UNNEST({}) AS \"{}\") AS \"{}_unnest\"",
+                source,
+                alias,
+                alias.trim_end_matches("_item")
+            )
+        })
+        .collect()
+}
+
 // Helper functions
 
+fn escape_jinja_string(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('\'', "\\'")
+}
+
 fn escape_identifier(name: &str) -> String {
     if name.contains('.') {
         // For nested paths, use JSON extraction in SQL
@@ -372,49 +1024,50 @@ fn escape_identifier(name: &str) -> String {
     }
 }
 
-fn transform_to_sql(transform: &TransformType, sources: &[String]) -> String {
+pub(super) fn transform_to_sql(transform: &TransformType, sources: &[String]) -> String {
+    let cols: Vec<String> = sources.iter().map(|s| escape_identifier(s)).collect();
+    sql_transform_expr(transform, &cols)
+}
+
+/// Render a transformation over already-rendered SQL column expressions
+fn sql_transform_expr(transform: &TransformType, cols: &[String]) -> String {
     match transform {
         TransformType::TypeCast { to_type, .. } => {
             let sql_type = json_type_to_sql(to_type);
-            format!("CAST({} AS {})", escape_identifier(&sources[0]), sql_type)
+            format!("CAST({} AS {})", cols[0], sql_type)
+        }
+        TransformType::Rename | TransformType::Explode { .. } | TransformType::Collect { .. } => {
+            cols[0].clone()
         }
-        TransformType::Rename => escape_identifier(&sources[0]),
         TransformType::Merge { separator } => {
             let sep = separator.as_deref().unwrap_or(" ");
-            let cols: Vec<String> = sources.iter().map(|s| escape_identifier(s)).collect();
             format!("CONCAT_WS('{}', {})", sep, cols.join(", "))
         }
         TransformType::Split { delimiter, .. } => {
-            format!(
-                "STRING_SPLIT({}, '{}')",
-                escape_identifier(&sources[0]),
-                delimiter
-            )
+            format!("STRING_SPLIT({}, '{}')", cols[0], delimiter)
         }
         TransformType::FormatChange { to_format, .. } => {
             // Date format conversion
-            format!(
-                "STRFTIME({}, '{}')",
-                escape_identifier(&sources[0]),
-                to_format
-            )
+            format!("STRFTIME({}, '{}')", cols[0], to_format)
         }
         TransformType::Custom { expression } => expression.clone(),
         TransformType::Extract { json_path } => {
-            format!(
-                "JSON_EXTRACT({}, '{}')",
-                escape_identifier(&sources[0]),
-                json_path
-            )
+            format!("JSON_EXTRACT({}, '{}')", cols[0], json_path)
         }
         TransformType::Default { value } => value_to_sql(value),
     }
 }
 
 fn transform_to_jq(transform: &TransformType, sources: &[String]) -> String {
+    let cols: Vec<String> = sources.iter().map(|s| path_to_jq(s)).collect();
+    jq_transform_expr(transform, &cols)
+}
+
+/// Render a transformation over already-rendered jq paths
+fn jq_transform_expr(transform: &TransformType, cols: &[String]) -> String {
     match transform {
         TransformType::TypeCast { to_type, .. } => {
-            let source = path_to_jq(&sources[0]);
+            let source = cols[0].clone();
             match to_type.as_str() {
                 "integer" => format!("({} | tonumber)", source),
                 "number" => format!("({} | tonumber)", source),
@@ -423,16 +1076,17 @@ fn transform_to_jq(transform: &TransformType, sources: &[String]) -> String {
                 _ => source,
             }
         }
-        TransformType::Rename => path_to_jq(&sources[0]),
+        TransformType::Rename
+        | TransformType::FormatChange { .. }
+        | TransformType::Explode { .. }
+        | TransformType::Collect { .. } => cols[0].clone(),
         TransformType::Merge { separator } => {
             let sep = separator.as_deref().unwrap_or(" ");
-            let parts: Vec<String> = sources.iter().map(|s| path_to_jq(s)).collect();
-            format!("([{}] | join(\"{}\"))", parts.join(", "), sep)
+            format!("([{}] | join(\"{}\"))", cols.join(", "), sep)
         }
         TransformType::Split { delimiter, .. } => {
-            format!("({} | split(\"{}\"))", path_to_jq(&sources[0]), delimiter)
+            format!("({} | split(\"{}\"))", cols[0], delimiter)
         }
-        TransformType::FormatChange { .. } => path_to_jq(&sources[0]),
         TransformType::Custom { expression } => expression.clone(),
         TransformType::Extract { json_path } => {
             // Convert JSON path to jq path
@@ -481,43 +1135,39 @@ fn transform_to_python(transform: &TransformType, sources: &[String]) -> String
             access
         }
         TransformType::Default { value } => value_to_python(value),
+        // Row-changing transforms are not expressible in a per-record function
+        TransformType::Explode { .. } | TransformType::Collect { .. } => "None".to_string(),
     }
 }
 
 fn transform_to_spark(transform: &TransformType, sources: &[String]) -> String {
+    let cols: Vec<String> = sources.iter().map(|s| path_to_spark_col(s)).collect();
+    spark_transform_expr(transform, &cols)
+}
+
+/// Render a transformation over already-rendered PySpark column expressions
+fn spark_transform_expr(transform: &TransformType, cols: &[String]) -> String {
     match transform {
         TransformType::TypeCast { to_type, .. } => {
-            let source = path_to_spark_col(&sources[0]);
             let spark_type = json_type_to_spark(to_type);
-            format!("{}.cast({})", source, spark_type)
+            format!("{}.cast({})", cols[0], spark_type)
+        }
+        TransformType::Rename | TransformType::Explode { .. } | TransformType::Collect { .. } => {
+            cols[0].clone()
         }
-        TransformType::Rename => path_to_spark_col(&sources[0]),
         TransformType::Merge { separator } => {
             let sep = separator.as_deref().unwrap_or(" ");
-            let cols: Vec<String> = sources.iter().map(|s| path_to_spark_col(s)).collect();
             format!("concat_ws(\"{}\", {})", sep, cols.join(", "))
         }
         TransformType::Split { delimiter, .. } => {
-            format!(
-                "split({}, \"{}\")",
-                path_to_spark_col(&sources[0]),
-                delimiter
-            )
+            format!("split({}, \"{}\")", cols[0], delimiter)
         }
         TransformType::FormatChange { to_format, .. } => {
-            format!(
-                "date_format({}, \"{}\")",
-                path_to_spark_col(&sources[0]),
-                to_format
-            )
+            format!("date_format({}, \"{}\")", cols[0], to_format)
         }
         TransformType::Custom { expression } => expression.clone(),
         TransformType::Extract { json_path } => {
-            format!(
-                "get_json_object({}, \"{}\")",
-                path_to_spark_col(&sources[0]),
-                json_path
-            )
+            format!("get_json_object({}, \"{}\")", cols[0], json_path)
         }
         TransformType::Default { value } => value_to_spark_lit(value),
     }
@@ -531,6 +1181,24 @@ fn path_to_jq(path: &str) -> String {
     }
 }
 
+/// jq path to a field below a base expression (`.` when both are empty)
+fn jq_element_path(base: &str, path: &str) -> String {
+    match (base, path) {
+        ("", "") => ".".to_string(),
+        (base, "") => base.to_string(),
+        (base, path) => format!("{}.{}", base, path),
+    }
+}
+
+/// PySpark access to a field of the lambda-bound array element `x`
+fn spark_element_field(path: &str) -> String {
+    path.split('.')
+        .filter(|p| !p.is_empty())
+        .fold("x".to_string(), |access, part| {
+            format!("{}[\"{}\"]", access, part)
+        })
+}
+
 fn path_to_python_access(path: &str) -> String {
     let parts: Vec<&str> = path.split('.').collect();
     if parts.len() == 1 {
@@ -681,6 +1349,122 @@ mod tests {
         assert!(spark.contains(".cast("));
     }
 
+    #[test]
+    fn test_generate_dbt() {
+        let mapping = create_test_mapping();
+        let dbt = generate_dbt(&mapping, "raw.customers", "stg_customers").unwrap();
+
+        assert!(dbt.contains("{{ config(materialized='view') }}"));
+        assert!(dbt.contains("select * from {{ source('raw', 'customers') }}"));
+        assert!(dbt.contains("\"source_name\" as \"target_name\""));
+        assert!(dbt.contains("CAST(\"amount\" AS INTEGER) as \"amount_int\""));
+
+        let dbt = generate_dbt(&mapping, "int_customers", "stg_customers").unwrap();
+        assert!(dbt.contains("{{ ref('int_customers') }}"));
+    }
+
+    fn create_array_mapping() -> SchemaMapping {
+        let mut mapping = SchemaMapping::empty();
+        mapping
+            .direct_mappings
+            .push(FieldMapping::new("order_id", "order_id"));
+        mapping
+            .direct_mappings
+            .push(FieldMapping::new("items[].sku", "lines[].product"));
+        mapping.transformations.push(TransformMapping::new(
+            vec!["items[].qty".to_string()],
+            "quantity",
+            TransformType::Explode {
+                array_path: "items[]".to_string(),
+            },
+        ));
+        mapping
+    }
+
+    fn create_collect_mapping() -> SchemaMapping {
+        let mut mapping = SchemaMapping::empty();
+        mapping
+            .direct_mappings
+            .push(FieldMapping::new("order_id", "order_id"));
+        mapping.transformations.push(TransformMapping::new(
+            vec!["sku".to_string()],
+            "items[].sku",
+            TransformType::Collect {
+                array_path: "items[]".to_string(),
+            },
+        ));
+        mapping
+    }
+
+    #[test]
+    fn test_generate_array_sql() {
+        let sql = generate_sql(&create_array_mapping(), "orders", "order_lines").unwrap();
+        assert!(sql.contains(
+            "CROSS JOIN LATERAL (SELECT UNNEST(\"items\") AS \"items_item\") AS \"items_unnest\""
+        ));
+        assert!(sql.contains("\"items_item\".\"qty\" AS \"quantity\""));
+        assert!(
+            sql.contains("LIST_TRANSFORM(\"items\", x -> {'product': x.\"sku\"}) AS \"lines\"")
+        );
+        assert!(!sql.contains("\"items[]"));
+
+        let sql = generate_sql(&create_collect_mapping(), "lines", "orders").unwrap();
+        assert!(sql.contains("ARRAY_AGG({'sku': \"sku\"}) AS \"items\""));
+        assert!(sql.ends_with("GROUP BY ALL;"));
+    }
+
+    #[test]
+    fn test_generate_array_jq() {
+        let jq = generate_jq(&create_array_mapping()).unwrap();
+        assert!(jq.contains(".items[] as $items_item |"));
+        assert!(jq.contains("\"quantity\": $items_item.qty,"));
+        assert!(jq.contains("\"lines\": [.items[] | {\"product\": .sku}]"));
+
+        let jq = generate_jq(&create_collect_mapping()).unwrap();
+        assert!(jq.contains("| group_by(del(.[\"items\"]))"));
+        assert!(jq.contains("| map(.[0] + {\"items\": map(.[\"items\"])})"));
+    }
+
+    #[test]
+    fn test_generate_array_spark() {
+        let spark = generate_spark(&create_array_mapping(), "orders", "order_lines").unwrap();
+        assert!(spark.contains("df = df.withColumn(\"items_item\", explode(col(\"items\")))"));
+        assert!(spark.contains("col(\"items_item.qty\").alias(\"quantity\")"));
+        assert!(spark.contains(
+            "transform_array(col(\"items\"), lambda x: struct(x[\"sku\"].alias(\"product\"))).alias(\"lines\")"
+        ));
+
+        let spark = generate_spark(&create_collect_mapping(), "lines", "orders").unwrap();
+        assert!(spark.contains("collect_list(\"items\").alias(\"items\")"));
+        assert!(spark.contains("import col, lit, concat, concat_ws, split, struct, collect_list"));
+    }
+
+    #[test]
+    fn test_generate_explode_without_source_path() {
+        let mut mapping = create_array_mapping();
+        mapping.transformations.push(TransformMapping::new(
+            Vec::new(),
+            "price",
+            TransformType::Explode {
+                array_path: "items[]".to_string(),
+            },
+        ));
+
+        for format in [
+            TransformFormat::Sql,
+            TransformFormat::Jq,
+            TransformFormat::Python,
+            TransformFormat::Spark,
+            TransformFormat::Dbt,
+        ] {
+            let script = generate_transform(&mapping, format, "orders", "order_lines").unwrap();
+            assert!(script.contains("not generated"), "{}", format);
+        }
+        let sql = generate_sql(&mapping, "orders", "order_lines").unwrap();
+        assert!(sql.contains("price"));
+        assert!(!sql.contains("AS \"price\""));
+    }
+
     #[test]
     fn test_transform_to_sql() {
         let cast = TransformType::TypeCast {
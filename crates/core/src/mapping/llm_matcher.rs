@@ -12,7 +12,7 @@ use super::error::{MappingError, MappingResult};
 use super::types::{FieldMapping, MatchMethod, SchemaMapping, TransformMapping, TransformType};
 
 #[cfg(feature = "llm")]
-use crate::llm::LlmClient;
+use crate::llm::{DocumentIndex, LlmClient, RefinementConfig, create_client};
 
 /// Configuration for LLM-enhanced matching
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -120,6 +120,24 @@ pub struct LlmSchemaMatcher<C: LlmClient> {
     client: C,
     config: LlmMatcherConfig,
     mapping_config: MappingConfig,
+    documentation: Option<DocumentIndex>,
+}
+
+#[cfg(feature = "llm")]
+impl LlmSchemaMatcher<Box<dyn LlmClient>> {
+    /// Create a matcher backed by the client selected in `llm_config`
+    ///
+    /// Use this to pick Ollama, llama.cpp or an OpenAI-compatible server at
+    /// runtime instead of naming the client type.
+    pub fn from_llm_config(
+        llm_config: &RefinementConfig,
+        config: LlmMatcherConfig,
+        mapping_config: MappingConfig,
+    ) -> MappingResult<Self> {
+        let client =
+            create_client(llm_config).map_err(|e| MappingError::LlmError(e.to_string()))?;
+        Ok(Self::with_config(client, config, mapping_config))
+    }
 }
 
 #[cfg(feature = "llm")]
@@ -130,6 +148,7 @@ impl<C: LlmClient> LlmSchemaMatcher<C> {
             client,
             config: LlmMatcherConfig::default(),
             mapping_config: MappingConfig::default(),
+            documentation: None,
         }
     }
 
@@ -139,9 +158,19 @@ impl<C: LlmClient> LlmSchemaMatcher<C> {
             client,
             config,
             mapping_config,
+            documentation: None,
         }
     }
 
+    /// Add documentation; chunks relevant to each batch of fields are included in its prompt
+    ///
+    /// Compute [`DocumentIndex::embed_chunks`] beforehand to rank chunks by
+    /// embeddings as well as keywords.
+    pub fn with_documentation(mut self, index: DocumentIndex) -> Self {
+        self.documentation = Some(index);
+        self
+    }
+
     /// Match schemas using LLM
     pub async fn match_schemas(
         &self,
@@ -219,7 +248,29 @@ impl<C: LlmClient> LlmSchemaMatcher<C> {
                 continue;
             }
 
-            let prompt = self.build_matching_prompt_for_batch(source_batch, target_batch)?;
+            let documentation = match &self.documentation {
+                Some(index) => {
+                    let queries: Vec<String> = source_batch
+                        .iter()
+                        .chain(target_batch)
+                        .map(|(path, info)| match &info.description {
+                            Some(description) => format!("{} {}", path, description),
+                            None => path.to_string(),
+                        })
+                        .collect();
+                    index
+                        .context_for_fields_with(
+                            &self.client,
+                            &queries,
+                            self.client.max_tokens() / 4,
+                        )
+                        .await
+                }
+                None => String::new(),
+            };
+
+            let prompt =
+                self.build_matching_prompt_for_batch(source_batch, target_batch, &documentation)?;
             let batch_response = self.call_llm_with_retry(&prompt).await?;
 
             combined_response
@@ -283,7 +334,14 @@ impl<C: LlmClient> LlmSchemaMatcher<C> {
         &self,
         source_fields: &[(&String, &FieldPromptInfo)],
         target_fields: &[(&String, &FieldPromptInfo)],
+        documentation: &str,
     ) -> MappingResult<String> {
+        let documentation_section = if documentation.trim().is_empty() {
+            String::new()
+        } else {
+            format!("\n## Documentation Context\n```\n{}\n```\n", documentation)
+        };
+
         let prompt = format!(
             r#"You are a schema mapping expert. Match source fields to target fields based on semantic meaning.
 
@@ -292,10 +350,10 @@ impl<C: LlmClient> LlmSchemaMatcher<C> {
 
 ## Target Schema Fields (unmatched)
 {}
-
+{}
 ## Instructions
 1. For each unmatched source field, find the best matching target field based on semantic meaning
-2. Consider field names, types, and descriptions
+2. Consider field names, types, descriptions and any documentation context
 3. Assign a confidence score (0.0-1.0) for each match
 4. Note if type conversion is required
 5. If type conversion is needed, provide a transform_hint describing the transformation (e.g., "uppercase", "parse_date", "to_string")
@@ -327,6 +385,7 @@ Respond with valid JSON only, no markdown or explanation:
                 self.config.include_descriptions,
                 self.config.include_examples
             ),
+            documentation_section,
         );
 
         Ok(prompt)
@@ -766,6 +825,26 @@ mod tests {
         assert!(!config.include_examples);
     }
 
+    #[test]
+    fn test_matcher_from_llm_config() {
+        let matcher = LlmSchemaMatcher::from_llm_config(
+            &RefinementConfig::with_openai("http://localhost:8000/v1", "qwen2.5"),
+            LlmMatcherConfig::default(),
+            MappingConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(matcher.client.model_name(), "qwen2.5");
+
+        assert!(
+            LlmSchemaMatcher::from_llm_config(
+                &RefinementConfig::default(),
+                LlmMatcherConfig::default(),
+                MappingConfig::default()
+            )
+            .is_err()
+        );
+    }
+
     #[test]
     fn test_extract_json_from_response_direct() {
         let response = r#"{"suggestions": [], "overall_confidence": 0.9}"#;
@@ -980,6 +1059,53 @@ That's my analysis."#;
             assert!(mapping.gaps.is_empty());
         }
 
+        #[tokio::test]
+        async fn test_llm_matcher_includes_relevant_documentation() {
+            use crate::llm::{LlmResult, RetrievalConfig};
+            use std::sync::Mutex;
+
+            struct RecordingClient(Mutex<Vec<String>>);
+
+            #[async_trait::async_trait]
+            impl LlmClient for RecordingClient {
+                async fn complete(&self, prompt: &str) -> LlmResult<String> {
+                    self.0.lock().unwrap().push(prompt.to_string());
+                    Ok(r#"{"suggestions": [], "unmatched_source": [], "unmatched_target": [], "overall_confidence": 0.0}"#.to_string())
+                }
+
+                fn model_name(&self) -> &str {
+                    "recording"
+                }
+
+                fn max_tokens(&self) -> usize {
+                    4096
+                }
+
+                async fn is_ready(&self) -> bool {
+                    true
+                }
+            }
+
+            let mut index = DocumentIndex::new(RetrievalConfig::default());
+            index.add_text(
+                "dictionary.md",
+                "# Customers\n\nThe cust_ref is the CRM reference of a customer.\n\n# Payroll\n\nSalaries are paid monthly.",
+            );
+
+            let matcher = LlmSchemaMatcher::new(RecordingClient(Mutex::new(Vec::new())))
+                .with_documentation(index);
+            let source = json!({"type": "object", "properties": {"cust_ref": {"type": "string"}}});
+            let target =
+                json!({"type": "object", "properties": {"customer_key": {"type": "string"}}});
+            matcher.match_schemas(&source, &target).await.unwrap();
+
+            let prompts = matcher.client.0.lock().unwrap();
+            assert_eq!(prompts.len(), 1);
+            assert!(prompts[0].contains("## Documentation Context"));
+            assert!(prompts[0].contains("CRM reference"));
+            assert!(!prompts[0].contains("Salaries"));
+        }
+
         #[tokio::test]
         async fn test_llm_matcher_respects_confidence_threshold() {
             let mock_response = r#"{
@@ -6,6 +6,7 @@ use serde_json::Value;
 
 use super::config::MappingConfig;
 use super::error::{MappingError, MappingResult};
+use super::path::{ARRAY_MARKER, array_containers, array_depth, array_prefix, element_path};
 use super::types::{
     FieldGap, FieldMapping, MappingStats, MatchMethod, SchemaMapping, TransformMapping,
     TransformType,
@@ -141,7 +142,29 @@ impl SchemaMatcher {
             }
         }
 
-        // Phase 4: Type coercion suggestions
+        // Phase 4: Array restructuring (explode, collect, renamed arrays)
+        match_array_paths(
+            &self.config,
+            &source_fields,
+            &target_fields,
+            &mut matched_sources,
+            &mut matched_targets,
+            &mut mapping,
+        );
+
+        // Arrays whose elements are mapped are neither gaps nor extras
+        for m in &mapping.direct_mappings {
+            matched_sources.extend(array_containers(&m.source_path));
+            matched_targets.extend(array_containers(&m.target_path));
+        }
+        for t in &mapping.transformations {
+            for source_path in &t.source_paths {
+                matched_sources.extend(array_containers(source_path));
+            }
+            matched_targets.extend(array_containers(&t.target_path));
+        }
+
+        // Phase 5: Type coercion suggestions
         if self.config.suggest_type_coercions {
             for mapping_item in &mut mapping.direct_mappings {
                 if !mapping_item.type_compatible {
@@ -177,7 +200,7 @@ impl SchemaMatcher {
                 .retain(|m| m.type_compatible || !transform_targets.contains(&m.target_path));
         }
 
-        // Phase 5: Identify gaps
+        // Phase 6: Identify gaps
         if self.config.track_gaps {
             for (target_path, target_info) in &target_fields {
                 if !matched_targets.contains(target_path) {
@@ -208,7 +231,7 @@ impl SchemaMatcher {
             }
         }
 
-        // Phase 6: Identify extras
+        // Phase 7: Identify extras
         if self.config.track_extras {
             for source_path in source_fields.keys() {
                 if !matched_sources.contains(source_path) {
@@ -243,15 +266,15 @@ impl Default for SchemaMatcher {
 
 /// Information about a field extracted from a schema
 #[derive(Debug, Clone)]
-struct FieldInfo {
-    field_type: String,
-    required: bool,
+pub(super) struct FieldInfo {
+    pub(super) field_type: String,
+    pub(super) required: bool,
     #[allow(dead_code)]
     format: Option<String>,
 }
 
 /// Extract fields from a JSON Schema
-fn extract_fields(schema: &Value) -> MappingResult<HashMap<String, FieldInfo>> {
+pub(super) fn extract_fields(schema: &Value) -> MappingResult<HashMap<String, FieldInfo>> {
     let mut fields = HashMap::new();
 
     let properties = schema
@@ -307,23 +330,143 @@ fn extract_properties_recursive(
             },
         );
 
-        // Recurse into nested objects
-        if field_type == "object" {
-            if let Some(nested_props) = prop.get("properties").and_then(|p| p.as_object()) {
-                let nested_required: HashSet<&str> = prop
-                    .get("required")
-                    .and_then(|r| r.as_array())
-                    .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
-                    .unwrap_or_default();
+        // Recurse into nested objects, and into arrays of objects as `path[]`
+        let (nested, nested_prefix) = match field_type.as_str() {
+            "object" => (Some(prop), path.clone()),
+            "array" => (prop.get("items"), format!("{}{}", path, ARRAY_MARKER)),
+            _ => (None, path.clone()),
+        };
+        if let Some(nested) = nested
+            && let Some(nested_props) = nested.get("properties").and_then(|p| p.as_object())
+        {
+            let nested_required: HashSet<&str> = nested
+                .get("required")
+                .and_then(|r| r.as_array())
+                .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
+                .unwrap_or_default();
+
+            extract_properties_recursive(nested_props, &nested_required, &nested_prefix, fields);
+        }
+    }
+}
 
-                extract_properties_recursive(nested_props, &nested_required, &path, fields);
-            }
+/// Match fields whose array nesting differs between source and target
+///
+/// A source field inside an array matched to a target field outside one is
+/// exploded; the reverse is collected; fields at the same depth in differently
+/// named arrays are mapped element-wise. Explodes are limited to one array
+/// family so that each source record yields a single row stream, and collects
+/// to top-level target arrays.
+fn match_array_paths(
+    config: &MappingConfig,
+    source_fields: &HashMap<String, FieldInfo>,
+    target_fields: &HashMap<String, FieldInfo>,
+    matched_sources: &mut HashSet<String>,
+    matched_targets: &mut HashSet<String>,
+    mapping: &mut SchemaMapping,
+) {
+    let same = |a: &str, b: &str| {
+        if config.case_insensitive {
+            a.eq_ignore_ascii_case(b)
+        } else {
+            a == b
+        }
+    };
+
+    let mut target_paths: Vec<&String> = target_fields
+        .keys()
+        .filter(|t| !matched_targets.contains(*t))
+        .collect();
+    target_paths.sort();
+    let mut source_paths: Vec<&String> = source_fields
+        .keys()
+        .filter(|s| !matched_sources.contains(*s))
+        .collect();
+    source_paths.sort();
+
+    let mut explodes = Vec::new();
+    for target_path in target_paths {
+        let target_depth = array_depth(target_path);
+        let candidate = source_paths.iter().copied().find(|source_path| {
+            let restructured = match (array_depth(source_path), target_depth) {
+                (0, 0) => false,
+                (s, t) if s == t => array_prefix(source_path) != array_prefix(target_path),
+                (_, 0) => true,
+                // Collecting into nested arrays is not supported
+                (0, t) => t == 1,
+                _ => false,
+            };
+            restructured
+                && !matched_sources.contains(*source_path)
+                && same(element_path(source_path), element_path(target_path))
+        });
+        let Some(source_path) = candidate else {
+            continue;
+        };
+        matched_sources.insert(source_path.clone());
+        matched_targets.insert(target_path.clone());
+
+        if array_depth(source_path) == target_depth {
+            let type_compatible = types_compatible(
+                &source_fields[source_path].field_type,
+                &target_fields[target_path].field_type,
+            );
+            mapping.direct_mappings.push(
+                FieldMapping::new(source_path.clone(), target_path.clone())
+                    .with_confidence(0.9)
+                    .with_type_compatible(type_compatible)
+                    .with_match_method(MatchMethod::Fuzzy),
+            );
+        } else if target_depth == 0 {
+            explodes.push((source_path.clone(), target_path.clone()));
+        } else {
+            let array_path = array_prefix(target_path).unwrap_or_default().to_string();
+            mapping.transformations.push(
+                TransformMapping::new(
+                    vec![source_path.clone()],
+                    target_path.clone(),
+                    TransformType::Collect { array_path },
+                )
+                .with_confidence(0.85),
+            );
+        }
+    }
+
+    // Keep the explodes of the array with the most matches, plus those of its
+    // enclosing arrays, which repeat on every exploded row
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for (source_path, _) in &explodes {
+        *counts
+            .entry(array_prefix(source_path).unwrap_or_default())
+            .or_default() += 1;
+    }
+    let Some(driver) = counts
+        .iter()
+        .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+        .map(|(prefix, _)| prefix.to_string())
+    else {
+        return;
+    };
+    for (source_path, target_path) in explodes {
+        let array_path = array_prefix(&source_path).unwrap_or_default().to_string();
+        if !driver.starts_with(&array_path) {
+            matched_sources.remove(&source_path);
+            matched_targets.remove(&target_path);
+            continue;
         }
+        mapping.transformations.push(
+            TransformMapping::new(
+                vec![source_path],
+                target_path,
+                TransformType::Explode { array_path },
+            )
+            .with_confidence(0.85),
+        );
     }
 }
 
 /// Check if two types are directly compatible
-fn types_compatible(source: &str, target: &str) -> bool {
+pub(super) fn types_compatible(source: &str, target: &str) -> bool {
     if source == target {
         return true;
     }
@@ -342,7 +485,7 @@ fn types_compatible(source: &str, target: &str) -> bool {
 }
 
 /// Check if a type can be coerced to another
-fn can_coerce(from: &str, to: &str) -> bool {
+pub(super) fn can_coerce(from: &str, to: &str) -> bool {
     match (from, to) {
         // Numeric conversions
         ("string", "integer") => true,
@@ -402,7 +545,7 @@ fn levenshtein_distance(s1: &str, s2: &str) -> usize {
 }
 
 /// Calculate overall compatibility score
-fn calculate_compatibility_score(mapping: &SchemaMapping) -> f64 {
+pub(super) fn calculate_compatibility_score(mapping: &SchemaMapping) -> f64 {
     if mapping.stats.target_fields == 0 {
         return 1.0;
     }
@@ -427,7 +570,7 @@ fn calculate_compatibility_score(mapping: &SchemaMapping) -> f64 {
 }
 
 /// Suggest a default value for a type
-fn suggest_default(field_type: &str) -> Option<Value> {
+pub(super) fn suggest_default(field_type: &str) -> Option<Value> {
     match field_type {
         "string" => Some(Value::String(String::new())),
         "integer" => Some(Value::Number(0.into())),
@@ -666,4 +809,82 @@ mod tests {
         // Should match: user, user.name, user.email
         assert_eq!(result.direct_mappings.len(), 3);
     }
+
+    #[test]
+    fn test_array_path_matching() {
+        let source = json!({
+            "type": "object",
+            "properties": {
+                "order_id": {"type": "integer"},
+                "items": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "sku": {"type": "string"},
+                            "qty": {"type": "integer"}
+                        }
+                    }
+                }
+            }
+        });
+
+        // Flat target: array fields are exploded into rows
+        let flat = json!({
+            "type": "object",
+            "properties": {
+                "order_id": {"type": "integer"},
+                "sku": {"type": "string"},
+                "qty": {"type": "integer"}
+            }
+        });
+        let result = SchemaMatcher::new().match_schemas(&source, &flat).unwrap();
+        assert!(result.gaps.is_empty());
+        let explodes: Vec<_> = result
+            .transformations
+            .iter()
+            .filter(|t| {
+                t.transform_type
+                    == TransformType::Explode {
+                        array_path: "items[]".to_string(),
+                    }
+            })
+            .map(|t| t.source_paths[0].as_str())
+            .collect();
+        assert_eq!(explodes, vec!["items[].qty", "items[].sku"]);
+
+        // Differently named array: fields are mapped element-wise
+        let renamed = json!({
+            "type": "object",
+            "properties": {
+                "order_id": {"type": "integer"},
+                "lines": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {"sku": {"type": "string"}}
+                    }
+                }
+            }
+        });
+        let result = SchemaMatcher::new()
+            .match_schemas(&source, &renamed)
+            .unwrap();
+        assert!(
+            result
+                .direct_mappings
+                .iter()
+                .any(|m| m.source_path == "items[].sku" && m.target_path == "lines[].sku")
+        );
+
+        // Flat source into a target array: rows are collected
+        let result = SchemaMatcher::new().match_schemas(&flat, &source).unwrap();
+        assert!(result.transformations.iter().any(|t| {
+            t.target_path == "items[].sku"
+                && t.transform_type
+                    == TransformType::Collect {
+                        array_path: "items[]".to_string(),
+                    }
+        }));
+    }
 }
@@ -3,8 +3,12 @@
 //! This module provides functionality to:
 //! - Match fields between source and target JSON Schemas
 //! - Detect type mismatches and suggest transformations
-//! - Generate transformation scripts (SQL, JQ, Python, PySpark)
+//! - Generate transformation scripts (SQL, JQ, Python, PySpark, dbt)
+//! - Map arrays of objects element-wise, explode arrays to rows and collect rows into arrays
 //! - Identify gaps and unmapped fields
+//! - Execute mappings against JSON records and validate the output
+//! - Keep reviewed mappings as declarative YAML specification files
+//! - Match ODCS contracts on business metadata and record column lineage
 //!
 //! # Example
 //!
@@ -43,19 +47,31 @@
 //! ```
 
 mod config;
+mod contract;
 mod error;
+mod executor;
 mod generator;
 #[cfg(feature = "llm")]
 mod llm_matcher;
 mod matcher;
+mod path;
+mod spec;
 mod types;
 
 pub use config::{MappingConfig, TransformFormat};
+pub use contract::{ContractMapping, ContractMatcher, MatchEvidence, MatchSignal, apply_lineage};
 pub use error::{MappingError, MappingResult};
+pub use executor::{
+    ExecutionReport, ExecutionResult, FailureKind, FieldFailures, MappingExecutor, RecordError,
+};
 pub use generator::generate_transform;
 #[cfg(feature = "llm")]
 pub use llm_matcher::{LlmFieldSuggestion, LlmMatchResponse, LlmMatcherConfig, LlmSchemaMatcher};
 pub use matcher::SchemaMatcher;
+pub use spec::{
+    FieldSpec, IgnoredFields, MAPPING_SPEC_VERSION, MappingSpec, MergeSummary, SpecIssue,
+    SpecIssueSeverity,
+};
 pub use types::{
     FieldGap, FieldMapping, MappingStats, MatchMethod, SchemaMapping, TransformMapping,
     TransformType,
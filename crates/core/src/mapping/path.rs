@@ -0,0 +1,115 @@
+//! Field path helpers for array-aware mapping
+//!
+//! Paths are dotted, with `[]` marking iteration over the elements of an
+//! array: `items[].sku` is the `sku` field of every element of `items`, and
+//! `tags[]` is every element of a scalar array.
+
+use super::types::{TransformMapping, TransformType};
+
+/// Marker for iterating over array elements
+pub(super) const ARRAY_MARKER: &str = "[]";
+
+/// Check whether a path iterates over an array
+pub(super) fn is_array_path(path: &str) -> bool {
+    path.contains(ARRAY_MARKER)
+}
+
+/// Number of arrays a path iterates over
+pub(super) fn array_depth(path: &str) -> usize {
+    path.matches(ARRAY_MARKER).count()
+}
+
+/// Array portion of a path, up to and including the innermost marker
+///
+/// `a[].b[].c` gives `a[].b[]`; paths without arrays give `None`.
+pub(super) fn array_prefix(path: &str) -> Option<&str> {
+    path.rfind(ARRAY_MARKER)
+        .map(|i| &path[..i + ARRAY_MARKER.len()])
+}
+
+/// Path relative to the innermost array element
+///
+/// `items[].sku` gives `sku`, `tags[]` gives an empty path, and paths without
+/// arrays are returned unchanged.
+pub(super) fn element_path(path: &str) -> &str {
+    match path.rfind(ARRAY_MARKER) {
+        Some(i) => path[i + ARRAY_MARKER.len()..].trim_start_matches('.'),
+        None => path,
+    }
+}
+
+/// Split a path at its array markers
+///
+/// `a[].b[].c` gives `["a", "b", "c"]` and `tags[]` gives `["tags", ""]`.
+pub(super) fn array_segments(path: &str) -> Vec<&str> {
+    path.split(ARRAY_MARKER)
+        .map(|s| s.trim_start_matches('.'))
+        .collect()
+}
+
+/// Paths of the arrays a path iterates over, outermost first
+///
+/// `a[].b[].c` gives `["a", "a[].b"]`.
+pub(super) fn array_containers(path: &str) -> Vec<String> {
+    path.match_indices(ARRAY_MARKER)
+        .map(|(i, _)| path[..i].to_string())
+        .collect()
+}
+
+/// Append a child name to a path
+pub(super) fn child_path(parent: &str, name: &str) -> String {
+    if parent.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", parent, name)
+    }
+}
+
+/// Whether a transform applies to each element of a single-level array
+///
+/// All sources must sit in the same array, and the target in one array.
+pub(super) fn is_elementwise(transform: &TransformMapping) -> bool {
+    if matches!(
+        transform.transform_type,
+        TransformType::Explode { .. } | TransformType::Collect { .. }
+    ) {
+        return false;
+    }
+    let Some(prefix) = array_prefix(&transform.target_path) else {
+        return false;
+    };
+    let Some(first) = transform.source_paths.first() else {
+        return false;
+    };
+    array_depth(prefix) == 1
+        && transform
+            .source_paths
+            .iter()
+            .all(|p| array_depth(p) == 1 && array_prefix(p) == array_prefix(first))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_array_path_parts() {
+        assert!(is_array_path("items[].sku"));
+        assert!(!is_array_path("customer.name"));
+        assert_eq!(array_depth("a[].b[].c"), 2);
+
+        assert_eq!(array_prefix("items[].sku"), Some("items[]"));
+        assert_eq!(array_prefix("a[].b[].c"), Some("a[].b[]"));
+        assert_eq!(array_prefix("customer.name"), None);
+
+        assert_eq!(element_path("items[].product.sku"), "product.sku");
+        assert_eq!(element_path("tags[]"), "");
+        assert_eq!(element_path("customer.name"), "customer.name");
+
+        assert_eq!(array_segments("a[].b[].c"), vec!["a", "b", "c"]);
+        assert_eq!(array_segments("tags[]"), vec!["tags", ""]);
+
+        assert_eq!(array_containers("a[].b[].c"), vec!["a", "a[].b"]);
+        assert!(array_containers("customer.name").is_empty());
+    }
+}
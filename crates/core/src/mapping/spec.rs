@@ -0,0 +1,892 @@
+//! Declarative mapping specification files
+//!
+//! A [`MappingSpec`] is the version-controlled form of a [`SchemaMapping`].
+//! It is stored as YAML next to the ODCS files of a domain
+//! (`{workspace}_{domain}_{name}.mapping.yaml`) and records the decisions
+//! people made on top of matcher output: manual field mappings, ignored
+//! fields, custom expressions and a comment per field.
+//!
+//! ```yaml
+//! version: 1
+//! name: orders_to_warehouse
+//! domain: sales
+//! source: orders.schema.json
+//! target: fact_orders.schema.json
+//! fields:
+//!   - target: order_id
+//!     source: id
+//!     method: exact
+//!   - target: customer_name
+//!     source: [first_name, last_name]
+//!     transform:
+//!       type: merge
+//!       separator: " "
+//!     comment: Agreed with finance in ADR-0012
+//!   - target: region
+//!     source: region_code
+//!     expression: upper(region_code)
+//! ignore:
+//!   source: [internal_flag]
+//!   target: [legacy_code]
+//! ```
+//!
+//! Entries without a `method` are manual. Manual entries are never replaced
+//! when the spec is merged with fresh matcher output.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+use super::error::{MappingError, MappingResult};
+use super::matcher::{calculate_compatibility_score, extract_fields, suggest_default};
+use super::types::{
+    FieldGap, FieldMapping, MappingStats, MatchMethod, SchemaMapping, TransformMapping,
+    TransformType,
+};
+
+/// Current mapping specification format version
+pub const MAPPING_SPEC_VERSION: u32 = 1;
+
+/// A version-controlled mapping between a source and a target schema
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MappingSpec {
+    /// Format version
+    #[serde(default = "default_version")]
+    pub version: u32,
+    /// Mapping name
+    pub name: String,
+    /// Domain the mapping belongs to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+    /// Description of the mapping
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Source schema reference (file or contract name)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Target schema reference (file or contract name)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    /// Field mappings
+    #[serde(default)]
+    pub fields: Vec<FieldSpec>,
+    /// Fields excluded from mapping
+    #[serde(default, skip_serializing_if = "IgnoredFields::is_empty")]
+    pub ignore: IgnoredFields,
+}
+
+/// A single target field in a mapping specification
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldSpec {
+    /// Target field path
+    pub target: String,
+    /// Source field path(s)
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "serialize_paths",
+        deserialize_with = "deserialize_paths"
+    )]
+    pub source: Vec<String>,
+    /// Transformation applied to the source value(s)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transform: Option<TransformType>,
+    /// Custom expression (shorthand for a `custom` transform)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expression: Option<String>,
+    /// How the mapping was determined
+    #[serde(default = "default_method")]
+    pub method: MatchMethod,
+    /// Confidence score (0.0-1.0)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f64>,
+    /// Whether source and target types are directly compatible
+    #[serde(default = "default_true", skip_serializing_if = "is_true")]
+    pub type_compatible: bool,
+    /// Reviewer comment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+impl FieldSpec {
+    /// Create a manual direct mapping
+    pub fn new(source: impl Into<String>, target: impl Into<String>) -> Self {
+        Self {
+            target: target.into(),
+            source: vec![source.into()],
+            transform: None,
+            expression: None,
+            method: MatchMethod::Manual,
+            confidence: None,
+            type_compatible: true,
+            comment: None,
+        }
+    }
+
+    /// Set the transformation
+    pub fn with_transform(mut self, transform: TransformType) -> Self {
+        self.transform = Some(transform);
+        self
+    }
+
+    /// Set a custom expression
+    pub fn with_expression(mut self, expression: impl Into<String>) -> Self {
+        self.expression = Some(expression.into());
+        self
+    }
+
+    /// Set the match method
+    pub fn with_method(mut self, method: MatchMethod) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Set a reviewer comment
+    pub fn with_comment(mut self, comment: impl Into<String>) -> Self {
+        self.comment = Some(comment.into());
+        self
+    }
+
+    /// Check whether this entry is a human decision
+    pub fn is_manual(&self) -> bool {
+        self.method == MatchMethod::Manual
+    }
+
+    /// The effective transformation, with `expression` as a custom transform
+    pub fn effective_transform(&self) -> Option<TransformType> {
+        match (&self.transform, &self.expression) {
+            (Some(transform), _) => Some(transform.clone()),
+            (None, Some(expression)) => Some(TransformType::Custom {
+                expression: expression.clone(),
+            }),
+            (None, None) => None,
+        }
+    }
+
+    /// All target paths written by this entry
+    pub fn target_paths(&self) -> Vec<&str> {
+        match &self.transform {
+            Some(TransformType::Split { target_paths, .. }) if !target_paths.is_empty() => {
+                target_paths.iter().map(String::as_str).collect()
+            }
+            _ => vec![self.target.as_str()],
+        }
+    }
+}
+
+/// Source and target fields excluded from mapping
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IgnoredFields {
+    /// Source fields that are intentionally not mapped
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub source: Vec<String>,
+    /// Target fields that are intentionally left empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub target: Vec<String>,
+}
+
+impl IgnoredFields {
+    /// Check if nothing is ignored
+    pub fn is_empty(&self) -> bool {
+        self.source.is_empty() && self.target.is_empty()
+    }
+}
+
+/// Changes made when merging matcher output into a specification
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MergeSummary {
+    /// Target fields newly mapped by the matcher
+    pub added: Vec<String>,
+    /// Target fields whose generated mapping changed
+    pub updated: Vec<String>,
+    /// Target fields whose generated mapping no longer applies
+    pub removed: Vec<String>,
+    /// Target fields kept because they are manual
+    pub preserved: Vec<String>,
+}
+
+impl MergeSummary {
+    /// Check if the merge changed any generated entries
+    pub fn has_changes(&self) -> bool {
+        !(self.added.is_empty() && self.updated.is_empty() && self.removed.is_empty())
+    }
+}
+
+/// Severity of a specification issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpecIssueSeverity {
+    /// The specification cannot be applied as written
+    Error,
+    /// The specification is applicable but likely stale or incomplete
+    Warning,
+}
+
+/// A problem found when validating a specification against schemas
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpecIssue {
+    /// Issue severity
+    pub severity: SpecIssueSeverity,
+    /// Field path the issue refers to
+    pub field: String,
+    /// Issue description
+    pub message: String,
+}
+
+impl SpecIssue {
+    fn error(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: SpecIssueSeverity::Error,
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+
+    fn warning(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: SpecIssueSeverity::Warning,
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for SpecIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let severity = match self.severity {
+            SpecIssueSeverity::Error => "error",
+            SpecIssueSeverity::Warning => "warning",
+        };
+        write!(f, "{}: {}: {}", severity, self.field, self.message)
+    }
+}
+
+impl MappingSpec {
+    /// Create an empty specification
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            version: MAPPING_SPEC_VERSION,
+            name: name.into(),
+            domain: None,
+            description: None,
+            source: None,
+            target: None,
+            fields: Vec::new(),
+            ignore: IgnoredFields::default(),
+        }
+    }
+
+    /// Set the domain
+    pub fn with_domain(mut self, domain: impl Into<String>) -> Self {
+        self.domain = Some(domain.into());
+        self
+    }
+
+    /// Set the description
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the source and target schema references
+    pub fn with_schemas(mut self, source: impl Into<String>, target: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self.target = Some(target.into());
+        self
+    }
+
+    /// Build a specification from matcher output
+    ///
+    /// Transformations carry no match method, so they are recorded as
+    /// `semantic` to keep them distinct from manual entries.
+    pub fn from_mapping(name: impl Into<String>, mapping: &SchemaMapping) -> Self {
+        let mut spec = Self::new(name);
+        spec.fields = fields_from_mapping(mapping);
+        spec
+    }
+
+    /// Parse a specification from YAML
+    pub fn from_yaml(yaml: &str) -> MappingResult<Self> {
+        let spec: Self = serde_yaml::from_str(yaml)
+            .map_err(|e| MappingError::InvalidSpec(format!("Failed to parse YAML: {}", e)))?;
+        if spec.version > MAPPING_SPEC_VERSION {
+            return Err(MappingError::InvalidSpec(format!(
+                "Unsupported version {} (latest supported is {})",
+                spec.version, MAPPING_SPEC_VERSION
+            )));
+        }
+        Ok(spec)
+    }
+
+    /// Serialize the specification to YAML
+    pub fn to_yaml(&self) -> MappingResult<String> {
+        serde_yaml::to_string(self)
+            .map_err(|e| MappingError::InvalidSpec(format!("Failed to serialize YAML: {}", e)))
+    }
+
+    /// Workspace file name for this specification
+    ///
+    /// `{workspace}_{domain}_{name}.mapping.yaml`, or
+    /// `{workspace}_{name}.mapping.yaml` when no domain is set.
+    pub fn file_name(&self, workspace_name: &str) -> String {
+        let sanitize = |s: &str| s.to_lowercase().replace([' ', '/', '\\'], "_");
+        match &self.domain {
+            Some(domain) => format!(
+                "{}_{}_{}.mapping.yaml",
+                sanitize(workspace_name),
+                sanitize(domain),
+                sanitize(&self.name)
+            ),
+            None => format!(
+                "{}_{}.mapping.yaml",
+                sanitize(workspace_name),
+                sanitize(&self.name)
+            ),
+        }
+    }
+
+    /// Find the entry for a target field
+    pub fn field(&self, target: &str) -> Option<&FieldSpec> {
+        self.fields.iter().find(|f| f.target == target)
+    }
+
+    /// Merge fresh matcher output into the specification
+    ///
+    /// Manual entries are kept as-is, ignored fields are never re-added, and
+    /// generated entries are replaced by (or removed in favour of) the fresh
+    /// matcher result for the same target field.
+    pub fn merge(&mut self, fresh: &SchemaMapping) -> MergeSummary {
+        let ignored_sources: HashSet<&str> =
+            self.ignore.source.iter().map(String::as_str).collect();
+        let ignored_targets: HashSet<&str> =
+            self.ignore.target.iter().map(String::as_str).collect();
+        let mut manual_targets: HashSet<String> = HashSet::new();
+        let mut manual_sources: HashSet<String> = HashSet::new();
+        for field in self.fields.iter().filter(|f| f.is_manual()) {
+            manual_targets.extend(field.target_paths().into_iter().map(String::from));
+            manual_sources.extend(field.source.iter().cloned());
+        }
+
+        let mut candidates: Vec<FieldSpec> = fields_from_mapping(fresh)
+            .into_iter()
+            .filter(|f| {
+                f.target_paths()
+                    .iter()
+                    .all(|t| !ignored_targets.contains(t) && !manual_targets.contains(*t))
+                    && f.source.iter().all(|s| {
+                        !ignored_sources.contains(s.as_str()) && !manual_sources.contains(s)
+                    })
+            })
+            .collect();
+
+        let mut summary = MergeSummary::default();
+        let mut merged = Vec::with_capacity(self.fields.len());
+        for existing in self.fields.drain(..) {
+            if existing.is_manual() {
+                summary.preserved.push(existing.target.clone());
+                merged.push(existing);
+            } else if let Some(pos) = candidates.iter().position(|c| c.target == existing.target) {
+                let mut candidate = candidates.remove(pos);
+                // Reviewer comments survive regeneration
+                if candidate.comment.is_none() {
+                    candidate.comment = existing.comment.clone();
+                }
+                if candidate != existing {
+                    summary.updated.push(candidate.target.clone());
+                }
+                merged.push(candidate);
+            } else {
+                summary.removed.push(existing.target);
+            }
+        }
+
+        for candidate in candidates {
+            summary.added.push(candidate.target.clone());
+            merged.push(candidate);
+        }
+
+        self.fields = merged;
+        summary
+    }
+
+    /// Convert the specification into a [`SchemaMapping`]
+    ///
+    /// Gaps and extras are not known without the schemas; use
+    /// [`MappingSpec::resolve`] to compute them.
+    pub fn to_mapping(&self) -> SchemaMapping {
+        let mut mapping = SchemaMapping::empty();
+
+        for field in &self.fields {
+            let confidence = field.confidence.unwrap_or(1.0);
+            match field.effective_transform() {
+                None if field.source.len() == 1 => {
+                    mapping.direct_mappings.push(
+                        FieldMapping::new(field.source[0].clone(), field.target.clone())
+                            .with_confidence(confidence)
+                            .with_type_compatible(field.type_compatible)
+                            .with_match_method(field.method),
+                    );
+                }
+                transform => {
+                    let transform = transform.unwrap_or(TransformType::Rename);
+                    let mut t = TransformMapping::new(
+                        field.source.clone(),
+                        field.target.clone(),
+                        transform,
+                    )
+                    .with_confidence(confidence);
+                    if let Some(comment) = &field.comment {
+                        t = t.with_description(comment.clone());
+                    }
+                    mapping.transformations.push(t);
+                }
+            }
+        }
+
+        let sources: HashSet<&str> = mapping.mapped_sources().into_iter().collect();
+        let targets: HashSet<&str> = self.fields.iter().flat_map(|f| f.target_paths()).collect();
+        mapping.stats = MappingStats {
+            source_fields: sources.len() + self.ignore.source.len(),
+            target_fields: targets.len() + self.ignore.target.len(),
+            direct_mapped: mapping.direct_mappings.len(),
+            transform_mapped: mapping.transformations.len(),
+            ..Default::default()
+        };
+        mapping.compatibility_score = calculate_compatibility_score(&mapping);
+        mapping
+    }
+
+    /// Merge fresh matcher output and compute gaps and extras from the schemas
+    ///
+    /// The specification itself is not modified.
+    pub fn resolve(
+        &self,
+        fresh: &SchemaMapping,
+        source_schema: &Value,
+        target_schema: &Value,
+    ) -> MappingResult<SchemaMapping> {
+        let source_fields = extract_fields(source_schema)?;
+        let target_fields = extract_fields(target_schema)?;
+
+        let mut spec = self.clone();
+        spec.merge(fresh);
+        let mut mapping = spec.to_mapping();
+
+        let mapped_targets: HashSet<&str> =
+            spec.fields.iter().flat_map(|f| f.target_paths()).collect();
+        let used_sources: HashSet<&str> = spec
+            .fields
+            .iter()
+            .flat_map(|f| f.source.iter().map(String::as_str))
+            .collect();
+        let fresh_gaps: HashMap<&str, &FieldGap> = fresh
+            .gaps
+            .iter()
+            .map(|g| (g.target_path.as_str(), g))
+            .collect();
+
+        let mut target_paths: Vec<&String> = target_fields.keys().collect();
+        target_paths.sort();
+        for path in target_paths {
+            if mapped_targets.contains(path.as_str())
+                || spec.ignore.target.contains(path)
+                || is_nested_under(path, &mapped_targets)
+            {
+                continue;
+            }
+            let gap = match fresh_gaps.get(path.as_str()) {
+                Some(gap) => (*gap).clone(),
+                None => {
+                    let info = &target_fields[path];
+                    let mut gap =
+                        FieldGap::new(path.clone(), info.field_type.clone(), info.required);
+                    gap.suggested_default = suggest_default(&info.field_type);
+                    gap
+                }
+            };
+            mapping.gaps.push(gap);
+        }
+
+        let mut extras: Vec<String> = source_fields
+            .keys()
+            .filter(|p| {
+                !used_sources.contains(p.as_str())
+                    && !spec.ignore.source.contains(p)
+                    && !is_nested_under(p, &used_sources)
+            })
+            .cloned()
+            .collect();
+        extras.sort();
+        mapping.extras = extras;
+
+        mapping.stats = MappingStats {
+            source_fields: source_fields.len(),
+            target_fields: target_fields.len(),
+            direct_mapped: mapping.direct_mappings.len(),
+            transform_mapped: mapping.transformations.len(),
+            gaps_count: mapping.gaps.len(),
+            required_gaps: mapping.gaps.iter().filter(|g| g.required).count(),
+            extras_count: mapping.extras.len(),
+        };
+        mapping.compatibility_score = calculate_compatibility_score(&mapping);
+
+        Ok(mapping)
+    }
+
+    /// Validate the specification against the current source and target schemas
+    pub fn validate(
+        &self,
+        source_schema: &Value,
+        target_schema: &Value,
+    ) -> MappingResult<Vec<SpecIssue>> {
+        let source_fields = extract_fields(source_schema)?;
+        let target_fields = extract_fields(target_schema)?;
+        let mut issues = Vec::new();
+        let mut seen_targets: HashSet<&str> = HashSet::new();
+
+        for field in &self.fields {
+            for target in field.target_paths() {
+                if !seen_targets.insert(target) {
+                    issues.push(SpecIssue::error(target, "mapped more than once"));
+                }
+                if !target_fields.contains_key(target) {
+                    issues.push(SpecIssue::error(target, "not found in target schema"));
+                }
+                if self.ignore.target.iter().any(|t| t == target) {
+                    issues.push(SpecIssue::warning(
+                        target,
+                        "mapped but also listed as ignored",
+                    ));
+                }
+            }
+
+            for source in &field.source {
+                if !source_fields.contains_key(source) {
+                    issues.push(SpecIssue::error(
+                        &field.target,
+                        format!("source field '{}' not found in source schema", source),
+                    ));
+                }
+                if self.ignore.source.contains(source) {
+                    issues.push(SpecIssue::warning(
+                        &field.target,
+                        format!("source field '{}' is also listed as ignored", source),
+                    ));
+                }
+            }
+
+            if field.transform.is_some() && field.expression.is_some() {
+                issues.push(SpecIssue::error(
+                    &field.target,
+                    "has both a transform and an expression",
+                ));
+            }
+
+            match field.effective_transform() {
+                Some(TransformType::Default { .. }) => {}
+                Some(TransformType::Merge { .. }) if field.source.len() < 2 => {
+                    issues.push(SpecIssue::warning(
+                        &field.target,
+                        "merge with a single source",
+                    ));
+                }
+                Some(TransformType::Merge { .. }) => {}
+                _ if field.source.is_empty() => {
+                    issues.push(SpecIssue::error(&field.target, "no source field"));
+                }
+                _ if field.source.len() > 1 => {
+                    issues.push(SpecIssue::error(
+                        &field.target,
+                        "multiple source fields require a merge transform",
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        for source in &self.ignore.source {
+            if !source_fields.contains_key(source) {
+                issues.push(SpecIssue::warning(
+                    source,
+                    "ignored source field not found in source schema",
+                ));
+            }
+        }
+        for target in &self.ignore.target {
+            match target_fields.get(target) {
+                None => issues.push(SpecIssue::warning(
+                    target,
+                    "ignored target field not found in target schema",
+                )),
+                Some(info) if info.required => issues.push(SpecIssue::warning(
+                    target,
+                    "required target field is ignored",
+                )),
+                Some(_) => {}
+            }
+        }
+
+        Ok(issues)
+    }
+}
+
+/// Check if `path` is a child of one of the given parent paths
+fn is_nested_under(path: &str, parents: &HashSet<&str>) -> bool {
+    parents.iter().any(|parent| {
+        path.len() > parent.len()
+            && path.starts_with(parent)
+            && path[parent.len()..].starts_with('.')
+    })
+}
+
+fn fields_from_mapping(mapping: &SchemaMapping) -> Vec<FieldSpec> {
+    let mut fields: Vec<FieldSpec> = mapping
+        .direct_mappings
+        .iter()
+        .map(|m| FieldSpec {
+            target: m.target_path.clone(),
+            source: vec![m.source_path.clone()],
+            transform: None,
+            expression: None,
+            method: m.match_method,
+            confidence: Some(m.confidence),
+            type_compatible: m.type_compatible,
+            comment: None,
+        })
+        .collect();
+
+    fields.extend(mapping.transformations.iter().map(|t| {
+        let (transform, expression) = match &t.transform_type {
+            TransformType::Custom { expression } => (None, Some(expression.clone())),
+            other => (Some(other.clone()), None),
+        };
+        let comment = (t.description != t.transform_type.describe()).then(|| t.description.clone());
+        FieldSpec {
+            target: t.target_path.clone(),
+            source: t.source_paths.clone(),
+            transform,
+            expression,
+            method: MatchMethod::Semantic,
+            confidence: Some(t.confidence),
+            type_compatible: true,
+            comment,
+        }
+    }));
+
+    fields.sort_by(|a, b| a.target.cmp(&b.target));
+    fields
+}
+
+fn default_version() -> u32 {
+    MAPPING_SPEC_VERSION
+}
+
+fn default_method() -> MatchMethod {
+    MatchMethod::Manual
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn is_true(value: &bool) -> bool {
+    *value
+}
+
+/// Write a single source path as a plain string and several as a list
+fn serialize_paths<S: Serializer>(paths: &[String], serializer: S) -> Result<S::Ok, S::Error> {
+    match paths {
+        [single] => serializer.serialize_str(single),
+        _ => paths.serialize(serializer),
+    }
+}
+
+fn deserialize_paths<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(path) => vec![path],
+        OneOrMany::Many(paths) => paths,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mapping::SchemaMatcher;
+    use serde_json::json;
+
+    const SPEC: &str = r#"
+version: 1
+name: customers
+domain: sales
+fields:
+  - target: email
+    source: email_address
+    comment: Verified with the CRM team
+  - target: full_name
+    source: [first_name, last_name]
+    transform:
+      type: merge
+      separator: " "
+  - target: region
+    source: region_code
+    expression: upper(region_code)
+  - target: id
+    source: id
+    method: exact
+    confidence: 1.0
+ignore:
+  source: [internal_flag]
+  target: [legacy_code]
+"#;
+
+    fn source_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "id": {"type": "string"},
+                "email_address": {"type": "string"},
+                "first_name": {"type": "string"},
+                "last_name": {"type": "string"},
+                "region_code": {"type": "string"},
+                "internal_flag": {"type": "boolean"},
+                "email": {"type": "string"}
+            }
+        })
+    }
+
+    fn target_schema() -> Value {
+        json!({
+            "type": "object",
+            "required": ["id", "status"],
+            "properties": {
+                "id": {"type": "string"},
+                "email": {"type": "string"},
+                "full_name": {"type": "string"},
+                "region": {"type": "string"},
+                "legacy_code": {"type": "string"},
+                "status": {"type": "string"}
+            }
+        })
+    }
+
+    #[test]
+    fn test_yaml_round_trip() {
+        let spec = MappingSpec::from_yaml(SPEC).unwrap();
+        assert_eq!(spec.fields.len(), 4);
+        assert!(spec.fields[0].is_manual());
+        assert_eq!(spec.fields[1].source, vec!["first_name", "last_name"]);
+        assert_eq!(spec.fields[3].method, MatchMethod::Exact);
+        assert_eq!(spec.ignore.target, vec!["legacy_code"]);
+
+        let yaml = spec.to_yaml().unwrap();
+        assert!(yaml.contains("source: email_address"));
+        assert!(!yaml.contains("type_compatible"));
+        assert_eq!(MappingSpec::from_yaml(&yaml).unwrap(), spec);
+
+        assert_eq!(spec.file_name("Acme"), "acme_sales_customers.mapping.yaml");
+    }
+
+    #[test]
+    fn test_unsupported_version_rejected() {
+        let err = MappingSpec::from_yaml("version: 99\nname: x\n").unwrap_err();
+        assert!(err.to_string().contains("Unsupported version"));
+    }
+
+    #[test]
+    fn test_merge_keeps_manual_decisions() {
+        let mut spec = MappingSpec::from_yaml(SPEC).unwrap();
+        let fresh = SchemaMatcher::new()
+            .match_schemas(&source_schema(), &target_schema())
+            .unwrap();
+
+        let summary = spec.merge(&fresh);
+
+        // The matcher maps email -> email exactly, but the manual entry wins
+        assert_eq!(spec.field("email").unwrap().source, vec!["email_address"]);
+        assert!(summary.preserved.contains(&"email".to_string()));
+        // Generated entries are refreshed rather than duplicated
+        assert_eq!(spec.fields.iter().filter(|f| f.target == "id").count(), 1);
+        // Ignored fields are never re-added
+        assert!(spec.field("legacy_code").is_none());
+        assert!(
+            spec.fields
+                .iter()
+                .all(|f| !f.source.contains(&"internal_flag".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_resolve_computes_gaps_and_extras() {
+        let spec = MappingSpec::from_yaml(SPEC).unwrap();
+        let fresh = SchemaMatcher::new()
+            .match_schemas(&source_schema(), &target_schema())
+            .unwrap();
+
+        let mapping = spec
+            .resolve(&fresh, &source_schema(), &target_schema())
+            .unwrap();
+
+        let gaps: Vec<&str> = mapping
+            .gaps
+            .iter()
+            .map(|g| g.target_path.as_str())
+            .collect();
+        assert_eq!(gaps, vec!["status"]);
+        assert_eq!(mapping.stats.required_gaps, 1);
+        // The unused exact-match source is reported; ignored sources are not
+        assert_eq!(mapping.extras, vec!["email"]);
+        assert!(mapping.transformations.iter().any(|t| t.transform_type
+            == TransformType::Custom {
+                expression: "upper(region_code)".to_string()
+            }));
+    }
+
+    #[test]
+    fn test_validate_against_schemas() {
+        let mut spec = MappingSpec::from_yaml(SPEC).unwrap();
+        assert!(
+            spec.validate(&source_schema(), &target_schema())
+                .unwrap()
+                .is_empty()
+        );
+
+        spec.fields.push(FieldSpec::new("phone", "telephone"));
+        spec.fields.push(FieldSpec::new("id", "email"));
+        spec.ignore.target.push("status".to_string());
+
+        let issues = spec.validate(&source_schema(), &target_schema()).unwrap();
+        let errors: Vec<String> = issues
+            .iter()
+            .filter(|i| i.severity == SpecIssueSeverity::Error)
+            .map(|i| i.to_string())
+            .collect();
+        assert!(
+            errors
+                .iter()
+                .any(|e| e.contains("telephone: not found in target schema"))
+        );
+        assert!(
+            errors
+                .iter()
+                .any(|e| e.contains("'phone' not found in source schema"))
+        );
+        assert!(
+            errors
+                .iter()
+                .any(|e| e.contains("email: mapped more than once"))
+        );
+        assert!(
+            issues
+                .iter()
+                .any(|i| i.field == "status" && i.message.contains("required"))
+        );
+    }
+}
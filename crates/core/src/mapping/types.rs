@@ -206,6 +206,16 @@ pub enum TransformType {
     Extract { json_path: String },
     /// Default value for missing field
     Default { value: serde_json::Value },
+    /// One output row per element of a source array (array-to-row)
+    ///
+    /// `array_path` is the exploded array, e.g. `items[]`; the source path
+    /// addresses a field of each element, e.g. `items[].sku`.
+    Explode { array_path: String },
+    /// Aggregate rows into a target array (row-to-array)
+    ///
+    /// `array_path` is the target array, e.g. `lines[]`. Rows are grouped by
+    /// all other mapped target fields.
+    Collect { array_path: String },
 }
 
 impl TransformType {
@@ -238,6 +248,12 @@ impl TransformType {
             TransformType::Default { value } => {
                 format!("Default value: {}", value)
             }
+            TransformType::Explode { array_path } => {
+                format!("Explode array {} into rows", array_path)
+            }
+            TransformType::Collect { array_path } => {
+                format!("Collect rows into array {}", array_path)
+            }
         }
     }
 }
@@ -25,6 +25,8 @@ use crate::import::knowledge::KnowledgeImporter;
 #[cfg(feature = "openapi")]
 use crate::import::openapi::OpenAPIImporter;
 use crate::import::{cads::CADSImporter, odcs::ODCSImporter, odps::ODPSImporter};
+#[cfg(feature = "mapping")]
+use crate::mapping::MappingSpec;
 #[cfg(feature = "bpmn")]
 use crate::models::bpmn::BPMNModel;
 use crate::models::decision::{Decision, DecisionIndex};
@@ -1191,6 +1193,89 @@ impl<B: StorageBackend> ModelLoader<B> {
         })
     }
 
+    // ==================== Mapping Specification Loading ====================
+
+    /// Load all mapping specifications from workspace using flat file structure
+    ///
+    /// Loads all `.mapping.yaml` files from the workspace directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace_path` - Path to the workspace directory
+    ///
+    /// # Returns
+    ///
+    /// A MappingSpecLoadResult containing loaded specifications and any errors encountered
+    #[cfg(feature = "mapping")]
+    pub async fn load_mapping_specs(
+        &self,
+        workspace_path: &str,
+    ) -> Result<MappingSpecLoadResult, StorageError> {
+        let mut specs = Vec::new();
+        let mut load_errors = Vec::new();
+
+        let files = self.storage.list_files(workspace_path).await?;
+
+        for file_name in files {
+            if let Some(AssetType::Mapping) = AssetType::from_filename(&file_name) {
+                let file_path = format!("{}/{}", workspace_path, file_name);
+                let result = match self.storage.read_file(&file_path).await {
+                    Ok(content) => String::from_utf8(content)
+                        .map_err(|e| format!("Invalid UTF-8: {}", e))
+                        .and_then(|yaml| MappingSpec::from_yaml(&yaml).map_err(|e| e.to_string())),
+                    Err(e) => Err(format!("Failed to read file: {}", e)),
+                };
+
+                match result {
+                    Ok(spec) => specs.push(spec),
+                    Err(error) => load_errors.push(MappingSpecLoadError { file_path, error }),
+                }
+            }
+        }
+
+        info!(
+            "Loaded {} mapping specifications ({} errors) from workspace {}",
+            specs.len(),
+            load_errors.len(),
+            workspace_path
+        );
+
+        Ok(MappingSpecLoadResult {
+            specs,
+            errors: load_errors,
+        })
+    }
+
+    /// Load mapping specifications by domain
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace_path` - Path to the workspace directory
+    /// * `domain` - Domain name to filter by
+    ///
+    /// # Returns
+    ///
+    /// A MappingSpecLoadResult containing matching specifications and any errors encountered
+    #[cfg(feature = "mapping")]
+    pub async fn load_mapping_specs_by_domain(
+        &self,
+        workspace_path: &str,
+        domain: &str,
+    ) -> Result<MappingSpecLoadResult, StorageError> {
+        let result = self.load_mapping_specs(workspace_path).await?;
+
+        let filtered_specs: Vec<_> = result
+            .specs
+            .into_iter()
+            .filter(|spec| spec.domain.as_deref() == Some(domain))
+            .collect();
+
+        Ok(MappingSpecLoadResult {
+            specs: filtered_specs,
+            errors: result.errors,
+        })
+    }
+
     // ==================== Workspace and Domain Config Loading ====================
 
     /// Load workspace configuration from workspace.yaml
@@ -1439,3 +1524,23 @@ pub struct KnowledgeLoadError {
     /// Error message
     pub error: String,
 }
+
+/// Result of loading mapping specifications
+#[cfg(feature = "mapping")]
+#[derive(Debug)]
+pub struct MappingSpecLoadResult {
+    /// Successfully loaded mapping specifications
+    pub specs: Vec<MappingSpec>,
+    /// Errors encountered during loading
+    pub errors: Vec<MappingSpecLoadError>,
+}
+
+/// Error encountered while loading a mapping specification
+#[cfg(feature = "mapping")]
+#[derive(Debug, Clone)]
+pub struct MappingSpecLoadError {
+    /// Path to the file that failed to load
+    pub file_path: String,
+    /// Error message
+    pub error: String,
+}
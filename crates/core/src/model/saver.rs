@@ -15,6 +15,8 @@ use crate::export::{
     cads::CADSExporter, decision::DecisionExporter, knowledge::KnowledgeExporter,
     markdown::MarkdownExporter, odcs::ODCSExporter, odps::ODPSExporter,
 };
+#[cfg(feature = "mapping")]
+use crate::mapping::MappingSpec;
 #[cfg(feature = "bpmn")]
 use crate::models::bpmn::BPMNModel;
 use crate::models::decision::{Decision, DecisionIndex};
@@ -531,6 +533,39 @@ impl<B: StorageBackend> ModelSaver<B> {
         Ok(())
     }
 
+    /// Save a mapping specification to storage
+    ///
+    /// Saves the specification next to the domain's ODCS files using the
+    /// naming convention `{workspace}_{domain}_{name}.mapping.yaml`
+    /// (or `{workspace}_{name}.mapping.yaml` without domain)
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace_path` - Path to the workspace directory
+    /// * `workspace_name` - Name of the workspace for filename generation
+    /// * `spec` - The mapping specification to save
+    #[cfg(feature = "mapping")]
+    pub async fn save_mapping_spec(
+        &self,
+        workspace_path: &str,
+        workspace_name: &str,
+        spec: &MappingSpec,
+    ) -> Result<String, StorageError> {
+        let file_path = format!("{}/{}", workspace_path, spec.file_name(workspace_name));
+
+        let yaml_content = spec.to_yaml().map_err(|e| {
+            StorageError::SerializationError(format!("Failed to export mapping spec: {}", e))
+        })?;
+
+        self.storage
+            .write_file(&file_path, yaml_content.as_bytes())
+            .await?;
+
+        info!("Saved mapping spec '{}' to {}", spec.name, file_path);
+
+        Ok(file_path)
+    }
+
     /// Export a decision to Markdown
     ///
     /// Saves the decision as a Markdown file in the decisions/ subdirectory
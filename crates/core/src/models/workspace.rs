@@ -68,6 +68,8 @@ pub enum AssetType {
     Decision,
     /// Knowledge base article
     Knowledge,
+    /// Schema mapping specification
+    Mapping,
     /// Decision log index file
     DecisionIndex,
     /// Knowledge base index file
@@ -88,6 +90,7 @@ impl AssetType {
             AssetType::Openapi => "openapi.yaml",
             AssetType::Decision => "madr.yaml",
             AssetType::Knowledge => "kb.yaml",
+            AssetType::Mapping => "mapping.yaml",
             AssetType::DecisionIndex => "yaml",
             AssetType::KnowledgeIndex => "yaml",
         }
@@ -135,6 +138,8 @@ impl AssetType {
             Some(AssetType::Decision)
         } else if filename.ends_with(".kb.yaml") {
             Some(AssetType::Knowledge)
+        } else if filename.ends_with(".mapping.yaml") {
+            Some(AssetType::Mapping)
         } else if filename.ends_with(".bpmn.xml") {
             Some(AssetType::Bpmn)
         } else if filename.ends_with(".dmn.xml") {
@@ -158,6 +163,7 @@ impl AssetType {
             ".cads.yaml",
             ".madr.yaml",
             ".kb.yaml",
+            ".mapping.yaml",
             ".bpmn.xml",
             ".dmn.xml",
             ".openapi.yaml",
@@ -802,6 +808,10 @@ mod tests {
             AssetType::from_filename("test.openapi.json"),
             Some(AssetType::Openapi)
         );
+        assert_eq!(
+            AssetType::from_filename("acme_sales_orders.mapping.yaml"),
+            Some(AssetType::Mapping)
+        );
         assert_eq!(AssetType::from_filename("random.txt"), None);
         assert_eq!(AssetType::from_filename("test.yaml"), None);
     }
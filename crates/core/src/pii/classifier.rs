@@ -0,0 +1,440 @@
+//! PII classifier
+//!
+//! Matches columns against the configured rules using three kinds of evidence:
+//! the column name, the detected format (`logicalTypeOptions.format`) and the
+//! sampled example values.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use super::config::{Checksum, PiiConfig, PiiRule, SensitivityLevel};
+use super::error::{PiiError, PiiResult};
+use super::report::PiiReport;
+use crate::models::{Column, Table};
+
+/// Custom property recording the detected PII category on a column
+pub const PII_CATEGORY_PROPERTY: &str = "piiCategory";
+
+/// Custom property recording why a column was classified
+pub const PII_REASON_PROPERTY: &str = "piiReason";
+
+/// A PII match for a single column
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PiiFinding {
+    /// Table name
+    pub table: String,
+    /// Column name
+    pub column: String,
+    /// Matching rule name (PII category)
+    pub category: String,
+    /// Assigned sensitivity level
+    pub level: SensitivityLevel,
+    /// Evidence for the match
+    pub reasons: Vec<String>,
+}
+
+struct CompiledRule {
+    rule: PiiRule,
+    name_patterns: Vec<Regex>,
+    value_patterns: Vec<Regex>,
+}
+
+/// Classifies columns by sensitivity
+pub struct PiiClassifier {
+    config: PiiConfig,
+    rules: Vec<CompiledRule>,
+}
+
+impl PiiClassifier {
+    /// Create a classifier with the built-in rules
+    pub fn new() -> Self {
+        Self::with_config(PiiConfig::default()).expect("built-in PII rules are valid")
+    }
+
+    /// Create a classifier from a configuration, compiling its patterns
+    pub fn with_config(config: PiiConfig) -> PiiResult<Self> {
+        let compile = |rule: &PiiRule, patterns: &[String]| -> PiiResult<Vec<Regex>> {
+            patterns
+                .iter()
+                .map(|p| {
+                    Regex::new(p).map_err(|e| PiiError::InvalidPattern {
+                        rule: rule.name.clone(),
+                        message: e.to_string(),
+                    })
+                })
+                .collect()
+        };
+
+        let rules = config
+            .effective_rules()
+            .into_iter()
+            .map(|rule| {
+                Ok(CompiledRule {
+                    name_patterns: compile(&rule, &rule.name_patterns)?,
+                    value_patterns: compile(&rule, &rule.value_patterns)?,
+                    rule,
+                })
+            })
+            .collect::<PiiResult<Vec<_>>>()?;
+
+        Ok(Self { config, rules })
+    }
+
+    /// Classifier configuration
+    pub fn config(&self) -> &PiiConfig {
+        &self.config
+    }
+
+    /// Classify a column by name and sampled values
+    ///
+    /// Returns the matching rule with the highest level, or `None`.
+    pub fn classify(
+        &self,
+        column_name: &str,
+        format: Option<&str>,
+        values: &[Value],
+    ) -> Option<(&PiiRule, Vec<String>)> {
+        let name = normalize_name(column_name);
+        let strings: Vec<String> = values
+            .iter()
+            .filter_map(|v| match v {
+                Value::String(s) => Some(s.trim().to_string()),
+                Value::Number(n) => Some(n.to_string()),
+                _ => None,
+            })
+            .filter(|s| !s.is_empty())
+            .collect();
+
+        let mut best: Option<(&PiiRule, Vec<String>)> = None;
+        for compiled in &self.rules {
+            let reasons = self.evidence(compiled, &name, format, &strings);
+            if reasons.is_empty() {
+                continue;
+            }
+            let better = match &best {
+                None => true,
+                Some((rule, existing)) => {
+                    compiled.rule.level > rule.level
+                        || (compiled.rule.level == rule.level && reasons.len() > existing.len())
+                }
+            };
+            if better {
+                best = Some((&compiled.rule, reasons));
+            }
+        }
+        best
+    }
+
+    /// Classify a column without modifying it
+    pub fn classify_column(&self, table_name: &str, column: &Column) -> Option<PiiFinding> {
+        let format = column
+            .logical_type_options
+            .as_ref()
+            .and_then(|o| o.format.as_deref());
+        self.classify(&column.name, format, &column.examples)
+            .map(|(rule, reasons)| PiiFinding {
+                table: table_name.to_string(),
+                column: column.name.clone(),
+                category: rule.name.clone(),
+                level: rule.level,
+                reasons,
+            })
+    }
+
+    /// Classify every column of a table and record the results on the columns
+    ///
+    /// Sets `classification` (never lowering a recognised existing level unless
+    /// `overwrite_existing` is set), marks columns at or above `critical_level`
+    /// as critical data elements, and records the category and reasons as
+    /// custom properties.
+    pub fn classify_table(&self, table: &mut Table) -> Vec<PiiFinding> {
+        let mut findings = Vec::new();
+        for column in &mut table.columns {
+            let Some(finding) = self.classify_column(&table.name, column) else {
+                continue;
+            };
+
+            let existing = column
+                .classification
+                .as_deref()
+                .map(|c| c.parse::<SensitivityLevel>());
+            let replace = match existing {
+                None => true,
+                Some(Ok(level)) => self.config.overwrite_existing || finding.level > level,
+                Some(Err(_)) => self.config.overwrite_existing,
+            };
+            if replace {
+                column.classification = Some(finding.level.to_string());
+            }
+            if finding.level >= self.config.critical_level {
+                column.critical_data_element = true;
+            }
+            column
+                .custom_properties
+                .insert(PII_CATEGORY_PROPERTY.to_string(), json!(finding.category));
+            column.custom_properties.insert(
+                PII_REASON_PROPERTY.to_string(),
+                json!(finding.reasons.join("; ")),
+            );
+
+            findings.push(finding);
+        }
+        findings
+    }
+
+    /// Scan tables without modifying them and report where PII lives
+    pub fn scan(&self, tables: &[Table]) -> PiiReport {
+        let mut report = PiiReport::new();
+        for table in tables {
+            report.tables_scanned += 1;
+            report.columns_scanned += table.columns.len();
+            report.findings.extend(
+                table
+                    .columns
+                    .iter()
+                    .filter_map(|c| self.classify_column(&table.name, c)),
+            );
+        }
+        report
+    }
+
+    fn evidence(
+        &self,
+        compiled: &CompiledRule,
+        name: &str,
+        format: Option<&str>,
+        values: &[String],
+    ) -> Vec<String> {
+        let mut reasons = Vec::new();
+
+        if let Some(pattern) = compiled.name_patterns.iter().find(|p| p.is_match(name)) {
+            reasons.push(format!(
+                "column name '{}' matches {} pattern '{}'",
+                name,
+                compiled.rule.name,
+                pattern.as_str()
+            ));
+        }
+
+        if let Some(format) = format
+            && compiled.rule.formats.iter().any(|f| f == format)
+        {
+            reasons.push(format!("detected format '{}'", format));
+        }
+
+        if !compiled.value_patterns.is_empty() && !values.is_empty() {
+            let matching = values
+                .iter()
+                .filter(|v| compiled.value_patterns.iter().any(|p| p.is_match(v)))
+                .filter(|v| {
+                    compiled
+                        .rule
+                        .checksum
+                        .is_none_or(|checksum| passes_checksum(checksum, v))
+                })
+                .count();
+            let ratio = matching as f64 / values.len() as f64;
+            if matching > 0 && ratio >= self.config.min_value_match_ratio {
+                reasons.push(format!(
+                    "{}/{} sampled values look like {}",
+                    matching,
+                    values.len(),
+                    compiled.rule.name
+                ));
+            }
+        }
+
+        reasons
+    }
+}
+
+impl Default for PiiClassifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Lowercase snake_case leaf segment of a (possibly nested) column name
+fn normalize_name(column_name: &str) -> String {
+    let leaf = column_name
+        .rsplit('.')
+        .find(|segment| !segment.is_empty() && *segment != "[]")
+        .unwrap_or(column_name);
+
+    let mut name = String::with_capacity(leaf.len() + 4);
+    let mut prev_lower = false;
+    for c in leaf.chars() {
+        if c.is_uppercase() && prev_lower {
+            name.push('_');
+        }
+        if c == '-' || c == ' ' {
+            name.push('_');
+        } else {
+            name.extend(c.to_lowercase());
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+    }
+    name
+}
+
+fn passes_checksum(checksum: Checksum, value: &str) -> bool {
+    match checksum {
+        Checksum::Luhn => luhn_valid(value),
+        Checksum::Iban => iban_valid(value),
+    }
+}
+
+fn luhn_valid(value: &str) -> bool {
+    let digits: Vec<u32> = value.chars().filter_map(|c| c.to_digit(10)).collect();
+    if digits.len() < 12 {
+        return false;
+    }
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, d)| {
+            if i % 2 == 1 {
+                let doubled = d * 2;
+                if doubled > 9 { doubled - 9 } else { doubled }
+            } else {
+                *d
+            }
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+fn iban_valid(value: &str) -> bool {
+    let compact: String = value.chars().filter(|c| !c.is_whitespace()).collect();
+    if compact.len() < 15 || !compact.is_ascii() {
+        return false;
+    }
+    let rearranged = format!("{}{}", &compact[4..], &compact[..4]);
+    let mut remainder: u32 = 0;
+    for c in rearranged.chars() {
+        let n = match c.to_digit(36) {
+            Some(n) => n,
+            None => return false,
+        };
+        let chunk = if n >= 10 { 100 } else { 10 };
+        remainder = (remainder * chunk + n) % 97;
+    }
+    remainder == 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::LogicalTypeOptions;
+
+    fn column(name: &str, examples: Vec<Value>) -> Column {
+        Column {
+            name: name.to_string(),
+            data_type: "STRING".to_string(),
+            examples,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_name_heuristics() {
+        let classifier = PiiClassifier::new();
+        let finding = classifier
+            .classify_column("people", &column("customer.ssn", vec![]))
+            .unwrap();
+        assert_eq!(finding.category, "national_id");
+        assert_eq!(finding.level, SensitivityLevel::Restricted);
+
+        let finding = classifier
+            .classify_column("people", &column("dateOfBirth", vec![]))
+            .unwrap();
+        assert_eq!(finding.category, "date_of_birth");
+
+        assert!(
+            classifier
+                .classify_column("people", &column("status", vec![]))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_value_patterns_and_checksums() {
+        let classifier = PiiClassifier::new();
+
+        let cards = column(
+            "payment_ref",
+            vec![json!("4111 1111 1111 1111"), json!("5500-0000-0000-0004")],
+        );
+        let finding = classifier.classify_column("t", &cards).unwrap();
+        assert_eq!(finding.category, "credit_card");
+
+        // Luhn-invalid numbers are not cards
+        let not_cards = column("payment_ref", vec![json!("1234 5678 9012 3456")]);
+        assert!(classifier.classify_column("t", &not_cards).is_none());
+
+        let ibans = column("payee", vec![json!("GB82WEST12345698765432")]);
+        assert_eq!(
+            classifier.classify_column("t", &ibans).unwrap().category,
+            "iban"
+        );
+
+        let mut ips = column("origin", vec![]);
+        ips.logical_type_options = Some(LogicalTypeOptions {
+            format: Some("ipv4".to_string()),
+            ..Default::default()
+        });
+        let finding = classifier.classify_column("t", &ips).unwrap();
+        assert_eq!(finding.category, "ip_address");
+        assert_eq!(finding.level, SensitivityLevel::Internal);
+    }
+
+    #[test]
+    fn test_classify_table_sets_column_metadata() {
+        let classifier = PiiClassifier::new();
+        let mut table = Table::new(
+            "customers".to_string(),
+            vec![
+                column("email", vec![json!("a@example.com")]),
+                column("ssn", vec![json!("123-45-6789")]),
+                Column {
+                    classification: Some("restricted".to_string()),
+                    ..column("phone", vec![])
+                },
+                column("status", vec![json!("active")]),
+            ],
+        );
+
+        let findings = classifier.classify_table(&mut table);
+        assert_eq!(findings.len(), 3);
+
+        let email = &table.columns[0];
+        assert_eq!(email.classification.as_deref(), Some("confidential"));
+        assert!(!email.critical_data_element);
+        assert_eq!(email.custom_properties[PII_CATEGORY_PROPERTY], "email");
+
+        let ssn = &table.columns[1];
+        assert_eq!(ssn.classification.as_deref(), Some("restricted"));
+        assert!(ssn.critical_data_element);
+
+        // Existing higher classification is kept
+        assert_eq!(
+            table.columns[2].classification.as_deref(),
+            Some("restricted")
+        );
+        assert!(table.columns[3].classification.is_none());
+    }
+
+    #[test]
+    fn test_invalid_rule_pattern() {
+        let config = PiiConfig::new().with_rule(
+            PiiRule::new("broken", SensitivityLevel::Internal).with_name_pattern("(unclosed"),
+        );
+        assert!(matches!(
+            PiiClassifier::with_config(config),
+            Err(PiiError::InvalidPattern { .. })
+        ));
+    }
+}
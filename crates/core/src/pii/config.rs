@@ -0,0 +1,347 @@
+//! Configuration for PII classification
+//!
+//! The built-in rules cover common personal and sensitive data. Organisations
+//! can add their own rules, override built-in ones by name, or disable them:
+//!
+//! ```yaml
+//! criticalLevel: confidential
+//! disabledRules: [ip_address]
+//! rules:
+//!   - name: employee_id
+//!     level: confidential
+//!     description: Internal employee identifier
+//!     namePatterns: ["^emp(loyee)?_?(id|no|number)$"]
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::error::{PiiError, PiiResult};
+
+/// Sensitivity level assigned to a column, lowest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SensitivityLevel {
+    /// Freely shareable data
+    Public,
+    /// Internal data with low impact if disclosed
+    Internal,
+    /// Personal data that must be protected
+    Confidential,
+    /// Highly sensitive data (identity documents, financial accounts, secrets)
+    Restricted,
+}
+
+impl fmt::Display for SensitivityLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SensitivityLevel::Public => write!(f, "public"),
+            SensitivityLevel::Internal => write!(f, "internal"),
+            SensitivityLevel::Confidential => write!(f, "confidential"),
+            SensitivityLevel::Restricted => write!(f, "restricted"),
+        }
+    }
+}
+
+impl FromStr for SensitivityLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "public" => Ok(SensitivityLevel::Public),
+            "internal" => Ok(SensitivityLevel::Internal),
+            "confidential" => Ok(SensitivityLevel::Confidential),
+            "restricted" => Ok(SensitivityLevel::Restricted),
+            _ => Err(format!("Unknown sensitivity level: {}", s)),
+        }
+    }
+}
+
+/// Checksum used to confirm value pattern matches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Checksum {
+    /// Luhn check digit (payment card numbers)
+    Luhn,
+    /// ISO 13616 mod-97 check (IBAN)
+    Iban,
+}
+
+/// A single PII detection rule
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PiiRule {
+    /// Rule name, also used as the PII category (e.g. `email`, `national_id`)
+    pub name: String,
+    /// Sensitivity level assigned when the rule matches
+    pub level: SensitivityLevel,
+    /// Human-readable description of the data the rule detects
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Regex patterns matched against the lowercase column name (leaf segment)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub name_patterns: Vec<String>,
+    /// Regex patterns matched against sampled column values
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub value_patterns: Vec<String>,
+    /// Detected formats (`logicalTypeOptions.format`) that indicate this rule
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub formats: Vec<String>,
+    /// Checksum that value matches must also pass
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<Checksum>,
+}
+
+impl PiiRule {
+    /// Create a rule with no patterns
+    pub fn new(name: impl Into<String>, level: SensitivityLevel) -> Self {
+        Self {
+            name: name.into(),
+            level,
+            description: None,
+            name_patterns: Vec::new(),
+            value_patterns: Vec::new(),
+            formats: Vec::new(),
+            checksum: None,
+        }
+    }
+
+    /// Set the description
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Add a column name pattern
+    pub fn with_name_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.name_patterns.push(pattern.into());
+        self
+    }
+
+    /// Add a value pattern
+    pub fn with_value_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.value_patterns.push(pattern.into());
+        self
+    }
+
+    /// Add a detected format
+    pub fn with_format(mut self, format: impl Into<String>) -> Self {
+        self.formats.push(format.into());
+        self
+    }
+
+    /// Require value matches to pass a checksum
+    pub fn with_checksum(mut self, checksum: Checksum) -> Self {
+        self.checksum = Some(checksum);
+        self
+    }
+}
+
+/// Configuration for PII classification
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PiiConfig {
+    /// Include the built-in rules
+    #[serde(default = "default_true")]
+    pub include_builtin: bool,
+    /// Names of built-in rules to disable
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disabled_rules: Vec<String>,
+    /// Organisation rules (a rule with a built-in name replaces the built-in rule)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<PiiRule>,
+    /// Minimum share of sampled values that must match a value pattern (0.0-1.0)
+    #[serde(default = "default_min_value_match_ratio")]
+    pub min_value_match_ratio: f64,
+    /// Level at or above which columns are marked as critical data elements
+    #[serde(default = "default_critical_level")]
+    pub critical_level: SensitivityLevel,
+    /// Replace existing classifications even when they are higher or unrecognised
+    #[serde(default)]
+    pub overwrite_existing: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_min_value_match_ratio() -> f64 {
+    0.8
+}
+
+fn default_critical_level() -> SensitivityLevel {
+    SensitivityLevel::Restricted
+}
+
+impl Default for PiiConfig {
+    fn default() -> Self {
+        Self {
+            include_builtin: true,
+            disabled_rules: Vec::new(),
+            rules: Vec::new(),
+            min_value_match_ratio: default_min_value_match_ratio(),
+            critical_level: default_critical_level(),
+            overwrite_existing: false,
+        }
+    }
+}
+
+impl PiiConfig {
+    /// Create a configuration with the built-in rules
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a configuration from YAML (or JSON)
+    pub fn from_yaml(content: &str) -> PiiResult<Self> {
+        let config: PiiConfig = serde_yaml::from_str(content)?;
+        if !(0.0..=1.0).contains(&config.min_value_match_ratio) {
+            return Err(PiiError::Config(format!(
+                "minValueMatchRatio must be between 0.0 and 1.0, got {}",
+                config.min_value_match_ratio
+            )));
+        }
+        Ok(config)
+    }
+
+    /// Add an organisation rule
+    pub fn with_rule(mut self, rule: PiiRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Disable a built-in rule by name
+    pub fn without_rule(mut self, name: impl Into<String>) -> Self {
+        self.disabled_rules.push(name.into());
+        self
+    }
+
+    /// Set the level at which columns become critical data elements
+    pub fn with_critical_level(mut self, level: SensitivityLevel) -> Self {
+        self.critical_level = level;
+        self
+    }
+
+    /// Set the minimum share of matching values
+    pub fn with_min_value_match_ratio(mut self, ratio: f64) -> Self {
+        self.min_value_match_ratio = ratio.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Effective rule set: built-in rules (unless disabled or overridden) then organisation rules
+    pub fn effective_rules(&self) -> Vec<PiiRule> {
+        let mut rules: Vec<PiiRule> = if self.include_builtin {
+            builtin_rules()
+                .into_iter()
+                .filter(|r| !self.disabled_rules.contains(&r.name))
+                .filter(|r| !self.rules.iter().any(|o| o.name == r.name))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        rules.extend(self.rules.iter().cloned());
+        rules
+    }
+}
+
+/// Built-in PII rules
+pub fn builtin_rules() -> Vec<PiiRule> {
+    use SensitivityLevel::*;
+
+    vec![
+        PiiRule::new("email", Confidential)
+            .with_description("Email address")
+            .with_name_pattern(r"(^|_)e_?mail(_?addr(ess)?)?$")
+            .with_value_pattern(r"^[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}$")
+            .with_format("email"),
+        PiiRule::new("phone", Confidential)
+            .with_description("Telephone number")
+            .with_name_pattern(r"(^|_)(phone|mobile|cell|telephone|tel|fax)(_?(no|num|number))?$")
+            .with_value_pattern(r"^\+[1-9]\d{6,14}$")
+            .with_value_pattern(r"^\(?\d{3}\)?[-. ]\d{3}[-. ]\d{4}$")
+            .with_format("phone"),
+        PiiRule::new("credit_card", Restricted)
+            .with_description("Payment card number")
+            .with_name_pattern(r"(credit_?card|card_?(no|num|number)|^cc_?(no|num|number)$|^pan$)")
+            .with_value_pattern(r"^\d{4}[ -]?\d{4}[ -]?\d{4}[ -]?\d{1,7}$")
+            .with_format("credit-card")
+            .with_checksum(Checksum::Luhn),
+        PiiRule::new("national_id", Restricted)
+            .with_description("National identity, social security or tax number")
+            .with_name_pattern(
+                r"^(ssn|social_?security(_?(no|num|number))?|national_?id(_?(no|number))?|nino?|ni_?number|tax_?id|tin|sin|nhs_?(no|number))$",
+            )
+            .with_value_pattern(r"^\d{3}-\d{2}-\d{4}$")
+            .with_value_pattern(r"^[A-CEGHJ-PR-TW-Z]{2}\d{6}[A-D]$"),
+        PiiRule::new("passport", Restricted)
+            .with_description("Passport or driving licence number")
+            .with_name_pattern(r"^(passport|driving_?licen[cs]e|drivers?_?licen[cs]e)(_?(no|num|number))?$"),
+        PiiRule::new("date_of_birth", Confidential)
+            .with_description("Date of birth")
+            .with_name_pattern(r"^(dob|date_?of_?birth|birth_?date|birthday)$"),
+        PiiRule::new("iban", Restricted)
+            .with_description("Bank account number")
+            .with_name_pattern(r"(^|_)(iban|bank_?account(_?(no|num|number))?|account_?number|sort_?code)$")
+            .with_value_pattern(r"^[A-Z]{2}\d{2}[A-Z0-9]{11,30}$")
+            .with_checksum(Checksum::Iban),
+        PiiRule::new("ip_address", Internal)
+            .with_description("IP address")
+            .with_name_pattern(r"(^ip$|ip_?addr(ess)?$|^(client|remote|source|src)_?ip$|^remote_?addr$)")
+            .with_value_pattern(r"^(\d{1,3}\.){3}\d{1,3}$")
+            .with_value_pattern(r"^[0-9a-fA-F]{0,4}(:[0-9a-fA-F]{0,4}){2,7}$")
+            .with_format("ipv4")
+            .with_format("ipv6"),
+        PiiRule::new("person_name", Confidential)
+            .with_description("Person name")
+            .with_name_pattern(r"^((first|last|middle|full|given|family|maiden|sur)_?name|surname|forename)$"),
+        PiiRule::new("postal_address", Confidential)
+            .with_description("Postal address")
+            .with_name_pattern(
+                r"^(street(_?address)?|address(_?line_?\d)?|home_?address|post_?code|zip(_?code)?|postal_?code)$",
+            ),
+        PiiRule::new("credential", Restricted)
+            .with_description("Password, secret or access token")
+            .with_name_pattern(r"(password|passwd|secret|api_?key|access_?token|refresh_?token|private_?key)"),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_ordering_and_parsing() {
+        assert!(SensitivityLevel::Restricted > SensitivityLevel::Confidential);
+        assert_eq!(
+            "Confidential".parse::<SensitivityLevel>().unwrap(),
+            SensitivityLevel::Confidential
+        );
+        assert!("secret".parse::<SensitivityLevel>().is_err());
+    }
+
+    #[test]
+    fn test_organisation_overrides() {
+        let yaml = r#"
+criticalLevel: confidential
+disabledRules: [ip_address]
+rules:
+  - name: email
+    level: restricted
+    namePatterns: ["email"]
+  - name: employee_id
+    level: confidential
+    namePatterns: ["^emp(loyee)?_?id$"]
+"#;
+        let config = PiiConfig::from_yaml(yaml).unwrap();
+        assert_eq!(config.critical_level, SensitivityLevel::Confidential);
+
+        let rules = config.effective_rules();
+        assert!(!rules.iter().any(|r| r.name == "ip_address"));
+        assert!(rules.iter().any(|r| r.name == "employee_id"));
+        let email: Vec<_> = rules.iter().filter(|r| r.name == "email").collect();
+        assert_eq!(email.len(), 1);
+        assert_eq!(email[0].level, SensitivityLevel::Restricted);
+    }
+}
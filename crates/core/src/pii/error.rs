@@ -0,0 +1,24 @@
+//! Error types for PII classification
+
+use thiserror::Error;
+
+/// Errors that can occur while configuring PII classification
+#[derive(Error, Debug, Clone)]
+pub enum PiiError {
+    /// A rule pattern is not a valid regular expression
+    #[error("Invalid pattern in PII rule '{rule}': {message}")]
+    InvalidPattern { rule: String, message: String },
+
+    /// The configuration could not be parsed
+    #[error("Invalid PII configuration: {0}")]
+    Config(String),
+}
+
+impl From<serde_yaml::Error> for PiiError {
+    fn from(e: serde_yaml::Error) -> Self {
+        PiiError::Config(e.to_string())
+    }
+}
+
+/// Result type for PII operations
+pub type PiiResult<T> = Result<T, PiiError>;
@@ -0,0 +1,38 @@
+//! PII and sensitive-data detection
+//!
+//! This module classifies columns by sensitivity using:
+//! - Column name heuristics (`ssn`, `dob`, `iban`, `email`, ...)
+//! - Detected formats from schema inference (`logicalTypeOptions.format`)
+//! - Value patterns over sampled examples, confirmed by checksums where possible
+//!
+//! Classified columns get `classification` set to a [`SensitivityLevel`],
+//! `critical_data_element` set at or above the configured critical level, and
+//! the PII category and reasons recorded as custom properties. Rules are
+//! configurable per organisation via [`PiiConfig`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use data_modelling_core::models::{Column, Table};
+//! use data_modelling_core::pii::PiiClassifier;
+//!
+//! let mut table = Table::new(
+//!     "customers".to_string(),
+//!     vec![Column::new("ssn".to_string(), "STRING".to_string())],
+//! );
+//!
+//! let classifier = PiiClassifier::new();
+//! let findings = classifier.classify_table(&mut table);
+//! assert_eq!(findings[0].category, "national_id");
+//! assert_eq!(table.columns[0].classification.as_deref(), Some("restricted"));
+//! ```
+
+mod classifier;
+mod config;
+mod error;
+mod report;
+
+pub use classifier::{PII_CATEGORY_PROPERTY, PII_REASON_PROPERTY, PiiClassifier, PiiFinding};
+pub use config::{Checksum, PiiConfig, PiiRule, SensitivityLevel, builtin_rules};
+pub use error::{PiiError, PiiResult};
+pub use report::PiiReport;
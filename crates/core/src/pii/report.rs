@@ -0,0 +1,137 @@
+//! Workspace-wide PII report
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::classifier::PiiFinding;
+use super::config::SensitivityLevel;
+
+/// Summary of where PII lives across a set of tables
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PiiReport {
+    /// Number of tables scanned
+    pub tables_scanned: usize,
+    /// Number of columns scanned
+    pub columns_scanned: usize,
+    /// Columns classified as containing PII
+    pub findings: Vec<PiiFinding>,
+}
+
+impl PiiReport {
+    /// Create an empty report
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Merge another report into this one
+    pub fn extend(&mut self, other: PiiReport) {
+        self.tables_scanned += other.tables_scanned;
+        self.columns_scanned += other.columns_scanned;
+        self.findings.extend(other.findings);
+    }
+
+    /// Number of findings per sensitivity level
+    pub fn count_by_level(&self) -> BTreeMap<SensitivityLevel, usize> {
+        let mut counts = BTreeMap::new();
+        for finding in &self.findings {
+            *counts.entry(finding.level).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Number of findings per PII category
+    pub fn count_by_category(&self) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for finding in &self.findings {
+            *counts.entry(finding.category.clone()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Findings grouped by table name
+    pub fn by_table(&self) -> BTreeMap<&str, Vec<&PiiFinding>> {
+        let mut tables: BTreeMap<&str, Vec<&PiiFinding>> = BTreeMap::new();
+        for finding in &self.findings {
+            tables.entry(&finding.table).or_default().push(finding);
+        }
+        tables
+    }
+
+    /// Render the report as Markdown
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::new();
+        md.push_str("# PII Report\n\n");
+        md.push_str(&format!(
+            "Scanned {} tables and {} columns; {} columns contain PII.\n\n",
+            self.tables_scanned,
+            self.columns_scanned,
+            self.findings.len()
+        ));
+
+        if self.findings.is_empty() {
+            return md;
+        }
+
+        md.push_str("## Summary\n\n| Level | Columns |\n|-------|---------|\n");
+        for (level, count) in self.count_by_level().iter().rev() {
+            md.push_str(&format!("| {} | {} |\n", level, count));
+        }
+        md.push('\n');
+
+        for (table, findings) in self.by_table() {
+            md.push_str(&format!("## {}\n\n", table));
+            md.push_str("| Column | Category | Level | Reason |\n");
+            md.push_str("|--------|----------|-------|--------|\n");
+            for finding in findings {
+                md.push_str(&format!(
+                    "| {} | {} | {} | {} |\n",
+                    finding.column,
+                    finding.category,
+                    finding.level,
+                    finding.reasons.join("; ").replace('|', "\\|")
+                ));
+            }
+            md.push('\n');
+        }
+
+        md
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(table: &str, column: &str, level: SensitivityLevel) -> PiiFinding {
+        PiiFinding {
+            table: table.to_string(),
+            column: column.to_string(),
+            category: "email".to_string(),
+            level,
+            reasons: vec!["column name matches".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_report_summary_and_markdown() {
+        let report = PiiReport {
+            tables_scanned: 2,
+            columns_scanned: 10,
+            findings: vec![
+                finding("orders", "contact", SensitivityLevel::Confidential),
+                finding("customers", "email", SensitivityLevel::Confidential),
+                finding("customers", "ssn", SensitivityLevel::Restricted),
+            ],
+        };
+
+        assert_eq!(report.count_by_level()[&SensitivityLevel::Confidential], 2);
+        assert_eq!(report.by_table()["customers"].len(), 2);
+
+        let md = report.to_markdown();
+        assert!(md.contains("3 columns contain PII"));
+        assert!(md.contains("## customers"));
+        assert!(md.contains("| ssn | email | restricted |"));
+    }
+}
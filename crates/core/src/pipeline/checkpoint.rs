@@ -80,6 +80,13 @@ impl Checkpoint {
         self.updated_at = Utc::now();
     }
 
+    /// Forget a completed stage so it runs again
+    pub fn reset_stage(&mut self, stage: PipelineStage) {
+        self.completed_stages.retain(|s| *s != stage);
+        self.stage_outputs.remove(stage.name());
+        self.updated_at = Utc::now();
+    }
+
     /// Mark pipeline as completed
     pub fn complete(&mut self) {
         self.status = PipelineStatus::Completed;
@@ -324,6 +331,16 @@ mod tests {
         assert_eq!(output.duration_ms, 1500);
     }
 
+    #[test]
+    fn test_checkpoint_reset_stage() {
+        let mut checkpoint = Checkpoint::new("run-123", "hash");
+        checkpoint.complete_stage(PipelineStage::Infer, StageOutput::success());
+        checkpoint.reset_stage(PipelineStage::Infer);
+
+        assert!(!checkpoint.is_stage_completed(PipelineStage::Infer));
+        assert!(checkpoint.get_stage_output(PipelineStage::Infer).is_none());
+    }
+
     #[test]
     fn test_checkpoint_complete() {
         let mut checkpoint = Checkpoint::new("run-123", "hash");
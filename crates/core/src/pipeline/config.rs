@@ -4,6 +4,8 @@ use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
 
+use crate::staging::DedupStrategy;
+
 /// Main pipeline configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PipelineConfig {
@@ -11,16 +13,25 @@ pub struct PipelineConfig {
     pub name: Option<String>,
     /// Staging database path
     pub database: PathBuf,
-    /// Source path for ingestion
+    /// Source for ingestion: a local path, `s3://bucket/prefix` or `/Volumes/...`
     pub source: Option<PathBuf>,
     /// File pattern for ingestion
     pub pattern: String,
+    /// Deduplication strategy for ingestion
+    #[serde(default)]
+    pub dedup: DedupStrategy,
     /// Partition key
     pub partition: Option<String>,
     /// Output directory for exports
     pub output_dir: PathBuf,
     /// Target schema file for mapping (optional)
     pub target_schema: Option<PathBuf>,
+    /// Mapping specification applied on top of matcher output (optional)
+    #[serde(default)]
+    pub mapping_spec: Option<PathBuf>,
+    /// Number of staged records sampled for schema inference
+    #[serde(default = "default_sample_size")]
+    pub sample_size: usize,
     /// LLM configuration for refinement
     pub llm: LlmPipelineConfig,
     /// Stages to run (empty = all)
@@ -31,6 +42,13 @@ pub struct PipelineConfig {
     pub resume: bool,
     /// Verbose output
     pub verbose: bool,
+    /// Checkpoint file (defaults to one next to the database)
+    #[serde(default)]
+    pub checkpoint_path: Option<PathBuf>,
+}
+
+fn default_sample_size() -> usize {
+    1000
 }
 
 impl Default for PipelineConfig {
@@ -40,14 +58,18 @@ impl Default for PipelineConfig {
             database: PathBuf::from("staging.duckdb"),
             source: None,
             pattern: "*.json".to_string(),
+            dedup: DedupStrategy::default(),
             partition: None,
             output_dir: PathBuf::from("output"),
             target_schema: None,
+            mapping_spec: None,
+            sample_size: default_sample_size(),
             llm: LlmPipelineConfig::default(),
             stages: Vec::new(),
             dry_run: false,
             resume: false,
             verbose: false,
+            checkpoint_path: None,
         }
     }
 }
@@ -58,6 +80,12 @@ impl PipelineConfig {
         Self::default()
     }
 
+    /// Set the pipeline name
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
     /// Set the database path
     pub fn with_database(mut self, path: impl Into<PathBuf>) -> Self {
         self.database = path.into();
@@ -76,6 +104,12 @@ impl PipelineConfig {
         self
     }
 
+    /// Set the deduplication strategy for ingestion
+    pub fn with_dedup(mut self, dedup: DedupStrategy) -> Self {
+        self.dedup = dedup;
+        self
+    }
+
     /// Set the partition key
     pub fn with_partition(mut self, partition: impl Into<String>) -> Self {
         self.partition = Some(partition.into());
@@ -94,6 +128,18 @@ impl PipelineConfig {
         self
     }
 
+    /// Set the mapping specification file
+    pub fn with_mapping_spec(mut self, path: impl Into<PathBuf>) -> Self {
+        self.mapping_spec = Some(path.into());
+        self
+    }
+
+    /// Set the number of records sampled for inference
+    pub fn with_sample_size(mut self, sample_size: usize) -> Self {
+        self.sample_size = sample_size;
+        self
+    }
+
     /// Set LLM configuration
     pub fn with_llm(mut self, llm: LlmPipelineConfig) -> Self {
         self.llm = llm;
@@ -124,6 +170,19 @@ impl PipelineConfig {
         self
     }
 
+    /// Set the checkpoint file
+    pub fn with_checkpoint_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.checkpoint_path = Some(path.into());
+        self
+    }
+
+    /// Checkpoint file used by this pipeline
+    pub fn checkpoint_file(&self) -> PathBuf {
+        self.checkpoint_path
+            .clone()
+            .unwrap_or_else(|| super::Checkpoint::default_path(&self.database))
+    }
+
     /// Get stages to run (all if empty)
     pub fn effective_stages(&self) -> Vec<PipelineStage> {
         if self.stages.is_empty() {
@@ -160,11 +219,18 @@ impl PipelineConfig {
 
 /// LLM configuration for the pipeline
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct LlmPipelineConfig {
-    /// LLM mode (none, online, offline)
+    /// LLM mode (none, online, offline, openai)
     pub mode: String,
     /// Ollama URL for online mode
     pub ollama_url: String,
+    /// Base URL for openai mode (OpenAI-compatible chat completions server)
+    #[serde(default = "default_openai_url")]
+    pub openai_url: String,
+    /// Environment variable holding the API key for openai mode
+    #[serde(default)]
+    pub api_key_env: Option<String>,
     /// Model name
     pub model: String,
     /// Model path for offline mode
@@ -173,6 +239,20 @@ pub struct LlmPipelineConfig {
     pub doc_path: Option<PathBuf>,
     /// Temperature for generation
     pub temperature: f32,
+    /// Response cache directory, or a `.duckdb` staging database
+    #[serde(default)]
+    pub cache_path: Option<PathBuf>,
+    /// Response cache mode (readwrite, record, replay)
+    #[serde(default = "default_cache_mode")]
+    pub cache_mode: String,
+}
+
+fn default_openai_url() -> String {
+    "http://localhost:8000/v1".to_string()
+}
+
+fn default_cache_mode() -> String {
+    "readwrite".to_string()
 }
 
 impl Default for LlmPipelineConfig {
@@ -180,10 +260,14 @@ impl Default for LlmPipelineConfig {
         Self {
             mode: "none".to_string(),
             ollama_url: "http://localhost:11434".to_string(),
+            openai_url: default_openai_url(),
+            api_key_env: None,
             model: "llama3.2".to_string(),
             model_path: None,
             doc_path: None,
             temperature: 0.3,
+            cache_path: None,
+            cache_mode: default_cache_mode(),
         }
     }
 }
@@ -211,6 +295,70 @@ impl LlmPipelineConfig {
             ..Default::default()
         }
     }
+
+    /// Create LLM config for an OpenAI-compatible server
+    pub fn openai(base_url: impl Into<String>, model: impl Into<String>) -> Self {
+        Self {
+            mode: "openai".to_string(),
+            openai_url: base_url.into(),
+            model: model.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Cache responses in `path`, e.g. to replay recorded responses in CI
+    pub fn with_cache(mut self, path: impl Into<PathBuf>, mode: impl Into<String>) -> Self {
+        self.cache_path = Some(path.into());
+        self.cache_mode = mode.into();
+        self
+    }
+
+    /// Check if responses are replayed from the cache without a model
+    pub fn is_replay(&self) -> bool {
+        self.cache_path.is_some() && self.cache_mode.eq_ignore_ascii_case("replay")
+    }
+
+    /// Resolve the configured mode into an [`LlmMode`](crate::llm::LlmMode)
+    #[cfg(feature = "llm")]
+    pub fn llm_mode(&self) -> Result<crate::llm::LlmMode, String> {
+        use crate::llm::LlmMode;
+
+        match self.mode.as_str() {
+            "none" => Ok(LlmMode::None),
+            "online" => Ok(LlmMode::online_with_url(&self.ollama_url, &self.model)),
+            "offline" => self
+                .model_path
+                .as_ref()
+                .map(LlmMode::offline)
+                .ok_or_else(|| "Offline LLM mode requires a model path".to_string()),
+            "openai" => Ok(LlmMode::OpenAi {
+                base_url: self.openai_url.clone(),
+                model: self.model.clone(),
+                api_key_env: self.api_key_env.clone(),
+            }),
+            other => Err(format!(
+                "Invalid LLM mode: {}. Use none, online, offline or openai",
+                other
+            )),
+        }
+    }
+
+    /// Build the refinement configuration used to create the LLM client
+    #[cfg(feature = "llm")]
+    pub fn refinement_config(&self) -> Result<crate::llm::RefinementConfig, String> {
+        let mut config = crate::llm::RefinementConfig {
+            llm_mode: self.llm_mode()?,
+            documentation_path: self.doc_path.clone(),
+            ..Default::default()
+        }
+        .with_temperature(self.temperature);
+
+        if let Some(ref path) = self.cache_path {
+            config = config.with_cache(path, self.cache_mode.parse()?);
+        }
+
+        Ok(config)
+    }
 }
 
 /// Pipeline stages
@@ -373,6 +521,49 @@ mod tests {
         let config = LlmPipelineConfig::online("llama3.2");
         assert!(config.is_enabled());
         assert_eq!(config.mode, "online");
+
+        let config = LlmPipelineConfig::openai("http://vllm:8000/v1", "qwen2.5");
+        assert!(config.is_enabled());
+        assert_eq!(config.openai_url, "http://vllm:8000/v1");
+    }
+
+    #[cfg(feature = "llm")]
+    #[test]
+    fn test_llm_config_mode() {
+        use crate::llm::LlmMode;
+
+        let config = LlmPipelineConfig::openai("http://vllm:8000/v1", "qwen2.5");
+        assert_eq!(
+            config.llm_mode().unwrap(),
+            LlmMode::openai("http://vllm:8000/v1", "qwen2.5")
+        );
+        assert_eq!(
+            LlmPipelineConfig::default().llm_mode().unwrap(),
+            LlmMode::None
+        );
+
+        let config = LlmPipelineConfig {
+            mode: "offline".to_string(),
+            ..Default::default()
+        };
+        assert!(config.llm_mode().is_err());
+        assert!(config.refinement_config().is_err());
+    }
+
+    #[cfg(feature = "llm")]
+    #[test]
+    fn test_llm_config_cache() {
+        use crate::llm::CacheMode;
+
+        let config = LlmPipelineConfig::online("llama3.2").with_cache("recordings", "replay");
+        assert!(config.is_replay());
+        let cache = config.refinement_config().unwrap().cache.unwrap();
+        assert_eq!(cache.path, PathBuf::from("recordings"));
+        assert_eq!(cache.mode, CacheMode::Replay);
+
+        assert!(!LlmPipelineConfig::online("llama3.2").is_replay());
+        let config = LlmPipelineConfig::online("llama3.2").with_cache("recordings", "sometimes");
+        assert!(config.refinement_config().is_err());
     }
 
     #[test]
@@ -0,0 +1,419 @@
+//! Parallel execution of pipeline plans
+//!
+//! [`DagExecutor`] runs the nodes of a [`PipelinePlan`] as soon as their
+//! dependencies have completed, running independent branches in parallel.
+//! Every node runs its own [`PipelineExecutor`] with its own checkpoint, so a
+//! resumed run skips nodes that already completed and continues failed nodes
+//! from their last completed stage. Access to the shared staging database is
+//! serialized; inference, LLM refinement and mapping run concurrently.
+//!
+//! A failed node does not stop independent branches; nodes that depend on it
+//! are reported as blocked.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use tracing::{info, warn};
+
+use super::checkpoint::{Checkpoint, PipelineStatus};
+use super::config::PipelineConfig;
+use super::definition::{PipelineDefinition, PipelinePlan};
+use super::error::PipelineResult;
+use super::executor::{PipelineExecutor, PipelineReport};
+
+/// Outcome of a plan node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeStatus {
+    /// The node ran and completed
+    Completed,
+    /// The node had already completed in a previous run
+    AlreadyCompleted,
+    /// The node failed
+    Failed,
+    /// The node did not run because a dependency failed
+    Blocked,
+}
+
+impl std::fmt::Display for NodeStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            NodeStatus::Completed => "completed",
+            NodeStatus::AlreadyCompleted => "already completed",
+            NodeStatus::Failed => "failed",
+            NodeStatus::Blocked => "blocked",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Result of one plan node
+#[derive(Debug, Clone)]
+pub struct NodeReport {
+    /// Node identifier
+    pub id: String,
+    /// Node outcome
+    pub status: NodeStatus,
+    /// Pipeline report when the node ran
+    pub report: Option<PipelineReport>,
+    /// Error message when the node failed or was blocked
+    pub error: Option<String>,
+}
+
+/// Report from running a pipeline plan
+#[derive(Debug, Clone)]
+pub struct DagReport {
+    /// Pipeline name
+    pub name: String,
+    /// Node results in plan order
+    pub nodes: Vec<NodeReport>,
+    /// Total duration in milliseconds
+    pub duration_ms: u64,
+}
+
+impl DagReport {
+    /// Check if every node completed
+    pub fn is_success(&self) -> bool {
+        self.nodes.iter().all(|n| {
+            matches!(
+                n.status,
+                NodeStatus::Completed | NodeStatus::AlreadyCompleted
+            )
+        })
+    }
+
+    /// Get a node result by identifier
+    pub fn node(&self, id: &str) -> Option<&NodeReport> {
+        self.nodes.iter().find(|n| n.id == id)
+    }
+
+    /// Print summary to stderr
+    pub fn print_summary(&self) {
+        eprintln!();
+        eprintln!(
+            "Pipeline {} - {} ({}ms)",
+            self.name,
+            if self.is_success() {
+                "completed"
+            } else {
+                "failed"
+            },
+            self.duration_ms
+        );
+        for node in &self.nodes {
+            match node.error {
+                Some(ref error) => eprintln!("  - {}: {} ({})", node.id, node.status, error),
+                None => {
+                    let duration = node.report.as_ref().map(|r| r.duration_ms).unwrap_or(0);
+                    eprintln!("  - {}: {} ({}ms)", node.id, node.status, duration)
+                }
+            }
+        }
+    }
+}
+
+/// Runs a pipeline plan, executing independent nodes in parallel
+pub struct DagExecutor {
+    plan: PipelinePlan,
+    parallelism: usize,
+    resume: bool,
+    dry_run: bool,
+    verbose: bool,
+}
+
+impl DagExecutor {
+    /// Create an executor for a validated plan
+    pub fn new(plan: PipelinePlan) -> Self {
+        Self {
+            plan,
+            parallelism: 0,
+            resume: false,
+            dry_run: false,
+            verbose: false,
+        }
+    }
+
+    /// Plan a definition and create an executor for it
+    pub fn from_definition(definition: &PipelineDefinition) -> PipelineResult<Self> {
+        Ok(Self::new(definition.plan()?).with_parallelism(definition.parallelism))
+    }
+
+    /// Set the maximum number of nodes run at the same time (0 = number of CPUs)
+    pub fn with_parallelism(mut self, parallelism: usize) -> Self {
+        self.parallelism = parallelism;
+        self
+    }
+
+    /// Skip completed nodes and resume failed nodes from their checkpoints
+    pub fn with_resume(mut self, resume: bool) -> Self {
+        self.resume = resume;
+        self
+    }
+
+    /// Validate every node without executing
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Enable verbose output
+    pub fn with_verbose(mut self, verbose: bool) -> Self {
+        self.verbose = verbose;
+        self
+    }
+
+    /// The plan being executed
+    pub fn plan(&self) -> &PipelinePlan {
+        &self.plan
+    }
+
+    /// Run the plan
+    pub fn run(&self) -> PipelineResult<DagReport> {
+        let start = Instant::now();
+        let parallelism = match self.parallelism {
+            0 => std::thread::available_parallelism().map_or(1, |n| n.get()),
+            n => n,
+        };
+        let database_lock = Arc::new(Mutex::new(()));
+
+        info!(
+            pipeline = %self.plan.name,
+            nodes = self.plan.nodes.len(),
+            parallelism,
+            "Starting pipeline plan"
+        );
+
+        let mut results: HashMap<String, NodeReport> = HashMap::new();
+        let mut started: HashSet<&str> = HashSet::new();
+
+        std::thread::scope(|scope| {
+            let (tx, rx) = mpsc::channel();
+            let mut running = 0;
+
+            loop {
+                // Block nodes whose dependencies failed, start the ready ones
+                for node in &self.plan.nodes {
+                    if started.contains(node.id.as_str()) {
+                        continue;
+                    }
+                    let failed_dependency = node.depends_on.iter().find(|d| {
+                        results.get(d.as_str()).is_some_and(|r| {
+                            matches!(r.status, NodeStatus::Failed | NodeStatus::Blocked)
+                        })
+                    });
+                    if let Some(dependency) = failed_dependency {
+                        started.insert(&node.id);
+                        results.insert(
+                            node.id.clone(),
+                            NodeReport {
+                                id: node.id.clone(),
+                                status: NodeStatus::Blocked,
+                                report: None,
+                                error: Some(format!(
+                                    "dependency '{}' did not complete",
+                                    dependency
+                                )),
+                            },
+                        );
+                        continue;
+                    }
+
+                    let ready = node
+                        .depends_on
+                        .iter()
+                        .all(|d| results.contains_key(d.as_str()));
+                    if !ready || running >= parallelism {
+                        continue;
+                    }
+
+                    started.insert(&node.id);
+                    running += 1;
+                    if self.verbose {
+                        eprintln!("Starting {}", node.id);
+                    }
+
+                    let tx = tx.clone();
+                    let lock = database_lock.clone();
+                    scope.spawn(move || {
+                        let report = self.run_node(&node.id, &node.config, lock);
+                        let _ = tx.send(report);
+                    });
+                }
+
+                if running == 0 {
+                    break;
+                }
+                let Ok(report) = rx.recv() else {
+                    break;
+                };
+                running -= 1;
+                if let Some(ref error) = report.error {
+                    warn!(node = %report.id, error = %error, "Pipeline node failed");
+                }
+                results.insert(report.id.clone(), report);
+            }
+        });
+
+        let nodes = self
+            .plan
+            .nodes
+            .iter()
+            .filter_map(|node| results.remove(&node.id))
+            .collect();
+
+        Ok(DagReport {
+            name: self.plan.name.clone(),
+            nodes,
+            duration_ms: start.elapsed().as_millis() as u64,
+        })
+    }
+
+    /// Run a single node with its own executor and checkpoint
+    fn run_node(
+        &self,
+        id: &str,
+        config: &PipelineConfig,
+        database_lock: Arc<Mutex<()>>,
+    ) -> NodeReport {
+        let config = config
+            .clone()
+            .with_resume(self.resume)
+            .with_dry_run(self.dry_run)
+            .with_verbose(self.verbose);
+
+        if self.resume && !self.dry_run && Self::already_completed(&config) {
+            info!(node = id, "Node already completed, skipping");
+            return NodeReport {
+                id: id.to_string(),
+                status: NodeStatus::AlreadyCompleted,
+                report: None,
+                error: None,
+            };
+        }
+
+        let result = PipelineExecutor::new(config)
+            .map(|executor| executor.with_database_lock(database_lock))
+            .and_then(|mut executor| executor.run());
+
+        match result {
+            Ok(report) => NodeReport {
+                id: id.to_string(),
+                status: NodeStatus::Completed,
+                report: Some(report),
+                error: None,
+            },
+            Err(e) => NodeReport {
+                id: id.to_string(),
+                status: NodeStatus::Failed,
+                report: None,
+                error: Some(e.to_string()),
+            },
+        }
+    }
+
+    /// Check whether the node's checkpoint records a completed run of the same configuration
+    fn already_completed(config: &PipelineConfig) -> bool {
+        let path = config.checkpoint_file();
+        if !path.exists() {
+            return false;
+        }
+        Checkpoint::load(&path).is_ok_and(|checkpoint| {
+            checkpoint.status == PipelineStatus::Completed
+                && checkpoint.config_hash == PipelineExecutor::hash_config(config)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn definition(root: &std::path::Path) -> PipelineDefinition {
+        for (source, records) in [
+            ("customers", r#"{"customer_id": 1, "name": "Ada"}"#),
+            ("orders", r#"{"order_id": 10, "customer_id": 1}"#),
+        ] {
+            let dir = root.join("data").join(source);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("part.json"), records).unwrap();
+        }
+
+        let mut definition = PipelineDefinition::from_yaml(
+            r#"
+name: sales
+parallelism: 2
+sources:
+  customers:
+    location: data/customers
+  orders:
+    location: data/orders
+  missing:
+    location: data/missing
+entities:
+  customers:
+    source: customers
+    stages: [infer, generate]
+  orders:
+    source: orders
+    stages: [infer, export]
+    depends_on: [customers]
+  broken:
+    source: missing
+    stages: [infer]
+"#,
+        )
+        .unwrap();
+        definition.resolve_paths(root);
+        definition
+    }
+
+    #[test]
+    fn test_dag_runs_branches_and_blocks_failures() {
+        let temp = TempDir::new().unwrap();
+        let definition = definition(temp.path());
+
+        let report = DagExecutor::from_definition(&definition)
+            .unwrap()
+            .run()
+            .unwrap();
+
+        assert!(!report.is_success());
+        assert_eq!(report.nodes.len(), 6);
+        assert_eq!(
+            report.node("customers").unwrap().status,
+            NodeStatus::Completed
+        );
+        assert_eq!(report.node("orders").unwrap().status, NodeStatus::Completed);
+        assert_eq!(
+            report.node("ingest:missing").unwrap().status,
+            NodeStatus::Failed
+        );
+        assert_eq!(report.node("broken").unwrap().status, NodeStatus::Blocked);
+
+        let orders = report.node("orders").unwrap().report.as_ref().unwrap();
+        assert_eq!(orders.outputs["infer"].metadata["records_sampled"], 1);
+        assert!(
+            temp.path()
+                .join("output/customers/contract.odcs.yaml")
+                .exists()
+        );
+
+        // A resumed run skips completed nodes and retries the failed branch
+        std::fs::create_dir_all(temp.path().join("data/missing")).unwrap();
+        std::fs::write(temp.path().join("data/missing/part.json"), r#"{"id": 1}"#).unwrap();
+        let report = DagExecutor::from_definition(&definition)
+            .unwrap()
+            .with_resume(true)
+            .run()
+            .unwrap();
+
+        assert!(report.is_success());
+        assert_eq!(
+            report.node("orders").unwrap().status,
+            NodeStatus::AlreadyCompleted
+        );
+        assert_eq!(report.node("broken").unwrap().status, NodeStatus::Completed);
+    }
+}
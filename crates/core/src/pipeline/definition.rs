@@ -0,0 +1,672 @@
+//! Declarative multi-source pipeline definitions
+//!
+//! A [`PipelineDefinition`] is the version-controlled form of a pipeline run.
+//! It declares named sources, the entities built from them and how entities
+//! depend on each other, in YAML or TOML:
+//!
+//! ```yaml
+//! version: 1
+//! name: sales
+//! database: staging.duckdb
+//! output_dir: output
+//! sources:
+//!   orders:
+//!     location: ./data/orders
+//!     pattern: "**/*.jsonl"
+//!     dedup: content
+//!   customers:
+//!     location: s3://acme-raw/customers/
+//!     partition: crm-customers
+//! entities:
+//!   customers:
+//!     source: customers
+//!     stages: [infer, export, generate]
+//!   orders:
+//!     source: orders
+//!     contract: contracts/fact_orders.odcs.yaml
+//!     mapping: mappings/orders.mapping.yaml
+//!     depends_on: [customers]
+//! ```
+//!
+//! Source locations are local paths, `s3://bucket/prefix` URLs or
+//! `/Volumes/<catalog>/<schema>/<volume>` paths. Each source is staged under
+//! its own partition (the source name unless `partition` is set). Relative
+//! paths are resolved against the directory of the definition file.
+//!
+//! [`PipelineDefinition::plan`] turns the definition into a [`PipelinePlan`]:
+//! one `ingest:<source>` node per source and one node per entity running the
+//! entity's remaining stages. An entity node depends on the ingest node of its
+//! source and on the entities listed in `depends_on`.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use petgraph::Graph;
+use petgraph::algo::toposort;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use super::config::{LlmPipelineConfig, PipelineConfig, PipelineStage};
+use super::error::{PipelineError, PipelineResult};
+use crate::staging::{DedupStrategy, SourceType};
+
+/// Current pipeline definition format version
+pub const PIPELINE_DEFINITION_VERSION: u32 = 1;
+
+/// Checkpoint file written in the output directory of each plan node
+const NODE_CHECKPOINT_FILE: &str = "pipeline.checkpoint.json";
+
+/// A declarative pipeline over multiple sources and entities
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineDefinition {
+    /// Format version
+    #[serde(default = "default_version")]
+    pub version: u32,
+    /// Pipeline name
+    pub name: String,
+    /// Staging database path
+    #[serde(default = "default_database")]
+    pub database: PathBuf,
+    /// Root output directory; each entity writes to a subdirectory
+    #[serde(default = "default_output_dir")]
+    pub output_dir: PathBuf,
+    /// Number of staged records sampled for schema inference
+    #[serde(default = "default_sample_size")]
+    pub sample_size: usize,
+    /// Maximum number of nodes run at the same time (0 = number of CPUs)
+    #[serde(default)]
+    pub parallelism: usize,
+    /// LLM configuration for refinement
+    #[serde(default)]
+    pub llm: LlmPipelineConfig,
+    /// Named sources to ingest
+    pub sources: BTreeMap<String, SourceDefinition>,
+    /// Named entities built from the sources
+    #[serde(default)]
+    pub entities: BTreeMap<String, EntityDefinition>,
+}
+
+/// A source of raw JSON files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceDefinition {
+    /// Local path, `s3://bucket/prefix` or `/Volumes/...` path
+    pub location: String,
+    /// File pattern to match
+    #[serde(default = "default_pattern")]
+    pub pattern: String,
+    /// Staging partition key (defaults to the source name)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partition: Option<String>,
+    /// Deduplication strategy (none, path, content, both)
+    #[serde(default, deserialize_with = "deserialize_dedup")]
+    pub dedup: DedupStrategy,
+}
+
+/// An entity inferred, mapped and exported from one source
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityDefinition {
+    /// Name of the source the entity is built from
+    pub source: String,
+    /// Target contract or JSON Schema for the map stage
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contract: Option<PathBuf>,
+    /// Mapping specification applied on top of matcher output
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mapping: Option<PathBuf>,
+    /// Stages to run (empty = all stages after ingest)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stages: Vec<PipelineStage>,
+    /// Entities that must finish before this one starts
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+    /// Sample size override for this entity
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sample_size: Option<usize>,
+}
+
+/// Kind of work a plan node performs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlanNodeKind {
+    /// Ingest a source into the staging database
+    Ingest { source: String },
+    /// Run the stages of an entity
+    Entity { entity: String },
+}
+
+/// A node of a [`PipelinePlan`]
+#[derive(Debug, Clone)]
+pub struct PlanNode {
+    /// Node identifier (`ingest:<source>` or the entity name)
+    pub id: String,
+    /// What the node does
+    pub kind: PlanNodeKind,
+    /// Identifiers of the nodes that must complete first
+    pub depends_on: Vec<String>,
+    /// Pipeline configuration the node runs with
+    pub config: PipelineConfig,
+}
+
+/// Nodes of a pipeline definition in dependency order
+#[derive(Debug, Clone)]
+pub struct PipelinePlan {
+    /// Pipeline name
+    pub name: String,
+    /// Nodes, each listed after all of its dependencies
+    pub nodes: Vec<PlanNode>,
+}
+
+impl PipelinePlan {
+    /// Find a node by identifier
+    pub fn node(&self, id: &str) -> Option<&PlanNode> {
+        self.nodes.iter().find(|n| n.id == id)
+    }
+}
+
+fn default_version() -> u32 {
+    PIPELINE_DEFINITION_VERSION
+}
+
+fn default_database() -> PathBuf {
+    PipelineConfig::default().database
+}
+
+fn default_output_dir() -> PathBuf {
+    PipelineConfig::default().output_dir
+}
+
+fn default_sample_size() -> usize {
+    PipelineConfig::default().sample_size
+}
+
+fn default_pattern() -> String {
+    PipelineConfig::default().pattern
+}
+
+/// Accept the CLI spellings of dedup strategies (`path`, `content`, ...)
+fn deserialize_dedup<'de, D>(deserializer: D) -> Result<DedupStrategy, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    value.parse().map_err(serde::de::Error::custom)
+}
+
+impl SourceDefinition {
+    /// Staging partition key for a source
+    pub fn partition_key(&self, name: &str) -> String {
+        self.partition.clone().unwrap_or_else(|| name.to_string())
+    }
+
+    fn is_remote(&self) -> bool {
+        self.location.starts_with("s3://") || self.location.starts_with("/Volumes/")
+    }
+}
+
+impl EntityDefinition {
+    /// Stages the entity runs
+    ///
+    /// Without explicit stages this is every stage after ingest; the map stage
+    /// is left out when no contract is set.
+    pub fn effective_stages(&self) -> Vec<PipelineStage> {
+        if !self.stages.is_empty() {
+            return self.stages.clone();
+        }
+        PipelineStage::all()
+            .into_iter()
+            .filter(|s| *s != PipelineStage::Ingest)
+            .filter(|s| *s != PipelineStage::Map || self.contract.is_some())
+            .collect()
+    }
+}
+
+impl PipelineDefinition {
+    /// Parse a definition from YAML
+    pub fn from_yaml(yaml: &str) -> PipelineResult<Self> {
+        let definition: Self = serde_yaml::from_str(yaml).map_err(|e| {
+            PipelineError::ConfigError(format!("Failed to parse pipeline definition: {}", e))
+        })?;
+        definition.check_version()?;
+        Ok(definition)
+    }
+
+    /// Parse a definition from TOML
+    pub fn from_toml(content: &str) -> PipelineResult<Self> {
+        let definition: Self = toml::from_str(content).map_err(|e| {
+            PipelineError::ConfigError(format!("Failed to parse pipeline definition: {}", e))
+        })?;
+        definition.check_version()?;
+        Ok(definition)
+    }
+
+    /// Load a definition from a `.yaml`, `.yml` or `.toml` file
+    ///
+    /// Relative paths in the definition are resolved against the file's
+    /// directory.
+    pub fn from_file(path: &Path) -> PipelineResult<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            PipelineError::io_with_path(path, "Failed to read pipeline definition", e)
+        })?;
+        let is_toml = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e.eq_ignore_ascii_case("toml"));
+        let mut definition = if is_toml {
+            Self::from_toml(&content)?
+        } else {
+            Self::from_yaml(&content)?
+        };
+
+        if let Some(base) = path.parent() {
+            definition.resolve_paths(base);
+        }
+        Ok(definition)
+    }
+
+    /// Serialize the definition to YAML
+    pub fn to_yaml(&self) -> PipelineResult<String> {
+        serde_yaml::to_string(self).map_err(|e| {
+            PipelineError::ConfigError(format!("Failed to serialize pipeline definition: {}", e))
+        })
+    }
+
+    fn check_version(&self) -> PipelineResult<()> {
+        if self.version > PIPELINE_DEFINITION_VERSION {
+            return Err(PipelineError::ConfigError(format!(
+                "Pipeline definition version {} is newer than supported version {}",
+                self.version, PIPELINE_DEFINITION_VERSION
+            )));
+        }
+        Ok(())
+    }
+
+    /// Make relative paths absolute against `base`
+    pub fn resolve_paths(&mut self, base: &Path) {
+        let resolve = |path: &mut PathBuf| {
+            if path.is_relative() {
+                *path = base.join(&*path);
+            }
+        };
+
+        resolve(&mut self.database);
+        resolve(&mut self.output_dir);
+        if let Some(ref mut doc_path) = self.llm.doc_path {
+            resolve(doc_path);
+        }
+        for source in self.sources.values_mut() {
+            if !source.is_remote() && Path::new(&source.location).is_relative() {
+                source.location = base.join(&source.location).display().to_string();
+            }
+        }
+        for entity in self.entities.values_mut() {
+            if let Some(ref mut contract) = entity.contract {
+                resolve(contract);
+            }
+            if let Some(ref mut mapping) = entity.mapping {
+                resolve(mapping);
+            }
+        }
+    }
+
+    /// Check the definition for errors
+    ///
+    /// Reports every problem found: unknown sources and dependencies, invalid
+    /// source locations, conflicting partitions, misplaced stages and
+    /// dependency cycles.
+    pub fn validate(&self) -> PipelineResult<()> {
+        let mut errors = Vec::new();
+
+        if self.sources.is_empty() {
+            errors.push("at least one source is required".to_string());
+        }
+
+        let mut partitions: HashMap<String, &str> = HashMap::new();
+        for (name, source) in &self.sources {
+            if let Err(e) = SourceType::parse(&source.location) {
+                errors.push(format!("source '{}': {}", name, e));
+            }
+            let partition = source.partition_key(name);
+            if let Some(other) = partitions.insert(partition.clone(), name) {
+                errors.push(format!(
+                    "sources '{}' and '{}' share partition '{}'",
+                    other, name, partition
+                ));
+            }
+        }
+
+        for (name, entity) in &self.entities {
+            if name.starts_with("ingest:") {
+                errors.push(format!(
+                    "entity '{}': names starting with 'ingest:' are reserved",
+                    name
+                ));
+            }
+            if !self.sources.contains_key(&entity.source) {
+                errors.push(format!(
+                    "entity '{}': unknown source '{}'",
+                    name, entity.source
+                ));
+            }
+            if entity.stages.contains(&PipelineStage::Ingest) {
+                errors.push(format!(
+                    "entity '{}': ingest runs per source and cannot be an entity stage",
+                    name
+                ));
+            }
+            let maps = entity.effective_stages().contains(&PipelineStage::Map);
+            if maps && entity.contract.is_none() {
+                errors.push(format!(
+                    "entity '{}': the map stage requires a contract",
+                    name
+                ));
+            }
+            if entity.mapping.is_some() && entity.contract.is_none() {
+                errors.push(format!("entity '{}': a mapping requires a contract", name));
+            }
+            for dependency in &entity.depends_on {
+                if dependency == name {
+                    errors.push(format!("entity '{}': depends on itself", name));
+                } else if !self.entities.contains_key(dependency) {
+                    errors.push(format!(
+                        "entity '{}': unknown dependency '{}'",
+                        name, dependency
+                    ));
+                }
+            }
+        }
+
+        if errors.is_empty()
+            && let Err(e) = self.ordered_nodes()
+        {
+            errors.push(e);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(PipelineError::ConfigError(format!(
+                "Invalid pipeline definition '{}':\n  {}",
+                self.name,
+                errors.join("\n  ")
+            )))
+        }
+    }
+
+    /// Validate the definition and build its execution plan
+    pub fn plan(&self) -> PipelineResult<PipelinePlan> {
+        self.validate()?;
+
+        let nodes = self
+            .ordered_nodes()
+            .map_err(PipelineError::ConfigError)?
+            .into_iter()
+            .map(|(id, kind, depends_on)| {
+                let config = match &kind {
+                    PlanNodeKind::Ingest { source } => self.ingest_config(source),
+                    PlanNodeKind::Entity { entity } => self.entity_config(entity),
+                };
+                PlanNode {
+                    id,
+                    kind,
+                    depends_on,
+                    config,
+                }
+            })
+            .collect();
+
+        Ok(PipelinePlan {
+            name: self.name.clone(),
+            nodes,
+        })
+    }
+
+    /// Nodes with their dependencies in topological order
+    fn ordered_nodes(&self) -> Result<Vec<(String, PlanNodeKind, Vec<String>)>, String> {
+        let mut nodes = Vec::new();
+        for name in self.sources.keys() {
+            nodes.push((
+                ingest_node_id(name),
+                PlanNodeKind::Ingest {
+                    source: name.clone(),
+                },
+                Vec::new(),
+            ));
+        }
+        for (name, entity) in &self.entities {
+            let mut depends_on = vec![ingest_node_id(&entity.source)];
+            depends_on.extend(entity.depends_on.iter().cloned());
+            nodes.push((
+                name.clone(),
+                PlanNodeKind::Entity {
+                    entity: name.clone(),
+                },
+                depends_on,
+            ));
+        }
+
+        let mut graph: Graph<usize, ()> = Graph::new();
+        let indices: Vec<_> = (0..nodes.len()).map(|i| graph.add_node(i)).collect();
+        let by_id: HashMap<&str, usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, (id, _, _))| (id.as_str(), i))
+            .collect();
+        for (i, (_, _, depends_on)) in nodes.iter().enumerate() {
+            for dependency in depends_on {
+                if let Some(&d) = by_id.get(dependency.as_str()) {
+                    graph.add_edge(indices[d], indices[i], ());
+                }
+            }
+        }
+
+        let order = toposort(&graph, None).map_err(|cycle| {
+            format!(
+                "dependency cycle involving '{}'",
+                nodes[graph[cycle.node_id()]].0
+            )
+        })?;
+
+        let mut slots: Vec<Option<_>> = nodes.into_iter().map(Some).collect();
+        Ok(order
+            .into_iter()
+            .filter_map(|index| slots[graph[index]].take())
+            .collect())
+    }
+
+    fn ingest_config(&self, name: &str) -> PipelineConfig {
+        let source = &self.sources[name];
+        let output_dir = self.output_dir.join("_sources").join(name);
+        PipelineConfig::new()
+            .with_name(name)
+            .with_database(&self.database)
+            .with_source(&source.location)
+            .with_pattern(&source.pattern)
+            .with_dedup(source.dedup)
+            .with_partition(source.partition_key(name))
+            .with_checkpoint_path(output_dir.join(NODE_CHECKPOINT_FILE))
+            .with_output_dir(output_dir)
+            .with_stages(vec![PipelineStage::Ingest])
+    }
+
+    fn entity_config(&self, name: &str) -> PipelineConfig {
+        let entity = &self.entities[name];
+        let source = &self.sources[&entity.source];
+        let output_dir = self.output_dir.join(name);
+
+        let mut config = PipelineConfig::new()
+            .with_name(name)
+            .with_database(&self.database)
+            .with_partition(source.partition_key(&entity.source))
+            .with_sample_size(entity.sample_size.unwrap_or(self.sample_size))
+            .with_llm(self.llm.clone())
+            .with_checkpoint_path(output_dir.join(NODE_CHECKPOINT_FILE))
+            .with_output_dir(output_dir)
+            .with_stages(entity.effective_stages());
+        if let Some(ref contract) = entity.contract {
+            config = config.with_target_schema(contract);
+        }
+        if let Some(ref mapping) = entity.mapping {
+            config = config.with_mapping_spec(mapping);
+        }
+        config
+    }
+}
+
+/// Plan node identifier of a source's ingestion
+pub fn ingest_node_id(source: &str) -> String {
+    format!("ingest:{}", source)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEFINITION: &str = r#"
+name: sales
+database: staging.duckdb
+sources:
+  orders:
+    location: ./data/orders
+    pattern: "*.jsonl"
+    dedup: content
+  customers:
+    location: ./data/customers
+    partition: crm
+entities:
+  customers:
+    source: customers
+    stages: [infer, export]
+  orders:
+    source: orders
+    contract: contracts/orders.odcs.yaml
+    depends_on: [customers]
+"#;
+
+    #[test]
+    fn test_parse_yaml_definition() {
+        let definition = PipelineDefinition::from_yaml(DEFINITION).unwrap();
+        assert_eq!(definition.version, PIPELINE_DEFINITION_VERSION);
+        assert_eq!(definition.sources.len(), 2);
+        assert_eq!(definition.sources["orders"].dedup, DedupStrategy::ByContent);
+        assert_eq!(definition.sources["customers"].pattern, "*.json");
+        assert_eq!(
+            definition.sources["customers"].partition_key("customers"),
+            "crm"
+        );
+        assert_eq!(
+            definition.entities["orders"].effective_stages(),
+            vec![
+                PipelineStage::Infer,
+                PipelineStage::Refine,
+                PipelineStage::Map,
+                PipelineStage::Export,
+                PipelineStage::Generate,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_toml_definition() {
+        let definition = PipelineDefinition::from_toml(
+            r#"
+name = "events"
+parallelism = 2
+
+[sources.clicks]
+location = "s3://acme-raw/clicks/"
+
+[entities.clicks]
+source = "clicks"
+stages = ["infer", "generate"]
+"#,
+        )
+        .unwrap();
+        assert_eq!(definition.parallelism, 2);
+        assert_eq!(
+            definition.sources["clicks"].location,
+            "s3://acme-raw/clicks/"
+        );
+        assert_eq!(definition.entities["clicks"].stages.len(), 2);
+    }
+
+    #[test]
+    fn test_plan_orders_dependencies() {
+        let definition = PipelineDefinition::from_yaml(DEFINITION).unwrap();
+        let plan = definition.plan().unwrap();
+        let position = |id: &str| plan.nodes.iter().position(|n| n.id == id).unwrap();
+
+        assert_eq!(plan.nodes.len(), 4);
+        assert!(position("ingest:customers") < position("customers"));
+        assert!(position("customers") < position("orders"));
+        assert!(position("ingest:orders") < position("orders"));
+
+        let orders = plan.node("orders").unwrap();
+        assert_eq!(orders.depends_on, vec!["ingest:orders", "customers"]);
+        assert_eq!(orders.config.partition.as_deref(), Some("orders"));
+        assert_eq!(orders.config.output_dir, PathBuf::from("output/orders"));
+        assert_eq!(
+            orders.config.checkpoint_file(),
+            PathBuf::from("output/orders").join(NODE_CHECKPOINT_FILE)
+        );
+
+        let ingest = plan.node("ingest:customers").unwrap();
+        assert_eq!(ingest.config.stages, vec![PipelineStage::Ingest]);
+        assert_eq!(ingest.config.partition.as_deref(), Some("crm"));
+    }
+
+    #[test]
+    fn test_validate_reports_errors() {
+        let mut definition = PipelineDefinition::from_yaml(DEFINITION).unwrap();
+        definition.entities.get_mut("customers").unwrap().source = "missing".to_string();
+        definition
+            .entities
+            .get_mut("orders")
+            .unwrap()
+            .depends_on
+            .push("unknown".to_string());
+
+        let message = definition.validate().unwrap_err().to_string();
+        assert!(message.contains("unknown source 'missing'"));
+        assert!(message.contains("unknown dependency 'unknown'"));
+    }
+
+    #[test]
+    fn test_validate_detects_cycles() {
+        let mut definition = PipelineDefinition::from_yaml(DEFINITION).unwrap();
+        definition
+            .entities
+            .get_mut("customers")
+            .unwrap()
+            .depends_on
+            .push("orders".to_string());
+
+        let message = definition.validate().unwrap_err().to_string();
+        assert!(message.contains("dependency cycle"));
+    }
+
+    #[test]
+    fn test_resolve_relative_paths() {
+        let mut definition = PipelineDefinition::from_yaml(DEFINITION).unwrap();
+        definition.sources.get_mut("customers").unwrap().location =
+            "s3://acme/customers/".to_string();
+        definition.resolve_paths(Path::new("/pipelines"));
+
+        assert_eq!(
+            definition.database,
+            PathBuf::from("/pipelines/staging.duckdb")
+        );
+        assert_eq!(
+            definition.sources["orders"].location,
+            Path::new("/pipelines")
+                .join("./data/orders")
+                .display()
+                .to_string()
+        );
+        assert_eq!(
+            definition.sources["customers"].location,
+            "s3://acme/customers/"
+        );
+        assert_eq!(
+            definition.entities["orders"].contract,
+            Some(PathBuf::from("/pipelines/contracts/orders.odcs.yaml"))
+        );
+    }
+}
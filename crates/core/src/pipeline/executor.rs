@@ -1,7 +1,11 @@
 //! Pipeline executor for running the full data pipeline
 
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
 use std::time::Instant;
 
+use serde_json::{Value, json};
 use sha2::{Digest, Sha256};
 use tracing::{debug, error, info, info_span, warn};
 use uuid::Uuid;
@@ -9,11 +13,37 @@ use uuid::Uuid;
 use super::checkpoint::{Checkpoint, PipelineStatus, StageOutput};
 use super::config::{PipelineConfig, PipelineStage};
 use super::error::{PipelineError, PipelineResult};
+use crate::export::{JSONSchemaExporter, ODCSExporter};
+use crate::import::ODCSImporter;
+use crate::inference::{
+    InferenceConfig, InferredSchema, SchemaInferrer, apply_schema_descriptions,
+    contract_from_tables,
+};
+use crate::mapping::{MappingSpec, SchemaMatcher, TransformFormat, generate_transform};
+use crate::staging::{IngestConfig, ParquetExportConfig, ParquetExporter, SourceType, StagingDb};
+
+/// JSON Schema written by the infer stage
+const INFERRED_SCHEMA_FILE: &str = "inferred_schema.json";
+/// Full inference result (types, field statistics, keys) used by the generate stage
+const INFERENCE_RESULT_FILE: &str = "inference_result.json";
+/// JSON Schema written by the refine stage
+const REFINED_SCHEMA_FILE: &str = "refined_schema.json";
+/// Schema mapping written by the map stage
+const MAPPING_FILE: &str = "mapping.json";
+/// Transformation script written by the map stage
+const TRANSFORM_FILE: &str = "transform.sql";
+/// Directory of typed Parquet files written by the export stage
+const EXPORT_DIR: &str = "data";
+/// Parquet data written by the export stage when no schema was inferred
+const EXPORT_FILE: &str = "data.parquet";
+/// Contract written by the generate stage
+const CONTRACT_FILE: &str = "contract.odcs.yaml";
 
 /// Pipeline executor that runs all stages
 pub struct PipelineExecutor {
     config: PipelineConfig,
     checkpoint: Checkpoint,
+    database_lock: Option<Arc<Mutex<()>>>,
 }
 
 impl PipelineExecutor {
@@ -30,13 +60,30 @@ impl PipelineExecutor {
             Checkpoint::new(&run_id, &config_hash)
         };
 
-        Ok(Self { config, checkpoint })
+        Ok(Self {
+            config,
+            checkpoint,
+            database_lock: None,
+        })
     }
 
     /// Create executor with existing checkpoint (for resume)
     pub fn with_checkpoint(config: PipelineConfig, checkpoint: Checkpoint) -> PipelineResult<Self> {
         config.validate().map_err(PipelineError::ConfigError)?;
-        Ok(Self { config, checkpoint })
+        Ok(Self {
+            config,
+            checkpoint,
+            database_lock: None,
+        })
+    }
+
+    /// Share a lock that serializes access to the staging database
+    ///
+    /// Executors running concurrently against the same database file must share
+    /// one lock; it is held only while a stage has the database open.
+    pub fn with_database_lock(mut self, lock: Arc<Mutex<()>>) -> Self {
+        self.database_lock = Some(lock);
+        self
     }
 
     /// Get the current checkpoint
@@ -79,15 +126,30 @@ impl PipelineExecutor {
             return self.dry_run(&stages);
         }
 
+        std::fs::create_dir_all(&self.config.output_dir).map_err(|e| {
+            PipelineError::io_with_path(
+                &self.config.output_dir,
+                "Failed to create output directory",
+                e,
+            )
+        })?;
+
         // Run each stage
         for stage in &stages {
-            // Skip if already completed (resume mode)
+            // Skip if already completed (resume mode), unless its artifacts are gone
             if self.checkpoint.is_stage_completed(*stage) {
-                debug!(stage = stage.name(), "Stage already completed, skipping");
-                if self.config.verbose {
-                    eprintln!("Stage {} already completed, skipping", stage.name());
+                if self.stage_outputs_exist(*stage) {
+                    debug!(stage = stage.name(), "Stage already completed, skipping");
+                    if self.config.verbose {
+                        eprintln!("Stage {} already completed, skipping", stage.name());
+                    }
+                    continue;
                 }
-                continue;
+                warn!(
+                    stage = stage.name(),
+                    "Stage outputs missing, running stage again"
+                );
+                self.checkpoint.reset_stage(*stage);
             }
 
             // Check if stage should be skipped
@@ -191,51 +253,104 @@ impl PipelineExecutor {
 
         debug!(source = %source.display(), pattern = %self.config.pattern, "Starting ingestion");
 
-        // In a real implementation, this would use the staging module
-        // For now, we just validate the source exists
-        if !source.exists() {
+        let source_type = parse_source(source)?;
+        if let SourceType::Local(ref path) = source_type
+            && !path.exists()
+        {
             warn!(source = %source.display(), "Source path not found");
             return Err(PipelineError::FileNotFound(source.clone()));
         }
 
-        let mut output = StageOutput::success();
-        output = output.with_metadata("source", serde_json::json!(source.display().to_string()));
-        output = output.with_metadata("pattern", serde_json::json!(self.config.pattern));
-
-        // Count files matching pattern
-        let pattern = source.join(&self.config.pattern);
-        let files: Vec<_> = glob::glob(pattern.to_str().unwrap_or(""))
-            .map(|paths| paths.filter_map(|p| p.ok()).collect())
-            .unwrap_or_default();
+        let mut builder = IngestConfig::builder()
+            .source_type(source_type)
+            .pattern(&self.config.pattern)
+            .dedup(self.config.dedup);
+        if let Some(ref partition) = self.config.partition {
+            builder = builder.partition(partition);
+        }
+        let ingest_config = builder
+            .build()
+            .map_err(|e| PipelineError::ConfigError(e.to_string()))?;
 
-        output = output.with_metadata("files_found", serde_json::json!(files.len()));
+        // Already staged files are skipped by the default dedup strategy
+        let db = self.open_database()?;
+        let stats = db.ingest(&ingest_config)?;
 
-        debug!(files_found = files.len(), "Ingestion scan complete");
+        debug!(
+            files_processed = stats.files_processed,
+            records_ingested = stats.records_ingested,
+            "Ingestion complete"
+        );
         if self.config.verbose {
-            eprintln!("  Found {} files matching pattern", files.len());
+            eprintln!(
+                "  Ingested {} records from {} files ({} skipped)",
+                stats.records_ingested, stats.files_processed, stats.files_skipped
+            );
+        }
+        for message in &stats.errors {
+            warn!(error = %message, "Ingestion error");
         }
 
-        Ok(output)
+        Ok(StageOutput::success()
+            .with_file(&self.config.database)
+            .with_metadata("source", json!(source.display().to_string()))
+            .with_metadata("pattern", json!(self.config.pattern))
+            .with_metadata("files_processed", json!(stats.files_processed))
+            .with_metadata("files_skipped", json!(stats.files_skipped))
+            .with_metadata("records_ingested", json!(stats.records_ingested))
+            .with_metadata("bytes_processed", json!(stats.bytes_processed))
+            .with_metadata("errors", json!(stats.errors_count)))
     }
 
     /// Run the infer stage
     fn run_infer(&self) -> PipelineResult<StageOutput> {
-        // Get schema output path
-        let schema_path = self.config.output_dir.join("inferred_schema.json");
+        let schema_path = self.artifact(INFERRED_SCHEMA_FILE);
+        let result_path = self.artifact(INFERENCE_RESULT_FILE);
         debug!(output = %schema_path.display(), "Running schema inference");
 
-        let mut output = StageOutput::success();
-        output = output.with_file(&schema_path);
-        output = output.with_metadata(
-            "schema_path",
-            serde_json::json!(schema_path.display().to_string()),
-        );
+        let samples = self
+            .open_database()?
+            .get_sample(self.config.sample_size, self.config.partition.as_deref())?;
+        if samples.is_empty() {
+            return Err(PipelineError::InferenceError(
+                "No records found in staging database".to_string(),
+            ));
+        }
 
-        Ok(output)
+        let config = InferenceConfig::builder()
+            .sample_size(self.config.sample_size)
+            .build();
+        let mut inferrer = SchemaInferrer::with_config(config);
+        for sample in &samples {
+            inferrer.add_json(sample)?;
+        }
+        let stats = inferrer.stats();
+        let schema = inferrer.finalize()?;
+
+        write_json(&schema_path, &schema.to_json_schema())?;
+        write_json(&result_path, &schema)?;
+
+        if self.config.verbose {
+            eprintln!(
+                "  Inferred {} fields from {} records",
+                stats.fields_discovered, stats.records_processed
+            );
+        }
+
+        Ok(StageOutput::success()
+            .with_file(&schema_path)
+            .with_file(&result_path)
+            .with_metadata("schema_path", json!(schema_path.display().to_string()))
+            .with_metadata("records_sampled", json!(stats.records_processed))
+            .with_metadata("fields_discovered", json!(stats.fields_discovered))
+            .with_metadata("variants", json!(schema.variants.len())))
     }
 
     /// Run the refine stage
+    #[cfg(feature = "llm")]
     fn run_refine(&self) -> PipelineResult<StageOutput> {
+        use crate::llm::{LlmClient, create_client, refine_schema};
+
         if !self.config.llm.is_enabled() {
             debug!("LLM not configured, skipping refinement");
             return Ok(StageOutput::skipped("LLM not configured"));
@@ -247,15 +362,60 @@ impl PipelineExecutor {
             "Running LLM refinement"
         );
 
-        let refined_path = self.config.output_dir.join("refined_schema.json");
+        let config = self
+            .config
+            .llm
+            .refinement_config()
+            .map_err(PipelineError::LlmError)?;
+        let schema = read_json(&self.artifact(INFERRED_SCHEMA_FILE))?;
+        let samples = self
+            .open_database()?
+            .get_sample(config.max_samples, self.config.partition.as_deref())?;
+
+        let runtime = tokio::runtime::Runtime::new()?;
+        let result = runtime.block_on(async {
+            let client = create_client(&config)?;
+            if !client.is_ready().await {
+                return Err(PipelineError::LlmError(
+                    "LLM server not reachable or model not available".to_string(),
+                ));
+            }
+            Ok(refine_schema(&client, &schema, &config, Some(samples)).await?)
+        })?;
+
+        for warning in &result.warnings {
+            warn!(warning = %warning, "Refinement warning");
+        }
 
-        let mut output = StageOutput::success();
-        output = output.with_file(&refined_path);
-        output = output.with_metadata("model", serde_json::json!(self.config.llm.model));
+        let refined_path = self.artifact(REFINED_SCHEMA_FILE);
+        write_json(&refined_path, &result.schema)?;
+
+        let mut output = StageOutput::success()
+            .with_file(&refined_path)
+            .with_metadata("model", json!(self.config.llm.model))
+            .with_metadata("refined", json!(result.was_refined))
+            .with_metadata("warnings", json!(result.warnings.len()));
+        if let Some(ref cache_path) = self.config.llm.cache_path {
+            output = output
+                .with_metadata("cache", json!(cache_path.display().to_string()))
+                .with_metadata("cache_mode", json!(self.config.llm.cache_mode));
+        }
 
         Ok(output)
     }
 
+    /// Run the refine stage
+    #[cfg(not(feature = "llm"))]
+    fn run_refine(&self) -> PipelineResult<StageOutput> {
+        if !self.config.llm.is_enabled() {
+            debug!("LLM not configured, skipping refinement");
+            return Ok(StageOutput::skipped("LLM not configured"));
+        }
+        Err(PipelineError::LlmError(
+            "LLM refinement requires the 'llm' feature".to_string(),
+        ))
+    }
+
     /// Run the map stage
     fn run_map(&self) -> PipelineResult<StageOutput> {
         let target_schema = self
@@ -271,48 +431,223 @@ impl PipelineExecutor {
             return Err(PipelineError::FileNotFound(target_schema.clone()));
         }
 
-        let mapping_path = self.config.output_dir.join("mapping.json");
-        let transform_path = self.config.output_dir.join("transform.sql");
+        let source = self.current_schema()?;
+        let target = load_target_schema(target_schema)?;
+        let mut mapping = SchemaMatcher::new().match_schemas(&source, &target)?;
+
+        // Manual decisions in the mapping spec take precedence over matcher output
+        if let Some(ref spec_path) = self.config.mapping_spec {
+            let content = std::fs::read_to_string(spec_path).map_err(|e| {
+                PipelineError::io_with_path(spec_path, "Failed to read mapping spec", e)
+            })?;
+            let spec = MappingSpec::from_yaml(&content)?;
+            mapping = spec.resolve(&mapping, &source, &target)?;
+        }
 
-        let mut output = StageOutput::success();
-        output = output.with_file(&mapping_path);
-        output = output.with_file(&transform_path);
-        output = output.with_metadata(
-            "target_schema",
-            serde_json::json!(target_schema.display().to_string()),
-        );
+        let target_table = target_schema
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|n| n.split('.').next())
+            .unwrap_or("target");
+        let script = generate_transform(
+            &mapping,
+            TransformFormat::Sql,
+            &self.table_name(),
+            target_table,
+        )?;
+
+        let mapping_path = self.artifact(MAPPING_FILE);
+        let transform_path = self.artifact(TRANSFORM_FILE);
+        write_json(&mapping_path, &mapping)?;
+        write_file(&transform_path, &script)?;
 
         debug!(
             mapping = %mapping_path.display(),
             transform = %transform_path.display(),
             "Mapping complete"
         );
+        if self.config.verbose {
+            eprintln!(
+                "  Mapped {} of {} target fields ({:.0}% compatible)",
+                mapping.stats.direct_mapped + mapping.stats.transform_mapped,
+                mapping.stats.target_fields,
+                mapping.compatibility_score * 100.0
+            );
+        }
+
+        let mut output = StageOutput::success()
+            .with_file(&mapping_path)
+            .with_file(&transform_path)
+            .with_metadata("target_schema", json!(target_schema.display().to_string()))
+            .with_metadata("direct_mappings", json!(mapping.direct_mappings.len()))
+            .with_metadata("transformations", json!(mapping.transformations.len()))
+            .with_metadata("gaps", json!(mapping.gaps.len()))
+            .with_metadata("compatibility_score", json!(mapping.compatibility_score));
+        if let Some(ref spec_path) = self.config.mapping_spec {
+            output = output.with_metadata("mapping_spec", json!(spec_path.display().to_string()));
+        }
 
         Ok(output)
     }
 
     /// Run the export stage
+    ///
+    /// Records are projected through the inferred schema into typed Parquet
+    /// files. Without an inference result the column types follow the merged
+    /// structure of the staged JSON instead.
     fn run_export(&self) -> PipelineResult<StageOutput> {
-        let export_path = self.config.output_dir.join("data.parquet");
+        let result_path = self.artifact(INFERENCE_RESULT_FILE);
+        if !result_path.exists() {
+            return self.run_untyped_export();
+        }
+
+        let export_dir = self.artifact(EXPORT_DIR);
+        debug!(output = %export_dir.display(), format = "parquet", "Running typed export");
+
+        let schema: InferredSchema = serde_json::from_value(read_json(&result_path)?)?;
+        let mut config = ParquetExportConfig::new(&export_dir);
+        if let Some(ref partition) = self.config.partition {
+            config = config.with_partition(partition);
+        }
+        let result =
+            ParquetExporter::from_inferred(&schema, config).export(&self.open_database()?)?;
+
+        for message in &result.errors {
+            warn!(error = %message, "Export error");
+        }
+        if result.records_written == 0 {
+            warn!("No staged records to export");
+        }
+        if self.config.verbose {
+            eprintln!(
+                "  Exported {} records to {} files ({} skipped)",
+                result.records_written,
+                result.files.len(),
+                result.records_skipped
+            );
+        }
+
+        Ok(StageOutput::success()
+            .with_files(result.files)
+            .with_metadata("format", json!("parquet"))
+            .with_metadata("typed", json!(true))
+            .with_metadata("records_exported", json!(result.records_written))
+            .with_metadata("records_skipped", json!(result.records_skipped))
+            .with_metadata("bytes_written", json!(result.bytes_written)))
+    }
+
+    /// Export staged records with column types taken from the JSON structure
+    fn run_untyped_export(&self) -> PipelineResult<StageOutput> {
+        let export_path = self.artifact(EXPORT_FILE);
         debug!(output = %export_path.display(), format = "parquet", "Running export");
 
-        let mut output = StageOutput::success();
-        output = output.with_file(&export_path);
-        output = output.with_metadata("format", serde_json::json!("parquet"));
+        let records = self.open_database()?.export_parquet(
+            &export_path.display().to_string(),
+            self.config.partition.as_deref(),
+        )?;
+
+        let mut output = StageOutput::success()
+            .with_metadata("format", json!("parquet"))
+            .with_metadata("typed", json!(false))
+            .with_metadata("records_exported", json!(records));
+        if records > 0 {
+            output = output.with_file(&export_path);
+        } else {
+            warn!("No staged records to export");
+        }
+        if self.config.verbose {
+            eprintln!("  Exported {} records", records);
+        }
 
         Ok(output)
     }
 
     /// Run the generate stage
     fn run_generate(&self) -> PipelineResult<StageOutput> {
-        let contract_path = self.config.output_dir.join("contract.odcs.yaml");
+        let contract_path = self.artifact(CONTRACT_FILE);
         debug!(output = %contract_path.display(), format = "odcs", "Generating contract");
 
-        let mut output = StageOutput::success();
-        output = output.with_file(&contract_path);
-        output = output.with_metadata("format", serde_json::json!("odcs"));
+        let result_path = self.artifact(INFERENCE_RESULT_FILE);
+        if !result_path.exists() {
+            return Err(PipelineError::MissingInput(
+                "inferred schema (run the infer stage first)".to_string(),
+            ));
+        }
+        let schema: InferredSchema = serde_json::from_value(read_json(&result_path)?)?;
 
-        Ok(output)
+        let name = self.table_name();
+        let mut tables = schema.to_variant_tables(&name);
+        apply_schema_descriptions(&mut tables, &self.current_schema()?);
+
+        let yaml = if tables.len() > 1 {
+            ODCSExporter::export_contract(&contract_from_tables(&name, &tables))
+        } else {
+            ODCSExporter::export_table(&tables[0], "odcs_v3_1_0")
+        };
+        write_file(&contract_path, &yaml)?;
+
+        let columns: usize = tables.iter().map(|t| t.columns.len()).sum();
+        Ok(StageOutput::success()
+            .with_file(&contract_path)
+            .with_metadata("format", json!("odcs"))
+            .with_metadata("tables", json!(tables.len()))
+            .with_metadata("columns", json!(columns)))
+    }
+
+    /// Open the staging database, creating its tables if needed
+    ///
+    /// Holds the shared database lock (if any) until the handle is dropped.
+    fn open_database(&self) -> PipelineResult<DatabaseHandle<'_>> {
+        let guard = self
+            .database_lock
+            .as_ref()
+            .map(|lock| lock.lock().unwrap_or_else(|e| e.into_inner()));
+        let db = StagingDb::open(&self.config.database.display().to_string())?;
+        if !db.is_initialized()? {
+            db.init()?;
+        }
+        Ok(DatabaseHandle { db, _guard: guard })
+    }
+
+    /// Path of a stage artifact in the output directory
+    fn artifact(&self, name: &str) -> PathBuf {
+        self.config.output_dir.join(name)
+    }
+
+    /// Name for the staged data in generated scripts and contracts
+    fn table_name(&self) -> String {
+        self.config
+            .name
+            .clone()
+            .or_else(|| self.config.partition.clone())
+            .unwrap_or_else(|| "staged_data".to_string())
+    }
+
+    /// The refined schema when the refine stage produced one, else the inferred schema
+    fn current_schema(&self) -> PipelineResult<Value> {
+        let refined = self.artifact(REFINED_SCHEMA_FILE);
+        let refine_ran = self
+            .checkpoint
+            .get_stage_output(PipelineStage::Refine)
+            .is_some_and(|output| !output.skipped);
+        if refine_ran && refined.exists() {
+            return read_json(&refined);
+        }
+
+        let inferred = self.artifact(INFERRED_SCHEMA_FILE);
+        if !inferred.exists() {
+            return Err(PipelineError::MissingInput(
+                "inferred schema (run the infer stage first)".to_string(),
+            ));
+        }
+        read_json(&inferred)
+    }
+
+    /// Check that the artifacts of a completed stage are still on disk
+    fn stage_outputs_exist(&self, stage: PipelineStage) -> bool {
+        self.checkpoint
+            .get_stage_output(stage)
+            .is_none_or(|output| output.files.iter().all(|f| f.exists()))
     }
 
     /// Check if a stage should be skipped
@@ -365,14 +700,28 @@ impl PipelineExecutor {
                     .source
                     .as_ref()
                     .ok_or_else(|| PipelineError::MissingInput("source path".to_string()))?;
-                if !source.exists() {
+                if let SourceType::Local(ref path) = parse_source(source)?
+                    && !path.exists()
+                {
                     return Err(PipelineError::FileNotFound(source.clone()));
                 }
             }
+            PipelineStage::Refine => {
+                // Replay needs the recordings, not the model
+                if self.config.llm.is_replay()
+                    && let Some(ref cache_path) = self.config.llm.cache_path
+                    && !cache_path.exists()
+                {
+                    return Err(PipelineError::FileNotFound(cache_path.clone()));
+                }
+            }
             PipelineStage::Map => {
-                if let Some(ref target) = self.config.target_schema {
-                    if !target.exists() {
-                        return Err(PipelineError::FileNotFound(target.clone()));
+                for path in [&self.config.target_schema, &self.config.mapping_spec]
+                    .into_iter()
+                    .flatten()
+                {
+                    if !path.exists() {
+                        return Err(PipelineError::FileNotFound(path.clone()));
                     }
                 }
             }
@@ -383,7 +732,7 @@ impl PipelineExecutor {
 
     /// Save checkpoint to disk
     fn save_checkpoint(&self) -> PipelineResult<()> {
-        let path = Checkpoint::default_path(&self.config.database);
+        let path = self.config.checkpoint_file();
         self.checkpoint.save(&path)
     }
 
@@ -393,7 +742,7 @@ impl PipelineExecutor {
         run_id: &str,
         config_hash: &str,
     ) -> PipelineResult<Checkpoint> {
-        let path = Checkpoint::default_path(&config.database);
+        let path = config.checkpoint_file();
 
         if path.exists() {
             let checkpoint = Checkpoint::load(&path)?;
@@ -420,7 +769,7 @@ impl PipelineExecutor {
     }
 
     /// Hash the config for change detection
-    fn hash_config(config: &PipelineConfig) -> String {
+    pub(super) fn hash_config(config: &PipelineConfig) -> String {
         let mut hasher = Sha256::new();
         hasher.update(config.database.display().to_string().as_bytes());
         if let Some(ref source) = config.source {
@@ -430,10 +779,66 @@ impl PipelineExecutor {
         if let Some(ref partition) = config.partition {
             hasher.update(partition.as_bytes());
         }
+        hasher.update(config.output_dir.display().to_string().as_bytes());
+        if let Some(ref target) = config.target_schema {
+            hasher.update(target.display().to_string().as_bytes());
+        }
+        if let Some(ref spec) = config.mapping_spec {
+            hasher.update(spec.display().to_string().as_bytes());
+        }
+        hasher.update(config.sample_size.to_string().as_bytes());
         format!("{:x}", hasher.finalize())
     }
 }
 
+/// Staging database connection, holding the shared database lock while open
+struct DatabaseHandle<'a> {
+    db: StagingDb,
+    _guard: Option<MutexGuard<'a, ()>>,
+}
+
+impl Deref for DatabaseHandle<'_> {
+    type Target = StagingDb;
+
+    fn deref(&self) -> &StagingDb {
+        &self.db
+    }
+}
+
+/// Parse the configured source into a staging source type
+fn parse_source(source: &Path) -> PipelineResult<SourceType> {
+    SourceType::parse(&source.display().to_string()).map_err(PipelineError::ConfigError)
+}
+
+/// Load a target schema from a JSON Schema file or an ODCS contract
+fn load_target_schema(path: &Path) -> PipelineResult<Value> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| PipelineError::io_with_path(path, "Failed to read target schema", e))?;
+    let name = path.to_string_lossy();
+    if name.ends_with(".odcs.yaml") || name.ends_with(".odcs.yml") {
+        let (table, _) = ODCSImporter::new()
+            .parse_table(&content)
+            .map_err(|e| PipelineError::MappingError(format!("{}: {}", path.display(), e)))?;
+        return Ok(JSONSchemaExporter::export_table(&table));
+    }
+    Ok(serde_json::from_str(&content)?)
+}
+
+fn read_json(path: &Path) -> PipelineResult<Value> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| PipelineError::io_with_path(path, "Failed to read artifact", e))?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+fn write_json(path: &Path, value: &impl serde::Serialize) -> PipelineResult<()> {
+    write_file(path, &serde_json::to_string_pretty(value)?)
+}
+
+fn write_file(path: &Path, content: &str) -> PipelineResult<()> {
+    std::fs::write(path, content)
+        .map_err(|e| PipelineError::io_with_path(path, "Failed to write artifact", e))
+}
+
 /// Report from a pipeline run
 #[derive(Debug, Clone)]
 pub struct PipelineReport {
@@ -521,6 +926,52 @@ mod tests {
         assert_eq!(executor.checkpoint().status, PipelineStatus::Running);
     }
 
+    #[test]
+    fn test_pipeline_run_writes_artifacts() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("input");
+        std::fs::create_dir(&source).unwrap();
+        std::fs::write(
+            source.join("orders.json"),
+            "{\"id\": 1, \"email\": \"a@example.com\"}\n{\"id\": 2, \"email\": \"b@example.com\"}\n",
+        )
+        .unwrap();
+
+        let config = PipelineConfig::new()
+            .with_source(&source)
+            .with_pattern("*.json")
+            .with_database(temp.path().join("staging.duckdb"))
+            .with_output_dir(temp.path().join("output"))
+            .with_stages(vec![
+                PipelineStage::Ingest,
+                PipelineStage::Infer,
+                PipelineStage::Export,
+                PipelineStage::Generate,
+            ]);
+
+        let mut executor = PipelineExecutor::new(config.clone()).unwrap();
+        let report = executor.run().unwrap();
+        assert!(report.is_success());
+
+        let ingest = &report.outputs["ingest"];
+        assert_eq!(ingest.metadata["records_ingested"], 2);
+        assert_eq!(report.outputs["export"].metadata["records_exported"], 2);
+        assert!(report.outputs["infer"].files.iter().all(|f| f.exists()));
+        assert!(report.outputs["export"].files.iter().all(|f| f.exists()));
+
+        let contract =
+            std::fs::read_to_string(temp.path().join("output").join(CONTRACT_FILE)).unwrap();
+        assert!(contract.contains("email"));
+
+        // Completed stages are skipped when resuming a failed run
+        let mut checkpoint = executor.checkpoint().clone();
+        checkpoint.fail("interrupted");
+        let mut resumed =
+            PipelineExecutor::with_checkpoint(config.with_resume(true), checkpoint).unwrap();
+        let report = resumed.run().unwrap();
+        assert_eq!(report.outputs["ingest"].metadata["records_ingested"], 2);
+    }
+
     #[test]
     fn test_config_hash() {
         let config1 = PipelineConfig::new()
@@ -64,14 +64,35 @@
 //! let mut executor = PipelineExecutor::new(config)?;
 //! let report = executor.run()?; // Validates but doesn't execute
 //! ```
+//!
+//! # Pipeline Definitions
+//!
+//! Multi-source pipelines are declared in YAML or TOML files and run as a
+//! dependency graph, with independent branches running in parallel:
+//!
+//! ```rust,ignore
+//! let definition = PipelineDefinition::from_file(Path::new("pipeline.yaml"))?;
+//! let report = DagExecutor::from_definition(&definition)?
+//!     .with_resume(true)
+//!     .run()?;
+//!
+//! report.print_summary();
+//! ```
 
 mod checkpoint;
 mod config;
+mod dag;
+mod definition;
 mod error;
 mod executor;
 
 pub use checkpoint::{Checkpoint, PipelineStatus, StageOutput};
 pub use config::{LlmPipelineConfig, PipelineConfig, PipelineStage};
+pub use dag::{DagExecutor, DagReport, NodeReport, NodeStatus};
+pub use definition::{
+    EntityDefinition, PIPELINE_DEFINITION_VERSION, PipelineDefinition, PipelinePlan, PlanNode,
+    PlanNodeKind, SourceDefinition, ingest_node_id,
+};
 pub use error::{PipelineError, PipelineResult};
 pub use executor::{PipelineExecutor, PipelineReport};
 
@@ -165,6 +165,14 @@ impl ProcessingBatch {
         matches!(self.status, BatchStatus::Running | BatchStatus::Failed)
     }
 
+    /// Check if a resume may ingest into `partition`
+    ///
+    /// A resumed batch keeps the partition it was started with; no partition
+    /// means the batch's own.
+    pub fn accepts_partition(&self, partition: Option<&str>) -> bool {
+        partition.is_none() || partition == self.partition_key.as_deref()
+    }
+
     /// Get duration in seconds (if started)
     pub fn duration_seconds(&self) -> Option<i64> {
         let started = self.started_at?;
@@ -5,6 +5,7 @@
 //! - AWS S3 Tables
 //! - Databricks Unity Catalog
 //! - AWS Glue
+//! - Local filesystem warehouse (development and tests)
 //!
 //! # Example
 //!
@@ -77,6 +78,16 @@ pub enum CatalogConfig {
         /// Optional AWS credentials profile
         profile: Option<String>,
     },
+
+    /// Catalog kept in a local warehouse directory
+    ///
+    /// Tables are stored under `<warehouse>/<namespace>/<table>`. The latest
+    /// metadata file of every table found in the warehouse is registered when
+    /// the catalog is opened.
+    Filesystem {
+        /// Warehouse directory
+        warehouse: String,
+    },
 }
 
 /// Errors that can occur during catalog operations
@@ -324,6 +335,27 @@ impl IcebergCatalog {
                  Enable it with: --features iceberg-glue"
                     .to_string(),
             )),
+
+            CatalogConfig::Filesystem { warehouse } => {
+                use iceberg::memory::{MEMORY_CATALOG_WAREHOUSE, MemoryCatalogBuilder};
+
+                let root = warehouse_root(warehouse)?;
+                let props = HashMap::from([(
+                    MEMORY_CATALOG_WAREHOUSE.to_string(),
+                    root.display().to_string(),
+                )]);
+
+                let catalog = MemoryCatalogBuilder::default()
+                    .load("filesystem", props)
+                    .await
+                    .map_err(|e| CatalogError::ConnectionError(e.to_string()))?;
+                register_warehouse_tables(&catalog, &root).await?;
+
+                Ok(Self {
+                    config,
+                    inner: Arc::new(catalog),
+                })
+            }
         }
     }
 
@@ -331,6 +363,105 @@ impl IcebergCatalog {
     pub fn config(&self) -> &CatalogConfig {
         &self.config
     }
+
+    /// Default storage location of a table
+    ///
+    /// Only catalogs that own their warehouse directory (REST and filesystem)
+    /// can place new tables.
+    pub fn table_location(&self, identifier: &TableIdentifier) -> CatalogResult<String> {
+        match &self.config {
+            CatalogConfig::Rest { warehouse, .. } => Ok(format!(
+                "{}/{}/{}",
+                warehouse, identifier.namespace, identifier.name
+            )),
+            CatalogConfig::Filesystem { warehouse } => Ok(format!(
+                "{}/{}/{}",
+                warehouse_root(warehouse)?.display(),
+                identifier.namespace,
+                identifier.name
+            )),
+            _ => Err(CatalogError::ConfigError(
+                "Unsupported catalog type for table creation".to_string(),
+            )),
+        }
+    }
+}
+
+/// Create a filesystem warehouse directory and return its absolute path
+fn warehouse_root(warehouse: &str) -> CatalogResult<std::path::PathBuf> {
+    std::fs::create_dir_all(warehouse).map_err(|e| {
+        CatalogError::IoError(format!("Failed to create warehouse {}: {}", warehouse, e))
+    })?;
+    std::fs::canonicalize(warehouse)
+        .map_err(|e| CatalogError::IoError(format!("Invalid warehouse {}: {}", warehouse, e)))
+}
+
+/// Register the tables of a filesystem warehouse with its catalog
+///
+/// Each `<namespace>/<table>/metadata` directory is registered with its latest
+/// `*.metadata.json` file; metadata file names start with a zero-padded
+/// version, so the latest sorts last.
+#[cfg(feature = "iceberg")]
+async fn register_warehouse_tables(
+    catalog: &dyn iceberg::Catalog,
+    root: &std::path::Path,
+) -> CatalogResult<()> {
+    use iceberg::{NamespaceIdent, TableIdent};
+
+    let read_dir = |path: &std::path::Path| {
+        std::fs::read_dir(path)
+            .map_err(|e| CatalogError::IoError(format!("Failed to read {}: {}", path.display(), e)))
+    };
+
+    for namespace in read_dir(root)? {
+        let namespace = namespace.map_err(|e| CatalogError::IoError(e.to_string()))?;
+        if !namespace.path().is_dir() {
+            continue;
+        }
+        let ns_name = namespace.file_name().to_string_lossy().to_string();
+        let ns_ident = NamespaceIdent::new(ns_name.clone());
+
+        for table in read_dir(&namespace.path())? {
+            let table = table.map_err(|e| CatalogError::IoError(e.to_string()))?;
+            let metadata_dir = table.path().join("metadata");
+            if !metadata_dir.is_dir() {
+                continue;
+            }
+            let latest = read_dir(&metadata_dir)?
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|n| n.to_str())
+                        .is_some_and(|n| n.ends_with(".metadata.json"))
+                })
+                .max();
+            let Some(latest) = latest else {
+                continue;
+            };
+
+            if !catalog
+                .namespace_exists(&ns_ident)
+                .await
+                .map_err(|e| CatalogError::IcebergError(e.to_string()))?
+            {
+                catalog
+                    .create_namespace(&ns_ident, HashMap::new())
+                    .await
+                    .map_err(|e| CatalogError::IcebergError(e.to_string()))?;
+            }
+
+            let table_ident = TableIdent::new(
+                ns_ident.clone(),
+                table.file_name().to_string_lossy().to_string(),
+            );
+            catalog
+                .register_table(&table_ident, latest.display().to_string())
+                .await
+                .map_err(|e| CatalogError::IcebergError(e.to_string()))?;
+        }
+    }
+
+    Ok(())
 }
 
 #[cfg(feature = "iceberg")]
@@ -511,6 +642,16 @@ mod tests {
         assert!(json.contains("staging_db"));
     }
 
+    #[test]
+    fn test_catalog_config_serialize_filesystem() {
+        let config = CatalogConfig::Filesystem {
+            warehouse: "./warehouse".to_string(),
+        };
+
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(json, r#"{"type":"filesystem","warehouse":"./warehouse"}"#);
+    }
+
     #[test]
     fn test_catalog_config_deserialize_rest() {
         let json = r#"{
@@ -149,6 +149,57 @@ pub struct IngestConfig {
     pub resume: bool,
     /// Batch ID for resume (auto-generated if not provided)
     pub batch_id: Option<String>,
+    /// Rejected records tolerated before the batch fails
+    #[serde(default)]
+    pub error_budget: ErrorBudget,
+}
+
+/// Number or share of rejected records a batch tolerates before it fails
+///
+/// Rejected records are quarantined as dead letters; unreadable files count
+/// as one error each. The default budget is unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorBudget {
+    /// Maximum number of errors
+    pub max_errors: Option<usize>,
+    /// Maximum share of errors among all records read (0.0-1.0)
+    pub max_error_rate: Option<f64>,
+}
+
+impl ErrorBudget {
+    /// Whether the budget limits errors at all
+    pub fn is_unlimited(&self) -> bool {
+        self.max_errors.is_none() && self.max_error_rate.is_none()
+    }
+
+    /// Check the error count while a batch is running
+    ///
+    /// Only the absolute limit is checked; rates are checked at the end of the batch.
+    pub fn check_count(&self, errors: usize) -> Result<(), String> {
+        match self.max_errors {
+            Some(max) if errors > max => {
+                Err(format!("{} errors exceed the budget of {}", errors, max))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Check the errors of a finished batch
+    pub fn check(&self, errors: usize, records: usize) -> Result<(), String> {
+        self.check_count(errors)?;
+        let total = errors + records;
+        match self.max_error_rate {
+            Some(max) if total > 0 && errors as f64 / total as f64 > max => Err(format!(
+                "error rate {:.2}% ({} of {} records) exceeds the budget of {:.2}%",
+                errors as f64 / total as f64 * 100.0,
+                errors,
+                total,
+                max * 100.0
+            )),
+            _ => Ok(()),
+        }
+    }
 }
 
 impl Default for IngestConfig {
@@ -162,6 +213,7 @@ impl Default for IngestConfig {
             dedup: DedupStrategy::ByPath,
             resume: false,
             batch_id: None,
+            error_budget: ErrorBudget::default(),
         }
     }
 }
@@ -184,6 +236,7 @@ pub struct IngestConfigBuilder {
     dedup: Option<DedupStrategy>,
     resume: bool,
     batch_id: Option<String>,
+    error_budget: ErrorBudget,
 }
 
 impl IngestConfigBuilder {
@@ -241,6 +294,18 @@ impl IngestConfigBuilder {
         self
     }
 
+    /// Fail the batch when more than this many records are rejected
+    pub fn max_errors(mut self, max_errors: usize) -> Self {
+        self.error_budget.max_errors = Some(max_errors);
+        self
+    }
+
+    /// Fail the batch when the share of rejected records exceeds this rate
+    pub fn max_error_rate(mut self, max_error_rate: f64) -> Self {
+        self.error_budget.max_error_rate = Some(max_error_rate);
+        self
+    }
+
     /// Build the IngestConfig
     pub fn build(self) -> Result<IngestConfig, String> {
         let source = self.source.ok_or("Source is required")?;
@@ -254,6 +319,7 @@ impl IngestConfigBuilder {
             dedup: self.dedup.unwrap_or_default(),
             resume: self.resume,
             batch_id: self.batch_id,
+            error_budget: self.error_budget,
         })
     }
 }
@@ -308,5 +374,19 @@ mod tests {
         assert_eq!(config.workers, 8);
         assert_eq!(config.batch_size, 500);
         assert_eq!(config.dedup, DedupStrategy::Both);
+        assert!(config.error_budget.is_unlimited());
+    }
+
+    #[test]
+    fn test_error_budget() {
+        let budget = ErrorBudget {
+            max_errors: Some(2),
+            max_error_rate: Some(0.1),
+        };
+        assert!(budget.check_count(2).is_ok());
+        assert!(budget.check_count(3).is_err());
+        assert!(budget.check(1, 9).is_ok());
+        assert!(budget.check(2, 8).is_err());
+        assert!(ErrorBudget::default().check(100, 0).is_ok());
     }
 }
@@ -7,6 +7,10 @@
 #[cfg(feature = "duckdb-backend")]
 use std::collections::HashSet;
 #[cfg(feature = "duckdb-backend")]
+use std::path::Path;
+#[cfg(feature = "duckdb-backend")]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "duckdb-backend")]
 use std::time::Instant;
 
 #[cfg(feature = "duckdb-backend")]
@@ -17,9 +21,20 @@ use super::batch::{BatchStatus, ProcessingBatch};
 #[cfg(feature = "duckdb-backend")]
 use super::config::{DedupStrategy, IngestConfig, SourceType};
 #[cfg(feature = "duckdb-backend")]
+use super::dead_letter::{DeadLetter, DeadLetterStatus, RejectedRecord};
+#[cfg(feature = "duckdb-backend")]
 use super::error::{IngestError, StagingError};
 #[cfg(feature = "duckdb-backend")]
-use super::ingest::{IngestStats, discover_local_files, parse_file, should_skip_file};
+use super::ingest::{
+    DiscoveredFile, IngestStats, discover_local_files, parse_file_lenient, should_skip_file,
+};
+#[cfg(feature = "duckdb-backend")]
+use super::lifecycle::{
+    CompactionStats, DroppedPartition, FailedPartition, PartitionInfo, RetentionOutcome,
+    RetentionPolicy, VacuumStats,
+};
+#[cfg(feature = "duckdb-backend")]
+use super::profile::DataProfile;
 #[cfg(feature = "duckdb-backend")]
 use super::schema::{SCHEMA_VERSION, StagingSchema};
 
@@ -35,12 +50,16 @@ pub struct StagingDb {
 #[cfg(feature = "duckdb-backend")]
 impl StagingDb {
     /// Open or create a staging database at the given path
+    ///
+    /// A database initialized with an older schema version is upgraded.
     pub fn open(path: &str) -> Result<Self, StagingError> {
         let conn = duckdb::Connection::open(path)?;
-        Ok(Self {
+        let db = Self {
             conn,
             path: Some(path.to_string()),
-        })
+        };
+        db.upgrade()?;
+        Ok(db)
     }
 
     /// Open an in-memory database (for testing)
@@ -70,6 +89,23 @@ impl StagingDb {
         Ok(())
     }
 
+    /// Bring an initialized database up to the current schema version
+    ///
+    /// The DDL only creates missing tables and indexes, so it is re-run as a
+    /// whole. Returns true if the database was upgraded.
+    pub fn upgrade(&self) -> Result<bool, StagingError> {
+        if !self.is_initialized()? {
+            return Ok(false);
+        }
+        // Databases without a recorded version predate version tracking
+        let version = self.schema_version().unwrap_or(0);
+        if version >= SCHEMA_VERSION {
+            return Ok(false);
+        }
+        self.init()?;
+        Ok(true)
+    }
+
     /// Check if the database is initialized
     pub fn is_initialized(&self) -> Result<bool, StagingError> {
         let result: Result<i32, _> = self.conn.query_row(
@@ -137,6 +173,44 @@ impl StagingDb {
         Ok(samples)
     }
 
+    /// Get records staged after a record id, in id order
+    ///
+    /// Used to fold newly ingested records into a saved inference state.
+    /// Returns `(id, raw_json)` pairs; a `limit` of 0 returns all records.
+    pub fn get_records_after(
+        &self,
+        after_id: i64,
+        limit: usize,
+        partition: Option<&str>,
+    ) -> Result<Vec<(i64, String)>, StagingError> {
+        let limit = if limit == 0 { i64::MAX } else { limit as i64 };
+        let mut records = Vec::new();
+
+        if let Some(partition) = partition {
+            let mut stmt = self.conn.prepare(
+                "SELECT id, raw_json FROM staged_json WHERE id > ?1 AND partition_key = ?2 ORDER BY id LIMIT ?3",
+            )?;
+            let rows = stmt.query_map(duckdb::params![after_id, partition, limit], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+            })?;
+            for row in rows {
+                records.push(row?);
+            }
+        } else {
+            let mut stmt = self.conn.prepare(
+                "SELECT id, raw_json FROM staged_json WHERE id > ?1 ORDER BY id LIMIT ?2",
+            )?;
+            let rows = stmt.query_map(duckdb::params![after_id, limit], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+            })?;
+            for row in rows {
+                records.push(row?);
+            }
+        }
+
+        Ok(records)
+    }
+
     /// Execute a query and return results as JSON
     pub fn query(&self, sql: &str) -> Result<Vec<serde_json::Value>, StagingError> {
         let mut stmt = self.conn.prepare(sql)?;
@@ -183,6 +257,39 @@ impl StagingDb {
         Ok(results)
     }
 
+    /// Export staged records to a Parquet file
+    ///
+    /// Columns and types follow the merged structure of the staged JSON
+    /// (`json_group_structure`): nested objects become structs and arrays
+    /// become lists. Returns the number of records written; nothing is written
+    /// when no records are staged.
+    pub fn export_parquet(&self, path: &str, partition: Option<&str>) -> Result<u64, StagingError> {
+        let filter = partition
+            .map(|p| format!(" WHERE partition_key = '{}'", p.replace('\'', "''")))
+            .unwrap_or_default();
+
+        let structure: Option<String> = self.conn.query_row(
+            &format!(
+                "SELECT json_group_structure(raw_json)::VARCHAR FROM staged_json{}",
+                filter
+            ),
+            [],
+            |row| row.get(0),
+        )?;
+        let Some(structure) = structure else {
+            return Ok(0);
+        };
+
+        let count = self.record_count(partition)?;
+        self.conn.execute_batch(&format!(
+            "COPY (SELECT record.* FROM (SELECT json_transform(raw_json, '{}') AS record FROM staged_json{} ORDER BY id)) TO '{}' (FORMAT PARQUET)",
+            structure.replace('\'', "''"),
+            filter,
+            path.replace('\'', "''")
+        ))?;
+        Ok(count as u64)
+    }
+
     /// Get existing file paths for deduplication
     fn get_existing_paths(&self, partition: Option<&str>) -> Result<HashSet<String>, StagingError> {
         let mut paths = HashSet::new();
@@ -236,6 +343,24 @@ impl StagingDb {
         Ok(hashes)
     }
 
+    /// Remove the records and pending dead letters staged from a file
+    pub(crate) fn remove_file_records(
+        &self,
+        file_path: &str,
+        partition: Option<&str>,
+    ) -> Result<usize, StagingError> {
+        let removed = self.conn.execute(
+            "DELETE FROM staged_json WHERE file_path = ?1 AND partition_key IS NOT DISTINCT FROM ?2",
+            duckdb::params![file_path, partition],
+        )?;
+        self.conn.execute(
+            "DELETE FROM dead_letters
+             WHERE file_path = ?1 AND partition_key IS NOT DISTINCT FROM ?2 AND status = ?3",
+            duckdb::params![file_path, partition, DeadLetterStatus::Pending.to_string()],
+        )?;
+        Ok(removed)
+    }
+
     /// Get the next available ID for staged_json
     fn next_id(&self) -> Result<i64, StagingError> {
         let id: i64 = self.conn.query_row(
@@ -458,8 +583,486 @@ impl StagingDb {
         Ok(stats)
     }
 
+    /// List partitions of staged records, most recently ingested first
+    pub fn list_partitions(&self) -> Result<Vec<PartitionInfo>, StagingError> {
+        let mut partitions = Vec::new();
+        let mut stmt = self.conn.prepare(
+            "SELECT s.partition_key, s.records, s.files,
+                    (SELECT COUNT(*) FROM processing_batches b
+                     WHERE b.partition_key IS NOT DISTINCT FROM s.partition_key),
+                    strftime(s.first_ingested, '%Y-%m-%d %H:%M:%S'),
+                    strftime(s.last_ingested, '%Y-%m-%d %H:%M:%S'),
+                    CAST(floor((epoch(CAST(current_timestamp AS TIMESTAMP)) - epoch(s.last_ingested)) / 86400) AS BIGINT)
+             FROM (
+                 SELECT partition_key, COUNT(*) AS records, COUNT(DISTINCT file_path) AS files,
+                        MIN(ingested_at) AS first_ingested, MAX(ingested_at) AS last_ingested
+                 FROM staged_json
+                 GROUP BY partition_key
+             ) s
+             ORDER BY s.last_ingested DESC, s.partition_key",
+        )?;
+
+        let parse_time = |value: Option<String>| {
+            value.and_then(|s| chrono::NaiveDateTime::parse_from_str(&s, "%Y-%m-%d %H:%M:%S").ok())
+        };
+        let rows = stmt.query_map([], |row| {
+            Ok(PartitionInfo {
+                partition: row.get(0)?,
+                record_count: row.get(1)?,
+                file_count: row.get(2)?,
+                batch_count: row.get(3)?,
+                first_ingested_at: parse_time(row.get(4)?),
+                last_ingested_at: parse_time(row.get(5)?),
+                age_days: row.get::<_, Option<i64>>(6)?.unwrap_or(0),
+            })
+        })?;
+        for row in rows {
+            partitions.push(row?);
+        }
+
+        Ok(partitions)
+    }
+
+    /// Count the records of one partition (None selects unpartitioned records)
+    fn partition_record_count(&self, partition: Option<&str>) -> Result<u64, StagingError> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM staged_json WHERE partition_key IS NOT DISTINCT FROM ?1",
+            [partition],
+            |row| row.get(0),
+        )?;
+        Ok(count as u64)
+    }
+
+    fn require_partition(&self, partition: Option<&str>) -> Result<u64, StagingError> {
+        let count = self.partition_record_count(partition)?;
+        if count == 0 {
+            return Err(StagingError::Query(format!(
+                "Partition not found: {}",
+                partition.unwrap_or("<none>")
+            )));
+        }
+        Ok(count)
+    }
+
+    /// Run statements in a transaction, rolling back on error
+    fn in_transaction<T>(
+        &self,
+        f: impl FnOnce() -> Result<T, StagingError>,
+    ) -> Result<T, StagingError> {
+        self.conn.execute_batch("BEGIN TRANSACTION")?;
+        match f() {
+            Ok(value) => {
+                self.conn.execute_batch("COMMIT")?;
+                Ok(value)
+            }
+            Err(e) => {
+                let _ = self.conn.execute_batch("ROLLBACK");
+                Err(e)
+            }
+        }
+    }
+
+    /// Copy the staged records of a partition to a Parquet file
+    ///
+    /// Unlike [`StagingDb::export_parquet`] the rows are written as staged,
+    /// with raw JSON and ingestion metadata, so that nothing is lost when the
+    /// partition is dropped afterwards. An existing file is never overwritten.
+    /// Returns the number of records written.
+    pub fn archive_partition(
+        &self,
+        partition: Option<&str>,
+        path: &Path,
+    ) -> Result<u64, StagingError> {
+        let count = self.require_partition(partition)?;
+        if path.exists() {
+            return Err(StagingError::InvalidConfig(format!(
+                "Archive file already exists: {}",
+                path.display()
+            )));
+        }
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let filter = match partition {
+            Some(p) => format!("partition_key = '{}'", p.replace('\'', "''")),
+            None => "partition_key IS NULL".to_string(),
+        };
+        self.conn.execute_batch(&format!(
+            "COPY (SELECT * FROM staged_json WHERE {} ORDER BY id) TO '{}' (FORMAT PARQUET)",
+            filter,
+            path.display().to_string().replace('\'', "''")
+        ))?;
+        Ok(count)
+    }
+
+    /// Remove a partition with its dead letters and processing batches
+    ///
+    /// With `archive` the records are first archived to that Parquet file; the
+    /// partition is only removed once the archive has been written.
+    pub fn drop_partition(
+        &self,
+        partition: Option<&str>,
+        archive: Option<&Path>,
+    ) -> Result<DroppedPartition, StagingError> {
+        self.require_partition(partition)?;
+        if let Some(path) = archive {
+            self.archive_partition(partition, path)?;
+        }
+
+        let (records_removed, batches_removed) = self.in_transaction(|| {
+            let records = self.conn.execute(
+                "DELETE FROM staged_json WHERE partition_key IS NOT DISTINCT FROM ?1",
+                [partition],
+            )?;
+            self.conn.execute(
+                "DELETE FROM dead_letters WHERE partition_key IS NOT DISTINCT FROM ?1",
+                [partition],
+            )?;
+            let batches = self.conn.execute(
+                "DELETE FROM processing_batches WHERE partition_key IS NOT DISTINCT FROM ?1",
+                [partition],
+            )?;
+            Ok((records as u64, batches as u64))
+        })?;
+
+        Ok(DroppedPartition {
+            partition: partition.map(str::to_string),
+            records_removed,
+            batches_removed,
+            archive: archive.map(Path::to_path_buf),
+        })
+    }
+
+    /// Rename a partition
+    ///
+    /// Dead letters, processing batches, data profiles and inferred schemas
+    /// move with the records. Fails if the new partition already exists; use
+    /// [`StagingDb::merge_partitions`] to combine partitions.
+    pub fn rename_partition(&self, from: &str, to: &str) -> Result<u64, StagingError> {
+        let count = self.require_partition(Some(from))?;
+        if self.partition_record_count(Some(to))? > 0 {
+            return Err(StagingError::Query(format!(
+                "Partition already exists: {}",
+                to
+            )));
+        }
+
+        self.in_transaction(|| {
+            for table in [
+                "staged_json",
+                "dead_letters",
+                "processing_batches",
+                "data_profiles",
+                "inferred_schemas",
+            ] {
+                self.conn.execute(
+                    &format!(
+                        "UPDATE {} SET partition_key = ?1 WHERE partition_key = ?2",
+                        table
+                    ),
+                    [to, from],
+                )?;
+            }
+            Ok(())
+        })?;
+        Ok(count)
+    }
+
+    /// Move the records of several partitions into one
+    ///
+    /// Dead letters and processing batches move with the records; data
+    /// profiles and inferred schemas of the merged partitions are kept as
+    /// history. The target partition may already exist. Returns the number
+    /// of records moved.
+    pub fn merge_partitions(&self, sources: &[&str], target: &str) -> Result<u64, StagingError> {
+        let mut moved = 0;
+        for source in sources {
+            if *source == target {
+                return Err(StagingError::Query(format!(
+                    "Cannot merge partition {} into itself",
+                    source
+                )));
+            }
+            moved += self.require_partition(Some(*source))?;
+        }
+
+        self.in_transaction(|| {
+            for source in sources {
+                for table in ["staged_json", "dead_letters", "processing_batches"] {
+                    self.conn.execute(
+                        &format!(
+                            "UPDATE {} SET partition_key = ?1 WHERE partition_key = ?2",
+                            table
+                        ),
+                        [target, *source],
+                    )?;
+                }
+            }
+            Ok(())
+        })?;
+        Ok(moved)
+    }
+
+    /// Remove byte-identical duplicate records from a partition
+    ///
+    /// The first staged copy of each record is kept. The database is
+    /// checkpointed afterwards so that the space of removed records is
+    /// released. The `records_ingested` counters of processing batches keep
+    /// counting the removed duplicates, as they record ingestion history.
+    pub fn compact_partition(
+        &self,
+        partition: Option<&str>,
+    ) -> Result<CompactionStats, StagingError> {
+        let records_before = self.require_partition(partition)?;
+        let removed = self.conn.execute(
+            "DELETE FROM staged_json
+             WHERE partition_key IS NOT DISTINCT FROM ?1
+               AND id NOT IN (
+                   SELECT MIN(id) FROM staged_json
+                   WHERE partition_key IS NOT DISTINCT FROM ?1
+                   GROUP BY CAST(raw_json AS VARCHAR)
+               )",
+            [partition],
+        )?;
+        self.conn.execute_batch("CHECKPOINT")?;
+
+        Ok(CompactionStats {
+            records_before,
+            duplicates_removed: removed as u64,
+        })
+    }
+
+    /// Drop the partitions that expire under a retention policy
+    ///
+    /// Partitions are archived to the archive directory of the policy first,
+    /// if it has one. A partition that cannot be archived or dropped is kept
+    /// and reported; the other partitions are still processed.
+    pub fn apply_retention(
+        &self,
+        policy: &RetentionPolicy,
+    ) -> Result<RetentionOutcome, StagingError> {
+        let partitions = self.list_partitions()?;
+        let mut outcome = RetentionOutcome::default();
+        for info in policy.expired(&partitions) {
+            let archive = info
+                .partition
+                .as_deref()
+                .and_then(|name| policy.archive_path(name, Utc::now()));
+            match self.drop_partition(info.partition.as_deref(), archive.as_deref()) {
+                Ok(dropped) => outcome.dropped.push(dropped),
+                Err(e) => outcome.failed.push(FailedPartition {
+                    partition: info.partition.clone(),
+                    error: e.to_string(),
+                }),
+            }
+        }
+        Ok(outcome)
+    }
+
+    /// Release space held by deleted records
+    ///
+    /// Refreshes table statistics and checkpoints the database. DuckDB reuses
+    /// released blocks and truncates free blocks at the end of the file; use
+    /// [`StagingDb::vacuum_into`] to write a fully compacted copy.
+    pub fn vacuum(&self) -> Result<VacuumStats, StagingError> {
+        let file_size = || {
+            self.path
+                .as_deref()
+                .and_then(|path| std::fs::metadata(path).ok())
+                .map(|metadata| metadata.len())
+        };
+
+        let size_before = file_size();
+        self.conn
+            .execute_batch("VACUUM ANALYZE; FORCE CHECKPOINT;")?;
+        Ok(VacuumStats {
+            size_before,
+            size_after: file_size(),
+        })
+    }
+
+    /// Write a compacted copy of the database to a new file
+    pub fn vacuum_into(&self, path: &Path) -> Result<(), StagingError> {
+        if path.exists() {
+            return Err(StagingError::InvalidConfig(format!(
+                "Vacuum target already exists: {}",
+                path.display()
+            )));
+        }
+
+        let database: String = self
+            .conn
+            .query_row("SELECT current_database()", [], |row| row.get(0))?;
+        self.conn.execute_batch(&format!(
+            "ATTACH '{}' AS staging_vacuum;
+             COPY FROM DATABASE \"{}\" TO staging_vacuum;
+             DETACH staging_vacuum;",
+            path.display().to_string().replace('\'', "''"),
+            database.replace('"', "\"\"")
+        ))?;
+
+        // Recreate indexes in case the copy did not carry them over
+        StagingDb::open(&path.display().to_string())?.init()
+    }
+
+    /// Get a recorded LLM response by cache key
+    #[cfg(feature = "llm")]
+    pub fn get_llm_response(
+        &self,
+        key: &str,
+    ) -> Result<Option<crate::llm::CachedResponse>, StagingError> {
+        let result = self.conn.query_row(
+            "SELECT cache_key, model, temperature, prompt_hash, response, recorded_at
+             FROM llm_responses WHERE cache_key = ?1",
+            [key],
+            |row| {
+                Ok(crate::llm::CachedResponse {
+                    key: row.get(0)?,
+                    model: row.get(1)?,
+                    temperature: row.get(2)?,
+                    prompt_hash: row.get(3)?,
+                    response: row.get(4)?,
+                    recorded_at: chrono::DateTime::parse_from_rfc3339(&row.get::<_, String>(5)?)
+                        .map(|dt| dt.with_timezone(&Utc))
+                        .unwrap_or_else(|_| Utc::now()),
+                })
+            },
+        );
+
+        match result {
+            Ok(entry) => Ok(Some(entry)),
+            Err(duckdb::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Record an LLM response, replacing any entry with the same cache key
+    #[cfg(feature = "llm")]
+    pub fn put_llm_response(&self, entry: &crate::llm::CachedResponse) -> Result<(), StagingError> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO llm_responses
+             (cache_key, model, temperature, prompt_hash, response, recorded_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            duckdb::params![
+                entry.key,
+                entry.model,
+                entry.temperature,
+                entry.prompt_hash,
+                entry.response,
+                entry.recorded_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Execute one or more SQL statements that return no rows
+    pub(crate) fn execute_batch(&self, sql: &str) -> Result<(), StagingError> {
+        self.conn.execute_batch(sql)?;
+        Ok(())
+    }
+
+    /// Store a data profile as the next version for its partition
+    ///
+    /// Assigns the version to the profile and returns it.
+    pub fn save_profile(&self, profile: &mut DataProfile) -> Result<i32, StagingError> {
+        let latest: Option<i32> = self.conn.query_row(
+            "SELECT MAX(version) FROM data_profiles WHERE partition_key IS NOT DISTINCT FROM ?1",
+            [profile.partition.as_deref()],
+            |row| row.get(0),
+        )?;
+        profile.version = latest.unwrap_or(0) + 1;
+
+        self.conn.execute(
+            "INSERT INTO data_profiles
+             (id, partition_key, version, record_count, profile_json, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            duckdb::params![
+                profile.id,
+                profile.partition,
+                profile.version,
+                profile.record_count as i64,
+                serde_json::to_string(profile)?,
+                profile.created_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(profile.version)
+    }
+
+    /// Get a stored data profile of a partition (the latest version when unset)
+    pub fn get_profile(
+        &self,
+        partition: Option<&str>,
+        version: Option<i32>,
+    ) -> Result<Option<DataProfile>, StagingError> {
+        let result = self.conn.query_row(
+            "SELECT CAST(profile_json AS VARCHAR) FROM data_profiles
+             WHERE partition_key IS NOT DISTINCT FROM ?1 AND (?2 IS NULL OR version = ?2)
+             ORDER BY version DESC LIMIT 1",
+            duckdb::params![partition, version],
+            |row| row.get::<_, String>(0),
+        );
+
+        match result {
+            Ok(json) => Ok(Some(serde_json::from_str(&json)?)),
+            Err(duckdb::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// List the stored data profiles of a partition, newest first
+    pub fn list_profiles(&self, partition: Option<&str>) -> Result<Vec<DataProfile>, StagingError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT CAST(profile_json AS VARCHAR) FROM data_profiles
+             WHERE partition_key IS NOT DISTINCT FROM ?1
+             ORDER BY version DESC",
+        )?;
+        let rows = stmt.query_map([partition], |row| row.get::<_, String>(0))?;
+
+        let mut profiles = Vec::new();
+        for row in rows {
+            profiles.push(serde_json::from_str(&row?)?);
+        }
+        Ok(profiles)
+    }
+
     /// Ingest files from the configured source
     pub fn ingest(&self, config: &IngestConfig) -> Result<IngestStats, IngestError> {
+        // Discover files based on source type
+        let files = match &config.source {
+            SourceType::Local(path) => discover_local_files(path, &config.pattern)?,
+            #[cfg(feature = "s3")]
+            SourceType::S3 { .. } => {
+                // S3 discovery would go here
+                return Err(IngestError::SourceNotAccessible {
+                    path: config.source.display(),
+                    reason: "S3 ingestion not yet implemented".to_string(),
+                });
+            }
+            #[cfg(feature = "databricks")]
+            SourceType::UnityVolume { .. } => {
+                // Unity Catalog discovery would go here
+                return Err(IngestError::SourceNotAccessible {
+                    path: config.source.display(),
+                    reason: "Unity Catalog ingestion not yet implemented".to_string(),
+                });
+            }
+        };
+
+        self.ingest_files(config, files, None)
+    }
+
+    /// Ingest already discovered files as one batch
+    ///
+    /// `stop` is checked before each file. Once it is set, buffered records are
+    /// written and the batch is left running so that it can be resumed with
+    /// [`IngestConfig::resume`].
+    pub fn ingest_files(
+        &self,
+        config: &IngestConfig,
+        files: Vec<DiscoveredFile>,
+        stop: Option<&AtomicBool>,
+    ) -> Result<IngestStats, IngestError> {
         let start = Instant::now();
         let mut stats = IngestStats::new();
 
@@ -499,41 +1102,34 @@ impl StagingDb {
             self.create_batch(&b)?;
             b
         };
-
-        // Discover files based on source type
-        let files = match &config.source {
-            SourceType::Local(path) => discover_local_files(path, &config.pattern)?,
-            #[cfg(feature = "s3")]
-            SourceType::S3 { .. } => {
-                // S3 discovery would go here
-                return Err(IngestError::SourceNotAccessible {
-                    path: config.source.display(),
-                    reason: "S3 ingestion not yet implemented".to_string(),
-                });
-            }
-            #[cfg(feature = "databricks")]
-            SourceType::UnityVolume { .. } => {
-                // Unity Catalog discovery would go here
-                return Err(IngestError::SourceNotAccessible {
-                    path: config.source.display(),
-                    reason: "Unity Catalog ingestion not yet implemented".to_string(),
-                });
-            }
-        };
+        stats.batch_id = Some(batch.id.clone());
+
+        if config.resume && !batch.accepts_partition(config.partition.as_deref()) {
+            return Err(IngestError::PartitionMismatch {
+                batch_id: batch.id.clone(),
+                batch_partition: batch
+                    .partition_key
+                    .as_deref()
+                    .unwrap_or("<none>")
+                    .to_string(),
+                partition: config.partition.as_deref().unwrap_or("<none>").to_string(),
+            });
+        }
+        let partition = batch.partition_key.clone();
 
         batch.files_total = files.len() as i32;
 
         // Get existing data for deduplication
         let existing_paths = if matches!(config.dedup, DedupStrategy::ByPath | DedupStrategy::Both)
         {
-            self.get_existing_paths(config.partition.as_deref())?
+            self.get_existing_paths(partition.as_deref())?
         } else {
             HashSet::new()
         };
 
         let existing_hashes =
             if matches!(config.dedup, DedupStrategy::ByContent | DedupStrategy::Both) {
-                self.get_existing_hashes(config.partition.as_deref())?
+                self.get_existing_hashes(partition.as_deref())?
             } else {
                 HashSet::new()
             };
@@ -551,6 +1147,15 @@ impl StagingDb {
         let mut past_resume_point = resume_after.is_none();
 
         for mut file in files {
+            // Stop between files, leaving the batch resumable
+            if stop.is_some_and(|stop| stop.load(Ordering::Relaxed)) {
+                self.flush_records(&mut records_batch, next_id, &mut stats, &mut batch)?;
+                self.update_batch(&batch)?;
+                stats.interrupted = true;
+                stats.duration = start.elapsed();
+                return Ok(stats);
+            }
+
             let file_path_str = file.path.display().to_string();
 
             // Skip files before resume point
@@ -576,23 +1181,35 @@ impl StagingDb {
                 continue;
             }
 
-            // Parse the file
-            let records = match parse_file(&file.path) {
-                Ok(r) => r,
+            // Parse the file, quarantining records that are not valid JSON
+            let parsed = match parse_file_lenient(&file.path) {
+                Ok(p) => p,
                 Err(e) => {
                     stats.add_error(format!("Error parsing {}: {}", file_path_str, e));
                     batch.increment_errors();
                     continue;
                 }
             };
+            if !parsed.rejected.is_empty() {
+                self.insert_dead_letters(&batch.id, partition.as_deref(), &parsed.rejected)?;
+                for rejected in &parsed.rejected {
+                    stats.add_error(format!(
+                        "Rejected record in {} at line {}: {}",
+                        file_path_str,
+                        rejected.line_number.unwrap_or(1),
+                        rejected.error
+                    ));
+                    batch.increment_errors();
+                }
+            }
 
             // Add records to batch
-            for record in records {
+            for record in parsed.records {
                 records_batch.push((
                     file_path_str.clone(),
                     record.json,
                     record.index,
-                    config.partition.clone(),
+                    partition.clone(),
                     file.content_hash.clone(),
                     file.size,
                 ));
@@ -613,6 +1230,13 @@ impl StagingDb {
             batch.bytes_processed += file.size as i64;
             batch.last_file_path = Some(file_path_str);
 
+            // Stop once the error budget is spent; the file counts as processed
+            // so that resuming does not quarantine its records again
+            if let Err(reason) = config.error_budget.check_count(batch.errors_count as usize) {
+                self.flush_records(&mut records_batch, next_id, &mut stats, &mut batch)?;
+                return self.fail_batch(&mut batch, reason);
+            }
+
             // Update batch progress periodically
             if batch.files_processed % 100 == 0 {
                 self.update_batch(&batch)?;
@@ -620,10 +1244,13 @@ impl StagingDb {
         }
 
         // Insert remaining records
-        if !records_batch.is_empty() {
-            self.insert_records(&records_batch, next_id)?;
-            stats.records_ingested += records_batch.len();
-            batch.records_ingested += records_batch.len() as i64;
+        self.flush_records(&mut records_batch, next_id, &mut stats, &mut batch)?;
+
+        if let Err(reason) = config
+            .error_budget
+            .check(batch.errors_count as usize, batch.records_ingested as usize)
+        {
+            return self.fail_batch(&mut batch, reason);
         }
 
         // Complete batch
@@ -633,6 +1260,189 @@ impl StagingDb {
         stats.duration = start.elapsed();
         Ok(stats)
     }
+
+    /// Insert buffered records and add them to the statistics
+    fn flush_records(
+        &self,
+        records: &mut Vec<(String, String, usize, Option<String>, Option<String>, u64)>,
+        start_id: i64,
+        stats: &mut IngestStats,
+        batch: &mut ProcessingBatch,
+    ) -> Result<(), StagingError> {
+        if !records.is_empty() {
+            self.insert_records(records, start_id)?;
+            stats.records_ingested += records.len();
+            batch.records_ingested += records.len() as i64;
+            records.clear();
+        }
+        Ok(())
+    }
+
+    /// Mark a batch as failed because its error budget was exceeded
+    fn fail_batch(
+        &self,
+        batch: &mut ProcessingBatch,
+        reason: String,
+    ) -> Result<IngestStats, IngestError> {
+        batch.fail(&format!("Error budget exceeded: {}", reason));
+        self.update_batch(batch)?;
+        Err(IngestError::ErrorBudgetExceeded {
+            batch_id: batch.id.clone(),
+            reason,
+        })
+    }
+
+    /// Quarantine rejected records of a batch
+    fn insert_dead_letters(
+        &self,
+        batch_id: &str,
+        partition: Option<&str>,
+        rejected: &[RejectedRecord],
+    ) -> Result<(), StagingError> {
+        let mut stmt = self.conn.prepare(
+            "INSERT INTO dead_letters
+             (id, batch_id, file_path, line_number, partition_key, raw_data, error, status, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        )?;
+
+        let now = Utc::now().to_rfc3339();
+        for record in rejected {
+            stmt.execute(duckdb::params![
+                DeadLetter::generate_id(),
+                batch_id,
+                record.file_path,
+                record.line_number.map(|l| l as i32),
+                partition,
+                record.raw,
+                record.error,
+                DeadLetterStatus::Pending.to_string(),
+                now,
+            ])?;
+        }
+
+        Ok(())
+    }
+
+    /// List quarantined records, oldest first
+    pub fn list_dead_letters(
+        &self,
+        batch_id: Option<&str>,
+        status: Option<DeadLetterStatus>,
+        limit: usize,
+    ) -> Result<Vec<DeadLetter>, StagingError> {
+        let mut dead_letters = Vec::new();
+        let mut stmt = self.conn.prepare(
+            "SELECT id, batch_id, file_path, line_number, partition_key, raw_data, error,
+                    status, created_at, resolved_at
+             FROM dead_letters
+             WHERE (?1 IS NULL OR batch_id = ?1) AND (?2 IS NULL OR status = ?2)
+             ORDER BY created_at, file_path, line_number
+             LIMIT ?3",
+        )?;
+
+        let rows = stmt.query_map(
+            duckdb::params![batch_id, status.map(|s| s.to_string()), limit as i64],
+            Self::row_to_dead_letter,
+        )?;
+        for row in rows {
+            dead_letters.push(row?);
+        }
+
+        Ok(dead_letters)
+    }
+
+    /// Get a quarantined record by ID
+    pub fn get_dead_letter(&self, id: &str) -> Result<Option<DeadLetter>, StagingError> {
+        let result = self.conn.query_row(
+            "SELECT id, batch_id, file_path, line_number, partition_key, raw_data, error,
+                    status, created_at, resolved_at
+             FROM dead_letters WHERE id = ?1",
+            [id],
+            Self::row_to_dead_letter,
+        );
+
+        match result {
+            Ok(dead_letter) => Ok(Some(dead_letter)),
+            Err(duckdb::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn row_to_dead_letter(row: &duckdb::Row<'_>) -> duckdb::Result<DeadLetter> {
+        let parse_time = |value: Option<String>| {
+            value
+                .and_then(|s| chrono::DateTime::parse_from_rfc3339(&s).ok())
+                .map(|dt| dt.with_timezone(&Utc))
+        };
+        Ok(DeadLetter {
+            id: row.get(0)?,
+            batch_id: row.get(1)?,
+            file_path: row.get(2)?,
+            line_number: row.get(3)?,
+            partition_key: row.get(4)?,
+            raw_data: row.get(5)?,
+            error: row.get(6)?,
+            status: row
+                .get::<_, String>(7)?
+                .parse()
+                .unwrap_or(DeadLetterStatus::Pending),
+            created_at: parse_time(row.get(8)?),
+            resolved_at: parse_time(row.get(9)?),
+        })
+    }
+
+    /// Ingest a quarantined record into `staged_json` and mark it as re-ingested
+    ///
+    /// `fixed_json` replaces the raw data of the record; without it the raw
+    /// data is ingested as-is and must be valid JSON.
+    pub fn reingest_dead_letter(
+        &self,
+        id: &str,
+        fixed_json: Option<&str>,
+    ) -> Result<(), StagingError> {
+        let dead_letter = self.pending_dead_letter(id)?;
+        let raw_text = dead_letter.raw_text();
+        let json = fixed_json.unwrap_or(&raw_text).trim();
+        serde_json::from_str::<serde_json::Value>(json)?;
+
+        let record = (
+            dead_letter.file_path.clone(),
+            json.to_string(),
+            dead_letter.record_index(),
+            dead_letter.partition_key.clone(),
+            None,
+            json.len() as u64,
+        );
+        self.insert_records(&[record], self.next_id()?)?;
+        self.resolve_dead_letter(id, DeadLetterStatus::Reingested)
+    }
+
+    /// Drop a quarantined record without ingesting it
+    pub fn discard_dead_letter(&self, id: &str) -> Result<(), StagingError> {
+        self.pending_dead_letter(id)?;
+        self.resolve_dead_letter(id, DeadLetterStatus::Discarded)
+    }
+
+    fn pending_dead_letter(&self, id: &str) -> Result<DeadLetter, StagingError> {
+        let dead_letter = self
+            .get_dead_letter(id)?
+            .ok_or_else(|| StagingError::Query(format!("Dead letter not found: {}", id)))?;
+        if dead_letter.status != DeadLetterStatus::Pending {
+            return Err(StagingError::Query(format!(
+                "Dead letter {} is already {}",
+                id, dead_letter.status
+            )));
+        }
+        Ok(dead_letter)
+    }
+
+    fn resolve_dead_letter(&self, id: &str, status: DeadLetterStatus) -> Result<(), StagingError> {
+        self.conn.execute(
+            "UPDATE dead_letters SET status = ?1, resolved_at = ?2 WHERE id = ?3",
+            duckdb::params![status.to_string(), Utc::now().to_rfc3339(), id],
+        )?;
+        Ok(())
+    }
 }
 
 // ============================================================================
@@ -785,6 +1595,39 @@ mod postgres_impl {
             Ok(rows.iter().map(|r| r.get::<_, String>(0)).collect())
         }
 
+        /// Get records staged after a record id, in id order
+        ///
+        /// Returns `(id, raw_json)` pairs; a `limit` of 0 returns all records.
+        pub async fn get_records_after(
+            &self,
+            after_id: i64,
+            limit: usize,
+            partition: Option<&str>,
+        ) -> Result<Vec<(i64, String)>, StagingError> {
+            let limit = if limit == 0 { i64::MAX } else { limit as i64 };
+            let rows = if let Some(partition) = partition {
+                self.client
+                    .query(
+                        "SELECT id, raw_json::text FROM staged_json WHERE id > $1 AND partition_key = $2 ORDER BY id LIMIT $3",
+                        &[&after_id, &partition, &limit],
+                    )
+                    .await
+            } else {
+                self.client
+                    .query(
+                        "SELECT id, raw_json::text FROM staged_json WHERE id > $1 ORDER BY id LIMIT $2",
+                        &[&after_id, &limit],
+                    )
+                    .await
+            }
+            .map_err(|e| StagingError::Database(e.to_string()))?;
+
+            Ok(rows
+                .iter()
+                .map(|r| (r.get::<_, i64>(0), r.get::<_, String>(1)))
+                .collect())
+        }
+
         /// Get existing file paths for deduplication
         async fn get_existing_paths(
             &self,
@@ -1055,6 +1898,14 @@ mod postgres_impl {
                 return Err(IngestError::Staging(StagingError::NotInitialized));
             }
 
+            // Invalid records fail the ingest here instead of being quarantined,
+            // so there is no error budget to enforce
+            if !config.error_budget.is_unlimited() {
+                return Err(IngestError::Staging(StagingError::InvalidConfig(
+                    "Error budgets are not supported by the PostgreSQL backend".to_string(),
+                )));
+            }
+
             // Create or resume batch
             let batch_id = config
                 .batch_id
@@ -1087,6 +1938,19 @@ mod postgres_impl {
                 b
             };
 
+            if config.resume && !batch.accepts_partition(config.partition.as_deref()) {
+                return Err(IngestError::PartitionMismatch {
+                    batch_id: batch.id.clone(),
+                    batch_partition: batch
+                        .partition_key
+                        .as_deref()
+                        .unwrap_or("<none>")
+                        .to_string(),
+                    partition: config.partition.as_deref().unwrap_or("<none>").to_string(),
+                });
+            }
+            let partition = batch.partition_key.clone();
+
             // Discover files based on source type
             let files = match &config.source {
                 SourceType::Local(path) => discover_local_files(path, &config.pattern)?,
@@ -1111,15 +1975,14 @@ mod postgres_impl {
             // Get existing data for deduplication
             let existing_paths =
                 if matches!(config.dedup, DedupStrategy::ByPath | DedupStrategy::Both) {
-                    self.get_existing_paths(config.partition.as_deref()).await?
+                    self.get_existing_paths(partition.as_deref()).await?
                 } else {
                     HashSet::new()
                 };
 
             let existing_hashes =
                 if matches!(config.dedup, DedupStrategy::ByContent | DedupStrategy::Both) {
-                    self.get_existing_hashes(config.partition.as_deref())
-                        .await?
+                    self.get_existing_hashes(partition.as_deref()).await?
                 } else {
                     HashSet::new()
                 };
@@ -1186,7 +2049,7 @@ mod postgres_impl {
                         file_path_str.clone(),
                         record.json,
                         record.index,
-                        config.partition.clone(),
+                        partition.clone(),
                         file.content_hash.clone(),
                         file.size,
                     ));
@@ -1247,6 +2110,34 @@ mod tests {
         assert_eq!(db.schema_version().unwrap(), SCHEMA_VERSION);
     }
 
+    #[test]
+    fn test_staging_db_upgrade_from_v1() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("staging.duckdb");
+        let path = path.to_str().unwrap();
+
+        // A version 1 database has none of the tables added since
+        {
+            let db = StagingDb::open(path).unwrap();
+            db.init().unwrap();
+            db.conn
+                .execute_batch(
+                    "DROP TABLE llm_responses;
+                     DROP TABLE data_profiles;
+                     DROP TABLE dead_letters;
+                     UPDATE schema_info SET value = '1' WHERE key = 'version';",
+                )
+                .unwrap();
+        }
+
+        let db = StagingDb::open(path).unwrap();
+        assert_eq!(db.schema_version().unwrap(), SCHEMA_VERSION);
+        assert!(db.query("SELECT * FROM llm_responses").unwrap().is_empty());
+        assert!(db.query("SELECT * FROM data_profiles").unwrap().is_empty());
+        assert!(db.list_dead_letters(None, None, 10).unwrap().is_empty());
+        assert!(!db.upgrade().unwrap());
+    }
+
     #[test]
     fn test_staging_db_record_count() {
         let db = StagingDb::memory().unwrap();
@@ -1338,6 +2229,32 @@ mod tests {
         assert_eq!(stats2.files_skipped, 1);
     }
 
+    #[test]
+    fn test_staging_db_records_after() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("records.jsonl");
+        let mut f = File::create(&file).unwrap();
+        for i in 0..5 {
+            writeln!(f, r#"{{"n": {}}}"#, i).unwrap();
+        }
+
+        let db = StagingDb::memory().unwrap();
+        db.init().unwrap();
+        let config = IngestConfig::builder()
+            .source_type(SourceType::Local(dir.path().to_path_buf()))
+            .pattern("*.jsonl")
+            .build()
+            .unwrap();
+        db.ingest(&config).unwrap();
+
+        let first = db.get_records_after(0, 2, None).unwrap();
+        assert_eq!(first.len(), 2);
+        let rest = db.get_records_after(first[1].0, 0, None).unwrap();
+        assert_eq!(rest.len(), 3);
+        assert!(rest.iter().all(|(id, _)| *id > first[1].0));
+        assert!(db.get_records_after(rest[2].0, 0, None).unwrap().is_empty());
+    }
+
     #[test]
     fn test_staging_db_batch_tracking() {
         let db = StagingDb::memory().unwrap();
@@ -1360,6 +2277,280 @@ mod tests {
         assert_eq!(retrieved.status, BatchStatus::Running);
     }
 
+    #[test]
+    fn test_staging_db_resume_uses_batch_partition() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("orders.jsonl"), r#"{"id": 1}"#).unwrap();
+
+        let db = StagingDb::memory().unwrap();
+        db.init().unwrap();
+        let batch = ProcessingBatch::new(
+            "batch-1".to_string(),
+            dir.path().display().to_string(),
+            "local".to_string(),
+            Some("2024-01".to_string()),
+            "*.jsonl".to_string(),
+        );
+        db.create_batch(&batch).unwrap();
+
+        let resume = |partition: Option<&str>| {
+            let mut builder = IngestConfig::builder()
+                .source_type(SourceType::Local(dir.path().to_path_buf()))
+                .pattern("*.jsonl")
+                .batch_id("batch-1")
+                .resume(true);
+            if let Some(partition) = partition {
+                builder = builder.partition(partition);
+            }
+            db.ingest(&builder.build().unwrap())
+        };
+
+        assert!(matches!(
+            resume(Some("2024-02")),
+            Err(IngestError::PartitionMismatch { .. })
+        ));
+        assert_eq!(db.record_count(None).unwrap(), 0);
+
+        resume(None).unwrap();
+        assert_eq!(db.record_count(Some("2024-01")).unwrap(), 1);
+    }
+
+    fn write_orders_with_invalid_line(dir: &TempDir) {
+        let mut f = File::create(dir.path().join("orders.jsonl")).unwrap();
+        writeln!(f, r#"{{"id": 1}}"#).unwrap();
+        writeln!(f, r#"{{"id": 2,"#).unwrap();
+        writeln!(f, r#"{{"id": 3}}"#).unwrap();
+    }
+
+    #[test]
+    fn test_staging_db_dead_letters() {
+        let dir = TempDir::new().unwrap();
+        write_orders_with_invalid_line(&dir);
+
+        let db = StagingDb::memory().unwrap();
+        db.init().unwrap();
+        let config = IngestConfig::builder()
+            .source_type(SourceType::Local(dir.path().to_path_buf()))
+            .pattern("*.jsonl")
+            .partition("2024-01")
+            .batch_id("batch-1")
+            .build()
+            .unwrap();
+
+        let stats = db.ingest(&config).unwrap();
+        assert_eq!(stats.records_ingested, 2);
+        assert_eq!(stats.errors_count, 1);
+
+        let dead_letters = db
+            .list_dead_letters(Some("batch-1"), Some(DeadLetterStatus::Pending), 10)
+            .unwrap();
+        assert_eq!(dead_letters.len(), 1);
+        let dead_letter = &dead_letters[0];
+        assert_eq!(dead_letter.line_number, Some(2));
+        assert_eq!(dead_letter.raw_text(), r#"{"id": 2,"#);
+        assert_eq!(dead_letter.partition_key.as_deref(), Some("2024-01"));
+        assert_eq!(db.get_batch("batch-1").unwrap().unwrap().errors_count, 1);
+
+        // The raw record is still invalid; a fixed version is ingested
+        assert!(db.reingest_dead_letter(&dead_letter.id, None).is_err());
+        db.reingest_dead_letter(&dead_letter.id, Some(r#"{"id": 2}"#))
+            .unwrap();
+        assert_eq!(db.record_count(Some("2024-01")).unwrap(), 3);
+
+        let resolved = db.get_dead_letter(&dead_letter.id).unwrap().unwrap();
+        assert_eq!(resolved.status, DeadLetterStatus::Reingested);
+        assert!(resolved.resolved_at.is_some());
+        assert!(db.discard_dead_letter(&dead_letter.id).is_err());
+    }
+
+    #[test]
+    fn test_staging_db_error_budget() {
+        let dir = TempDir::new().unwrap();
+        write_orders_with_invalid_line(&dir);
+
+        let db = StagingDb::memory().unwrap();
+        db.init().unwrap();
+        let config = IngestConfig::builder()
+            .source_type(SourceType::Local(dir.path().to_path_buf()))
+            .pattern("*.jsonl")
+            .batch_id("batch-1")
+            .max_errors(0)
+            .build()
+            .unwrap();
+
+        let result = db.ingest(&config);
+        assert!(matches!(
+            result,
+            Err(IngestError::ErrorBudgetExceeded { ref batch_id, .. }) if batch_id == "batch-1"
+        ));
+
+        // Valid records of the file are kept and the batch is failed
+        assert_eq!(db.record_count(None).unwrap(), 2);
+        let batch = db.get_batch("batch-1").unwrap().unwrap();
+        assert_eq!(batch.status, BatchStatus::Failed);
+        assert!(
+            batch
+                .error_message
+                .unwrap()
+                .contains("Error budget exceeded")
+        );
+    }
+
+    fn ingest_partition(db: &StagingDb, dir: &TempDir, partition: &str, lines: &[&str]) {
+        let source = dir.path().join(partition);
+        std::fs::create_dir_all(&source).unwrap();
+        std::fs::write(source.join("data.jsonl"), lines.join("\n")).unwrap();
+        let config = IngestConfig::builder()
+            .source_type(SourceType::Local(source))
+            .pattern("*.jsonl")
+            .partition(partition)
+            .build()
+            .unwrap();
+        db.ingest(&config).unwrap();
+    }
+
+    #[test]
+    fn test_staging_db_partition_lifecycle() {
+        let dir = TempDir::new().unwrap();
+        let db = StagingDb::memory().unwrap();
+        db.init().unwrap();
+        ingest_partition(
+            &db,
+            &dir,
+            "a",
+            &[r#"{"id": 1}"#, r#"{"id": 1}"#, r#"{"id": 2}"#],
+        );
+        ingest_partition(&db, &dir, "b", &[r#"{"id": 3}"#]);
+        ingest_partition(&db, &dir, "c", &[r#"{"id": 4}"#]);
+
+        let partitions = db.list_partitions().unwrap();
+        assert_eq!(partitions.len(), 3);
+        let a = partitions.iter().find(|p| p.name() == "a").unwrap();
+        assert_eq!((a.record_count, a.file_count, a.batch_count), (3, 1, 1));
+        assert_eq!(a.age_days, 0);
+
+        // Compaction keeps the first copy of identical records
+        let stats = db.compact_partition(Some("a")).unwrap();
+        assert_eq!(stats.duplicates_removed, 1);
+        assert_eq!(db.record_count(Some("a")).unwrap(), 2);
+
+        // Batches move with renamed and merged records
+        assert!(db.rename_partition("a", "b").is_err());
+        db.rename_partition("a", "orders").unwrap();
+        assert_eq!(db.merge_partitions(&["b", "c"], "orders").unwrap(), 2);
+        let partitions = db.list_partitions().unwrap();
+        assert_eq!(partitions.len(), 1);
+        assert_eq!(partitions[0].record_count, 4);
+        assert_eq!(partitions[0].batch_count, 3);
+
+        // Dropping archives the staged rows first and removes the batches
+        let archive = dir.path().join("archive/orders.parquet");
+        let dropped = db.drop_partition(Some("orders"), Some(&archive)).unwrap();
+        assert_eq!((dropped.records_removed, dropped.batches_removed), (4, 3));
+        assert!(archive.exists());
+        assert_eq!(db.record_count(None).unwrap(), 0);
+
+        // An existing archive is kept and the partition is not dropped
+        ingest_partition(&db, &dir, "orders", &[r#"{"id": 5}"#]);
+        let archived = std::fs::read(&archive).unwrap();
+        assert!(db.drop_partition(Some("orders"), Some(&archive)).is_err());
+        assert_eq!(std::fs::read(&archive).unwrap(), archived);
+        assert_eq!(db.record_count(Some("orders")).unwrap(), 1);
+        db.drop_partition(Some("orders"), None).unwrap();
+        assert!(db.list_batches(10).unwrap().is_empty());
+        assert!(db.drop_partition(Some("orders"), None).is_err());
+    }
+
+    #[test]
+    fn test_staging_db_retention() {
+        let dir = TempDir::new().unwrap();
+        let db = StagingDb::memory().unwrap();
+        db.init().unwrap();
+        ingest_partition(&db, &dir, "old", &[r#"{"id": 1}"#]);
+        ingest_partition(&db, &dir, "new", &[r#"{"id": 2}"#]);
+        let expire_old = || {
+            db.execute_batch(
+                "UPDATE staged_json SET ingested_at = ingested_at - INTERVAL 40 DAY
+                 WHERE partition_key = 'old'",
+            )
+            .unwrap();
+        };
+        expire_old();
+
+        // A partition that cannot be archived is kept and reported
+        std::fs::write(dir.path().join("not-a-dir"), b"").unwrap();
+        let policy = RetentionPolicy::default()
+            .with_max_age_days(30)
+            .with_archive_dir(dir.path().join("not-a-dir"));
+        let outcome = db.apply_retention(&policy).unwrap();
+        assert!(outcome.dropped.is_empty());
+        assert_eq!(outcome.failed.len(), 1);
+        assert_eq!(outcome.failed[0].partition.as_deref(), Some("old"));
+        assert_eq!(db.list_partitions().unwrap().len(), 2);
+
+        let policy = RetentionPolicy::default()
+            .with_max_age_days(30)
+            .with_archive_dir(dir.path().join("archive"));
+        let outcome = db.apply_retention(&policy).unwrap();
+        assert!(outcome.failed.is_empty());
+        assert_eq!(outcome.dropped.len(), 1);
+        assert_eq!(outcome.dropped[0].partition.as_deref(), Some("old"));
+        let first_archive = outcome.dropped[0].archive.clone().unwrap();
+        assert!(first_archive.exists());
+
+        let partitions = db.list_partitions().unwrap();
+        assert_eq!(partitions.len(), 1);
+        assert_eq!(partitions[0].name(), "new");
+
+        // A re-ingested partition that expires again gets its own archive
+        ingest_partition(&db, &dir, "old", &[r#"{"id": 3}"#]);
+        expire_old();
+        let outcome = db.apply_retention(&policy).unwrap();
+        assert!(outcome.failed.is_empty());
+        let second_archive = outcome.dropped[0].archive.clone().unwrap();
+        assert_ne!(first_archive, second_archive);
+        assert!(first_archive.exists() && second_archive.exists());
+    }
+
+    #[test]
+    fn test_staging_db_export_parquet() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("orders.jsonl");
+        let mut f = File::create(&file).unwrap();
+        writeln!(
+            f,
+            r#"{{"id": 1, "customer": {{"name": "Ada"}}, "tags": ["a"]}}"#
+        )
+        .unwrap();
+        writeln!(f, r#"{{"id": 2, "customer": {{"name": "Bob"}}}}"#).unwrap();
+
+        let db = StagingDb::memory().unwrap();
+        db.init().unwrap();
+        let config = IngestConfig::builder()
+            .source_type(SourceType::Local(dir.path().to_path_buf()))
+            .pattern("*.jsonl")
+            .build()
+            .unwrap();
+        db.ingest(&config).unwrap();
+
+        let output = dir.path().join("orders.parquet");
+        let output = output.to_str().unwrap();
+        assert_eq!(db.export_parquet(output, None).unwrap(), 2);
+        assert_eq!(db.export_parquet(output, Some("missing")).unwrap(), 0);
+
+        let rows = db
+            .query(&format!(
+                "SELECT id, customer.name AS name, len(tags) AS tags FROM '{}' ORDER BY id",
+                output
+            ))
+            .unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0]["id"], 1);
+        assert_eq!(rows[1]["name"], "Bob");
+        assert_eq!(rows[0]["tags"], 1);
+        assert!(rows[1]["tags"].is_null());
+    }
+
     #[test]
     fn test_staging_db_query() {
         let db = StagingDb::memory().unwrap();
@@ -0,0 +1,120 @@
+//! Dead-letter handling for records rejected during ingestion
+//!
+//! Records that cannot be parsed are quarantined in the `dead_letters` table
+//! with their raw bytes, file path, line number and parse error instead of
+//! failing the whole file. Quarantined records can be inspected, fixed and
+//! re-ingested, or discarded.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A record rejected while parsing a source file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RejectedRecord {
+    /// Source file path
+    pub file_path: String,
+    /// 1-based line number (None when the whole file was rejected)
+    pub line_number: Option<usize>,
+    /// Raw bytes of the record as read from the file
+    pub raw: Vec<u8>,
+    /// Parse error
+    pub error: String,
+}
+
+/// Status of a quarantined record
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeadLetterStatus {
+    /// Waiting to be fixed or discarded
+    Pending,
+    /// Fixed and ingested into `staged_json`
+    Reingested,
+    /// Dropped without ingesting
+    Discarded,
+}
+
+impl std::fmt::Display for DeadLetterStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeadLetterStatus::Pending => write!(f, "pending"),
+            DeadLetterStatus::Reingested => write!(f, "reingested"),
+            DeadLetterStatus::Discarded => write!(f, "discarded"),
+        }
+    }
+}
+
+impl std::str::FromStr for DeadLetterStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "pending" => Ok(DeadLetterStatus::Pending),
+            "reingested" => Ok(DeadLetterStatus::Reingested),
+            "discarded" => Ok(DeadLetterStatus::Discarded),
+            _ => Err(format!("Invalid dead letter status: {}", s)),
+        }
+    }
+}
+
+/// A quarantined record stored in the staging database
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeadLetter {
+    /// Unique dead letter identifier
+    pub id: String,
+    /// Batch that rejected the record
+    pub batch_id: Option<String>,
+    /// Source file path
+    pub file_path: String,
+    /// 1-based line number (None when the whole file was rejected)
+    pub line_number: Option<i32>,
+    /// Partition the record was ingested into
+    pub partition_key: Option<String>,
+    /// Raw bytes of the record
+    #[serde(skip)]
+    pub raw_data: Vec<u8>,
+    /// Parse error
+    pub error: String,
+    /// Current status
+    pub status: DeadLetterStatus,
+    /// When the record was rejected
+    pub created_at: Option<DateTime<Utc>>,
+    /// When the record was re-ingested or discarded
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl DeadLetter {
+    /// Generate a new dead letter ID
+    pub fn generate_id() -> String {
+        uuid::Uuid::new_v4().to_string()
+    }
+
+    /// Raw data as text, with invalid UTF-8 replaced
+    pub fn raw_text(&self) -> String {
+        String::from_utf8_lossy(&self.raw_data).into_owned()
+    }
+
+    /// Record index used in `staged_json` when the record is re-ingested
+    pub fn record_index(&self) -> usize {
+        self.line_number
+            .map(|line| (line.max(1) - 1) as usize)
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dead_letter_status_roundtrip() {
+        for status in [
+            DeadLetterStatus::Pending,
+            DeadLetterStatus::Reingested,
+            DeadLetterStatus::Discarded,
+        ] {
+            assert_eq!(status.to_string().parse::<DeadLetterStatus>(), Ok(status));
+        }
+        assert!("unknown".parse::<DeadLetterStatus>().is_err());
+    }
+}
@@ -35,6 +35,10 @@ pub enum StagingError {
     /// Query error
     #[error("Query error: {0}")]
     Query(String),
+
+    /// Parquet export error
+    #[error("Parquet export error: {0}")]
+    Parquet(String),
 }
 
 /// Errors that can occur during ingestion
@@ -68,6 +72,18 @@ pub enum IngestError {
     #[error("Batch already completed: {0}")]
     BatchCompleted(String),
 
+    /// Resume asked for a different partition than the batch was started with
+    #[error("Batch {batch_id} ingests into partition {batch_partition}, not {partition}")]
+    PartitionMismatch {
+        batch_id: String,
+        batch_partition: String,
+        partition: String,
+    },
+
+    /// Too many records were rejected
+    #[error("Batch {batch_id} failed: error budget exceeded ({reason})")]
+    ErrorBudgetExceeded { batch_id: String, reason: String },
+
     /// Source not accessible
     #[error("Source not accessible: {path} - {reason}")]
     SourceNotAccessible { path: String, reason: String },
@@ -149,6 +165,22 @@ impl IngestError {
                     "Batch not found: {batch_id}\n\nHint: Use 'odm staging batches' to list available batches."
                 )
             }
+            IngestError::PartitionMismatch {
+                batch_id,
+                batch_partition,
+                partition,
+            } => {
+                format!(
+                    "Batch {batch_id} ingests into partition {batch_partition}, not {partition}\n\n\
+                    Hint: Omit --partition when resuming; the batch keeps its partition."
+                )
+            }
+            IngestError::ErrorBudgetExceeded { batch_id, reason } => {
+                format!(
+                    "Batch {batch_id} failed: error budget exceeded ({reason})\n\n\
+                    Hint: Inspect rejected records with 'odm staging dead-letters list --batch-id {batch_id}'."
+                )
+            }
             IngestError::InvalidPattern(pattern) => {
                 format!(
                     "Invalid glob pattern: {pattern}\n\n\
@@ -0,0 +1,995 @@
+//! Iceberg schemas derived from ODCS tables
+//!
+//! Converts the columns of an ODCS [`Table`] into an Iceberg schema and
+//! partition spec, and plans the schema evolution that brings an existing
+//! Iceberg table in line with a changed contract:
+//!
+//! - Logical types map to Iceberg primitives; a precise physical type
+//!   (`DECIMAL(12,2)`, `INT`) refines a generic logical type (`number`, `integer`)
+//! - Nested columns (`address.city`, `items.[].sku`) become structs and lists
+//! - `partitioned` columns ordered by `partition_key_position` become partition
+//!   fields, with the identity transform unless the column sets a
+//!   `partitionTransform` custom property (`day`, `bucket[16]`, ...)
+//!
+//! Evolution never rewrites data. Columns are added, renamed, widened
+//! (`int` → `long`, `float` → `double`, wider decimals) or made optional;
+//! columns missing from the contract are kept. A column is recognised as
+//! renamed when its ODCS property `id` matches the one recorded in the table
+//! properties when the table was created or last evolved. Incompatible type
+//! changes are rejected.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use iceberg::spec::{
+    ListType, NestedField, NestedFieldRef, PartitionSpec, PrimitiveType, Schema, StructType,
+    Transform, Type, UnboundPartitionSpec,
+};
+
+use super::catalog::{CatalogError, CatalogResult};
+use crate::models::{Column, Table};
+
+/// Table property prefix recording the column path of each ODCS property id
+pub const COLUMN_ID_PROPERTY_PREFIX: &str = "odcs.column.";
+
+/// Custom column property selecting the partition transform
+pub const PARTITION_TRANSFORM_PROPERTY: &str = "partitionTransform";
+
+/// Precision and scale used for decimal columns that do not declare them
+const DEFAULT_DECIMAL: (u32, u32) = (38, 9);
+
+/// Field of a contract schema, before Iceberg field ids are assigned
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContractField {
+    /// Field name
+    pub name: String,
+    /// ODCS property id, used to follow renames
+    pub property_id: Option<String>,
+    /// Whether the field is required
+    pub required: bool,
+    /// Field documentation
+    pub doc: Option<String>,
+    /// Field type
+    pub field_type: ContractType,
+}
+
+/// Type of a contract field
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContractType {
+    /// Primitive Iceberg type
+    Primitive(PrimitiveType),
+    /// Struct with named fields
+    Struct(Vec<ContractField>),
+    /// List of optional elements
+    List(Box<ContractType>),
+}
+
+/// Partition field of a contract schema
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContractPartition {
+    /// Source column name
+    pub column: String,
+    /// Partition transform
+    pub transform: Transform,
+}
+
+impl ContractPartition {
+    /// Partition field name (`<column>` for identity, `<column>_<transform>` otherwise)
+    pub fn field_name(&self) -> String {
+        match self.transform {
+            Transform::Identity => self.column.clone(),
+            ref transform => {
+                let transform = transform.to_string();
+                let name = transform.split('[').next().unwrap_or(&transform);
+                format!("{}_{}", self.column, name)
+            }
+        }
+    }
+}
+
+/// Iceberg layout of an ODCS table
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContractSchema {
+    /// Top-level fields in column order
+    pub fields: Vec<ContractField>,
+    /// Partition fields in partition key order
+    pub partitions: Vec<ContractPartition>,
+}
+
+/// A change applied by schema evolution
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaChange {
+    /// New optional column
+    AddColumn {
+        /// Column path
+        path: String,
+        /// Iceberg type
+        field_type: String,
+    },
+    /// Column renamed in place
+    RenameColumn {
+        /// Previous column path
+        from: String,
+        /// New column path
+        to: String,
+    },
+    /// Column type promoted to a wider type
+    WidenColumn {
+        /// Column path
+        path: String,
+        /// Previous Iceberg type
+        from: String,
+        /// New Iceberg type
+        to: String,
+    },
+    /// Required column made optional
+    MakeOptional {
+        /// Column path
+        path: String,
+    },
+    /// Column documentation changed
+    UpdateDoc {
+        /// Column path
+        path: String,
+    },
+    /// New default partition spec
+    UpdatePartitionSpec {
+        /// Partition field names of the new spec
+        fields: Vec<String>,
+    },
+}
+
+impl std::fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchemaChange::AddColumn { path, field_type } => {
+                write!(f, "add column {} ({})", path, field_type)
+            }
+            SchemaChange::RenameColumn { from, to } => write!(f, "rename {} to {}", from, to),
+            SchemaChange::WidenColumn { path, from, to } => {
+                write!(f, "widen {} from {} to {}", path, from, to)
+            }
+            SchemaChange::MakeOptional { path } => write!(f, "make {} optional", path),
+            SchemaChange::UpdateDoc { path } => write!(f, "update documentation of {}", path),
+            SchemaChange::UpdatePartitionSpec { fields } => {
+                write!(f, "partition by [{}]", fields.join(", "))
+            }
+        }
+    }
+}
+
+/// Planned evolution of an Iceberg table towards a contract
+#[derive(Debug, Clone)]
+pub struct SchemaEvolution {
+    /// Schema to add, present when columns change
+    pub schema: Option<Schema>,
+    /// Partition spec to add, present when partitioning changes
+    pub partition_spec: Option<UnboundPartitionSpec>,
+    /// Changes in the order they were found
+    pub changes: Vec<SchemaChange>,
+    /// Columns of the table that are not in the contract and were kept
+    pub retained: Vec<String>,
+    /// Contract settings that could not be applied to existing columns
+    pub warnings: Vec<String>,
+    /// Column path of each ODCS property id after evolution
+    pub column_properties: HashMap<String, String>,
+}
+
+impl SchemaEvolution {
+    /// Check if the table already matches the contract
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// Build the Iceberg layout of an ODCS table
+///
+/// Nested columns use the dot-notation layout of the ODCS converters and are
+/// rebuilt into structs and lists. Top-level fields are required when the
+/// column is not nullable; nested fields are always optional.
+pub fn contract_schema(table: &Table) -> CatalogResult<ContractSchema> {
+    let fields = table
+        .columns
+        .iter()
+        .filter(|c| !c.name.contains('.'))
+        .map(|column| contract_field(column, &column.name, &table.columns, !column.nullable))
+        .collect();
+
+    let mut partitioned: Vec<(usize, &Column)> = table
+        .columns
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| c.partitioned || c.partition_key_position.is_some())
+        .collect();
+    partitioned.sort_by_key(|(index, c)| (c.partition_key_position.unwrap_or(i32::MAX), *index));
+
+    let mut partitions = Vec::new();
+    for (_, column) in partitioned {
+        if column.name.contains('.') {
+            return Err(CatalogError::SchemaError(format!(
+                "Partition column '{}' must be a top-level column",
+                column.name
+            )));
+        }
+        let transform = match column
+            .custom_properties
+            .get(PARTITION_TRANSFORM_PROPERTY)
+            .and_then(|v| v.as_str())
+        {
+            Some(name) => name.parse::<Transform>().map_err(|e| {
+                CatalogError::SchemaError(format!(
+                    "Invalid partition transform '{}' on column '{}': {}",
+                    name, column.name, e
+                ))
+            })?,
+            None => Transform::Identity,
+        };
+        partitions.push(ContractPartition {
+            column: column.name.clone(),
+            transform,
+        });
+    }
+
+    Ok(ContractSchema { fields, partitions })
+}
+
+fn contract_field(
+    column: &Column,
+    path: &str,
+    columns: &[Column],
+    required: bool,
+) -> ContractField {
+    let name = path.rsplit('.').next().unwrap_or(path).to_string();
+    ContractField {
+        name,
+        property_id: column.id.clone(),
+        required,
+        doc: (!column.description.is_empty()).then(|| column.description.clone()),
+        field_type: column_type(column, path, columns),
+    }
+}
+
+/// Contract type of a column, including its nested child columns
+fn column_type(column: &Column, path: &str, columns: &[Column]) -> ContractType {
+    let data_type = column.data_type.trim().to_uppercase();
+
+    if let Some(inner) = data_type
+        .strip_prefix("ARRAY<")
+        .and_then(|t| t.strip_suffix('>'))
+    {
+        let item_path = format!("{}.[]", path);
+        let item = child_struct(&item_path, columns)
+            .unwrap_or_else(|| ContractType::Primitive(primitive_type(inner, column)));
+        return ContractType::List(Box::new(item));
+    }
+    if data_type == "ARRAY" {
+        let item_path = format!("{}.[]", path);
+        let item = child_struct(&item_path, columns)
+            .unwrap_or(ContractType::Primitive(PrimitiveType::String));
+        return ContractType::List(Box::new(item));
+    }
+    if matches!(data_type.as_str(), "STRUCT" | "OBJECT" | "RECORD")
+        || data_type.starts_with("STRUCT<")
+    {
+        return child_struct(path, columns)
+            .unwrap_or(ContractType::Primitive(PrimitiveType::String));
+    }
+
+    ContractType::Primitive(column_primitive(&data_type, column))
+}
+
+/// Struct type built from the direct children of a column path, if it has any
+fn child_struct(path: &str, columns: &[Column]) -> Option<ContractType> {
+    let prefix = format!("{}.", path);
+    let fields: Vec<ContractField> = columns
+        .iter()
+        .filter(|column| {
+            column
+                .name
+                .strip_prefix(&prefix)
+                .is_some_and(|name| !name.contains('.'))
+        })
+        .map(|column| contract_field(column, &column.name, columns, false))
+        .collect();
+
+    (!fields.is_empty()).then_some(ContractType::Struct(fields))
+}
+
+/// Primitive type of a scalar column
+///
+/// Generic logical types (`integer`, `number`, `string`) are refined by the
+/// physical type when it names a more specific type.
+fn column_primitive(data_type: &str, column: &Column) -> PrimitiveType {
+    let logical = primitive_type(data_type, column);
+    let generic = matches!(data_type, "INTEGER" | "NUMBER" | "STRING");
+    match column.physical_type {
+        Some(ref physical) if generic => match primitive_type(physical, column) {
+            PrimitiveType::String => logical,
+            refined => refined,
+        },
+        _ => logical,
+    }
+}
+
+/// Map a scalar SQL/ODCS type name to an Iceberg primitive type
+fn primitive_type(data_type: &str, column: &Column) -> PrimitiveType {
+    let data_type = data_type.trim().to_uppercase();
+    let (base, params) = match data_type.split_once('(') {
+        Some((base, rest)) => (base.trim(), Some(rest.trim_end_matches(')'))),
+        None => (data_type.as_str(), None),
+    };
+
+    match base {
+        "BOOLEAN" | "BOOL" => PrimitiveType::Boolean,
+        "TINYINT" | "SMALLINT" | "INT" | "INT32" => PrimitiveType::Int,
+        "INTEGER" | "BIGINT" | "LONG" | "INT64" => PrimitiveType::Long,
+        "FLOAT" | "REAL" | "FLOAT32" => PrimitiveType::Float,
+        "DOUBLE" | "DOUBLE PRECISION" | "NUMBER" | "FLOAT64" => PrimitiveType::Double,
+        "DECIMAL" | "NUMERIC" => decimal_type(params, column),
+        "DATE" => PrimitiveType::Date,
+        "TIMESTAMP" | "TIMESTAMP_TZ" | "TIMESTAMPTZ" | "TIMESTAMP WITH TIME ZONE" | "DATETIME" => {
+            PrimitiveType::Timestamptz
+        }
+        "TIMESTAMP_NTZ" | "TIMESTAMP WITHOUT TIME ZONE" => PrimitiveType::Timestamp,
+        "TIME" => PrimitiveType::Time,
+        "UUID" => PrimitiveType::Uuid,
+        "BINARY" | "VARBINARY" | "BYTES" | "BLOB" => PrimitiveType::Binary,
+        _ => {
+            // Strings may carry a temporal format in their logical type options
+            let format = column
+                .logical_type_options
+                .as_ref()
+                .and_then(|o| o.format.as_deref());
+            match format {
+                Some("date") => PrimitiveType::Date,
+                Some("date-time") => PrimitiveType::Timestamptz,
+                Some("time") => PrimitiveType::Time,
+                Some("uuid") => PrimitiveType::Uuid,
+                _ => PrimitiveType::String,
+            }
+        }
+    }
+}
+
+/// Decimal type from `DECIMAL(p,s)` parameters or the column's logical type options
+fn decimal_type(params: Option<&str>, column: &Column) -> PrimitiveType {
+    let mut precision = None;
+    let mut scale = None;
+
+    if let Some(params) = params {
+        let mut parts = params.split(',').map(|p| p.trim().parse::<i32>().ok());
+        precision = parts.next().flatten();
+        scale = parts.next().flatten();
+    }
+    if let Some(ref options) = column.logical_type_options {
+        precision = precision.or(options.precision);
+        scale = scale.or(options.scale);
+    }
+
+    let precision = precision
+        .map(|p| p.clamp(1, 38) as u32)
+        .unwrap_or(DEFAULT_DECIMAL.0);
+    let scale = scale
+        .map(|s| s.clamp(0, precision as i32) as u32)
+        .unwrap_or(DEFAULT_DECIMAL.1.min(precision));
+    PrimitiveType::Decimal { precision, scale }
+}
+
+impl ContractSchema {
+    /// Iceberg schema with field ids assigned from 1
+    pub fn to_iceberg_schema(&self) -> CatalogResult<Schema> {
+        let mut next_id = 1;
+        let fields = self
+            .fields
+            .iter()
+            .map(|field| new_field(field, &mut next_id))
+            .collect();
+
+        Schema::builder()
+            .with_schema_id(0)
+            .with_fields(fields)
+            .build()
+            .map_err(|e| CatalogError::SchemaError(e.to_string()))
+    }
+
+    /// Partition spec bound to the top-level fields of a schema
+    pub fn partition_spec(&self, schema: &Schema) -> CatalogResult<UnboundPartitionSpec> {
+        self.build_partition_spec(schema, 0)
+    }
+
+    fn build_partition_spec(
+        &self,
+        schema: &Schema,
+        spec_id: i32,
+    ) -> CatalogResult<UnboundPartitionSpec> {
+        let mut builder = UnboundPartitionSpec::builder().with_spec_id(spec_id);
+        for partition in &self.partitions {
+            let field = schema
+                .as_struct()
+                .fields()
+                .iter()
+                .find(|f| f.name == partition.column)
+                .ok_or_else(|| {
+                    CatalogError::SchemaError(format!(
+                        "Partition column '{}' not found in schema",
+                        partition.column
+                    ))
+                })?;
+            builder = builder
+                .add_partition_field(field.id, partition.field_name(), partition.transform)
+                .map_err(|e| CatalogError::SchemaError(e.to_string()))?;
+        }
+        Ok(builder.build())
+    }
+
+    /// Column path of each ODCS property id, stored as table properties
+    pub fn column_properties(&self) -> HashMap<String, String> {
+        let mut properties = HashMap::new();
+        collect_property_ids(&self.fields, "", &mut properties);
+        properties
+    }
+
+    /// Plan the evolution of a table schema and partition spec towards this contract
+    ///
+    /// `properties` are the current table properties, used to follow renamed
+    /// columns through their ODCS property ids.
+    pub fn evolve(
+        &self,
+        current: &Schema,
+        current_spec: &PartitionSpec,
+        properties: &HashMap<String, String>,
+    ) -> CatalogResult<SchemaEvolution> {
+        let previous_paths: HashMap<&str, &str> = properties
+            .iter()
+            .filter_map(|(key, path)| {
+                let id = key.strip_prefix(COLUMN_ID_PROPERTY_PREFIX)?;
+                Some((id, path.as_str()))
+            })
+            .collect();
+
+        let mut evolver = Evolver {
+            next_id: current.highest_field_id() + 1,
+            previous_paths,
+            changes: Vec::new(),
+            retained: Vec::new(),
+            warnings: Vec::new(),
+            errors: Vec::new(),
+        };
+        let fields = evolver.merge_struct(&self.fields, current.as_struct().fields(), "");
+
+        if !evolver.errors.is_empty() {
+            return Err(CatalogError::SchemaError(format!(
+                "Contract changes cannot be applied as schema evolution:\n  {}",
+                evolver.errors.join("\n  ")
+            )));
+        }
+
+        let schema = if evolver.changes.is_empty() {
+            None
+        } else {
+            Some(
+                Schema::builder()
+                    .with_schema_id(current.schema_id() + 1)
+                    .with_fields(fields)
+                    .build()
+                    .map_err(|e| CatalogError::SchemaError(e.to_string()))?,
+            )
+        };
+
+        // Partition fields are compared by source column name and transform;
+        // field ids are kept, so renamed source columns still match
+        let target_schema = schema.as_ref().unwrap_or(current);
+        let current_partitions: Vec<ContractPartition> = current_spec
+            .fields()
+            .iter()
+            .filter_map(|field| {
+                Some(ContractPartition {
+                    column: target_schema.name_by_field_id(field.source_id)?.to_string(),
+                    transform: field.transform,
+                })
+            })
+            .collect();
+
+        let mut partition_spec = None;
+        if current_partitions != self.partitions {
+            partition_spec =
+                Some(self.build_partition_spec(target_schema, current_spec.spec_id() + 1)?);
+            evolver.changes.push(SchemaChange::UpdatePartitionSpec {
+                fields: self.partitions.iter().map(|p| p.field_name()).collect(),
+            });
+        }
+
+        Ok(SchemaEvolution {
+            schema,
+            partition_spec,
+            changes: evolver.changes,
+            retained: evolver.retained,
+            warnings: evolver.warnings,
+            column_properties: self.column_properties(),
+        })
+    }
+}
+
+fn collect_property_ids(fields: &[ContractField], prefix: &str, out: &mut HashMap<String, String>) {
+    for field in fields {
+        let path = join_path(prefix, &field.name);
+        if let Some(ref id) = field.property_id {
+            out.insert(format!("{}{}", COLUMN_ID_PROPERTY_PREFIX, id), path.clone());
+        }
+        match field.field_type {
+            ContractType::Struct(ref children) => collect_property_ids(children, &path, out),
+            ContractType::List(ref element) => {
+                if let ContractType::Struct(ref children) = **element {
+                    collect_property_ids(children, &format!("{}.element", path), out);
+                }
+            }
+            ContractType::Primitive(_) => {}
+        }
+    }
+}
+
+fn join_path(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", prefix, name)
+    }
+}
+
+/// Create an Iceberg field with fresh ids
+fn new_field(field: &ContractField, next_id: &mut i32) -> NestedFieldRef {
+    let id = *next_id;
+    *next_id += 1;
+    let field_type = new_type(&field.field_type, next_id);
+    let mut nested = NestedField::new(id, &field.name, field_type, field.required);
+    if let Some(ref doc) = field.doc {
+        nested = nested.with_doc(doc);
+    }
+    Arc::new(nested)
+}
+
+fn new_type(contract_type: &ContractType, next_id: &mut i32) -> Type {
+    match contract_type {
+        ContractType::Primitive(primitive) => Type::Primitive(primitive.clone()),
+        ContractType::Struct(fields) => Type::Struct(StructType::new(
+            fields.iter().map(|f| new_field(f, next_id)).collect(),
+        )),
+        ContractType::List(element) => {
+            let id = *next_id;
+            *next_id += 1;
+            let element_type = new_type(element, next_id);
+            Type::List(ListType::new(Arc::new(NestedField::list_element(
+                id,
+                element_type,
+                false,
+            ))))
+        }
+    }
+}
+
+/// Merges contract fields into existing Iceberg fields, keeping field ids
+struct Evolver<'a> {
+    next_id: i32,
+    previous_paths: HashMap<&'a str, &'a str>,
+    changes: Vec<SchemaChange>,
+    retained: Vec<String>,
+    warnings: Vec<String>,
+    errors: Vec<String>,
+}
+
+impl Evolver<'_> {
+    /// Merge the fields of a struct
+    ///
+    /// Existing fields keep their position; new fields are appended.
+    fn merge_struct(
+        &mut self,
+        desired: &[ContractField],
+        existing: &[NestedFieldRef],
+        prefix: &str,
+    ) -> Vec<NestedFieldRef> {
+        let mut matches: HashMap<i32, &ContractField> = HashMap::new();
+        let mut added = Vec::new();
+        let mut claimed: HashSet<i32> = HashSet::new();
+
+        // Fields recorded under a property id are matched first, so a rename
+        // does not hand the old name to another column
+        let mut pending = Vec::new();
+        for field in desired {
+            let previous = field
+                .property_id
+                .as_deref()
+                .and_then(|id| self.previous_paths.get(id))
+                .and_then(|path| match prefix {
+                    "" => Some(*path),
+                    _ => path.strip_prefix(prefix)?.strip_prefix('.'),
+                })
+                .filter(|name| !name.contains('.'));
+            match previous.and_then(|name| existing.iter().find(|f| f.name == name)) {
+                Some(found) if claimed.insert(found.id) => {
+                    matches.insert(found.id, field);
+                }
+                _ => pending.push(field),
+            }
+        }
+        for field in pending {
+            match existing.iter().find(|f| f.name == field.name) {
+                Some(found) if claimed.insert(found.id) => {
+                    matches.insert(found.id, field);
+                }
+                _ => added.push(field),
+            }
+        }
+
+        let mut fields = Vec::with_capacity(existing.len() + added.len());
+        for current in existing {
+            match matches.get(&current.id) {
+                Some(field) => fields.push(self.merge_field(field, current, prefix)),
+                None => {
+                    self.retained.push(join_path(prefix, &current.name));
+                    fields.push(current.clone());
+                }
+            }
+        }
+        for field in added {
+            let path = join_path(prefix, &field.name);
+            let mut optional = field.clone();
+            if optional.required {
+                optional.required = false;
+                self.warnings.push(format!(
+                    "{}: added as optional, existing rows have no value",
+                    path
+                ));
+            }
+            let nested = new_field(&optional, &mut self.next_id);
+            self.changes.push(SchemaChange::AddColumn {
+                path,
+                field_type: nested.field_type.to_string(),
+            });
+            fields.push(nested);
+        }
+        fields
+    }
+
+    /// Merge a contract field into the existing field it matched
+    fn merge_field(
+        &mut self,
+        desired: &ContractField,
+        current: &NestedFieldRef,
+        prefix: &str,
+    ) -> NestedFieldRef {
+        let old_path = join_path(prefix, &current.name);
+        let path = join_path(prefix, &desired.name);
+        if desired.name != current.name {
+            self.changes.push(SchemaChange::RenameColumn {
+                from: old_path.clone(),
+                to: path.clone(),
+            });
+        }
+
+        let required = match (current.required, desired.required) {
+            (true, false) => {
+                self.changes
+                    .push(SchemaChange::MakeOptional { path: path.clone() });
+                false
+            }
+            (false, true) => {
+                self.warnings.push(format!(
+                    "{}: stays optional, an existing column cannot become required",
+                    path
+                ));
+                false
+            }
+            (required, _) => required,
+        };
+
+        let doc = desired.doc.clone().or_else(|| current.doc.clone());
+        if desired.doc.is_some() && desired.doc != current.doc {
+            self.changes
+                .push(SchemaChange::UpdateDoc { path: path.clone() });
+        }
+
+        let field_type = self.merge_type(&desired.field_type, &current.field_type, &path);
+
+        let mut nested = NestedField::new(current.id, &desired.name, field_type, required);
+        if let Some(doc) = doc {
+            nested = nested.with_doc(doc);
+        }
+        Arc::new(nested)
+    }
+
+    fn merge_type(&mut self, desired: &ContractType, current: &Type, path: &str) -> Type {
+        match (desired, current) {
+            (ContractType::Primitive(to), Type::Primitive(from)) => {
+                if to == from {
+                    current.clone()
+                } else if is_promotion(from, to) {
+                    self.changes.push(SchemaChange::WidenColumn {
+                        path: path.to_string(),
+                        from: from.to_string(),
+                        to: to.to_string(),
+                    });
+                    Type::Primitive(to.clone())
+                } else {
+                    self.errors.push(format!(
+                        "{}: cannot change type from {} to {}",
+                        path, from, to
+                    ));
+                    current.clone()
+                }
+            }
+            (ContractType::Struct(fields), Type::Struct(existing)) => Type::Struct(
+                StructType::new(self.merge_struct(fields, existing.fields(), path)),
+            ),
+            (ContractType::List(element), Type::List(list)) => {
+                let current_element = &list.element_field;
+                let element_path = format!("{}.element", path);
+                let element_type =
+                    self.merge_type(element, &current_element.field_type, &element_path);
+                Type::List(ListType::new(Arc::new(NestedField::list_element(
+                    current_element.id,
+                    element_type,
+                    current_element.required,
+                ))))
+            }
+            (desired, current) => {
+                self.errors.push(format!(
+                    "{}: cannot change type from {} to {}",
+                    path,
+                    current,
+                    describe(desired)
+                ));
+                current.clone()
+            }
+        }
+    }
+}
+
+/// Check if Iceberg allows promoting one primitive type to another
+fn is_promotion(from: &PrimitiveType, to: &PrimitiveType) -> bool {
+    match (from, to) {
+        (PrimitiveType::Int, PrimitiveType::Long) => true,
+        (PrimitiveType::Float, PrimitiveType::Double) => true,
+        (
+            PrimitiveType::Decimal {
+                precision: p1,
+                scale: s1,
+            },
+            PrimitiveType::Decimal {
+                precision: p2,
+                scale: s2,
+            },
+        ) => s1 == s2 && p2 > p1,
+        _ => false,
+    }
+}
+
+fn describe(contract_type: &ContractType) -> String {
+    match contract_type {
+        ContractType::Primitive(primitive) => primitive.to_string(),
+        ContractType::Struct(_) => "struct".to_string(),
+        ContractType::List(_) => "list".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(name: &str, data_type: &str) -> Column {
+        Column::new(name.to_string(), data_type.to_string())
+    }
+
+    fn orders() -> Table {
+        let mut id = column("order_id", "INT");
+        id.id = Some("orders_id".to_string());
+        id.nullable = false;
+        let mut amount = column("amount", "number");
+        amount.physical_type = Some("DECIMAL(10,2)".to_string());
+        let mut order_date = column("order_date", "date");
+        order_date.partitioned = true;
+        order_date.partition_key_position = Some(1);
+        let mut created_at = column("created_at", "string");
+        created_at.logical_type_options = Some(Default::default());
+        created_at.logical_type_options.as_mut().unwrap().format = Some("date-time".to_string());
+
+        Table::new(
+            "orders".to_string(),
+            vec![
+                id,
+                amount,
+                order_date,
+                created_at,
+                column("customer", "object"),
+                column("customer.name", "string"),
+                column("items", "array"),
+                column("items.[].sku", "string"),
+                column("items.[].quantity", "integer"),
+            ],
+        )
+    }
+
+    fn apply(schema: &Schema, evolution: &SchemaEvolution) -> Schema {
+        evolution.schema.clone().unwrap_or_else(|| schema.clone())
+    }
+
+    #[test]
+    fn test_contract_schema_types_and_partitions() {
+        let contract = contract_schema(&orders()).unwrap();
+        let schema = contract.to_iceberg_schema().unwrap();
+
+        let order_id = schema.field_by_name("order_id").unwrap();
+        assert!(order_id.required);
+        assert_eq!(*order_id.field_type, Type::Primitive(PrimitiveType::Int));
+        assert_eq!(
+            *schema.field_by_name("amount").unwrap().field_type,
+            Type::Primitive(PrimitiveType::Decimal {
+                precision: 10,
+                scale: 2
+            })
+        );
+        assert_eq!(
+            *schema.field_by_name("created_at").unwrap().field_type,
+            Type::Primitive(PrimitiveType::Timestamptz)
+        );
+        assert!(schema.field_by_name("customer.name").is_some());
+        assert_eq!(
+            *schema
+                .field_by_name("items.element.quantity")
+                .unwrap()
+                .field_type,
+            Type::Primitive(PrimitiveType::Long)
+        );
+
+        let spec = contract.partition_spec(&schema).unwrap();
+        assert_eq!(spec.fields().len(), 1);
+        assert_eq!(spec.fields()[0].name, "order_date");
+        assert_eq!(
+            spec.fields()[0].source_id,
+            schema.field_by_name("order_date").unwrap().id
+        );
+    }
+
+    #[test]
+    fn test_partition_transform_property() {
+        let mut table = orders();
+        let created_at = table
+            .columns
+            .iter_mut()
+            .find(|c| c.name == "created_at")
+            .unwrap();
+        created_at.partitioned = true;
+        created_at.partition_key_position = Some(2);
+        created_at.custom_properties.insert(
+            PARTITION_TRANSFORM_PROPERTY.to_string(),
+            serde_json::json!("day"),
+        );
+
+        let contract = contract_schema(&table).unwrap();
+        let names: Vec<String> = contract.partitions.iter().map(|p| p.field_name()).collect();
+        assert_eq!(names, vec!["order_date", "created_at_day"]);
+
+        created_at_transform(&mut table, "fortnight");
+        assert!(contract_schema(&table).is_err());
+    }
+
+    fn created_at_transform(table: &mut Table, transform: &str) {
+        let column = table
+            .columns
+            .iter_mut()
+            .find(|c| c.name == "created_at")
+            .unwrap();
+        column.custom_properties.insert(
+            PARTITION_TRANSFORM_PROPERTY.to_string(),
+            serde_json::json!(transform),
+        );
+    }
+
+    #[test]
+    fn test_evolve_adds_renames_and_widens() {
+        let contract = contract_schema(&orders()).unwrap();
+        let schema = contract.to_iceberg_schema().unwrap();
+        let spec = contract
+            .partition_spec(&schema)
+            .unwrap()
+            .bind(schema.clone())
+            .unwrap();
+        let properties = contract.column_properties();
+
+        // Unchanged contract: nothing to do
+        let evolution = contract.evolve(&schema, &spec, &properties).unwrap();
+        assert!(evolution.is_empty());
+        assert!(evolution.schema.is_none());
+
+        let mut table = orders();
+        let id = &mut table.columns[0];
+        id.name = "id".to_string();
+        id.data_type = "BIGINT".to_string();
+        table.columns.push(column("customer.email", "string"));
+        let mut status = column("status", "string");
+        status.nullable = false;
+        table.columns.push(status);
+        table.columns.retain(|c| c.name != "created_at");
+
+        let evolution = contract_schema(&table)
+            .unwrap()
+            .evolve(&schema, &spec, &properties)
+            .unwrap();
+        assert!(evolution.changes.contains(&SchemaChange::RenameColumn {
+            from: "order_id".to_string(),
+            to: "id".to_string(),
+        }));
+        assert!(evolution.changes.contains(&SchemaChange::WidenColumn {
+            path: "id".to_string(),
+            from: "int".to_string(),
+            to: "long".to_string(),
+        }));
+        assert!(evolution.changes.contains(&SchemaChange::AddColumn {
+            path: "customer.email".to_string(),
+            field_type: "string".to_string(),
+        }));
+        assert_eq!(evolution.retained, vec!["created_at"]);
+        assert_eq!(evolution.warnings.len(), 1);
+        assert!(evolution.partition_spec.is_none());
+
+        let evolved = apply(&schema, &evolution);
+        let id_field = evolved.field_by_name("id").unwrap();
+        assert_eq!(id_field.id, schema.field_by_name("order_id").unwrap().id);
+        assert!(!evolved.field_by_name("status").unwrap().required);
+        assert!(evolved.field_by_name("created_at").is_some());
+        assert!(evolved.field_by_name("customer.email").unwrap().id > schema.highest_field_id());
+        assert_eq!(evolved.schema_id(), schema.schema_id() + 1);
+    }
+
+    #[test]
+    fn test_evolve_rejects_incompatible_changes() {
+        let contract = contract_schema(&orders()).unwrap();
+        let schema = contract.to_iceberg_schema().unwrap();
+        let spec = contract
+            .partition_spec(&schema)
+            .unwrap()
+            .bind(schema.clone())
+            .unwrap();
+
+        let mut table = orders();
+        table.columns[0].data_type = "string".to_string();
+        let amount = &mut table.columns[1];
+        amount.physical_type = Some("DECIMAL(8,2)".to_string());
+
+        let err = contract_schema(&table)
+            .unwrap()
+            .evolve(&schema, &spec, &contract.column_properties())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("order_id: cannot change type from int to string"));
+        assert!(err.contains("amount: cannot change type"));
+    }
+
+    #[test]
+    fn test_evolve_partition_spec() {
+        let contract = contract_schema(&orders()).unwrap();
+        let schema = contract.to_iceberg_schema().unwrap();
+        let spec = contract
+            .partition_spec(&schema)
+            .unwrap()
+            .bind(schema.clone())
+            .unwrap();
+
+        let mut table = orders();
+        table.columns[0].partitioned = true;
+        table.columns[0].partition_key_position = Some(2);
+
+        let evolution = contract_schema(&table)
+            .unwrap()
+            .evolve(&schema, &spec, &contract.column_properties())
+            .unwrap();
+        assert_eq!(
+            evolution.changes,
+            vec![SchemaChange::UpdatePartitionSpec {
+                fields: vec!["order_date".to_string(), "order_id".to_string()],
+            }]
+        );
+        let new_spec = evolution.partition_spec.unwrap();
+        assert_eq!(new_spec.spec_id(), Some(spec.spec_id() + 1));
+        assert_eq!(new_spec.fields().len(), 2);
+    }
+}
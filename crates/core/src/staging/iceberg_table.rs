@@ -42,6 +42,10 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use super::catalog::{CatalogError, CatalogResult, IcebergCatalog, TableIdentifier};
+#[cfg(feature = "iceberg")]
+use super::iceberg_schema::{SchemaEvolution, contract_schema};
+#[cfg(feature = "iceberg")]
+use crate::models::Table;
 
 /// Raw JSON record for staging
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -246,16 +250,7 @@ impl IcebergTable {
         }
 
         // Create table location based on catalog warehouse
-        let location = match catalog.config() {
-            super::catalog::CatalogConfig::Rest { warehouse, .. } => {
-                format!("{}/{}/{}", warehouse, identifier.namespace, identifier.name)
-            }
-            _ => {
-                return Err(CatalogError::ConfigError(
-                    "Unsupported catalog type for table creation".to_string(),
-                ));
-            }
-        };
+        let location = catalog.table_location(identifier)?;
 
         // Create new table
         let creation = TableCreation::builder()
@@ -275,6 +270,172 @@ impl IcebergTable {
         })
     }
 
+    /// Create a typed table from an ODCS table
+    ///
+    /// The schema and partition spec are derived from the contract columns
+    /// (see [`super::iceberg_schema`]). The column path of every ODCS property
+    /// id is recorded in the table properties so later contract versions can
+    /// rename columns through [`IcebergTable::evolve_to_contract`].
+    #[cfg(feature = "iceberg")]
+    pub async fn create_from_contract(
+        catalog: &IcebergCatalog,
+        identifier: &TableIdentifier,
+        contract: &Table,
+    ) -> CatalogResult<Self> {
+        use iceberg::{NamespaceIdent, TableCreation, TableIdent};
+
+        let layout = contract_schema(contract)?;
+        let schema = layout.to_iceberg_schema()?;
+        let partition_spec = layout.partition_spec(&schema)?;
+
+        let ns_ident = NamespaceIdent::new(identifier.namespace.clone());
+        let table_ident = TableIdent::new(ns_ident.clone(), identifier.name.clone());
+
+        let inner = catalog.inner.clone();
+        if inner
+            .table_exists(&table_ident)
+            .await
+            .map_err(|e| CatalogError::IcebergError(e.to_string()))?
+        {
+            return Err(CatalogError::TableExists(identifier.to_string()));
+        }
+        if !inner
+            .namespace_exists(&ns_ident)
+            .await
+            .map_err(|e| CatalogError::IcebergError(e.to_string()))?
+        {
+            inner
+                .create_namespace(&ns_ident, HashMap::new())
+                .await
+                .map_err(|e| CatalogError::IcebergError(e.to_string()))?;
+        }
+
+        let creation = TableCreation::builder()
+            .name(identifier.name.clone())
+            .schema(schema)
+            .partition_spec(partition_spec)
+            .location(catalog.table_location(identifier)?)
+            .properties(layout.column_properties())
+            .build();
+
+        let table = inner
+            .create_table(&ns_ident, creation)
+            .await
+            .map_err(|e| CatalogError::IcebergError(e.to_string()))?;
+
+        Ok(Self {
+            identifier: identifier.clone(),
+            table: Arc::new(table),
+        })
+    }
+
+    /// Create a typed table from an ODCS table, or evolve it if it exists
+    ///
+    /// Returns the table and, for an existing table, the evolution applied.
+    #[cfg(feature = "iceberg")]
+    pub async fn create_or_evolve(
+        catalog: &IcebergCatalog,
+        identifier: &TableIdentifier,
+        contract: &Table,
+    ) -> CatalogResult<(Self, Option<SchemaEvolution>)> {
+        use super::catalog::CatalogOperations;
+
+        if catalog.table_exists(identifier).await? {
+            let mut table = Self::load(catalog, identifier).await?;
+            let evolution = table.evolve_to_contract(catalog, contract).await?;
+            Ok((table, Some(evolution)))
+        } else {
+            let table = Self::create_from_contract(catalog, identifier, contract).await?;
+            Ok((table, None))
+        }
+    }
+
+    /// Plan the schema evolution towards an ODCS table without applying it
+    #[cfg(feature = "iceberg")]
+    pub fn plan_evolution(&self, contract: &Table) -> CatalogResult<SchemaEvolution> {
+        let metadata = self.table.metadata();
+        contract_schema(contract)?.evolve(
+            metadata.current_schema(),
+            metadata.default_partition_spec(),
+            metadata.properties(),
+        )
+    }
+
+    /// Apply a changed ODCS table as Iceberg schema evolution
+    ///
+    /// Columns are added, renamed, widened or made optional in a single
+    /// commit; data files are not rewritten. Fails without changing the table
+    /// when the contract requires an incompatible change.
+    #[cfg(feature = "iceberg")]
+    pub async fn evolve_to_contract(
+        &mut self,
+        catalog: &IcebergCatalog,
+        contract: &Table,
+    ) -> CatalogResult<SchemaEvolution> {
+        use iceberg::transaction::{ApplyTransactionAction, Transaction};
+        use iceberg::{TableRequirement, TableUpdate};
+
+        let evolution = self.plan_evolution(contract)?;
+        let metadata = self.table.metadata();
+
+        let properties: HashMap<String, String> = evolution
+            .column_properties
+            .iter()
+            .filter(|(key, value)| metadata.properties().get(*key) != Some(*value))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+
+        let mut updates = Vec::new();
+        let mut requirements = Vec::new();
+        if let Some(ref schema) = evolution.schema {
+            updates.push(TableUpdate::AddSchema {
+                schema: schema.clone(),
+            });
+            updates.push(TableUpdate::SetCurrentSchema { schema_id: -1 });
+            requirements.push(TableRequirement::CurrentSchemaIdMatch {
+                current_schema_id: metadata.current_schema_id(),
+            });
+            requirements.push(TableRequirement::LastAssignedFieldIdMatch {
+                last_assigned_field_id: metadata.last_column_id(),
+            });
+        }
+        if let Some(ref spec) = evolution.partition_spec {
+            updates.push(TableUpdate::AddSpec { spec: spec.clone() });
+            updates.push(TableUpdate::SetDefaultSpec { spec_id: -1 });
+        }
+        if !properties.is_empty() {
+            updates.push(TableUpdate::SetProperties {
+                updates: properties,
+            });
+        }
+
+        if updates.is_empty() {
+            return Ok(evolution);
+        }
+
+        for change in &evolution.changes {
+            tracing::info!(table = %self.identifier.to_string(), "Schema evolution: {}", change);
+        }
+        for warning in &evolution.warnings {
+            tracing::warn!(table = %self.identifier.to_string(), "Schema evolution: {}", warning);
+        }
+
+        let action = EvolveTableAction {
+            updates,
+            requirements,
+        };
+        let transaction = action
+            .apply(Transaction::new(&self.table))
+            .map_err(|e| CatalogError::IcebergError(e.to_string()))?;
+        let table = transaction
+            .commit(catalog.inner.as_ref())
+            .await
+            .map_err(|e| CatalogError::IcebergError(e.to_string()))?;
+        self.table = Arc::new(table);
+
+        Ok(evolution)
+    }
+
     /// Load an existing table
     #[cfg(feature = "iceberg")]
     pub async fn load(
@@ -516,6 +677,27 @@ impl IcebergTable {
     }
 }
 
+/// Commits prepared table updates as one transaction action
+#[cfg(feature = "iceberg")]
+struct EvolveTableAction {
+    updates: Vec<iceberg::TableUpdate>,
+    requirements: Vec<iceberg::TableRequirement>,
+}
+
+#[cfg(feature = "iceberg")]
+#[async_trait::async_trait]
+impl iceberg::transaction::TransactionAction for EvolveTableAction {
+    async fn commit(
+        self: Arc<Self>,
+        _table: &iceberg::table::Table,
+    ) -> iceberg::Result<iceberg::transaction::ActionCommit> {
+        Ok(iceberg::transaction::ActionCommit::new(
+            self.updates.clone(),
+            self.requirements.clone(),
+        ))
+    }
+}
+
 /// Write a RecordBatch to a Parquet file
 ///
 /// This function handles:
@@ -776,4 +958,65 @@ mod tests {
         assert_eq!(id.name, "raw_json");
         assert_eq!(id.to_string(), "staging.raw_json");
     }
+
+    #[tokio::test]
+    async fn test_contract_table_create_and_evolve() {
+        use crate::models::{Column, Table};
+        use crate::staging::catalog::{CatalogConfig, CatalogOperations};
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let config = CatalogConfig::Filesystem {
+            warehouse: temp.path().display().to_string(),
+        };
+        let identifier = TableIdentifier::new("sales", "orders");
+
+        let mut order_id = Column::new("order_id".to_string(), "INT".to_string());
+        order_id.id = Some("orders_order_id".to_string());
+        order_id.nullable = false;
+        let mut order_date = Column::new("order_date".to_string(), "date".to_string());
+        order_date.partitioned = true;
+        order_date.partition_key_position = Some(1);
+        let mut contract = Table::new("orders".to_string(), vec![order_id, order_date]);
+
+        let catalog = IcebergCatalog::new(config.clone()).await.unwrap();
+        let table = IcebergTable::create_from_contract(&catalog, &identifier, &contract)
+            .await
+            .unwrap();
+        let metadata = table.inner().metadata();
+        assert_eq!(metadata.default_partition_spec().fields().len(), 1);
+        assert_eq!(
+            table.properties().get("odcs.column.orders_order_id"),
+            Some(&"order_id".to_string())
+        );
+
+        // A reopened catalog finds the table in the warehouse
+        let catalog = IcebergCatalog::new(config).await.unwrap();
+        assert!(catalog.table_exists(&identifier).await.unwrap());
+
+        contract.columns[0].name = "id".to_string();
+        contract.columns[0].data_type = "BIGINT".to_string();
+        contract
+            .columns
+            .push(Column::new("status".to_string(), "string".to_string()));
+
+        let (table, evolution) = IcebergTable::create_or_evolve(&catalog, &identifier, &contract)
+            .await
+            .unwrap();
+        let evolution = evolution.unwrap();
+        assert_eq!(evolution.changes.len(), 3);
+
+        let schema = table.inner().metadata().current_schema();
+        assert_eq!(schema.schema_id(), 1);
+        assert!(schema.field_by_name("id").is_some());
+        assert!(schema.field_by_name("order_id").is_none());
+        assert!(schema.field_by_name("status").is_some());
+        assert_eq!(
+            table.properties().get("odcs.column.orders_order_id"),
+            Some(&"id".to_string())
+        );
+
+        // Applying the same contract again changes nothing
+        let evolution = table.plan_evolution(&contract).unwrap();
+        assert!(evolution.is_empty());
+    }
 }
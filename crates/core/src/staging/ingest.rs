@@ -13,6 +13,7 @@ use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
 use super::config::DedupStrategy;
+use super::dead_letter::RejectedRecord;
 use super::error::IngestError;
 
 /// Statistics from an ingestion run
@@ -31,6 +32,10 @@ pub struct IngestStats {
     pub errors_count: usize,
     /// List of errors (limited to first 100)
     pub errors: Vec<String>,
+    /// Processing batch the files were ingested in
+    pub batch_id: Option<String>,
+    /// Whether ingestion stopped before all files were processed
+    pub interrupted: bool,
     /// Duration of the ingestion
     #[serde(skip)]
     pub duration: Duration,
@@ -171,34 +176,11 @@ pub fn parse_json_file(path: &Path) -> Result<Vec<ParsedRecord>, IngestError> {
 }
 
 /// Parse a JSONL file (newline-delimited JSON)
+///
+/// Fails on the first line that is not valid JSON; see [`parse_file_lenient`]
+/// to quarantine invalid lines instead.
 pub fn parse_jsonl_file(path: &Path) -> Result<Vec<ParsedRecord>, IngestError> {
-    let file = File::open(path)?;
-    let reader = BufReader::new(file);
-    let mut records = Vec::new();
-
-    for (index, line) in reader.lines().enumerate() {
-        let line = line?;
-        let trimmed = line.trim();
-
-        // Skip empty lines
-        if trimmed.is_empty() {
-            continue;
-        }
-
-        // Validate it's valid JSON
-        serde_json::from_str::<serde_json::Value>(trimmed).map_err(|e| IngestError::JsonParse {
-            path: path.to_path_buf(),
-            record: index,
-            error: e.to_string(),
-        })?;
-
-        records.push(ParsedRecord {
-            json: trimmed.to_string(),
-            index,
-        });
-    }
-
-    Ok(records)
+    StreamingJsonlReader::new(path)?.collect()
 }
 
 /// Parse a file based on its extension
@@ -224,6 +206,68 @@ pub fn parse_file(path: &Path) -> Result<Vec<ParsedRecord>, IngestError> {
     }
 }
 
+/// Records parsed from a file, with the records that failed to parse
+#[derive(Debug, Default)]
+pub struct LenientParse {
+    /// Valid records
+    pub records: Vec<ParsedRecord>,
+    /// Records that are not valid JSON
+    pub rejected: Vec<RejectedRecord>,
+}
+
+/// Parse a file based on its extension, quarantining invalid records
+///
+/// Invalid lines of JSONL files are returned as rejected records while the
+/// valid lines are kept. A single JSON document that fails to parse is
+/// rejected as a whole. Only I/O errors fail the file.
+pub fn parse_file_lenient(path: &Path) -> Result<LenientParse, IngestError> {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+
+    let single_document = match extension.as_str() {
+        "jsonl" | "ndjson" => false,
+        "json" => true,
+        _ => {
+            let content = fs::read(path)?;
+            let trimmed = content.trim_ascii_start();
+            trimmed.starts_with(b"[") || trimmed.starts_with(b"{")
+        }
+    };
+
+    let mut parsed = LenientParse::default();
+    if single_document {
+        let content = fs::read(path)?;
+        let result = std::str::from_utf8(&content)
+            .map_err(|e| e.to_string())
+            .and_then(|text| {
+                serde_json::from_str::<serde_json::Value>(text)
+                    .map(|_| text.trim().to_string())
+                    .map_err(|e| e.to_string())
+            });
+        match result {
+            Ok(json) => parsed.records.push(ParsedRecord { json, index: 0 }),
+            Err(error) => parsed.rejected.push(RejectedRecord {
+                file_path: path.display().to_string(),
+                line_number: None,
+                raw: content,
+                error,
+            }),
+        }
+    } else {
+        let mut reader = StreamingJsonlReader::new(path)?;
+        while let Some(outcome) = reader.next_outcome() {
+            match outcome? {
+                RecordOutcome::Parsed(record) => parsed.records.push(record),
+                RecordOutcome::Rejected(record) => parsed.rejected.push(record),
+            }
+        }
+    }
+    Ok(parsed)
+}
+
 /// Check if a file should be skipped based on dedup strategy
 pub fn should_skip_file(
     file: &DiscoveredFile,
@@ -296,10 +340,21 @@ pub fn compute_hashes_parallel(files: &mut [DiscoveredFile]) {
     });
 }
 
+/// A line read by [`StreamingJsonlReader`]
+#[derive(Debug)]
+pub enum RecordOutcome {
+    /// A valid JSON record
+    Parsed(ParsedRecord),
+    /// A line that is not valid JSON (or not UTF-8)
+    Rejected(RejectedRecord),
+}
+
 /// Streaming record iterator for memory-efficient processing
 ///
 /// Instead of loading all records into memory, this iterator yields
-/// records one at a time from a JSONL file.
+/// records one at a time from a JSONL file. Invalid lines are yielded as
+/// [`IngestError::JsonParse`] errors; use [`StreamingJsonlReader::next_outcome`]
+/// to keep their raw bytes.
 pub struct StreamingJsonlReader {
     reader: BufReader<File>,
     path: PathBuf,
@@ -316,39 +371,41 @@ impl StreamingJsonlReader {
             line_number: 0,
         })
     }
-}
 
-impl Iterator for StreamingJsonlReader {
-    type Item = Result<ParsedRecord, IngestError>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let mut line = String::new();
+    /// Read the next non-empty line as a parsed or rejected record
+    pub fn next_outcome(&mut self) -> Option<Result<RecordOutcome, IngestError>> {
+        let mut line = Vec::new();
 
         loop {
             line.clear();
-            match self.reader.read_line(&mut line) {
+            match self.reader.read_until(b'\n', &mut line) {
                 Ok(0) => return None, // EOF
                 Ok(_) => {
                     let index = self.line_number;
                     self.line_number += 1;
 
-                    let trimmed = line.trim();
+                    let trimmed = line.trim_ascii();
                     if trimmed.is_empty() {
                         continue; // Skip empty lines
                     }
 
                     // Validate JSON
-                    if let Err(e) = serde_json::from_str::<serde_json::Value>(trimmed) {
-                        return Some(Err(IngestError::JsonParse {
-                            path: self.path.clone(),
-                            record: index,
-                            error: e.to_string(),
-                        }));
-                    }
-
-                    return Some(Ok(ParsedRecord {
-                        json: trimmed.to_string(),
-                        index,
+                    let result = std::str::from_utf8(trimmed)
+                        .map_err(|e| e.to_string())
+                        .and_then(|text| {
+                            serde_json::from_str::<serde_json::Value>(text)
+                                .map(|_| text.to_string())
+                                .map_err(|e| e.to_string())
+                        });
+
+                    return Some(Ok(match result {
+                        Ok(json) => RecordOutcome::Parsed(ParsedRecord { json, index }),
+                        Err(error) => RecordOutcome::Rejected(RejectedRecord {
+                            file_path: self.path.display().to_string(),
+                            line_number: Some(index + 1),
+                            raw: trimmed.to_vec(),
+                            error,
+                        }),
                     }));
                 }
                 Err(e) => {
@@ -359,6 +416,22 @@ impl Iterator for StreamingJsonlReader {
     }
 }
 
+impl Iterator for StreamingJsonlReader {
+    type Item = Result<ParsedRecord, IngestError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(match self.next_outcome()? {
+            Ok(RecordOutcome::Parsed(record)) => Ok(record),
+            Ok(RecordOutcome::Rejected(record)) => Err(IngestError::JsonParse {
+                path: self.path.clone(),
+                record: record.line_number.unwrap_or(1) - 1,
+                error: record.error,
+            }),
+            Err(e) => Err(e),
+        })
+    }
+}
+
 /// Parallel batch processor for processing parsed records
 ///
 /// This struct provides a way to process records in parallel batches,
@@ -763,6 +836,34 @@ mod tests {
         assert_eq!(records[2].index, 2);
     }
 
+    #[test]
+    fn test_parse_file_lenient_quarantines_invalid_lines() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("test.jsonl");
+
+        let mut file = File::create(&file_path).unwrap();
+        writeln!(file, r#"{{"name": "row1"}}"#).unwrap();
+        writeln!(file, r#"{{"name": "row2""#).unwrap();
+        writeln!(file).unwrap();
+        file.write_all(b"{\"name\": \"\xff\"}\n").unwrap();
+        writeln!(file, r#"{{"name": "row5"}}"#).unwrap();
+
+        let parsed = parse_file_lenient(&file_path).unwrap();
+        assert_eq!(parsed.records.len(), 2);
+        assert_eq!(parsed.records[1].index, 4);
+        assert_eq!(parsed.rejected.len(), 2);
+        assert_eq!(parsed.rejected[0].line_number, Some(2));
+        assert_eq!(parsed.rejected[0].raw, br#"{"name": "row2""#.to_vec());
+        assert_eq!(parsed.rejected[1].line_number, Some(4));
+        assert_eq!(parsed.rejected[1].raw, b"{\"name\": \"\xff\"}".to_vec());
+
+        // Strict parsing still fails the file
+        assert!(matches!(
+            parse_jsonl_file(&file_path),
+            Err(IngestError::JsonParse { record: 1, .. })
+        ));
+    }
+
     #[test]
     fn test_discovered_file_hash() {
         let dir = TempDir::new().unwrap();
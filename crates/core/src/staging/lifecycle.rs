@@ -0,0 +1,283 @@
+//! Partition lifecycle management
+//!
+//! Partitions of `staged_json` can be dropped, renamed, merged and compacted,
+//! archived to Parquet and expired by a [`RetentionPolicy`]. The operations are
+//! methods of `StagingDb`; this module holds their inputs and results.
+//!
+//! Dropping, renaming and merging keep `dead_letters` and `processing_batches`
+//! in step with the staged records of a partition. Renaming also moves data
+//! profiles and inferred schemas; dropping and merging keep them as history.
+//!
+//! The counters of a processing batch record what the batch ingested, not what
+//! is staged now: compaction removes records without changing them, since
+//! staged records do not reference the batch that staged them.
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Summary of a partition of staged records
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartitionInfo {
+    /// Partition key (None for records staged without a partition)
+    pub partition: Option<String>,
+    /// Number of staged records
+    pub record_count: i64,
+    /// Number of source files
+    pub file_count: i64,
+    /// Number of processing batches
+    pub batch_count: i64,
+    /// When the first record was staged
+    pub first_ingested_at: Option<NaiveDateTime>,
+    /// When the last record was staged
+    pub last_ingested_at: Option<NaiveDateTime>,
+    /// Full days since the last record was staged
+    pub age_days: i64,
+}
+
+impl PartitionInfo {
+    /// Partition key for display
+    pub fn name(&self) -> &str {
+        self.partition.as_deref().unwrap_or("<none>")
+    }
+}
+
+/// Rules for expiring partitions
+///
+/// Only named partitions expire; records staged without a partition are kept.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionPolicy {
+    /// Drop partitions whose last record is at least this many days old
+    pub max_age_days: Option<i64>,
+    /// Keep only this many partitions, by most recent ingestion
+    pub max_partitions: Option<usize>,
+    /// Archive partitions to Parquet files in this directory before dropping
+    pub archive_dir: Option<PathBuf>,
+}
+
+impl RetentionPolicy {
+    /// Drop partitions whose last record is at least `days` old
+    pub fn with_max_age_days(mut self, days: i64) -> Self {
+        self.max_age_days = Some(days);
+        self
+    }
+
+    /// Keep only the `count` most recently ingested partitions
+    pub fn with_max_partitions(mut self, count: usize) -> Self {
+        self.max_partitions = Some(count);
+        self
+    }
+
+    /// Archive partitions to Parquet files in `dir` before dropping them
+    pub fn with_archive_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.archive_dir = Some(dir.into());
+        self
+    }
+
+    /// Whether the policy expires nothing
+    pub fn is_unlimited(&self) -> bool {
+        self.max_age_days.is_none() && self.max_partitions.is_none()
+    }
+
+    /// Partitions that expire under this policy, oldest first
+    pub fn expired<'a>(&self, partitions: &'a [PartitionInfo]) -> Vec<&'a PartitionInfo> {
+        let mut named: Vec<&PartitionInfo> = partitions
+            .iter()
+            .filter(|p| p.partition.is_some())
+            .collect();
+        // Most recent first, so that the partitions to keep come first
+        named.sort_by(|a, b| b.last_ingested_at.cmp(&a.last_ingested_at));
+
+        let mut expired: Vec<&PartitionInfo> = named
+            .into_iter()
+            .enumerate()
+            .filter(|(rank, p)| {
+                self.max_partitions.is_some_and(|max| *rank >= max)
+                    || self.max_age_days.is_some_and(|days| p.age_days >= days)
+            })
+            .map(|(_, p)| p)
+            .collect();
+        expired.reverse();
+        expired
+    }
+
+    /// Archive file of a partition archived at `archived_at`
+    pub fn archive_path(&self, partition: &str, archived_at: DateTime<Utc>) -> Option<PathBuf> {
+        self.archive_dir
+            .as_deref()
+            .map(|dir| archive_file_path(dir, partition, archived_at))
+    }
+}
+
+/// Parquet file a partition is archived to inside `dir`
+///
+/// The file is named after the partition, with characters that are not safe
+/// in file names replaced with `_`, and the archive time. A counter is added
+/// if that file exists, so a new archive never replaces an older one.
+pub fn archive_file_path(dir: &Path, partition: &str, archived_at: DateTime<Utc>) -> PathBuf {
+    let name: String = partition
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let stem = format!("{}-{}", name, archived_at.format("%Y%m%dT%H%M%SZ"));
+
+    let mut path = dir.join(format!("{}.parquet", stem));
+    let mut counter = 1;
+    while path.exists() {
+        path = dir.join(format!("{}-{}.parquet", stem, counter));
+        counter += 1;
+    }
+    path
+}
+
+/// A partition removed from the staging database
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DroppedPartition {
+    /// Partition key
+    pub partition: Option<String>,
+    /// Number of staged records removed
+    pub records_removed: u64,
+    /// Number of processing batches removed
+    pub batches_removed: u64,
+    /// Parquet file the records were archived to
+    pub archive: Option<PathBuf>,
+}
+
+/// A partition that could not be dropped by a retention policy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FailedPartition {
+    /// Partition key
+    pub partition: Option<String>,
+    /// Why the partition was kept
+    pub error: String,
+}
+
+/// Result of applying a retention policy
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionOutcome {
+    /// Partitions that were dropped
+    pub dropped: Vec<DroppedPartition>,
+    /// Expired partitions that could not be dropped
+    pub failed: Vec<FailedPartition>,
+}
+
+/// Result of compacting a partition
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompactionStats {
+    /// Records in the partition before compaction
+    pub records_before: u64,
+    /// Byte-identical records removed, keeping the first staged copy
+    pub duplicates_removed: u64,
+}
+
+/// Size of the database file before and after a vacuum
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VacuumStats {
+    /// File size in bytes before (None for in-memory databases)
+    pub size_before: Option<u64>,
+    /// File size in bytes after (None for in-memory databases)
+    pub size_after: Option<u64>,
+}
+
+impl VacuumStats {
+    /// Bytes reclaimed by the vacuum
+    pub fn reclaimed(&self) -> u64 {
+        match (self.size_before, self.size_after) {
+            (Some(before), Some(after)) => before.saturating_sub(after),
+            _ => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn partition(name: Option<&str>, day: u32, age_days: i64) -> PartitionInfo {
+        let ingested =
+            chrono::NaiveDate::from_ymd_opt(2024, 1, day).and_then(|d| d.and_hms_opt(12, 0, 0));
+        PartitionInfo {
+            partition: name.map(str::to_string),
+            record_count: 1,
+            file_count: 1,
+            batch_count: 1,
+            first_ingested_at: ingested,
+            last_ingested_at: ingested,
+            age_days,
+        }
+    }
+
+    #[test]
+    fn test_retention_policy_expired() {
+        let partitions = vec![
+            partition(Some("2024-01-01"), 1, 30),
+            partition(Some("2024-01-20"), 20, 11),
+            partition(Some("2024-01-10"), 10, 21),
+            partition(None, 2, 29),
+        ];
+
+        let names = |policy: &RetentionPolicy| -> Vec<String> {
+            policy
+                .expired(&partitions)
+                .iter()
+                .map(|p| p.name().to_string())
+                .collect()
+        };
+
+        assert!(names(&RetentionPolicy::default()).is_empty());
+        assert_eq!(
+            names(&RetentionPolicy::default().with_max_age_days(21)),
+            vec!["2024-01-01", "2024-01-10"]
+        );
+        assert_eq!(
+            names(&RetentionPolicy::default().with_max_partitions(2)),
+            vec!["2024-01-01"]
+        );
+        assert_eq!(
+            names(
+                &RetentionPolicy::default()
+                    .with_max_partitions(2)
+                    .with_max_age_days(15)
+            ),
+            vec!["2024-01-01", "2024-01-10"]
+        );
+    }
+
+    #[test]
+    fn test_archive_file_path() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let archived_at = DateTime::parse_from_rfc3339("2024-02-01T08:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let path = archive_file_path(dir.path(), "region=eu/2024-01", archived_at);
+        assert_eq!(
+            path,
+            dir.path()
+                .join("region_eu_2024-01-20240201T083000Z.parquet")
+        );
+
+        // An existing archive, e.g. of a partition with the same sanitised
+        // name, is never reused
+        std::fs::write(&path, b"").unwrap();
+        assert_eq!(
+            archive_file_path(dir.path(), "region_eu/2024-01", archived_at),
+            dir.path()
+                .join("region_eu_2024-01-20240201T083000Z-1.parquet")
+        );
+    }
+}
@@ -8,7 +8,11 @@
 //! - **Large dataset handling** - Process millions of records without loading into memory
 //! - **Deduplication** - Skip already-ingested files by path or content hash
 //! - **Batch tracking** - Resume interrupted ingestions
+//! - **Partition lifecycle** - Drop, rename, merge, compact, archive and expire partitions
+//! - **Watch mode** - Continuously ingest new and changed files from a landing directory
+//! - **Dead letters** - Quarantine unparseable records and fail batches over an error budget
 //! - **SQL queries** - Analyze staged data before export
+//! - **Profiling** - Per-field completeness, distributions and formats of staged data
 //!
 //! ## Example
 //!
@@ -37,12 +41,21 @@ mod batch;
 pub mod catalog;
 mod config;
 mod db;
+mod dead_letter;
 mod error;
 #[cfg(feature = "iceberg")]
 pub mod export;
 #[cfg(feature = "iceberg")]
+pub mod iceberg_schema;
+#[cfg(feature = "iceberg")]
 pub mod iceberg_table;
 mod ingest;
+#[cfg(feature = "duckdb-backend")]
+pub mod lifecycle;
+#[cfg(feature = "parquet-export")]
+pub mod parquet_export;
+#[cfg(feature = "duckdb-backend")]
+pub mod profile;
 #[cfg(feature = "staging")]
 pub mod progress;
 #[cfg(feature = "s3")]
@@ -50,30 +63,53 @@ pub mod s3;
 mod schema;
 #[cfg(feature = "databricks")]
 pub mod unity;
+#[cfg(feature = "duckdb-backend")]
+pub mod watch;
 
 pub use batch::{BatchStatus, ProcessingBatch};
 #[cfg(feature = "iceberg")]
 pub use catalog::{
     CatalogConfig, CatalogError, CatalogOperations, IcebergCatalog, TableIdentifier, TableInfo,
 };
-pub use config::{DedupStrategy, IngestConfig, IngestConfigBuilder, SourceType};
+pub use config::{DedupStrategy, ErrorBudget, IngestConfig, IngestConfigBuilder, SourceType};
 #[cfg(feature = "duckdb-backend")]
 pub use db::StagingDb;
 #[cfg(feature = "postgres-backend")]
 pub use db::StagingDbPostgres;
+pub use dead_letter::{DeadLetter, DeadLetterStatus, RejectedRecord};
 pub use error::{IngestError, StagingError};
 #[cfg(feature = "iceberg")]
 pub use export::{ExportConfig, ExportResult, ExportTarget};
 #[cfg(feature = "iceberg")]
+pub use iceberg_schema::{
+    ContractField, ContractPartition, ContractSchema, ContractType, SchemaChange, SchemaEvolution,
+    contract_schema,
+};
+#[cfg(feature = "iceberg")]
 pub use iceberg_table::{
     BatchMetadata as IcebergBatchMetadata, BatchStatus as IcebergBatchStatus, IcebergTable,
 };
 pub use ingest::{
-    DiscoveredFile, IngestStats, ParallelBatchProcessor, ParsedFile, ParsedRecord,
-    StreamingJsonlReader, compute_hashes_parallel, parse_files_parallel,
+    DiscoveredFile, IngestStats, LenientParse, ParallelBatchProcessor, ParsedFile, ParsedRecord,
+    RecordOutcome, StreamingJsonlReader, compute_hashes_parallel, parse_file_lenient,
+    parse_files_parallel,
 };
 #[cfg(feature = "iceberg")]
 pub use ingest::{IcebergIngestConfig, ingest_to_iceberg, ingest_to_iceberg_with_config};
+#[cfg(feature = "duckdb-backend")]
+pub use lifecycle::{
+    CompactionStats, DroppedPartition, FailedPartition, PartitionInfo, RetentionOutcome,
+    RetentionPolicy, VacuumStats, archive_file_path,
+};
+#[cfg(feature = "parquet-export")]
+pub use parquet_export::{
+    ParquetCompression, ParquetExportConfig, ParquetExportResult, ParquetExporter,
+    arrow_schema_from_inferred, arrow_schema_from_table,
+};
+#[cfg(feature = "duckdb-backend")]
+pub use profile::{
+    DataProfile, DataProfiler, Distribution, FieldProfile, ProfileConfig, ValueCount,
+};
 pub use schema::StagingSchema;
 
 #[cfg(feature = "staging")]
@@ -82,3 +118,5 @@ pub use progress::{InferenceProgress, IngestProgress, Spinner, format_bytes, for
 pub use s3::{S3Ingester, S3Source, SecureCredentials, redact_secret, redact_secrets_in_string};
 #[cfg(feature = "databricks")]
 pub use unity::{UnityVolumeIngester, UnityVolumeSource};
+#[cfg(feature = "duckdb-backend")]
+pub use watch::{WatchConfig, WatchCycle, WatchPartition, Watcher};
@@ -0,0 +1,814 @@
+//! Typed Parquet export of staged JSON
+//!
+//! Projects the raw JSON records of a staging partition through an
+//! [`InferredSchema`] or an ODCS [`Table`] into a typed Arrow schema and writes
+//! Parquet files:
+//!
+//! - Nested objects become structs and arrays become lists
+//! - `date`, `date-time` and `time` formats become Arrow temporal types
+//! - `DECIMAL(p,s)` columns become `Decimal128`
+//! - Values that do not fit a string column (objects, mixed types) are written
+//!   as JSON text
+//!
+//! Files are written Hive-style to `<output_dir>/partition_key=<value>/` when a
+//! partition is selected, and split into `part-NNNNN.parquet` files once
+//! `max_rows_per_file` is reached. Records that cannot be converted to the
+//! target schema are skipped and reported in the [`ParquetExportResult`].
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use data_modelling_core::staging::{ParquetCompression, ParquetExportConfig, ParquetExporter, StagingDb};
+//!
+//! let db = StagingDb::open("staging.duckdb")?;
+//! let config = ParquetExportConfig::new("./export")
+//!     .with_partition("2024-01")
+//!     .with_compression(ParquetCompression::Zstd);
+//!
+//! let result = ParquetExporter::from_inferred(&schema, config).export(&db)?;
+//! println!("Wrote {} records to {} files", result.records_written, result.files.len());
+//! ```
+
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use arrow::datatypes::{DataType, Field, Fields, Schema, SchemaRef, TimeUnit};
+use arrow::error::ArrowError;
+use arrow::json::ReaderBuilder;
+use arrow::record_batch::RecordBatch;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::{Compression, GzipLevel, ZstdLevel};
+use parquet::file::properties::WriterProperties;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{debug, warn};
+
+use super::db::StagingDb;
+use super::error::StagingError;
+use crate::inference::{Format, InferredSchema, InferredType};
+use crate::models::{Column, Table};
+
+/// Precision and scale used for decimal columns that do not declare them
+const DEFAULT_DECIMAL: (u8, i8) = (38, 9);
+
+/// Parquet compression codec
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParquetCompression {
+    /// No compression
+    None,
+    /// Snappy (fast, moderate ratio)
+    #[default]
+    Snappy,
+    /// Gzip (slower, better ratio)
+    Gzip,
+    /// Zstandard (good balance of speed and ratio)
+    Zstd,
+}
+
+impl ParquetCompression {
+    fn codec(self) -> Compression {
+        match self {
+            ParquetCompression::None => Compression::UNCOMPRESSED,
+            ParquetCompression::Snappy => Compression::SNAPPY,
+            ParquetCompression::Gzip => Compression::GZIP(GzipLevel::default()),
+            ParquetCompression::Zstd => Compression::ZSTD(ZstdLevel::default()),
+        }
+    }
+}
+
+impl FromStr for ParquetCompression {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" | "uncompressed" => Ok(ParquetCompression::None),
+            "snappy" => Ok(ParquetCompression::Snappy),
+            "gzip" => Ok(ParquetCompression::Gzip),
+            "zstd" => Ok(ParquetCompression::Zstd),
+            _ => Err(format!(
+                "Unknown compression '{}'. Valid values: none, snappy, gzip, zstd",
+                s
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for ParquetCompression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            ParquetCompression::None => "none",
+            ParquetCompression::Snappy => "snappy",
+            ParquetCompression::Gzip => "gzip",
+            ParquetCompression::Zstd => "zstd",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Configuration for a typed Parquet export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParquetExportConfig {
+    /// Directory the Parquet files are written to
+    pub output_dir: PathBuf,
+    /// Staging partition to export (all records when unset)
+    pub partition: Option<String>,
+    /// Maximum rows per Parquet row group
+    pub row_group_size: usize,
+    /// Maximum rows per file (0 = single file)
+    pub max_rows_per_file: usize,
+    /// Records read from the staging database at a time
+    pub batch_size: usize,
+    /// Compression codec
+    pub compression: ParquetCompression,
+}
+
+impl Default for ParquetExportConfig {
+    fn default() -> Self {
+        Self {
+            output_dir: PathBuf::from("export"),
+            partition: None,
+            row_group_size: 100_000,
+            max_rows_per_file: 1_000_000,
+            batch_size: 10_000,
+            compression: ParquetCompression::default(),
+        }
+    }
+}
+
+impl ParquetExportConfig {
+    /// Create a config writing to the given directory
+    pub fn new(output_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            output_dir: output_dir.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Export a single staging partition
+    pub fn with_partition(mut self, partition: impl Into<String>) -> Self {
+        self.partition = Some(partition.into());
+        self
+    }
+
+    /// Set the maximum rows per row group
+    pub fn with_row_group_size(mut self, rows: usize) -> Self {
+        self.row_group_size = rows;
+        self
+    }
+
+    /// Set the maximum rows per file (0 = single file)
+    pub fn with_max_rows_per_file(mut self, rows: usize) -> Self {
+        self.max_rows_per_file = rows;
+        self
+    }
+
+    /// Set the number of records read from the staging database at a time
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Set the compression codec
+    pub fn with_compression(mut self, compression: ParquetCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Directory the files of the selected partition are written to
+    pub fn partition_dir(&self) -> PathBuf {
+        match self.partition {
+            Some(ref partition) => self.output_dir.join(format!("partition_key={}", partition)),
+            None => self.output_dir.clone(),
+        }
+    }
+}
+
+/// Result of a typed Parquet export
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ParquetExportResult {
+    /// Parquet files written
+    pub files: Vec<PathBuf>,
+    /// Records written
+    pub records_written: usize,
+    /// Records skipped because they could not be converted
+    pub records_skipped: usize,
+    /// Total size of the written files in bytes
+    pub bytes_written: u64,
+    /// Conversion errors (record id and reason)
+    pub errors: Vec<String>,
+}
+
+/// Writes staged JSON records to typed Parquet files
+pub struct ParquetExporter {
+    schema: SchemaRef,
+    config: ParquetExportConfig,
+}
+
+impl ParquetExporter {
+    /// Create an exporter for an Arrow schema
+    pub fn new(schema: Schema, config: ParquetExportConfig) -> Self {
+        Self {
+            schema: Arc::new(schema),
+            config,
+        }
+    }
+
+    /// Create an exporter using the schema inferred from the staged data
+    pub fn from_inferred(schema: &InferredSchema, config: ParquetExportConfig) -> Self {
+        Self::new(arrow_schema_from_inferred(schema), config)
+    }
+
+    /// Create an exporter using the columns of an ODCS table
+    pub fn from_table(table: &Table, config: ParquetExportConfig) -> Self {
+        Self::new(arrow_schema_from_table(table), config)
+    }
+
+    /// The Arrow schema records are projected through
+    pub fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    /// Export the configured partition to Parquet
+    ///
+    /// Existing `part-*.parquet` files in the target directory are replaced.
+    /// Nothing is written when the partition has no records.
+    pub fn export(&self, db: &StagingDb) -> Result<ParquetExportResult, StagingError> {
+        if self.schema.fields().is_empty() {
+            return Err(StagingError::Parquet(
+                "Schema has no columns to export".to_string(),
+            ));
+        }
+
+        let dir = self.config.partition_dir();
+        fs::create_dir_all(&dir)?;
+        remove_part_files(&dir)?;
+
+        let mut result = ParquetExportResult::default();
+        let mut writer: Option<PartWriter> = None;
+        let batch_size = self.config.batch_size.max(1);
+        let mut after_id = 0;
+
+        loop {
+            let records =
+                db.get_records_after(after_id, batch_size, self.config.partition.as_deref())?;
+            let Some(&(last_id, _)) = records.last() else {
+                break;
+            };
+            after_id = last_id;
+
+            let Some(mut batch) = self.decode_records(&records, &mut result)? else {
+                continue;
+            };
+
+            while batch.num_rows() > 0 {
+                if writer.is_none() {
+                    writer = Some(self.open_part(&dir, result.files.len())?);
+                }
+                let part = writer.as_mut().expect("writer is open");
+
+                let room = if self.config.max_rows_per_file == 0 {
+                    batch.num_rows()
+                } else {
+                    self.config.max_rows_per_file - part.rows
+                };
+                let take = room.min(batch.num_rows());
+
+                part.write(&batch.slice(0, take))?;
+                result.records_written += take;
+                batch = batch.slice(take, batch.num_rows() - take);
+
+                if self.config.max_rows_per_file > 0 && part.rows >= self.config.max_rows_per_file {
+                    let part = writer.take().expect("writer is open");
+                    self.close_part(part, &mut result)?;
+                }
+            }
+        }
+
+        if let Some(part) = writer.take() {
+            self.close_part(part, &mut result)?;
+        }
+
+        debug!(
+            records_written = result.records_written,
+            records_skipped = result.records_skipped,
+            files = result.files.len(),
+            "Parquet export complete"
+        );
+
+        Ok(result)
+    }
+
+    /// Parse and convert a chunk of staged records, skipping those that do not fit the schema
+    fn decode_records(
+        &self,
+        records: &[(i64, String)],
+        result: &mut ParquetExportResult,
+    ) -> Result<Option<RecordBatch>, StagingError> {
+        let mut rows = Vec::with_capacity(records.len());
+        for (id, raw) in records {
+            match serde_json::from_str::<Value>(raw) {
+                Ok(value) => rows.push((*id, conform_record(value, self.schema.fields()))),
+                Err(e) => skip_record(result, *id, &e.to_string()),
+            }
+        }
+
+        let values: Vec<&Value> = rows.iter().map(|(_, value)| value).collect();
+        match self.decode_rows(&values) {
+            Ok(batch) => return Ok(batch),
+            Err(e) => debug!(error = %e, "Batch conversion failed, converting records one by one"),
+        }
+
+        // Find the offending records and convert the rest
+        let mut valid = Vec::with_capacity(values.len());
+        for (id, value) in &rows {
+            match self.decode_rows(&[value]) {
+                Ok(_) => valid.push(value),
+                Err(e) => skip_record(result, *id, &e.to_string()),
+            }
+        }
+        self.decode_rows(&valid)
+            .map_err(|e| StagingError::Parquet(e.to_string()))
+    }
+
+    fn decode_rows(&self, rows: &[&Value]) -> Result<Option<RecordBatch>, ArrowError> {
+        let mut decoder = ReaderBuilder::new(self.schema.clone())
+            .with_batch_size(rows.len().max(1))
+            .with_coerce_primitive(true)
+            .build_decoder()?;
+        decoder.serialize(rows)?;
+        decoder.flush()
+    }
+
+    fn open_part(&self, dir: &Path, index: usize) -> Result<PartWriter, StagingError> {
+        let path = dir.join(format!("part-{:05}.parquet", index));
+        let file = File::create(&path)?;
+        let props = WriterProperties::builder()
+            .set_compression(self.config.compression.codec())
+            .set_max_row_group_size(self.config.row_group_size.max(1))
+            .build();
+        let writer = ArrowWriter::try_new(file, self.schema.clone(), Some(props))
+            .map_err(|e| StagingError::Parquet(e.to_string()))?;
+        Ok(PartWriter {
+            path,
+            writer,
+            rows: 0,
+        })
+    }
+
+    fn close_part(
+        &self,
+        part: PartWriter,
+        result: &mut ParquetExportResult,
+    ) -> Result<(), StagingError> {
+        part.writer
+            .close()
+            .map_err(|e| StagingError::Parquet(e.to_string()))?;
+        result.bytes_written += fs::metadata(&part.path)?.len();
+        result.files.push(part.path);
+        Ok(())
+    }
+}
+
+/// An open Parquet file and the rows written to it
+struct PartWriter {
+    path: PathBuf,
+    writer: ArrowWriter<File>,
+    rows: usize,
+}
+
+impl PartWriter {
+    fn write(&mut self, batch: &RecordBatch) -> Result<(), StagingError> {
+        self.writer
+            .write(batch)
+            .map_err(|e| StagingError::Parquet(e.to_string()))?;
+        self.rows += batch.num_rows();
+        Ok(())
+    }
+}
+
+fn skip_record(result: &mut ParquetExportResult, id: i64, reason: &str) {
+    warn!(record_id = id, error = %reason, "Skipping record that does not fit the export schema");
+    result.records_skipped += 1;
+    result.errors.push(format!("record {}: {}", id, reason));
+}
+
+/// Remove Parquet part files left by a previous export
+fn remove_part_files(dir: &Path) -> Result<(), StagingError> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let is_part = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with("part-") && n.ends_with(".parquet"));
+        if is_part {
+            fs::remove_file(&path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Build an Arrow schema from an inferred schema
+///
+/// Every field is nullable: the schema is inferred from a sample, and a field
+/// required there may still be missing from other exported records. Use
+/// [`arrow_schema_from_table`] with a contract to make fields non-nullable.
+pub fn arrow_schema_from_inferred(schema: &InferredSchema) -> Schema {
+    let InferredType::Object { properties } = &schema.root else {
+        return Schema::empty();
+    };
+    let fields: Vec<Field> = properties
+        .iter()
+        .map(|(name, field)| Field::new(name, inferred_to_arrow(&field.field_type), true))
+        .collect();
+    Schema::new(fields)
+}
+
+/// Map an inferred type to an Arrow data type
+fn inferred_to_arrow(inferred: &InferredType) -> DataType {
+    match inferred {
+        InferredType::Boolean => DataType::Boolean,
+        InferredType::Integer => DataType::Int64,
+        InferredType::Number => DataType::Float64,
+        InferredType::String { format } => match format {
+            Some(Format::Date) => DataType::Date32,
+            Some(Format::DateTime) => utc_timestamp(),
+            Some(Format::Time) => DataType::Time64(TimeUnit::Microsecond),
+            _ => DataType::Utf8,
+        },
+        InferredType::Array { items } => list_of(inferred_to_arrow(items)),
+        InferredType::Object { properties } if !properties.is_empty() => {
+            DataType::Struct(Fields::from(
+                properties
+                    .iter()
+                    .map(|(name, field)| {
+                        Field::new(name, inferred_to_arrow(&field.field_type), true)
+                    })
+                    .collect::<Vec<_>>(),
+            ))
+        }
+        // Empty objects, mixed and unknown types are kept as JSON text
+        InferredType::Object { .. }
+        | InferredType::Mixed { .. }
+        | InferredType::Null
+        | InferredType::Unknown => DataType::Utf8,
+    }
+}
+
+/// Build an Arrow schema from the columns of an ODCS table
+///
+/// Nested columns use the dot-notation layout of the ODCS converters
+/// (`address.city`, `items.[].sku`) and are rebuilt into structs and lists.
+/// Top-level nullability follows `Column::nullable`; nested fields are always
+/// nullable.
+pub fn arrow_schema_from_table(table: &Table) -> Schema {
+    let fields: Vec<Field> = table
+        .columns
+        .iter()
+        .filter(|c| !c.name.contains('.'))
+        .map(|column| {
+            Field::new(
+                &column.name,
+                column_to_arrow(column, &column.name, &table.columns),
+                column.nullable,
+            )
+        })
+        .collect();
+    Schema::new(fields)
+}
+
+/// Arrow type of a column, including its nested child columns
+fn column_to_arrow(column: &Column, path: &str, columns: &[Column]) -> DataType {
+    let data_type = column.data_type.trim().to_uppercase();
+
+    if let Some(inner) = data_type
+        .strip_prefix("ARRAY<")
+        .and_then(|t| t.strip_suffix('>'))
+    {
+        let item_path = format!("{}.[]", path);
+        let item = match child_struct(&item_path, columns) {
+            Some(item) => item,
+            None => sql_type_to_arrow(inner, column),
+        };
+        return list_of(item);
+    }
+    if data_type == "ARRAY" {
+        let item_path = format!("{}.[]", path);
+        return list_of(child_struct(&item_path, columns).unwrap_or(DataType::Utf8));
+    }
+
+    if is_struct_type(&data_type) {
+        return child_struct(path, columns).unwrap_or(DataType::Utf8);
+    }
+
+    sql_type_to_arrow(&data_type, column)
+}
+
+/// Struct type built from the direct children of a column path, if it has any
+fn child_struct(path: &str, columns: &[Column]) -> Option<DataType> {
+    let prefix = format!("{}.", path);
+    let fields: Vec<Field> = columns
+        .iter()
+        .filter_map(|column| {
+            let name = column.name.strip_prefix(&prefix)?;
+            if name.contains('.') {
+                return None;
+            }
+            Some(Field::new(
+                name,
+                column_to_arrow(column, &column.name, columns),
+                true,
+            ))
+        })
+        .collect();
+
+    if fields.is_empty() {
+        None
+    } else {
+        Some(DataType::Struct(Fields::from(fields)))
+    }
+}
+
+fn is_struct_type(data_type: &str) -> bool {
+    matches!(data_type, "STRUCT" | "OBJECT" | "RECORD") || data_type.starts_with("STRUCT<")
+}
+
+/// Map a scalar SQL/ODCS type name to an Arrow data type
+fn sql_type_to_arrow(data_type: &str, column: &Column) -> DataType {
+    let data_type = data_type.trim().to_uppercase();
+    let (base, params) = match data_type.split_once('(') {
+        Some((base, rest)) => (base.trim(), Some(rest.trim_end_matches(')'))),
+        None => (data_type.as_str(), None),
+    };
+
+    match base {
+        "BOOLEAN" | "BOOL" => DataType::Boolean,
+        "TINYINT" | "SMALLINT" | "INT" | "INT32" => DataType::Int32,
+        "INTEGER" | "BIGINT" | "LONG" | "INT64" => DataType::Int64,
+        "FLOAT" | "REAL" | "FLOAT32" => DataType::Float32,
+        "DOUBLE" | "DOUBLE PRECISION" | "NUMBER" | "FLOAT64" => DataType::Float64,
+        "DECIMAL" | "NUMERIC" => decimal_type(params, column),
+        "DATE" => DataType::Date32,
+        "TIMESTAMP" | "TIMESTAMP_TZ" | "TIMESTAMPTZ" | "TIMESTAMP WITH TIME ZONE" | "DATETIME" => {
+            utc_timestamp()
+        }
+        "TIMESTAMP_NTZ" | "TIMESTAMP WITHOUT TIME ZONE" => {
+            DataType::Timestamp(TimeUnit::Microsecond, None)
+        }
+        "TIME" => DataType::Time64(TimeUnit::Microsecond),
+        _ => {
+            // Strings may carry a temporal format in their logical type options
+            let format = column
+                .logical_type_options
+                .as_ref()
+                .and_then(|o| o.format.as_deref());
+            match format {
+                Some("date") => DataType::Date32,
+                Some("date-time") => utc_timestamp(),
+                Some("time") => DataType::Time64(TimeUnit::Microsecond),
+                _ => DataType::Utf8,
+            }
+        }
+    }
+}
+
+/// Decimal type from `DECIMAL(p,s)` parameters or the column's logical type options
+fn decimal_type(params: Option<&str>, column: &Column) -> DataType {
+    let mut precision = None;
+    let mut scale = None;
+
+    if let Some(params) = params {
+        let mut parts = params.split(',').map(|p| p.trim().parse::<i32>().ok());
+        precision = parts.next().flatten();
+        scale = parts.next().flatten();
+    }
+    if let Some(ref options) = column.logical_type_options {
+        precision = precision.or(options.precision);
+        scale = scale.or(options.scale);
+    }
+
+    let precision = precision
+        .map(|p| p.clamp(1, 38) as u8)
+        .unwrap_or(DEFAULT_DECIMAL.0);
+    let scale = scale
+        .map(|s| s.clamp(0, precision as i32) as i8)
+        .unwrap_or(DEFAULT_DECIMAL.1.min(precision as i8));
+    DataType::Decimal128(precision, scale)
+}
+
+fn utc_timestamp() -> DataType {
+    DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into()))
+}
+
+fn list_of(item: DataType) -> DataType {
+    DataType::List(Arc::new(Field::new("item", item, true)))
+}
+
+/// Reshape a record so values fit the schema
+///
+/// Objects and arrays in string columns are replaced by their JSON text; other
+/// mismatches are left for the Arrow decoder to reject.
+fn conform_record(value: Value, fields: &Fields) -> Value {
+    match value {
+        Value::Object(mut map) => {
+            for field in fields {
+                if let Some(v) = map.remove(field.name()) {
+                    map.insert(field.name().clone(), conform_value(v, field.data_type()));
+                }
+            }
+            Value::Object(map)
+        }
+        other => other,
+    }
+}
+
+fn conform_value(value: Value, data_type: &DataType) -> Value {
+    match (data_type, value) {
+        (DataType::Utf8, v @ (Value::Object(_) | Value::Array(_))) => Value::String(v.to_string()),
+        (DataType::Struct(fields), v @ Value::Object(_)) => conform_record(v, fields),
+        (DataType::List(item), Value::Array(items)) => Value::Array(
+            items
+                .into_iter()
+                .map(|v| conform_value(v, item.data_type()))
+                .collect(),
+        ),
+        (_, v) => v,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inference::SchemaInferrer;
+    use crate::staging::{IngestConfig, SourceType};
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    fn staged_db(dir: &Path, lines: &[&str]) -> StagingDb {
+        let mut f = File::create(dir.join("records.jsonl")).unwrap();
+        for line in lines {
+            writeln!(f, "{}", line).unwrap();
+        }
+
+        let db = StagingDb::memory().unwrap();
+        db.init().unwrap();
+        let config = IngestConfig::builder()
+            .source_type(SourceType::Local(dir.to_path_buf()))
+            .pattern("*.jsonl")
+            .partition("orders")
+            .build()
+            .unwrap();
+        db.ingest(&config).unwrap();
+        db
+    }
+
+    #[test]
+    fn test_arrow_schema_from_inferred() {
+        let mut inferrer = SchemaInferrer::new();
+        inferrer
+            .add_json(r#"{"id": 1, "created": "2024-01-15T10:30:00Z", "customer": {"name": "Ada"}, "tags": ["a"]}"#)
+            .unwrap();
+        let schema = arrow_schema_from_inferred(&inferrer.finalize().unwrap());
+
+        let id = schema.field_with_name("id").unwrap();
+        assert_eq!(id.data_type(), &DataType::Int64);
+        assert!(id.is_nullable());
+        assert_eq!(
+            schema.field_with_name("created").unwrap().data_type(),
+            &utc_timestamp()
+        );
+        assert!(matches!(
+            schema.field_with_name("customer").unwrap().data_type(),
+            DataType::Struct(_)
+        ));
+        assert_eq!(
+            schema.field_with_name("tags").unwrap().data_type(),
+            &list_of(DataType::Utf8)
+        );
+    }
+
+    #[test]
+    fn test_arrow_schema_from_table() {
+        let column = |name: &str, data_type: &str| Column {
+            name: name.to_string(),
+            data_type: data_type.to_string(),
+            nullable: true,
+            ..Default::default()
+        };
+        let table = Table::new(
+            "orders".to_string(),
+            vec![
+                Column {
+                    nullable: false,
+                    ..column("id", "BIGINT")
+                },
+                column("amount", "DECIMAL(12,2)"),
+                column("customer", "STRUCT"),
+                column("customer.name", "STRING"),
+                column("items", "ARRAY<STRUCT>"),
+                column("items.[].sku", "STRING"),
+                column("items.[].quantity", "INT"),
+            ],
+        );
+        let schema = arrow_schema_from_table(&table);
+
+        assert_eq!(schema.fields().len(), 4);
+        assert!(!schema.field_with_name("id").unwrap().is_nullable());
+        assert_eq!(
+            schema.field_with_name("amount").unwrap().data_type(),
+            &DataType::Decimal128(12, 2)
+        );
+        let DataType::Struct(customer) = schema.field_with_name("customer").unwrap().data_type()
+        else {
+            panic!("customer should be a struct");
+        };
+        assert_eq!(customer[0].name(), "name");
+        let DataType::List(item) = schema.field_with_name("items").unwrap().data_type() else {
+            panic!("items should be a list");
+        };
+        let DataType::Struct(item_fields) = item.data_type() else {
+            panic!("items should hold structs");
+        };
+        assert_eq!(item_fields.len(), 2);
+    }
+
+    #[test]
+    fn test_compression_parse() {
+        assert_eq!(
+            "ZSTD".parse::<ParquetCompression>().unwrap(),
+            ParquetCompression::Zstd
+        );
+        assert_eq!(ParquetCompression::default().to_string(), "snappy");
+        assert!("lzma".parse::<ParquetCompression>().is_err());
+    }
+
+    #[test]
+    fn test_export_typed_parquet() {
+        let dir = TempDir::new().unwrap();
+        let db = staged_db(
+            dir.path(),
+            &[
+                r#"{"id": 1, "day": "2024-01-15", "customer": {"name": "Ada"}, "extra": {"a": 1}}"#,
+                r#"{"id": 2, "day": "2024-01-16", "customer": {"name": "Bob"}, "extra": [1, 2]}"#,
+                r#"{"id": "three", "day": "2024-01-17"}"#,
+            ],
+        );
+
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("day", DataType::Date32, true),
+            Field::new(
+                "customer",
+                DataType::Struct(Fields::from(vec![Field::new("name", DataType::Utf8, true)])),
+                true,
+            ),
+            Field::new("extra", DataType::Utf8, true),
+        ]);
+        let output = dir.path().join("export");
+        let config = ParquetExportConfig::new(&output)
+            .with_partition("orders")
+            .with_max_rows_per_file(1)
+            .with_compression(ParquetCompression::Zstd);
+        let result = ParquetExporter::new(schema, config).export(&db).unwrap();
+
+        assert_eq!(result.records_written, 2);
+        assert_eq!(result.records_skipped, 1);
+        assert_eq!(result.files.len(), 2);
+        assert!(result.bytes_written > 0);
+        assert!(result.files[0].starts_with(output.join("partition_key=orders")));
+
+        let rows = db
+            .query(&format!(
+                "SELECT id, day::VARCHAR AS day, customer.name AS name, extra FROM '{}/*.parquet' ORDER BY id",
+                output.join("partition_key=orders").display()
+            ))
+            .unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0]["day"], "2024-01-15");
+        assert_eq!(rows[1]["name"], "Bob");
+        assert_eq!(rows[0]["extra"], r#"{"a":1}"#);
+    }
+
+    #[test]
+    fn test_export_inferred_schema_outside_sample() {
+        let dir = TempDir::new().unwrap();
+        let db = staged_db(
+            dir.path(),
+            &[r#"{"id": 1, "name": "Ada"}"#, r#"{"name": "Bob"}"#],
+        );
+
+        // Inferred from the first record only, where `id` is always present
+        let mut inferrer = SchemaInferrer::new();
+        inferrer.add_json(r#"{"id": 1, "name": "Ada"}"#).unwrap();
+        let schema = inferrer.finalize().unwrap();
+
+        let config = ParquetExportConfig::new(dir.path().join("export")).with_partition("orders");
+        let result = ParquetExporter::from_inferred(&schema, config)
+            .export(&db)
+            .unwrap();
+        assert_eq!(result.records_written, 2);
+        assert_eq!(result.records_skipped, 0);
+    }
+}
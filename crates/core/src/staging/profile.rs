@@ -0,0 +1,627 @@
+//! Column-level data profiling of staged JSON
+//!
+//! Profiles every JSON path of the records in a staging partition using
+//! DuckDB's `json_tree` over `staged_json`:
+//!
+//! - Completeness (share of records with a non-null value) and null counts
+//! - JSON types seen and distinct value counts
+//! - The most frequent values
+//! - Min, max, mean and quantiles of numeric values
+//! - The distribution of string lengths
+//! - Match rates of common string formats (dates, emails, UUIDs, ...)
+//!
+//! Paths use the inference notation: `$.customer.name` for nested fields and
+//! `$.items[].sku` for fields of array elements. Profiles are stored in the
+//! staging database with a version per partition and can be rendered as JSON
+//! or Markdown.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use data_modelling_core::staging::{DataProfiler, ProfileConfig, StagingDb};
+//!
+//! let db = StagingDb::open("staging.duckdb")?;
+//! let mut profile = DataProfiler::new(ProfileConfig::default().with_partition("2024-01"))
+//!     .profile(&db)?;
+//! db.save_profile(&mut profile)?;
+//! println!("{}", profile.to_markdown());
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::debug;
+
+use super::db::StagingDb;
+use super::error::StagingError;
+
+/// Temporary table holding the flattened JSON nodes being profiled
+const NODES_TABLE: &str = "_profile_nodes";
+
+/// String formats whose match rates are measured, as RE2 patterns
+const FORMATS: &[(&str, &str)] = &[
+    ("date", r"\d{4}-\d{2}-\d{2}"),
+    (
+        "date-time",
+        r"\d{4}-\d{2}-\d{2}[T ]\d{2}:\d{2}:\d{2}(\.\d+)?(Z|[+-]\d{2}:?\d{2})?",
+    ),
+    ("time", r"\d{2}:\d{2}:\d{2}(\.\d+)?"),
+    ("email", r"[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}"),
+    (
+        "uuid",
+        r"[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}",
+    ),
+    ("uri", r"[a-zA-Z][a-zA-Z0-9+.-]*://\S+"),
+    ("ipv4", r"(\d{1,3}\.){3}\d{1,3}"),
+    ("integer", r"-?\d+"),
+    ("decimal", r"-?\d+\.\d+"),
+];
+
+/// Configuration for profiling staged records
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileConfig {
+    /// Staging partition to profile (all records when unset)
+    pub partition: Option<String>,
+    /// Number of most frequent values kept per path
+    pub top_k: usize,
+    /// Profile a random sample of this many records instead of all records
+    pub sample_size: Option<usize>,
+}
+
+impl Default for ProfileConfig {
+    fn default() -> Self {
+        Self {
+            partition: None,
+            top_k: 10,
+            sample_size: None,
+        }
+    }
+}
+
+impl ProfileConfig {
+    /// Profile a single staging partition
+    pub fn with_partition(mut self, partition: impl Into<String>) -> Self {
+        self.partition = Some(partition.into());
+        self
+    }
+
+    /// Set the number of most frequent values kept per path
+    pub fn with_top_k(mut self, top_k: usize) -> Self {
+        self.top_k = top_k;
+        self
+    }
+
+    /// Profile a random sample of records
+    pub fn with_sample_size(mut self, sample_size: usize) -> Self {
+        self.sample_size = Some(sample_size);
+        self
+    }
+}
+
+/// Profile of the staged records of a partition
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataProfile {
+    /// Unique profile ID
+    pub id: String,
+    /// Profiled partition (all records when unset)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partition: Option<String>,
+    /// Version within the partition, assigned when the profile is saved (0 = unsaved)
+    pub version: i32,
+    /// Number of records profiled
+    pub record_count: u64,
+    /// Whether a sample of the records was profiled
+    pub sampled: bool,
+    /// When the profile was computed
+    pub created_at: DateTime<Utc>,
+    /// Per-path profiles, ordered by path
+    pub fields: Vec<FieldProfile>,
+}
+
+/// Profile of a single JSON path
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldProfile {
+    /// JSON path (`$.items[].sku`)
+    pub path: String,
+    /// Number of values seen per JSON type (`string`, `integer`, `null`, ...)
+    pub types: BTreeMap<String, u64>,
+    /// Number of records with a non-null value at the path
+    pub present_count: u64,
+    /// Share of records with a non-null value at the path
+    pub completeness: f64,
+    /// Number of explicit null values
+    pub null_count: u64,
+    /// Number of distinct scalar values
+    pub distinct_count: u64,
+    /// Most frequent scalar values, most frequent first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub top_values: Vec<ValueCount>,
+    /// Distribution of numeric values
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub numeric: Option<Distribution>,
+    /// Distribution of string lengths
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub string_length: Option<Distribution>,
+    /// Share of string values matching each detected format
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub formats: BTreeMap<String, f64>,
+}
+
+/// A value and the number of times it occurs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValueCount {
+    /// Value as text
+    pub value: String,
+    /// Number of occurrences
+    pub count: u64,
+}
+
+/// Summary statistics of a set of numbers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Distribution {
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub p25: f64,
+    pub p50: f64,
+    pub p75: f64,
+    pub p95: f64,
+}
+
+impl DataProfile {
+    /// Profile of a path
+    pub fn field(&self, path: &str) -> Option<&FieldProfile> {
+        self.fields.iter().find(|f| f.path == path)
+    }
+
+    /// Serialize the profile as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String, StagingError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Render the profile as a Markdown report
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!(
+            "# Data Profile: {}\n\n",
+            self.partition.as_deref().unwrap_or("all partitions")
+        );
+        if self.version > 0 {
+            out.push_str(&format!("- Version: {}\n", self.version));
+        }
+        out.push_str(&format!(
+            "- Records: {}{}\n- Profiled at: {}\n\n",
+            self.record_count,
+            if self.sampled { " (sample)" } else { "" },
+            self.created_at.to_rfc3339()
+        ));
+
+        out.push_str("## Fields\n\n");
+        out.push_str("| Path | Types | Completeness | Nulls | Distinct | Top values | Formats |\n");
+        out.push_str("|------|-------|--------------|-------|----------|------------|---------|\n");
+        for field in &self.fields {
+            let types = field
+                .types
+                .iter()
+                .map(|(t, n)| format!("{} ({})", t, n))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let top_values = field
+                .top_values
+                .iter()
+                .take(5)
+                .map(|v| format!("`{}` ({})", truncate(&v.value, 30), v.count))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let formats = field
+                .formats
+                .iter()
+                .map(|(f, rate)| format!("{} {:.1}%", f, rate * 100.0))
+                .collect::<Vec<_>>()
+                .join(", ");
+            out.push_str(&format!(
+                "| `{}` | {} | {:.1}% | {} | {} | {} | {} |\n",
+                field.path,
+                types,
+                field.completeness * 100.0,
+                field.null_count,
+                field.distinct_count,
+                escape_cell(&top_values),
+                formats
+            ));
+        }
+
+        let numeric: Vec<_> = self
+            .fields
+            .iter()
+            .filter_map(|f| f.numeric.map(|d| (f.path.as_str(), d)))
+            .collect();
+        if !numeric.is_empty() {
+            out.push_str("\n## Numeric Values\n\n");
+            push_distribution_table(&mut out, &numeric);
+        }
+
+        let lengths: Vec<_> = self
+            .fields
+            .iter()
+            .filter_map(|f| f.string_length.map(|d| (f.path.as_str(), d)))
+            .collect();
+        if !lengths.is_empty() {
+            out.push_str("\n## String Lengths\n\n");
+            push_distribution_table(&mut out, &lengths);
+        }
+        out
+    }
+}
+
+fn push_distribution_table(out: &mut String, rows: &[(&str, Distribution)]) {
+    out.push_str("| Path | Min | P25 | Median | P75 | P95 | Max | Mean |\n");
+    out.push_str("|------|-----|-----|--------|-----|-----|-----|------|\n");
+    for (path, d) in rows {
+        out.push_str(&format!(
+            "| `{}` | {} | {} | {} | {} | {} | {} | {} |\n",
+            path,
+            format_number(d.min),
+            format_number(d.p25),
+            format_number(d.p50),
+            format_number(d.p75),
+            format_number(d.p95),
+            format_number(d.max),
+            format_number(d.mean)
+        ));
+    }
+}
+
+fn format_number(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{}", value as i64)
+    } else {
+        format!("{:.2}", value)
+    }
+}
+
+fn truncate(value: &str, max_chars: usize) -> String {
+    if value.chars().count() <= max_chars {
+        value.to_string()
+    } else {
+        format!("{}…", value.chars().take(max_chars).collect::<String>())
+    }
+}
+
+fn escape_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
+
+/// Computes data profiles of staged records
+pub struct DataProfiler {
+    config: ProfileConfig,
+}
+
+impl DataProfiler {
+    /// Create a profiler
+    pub fn new(config: ProfileConfig) -> Self {
+        Self { config }
+    }
+
+    /// Profile the staged records selected by the configuration
+    pub fn profile(&self, db: &StagingDb) -> Result<DataProfile, StagingError> {
+        if !db.is_initialized()? {
+            return Err(StagingError::NotInitialized);
+        }
+
+        db.execute_batch(&format!("DROP TABLE IF EXISTS {NODES_TABLE}"))?;
+        db.execute_batch(&self.nodes_sql())?;
+        let result = self.profile_nodes(db);
+        db.execute_batch(&format!("DROP TABLE IF EXISTS {NODES_TABLE}"))?;
+        result
+    }
+
+    /// Flatten the selected records into one row per JSON node
+    fn nodes_sql(&self) -> String {
+        let filter = self
+            .config
+            .partition
+            .as_ref()
+            .map(|p| format!(" WHERE partition_key = '{}'", p.replace('\'', "''")))
+            .unwrap_or_default();
+        let sample = self
+            .config
+            .sample_size
+            .map(|n| format!(" ORDER BY RANDOM() LIMIT {}", n))
+            .unwrap_or_default();
+
+        format!(
+            r#"CREATE TEMP TABLE {NODES_TABLE} AS
+WITH records AS (
+    SELECT id, raw_json FROM staged_json{filter}{sample}
+),
+nodes AS (
+    SELECT r.id AS record_id,
+           regexp_replace(t.fullkey, '\[[0-9]+\]', '[]', 'g') AS path,
+           CASE
+               WHEN t.type IN ('BIGINT', 'UBIGINT', 'INTEGER') THEN 'integer'
+               WHEN t.type IN ('DOUBLE', 'REAL') THEN 'number'
+               WHEN t.type IN ('VARCHAR', 'TEXT') THEN 'string'
+               WHEN t.type IN ('BOOLEAN', 'TRUE', 'FALSE') THEN 'boolean'
+               WHEN t.type = 'OBJECT' THEN 'object'
+               WHEN t.type = 'ARRAY' THEN 'array'
+               ELSE 'null'
+           END AS kind,
+           t.value AS node
+    FROM records r, json_tree(r.raw_json) t
+    WHERE t.fullkey <> '$'
+)
+SELECT record_id, path, kind,
+       CASE WHEN kind IN ('object', 'array', 'null') THEN NULL
+            ELSE json_extract_string(node, '$') END AS value
+FROM nodes"#
+        )
+    }
+
+    fn profile_nodes(&self, db: &StagingDb) -> Result<DataProfile, StagingError> {
+        let record_count = db.record_count(self.config.partition.as_deref())? as u64;
+        let record_count = self
+            .config
+            .sample_size
+            .map_or(record_count, |n| record_count.min(n as u64));
+
+        let mut fields: BTreeMap<String, FieldProfile> = BTreeMap::new();
+
+        // Presence, nulls and distinct values
+        for row in db.query(&format!(
+            "SELECT path,
+                    COUNT(DISTINCT record_id) FILTER (WHERE kind <> 'null') AS present,
+                    COUNT(*) FILTER (WHERE kind = 'null') AS nulls,
+                    COUNT(DISTINCT value) AS distinct_values
+             FROM {NODES_TABLE} GROUP BY path"
+        ))? {
+            let path = text(&row, "path");
+            let present = count(&row, "present");
+            fields.insert(
+                path.clone(),
+                FieldProfile {
+                    path,
+                    present_count: present,
+                    completeness: ratio(present, record_count),
+                    null_count: count(&row, "nulls"),
+                    distinct_count: count(&row, "distinct_values"),
+                    ..Default::default()
+                },
+            );
+        }
+
+        // JSON types
+        for row in db.query(&format!(
+            "SELECT path, kind, COUNT(*) AS n FROM {NODES_TABLE} GROUP BY path, kind"
+        ))? {
+            if let Some(field) = fields.get_mut(&text(&row, "path")) {
+                field.types.insert(text(&row, "kind"), count(&row, "n"));
+            }
+        }
+
+        // Most frequent values
+        if self.config.top_k > 0 {
+            for row in db.query(&format!(
+                "SELECT path, value, n FROM (
+                     SELECT path, value, COUNT(*) AS n,
+                            row_number() OVER (PARTITION BY path ORDER BY COUNT(*) DESC, value) AS rank
+                     FROM {NODES_TABLE} WHERE value IS NOT NULL GROUP BY path, value
+                 ) WHERE rank <= {} ORDER BY path, rank",
+                self.config.top_k
+            ))? {
+                if let Some(field) = fields.get_mut(&text(&row, "path")) {
+                    field.top_values.push(ValueCount {
+                        value: text(&row, "value"),
+                        count: count(&row, "n"),
+                    });
+                }
+            }
+        }
+
+        // Numeric values and string lengths
+        for (path, distribution) in
+            self.distributions(db, "CAST(value AS DOUBLE)", "'integer', 'number'")?
+        {
+            if let Some(field) = fields.get_mut(&path) {
+                field.numeric = Some(distribution);
+            }
+        }
+        for (path, distribution) in
+            self.distributions(db, "CAST(length(value) AS DOUBLE)", "'string'")?
+        {
+            if let Some(field) = fields.get_mut(&path) {
+                field.string_length = Some(distribution);
+            }
+        }
+
+        // String format match rates
+        let format_columns = FORMATS
+            .iter()
+            .enumerate()
+            .map(|(i, (_, pattern))| {
+                format!(
+                    "COUNT(*) FILTER (WHERE regexp_full_match(value, '{}')) AS f{}",
+                    pattern.replace('\'', "''"),
+                    i
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        for row in db.query(&format!(
+            "SELECT path, COUNT(*) AS strings, {format_columns}
+             FROM {NODES_TABLE} WHERE kind = 'string' GROUP BY path"
+        ))? {
+            let Some(field) = fields.get_mut(&text(&row, "path")) else {
+                continue;
+            };
+            let strings = count(&row, "strings");
+            for (i, (name, _)) in FORMATS.iter().enumerate() {
+                let matches = count(&row, &format!("f{}", i));
+                if matches > 0 {
+                    field
+                        .formats
+                        .insert(name.to_string(), ratio(matches, strings));
+                }
+            }
+        }
+
+        debug!(
+            paths = fields.len(),
+            records = record_count,
+            "Profiled staged records"
+        );
+        Ok(DataProfile {
+            id: uuid::Uuid::new_v4().to_string(),
+            partition: self.config.partition.clone(),
+            version: 0,
+            record_count,
+            sampled: self.config.sample_size.is_some(),
+            created_at: Utc::now(),
+            fields: fields.into_values().collect(),
+        })
+    }
+
+    /// Summary statistics of an expression over the nodes of the given kinds
+    fn distributions(
+        &self,
+        db: &StagingDb,
+        expression: &str,
+        kinds: &str,
+    ) -> Result<HashMap<String, Distribution>, StagingError> {
+        let rows = db.query(&format!(
+            "SELECT path, MIN(x) AS min, MAX(x) AS max, AVG(x) AS mean,
+                    quantile_cont(x, 0.25) AS p25, quantile_cont(x, 0.5) AS p50,
+                    quantile_cont(x, 0.75) AS p75, quantile_cont(x, 0.95) AS p95
+             FROM (SELECT path, {expression} AS x FROM {NODES_TABLE} WHERE kind IN ({kinds}))
+             GROUP BY path"
+        ))?;
+        Ok(rows
+            .iter()
+            .map(|row| {
+                (
+                    text(row, "path"),
+                    Distribution {
+                        min: number(row, "min"),
+                        max: number(row, "max"),
+                        mean: number(row, "mean"),
+                        p25: number(row, "p25"),
+                        p50: number(row, "p50"),
+                        p75: number(row, "p75"),
+                        p95: number(row, "p95"),
+                    },
+                )
+            })
+            .collect())
+    }
+}
+
+fn text(row: &Value, key: &str) -> String {
+    match row.get(key) {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Null) | None => String::new(),
+        Some(other) => other.to_string(),
+    }
+}
+
+fn count(row: &Value, key: &str) -> u64 {
+    row.get(key).and_then(Value::as_u64).unwrap_or(0)
+}
+
+fn number(row: &Value, key: &str) -> f64 {
+    row.get(key).and_then(Value::as_f64).unwrap_or(0.0)
+}
+
+fn ratio(part: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 / total as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::staging::{DedupStrategy, IngestConfig, SourceType};
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn staged_db(temp: &TempDir) -> StagingDb {
+        let records = [
+            r#"{"id": 1, "email": "a@example.com", "amount": 10.5, "items": [{"sku": "A1"}, {"sku": "B2"}]}"#,
+            r#"{"id": 2, "email": "b@example.com", "amount": 20, "items": [{"sku": "A1"}]}"#,
+            r#"{"id": 3, "email": null, "amount": 30, "items": []}"#,
+            r#"{"id": 4, "email": "not an email", "items": [{"sku": "C3"}]}"#,
+        ];
+        fs::write(temp.path().join("records.jsonl"), records.join("\n")).unwrap();
+
+        let db = StagingDb::memory().unwrap();
+        db.init().unwrap();
+        let config = IngestConfig::builder()
+            .source_type(SourceType::Local(temp.path().to_path_buf()))
+            .pattern("*.jsonl")
+            .partition("p1")
+            .dedup(DedupStrategy::None)
+            .build()
+            .unwrap();
+        db.ingest(&config).unwrap();
+        db
+    }
+
+    #[test]
+    fn test_profile_staged_records() {
+        let temp = TempDir::new().unwrap();
+        let db = staged_db(&temp);
+
+        let profile = DataProfiler::new(ProfileConfig::default().with_partition("p1"))
+            .profile(&db)
+            .unwrap();
+        assert_eq!(profile.record_count, 4);
+
+        let email = profile.field("$.email").unwrap();
+        assert_eq!(email.present_count, 3);
+        assert_eq!(email.completeness, 0.75);
+        assert_eq!(email.null_count, 1);
+        assert_eq!(email.types["string"], 3);
+        assert!((email.formats["email"] - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(email.string_length.unwrap().min, 12.0);
+
+        let amount = profile.field("$.amount").unwrap();
+        assert_eq!(amount.types["integer"], 2);
+        assert_eq!(amount.types["number"], 1);
+        let numeric = amount.numeric.unwrap();
+        assert_eq!((numeric.min, numeric.max, numeric.p50), (10.5, 30.0, 20.0));
+
+        let sku = profile.field("$.items[].sku").unwrap();
+        assert_eq!(sku.present_count, 3);
+        assert_eq!(sku.distinct_count, 3);
+        assert_eq!(sku.top_values[0].value, "A1");
+        assert_eq!(sku.top_values[0].count, 2);
+
+        let markdown = profile.to_markdown();
+        assert!(markdown.contains("| `$.items[].sku` |"));
+        assert!(markdown.contains("## Numeric Values"));
+    }
+
+    #[test]
+    fn test_profile_versions() {
+        let temp = TempDir::new().unwrap();
+        let db = staged_db(&temp);
+        let profiler = DataProfiler::new(ProfileConfig::default().with_partition("p1"));
+
+        let mut first = profiler.profile(&db).unwrap();
+        assert_eq!(db.save_profile(&mut first).unwrap(), 1);
+        let mut second = profiler.profile(&db).unwrap();
+        assert_eq!(db.save_profile(&mut second).unwrap(), 2);
+
+        let latest = db.get_profile(Some("p1"), None).unwrap().unwrap();
+        assert_eq!(latest, second);
+        let stored = db.get_profile(Some("p1"), Some(1)).unwrap().unwrap();
+        assert_eq!(stored.id, first.id);
+        assert!(db.get_profile(None, None).unwrap().is_none());
+        assert_eq!(db.list_profiles(Some("p1")).unwrap().len(), 2);
+    }
+}
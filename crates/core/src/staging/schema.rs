@@ -1,7 +1,11 @@
 //! Database schema definitions for staging tables
 
 /// Current schema version
-pub const SCHEMA_VERSION: i32 = 1;
+///
+/// Databases with an older version are upgraded when opened. Version 2 added
+/// the `llm_responses` table, version 3 the `data_profiles` table and
+/// version 4 the `dead_letters` table.
+pub const SCHEMA_VERSION: i32 = 4;
 
 /// Schema for staging database tables
 pub struct StagingSchema;
@@ -64,12 +68,48 @@ CREATE TABLE IF NOT EXISTS inferred_schemas (
     created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
 );
 
+-- Recorded LLM responses for deterministic replay
+CREATE TABLE IF NOT EXISTS llm_responses (
+    cache_key VARCHAR PRIMARY KEY,
+    model VARCHAR NOT NULL,
+    temperature REAL,
+    prompt_hash VARCHAR NOT NULL,
+    response VARCHAR NOT NULL,
+    recorded_at VARCHAR NOT NULL
+);
+
+-- Records rejected during ingestion
+CREATE TABLE IF NOT EXISTS dead_letters (
+    id VARCHAR PRIMARY KEY,
+    batch_id VARCHAR,
+    file_path VARCHAR NOT NULL,
+    line_number INTEGER,
+    partition_key VARCHAR,
+    raw_data BLOB NOT NULL,
+    error VARCHAR NOT NULL,
+    status VARCHAR NOT NULL,
+    created_at VARCHAR NOT NULL,
+    resolved_at VARCHAR
+);
+
+-- Versioned data profiles of staged partitions
+CREATE TABLE IF NOT EXISTS data_profiles (
+    id VARCHAR PRIMARY KEY,
+    partition_key VARCHAR,
+    version INTEGER NOT NULL,
+    record_count BIGINT,
+    profile_json JSON NOT NULL,
+    created_at VARCHAR NOT NULL
+);
+
 -- Create indexes for performance
 CREATE INDEX IF NOT EXISTS idx_staged_partition ON staged_json(partition_key);
 CREATE INDEX IF NOT EXISTS idx_staged_file ON staged_json(file_path);
 CREATE INDEX IF NOT EXISTS idx_staged_hash ON staged_json(content_hash);
 CREATE INDEX IF NOT EXISTS idx_batches_status ON processing_batches(status);
 CREATE INDEX IF NOT EXISTS idx_schemas_partition ON inferred_schemas(partition_key);
+CREATE INDEX IF NOT EXISTS idx_profiles_partition ON data_profiles(partition_key);
+CREATE INDEX IF NOT EXISTS idx_dead_letters_batch ON dead_letters(batch_id);
 
 -- Create sequence for staged_json IDs
 CREATE SEQUENCE IF NOT EXISTS staged_json_id_seq START 1;
@@ -133,6 +173,16 @@ CREATE TABLE IF NOT EXISTS inferred_schemas (
     created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
 );
 
+-- Recorded LLM responses for deterministic replay
+CREATE TABLE IF NOT EXISTS llm_responses (
+    cache_key VARCHAR PRIMARY KEY,
+    model VARCHAR NOT NULL,
+    temperature REAL,
+    prompt_hash VARCHAR NOT NULL,
+    response VARCHAR NOT NULL,
+    recorded_at VARCHAR NOT NULL
+);
+
 -- Create indexes for performance
 CREATE INDEX IF NOT EXISTS idx_staged_partition ON staged_json(partition_key);
 CREATE INDEX IF NOT EXISTS idx_staged_file ON staged_json(file_path);
@@ -169,6 +219,9 @@ mod tests {
         assert!(ddl.contains("CREATE TABLE IF NOT EXISTS staged_json"));
         assert!(ddl.contains("CREATE TABLE IF NOT EXISTS processing_batches"));
         assert!(ddl.contains("CREATE TABLE IF NOT EXISTS inferred_schemas"));
+        assert!(ddl.contains("CREATE TABLE IF NOT EXISTS llm_responses"));
+        assert!(ddl.contains("CREATE TABLE IF NOT EXISTS data_profiles"));
+        assert!(ddl.contains("CREATE TABLE IF NOT EXISTS dead_letters"));
         assert!(ddl.contains("CREATE INDEX IF NOT EXISTS idx_staged_partition"));
     }
 }
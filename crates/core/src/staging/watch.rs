@@ -0,0 +1,479 @@
+//! Watch mode for continuous incremental ingestion
+//!
+//! A [`Watcher`] polls a local source directory and ingests files that are new
+//! or changed since they were last ingested. Files are only ingested once their
+//! size and modification time are unchanged between two polls, so files that
+//! are still being written are picked up by a later poll. A changed file
+//! replaces the records previously staged from it; deleted files keep their
+//! records.
+//!
+//! Each poll ingests its files in one processing batch per partition, using
+//! the deduplication strategy of the ingest configuration. Setting the stop
+//! flag ends the watch between two files and leaves the running batch
+//! resumable.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use super::config::{IngestConfig, SourceType};
+use super::db::StagingDb;
+use super::error::IngestError;
+use super::ingest::{DiscoveredFile, IngestStats, discover_local_files};
+#[cfg(feature = "inference")]
+use crate::inference::{InferenceConfig, InferenceError, InferredSchema, SchemaInferrer};
+
+/// How often a sleeping watcher checks the stop flag
+const STOP_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How watched files are assigned to partitions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WatchPartition {
+    /// Partition of the ingest configuration
+    #[default]
+    Fixed,
+    /// First directory below the source directory; files directly in the
+    /// source directory use the partition of the ingest configuration
+    Directory,
+    /// UTC date the file was ingested (YYYY-MM-DD)
+    Date,
+}
+
+impl std::fmt::Display for WatchPartition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WatchPartition::Fixed => write!(f, "fixed"),
+            WatchPartition::Directory => write!(f, "directory"),
+            WatchPartition::Date => write!(f, "date"),
+        }
+    }
+}
+
+impl std::str::FromStr for WatchPartition {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "fixed" => Ok(WatchPartition::Fixed),
+            "directory" | "dir" => Ok(WatchPartition::Directory),
+            "date" => Ok(WatchPartition::Date),
+            _ => Err(format!(
+                "Invalid watch partitioning: {}. Expected: fixed, directory, date",
+                s
+            )),
+        }
+    }
+}
+
+/// Configuration for watch mode
+#[derive(Debug, Clone)]
+pub struct WatchConfig {
+    /// Ingest settings; the source must be a local directory
+    pub ingest: IngestConfig,
+    /// Time between two polls
+    pub poll_interval: Duration,
+    /// How files are assigned to partitions
+    pub partition_by: WatchPartition,
+    /// Wait until a file is unchanged between two polls before ingesting it
+    pub wait_for_stable: bool,
+}
+
+impl WatchConfig {
+    /// Create a watch configuration polling every 5 seconds
+    pub fn new(ingest: IngestConfig) -> Self {
+        Self {
+            ingest,
+            poll_interval: Duration::from_secs(5),
+            partition_by: WatchPartition::Fixed,
+            wait_for_stable: true,
+        }
+    }
+
+    /// Set the time between two polls
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Set how files are assigned to partitions
+    pub fn with_partition_by(mut self, partition_by: WatchPartition) -> Self {
+        self.partition_by = partition_by;
+        self
+    }
+
+    /// Set whether files must be unchanged between two polls before ingesting
+    pub fn with_wait_for_stable(mut self, wait_for_stable: bool) -> Self {
+        self.wait_for_stable = wait_for_stable;
+        self
+    }
+}
+
+/// Result of one poll
+#[derive(Debug, Clone, Default)]
+pub struct WatchCycle {
+    /// Files seen for the first time
+    pub files_new: usize,
+    /// Files whose size or modification time changed since they were ingested
+    pub files_changed: usize,
+    /// Ingestion statistics, one entry per partition
+    pub batches: Vec<IngestStats>,
+    /// Whether new records were folded into the inferred schema
+    pub schema_updated: bool,
+}
+
+impl WatchCycle {
+    /// Total number of records ingested by this poll
+    pub fn records_ingested(&self) -> usize {
+        self.batches.iter().map(|b| b.records_ingested).sum()
+    }
+
+    /// Whether the poll was stopped before all files were ingested
+    pub fn interrupted(&self) -> bool {
+        self.batches.iter().any(|b| b.interrupted)
+    }
+
+    /// Whether the poll found files to ingest
+    pub fn is_empty(&self) -> bool {
+        self.files_new == 0 && self.files_changed == 0
+    }
+}
+
+/// Size and modification time of a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileState {
+    size: u64,
+    modified: Option<SystemTime>,
+}
+
+/// A file ingested by the watcher
+#[derive(Debug, Clone)]
+struct IngestedFile {
+    state: FileState,
+    partition: Option<String>,
+}
+
+/// Polls a local directory and ingests new and changed files
+pub struct Watcher {
+    config: WatchConfig,
+    /// Files ingested so far, with their state at ingestion time
+    ingested: HashMap<PathBuf, IngestedFile>,
+    /// State seen by the previous poll for files waiting to become stable
+    pending: HashMap<PathBuf, FileState>,
+    #[cfg(feature = "inference")]
+    inferrer: Option<SchemaInferrer>,
+    #[cfg(feature = "inference")]
+    last_record_id: i64,
+}
+
+impl Watcher {
+    /// Create a watcher
+    pub fn new(config: WatchConfig) -> Self {
+        Self {
+            config,
+            ingested: HashMap::new(),
+            pending: HashMap::new(),
+            #[cfg(feature = "inference")]
+            inferrer: None,
+            #[cfg(feature = "inference")]
+            last_record_id: 0,
+        }
+    }
+
+    /// Fold staged records into an inferred schema after every poll
+    ///
+    /// The first poll folds the records already staged in the watched
+    /// partition; later polls fold the records staged since.
+    #[cfg(feature = "inference")]
+    pub fn with_inference(mut self, mut config: InferenceConfig) -> Self {
+        // The inferrer accumulates every folded record
+        config.sample_size = 0;
+        self.inferrer = Some(SchemaInferrer::with_config(config));
+        self
+    }
+
+    /// The watch configuration
+    pub fn config(&self) -> &WatchConfig {
+        &self.config
+    }
+
+    /// Snapshot of the inferred schema, if inference is enabled
+    #[cfg(feature = "inference")]
+    pub fn inferred_schema(&self) -> Result<Option<InferredSchema>, InferenceError> {
+        self.inferrer
+            .as_ref()
+            .filter(|inferrer| inferrer.record_count() > 0)
+            .map(SchemaInferrer::snapshot)
+            .transpose()
+    }
+
+    /// Poll until `stop` is set
+    ///
+    /// `on_cycle` is called after every poll that ingested files or updated
+    /// the inferred schema.
+    pub fn run(
+        &mut self,
+        db: &StagingDb,
+        stop: &AtomicBool,
+        mut on_cycle: impl FnMut(&Watcher, &WatchCycle),
+    ) -> Result<(), IngestError> {
+        while !stop.load(Ordering::Relaxed) {
+            let cycle = self.poll(db, Some(stop))?;
+            if !cycle.is_empty() || cycle.schema_updated {
+                on_cycle(self, &cycle);
+            }
+            if cycle.interrupted() {
+                break;
+            }
+
+            // Sleep in short steps so that a stop request is handled promptly
+            let deadline = Instant::now() + self.config.poll_interval;
+            while !stop.load(Ordering::Relaxed) {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    break;
+                }
+                std::thread::sleep(remaining.min(STOP_CHECK_INTERVAL));
+            }
+        }
+        Ok(())
+    }
+
+    /// Poll the source directory once and ingest new and changed files
+    pub fn poll(
+        &mut self,
+        db: &StagingDb,
+        stop: Option<&AtomicBool>,
+    ) -> Result<WatchCycle, IngestError> {
+        let root = match &self.config.ingest.source {
+            SourceType::Local(path) => path.clone(),
+            #[cfg(any(feature = "s3", feature = "databricks"))]
+            other => {
+                return Err(IngestError::SourceNotAccessible {
+                    path: other.display(),
+                    reason: "Watch mode requires a local source directory".to_string(),
+                });
+            }
+        };
+
+        let mut cycle = WatchCycle::default();
+        let mut ready: BTreeMap<Option<String>, Vec<(DiscoveredFile, FileState)>> = BTreeMap::new();
+        let mut seen = HashSet::new();
+
+        for file in discover_local_files(&root, &self.config.ingest.pattern)? {
+            // The file may have been removed since it was discovered
+            let Ok(metadata) = std::fs::metadata(&file.path) else {
+                continue;
+            };
+            let state = FileState {
+                size: metadata.len(),
+                modified: metadata.modified().ok(),
+            };
+            seen.insert(file.path.clone());
+
+            let previous = self.ingested.get(&file.path);
+            if previous.is_some_and(|p| p.state == state) {
+                continue;
+            }
+            if self.config.wait_for_stable && self.pending.get(&file.path) != Some(&state) {
+                self.pending.insert(file.path.clone(), state);
+                continue;
+            }
+            self.pending.remove(&file.path);
+
+            // A changed file replaces the records staged from it
+            if let Some(previous) = previous {
+                db.remove_file_records(
+                    &file.path.display().to_string(),
+                    previous.partition.as_deref(),
+                )?;
+                cycle.files_changed += 1;
+            } else {
+                cycle.files_new += 1;
+            }
+
+            let partition = self.partition_for(&root, &file.path);
+            ready
+                .entry(partition)
+                .or_default()
+                .push((DiscoveredFile::new(file.path, state.size), state));
+        }
+
+        // Forget files that were deleted
+        self.ingested.retain(|path, _| seen.contains(path));
+        self.pending.retain(|path, _| seen.contains(path));
+
+        for (partition, files) in ready {
+            if stop.is_some_and(|stop| stop.load(Ordering::Relaxed)) {
+                break;
+            }
+
+            let mut config = self.config.ingest.clone();
+            config.partition = partition.clone();
+            config.batch_id = None;
+            config.resume = false;
+
+            let (files, states): (Vec<_>, Vec<_>) = files.into_iter().unzip();
+            let paths: Vec<PathBuf> = files.iter().map(|f| f.path.clone()).collect();
+            let stats = db.ingest_files(&config, files, stop)?;
+
+            // Files of an interrupted batch are looked at again by the next poll
+            if !stats.interrupted {
+                for (path, state) in paths.into_iter().zip(states) {
+                    self.ingested.insert(
+                        path,
+                        IngestedFile {
+                            state,
+                            partition: partition.clone(),
+                        },
+                    );
+                }
+            }
+
+            let interrupted = stats.interrupted;
+            cycle.batches.push(stats);
+            if interrupted {
+                break;
+            }
+        }
+
+        #[cfg(feature = "inference")]
+        {
+            cycle.schema_updated = self.fold_new_records(db)?;
+        }
+
+        Ok(cycle)
+    }
+
+    /// Partition a watched file is ingested into
+    fn partition_for(&self, root: &Path, path: &Path) -> Option<String> {
+        match self.config.partition_by {
+            WatchPartition::Fixed => self.config.ingest.partition.clone(),
+            WatchPartition::Directory => path
+                .strip_prefix(root)
+                .ok()
+                .and_then(Path::parent)
+                .and_then(|dir| dir.components().next())
+                .map(|dir| dir.as_os_str().to_string_lossy().into_owned())
+                .or_else(|| self.config.ingest.partition.clone()),
+            WatchPartition::Date => Some(chrono::Utc::now().format("%Y-%m-%d").to_string()),
+        }
+    }
+
+    /// Fold records staged since the last fold into the inferrer
+    #[cfg(feature = "inference")]
+    fn fold_new_records(&mut self, db: &StagingDb) -> Result<bool, IngestError> {
+        let Some(inferrer) = self.inferrer.as_mut() else {
+            return Ok(false);
+        };
+
+        // Only a fixed partition can be filtered on; other modes fold everything
+        let partition = match self.config.partition_by {
+            WatchPartition::Fixed => self.config.ingest.partition.as_deref(),
+            WatchPartition::Directory | WatchPartition::Date => None,
+        };
+        let records = db.get_records_after(self.last_record_id, 0, partition)?;
+        for (id, raw_json) in &records {
+            if let Ok(value) = serde_json::from_str::<serde_json::Value>(raw_json) {
+                let _ = inferrer.add_value(&value);
+            }
+            self.last_record_id = *id;
+        }
+
+        Ok(!records.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::staging::config::DedupStrategy;
+    use tempfile::TempDir;
+
+    fn ingest_config(dir: &TempDir) -> IngestConfig {
+        IngestConfig::builder()
+            .source_type(SourceType::Local(dir.path().to_path_buf()))
+            .pattern("**/*.jsonl")
+            .partition("landing")
+            .dedup(DedupStrategy::ByPath)
+            .build()
+            .unwrap()
+    }
+
+    fn watcher(dir: &TempDir, partition_by: WatchPartition) -> Watcher {
+        Watcher::new(WatchConfig::new(ingest_config(dir)).with_partition_by(partition_by))
+    }
+
+    #[test]
+    fn test_watcher_ingests_new_and_changed_files() {
+        let dir = TempDir::new().unwrap();
+        let db = StagingDb::memory().unwrap();
+        db.init().unwrap();
+        let mut watcher = watcher(&dir, WatchPartition::Fixed);
+
+        let path = dir.path().join("events.jsonl");
+        std::fs::write(&path, "{\"id\": 1}\n").unwrap();
+
+        // A new file is only ingested once it is unchanged between two polls
+        assert!(watcher.poll(&db, None).unwrap().is_empty());
+        let cycle = watcher.poll(&db, None).unwrap();
+        assert_eq!(cycle.files_new, 1);
+        assert_eq!(cycle.records_ingested(), 1);
+        assert!(watcher.poll(&db, None).unwrap().is_empty());
+
+        // Appending replaces the records staged from the file
+        std::fs::write(&path, "{\"id\": 1}\n{\"id\": 2}\n").unwrap();
+        watcher.poll(&db, None).unwrap();
+        let cycle = watcher.poll(&db, None).unwrap();
+        assert_eq!(cycle.files_changed, 1);
+        assert_eq!(db.record_count(Some("landing")).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_watcher_partitions_by_directory() {
+        let dir = TempDir::new().unwrap();
+        let db = StagingDb::memory().unwrap();
+        db.init().unwrap();
+        let mut watcher = watcher(&dir, WatchPartition::Directory);
+
+        std::fs::create_dir(dir.path().join("orders")).unwrap();
+        std::fs::write(dir.path().join("orders/a.jsonl"), "{\"id\": 1}\n").unwrap();
+        std::fs::write(dir.path().join("root.jsonl"), "{\"id\": 2}\n").unwrap();
+        watcher.poll(&db, None).unwrap();
+        let cycle = watcher.poll(&db, None).unwrap();
+
+        assert_eq!(cycle.batches.len(), 2);
+        assert_eq!(db.record_count(Some("orders")).unwrap(), 1);
+        assert_eq!(db.record_count(Some("landing")).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_watcher_stop_leaves_batch_resumable() {
+        let dir = TempDir::new().unwrap();
+        let db = StagingDb::memory().unwrap();
+        db.init().unwrap();
+        let mut watcher =
+            Watcher::new(WatchConfig::new(ingest_config(&dir)).with_wait_for_stable(false));
+
+        std::fs::write(dir.path().join("a.jsonl"), "{\"id\": 1}\n").unwrap();
+        let stop = AtomicBool::new(true);
+        let cycle = watcher.poll(&db, Some(&stop)).unwrap();
+        assert!(cycle.batches.is_empty());
+
+        // Stopping inside a batch keeps it running
+        let files = vec![DiscoveredFile::new(dir.path().join("a.jsonl"), 10)];
+        let stats = db
+            .ingest_files(&watcher.config().ingest, files, Some(&stop))
+            .unwrap();
+        assert!(stats.interrupted);
+        let batch = db.get_batch(stats.batch_id.as_deref().unwrap()).unwrap();
+        assert!(batch.unwrap().can_resume());
+
+        stop.store(false, Ordering::Relaxed);
+        let cycle = watcher.poll(&db, Some(&stop)).unwrap();
+        assert_eq!(cycle.records_ingested(), 1);
+    }
+}
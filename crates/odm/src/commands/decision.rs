@@ -2,11 +2,11 @@
 //!
 //! Provides CLI commands for managing architecture decision records (ADRs).
 //!
-//! Note: These commands are defined but not yet wired into the main CLI.
-//! They will be enabled in a future release.
+//! Decisions follow the MADR lifecycle: `new` creates a draft, `propose` and
+//! `accept` move it forward, and `supersede` replaces an accepted decision
+//! while linking both records to each other.
 
 #![allow(clippy::collapsible_if)]
-#![allow(dead_code)]
 
 use crate::error::CliError;
 use chrono::Utc;
@@ -84,6 +84,33 @@ pub struct DecisionExportArgs {
     pub generate_index: bool,
 }
 
+/// Arguments for the `decision propose` and `decision accept` commands
+#[derive(Debug)]
+pub struct DecisionTransitionArgs {
+    /// Decision number
+    pub number: String,
+    /// Workspace path
+    pub workspace: PathBuf,
+}
+
+/// Arguments for the `decision supersede` command
+#[derive(Debug)]
+pub struct DecisionSupersedeArgs {
+    /// Number of the decision being replaced
+    pub number: String,
+    /// Number of the decision replacing it
+    pub by: String,
+    /// Workspace path
+    pub workspace: PathBuf,
+}
+
+/// Arguments for the `decision index` command
+#[derive(Debug)]
+pub struct DecisionIndexArgs {
+    /// Workspace path
+    pub workspace: PathBuf,
+}
+
 /// Handle `decision new` command
 pub fn handle_decision_new(args: &DecisionNewArgs) -> Result<(), CliError> {
     // Parse category
@@ -114,6 +141,9 @@ pub fn handle_decision_new(args: &DecisionNewArgs) -> Result<(), CliError> {
         "[Describe the decision that was made]",
     );
 
+    // New decisions start as drafts until proposed
+    decision.status = DecisionStatus::Draft;
+
     // Apply category and domain
     decision.category = category;
     if let Some(ref domain) = args.domain {
@@ -328,20 +358,7 @@ pub fn handle_decision_status(args: &DecisionStatusArgs) -> Result<(), CliError>
     let old_status = decision.status.clone();
     decision.status = new_status.clone();
     decision.updated_at = Utc::now();
-
-    // Save the updated decision
-    let exporter = DecisionExporter;
-    let yaml = exporter
-        .export(&decision)
-        .map_err(|e| CliError::IoError(format!("Failed to export: {}", e)))?;
-    fs::write(&file_path, &yaml)
-        .map_err(|e| CliError::IoError(format!("Failed to write file: {}", e)))?;
-
-    // Update index if it exists
-    let index_path = args.workspace.join("decisions.yaml");
-    if index_path.exists() {
-        update_decision_in_index(&index_path, &decision)?;
-    }
+    save_decision(&args.workspace, &file_path, &decision)?;
 
     println!(
         "Updated {} status: {} -> {}",
@@ -353,6 +370,96 @@ pub fn handle_decision_status(args: &DecisionStatusArgs) -> Result<(), CliError>
     Ok(())
 }
 
+/// Handle `decision propose` command
+pub fn handle_decision_propose(args: &DecisionTransitionArgs) -> Result<(), CliError> {
+    transition_decision(args, &[DecisionStatus::Draft], DecisionStatus::Proposed)
+}
+
+/// Handle `decision accept` command
+pub fn handle_decision_accept(args: &DecisionTransitionArgs) -> Result<(), CliError> {
+    transition_decision(args, &[DecisionStatus::Proposed], DecisionStatus::Accepted)
+}
+
+/// Handle `decision supersede` command
+///
+/// Marks the old decision as superseded and links both records: the old one
+/// gets `superseded_by`, the replacement gets `supersedes`.
+pub fn handle_decision_supersede(args: &DecisionSupersedeArgs) -> Result<(), CliError> {
+    let old_number = parse_decision_number(&args.number)?;
+    let new_number = parse_decision_number(&args.by)?;
+    if old_number == new_number {
+        return Err(CliError::InvalidArgument(
+            "A decision cannot supersede itself".to_string(),
+        ));
+    }
+
+    let (old_path, mut old) = find_decision_file_and_load(&args.workspace, old_number)?;
+    let (new_path, new) = find_decision_file_and_load(&args.workspace, new_number)?;
+
+    if old.status != DecisionStatus::Accepted {
+        return Err(CliError::InvalidArgument(format!(
+            "Only accepted decisions can be superseded; {} is {}",
+            old.formatted_number(),
+            old.status
+        )));
+    }
+    if let Some(existing) = new.supersedes.filter(|id| *id != old.id) {
+        return Err(CliError::InvalidArgument(format!(
+            "{} already supersedes decision {}",
+            new.formatted_number(),
+            existing
+        )));
+    }
+
+    old.superseded_by_decision(new.id);
+    let new = new.supersedes_decision(old.id);
+
+    save_decision(&args.workspace, &old_path, &old)?;
+    save_decision(&args.workspace, &new_path, &new)?;
+
+    println!(
+        "{} superseded by {}",
+        old.formatted_number(),
+        new.formatted_number()
+    );
+
+    Ok(())
+}
+
+/// Handle `decision index` command
+///
+/// Rebuilds `decisions.yaml` from the decision files in the workspace, keeping
+/// the numbering scheme of the existing index.
+pub fn handle_decision_index(args: &DecisionIndexArgs) -> Result<(), CliError> {
+    let index_path = args.workspace.join("decisions.yaml");
+    let existing =
+        if index_path.exists() {
+            let content = fs::read_to_string(&index_path)
+                .map_err(|e| CliError::IoError(format!("Failed to read decisions.yaml: {}", e)))?;
+            Some(DecisionImporter.import_index(&content).map_err(|e| {
+                CliError::ParseError(format!("Failed to parse decisions.yaml: {}", e))
+            })?)
+        } else {
+            None
+        };
+
+    let index = build_decision_index(&args.workspace, existing.as_ref())?;
+
+    let content = DecisionExporter
+        .export_index(&index)
+        .map_err(|e| CliError::IoError(format!("Failed to export index: {}", e)))?;
+    fs::write(&index_path, &content)
+        .map_err(|e| CliError::IoError(format!("Failed to write decisions.yaml: {}", e)))?;
+
+    println!(
+        "Indexed {} decision(s) in {}",
+        index.decisions.len(),
+        index_path.display()
+    );
+
+    Ok(())
+}
+
 /// Handle `decision export` command
 pub fn handle_decision_export(args: &DecisionExportArgs) -> Result<(), CliError> {
     let output_dir = args
@@ -444,13 +551,14 @@ fn parse_category(s: &str) -> Result<DecisionCategory, CliError> {
 
 fn parse_status(s: &str) -> Result<DecisionStatus, CliError> {
     match s.to_lowercase().as_str() {
+        "draft" => Ok(DecisionStatus::Draft),
         "proposed" => Ok(DecisionStatus::Proposed),
         "accepted" => Ok(DecisionStatus::Accepted),
         "rejected" => Ok(DecisionStatus::Rejected),
         "deprecated" => Ok(DecisionStatus::Deprecated),
         "superseded" => Ok(DecisionStatus::Superseded),
         _ => Err(CliError::InvalidArgument(format!(
-            "Unknown status: {}. Valid statuses: proposed, accepted, rejected, deprecated, superseded",
+            "Unknown status: {}. Valid statuses: draft, proposed, accepted, rejected, deprecated, superseded",
             s
         ))),
     }
@@ -470,6 +578,14 @@ fn parse_decision_number(s: &str) -> Result<u64, CliError> {
 }
 
 fn load_all_decisions(workspace: &Path) -> Result<Vec<Decision>, CliError> {
+    Ok(load_decision_files(workspace)?
+        .into_iter()
+        .map(|(_, decision)| decision)
+        .collect())
+}
+
+/// Load every decision file in the workspace along with its path, sorted by number
+fn load_decision_files(workspace: &Path) -> Result<Vec<(PathBuf, Decision)>, CliError> {
     let importer = DecisionImporter;
     let mut decisions = Vec::new();
 
@@ -487,7 +603,7 @@ fn load_all_decisions(workspace: &Path) -> Result<Vec<Decision>, CliError> {
                     })?;
 
                     match importer.import(&content) {
-                        Ok(decision) => decisions.push(decision),
+                        Ok(decision) => decisions.push((path, decision)),
                         Err(e) => {
                             eprintln!("Warning: Failed to parse {}: {}", path.display(), e);
                         }
@@ -498,7 +614,7 @@ fn load_all_decisions(workspace: &Path) -> Result<Vec<Decision>, CliError> {
     }
 
     // Sort by number
-    decisions.sort_by_key(|d| d.number);
+    decisions.sort_by_key(|(_, d)| d.number);
     Ok(decisions)
 }
 
@@ -579,6 +695,85 @@ fn update_decision_in_index(index_path: &Path, decision: &Decision) -> Result<()
     Ok(())
 }
 
+/// Move a decision to `to` if its current status is one of `from`
+fn transition_decision(
+    args: &DecisionTransitionArgs,
+    from: &[DecisionStatus],
+    to: DecisionStatus,
+) -> Result<(), CliError> {
+    let number = parse_decision_number(&args.number)?;
+    let (file_path, mut decision) = find_decision_file_and_load(&args.workspace, number)?;
+
+    if !from.contains(&decision.status) {
+        return Err(CliError::InvalidArgument(format!(
+            "Cannot move {} from {} to {}",
+            decision.formatted_number(),
+            decision.status,
+            to
+        )));
+    }
+
+    let old_status = std::mem::replace(&mut decision.status, to);
+    decision.updated_at = Utc::now();
+    save_decision(&args.workspace, &file_path, &decision)?;
+
+    println!(
+        "Updated {} status: {} -> {}",
+        decision.formatted_number(),
+        old_status,
+        decision.status
+    );
+
+    Ok(())
+}
+
+/// Write a decision back to its file and refresh its index entry
+fn save_decision(workspace: &Path, file_path: &Path, decision: &Decision) -> Result<(), CliError> {
+    let exporter = DecisionExporter;
+    let yaml = exporter
+        .export(decision)
+        .map_err(|e| CliError::IoError(format!("Failed to export: {}", e)))?;
+    fs::write(file_path, &yaml)
+        .map_err(|e| CliError::IoError(format!("Failed to write file: {}", e)))?;
+
+    // Update index if it exists
+    let index_path = workspace.join("decisions.yaml");
+    if index_path.exists() {
+        update_decision_in_index(&index_path, decision)?;
+    }
+
+    Ok(())
+}
+
+/// Build a decision index from the decision files in the workspace
+fn build_decision_index(
+    workspace: &Path,
+    existing: Option<&DecisionIndex>,
+) -> Result<DecisionIndex, CliError> {
+    let mut index = match existing {
+        Some(existing) if existing.use_timestamp_numbering => {
+            DecisionIndex::new_with_timestamp_numbering()
+        }
+        _ => DecisionIndex::new(),
+    };
+
+    for (path, decision) in load_decision_files(workspace)? {
+        let filename = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default()
+            .to_string();
+        index.add_decision(&decision, filename);
+    }
+
+    // Never hand out a number that was used by a since-deleted decision
+    if let Some(existing) = existing {
+        index.next_number = index.next_number.max(existing.next_number);
+    }
+
+    Ok(index)
+}
+
 fn escape_csv(s: &str) -> String {
     if s.contains(',') || s.contains('"') || s.contains('\n') {
         format!("\"{}\"", s.replace('"', "\"\""))
@@ -621,4 +816,91 @@ mod tests {
         assert_eq!(escape_csv("has,comma"), "\"has,comma\"");
         assert_eq!(escape_csv("has\"quote"), "\"has\"\"quote\"");
     }
+
+    fn temp_workspace() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("odm-decisions-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn new_decision(workspace: &Path, title: &str) {
+        handle_decision_new(&DecisionNewArgs {
+            title: title.to_string(),
+            category: "architecture".to_string(),
+            domain: None,
+            workspace: workspace.to_path_buf(),
+            export_markdown: false,
+        })
+        .unwrap();
+    }
+
+    fn transition(workspace: &Path, number: &str) -> DecisionTransitionArgs {
+        DecisionTransitionArgs {
+            number: number.to_string(),
+            workspace: workspace.to_path_buf(),
+        }
+    }
+
+    #[test]
+    fn test_decision_lifecycle_and_supersede() {
+        let workspace = temp_workspace();
+        new_decision(&workspace, "Use Postgres");
+        new_decision(&workspace, "Use DuckDB");
+
+        assert_eq!(
+            find_decision_by_number(&workspace, 1).unwrap().status,
+            DecisionStatus::Draft
+        );
+        // Drafts must be proposed before they can be accepted
+        assert!(handle_decision_accept(&transition(&workspace, "1")).is_err());
+        handle_decision_propose(&transition(&workspace, "1")).unwrap();
+        handle_decision_accept(&transition(&workspace, "ADR-0001")).unwrap();
+
+        let args = DecisionSupersedeArgs {
+            number: "1".to_string(),
+            by: "2".to_string(),
+            workspace: workspace.clone(),
+        };
+        handle_decision_supersede(&args).unwrap();
+
+        let old = find_decision_by_number(&workspace, 1).unwrap();
+        let new = find_decision_by_number(&workspace, 2).unwrap();
+        assert_eq!(old.status, DecisionStatus::Superseded);
+        assert_eq!(old.superseded_by, Some(new.id));
+        assert_eq!(new.supersedes, Some(old.id));
+
+        let content = fs::read_to_string(workspace.join("decisions.yaml")).unwrap();
+        let index = DecisionImporter.import_index(&content).unwrap();
+        assert_eq!(
+            index.find_by_number(1).unwrap().status,
+            DecisionStatus::Superseded
+        );
+
+        fs::remove_dir_all(&workspace).unwrap();
+    }
+
+    #[test]
+    fn test_decision_index_regeneration() {
+        let workspace = temp_workspace();
+        new_decision(&workspace, "First");
+        new_decision(&workspace, "Second");
+        new_decision(&workspace, "Third");
+
+        let index_path = workspace.join("decisions.yaml");
+        fs::remove_file(workspace.join("adr-0003.madr.yaml")).unwrap();
+        fs::write(&index_path, "schema_version: '1.0'\nnext_number: 4\n").unwrap();
+
+        handle_decision_index(&DecisionIndexArgs {
+            workspace: workspace.clone(),
+        })
+        .unwrap();
+
+        let content = fs::read_to_string(&index_path).unwrap();
+        let index = DecisionImporter.import_index(&content).unwrap();
+        assert_eq!(index.decisions.len(), 2);
+        assert_eq!(index.decisions[1].file, "adr-0002.madr.yaml");
+        assert_eq!(index.next_number, 4);
+
+        fs::remove_dir_all(&workspace).unwrap();
+    }
 }
@@ -0,0 +1,351 @@
+//! CLI commands for LLM-generated table documentation
+
+use std::path::{Path, PathBuf};
+
+use crate::error::CliError;
+use data_modelling_core::llm::{
+    CacheMode, DocumentationPatch, LlmCacheConfig, LlmMode, RefinementConfig, RetrievalConfig,
+    TableDocumenter, create_client,
+};
+use data_modelling_core::models::Table;
+use data_modelling_core::models::odcs::{ODCSContract, SchemaObject};
+
+use super::knowledge::load_all_articles;
+use super::pii::{find_odcs_files, parse_contract, write_contract};
+
+/// Arguments for the `docs suggest` command
+pub struct DocsSuggestArgs {
+    /// Workspace directory or ODCS file to document
+    pub path: PathBuf,
+    /// Directory containing knowledge base articles (`*.kb.yaml`)
+    pub knowledge: Option<PathBuf>,
+    /// Output file for the patch (stdout if not provided)
+    pub output: Option<PathBuf>,
+    /// Output format (yaml, markdown)
+    pub format: String,
+    /// Skip business name suggestions
+    pub no_business_names: bool,
+    /// LLM mode (online, offline, openai)
+    pub llm_mode: String,
+    /// Ollama URL for online mode
+    pub ollama_url: String,
+    /// Base URL of an OpenAI-compatible server for openai mode
+    pub openai_url: String,
+    /// Environment variable holding the API key for openai mode
+    pub api_key_env: Option<String>,
+    /// Model name
+    pub model: String,
+    /// Path to GGUF model for offline mode
+    pub model_path: Option<PathBuf>,
+    /// Path to documentation file
+    pub doc_path: Option<PathBuf>,
+    /// Embedding model for ranking documentation chunks
+    pub embedding_model: Option<String>,
+    /// Temperature for generation
+    pub temperature: f32,
+    /// LLM response cache directory or `.duckdb` staging database
+    pub llm_cache: Option<PathBuf>,
+    /// LLM response cache mode (readwrite, record, replay)
+    pub llm_cache_mode: String,
+}
+
+/// Arguments for the `docs apply` command
+pub struct DocsApplyArgs {
+    /// Documentation patch file (YAML)
+    pub patch: PathBuf,
+    /// Workspace directory or ODCS file to update
+    pub path: PathBuf,
+    /// Show what would change without writing files
+    pub dry_run: bool,
+}
+
+/// Handle the `docs suggest` command
+pub fn handle_docs_suggest(args: &DocsSuggestArgs) -> Result<(), CliError> {
+    let contracts = load_contracts(&args.path)?;
+    if contracts.is_empty() {
+        return Err(CliError::NotFound(format!(
+            "No .odcs.yaml files found in {}",
+            args.path.display()
+        )));
+    }
+
+    let articles = match args.knowledge {
+        Some(ref dir) => load_all_articles(dir)?,
+        None => Vec::new(),
+    };
+
+    let config = refinement_config(args)?;
+    let client = create_client(&config).map_err(|e| CliError::LlmError(e.to_string()))?;
+    let documenter = TableDocumenter::new(client, config)
+        .with_knowledge(articles)
+        .with_business_names(!args.no_business_names);
+
+    eprintln!(
+        "Documenting {} tables with {}...",
+        contracts.iter().map(|c| c.tables.len()).sum::<usize>(),
+        args.model
+    );
+
+    let rt = tokio::runtime::Runtime::new()
+        .map_err(|e| CliError::LlmError(format!("Failed to create runtime: {}", e)))?;
+    let tables: Vec<Table> = contracts
+        .into_iter()
+        .flat_map(|contract| contract.tables)
+        .collect();
+    let patch = rt.block_on(documenter.document_tables(&tables));
+
+    eprintln!(
+        "  {} suggestions, {} warnings",
+        patch.suggestions.len(),
+        patch.warnings.len()
+    );
+
+    let output = match args.format.as_str() {
+        "yaml" => patch.to_yaml().map_err(CliError::SerializationError)?,
+        "markdown" | "md" => patch.to_markdown(),
+        other => {
+            return Err(CliError::InvalidArgument(format!(
+                "Unknown format: {}. Use yaml or markdown",
+                other
+            )));
+        }
+    };
+
+    if let Some(ref output_path) = args.output {
+        std::fs::write(output_path, &output)
+            .map_err(|e| CliError::FileWriteError(output_path.clone(), e.to_string()))?;
+        eprintln!("Patch written to: {}", output_path.display());
+    } else {
+        println!("{}", output);
+    }
+
+    Ok(())
+}
+
+/// Handle the `docs apply` command
+pub fn handle_docs_apply(args: &DocsApplyArgs) -> Result<(), CliError> {
+    let content = std::fs::read_to_string(&args.patch)
+        .map_err(|e| CliError::FileReadError(args.patch.clone(), e.to_string()))?;
+    let patch = DocumentationPatch::from_yaml(&content).map_err(CliError::ParseError)?;
+
+    let mut total = 0;
+    for mut loaded in load_contracts(&args.path)? {
+        let mut applied = 0;
+        for (schema, table) in loaded.contract.schema.iter_mut().zip(&mut loaded.tables) {
+            let count = patch.apply(table);
+            if count > 0 {
+                *schema = SchemaObject::from(&*table);
+                applied += count;
+            }
+        }
+        if applied == 0 {
+            continue;
+        }
+        total += applied;
+
+        if args.dry_run {
+            eprintln!(
+                "  Would apply {} suggestions to {}",
+                applied,
+                loaded.file.display()
+            );
+        } else {
+            write_contract(&loaded.file, &loaded.contract)?;
+            eprintln!(
+                "  Applied {} suggestions to {}",
+                applied,
+                loaded.file.display()
+            );
+        }
+    }
+
+    eprintln!(
+        "{} {} of {} suggestions",
+        if args.dry_run {
+            "Would apply"
+        } else {
+            "Applied"
+        },
+        total,
+        patch.suggestions.len()
+    );
+    Ok(())
+}
+
+/// An ODCS contract file with one table per schema
+struct LoadedContract {
+    file: PathBuf,
+    contract: ODCSContract,
+    tables: Vec<Table>,
+}
+
+/// Load the ODCS contracts under `path`, skipping files that fail to parse
+fn load_contracts(path: &Path) -> Result<Vec<LoadedContract>, CliError> {
+    let mut contracts = Vec::new();
+    for file in find_odcs_files(path)? {
+        let content = std::fs::read_to_string(&file)
+            .map_err(|e| CliError::FileReadError(file.clone(), e.to_string()))?;
+        match parse_contract(&content) {
+            Ok((contract, tables)) => contracts.push(LoadedContract {
+                file,
+                contract,
+                tables,
+            }),
+            Err(e) => eprintln!("  Skipping {}: {}", file.display(), e),
+        }
+    }
+    Ok(contracts)
+}
+
+/// Build the LLM configuration from command-line arguments
+fn refinement_config(args: &DocsSuggestArgs) -> Result<RefinementConfig, CliError> {
+    let llm_mode = match args.llm_mode.as_str() {
+        "online" => LlmMode::online_with_url(&args.ollama_url, &args.model),
+        "offline" => LlmMode::offline(args.model_path.clone().ok_or_else(|| {
+            CliError::InvalidArgument("Offline mode requires --model-path".to_string())
+        })?),
+        "openai" => LlmMode::OpenAi {
+            base_url: args.openai_url.clone(),
+            model: args.model.clone(),
+            api_key_env: args.api_key_env.clone(),
+        },
+        other => {
+            return Err(CliError::InvalidArgument(format!(
+                "Invalid LLM mode: {}. Use 'online', 'offline' or 'openai'",
+                other
+            )));
+        }
+    };
+
+    let mut config = RefinementConfig {
+        llm_mode,
+        documentation_path: args.doc_path.clone(),
+        ..Default::default()
+    }
+    .with_temperature(args.temperature);
+
+    if let Some(ref path) = args.llm_cache {
+        let mode = args
+            .llm_cache_mode
+            .parse::<CacheMode>()
+            .map_err(CliError::InvalidArgument)?;
+        config.cache = Some(LlmCacheConfig::new(path, mode));
+    }
+    if let Some(ref model) = args.embedding_model {
+        config.retrieval = RetrievalConfig::default().with_embeddings(Some(model.clone()));
+    }
+
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use data_modelling_core::llm::table_description;
+
+    #[test]
+    fn test_docs_apply() {
+        let dir = std::env::temp_dir().join(format!("odm-docs-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let file = dir.join("shop.odcs.yaml");
+        std::fs::write(
+            &file,
+            "apiVersion: v3.1.0\n\
+             kind: DataContract\n\
+             id: shop\n\
+             version: 1.0.0\n\
+             name: shop\n\
+             domain: sales\n\
+             schema:\n\
+             - name: orders\n  properties:\n  - name: status\n    logicalType: string\n\
+             - name: customers\n  properties:\n  - name: tier\n    logicalType: string\n",
+        )
+        .unwrap();
+
+        let patch_file = dir.join("patch.yaml");
+        std::fs::write(
+            &patch_file,
+            "suggestions:\n\
+             - table: orders\n  column: status\n  field: description\n  proposed: Fulfilment status\n\
+             - table: orders\n  column: status\n  field: businessName\n  proposed: Order Status\n\
+             - table: customers\n  column: tier\n  field: description\n  proposed: Loyalty tier\n",
+        )
+        .unwrap();
+
+        let args = DocsApplyArgs {
+            patch: patch_file,
+            path: dir.clone(),
+            dry_run: false,
+        };
+        handle_docs_apply(&args).unwrap();
+
+        let loaded = load_contracts(&dir).unwrap().remove(0);
+        assert_eq!(loaded.contract.domain.as_deref(), Some("sales"));
+        assert_eq!(loaded.tables.len(), 2);
+        assert_eq!(loaded.tables[0].columns[0].description, "Fulfilment status");
+        assert_eq!(
+            loaded.tables[0].columns[0].business_name.as_deref(),
+            Some("Order Status")
+        );
+        assert_eq!(loaded.tables[1].columns[0].description, "Loyalty tier");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_docs_apply_table_description() {
+        let dir = std::env::temp_dir().join(format!("odm-docs-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let file = dir.join("shop.odcs.yaml");
+        std::fs::write(
+            &file,
+            "apiVersion: v3.1.0\n\
+             kind: DataContract\n\
+             id: shop\n\
+             version: 1.0.0\n\
+             name: shop\n\
+             description:\n  purpose: Webshop data\n\
+             schema:\n\
+             - name: orders\n  properties:\n  - name: status\n    logicalType: string\n",
+        )
+        .unwrap();
+
+        // The contract purpose is not taken as the table's description
+        let loaded = load_contracts(&dir).unwrap().remove(0);
+        assert_eq!(table_description(&loaded.tables[0]), None);
+
+        let patch_file = dir.join("patch.yaml");
+        std::fs::write(
+            &patch_file,
+            "suggestions:\n\
+             - table: orders\n  field: description\n  proposed: Customer orders\n",
+        )
+        .unwrap();
+
+        let args = DocsApplyArgs {
+            patch: patch_file,
+            path: dir.clone(),
+            dry_run: false,
+        };
+        handle_docs_apply(&args).unwrap();
+
+        let loaded = load_contracts(&dir).unwrap().remove(0);
+        assert_eq!(
+            table_description(&loaded.tables[0]),
+            Some("Customer orders")
+        );
+        assert_eq!(
+            loaded.contract.schema[0].description.as_deref(),
+            Some("Customer orders")
+        );
+        assert!(
+            std::fs::read_to_string(&file)
+                .unwrap()
+                .contains("Webshop data")
+        );
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
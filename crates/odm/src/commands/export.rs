@@ -3,7 +3,7 @@
 use crate::error::CliError;
 use data_modelling_core::export::pdf::BrandingConfig;
 use data_modelling_core::export::{
-    AvroExporter, BrandedMarkdownExporter, JSONSchemaExporter, MarkdownBrandingConfig,
+    AvroExporter, BrandedMarkdownExporter, DbtExporter, JSONSchemaExporter, MarkdownBrandingConfig,
     MarkdownExporter, ODCSExporter, PdfExporter, ProtobufExporter,
 };
 use std::path::PathBuf;
@@ -22,6 +22,8 @@ pub enum ExportFormat {
     Pdf,
     /// Branded Markdown export
     BrandedMarkdown,
+    /// dbt project fragment
+    Dbt,
 }
 
 /// Arguments for export operations
@@ -40,6 +42,8 @@ pub struct ExportArgs {
     pub brand_color: Option<String>,
     pub company_name: Option<String>,
     pub include_toc: bool,
+    /// Export as a dbt source with this name instead of a model (dbt format)
+    pub dbt_source: Option<String>,
 }
 
 /// Load tables from ODCS YAML file(s)
@@ -321,6 +325,49 @@ pub fn handle_export_protobuf_descriptor(args: &ExportArgs) -> Result<(), CliErr
     Ok(())
 }
 
+/// Handle dbt export command
+///
+/// Writes a project fragment into the output directory: `models/schema.yml`
+/// (or `models/sources.yml` with `--dbt-source`) and singular tests under `tests/`.
+pub fn handle_export_dbt(args: &ExportArgs) -> Result<(), CliError> {
+    let content = std::fs::read_to_string(&args.input)
+        .map_err(|e| CliError::FileReadError(args.input.clone(), e.to_string()))?;
+    let table = super::pii::parse_table(&content)
+        .map_err(|e| CliError::InvalidArgument(format!("Failed to import ODCS file: {}", e)))?;
+
+    let tables = std::slice::from_ref(&table);
+    let project = match &args.dbt_source {
+        Some(source) => DbtExporter::export_project(tables, &[], source),
+        None => DbtExporter::export_project(&[], tables, "raw"),
+    }
+    .map_err(CliError::ExportError)?;
+
+    let files = project.files();
+    for (path, _) in &files {
+        check_file_overwrite(&args.output.join(path), args.force)?;
+    }
+    for (path, content) in &files {
+        write_export_output(&args.output.join(path), content)?;
+    }
+
+    for warning in &project.warnings {
+        eprintln!("⚠️  {}", warning);
+    }
+    println!(
+        "✅ Exported dbt {} for '{}' ({} test file(s)) to {}",
+        if args.dbt_source.is_some() {
+            "source"
+        } else {
+            "model"
+        },
+        table.name,
+        project.tests.len(),
+        args.output.display()
+    );
+
+    Ok(())
+}
+
 /// Handle ODPS export command
 ///
 /// ODPS is a native format - it only accepts ODPS input files.
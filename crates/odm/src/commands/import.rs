@@ -5,9 +5,11 @@ use crate::output::{collect_type_mappings, format_compact_output, format_pretty_
 use crate::reference::resolve_reference;
 use data_modelling_core::export::odcs::ODCSExporter;
 use data_modelling_core::import::{
-    AvroImporter, ColumnData, ImportResult, JSONSchemaImporter, ODCSImporter, ODPSImporter,
-    ProtobufImporter, SQLImporter, TableData,
+    AvroImporter, ColumnData, IcebergMetadataImporter, ImportResult, JSONSchemaImporter,
+    ODCSImporter, ODPSImporter, ProtobufImporter, SQLImporter, TableData,
 };
+#[cfg(feature = "lakehouse-import")]
+use data_modelling_core::import::{DeltaLogImporter, ParquetImporter};
 use data_modelling_core::models::{Column, Table};
 #[cfg(feature = "openapi")]
 use data_modelling_core::validation::schema::validate_openapi_internal;
@@ -17,7 +19,7 @@ use data_modelling_core::validation::schema::{
 };
 use serde_json::Value as JsonValue;
 use std::io::{self, Read};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
 /// Input source for import operations
@@ -55,6 +57,9 @@ pub enum ImportFormat {
     Odcs,
     Odcl,
     Odps,
+    Parquet,
+    Iceberg,
+    Delta,
 }
 
 /// Load input content from InputSource
@@ -103,15 +108,20 @@ fn column_data_to_column(col_data: &ColumnData) -> Column {
     };
 
     Column {
+        id: col_data.id.clone(),
         name: col_data.name.clone(),
         data_type,
         physical_type: col_data.physical_type.clone(),
+        physical_name: col_data.physical_name.clone(),
         nullable: col_data.nullable,
         primary_key: col_data.primary_key,
+        partitioned: col_data.partitioned,
+        partition_key_position: col_data.partition_key_position,
         description,
         quality: col_data.quality.clone().unwrap_or_default(),
         relationships: col_data.relationships.clone(),
         enum_values: col_data.enum_values.clone().unwrap_or_default(),
+        custom_properties: col_data.custom_properties.clone(),
         ..Default::default()
     }
 }
@@ -209,6 +219,15 @@ fn table_data_to_table(table_data: &TableData, uuid: Option<Uuid>) -> Table {
 
     let mut table = Table::new(table_name, all_columns);
 
+    // Table-level metadata (e.g. lakehouse table properties) becomes custom properties
+    table.odcl_metadata.extend(table_data.odcs_metadata.clone());
+    if let Some(ref description) = table_data.description {
+        table
+            .odcl_metadata
+            .entry("description".to_string())
+            .or_insert_with(|| description.clone());
+    }
+
     // Override UUID if provided
     if let Some(uuid_val) = uuid {
         table.id = uuid_val;
@@ -1929,3 +1948,72 @@ pub fn handle_import_odcl(args: &ImportArgs) -> Result<(), CliError> {
 
     Ok(())
 }
+
+/// Local path of a lakehouse table import
+fn lakehouse_path<'a>(args: &'a ImportArgs, format: &str) -> Result<&'a Path, CliError> {
+    match &args.input {
+        InputSource::File(path) if path.exists() => Ok(path),
+        InputSource::File(path) => Err(CliError::FileNotFound(path.clone())),
+        _ => Err(CliError::InvalidArgument(format!(
+            "{} import requires a local path",
+            format
+        ))),
+    }
+}
+
+/// Display a lakehouse import result and write its ODCS file
+fn finish_lakehouse_import(
+    args: &ImportArgs,
+    path: &Path,
+    mut result: ImportResult,
+) -> Result<(), CliError> {
+    // Apply UUID override if provided
+    if let Some(ref uuid) = args.uuid_override {
+        apply_uuid_override(&mut result, uuid)?;
+    }
+
+    // Display results
+    let mappings = collect_type_mappings(&result);
+    let output = if args.pretty {
+        format_pretty_output(&result, &mappings)
+    } else {
+        format_compact_output(&result)
+    };
+    print!("{}", output);
+
+    // Write the ODCS file next to the table directory or file
+    if !args.no_odcs {
+        write_odcs_files(&result, path.parent(), args.uuid_override.as_deref())?;
+    }
+
+    Ok(())
+}
+
+/// Handle Parquet import command (reads the schema from a file footer)
+#[cfg(feature = "lakehouse-import")]
+pub fn handle_import_parquet(args: &ImportArgs) -> Result<(), CliError> {
+    let path = lakehouse_path(args, "Parquet")?;
+    let result = ParquetImporter::new()
+        .import_path(path)
+        .map_err(CliError::ImportError)?;
+    finish_lakehouse_import(args, path, result)
+}
+
+/// Handle Iceberg import command (reads a table's metadata.json)
+pub fn handle_import_iceberg(args: &ImportArgs) -> Result<(), CliError> {
+    let path = lakehouse_path(args, "Iceberg")?;
+    let result = IcebergMetadataImporter::new()
+        .import_path(path)
+        .map_err(CliError::ImportError)?;
+    finish_lakehouse_import(args, path, result)
+}
+
+/// Handle Delta Lake import command (replays the table's _delta_log)
+#[cfg(feature = "lakehouse-import")]
+pub fn handle_import_delta(args: &ImportArgs) -> Result<(), CliError> {
+    let path = lakehouse_path(args, "Delta Lake")?;
+    let result = DeltaLogImporter::new()
+        .import_path(path)
+        .map_err(CliError::ImportError)?;
+    finish_lakehouse_import(args, path, result)
+}
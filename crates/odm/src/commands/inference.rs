@@ -5,12 +5,14 @@
 
 #![allow(dead_code)]
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
 
 use crate::error::CliError;
 use data_modelling_core::inference::{
-    InferenceConfig, InferredSchema, InferredType, SchemaInferrer, group_similar_schemas,
-    merge_schemas,
+    InferenceConfig, InferenceStats, InferredSchema, InferredType, SchemaInferrer,
+    apply_schema_descriptions, discover_foreign_keys, group_similar_schemas, merge_schemas,
 };
 use data_modelling_core::staging::StagingDb;
 
@@ -28,26 +30,60 @@ pub struct InferenceInferArgs {
     pub max_depth: usize,
     /// Enable format detection
     pub detect_formats: bool,
-    /// Output format (json, yaml, json-schema)
+    /// Output format (json, yaml, json-schema, odcs)
     pub format: String,
     /// Output file path (stdout if not provided)
     pub output: Option<PathBuf>,
-    /// LLM mode (none, online, offline)
+    /// LLM mode (none, online, offline, openai)
     pub llm_mode: String,
     /// Ollama URL for online mode
     pub ollama_url: String,
+    /// Base URL of an OpenAI-compatible server for openai mode
+    pub openai_url: String,
+    /// Environment variable holding the API key for openai mode
+    pub api_key_env: Option<String>,
+    /// LLM response cache directory or `.duckdb` staging database
+    pub llm_cache: Option<PathBuf>,
+    /// LLM response cache mode (readwrite, record, replay)
+    pub llm_cache_mode: String,
     /// Model name
     pub model: String,
     /// Path to GGUF model for offline mode
     pub model_path: Option<PathBuf>,
     /// Path to documentation file
     pub doc_path: Option<PathBuf>,
+    /// Embedding model for ranking documentation chunks
+    pub embedding_model: Option<String>,
     /// Skip LLM refinement
     pub no_refine: bool,
     /// Temperature for generation
     pub temperature: f32,
     /// Verbose LLM output
     pub verbose_llm: bool,
+    /// Organisation PII rules file for classifying ODCS output
+    pub pii_config: Option<PathBuf>,
+    /// Skip PII classification of ODCS output
+    pub no_pii: bool,
+    /// Field that discriminates record variants (detected automatically if not set)
+    pub discriminator: Option<String>,
+    /// Disable discriminated union detection
+    pub no_variants: bool,
+    /// Incremental state file: fold only records staged since the last run
+    pub state: Option<PathBuf>,
+    /// Maximum new records folded into the state per run (0 = all)
+    pub max_new_records: usize,
+}
+
+/// Saved inferrer state for incremental `inference infer --state` runs
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InferenceStateFile {
+    /// Highest staged record id folded into the inferrer
+    last_record_id: i64,
+    /// Partition the state was built from
+    partition: Option<String>,
+    /// Accumulated inferrer state
+    inferrer: SchemaInferrer,
 }
 
 /// Arguments for the `inference schemas` command
@@ -76,49 +112,67 @@ pub fn handle_inference_infer(args: &InferenceInferArgs) -> Result<(), CliError>
     }
 
     // Build inference configuration
-    let config = InferenceConfig::builder()
+    let mut builder = InferenceConfig::builder()
         .sample_size(args.sample_size)
         .min_field_frequency(args.min_frequency)
         .detect_formats(args.detect_formats)
         .max_depth(args.max_depth)
-        .build();
+        .detect_variants(!args.no_variants);
+    if let Some(ref discriminator) = args.discriminator {
+        builder = builder.discriminator_field(discriminator);
+    }
+    let config = builder.build();
 
     eprintln!("Inferring schema from staging database...");
     eprintln!("  Sample size: {}", args.sample_size);
     eprintln!("  Min frequency: {:.0}%", args.min_frequency * 100.0);
     eprintln!("  Format detection: {}", args.detect_formats);
 
-    // Get samples from the database
-    let samples = db
-        .get_sample(args.sample_size, args.partition.as_deref())
-        .map_err(|e| CliError::InferenceError(e.to_string()))?;
+    // Samples are only needed again for LLM refinement
+    #[cfg_attr(not(feature = "llm"), allow(unused_variables))]
+    let (samples, stats, schema) = if let Some(ref state_path) = args.state {
+        infer_incremental(args, &db, config, state_path)?
+    } else {
+        // Get samples from the database
+        let samples = db
+            .get_sample(args.sample_size, args.partition.as_deref())
+            .map_err(|e| CliError::InferenceError(e.to_string()))?;
 
-    if samples.is_empty() {
-        return Err(CliError::InferenceError(
-            "No records found in staging database.".to_string(),
-        ));
-    }
+        if samples.is_empty() {
+            return Err(CliError::InferenceError(
+                "No records found in staging database.".to_string(),
+            ));
+        }
 
-    eprintln!("  Records sampled: {}", samples.len());
+        eprintln!("  Records sampled: {}", samples.len());
 
-    // Create inferrer and process samples
-    let mut inferrer = SchemaInferrer::with_config(config);
+        // Create inferrer and process samples
+        let mut inferrer = SchemaInferrer::with_config(config);
 
-    for sample in &samples {
-        if let Ok(value) = serde_json::from_str::<serde_json::Value>(sample) {
-            let _ = inferrer.add_value(&value);
+        for sample in &samples {
+            if let Ok(value) = serde_json::from_str::<serde_json::Value>(sample) {
+                let _ = inferrer.add_value(&value);
+            }
         }
-    }
 
-    let stats = inferrer.stats();
-    let schema = inferrer
-        .finalize()
-        .map_err(|e| CliError::InferenceError(e.to_string()))?;
+        let stats = inferrer.stats();
+        let schema = inferrer
+            .finalize()
+            .map_err(|e| CliError::InferenceError(e.to_string()))?;
+        (samples, stats, schema)
+    };
 
     eprintln!();
     eprintln!("Inference complete:");
     eprintln!("  Records processed: {}", stats.records_processed);
     eprintln!("  Fields discovered: {}", stats.fields_discovered);
+    if let Some(ref discriminator) = schema.discriminator {
+        eprintln!(
+            "  Variants: {} (discriminated by '{}')",
+            schema.variants.len(),
+            discriminator
+        );
+    }
 
     // Convert to JSON Schema for potential LLM refinement
     let json_schema = schema.to_json_schema();
@@ -144,6 +198,27 @@ pub fn handle_inference_infer(args: &InferenceInferArgs) -> Result<(), CliError>
     let output_str = match args.format.as_str() {
         "json-schema" | "json" => serde_json::to_string_pretty(&final_schema)
             .map_err(|e| CliError::InferenceError(e.to_string()))?,
+        "odcs" => {
+            let name = args.partition.as_deref().unwrap_or("inferred_schema");
+            let mut tables = schema.to_variant_tables(name);
+            apply_schema_descriptions(&mut tables, &final_schema);
+            if !args.no_pii {
+                let classifier = super::pii::load_classifier(args.pii_config.as_deref())?;
+                let findings: usize = tables
+                    .iter_mut()
+                    .map(|table| classifier.classify_table(table).len())
+                    .sum();
+                if findings > 0 {
+                    eprintln!("Classified {} columns as PII", findings);
+                }
+            }
+            if tables.len() > 1 {
+                let contract = data_modelling_core::inference::contract_from_tables(name, &tables);
+                data_modelling_core::export::ODCSExporter::export_contract(&contract)
+            } else {
+                data_modelling_core::export::ODCSExporter::export_table(&tables[0], "odcs_v3_1_0")
+            }
+        }
         "yaml" => serde_yaml::to_string(&final_schema)
             .map_err(|e| CliError::InferenceError(e.to_string()))?,
         _ => serde_json::to_string_pretty(&final_schema)
@@ -163,6 +238,105 @@ pub fn handle_inference_infer(args: &InferenceInferArgs) -> Result<(), CliError>
     Ok(())
 }
 
+/// Fold records staged since the last run into a saved inferrer state
+///
+/// The state file is created on the first run. Up to `max_new_records` new
+/// records (0 = all) are folded per run; the schema is generated from the full
+/// state. Later runs must use the inference options the state was built with.
+fn infer_incremental(
+    args: &InferenceInferArgs,
+    db: &StagingDb,
+    mut config: InferenceConfig,
+    state_path: &Path,
+) -> Result<(Vec<String>, InferenceStats, InferredSchema), CliError> {
+    // The saved state accumulates every folded record
+    config.sample_size = 0;
+
+    let mut state = if state_path.exists() {
+        let content = std::fs::read_to_string(state_path)
+            .map_err(|e| CliError::FileReadError(state_path.to_path_buf(), e.to_string()))?;
+        let state: InferenceStateFile = serde_json::from_str(&content).map_err(|e| {
+            CliError::InferenceError(format!("Invalid inference state file: {}", e))
+        })?;
+        if state.partition != args.partition {
+            return Err(CliError::InferenceError(format!(
+                "State file was built from partition {:?}, not {:?}",
+                state.partition, args.partition
+            )));
+        }
+        let changed = changed_options(state.inferrer.config(), &config);
+        if !changed.is_empty() {
+            return Err(CliError::InferenceError(format!(
+                "State file was built with different inference options ({}). \
+                 Rerun with the original options or delete {} to start over",
+                changed.join(", "),
+                state_path.display()
+            )));
+        }
+        state
+    } else {
+        InferenceStateFile {
+            last_record_id: 0,
+            partition: args.partition.clone(),
+            inferrer: SchemaInferrer::with_config(config),
+        }
+    };
+
+    eprintln!("  State file: {}", state_path.display());
+    eprintln!("  Records in state: {}", state.inferrer.record_count());
+
+    let records = db
+        .get_records_after(
+            state.last_record_id,
+            args.max_new_records,
+            args.partition.as_deref(),
+        )
+        .map_err(|e| CliError::InferenceError(e.to_string()))?;
+
+    eprintln!("  New records: {}", records.len());
+
+    let mut samples = Vec::with_capacity(records.len());
+    for (id, raw_json) in records {
+        if let Ok(value) = serde_json::from_str::<serde_json::Value>(&raw_json) {
+            let _ = state.inferrer.add_value(&value);
+        }
+        state.last_record_id = id;
+        samples.push(raw_json);
+    }
+
+    if state.inferrer.record_count() == 0 {
+        return Err(CliError::InferenceError(
+            "No records found in staging database.".to_string(),
+        ));
+    }
+
+    let content =
+        serde_json::to_string(&state).map_err(|e| CliError::SerializationError(e.to_string()))?;
+    std::fs::write(state_path, content)
+        .map_err(|e| CliError::FileWriteError(state_path.to_path_buf(), e.to_string()))?;
+
+    let stats = state.inferrer.stats();
+    let schema = state
+        .inferrer
+        .snapshot()
+        .map_err(|e| CliError::InferenceError(e.to_string()))?;
+    Ok((samples, stats, schema))
+}
+
+/// Names of the inference options that differ from a saved configuration
+fn changed_options(saved: &InferenceConfig, requested: &InferenceConfig) -> Vec<String> {
+    let (Ok(serde_json::Value::Object(saved)), Ok(serde_json::Value::Object(requested))) =
+        (serde_json::to_value(saved), serde_json::to_value(requested))
+    else {
+        return Vec::new();
+    };
+    requested
+        .iter()
+        .filter(|(key, value)| saved.get(*key) != Some(*value))
+        .map(|(key, _)| key.clone())
+        .collect()
+}
+
 /// Refine schema using LLM (feature-gated)
 #[cfg(feature = "llm")]
 fn refine_with_llm(
@@ -170,7 +344,10 @@ fn refine_with_llm(
     schema: &serde_json::Value,
     samples: &[String],
 ) -> Result<serde_json::Value, CliError> {
-    use data_modelling_core::llm::{LlmClient, LlmMode, RefinementConfig, refine_schema};
+    use data_modelling_core::llm::{
+        CacheMode, LlmCacheConfig, LlmClient, LlmMode, RefinementConfig, RetrievalConfig,
+        refine_schema,
+    };
 
     eprintln!();
     eprintln!("Refining schema with LLM...");
@@ -198,9 +375,18 @@ fn refine_with_llm(
                 gpu_layers: 0,
             }
         }
+        "openai" => {
+            eprintln!("  URL: {}", args.openai_url);
+            eprintln!("  Model: {}", args.model);
+            LlmMode::OpenAi {
+                base_url: args.openai_url.clone(),
+                model: args.model.clone(),
+                api_key_env: args.api_key_env.clone(),
+            }
+        }
         _ => {
             return Err(CliError::InferenceError(format!(
-                "Invalid LLM mode: {}. Use 'online' or 'offline'",
+                "Invalid LLM mode: {}. Use 'online', 'offline' or 'openai'",
                 args.llm_mode
             )));
         }
@@ -217,11 +403,31 @@ fn refine_with_llm(
         include_samples: true,
         max_samples: 5,
         verbose: args.verbose_llm,
+        cache: args
+            .llm_cache
+            .as_ref()
+            .map(|path| {
+                args.llm_cache_mode
+                    .parse::<CacheMode>()
+                    .map(|mode| LlmCacheConfig::new(path, mode))
+            })
+            .transpose()
+            .map_err(CliError::InvalidArgument)?,
+        retrieval: match args.embedding_model {
+            Some(ref model) => RetrievalConfig::default().with_embeddings(Some(model.clone())),
+            None => RetrievalConfig::default(),
+        },
     };
 
     if let Some(ref doc_path) = args.doc_path {
         eprintln!("  Documentation: {}", doc_path.display());
     }
+    if let Some(ref model) = args.embedding_model {
+        eprintln!("  Embedding model: {}", model);
+    }
+    if let Some(ref cache) = config.cache {
+        eprintln!("  Cache: {} ({})", cache.path.display(), cache.mode);
+    }
 
     // Create async runtime for LLM call
     let rt = tokio::runtime::Runtime::new()
@@ -232,7 +438,10 @@ fn refine_with_llm(
         #[cfg(feature = "llm-online")]
         if matches!(config.llm_mode, LlmMode::Online { .. }) {
             if let LlmMode::Online { ref url, ref model } = config.llm_mode {
-                let client = data_modelling_core::llm::OllamaClient::new(url, model);
+                let mut client = data_modelling_core::llm::OllamaClient::new(url, model);
+                if let Some(ref embedding_model) = config.retrieval.embedding_model {
+                    client = client.with_embedding_model(embedding_model);
+                }
 
                 // Check if model is available
                 if !client.is_ready().await {
@@ -255,6 +464,28 @@ fn refine_with_llm(
             }
         }
 
+        // Cached and OpenAI-compatible clients are built from the config
+        if config.cache.is_some() || (cfg!(feature = "llm-online") && config.llm_mode.is_openai()) {
+            let client = data_modelling_core::llm::create_client(&config)
+                .map_err(|e| CliError::InferenceError(format!("Failed to create client: {}", e)))?;
+
+            if !client.is_ready().await {
+                return Err(CliError::InferenceError(
+                    "LLM server not reachable or model not available".to_string(),
+                ));
+            }
+
+            let sample_strings: Option<Vec<String>> = if config.include_samples {
+                Some(samples.iter().take(config.max_samples).cloned().collect())
+            } else {
+                None
+            };
+
+            return refine_schema(&client, schema, &config, sample_strings)
+                .await
+                .map_err(|e| CliError::InferenceError(format!("LLM refinement failed: {}", e)));
+        }
+
         #[cfg(feature = "llm-offline")]
         if matches!(config.llm_mode, LlmMode::Offline { .. }) {
             if let LlmMode::Offline {
@@ -379,6 +610,30 @@ pub fn handle_inference_schemas(args: &InferenceSchemasArgs) -> Result<(), CliEr
     // Group similar schemas
     let groups = group_similar_schemas(&partition_schemas, args.threshold);
 
+    // Merge each group and name it after its first partition
+    let merged_groups: Vec<(String, InferredSchema)> = groups
+        .iter()
+        .enumerate()
+        .map(|(i, group)| {
+            let name = group
+                .first()
+                .and_then(|&idx| partition_schemas[idx].partition.clone())
+                .unwrap_or_else(|| format!("group_{}", i + 1));
+            let schemas_to_merge: Vec<_> = group
+                .iter()
+                .map(|&idx| partition_schemas[idx].clone())
+                .collect();
+            (name, merge_schemas(schemas_to_merge))
+        })
+        .collect();
+
+    // Discover foreign keys between schema groups
+    let named: Vec<(&str, &InferredSchema)> = merged_groups
+        .iter()
+        .map(|(name, schema)| (name.as_str(), schema))
+        .collect();
+    let foreign_keys = discover_foreign_keys(&named);
+
     match args.format.as_str() {
         "json" => {
             let output: Vec<serde_json::Value> = groups
@@ -394,19 +649,23 @@ pub fn handle_inference_schemas(args: &InferenceSchemasArgs) -> Result<(), CliEr
                         .map(|&idx| partition_schemas[idx].record_count)
                         .sum();
 
-                    // Merge schemas in this group
-                    let schemas_to_merge: Vec<_> = group
+                    let (name, merged) = &merged_groups[i];
+                    let references: Vec<_> = foreign_keys
                         .iter()
-                        .map(|&idx| partition_schemas[idx].clone())
+                        .filter(|fk| &fk.source_schema == name)
                         .collect();
-                    let merged = merge_schemas(schemas_to_merge);
+
+                    // Value sketches are only needed for key discovery, not for display
+                    let mut merged = merged.clone();
+                    merged.value_sketches.clear();
 
                     serde_json::json!({
                         "group": i + 1,
                         "partitions": partitions,
                         "partition_count": group.len(),
                         "total_records": total_records,
-                        "schema": merged
+                        "schema": merged,
+                        "foreign_keys": references
                     })
                 })
                 .collect();
@@ -457,6 +716,31 @@ pub fn handle_inference_schemas(args: &InferenceSchemasArgs) -> Result<(), CliEr
                         }
                     }
                 }
+
+                let keys: Vec<String> = merged_groups[i]
+                    .1
+                    .candidate_keys
+                    .iter()
+                    .map(|key| key.fields.join(" + "))
+                    .collect();
+                if !keys.is_empty() {
+                    println!("  Candidate keys: {}", keys.join(", "));
+                }
+                println!();
+            }
+
+            if !foreign_keys.is_empty() {
+                println!("Foreign keys:");
+                for fk in &foreign_keys {
+                    println!(
+                        "  - {}.{} -> {}.{}{}",
+                        fk.source_schema,
+                        fk.source_field.trim_start_matches("$."),
+                        fk.target_schema,
+                        fk.target_field.trim_start_matches("$."),
+                        if fk.estimated { " (estimated)" } else { "" }
+                    );
+                }
                 println!();
             }
 
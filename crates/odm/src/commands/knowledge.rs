@@ -2,11 +2,10 @@
 //!
 //! Provides CLI commands for managing knowledge base articles.
 //!
-//! Note: These commands are defined but not yet wired into the main CLI.
-//! They will be enabled in a future release.
+//! Articles follow a draft → review → published lifecycle: `new` creates a
+//! draft, `review` submits it for review and `publish` releases it.
 
 #![allow(clippy::collapsible_if)]
-#![allow(dead_code)]
 
 use crate::error::CliError;
 use chrono::Utc;
@@ -103,6 +102,26 @@ pub struct KnowledgeSearchArgs {
     pub format: String,
 }
 
+/// Arguments for the `knowledge review` command
+#[derive(Debug)]
+pub struct KnowledgeReviewArgs {
+    /// Article number
+    pub number: String,
+    /// Reviewer to add to the article (optional)
+    pub reviewer: Option<String>,
+    /// Workspace path
+    pub workspace: PathBuf,
+}
+
+/// Arguments for the `knowledge publish` command
+#[derive(Debug)]
+pub struct KnowledgePublishArgs {
+    /// Article number
+    pub number: String,
+    /// Workspace path
+    pub workspace: PathBuf,
+}
+
 /// Handle `knowledge new` command
 pub fn handle_knowledge_new(args: &KnowledgeNewArgs) -> Result<(), CliError> {
     // Parse article type
@@ -125,13 +144,18 @@ pub fn handle_knowledge_new(args: &KnowledgeNewArgs) -> Result<(), CliError> {
     let number = index.next_number;
     index.next_number += 1;
 
+    let author = match args.author_email {
+        Some(ref email) => format!("{} <{}>", args.author, email),
+        None => args.author.clone(),
+    };
+
     // Create the article with placeholder content
     let mut article = KnowledgeArticle::new(
         number,
         &args.title,
         "[Brief summary of this article]",
         "# Content\n\n[Write your article content here in Markdown format]",
-        &args.author,
+        &author,
     );
 
     // Apply type and optional fields
@@ -355,19 +379,7 @@ pub fn handle_knowledge_status(args: &KnowledgeStatusArgs) -> Result<(), CliErro
     article.status = new_status.clone();
     article.updated_at = Utc::now();
 
-    // Save the updated article
-    let exporter = KnowledgeExporter;
-    let yaml = exporter
-        .export(&article)
-        .map_err(|e| CliError::IoError(format!("Failed to export: {}", e)))?;
-    fs::write(&file_path, &yaml)
-        .map_err(|e| CliError::IoError(format!("Failed to write file: {}", e)))?;
-
-    // Update index if it exists
-    let index_path = args.workspace.join("knowledge.yaml");
-    if index_path.exists() {
-        update_article_in_index(&index_path, &article)?;
-    }
+    save_article(&args.workspace, &file_path, &article)?;
 
     println!(
         "Updated {} status: {} -> {}",
@@ -377,6 +389,54 @@ pub fn handle_knowledge_status(args: &KnowledgeStatusArgs) -> Result<(), CliErro
     Ok(())
 }
 
+/// Handle `knowledge review` command
+pub fn handle_knowledge_review(args: &KnowledgeReviewArgs) -> Result<(), CliError> {
+    let number = parse_article_number(&args.number)?;
+    let (file_path, mut article) = find_article_file_and_load(&args.workspace, number)?;
+    check_article_status(&article, KnowledgeStatus::Draft, KnowledgeStatus::Review)?;
+
+    article.status = KnowledgeStatus::Review;
+    if let Some(ref reviewer) = args.reviewer {
+        if !article.reviewers.contains(reviewer) {
+            article.reviewers.push(reviewer.clone());
+        }
+    }
+    article.updated_at = Utc::now();
+    save_article(&args.workspace, &file_path, &article)?;
+
+    println!(
+        "Submitted {} for review: {}",
+        article.formatted_number(),
+        article.title
+    );
+
+    Ok(())
+}
+
+/// Handle `knowledge publish` command
+pub fn handle_knowledge_publish(args: &KnowledgePublishArgs) -> Result<(), CliError> {
+    let number = parse_article_number(&args.number)?;
+    let (file_path, mut article) = find_article_file_and_load(&args.workspace, number)?;
+    check_article_status(
+        &article,
+        KnowledgeStatus::Review,
+        KnowledgeStatus::Published,
+    )?;
+
+    article.status = KnowledgeStatus::Published;
+    article.mark_reviewed();
+    article.published_at = article.reviewed_at;
+    save_article(&args.workspace, &file_path, &article)?;
+
+    println!(
+        "Published {}: {}",
+        article.formatted_number(),
+        article.title
+    );
+
+    Ok(())
+}
+
 /// Handle `knowledge export` command
 pub fn handle_knowledge_export(args: &KnowledgeExportArgs) -> Result<(), CliError> {
     let output_dir = args
@@ -571,7 +631,8 @@ fn parse_article_number(s: &str) -> Result<u64, CliError> {
         .map_err(|_| CliError::InvalidArgument(format!("Invalid article number: {}", s)))
 }
 
-fn load_all_articles(workspace: &Path) -> Result<Vec<KnowledgeArticle>, CliError> {
+/// Load all knowledge articles in a workspace, sorted by number
+pub fn load_all_articles(workspace: &Path) -> Result<Vec<KnowledgeArticle>, CliError> {
     let importer = KnowledgeImporter;
     let mut articles = Vec::new();
 
@@ -676,6 +737,46 @@ fn update_article_in_index(index_path: &Path, article: &KnowledgeArticle) -> Res
     Ok(())
 }
 
+/// Ensure an article is in `from` before moving it to `to`
+fn check_article_status(
+    article: &KnowledgeArticle,
+    from: KnowledgeStatus,
+    to: KnowledgeStatus,
+) -> Result<(), CliError> {
+    if article.status == from {
+        Ok(())
+    } else {
+        Err(CliError::InvalidArgument(format!(
+            "Cannot move {} from {} to {}",
+            article.formatted_number(),
+            article.status,
+            to
+        )))
+    }
+}
+
+/// Write an article back to its file and refresh its index entry
+fn save_article(
+    workspace: &Path,
+    file_path: &Path,
+    article: &KnowledgeArticle,
+) -> Result<(), CliError> {
+    let exporter = KnowledgeExporter;
+    let yaml = exporter
+        .export(article)
+        .map_err(|e| CliError::IoError(format!("Failed to export: {}", e)))?;
+    fs::write(file_path, &yaml)
+        .map_err(|e| CliError::IoError(format!("Failed to write file: {}", e)))?;
+
+    // Update index if it exists
+    let index_path = workspace.join("knowledge.yaml");
+    if index_path.exists() {
+        update_article_in_index(&index_path, article)?;
+    }
+
+    Ok(())
+}
+
 fn escape_csv(s: &str) -> String {
     if s.contains(',') || s.contains('"') || s.contains('\n') {
         format!("\"{}\"", s.replace('"', "\"\""))
@@ -711,4 +812,48 @@ mod tests {
         assert_eq!(parse_article_number("kb-42").unwrap(), 42);
         assert!(parse_article_number("invalid").is_err());
     }
+
+    #[test]
+    fn test_knowledge_review_and_publish() {
+        let workspace =
+            std::env::temp_dir().join(format!("odm-knowledge-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&workspace).unwrap();
+
+        handle_knowledge_new(&KnowledgeNewArgs {
+            title: "Onboarding".to_string(),
+            article_type: "guide".to_string(),
+            domain: None,
+            author: "Data Team".to_string(),
+            author_email: None,
+            workspace: workspace.clone(),
+            export_markdown: false,
+        })
+        .unwrap();
+
+        let publish = KnowledgePublishArgs {
+            number: "KB-0001".to_string(),
+            workspace: workspace.clone(),
+        };
+        // Drafts must go through review before publishing
+        assert!(handle_knowledge_publish(&publish).is_err());
+
+        handle_knowledge_review(&KnowledgeReviewArgs {
+            number: "1".to_string(),
+            reviewer: Some("Reviewer".to_string()),
+            workspace: workspace.clone(),
+        })
+        .unwrap();
+        handle_knowledge_publish(&publish).unwrap();
+
+        let article = find_article_by_number(&workspace, 1).unwrap();
+        assert_eq!(article.status, KnowledgeStatus::Published);
+        assert_eq!(article.reviewers, vec!["Reviewer"]);
+        assert!(article.published_at.is_some());
+
+        let content = fs::read_to_string(workspace.join("knowledge.yaml")).unwrap();
+        let index = KnowledgeImporter.import_index(&content).unwrap();
+        assert_eq!(index.articles[0].status, KnowledgeStatus::Published);
+
+        fs::remove_dir_all(&workspace).unwrap();
+    }
 }
@@ -1,11 +1,15 @@
 //! CLI commands for schema mapping operations
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use super::pii::parse_table;
 use crate::error::CliError;
+use data_modelling_core::export::{JSONSchemaExporter, ODCSExporter};
 use data_modelling_core::mapping::{
-    MappingConfig, SchemaMatcher, TransformFormat, generate_transform,
+    ContractMatcher, ExecutionResult, MappingConfig, MappingExecutor, MappingSpec, SchemaMapping,
+    SchemaMatcher, SpecIssueSeverity, TransformFormat, apply_lineage, generate_transform,
 };
+use data_modelling_core::models::Table;
 
 /// Arguments for the `map` command
 pub struct MapArgs {
@@ -25,25 +29,59 @@ pub struct MapArgs {
     pub transform_format: String,
     /// Transform output file
     pub transform_output: Option<PathBuf>,
+    /// dbt source name for staging models generated from contracts
+    pub dbt_source: String,
+    /// Mapping specification file
+    pub spec: Option<PathBuf>,
+    /// Domain for a newly created mapping specification
+    pub domain: Option<String>,
+    /// Write lineage into the target contract
+    pub write_lineage: bool,
+    /// Sample records to execute the mapping against
+    pub test_records: Option<PathBuf>,
+    /// Staging database to execute the mapping against
+    #[cfg(feature = "staging")]
+    pub test_database: Option<PathBuf>,
+    /// Staging partition to execute against
+    #[cfg(feature = "staging")]
+    pub partition: Option<String>,
+    /// Maximum staged records to execute (0 = all)
+    #[cfg(feature = "staging")]
+    pub test_limit: usize,
+    /// Execution report output file
+    pub test_report: Option<PathBuf>,
     /// Verbose output
     pub verbose: bool,
 }
 
 /// Handle the `map` command
 pub fn handle_map(args: &MapArgs) -> Result<(), CliError> {
-    // Load source schema
-    let source_content = std::fs::read_to_string(&args.source)
-        .map_err(|e| CliError::MappingError(format!("Failed to read source schema: {}", e)))?;
+    // ODCS contracts are matched on their metadata, JSON Schemas on names
+    let source_table = load_contract(&args.source)?;
+    let mut target_table = load_contract(&args.target)?;
+    if source_table.is_some() != target_table.is_some() {
+        return Err(CliError::InvalidArgument(
+            "Source and target must both be ODCS contracts (.odcs.yaml) or both JSON Schemas"
+                .to_string(),
+        ));
+    }
+    if args.write_lineage && target_table.is_none() {
+        return Err(CliError::InvalidArgument(
+            "--write-lineage requires ODCS contracts".to_string(),
+        ));
+    }
 
-    let source_schema: serde_json::Value = serde_json::from_str(&source_content)
-        .map_err(|e| CliError::MappingError(format!("Failed to parse source schema: {}", e)))?;
+    // Load source schema
+    let source_schema = match source_table {
+        Some(ref table) => JSONSchemaExporter::export_table(table),
+        None => load_schema(&args.source, "source")?,
+    };
 
     // Load target schema
-    let target_content = std::fs::read_to_string(&args.target)
-        .map_err(|e| CliError::MappingError(format!("Failed to read target schema: {}", e)))?;
-
-    let target_schema: serde_json::Value = serde_json::from_str(&target_content)
-        .map_err(|e| CliError::MappingError(format!("Failed to parse target schema: {}", e)))?;
+    let target_schema = match target_table {
+        Some(ref table) => JSONSchemaExporter::export_table(table),
+        None => load_schema(&args.target, "target")?,
+    };
 
     // Parse transform format
     let transform_format: TransformFormat = args
@@ -67,10 +105,46 @@ pub fn handle_map(args: &MapArgs) -> Result<(), CliError> {
     }
 
     // Run mapping
-    let matcher = SchemaMatcher::with_config(config.clone());
-    let mapping = matcher
-        .match_schemas(&source_schema, &target_schema)
-        .map_err(|e| CliError::MappingError(format!("Mapping failed: {}", e)))?;
+    let mapping = match (&source_table, &target_table) {
+        (Some(source), Some(target)) => {
+            let result = ContractMatcher::with_config(config.clone())
+                .match_tables(source, target)
+                .map_err(|e| CliError::MappingError(format!("Mapping failed: {}", e)))?;
+
+            if args.verbose && !result.evidence.is_empty() {
+                eprintln!();
+                eprintln!("Contract matches:");
+                for evidence in &result.evidence {
+                    let signals: Vec<String> =
+                        evidence.signals.iter().map(|s| s.to_string()).collect();
+                    eprintln!(
+                        "  {} -> {}: {}",
+                        evidence.source,
+                        evidence.target,
+                        signals.join(", ")
+                    );
+                }
+            }
+            for warning in &result.warnings {
+                eprintln!("WARNING: {}", warning);
+            }
+            result.mapping
+        }
+        _ => {
+            let matcher = SchemaMatcher::with_config(config.clone());
+            matcher
+                .match_schemas(&source_schema, &target_schema)
+                .map_err(|e| CliError::MappingError(format!("Mapping failed: {}", e)))?
+        }
+    };
+
+    // Apply the reviewed mapping specification on top of the matcher output
+    let mapping = match args.spec {
+        Some(ref spec_path) => {
+            apply_spec(args, spec_path, &mapping, &source_schema, &target_schema)?
+        }
+        None => mapping,
+    };
 
     // Print summary
     eprintln!();
@@ -163,7 +237,15 @@ pub fn handle_map(args: &MapArgs) -> Result<(), CliError> {
 
     // Generate transform script
     if let Some(ref transform_path) = args.transform_output {
-        let script = generate_transform(&mapping, transform_format, "source_table", "target_table")
+        // dbt models read from `{{ source() }}`, which needs the real table names
+        let (source_name, target_name) = match (&source_table, &target_table) {
+            (Some(source), Some(target)) if transform_format == TransformFormat::Dbt => (
+                format!("{}.{}", args.dbt_source, source.name),
+                target.name.clone(),
+            ),
+            _ => ("source_table".to_string(), "target_table".to_string()),
+        };
+        let script = generate_transform(&mapping, transform_format, &source_name, &target_name)
             .map_err(|e| CliError::MappingError(format!("Failed to generate transform: {}", e)))?;
 
         std::fs::write(transform_path, &script).map_err(|e| {
@@ -173,6 +255,38 @@ pub fn handle_map(args: &MapArgs) -> Result<(), CliError> {
         eprintln!("Transform script written to: {}", transform_path.display());
     }
 
+    // Record lineage in the target contract
+    if args.write_lineage
+        && let (Some(source), Some(target)) = (&source_table, &mut target_table)
+    {
+        let updated = apply_lineage(&mapping, source, target);
+        std::fs::write(
+            &args.target,
+            ODCSExporter::export_table(target, "odcs_v3_1_0"),
+        )
+        .map_err(|e| CliError::FileWriteError(args.target.clone(), e.to_string()))?;
+        eprintln!(
+            "Lineage written to {} column(s) in {}",
+            updated,
+            args.target.display()
+        );
+    }
+
+    // Execute mapping against sample records
+    if let Some(result) = execute_mapping(args, &mapping, &target_schema)? {
+        print_execution_report(&result, args.verbose);
+
+        if let Some(ref report_path) = args.test_report {
+            let report_json = serde_json::to_string_pretty(&result.report).map_err(|e| {
+                CliError::MappingError(format!("Failed to serialize execution report: {}", e))
+            })?;
+            std::fs::write(report_path, &report_json).map_err(|e| {
+                CliError::MappingError(format!("Failed to write execution report: {}", e))
+            })?;
+            eprintln!("Execution report written to: {}", report_path.display());
+        }
+    }
+
     // Return error if there are required gaps
     if mapping.stats.required_gaps > 0 {
         eprintln!();
@@ -184,3 +298,200 @@ pub fn handle_map(args: &MapArgs) -> Result<(), CliError> {
 
     Ok(())
 }
+
+/// Load a JSON Schema file
+fn load_schema(path: &Path, role: &str) -> Result<serde_json::Value, CliError> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| CliError::MappingError(format!("Failed to read {} schema: {}", role, e)))?;
+
+    serde_json::from_str(&content)
+        .map_err(|e| CliError::MappingError(format!("Failed to parse {} schema: {}", role, e)))
+}
+
+/// Load an ODCS contract, or `None` if the file is not one
+fn load_contract(path: &Path) -> Result<Option<Table>, CliError> {
+    let name = path.to_string_lossy();
+    if !name.ends_with(".odcs.yaml") && !name.ends_with(".odcs.yml") {
+        return Ok(None);
+    }
+
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| CliError::FileReadError(path.to_path_buf(), e.to_string()))?;
+    parse_table(&content)
+        .map(Some)
+        .map_err(|e| CliError::MappingError(format!("Failed to parse {}: {}", path.display(), e)))
+}
+
+/// Merge matcher output into a mapping specification, validate it and write it back
+fn apply_spec(
+    args: &MapArgs,
+    spec_path: &Path,
+    fresh: &SchemaMapping,
+    source_schema: &serde_json::Value,
+    target_schema: &serde_json::Value,
+) -> Result<SchemaMapping, CliError> {
+    let spec = if spec_path.exists() {
+        let content = std::fs::read_to_string(spec_path)
+            .map_err(|e| CliError::FileReadError(spec_path.to_path_buf(), e.to_string()))?;
+        let mut spec =
+            MappingSpec::from_yaml(&content).map_err(|e| CliError::MappingError(e.to_string()))?;
+        let summary = spec.merge(fresh);
+
+        eprintln!("Mapping spec: {}", spec_path.display());
+        eprintln!(
+            "  {} manual kept, {} added, {} updated, {} removed",
+            summary.preserved.len(),
+            summary.added.len(),
+            summary.updated.len(),
+            summary.removed.len()
+        );
+        if args.verbose {
+            for field in &summary.added {
+                eprintln!("    + {}", field);
+            }
+            for field in &summary.updated {
+                eprintln!("    ~ {}", field);
+            }
+            for field in &summary.removed {
+                eprintln!("    - {}", field);
+            }
+        }
+        spec
+    } else {
+        let file_name = spec_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("mapping");
+        let name = file_name
+            .strip_suffix(".mapping.yaml")
+            .or_else(|| file_name.strip_suffix(".yaml"))
+            .unwrap_or(file_name);
+        let mut spec = MappingSpec::from_mapping(name, fresh).with_schemas(
+            args.source.display().to_string(),
+            args.target.display().to_string(),
+        );
+        spec.domain = args.domain.clone();
+
+        eprintln!("Created mapping spec: {}", spec_path.display());
+        spec
+    };
+
+    let issues = spec
+        .validate(source_schema, target_schema)
+        .map_err(|e| CliError::MappingError(e.to_string()))?;
+    for issue in &issues {
+        eprintln!("  {}", issue);
+    }
+    let error_count = issues
+        .iter()
+        .filter(|i| i.severity == SpecIssueSeverity::Error)
+        .count();
+    if error_count > 0 {
+        return Err(CliError::ValidationError(format!(
+            "{} error(s) in mapping spec {}",
+            error_count,
+            spec_path.display()
+        )));
+    }
+
+    let yaml = spec
+        .to_yaml()
+        .map_err(|e| CliError::MappingError(e.to_string()))?;
+    std::fs::write(spec_path, yaml)
+        .map_err(|e| CliError::FileWriteError(spec_path.to_path_buf(), e.to_string()))?;
+
+    spec.resolve(fresh, source_schema, target_schema)
+        .map_err(|e| CliError::MappingError(e.to_string()))
+}
+
+/// Execute the mapping against test records, if any were requested
+fn execute_mapping(
+    args: &MapArgs,
+    mapping: &SchemaMapping,
+    target_schema: &serde_json::Value,
+) -> Result<Option<ExecutionResult>, CliError> {
+    let executor = MappingExecutor::new(mapping.clone()).with_target_schema(target_schema.clone());
+
+    if let Some(ref path) = args.test_records {
+        let records = load_records(path)?;
+        return Ok(Some(executor.execute(&records)));
+    }
+
+    #[cfg(feature = "staging")]
+    if let Some(ref db_path) = args.test_database {
+        let db = data_modelling_core::staging::StagingDb::open(&db_path.to_string_lossy())
+            .map_err(|e| CliError::MappingError(format!("Failed to open database: {}", e)))?;
+        let result = executor
+            .execute_partition(&db, args.partition.as_deref(), args.test_limit)
+            .map_err(|e| CliError::MappingError(e.to_string()))?;
+        return Ok(Some(result));
+    }
+
+    Ok(None)
+}
+
+/// Load records from a JSON array or JSON Lines file
+fn load_records(path: &PathBuf) -> Result<Vec<serde_json::Value>, CliError> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| CliError::MappingError(format!("Failed to read test records: {}", e)))?;
+
+    if let Ok(serde_json::Value::Array(records)) = serde_json::from_str(&content) {
+        return Ok(records);
+    }
+
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line).map_err(|e| {
+                CliError::MappingError(format!("Invalid test record on line {}: {}", i + 1, e))
+            })
+        })
+        .collect()
+}
+
+/// Print a summary of a mapping execution
+fn print_execution_report(result: &ExecutionResult, verbose: bool) {
+    let report = &result.report;
+
+    eprintln!();
+    eprintln!("Execution Results");
+    eprintln!("=================");
+    eprintln!("Records processed: {}", report.records_processed);
+    eprintln!("Records valid: {}", report.records_valid);
+    eprintln!("Records failed: {}", report.records_failed);
+    if report.records_emitted != report.records_processed {
+        eprintln!("Records emitted: {}", report.records_emitted);
+    }
+
+    if !report.fields.is_empty() {
+        eprintln!();
+        eprintln!("Field failures:");
+        for (field, failures) in &report.fields {
+            eprintln!(
+                "  {}: {} transform, {} validation",
+                field, failures.transform, failures.validation
+            );
+        }
+    }
+
+    if !report.unsupported.is_empty() {
+        eprintln!();
+        eprintln!("Not executed (unsupported):");
+        for item in &report.unsupported {
+            eprintln!("  {}", item);
+        }
+    }
+
+    if verbose && !report.errors.is_empty() {
+        eprintln!();
+        eprintln!("Errors:");
+        for error in &report.errors {
+            eprintln!(
+                "  record {} {} ({}): {}",
+                error.record, error.field, error.kind, error.message
+            );
+        }
+    }
+}
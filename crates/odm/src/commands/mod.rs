@@ -1,9 +1,12 @@
 //! CLI command implementations
 
 pub mod decision;
+#[cfg(feature = "llm")]
+pub mod docs;
 pub mod export;
 pub mod import;
 pub mod knowledge;
+pub mod pii;
 pub mod validate;
 
 #[cfg(feature = "duckdb-backend")]
@@ -0,0 +1,248 @@
+//! CLI commands for PII detection
+
+use std::path::{Path, PathBuf};
+
+use crate::error::CliError;
+use data_modelling_core::export::ODCSExporter;
+use data_modelling_core::import::ODCSImporter;
+use data_modelling_core::models::Table;
+use data_modelling_core::models::odcs::{ODCSContract, SchemaObject};
+use data_modelling_core::pii::{PiiClassifier, PiiConfig, PiiReport};
+
+/// Arguments for the `pii scan` command
+pub struct PiiScanArgs {
+    /// Workspace directory or ODCS file to scan
+    pub path: PathBuf,
+    /// Organisation PII configuration file (YAML)
+    pub config: Option<PathBuf>,
+    /// Output format (table, json, markdown)
+    pub format: String,
+    /// Output file (stdout if not provided)
+    pub output: Option<PathBuf>,
+    /// Write classifications back into the ODCS files
+    pub apply: bool,
+}
+
+/// Build a PII classifier from an optional organisation configuration file
+pub fn load_classifier(config_path: Option<&Path>) -> Result<PiiClassifier, CliError> {
+    let config = match config_path {
+        Some(path) => {
+            let content = std::fs::read_to_string(path)
+                .map_err(|e| CliError::FileReadError(path.to_path_buf(), e.to_string()))?;
+            PiiConfig::from_yaml(&content).map_err(|e| CliError::PiiError(e.to_string()))?
+        }
+        None => PiiConfig::default(),
+    };
+    PiiClassifier::with_config(config).map_err(|e| CliError::PiiError(e.to_string()))
+}
+
+/// Handle the `pii scan` command
+pub fn handle_pii_scan(args: &PiiScanArgs) -> Result<(), CliError> {
+    let classifier = load_classifier(args.config.as_deref())?;
+
+    let files = find_odcs_files(&args.path)?;
+    if files.is_empty() {
+        return Err(CliError::NotFound(format!(
+            "No .odcs.yaml files found in {}",
+            args.path.display()
+        )));
+    }
+
+    eprintln!("Scanning {} ODCS files for PII...", files.len());
+
+    let mut report = PiiReport::new();
+    for file in &files {
+        let content = std::fs::read_to_string(file)
+            .map_err(|e| CliError::FileReadError(file.clone(), e.to_string()))?;
+        let (mut contract, mut tables) = match parse_contract(&content) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                eprintln!("  Skipping {}: {}", file.display(), e);
+                continue;
+            }
+        };
+
+        if args.apply {
+            let mut classified = 0;
+            for (schema, table) in contract.schema.iter_mut().zip(tables.iter_mut()) {
+                let findings = classifier.classify_table(table);
+                if !findings.is_empty() {
+                    *schema = SchemaObject::from(&*table);
+                    classified += findings.len();
+                }
+            }
+            if classified > 0 {
+                write_contract(file, &contract)?;
+                eprintln!("  Classified {} columns in {}", classified, file.display());
+            }
+        }
+
+        report.extend(classifier.scan(&tables));
+    }
+
+    let output = match args.format.as_str() {
+        "json" => serde_json::to_string_pretty(&report)
+            .map_err(|e| CliError::SerializationError(e.to_string()))?,
+        "markdown" | "md" => report.to_markdown(),
+        "table" => format_table(&report),
+        other => {
+            return Err(CliError::InvalidArgument(format!(
+                "Unknown format: {}. Use table, json or markdown",
+                other
+            )));
+        }
+    };
+
+    if let Some(ref output_path) = args.output {
+        std::fs::write(output_path, &output)
+            .map_err(|e| CliError::FileWriteError(output_path.clone(), e.to_string()))?;
+        eprintln!("Report written to: {}", output_path.display());
+    } else {
+        println!("{}", output);
+    }
+
+    Ok(())
+}
+
+/// Parse an ODCS v3 contract with one table per schema, in schema order
+///
+/// Changes to a table are written back by replacing its schema with
+/// `SchemaObject::from(&table)` and saving the contract with [`write_contract`],
+/// which keeps the other schemas and the contract-level fields.
+pub fn parse_contract(content: &str) -> Result<(ODCSContract, Vec<Table>), String> {
+    let mut importer = ODCSImporter::new();
+    let contract = importer
+        .import_contract(content)
+        .map_err(|e| e.to_string())?;
+    let tables = contract.to_tables();
+    Ok((contract, tables))
+}
+
+/// Write a whole ODCS contract back to its file
+pub fn write_contract(file: &Path, contract: &ODCSContract) -> Result<(), CliError> {
+    let yaml = ODCSExporter::export_contract_validated(contract)
+        .map_err(|e| CliError::SerializationError(e.to_string()))?;
+    std::fs::write(file, yaml)
+        .map_err(|e| CliError::FileWriteError(file.to_path_buf(), e.to_string()))
+}
+
+/// Parse a single-table ODCS document
+pub fn parse_table(content: &str) -> Result<Table, String> {
+    let mut importer = ODCSImporter::new();
+    importer
+        .parse_table(content)
+        .map(|(table, _)| table)
+        .map_err(|e| e.to_string())
+}
+
+/// Collect `.odcs.yaml` files from a file or directory (recursively)
+pub fn find_odcs_files(path: &Path) -> Result<Vec<PathBuf>, CliError> {
+    if path.is_file() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    if !path.is_dir() {
+        return Err(CliError::FileNotFound(path.to_path_buf()));
+    }
+
+    let mut files = Vec::new();
+    let mut dirs = vec![path.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let entries = std::fs::read_dir(&dir)
+            .map_err(|e| CliError::FileReadError(dir.clone(), e.to_string()))?;
+        for entry in entries.flatten() {
+            let entry_path = entry.path();
+            let hidden = entry.file_name().to_string_lossy().starts_with('.');
+            if entry_path.is_dir() && !hidden {
+                dirs.push(entry_path);
+            } else if entry_path
+                .file_name()
+                .is_some_and(|n| n.to_string_lossy().ends_with(".odcs.yaml"))
+            {
+                files.push(entry_path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn format_table(report: &PiiReport) -> String {
+    let mut out = String::new();
+    out.push_str(&format!(
+        "PII Report: {} tables, {} columns scanned, {} columns with PII\n",
+        report.tables_scanned,
+        report.columns_scanned,
+        report.findings.len()
+    ));
+    out.push_str(&"=".repeat(60));
+    out.push('\n');
+
+    for (table, findings) in report.by_table() {
+        out.push_str(&format!("\n{}\n", table));
+        for finding in findings {
+            out.push_str(&format!(
+                "  - {} [{}] {}\n",
+                finding.column, finding.level, finding.category
+            ));
+        }
+    }
+
+    let levels = report.count_by_level();
+    if !levels.is_empty() {
+        out.push_str("\nBy level:\n");
+        for (level, count) in levels.iter().rev() {
+            out.push_str(&format!("  {}: {}\n", level, count));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pii_scan_apply_multi_schema_contract() {
+        let dir = std::env::temp_dir().join(format!("odm-pii-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let file = dir.join("crm.odcs.yaml");
+        std::fs::write(
+            &file,
+            "apiVersion: v3.1.0\n\
+             kind: DataContract\n\
+             id: crm\n\
+             version: 1.0.0\n\
+             name: crm\n\
+             status: active\n\
+             domain: sales\n\
+             schema:\n\
+             - name: orders\n  properties:\n  - name: id\n    logicalType: integer\n\
+             - name: customers\n  properties:\n  - name: id\n    logicalType: integer\n  \
+             - name: email\n    logicalType: string\n",
+        )
+        .unwrap();
+
+        let args = PiiScanArgs {
+            path: dir.clone(),
+            config: None,
+            format: "json".to_string(),
+            output: Some(dir.join("report.json")),
+            apply: true,
+        };
+        handle_pii_scan(&args).unwrap();
+
+        let content = std::fs::read_to_string(&file).unwrap();
+        let (contract, tables) = parse_contract(&content).unwrap();
+        assert_eq!(contract.domain.as_deref(), Some("sales"));
+        assert_eq!(contract.schema_names(), vec!["orders", "customers"]);
+        let email = tables[1]
+            .columns
+            .iter()
+            .find(|c| c.name == "email")
+            .unwrap();
+        assert!(email.classification.is_some());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
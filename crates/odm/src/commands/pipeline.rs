@@ -1,7 +1,6 @@
 //! CLI commands for pipeline operations
 //!
-//! Note: Some fields in argument structs are defined for future configuration
-//! file support but are not yet used.
+//! Note: Some fields in argument structs are defined for future use.
 
 #![allow(dead_code)]
 
@@ -9,7 +8,8 @@ use std::path::PathBuf;
 
 use crate::error::CliError;
 use data_modelling_core::pipeline::{
-    LlmPipelineConfig, PipelineConfig, PipelineExecutor, PipelineStage,
+    DagExecutor, LlmPipelineConfig, PipelineConfig, PipelineDefinition, PipelineExecutor,
+    PipelineStage,
 };
 
 /// Arguments for the `pipeline run` command
@@ -28,10 +28,18 @@ pub struct PipelineRunArgs {
     pub target_schema: Option<PathBuf>,
     /// Stages to run (empty = all)
     pub stages: Vec<String>,
-    /// LLM mode (none, online, offline)
+    /// LLM mode (none, online, offline, openai)
     pub llm_mode: String,
     /// Ollama URL
     pub ollama_url: String,
+    /// OpenAI-compatible server base URL
+    pub openai_url: String,
+    /// Environment variable holding the OpenAI API key
+    pub api_key_env: Option<String>,
+    /// LLM response cache directory or `.duckdb` staging database
+    pub llm_cache: Option<PathBuf>,
+    /// LLM response cache mode (readwrite, record, replay)
+    pub llm_cache_mode: String,
     /// Model name
     pub model: String,
     /// Model path for offline mode
@@ -40,7 +48,7 @@ pub struct PipelineRunArgs {
     pub doc_path: Option<PathBuf>,
     /// Temperature
     pub temperature: f32,
-    /// Configuration file
+    /// Pipeline definition file (YAML or TOML)
     pub config_file: Option<PathBuf>,
     /// Dry run mode
     pub dry_run: bool,
@@ -58,10 +66,18 @@ pub struct PipelineStatusArgs {
 
 /// Handle the `pipeline run` command
 pub fn handle_pipeline_run(args: &PipelineRunArgs) -> Result<(), CliError> {
+    if let Some(ref path) = args.config_file {
+        return run_definition(path, args);
+    }
+
     // Build LLM config
     let llm = LlmPipelineConfig {
         mode: args.llm_mode.clone(),
         ollama_url: args.ollama_url.clone(),
+        openai_url: args.openai_url.clone(),
+        api_key_env: args.api_key_env.clone(),
+        cache_path: args.llm_cache.clone(),
+        cache_mode: args.llm_cache_mode.clone(),
         model: args.model.clone(),
         model_path: args.model_path.clone(),
         doc_path: args.doc_path.clone(),
@@ -127,6 +143,37 @@ pub fn handle_pipeline_run(args: &PipelineRunArgs) -> Result<(), CliError> {
     }
 }
 
+/// Run every source and entity of a pipeline definition file
+fn run_definition(path: &std::path::Path, args: &PipelineRunArgs) -> Result<(), CliError> {
+    let definition =
+        PipelineDefinition::from_file(path).map_err(|e| CliError::PipelineError(e.to_string()))?;
+    let executor = DagExecutor::from_definition(&definition)
+        .map_err(|e| CliError::PipelineError(e.to_string()))?
+        .with_resume(args.resume)
+        .with_dry_run(args.dry_run)
+        .with_verbose(args.verbose);
+
+    eprintln!(
+        "Starting pipeline {} ({} nodes)",
+        definition.name,
+        executor.plan().nodes.len()
+    );
+
+    let report = executor
+        .run()
+        .map_err(|e| CliError::PipelineError(e.to_string()))?;
+
+    report.print_summary();
+
+    if report.is_success() {
+        eprintln!();
+        eprintln!("Pipeline completed successfully!");
+        Ok(())
+    } else {
+        Err(CliError::PipelineError("Pipeline failed".to_string()))
+    }
+}
+
 /// Handle the `pipeline status` command
 pub fn handle_pipeline_status(args: &PipelineStatusArgs) -> Result<(), CliError> {
     use data_modelling_core::pipeline::Checkpoint;
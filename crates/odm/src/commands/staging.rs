@@ -5,10 +5,18 @@
 
 #![allow(dead_code)]
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
 use crate::error::CliError;
-use data_modelling_core::staging::{DedupStrategy, IngestConfig, SourceType, StagingDb};
+#[cfg(feature = "inference")]
+use data_modelling_core::inference::InferenceConfig;
+use data_modelling_core::staging::{
+    DataProfiler, DeadLetterStatus, DedupStrategy, IngestConfig, ProfileConfig, RetentionPolicy,
+    SourceType, StagingDb, WatchConfig, WatchPartition, Watcher,
+};
 
 /// Arguments for the `staging init` command
 pub struct StagingInitArgs {
@@ -48,6 +56,18 @@ pub struct StagingIngestArgs {
     pub resume: bool,
     /// Batch ID for resume
     pub batch_id: Option<String>,
+    /// Maximum number of rejected records before the batch fails
+    pub max_errors: Option<usize>,
+    /// Maximum share of rejected records before the batch fails
+    pub max_error_rate: Option<f64>,
+    /// Keep watching the source directory
+    pub watch: bool,
+    /// Seconds between two polls in watch mode
+    pub poll_interval: u64,
+    /// Partitioning of watched files (fixed, directory, date)
+    pub partition_by: String,
+    /// Output file for the schema inferred in watch mode
+    pub infer_output: Option<PathBuf>,
 }
 
 /// Arguments for the `staging stats` command
@@ -90,6 +110,142 @@ pub struct StagingSampleArgs {
     pub partition: Option<String>,
 }
 
+/// Arguments for the `staging profile` command
+pub struct StagingProfileArgs {
+    /// Path to the staging database file
+    pub database: PathBuf,
+    /// Partition to profile
+    pub partition: Option<String>,
+    /// Number of most frequent values per field
+    pub top_k: usize,
+    /// Profile a random sample of this many records
+    pub sample_size: Option<usize>,
+    /// Output format (markdown, json)
+    pub format: String,
+    /// Output file path (stdout if not provided)
+    pub output: Option<PathBuf>,
+    /// Show a stored profile version instead of profiling
+    pub version: Option<i32>,
+    /// Do not store the profile in the staging database
+    pub no_save: bool,
+}
+
+/// Arguments for the `staging dead-letters list` command
+pub struct StagingDeadLetterListArgs {
+    /// Path to the staging database file
+    pub database: PathBuf,
+    /// Only show records rejected by this batch
+    pub batch_id: Option<String>,
+    /// Only show records with this status
+    pub status: Option<String>,
+    /// Maximum number of records to show
+    pub limit: usize,
+}
+
+/// Arguments for the `staging dead-letters show` command
+pub struct StagingDeadLetterShowArgs {
+    /// Path to the staging database file
+    pub database: PathBuf,
+    /// Dead letter ID
+    pub id: String,
+}
+
+/// Arguments for the `staging dead-letters reingest` command
+pub struct StagingDeadLetterReingestArgs {
+    /// Path to the staging database file
+    pub database: PathBuf,
+    /// Dead letter ID
+    pub id: String,
+    /// Fixed JSON to ingest instead of the raw data
+    pub data: Option<String>,
+    /// File containing fixed JSON
+    pub file: Option<PathBuf>,
+}
+
+/// Arguments for the `staging dead-letters discard` command
+pub struct StagingDeadLetterDiscardArgs {
+    /// Path to the staging database file
+    pub database: PathBuf,
+    /// Dead letter ID
+    pub id: String,
+}
+
+/// Arguments for the `staging partitions list` command
+pub struct StagingPartitionListArgs {
+    /// Path to the staging database file
+    pub database: PathBuf,
+}
+
+/// Arguments for the `staging partitions drop` command
+pub struct StagingPartitionDropArgs {
+    /// Path to the staging database file
+    pub database: PathBuf,
+    /// Partition to drop
+    pub partition: String,
+    /// Parquet file to archive the partition to before dropping it
+    pub archive: Option<PathBuf>,
+}
+
+/// Arguments for the `staging partitions rename` command
+pub struct StagingPartitionRenameArgs {
+    /// Path to the staging database file
+    pub database: PathBuf,
+    /// Current partition name
+    pub from: String,
+    /// New partition name
+    pub to: String,
+}
+
+/// Arguments for the `staging partitions merge` command
+pub struct StagingPartitionMergeArgs {
+    /// Path to the staging database file
+    pub database: PathBuf,
+    /// Partitions to merge
+    pub sources: Vec<String>,
+    /// Target partition
+    pub into: String,
+}
+
+/// Arguments for the `staging partitions compact` command
+pub struct StagingPartitionCompactArgs {
+    /// Path to the staging database file
+    pub database: PathBuf,
+    /// Partition to compact
+    pub partition: String,
+}
+
+/// Arguments for the `staging partitions archive` command
+pub struct StagingPartitionArchiveArgs {
+    /// Path to the staging database file
+    pub database: PathBuf,
+    /// Partition to archive
+    pub partition: String,
+    /// Output Parquet file
+    pub output: PathBuf,
+}
+
+/// Arguments for the `staging partitions retain` command
+pub struct StagingPartitionRetainArgs {
+    /// Path to the staging database file
+    pub database: PathBuf,
+    /// Maximum age in days of the last record of a partition
+    pub max_age_days: Option<i64>,
+    /// Number of most recently ingested partitions to keep
+    pub keep: Option<usize>,
+    /// Directory to archive dropped partitions to
+    pub archive_dir: Option<PathBuf>,
+    /// Only show which partitions would be dropped
+    pub dry_run: bool,
+}
+
+/// Arguments for the `staging vacuum` command
+pub struct StagingVacuumArgs {
+    /// Path to the staging database file
+    pub database: PathBuf,
+    /// Rewrite the database file to reclaim all free space
+    pub rewrite: bool,
+}
+
 /// Arguments for the `staging history` command
 pub struct StagingHistoryArgs {
     /// Path to the staging database file
@@ -224,6 +380,15 @@ fn handle_staging_init_iceberg(args: &StagingInitArgs, catalog_type: &str) -> Re
                 token,
             }
         }
+        "filesystem" => {
+            let warehouse = args
+                .warehouse
+                .clone()
+                .map(|p| p.display().to_string())
+                .unwrap_or_else(|| "./warehouse".to_string());
+
+            CatalogConfig::Filesystem { warehouse }
+        }
         "glue" => {
             let region = args
                 .region
@@ -243,7 +408,7 @@ fn handle_staging_init_iceberg(args: &StagingInitArgs, catalog_type: &str) -> Re
         }
         _ => {
             return Err(CliError::InvalidArgument(format!(
-                "Unknown catalog type: {}. Valid types: rest, s3-tables, unity, glue",
+                "Unknown catalog type: {}. Valid types: rest, s3-tables, unity, glue, filesystem",
                 catalog_type
             )));
         }
@@ -277,6 +442,9 @@ fn handle_staging_init_iceberg(args: &StagingInitArgs, catalog_type: &str) -> Re
             println!("  Region: {}", region);
             println!("  Database: {}", database);
         }
+        CatalogConfig::Filesystem { warehouse } => {
+            println!("  Warehouse: {}", warehouse);
+        }
     }
 
     // Save catalog configuration
@@ -317,10 +485,22 @@ pub fn handle_staging_ingest(args: &StagingIngestArgs) -> Result<(), CliError> {
         config_builder = config_builder.batch_id(batch_id);
     }
 
+    if let Some(max_errors) = args.max_errors {
+        config_builder = config_builder.max_errors(max_errors);
+    }
+
+    if let Some(max_error_rate) = args.max_error_rate {
+        config_builder = config_builder.max_error_rate(max_error_rate);
+    }
+
     let config = config_builder
         .build()
         .map_err(|e| CliError::StagingError(e.to_string()))?;
 
+    if args.watch {
+        return watch_ingest(args, &db, config);
+    }
+
     println!("Starting ingestion from: {}", args.source.display());
     println!("Pattern: {}", args.pattern);
     println!("Deduplication: {:?}", args.dedup);
@@ -349,11 +529,130 @@ pub fn handle_staging_ingest(args: &StagingIngestArgs) -> Result<(), CliError> {
         if stats.errors.len() > 10 {
             println!("  ... and {} more", stats.errors.len() - 10);
         }
+        println!();
+        println!(
+            "Rejected records were quarantined. Inspect them with 'staging dead-letters list'."
+        );
     }
 
     Ok(())
 }
 
+/// Ingest new and changed files until interrupted with Ctrl-C
+fn watch_ingest(
+    args: &StagingIngestArgs,
+    db: &StagingDb,
+    config: IngestConfig,
+) -> Result<(), CliError> {
+    let partition_by: WatchPartition = args
+        .partition_by
+        .parse()
+        .map_err(CliError::InvalidArgument)?;
+
+    let watch_config = WatchConfig::new(config)
+        .with_poll_interval(Duration::from_secs(args.poll_interval.max(1)))
+        .with_partition_by(partition_by);
+    #[allow(unused_mut)]
+    let mut watcher = Watcher::new(watch_config);
+
+    if args.infer_output.is_some() {
+        #[cfg(feature = "inference")]
+        {
+            watcher = watcher.with_inference(InferenceConfig::default());
+        }
+        #[cfg(not(feature = "inference"))]
+        return Err(CliError::InvalidArgument(
+            "--infer-output requires the 'inference' feature".to_string(),
+        ));
+    }
+
+    // Stop between two files on Ctrl-C so the running batch stays resumable
+    let stop = Arc::new(AtomicBool::new(false));
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| CliError::StagingError(format!("Failed to create runtime: {}", e)))?;
+    let signal_stop = Arc::clone(&stop);
+    std::thread::spawn(move || {
+        runtime.block_on(async {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                signal_stop.store(true, Ordering::Relaxed);
+            }
+        });
+    });
+
+    println!(
+        "Watching {} for {} every {}s (partition by {}). Press Ctrl-C to stop.",
+        args.source.display(),
+        args.pattern,
+        args.poll_interval.max(1),
+        partition_by
+    );
+
+    watcher
+        .run(db, &stop, |watcher, cycle| {
+            let now = chrono::Local::now().format("%H:%M:%S");
+            if !cycle.is_empty() {
+                println!(
+                    "[{}] {} new, {} changed files: {} records ingested",
+                    now,
+                    cycle.files_new,
+                    cycle.files_changed,
+                    cycle.records_ingested()
+                );
+            }
+            for stats in &cycle.batches {
+                if stats.errors_count > 0 {
+                    println!(
+                        "  {} errors; see 'staging dead-letters list'",
+                        stats.errors_count
+                    );
+                }
+                if stats.interrupted
+                    && let Some(ref batch_id) = stats.batch_id
+                {
+                    println!(
+                        "  Batch {} interrupted; remaining files are ingested by the next watch run",
+                        batch_id
+                    );
+                }
+            }
+            if cycle.schema_updated
+                && let Some(ref path) = args.infer_output
+            {
+                match write_watch_schema(watcher, path) {
+                    Ok(()) => println!("[{}] Schema written to {}", now, path.display()),
+                    Err(e) => eprintln!("[{}] {}", now, e),
+                }
+            }
+        })
+        .map_err(|e| CliError::StagingError(e.to_string()))?;
+
+    println!("Stopped watching.");
+    Ok(())
+}
+
+/// Write the schema inferred by a watcher as JSON Schema
+#[cfg(feature = "inference")]
+fn write_watch_schema(watcher: &Watcher, path: &Path) -> Result<(), CliError> {
+    let Some(schema) = watcher
+        .inferred_schema()
+        .map_err(|e| CliError::InferenceError(e.to_string()))?
+    else {
+        return Ok(());
+    };
+    let content = serde_json::to_string_pretty(&schema.to_json_schema())
+        .map_err(|e| CliError::SerializationError(e.to_string()))?;
+    std::fs::write(path, content)
+        .map_err(|e| CliError::FileWriteError(path.to_path_buf(), e.to_string()))
+}
+
+/// Write the schema inferred by a watcher (inference is not compiled in)
+#[cfg(not(feature = "inference"))]
+fn write_watch_schema(_watcher: &Watcher, _path: &Path) -> Result<(), CliError> {
+    Ok(())
+}
+
 /// Handle the `staging stats` command
 pub fn handle_staging_stats(args: &StagingStatsArgs) -> Result<(), CliError> {
     let db_path = args.database.display().to_string();
@@ -637,6 +936,462 @@ pub fn handle_staging_sample(args: &StagingSampleArgs) -> Result<(), CliError> {
     Ok(())
 }
 
+/// Handle the `staging profile` command
+pub fn handle_staging_profile(args: &StagingProfileArgs) -> Result<(), CliError> {
+    let db_path = args.database.display().to_string();
+
+    let db = StagingDb::open(&db_path).map_err(|e| CliError::StagingError(e.to_string()))?;
+
+    if !db
+        .is_initialized()
+        .map_err(|e| CliError::StagingError(e.to_string()))?
+    {
+        return Err(CliError::StagingError(
+            "Database not initialized. Run 'staging init' first.".to_string(),
+        ));
+    }
+
+    let profile = if args.version.is_some() {
+        db.get_profile(args.partition.as_deref(), args.version)
+            .map_err(|e| CliError::StagingError(e.to_string()))?
+            .ok_or_else(|| {
+                CliError::StagingError(format!(
+                    "Profile version {} not found",
+                    args.version.unwrap_or_default()
+                ))
+            })?
+    } else {
+        let mut config = ProfileConfig::default().with_top_k(args.top_k);
+        if let Some(ref partition) = args.partition {
+            config = config.with_partition(partition.clone());
+        }
+        if let Some(sample_size) = args.sample_size {
+            config = config.with_sample_size(sample_size);
+        }
+
+        let mut profile = DataProfiler::new(config)
+            .profile(&db)
+            .map_err(|e| CliError::StagingError(e.to_string()))?;
+        if !args.no_save {
+            let version = db
+                .save_profile(&mut profile)
+                .map_err(|e| CliError::StagingError(e.to_string()))?;
+            eprintln!("Stored profile version {}", version);
+        }
+        profile
+    };
+
+    let content = match args.format.as_str() {
+        "json" => profile
+            .to_json()
+            .map_err(|e| CliError::StagingError(e.to_string()))?,
+        "markdown" | "md" => profile.to_markdown(),
+        other => {
+            return Err(CliError::InvalidArgument(format!(
+                "Unknown profile format '{}'. Valid values: markdown, json",
+                other
+            )));
+        }
+    };
+
+    match args.output {
+        Some(ref path) => {
+            std::fs::write(path, content)
+                .map_err(|e| CliError::FileWriteError(path.clone(), e.to_string()))?;
+            println!("Profile written to {}", path.display());
+        }
+        None => println!("{}", content),
+    }
+
+    Ok(())
+}
+
+/// Open a staging database and check that it is initialized
+fn open_initialized(database: &std::path::Path) -> Result<StagingDb, CliError> {
+    let db_path = database.display().to_string();
+
+    let db = StagingDb::open(&db_path).map_err(|e| CliError::StagingError(e.to_string()))?;
+
+    if !db
+        .is_initialized()
+        .map_err(|e| CliError::StagingError(e.to_string()))?
+    {
+        return Err(CliError::StagingError(
+            "Database not initialized. Run 'staging init' first.".to_string(),
+        ));
+    }
+
+    Ok(db)
+}
+
+/// Handle the `staging dead-letters list` command
+pub fn handle_staging_dead_letter_list(args: &StagingDeadLetterListArgs) -> Result<(), CliError> {
+    let db = open_initialized(&args.database)?;
+
+    let status = args
+        .status
+        .as_deref()
+        .map(str::parse::<DeadLetterStatus>)
+        .transpose()
+        .map_err(CliError::InvalidArgument)?;
+
+    let dead_letters = db
+        .list_dead_letters(args.batch_id.as_deref(), status, args.limit)
+        .map_err(|e| CliError::StagingError(e.to_string()))?;
+
+    if dead_letters.is_empty() {
+        println!("No dead letters found.");
+        return Ok(());
+    }
+
+    println!("Dead Letters");
+    println!("============");
+    println!();
+
+    for dead_letter in dead_letters {
+        let location = match dead_letter.line_number {
+            Some(line) => format!("{}:{}", dead_letter.file_path, line),
+            None => dead_letter.file_path.clone(),
+        };
+        println!("{} [{}]", dead_letter.id, dead_letter.status);
+        println!("  Location: {}", location);
+        if let Some(ref batch_id) = dead_letter.batch_id {
+            println!("  Batch: {}", batch_id);
+        }
+        println!("  Error: {}", dead_letter.error);
+        println!();
+    }
+
+    Ok(())
+}
+
+/// Handle the `staging dead-letters show` command
+pub fn handle_staging_dead_letter_show(args: &StagingDeadLetterShowArgs) -> Result<(), CliError> {
+    let db = open_initialized(&args.database)?;
+
+    let dead_letter = db
+        .get_dead_letter(&args.id)
+        .map_err(|e| CliError::StagingError(e.to_string()))?
+        .ok_or_else(|| CliError::StagingError(format!("Dead letter not found: {}", args.id)))?;
+
+    println!("Dead letter: {}", dead_letter.id);
+    println!("  Status: {}", dead_letter.status);
+    println!("  File: {}", dead_letter.file_path);
+    if let Some(line) = dead_letter.line_number {
+        println!("  Line: {}", line);
+    }
+    if let Some(ref batch_id) = dead_letter.batch_id {
+        println!("  Batch: {}", batch_id);
+    }
+    if let Some(ref partition) = dead_letter.partition_key {
+        println!("  Partition: {}", partition);
+    }
+    if let Some(created) = dead_letter.created_at {
+        println!("  Rejected: {}", created.format("%Y-%m-%d %H:%M:%S"));
+    }
+    if let Some(resolved) = dead_letter.resolved_at {
+        println!("  Resolved: {}", resolved.format("%Y-%m-%d %H:%M:%S"));
+    }
+    println!("  Error: {}", dead_letter.error);
+    println!();
+    println!("{}", dead_letter.raw_text());
+
+    Ok(())
+}
+
+/// Handle the `staging dead-letters reingest` command
+pub fn handle_staging_dead_letter_reingest(
+    args: &StagingDeadLetterReingestArgs,
+) -> Result<(), CliError> {
+    let db = open_initialized(&args.database)?;
+
+    let fixed_json = match (&args.data, &args.file) {
+        (Some(data), _) => Some(data.clone()),
+        (None, Some(path)) => Some(
+            std::fs::read_to_string(path)
+                .map_err(|e| CliError::FileReadError(path.clone(), e.to_string()))?,
+        ),
+        (None, None) => None,
+    };
+
+    db.reingest_dead_letter(&args.id, fixed_json.as_deref())
+        .map_err(|e| CliError::StagingError(e.to_string()))?;
+
+    println!("Re-ingested dead letter {}", args.id);
+
+    Ok(())
+}
+
+/// Handle the `staging dead-letters discard` command
+pub fn handle_staging_dead_letter_discard(
+    args: &StagingDeadLetterDiscardArgs,
+) -> Result<(), CliError> {
+    let db = open_initialized(&args.database)?;
+
+    db.discard_dead_letter(&args.id)
+        .map_err(|e| CliError::StagingError(e.to_string()))?;
+
+    println!("Discarded dead letter {}", args.id);
+
+    Ok(())
+}
+
+/// Handle the `staging partitions list` command
+pub fn handle_staging_partition_list(args: &StagingPartitionListArgs) -> Result<(), CliError> {
+    let db = open_initialized(&args.database)?;
+
+    let partitions = db
+        .list_partitions()
+        .map_err(|e| CliError::StagingError(e.to_string()))?;
+
+    if partitions.is_empty() {
+        println!("No partitions found.");
+        return Ok(());
+    }
+
+    println!(
+        "{:<30} {:>12} {:>8} {:>8} {:<20} {:>6}",
+        "Partition", "Records", "Files", "Batches", "Last Ingested", "Age"
+    );
+    println!("{}", "-".repeat(90));
+
+    for partition in &partitions {
+        let last_ingested = partition
+            .last_ingested_at
+            .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_else(|| "-".to_string());
+        println!(
+            "{:<30} {:>12} {:>8} {:>8} {:<20} {:>5}d",
+            partition.name(),
+            partition.record_count,
+            partition.file_count,
+            partition.batch_count,
+            last_ingested,
+            partition.age_days
+        );
+    }
+
+    Ok(())
+}
+
+/// Handle the `staging partitions drop` command
+pub fn handle_staging_partition_drop(args: &StagingPartitionDropArgs) -> Result<(), CliError> {
+    let db = open_initialized(&args.database)?;
+
+    let dropped = db
+        .drop_partition(Some(&args.partition), args.archive.as_deref())
+        .map_err(|e| CliError::StagingError(e.to_string()))?;
+
+    println!(
+        "Dropped partition '{}': {} records, {} batches",
+        args.partition, dropped.records_removed, dropped.batches_removed
+    );
+    if let Some(ref archive) = dropped.archive {
+        println!("  Archived to: {}", archive.display());
+    }
+
+    Ok(())
+}
+
+/// Handle the `staging partitions rename` command
+pub fn handle_staging_partition_rename(args: &StagingPartitionRenameArgs) -> Result<(), CliError> {
+    let db = open_initialized(&args.database)?;
+
+    let moved = db
+        .rename_partition(&args.from, &args.to)
+        .map_err(|e| CliError::StagingError(e.to_string()))?;
+
+    println!(
+        "Renamed partition '{}' to '{}' ({} records)",
+        args.from, args.to, moved
+    );
+
+    Ok(())
+}
+
+/// Handle the `staging partitions merge` command
+pub fn handle_staging_partition_merge(args: &StagingPartitionMergeArgs) -> Result<(), CliError> {
+    let db = open_initialized(&args.database)?;
+
+    let sources: Vec<&str> = args.sources.iter().map(String::as_str).collect();
+    let moved = db
+        .merge_partitions(&sources, &args.into)
+        .map_err(|e| CliError::StagingError(e.to_string()))?;
+
+    println!(
+        "Merged {} partition(s) into '{}' ({} records moved)",
+        sources.len(),
+        args.into,
+        moved
+    );
+
+    Ok(())
+}
+
+/// Handle the `staging partitions compact` command
+pub fn handle_staging_partition_compact(
+    args: &StagingPartitionCompactArgs,
+) -> Result<(), CliError> {
+    let db = open_initialized(&args.database)?;
+
+    let stats = db
+        .compact_partition(Some(&args.partition))
+        .map_err(|e| CliError::StagingError(e.to_string()))?;
+
+    println!(
+        "Compacted partition '{}': removed {} of {} records as duplicates",
+        args.partition, stats.duplicates_removed, stats.records_before
+    );
+
+    Ok(())
+}
+
+/// Handle the `staging partitions archive` command
+pub fn handle_staging_partition_archive(
+    args: &StagingPartitionArchiveArgs,
+) -> Result<(), CliError> {
+    let db = open_initialized(&args.database)?;
+
+    let count = db
+        .archive_partition(Some(&args.partition), &args.output)
+        .map_err(|e| CliError::StagingError(e.to_string()))?;
+
+    println!(
+        "Archived {} records of partition '{}' to {}",
+        count,
+        args.partition,
+        args.output.display()
+    );
+
+    Ok(())
+}
+
+/// Handle the `staging partitions retain` command
+pub fn handle_staging_partition_retain(args: &StagingPartitionRetainArgs) -> Result<(), CliError> {
+    let mut policy = RetentionPolicy::default();
+    if let Some(days) = args.max_age_days {
+        policy = policy.with_max_age_days(days);
+    }
+    if let Some(keep) = args.keep {
+        policy = policy.with_max_partitions(keep);
+    }
+    if let Some(ref dir) = args.archive_dir {
+        policy = policy.with_archive_dir(dir);
+    }
+
+    if policy.is_unlimited() {
+        return Err(CliError::InvalidArgument(
+            "Specify --max-age-days and/or --keep".to_string(),
+        ));
+    }
+
+    let db = open_initialized(&args.database)?;
+
+    if args.dry_run {
+        let partitions = db
+            .list_partitions()
+            .map_err(|e| CliError::StagingError(e.to_string()))?;
+        let expired = policy.expired(&partitions);
+        if expired.is_empty() {
+            println!("No partitions would be dropped.");
+            return Ok(());
+        }
+        println!("Would drop {} partition(s):", expired.len());
+        for partition in expired {
+            println!(
+                "  {} ({} records, {}d old)",
+                partition.name(),
+                partition.record_count,
+                partition.age_days
+            );
+            if let Some(archive) = policy.archive_path(partition.name(), chrono::Utc::now()) {
+                println!("    Archive: {}", archive.display());
+            }
+        }
+        return Ok(());
+    }
+
+    let outcome = db
+        .apply_retention(&policy)
+        .map_err(|e| CliError::StagingError(e.to_string()))?;
+
+    if outcome.dropped.is_empty() && outcome.failed.is_empty() {
+        println!("No partitions dropped.");
+        return Ok(());
+    }
+
+    if !outcome.dropped.is_empty() {
+        println!("Dropped {} partition(s):", outcome.dropped.len());
+    }
+    for partition in &outcome.dropped {
+        println!(
+            "  {} ({} records)",
+            partition.partition.as_deref().unwrap_or("<none>"),
+            partition.records_removed
+        );
+        if let Some(ref archive) = partition.archive {
+            println!("    Archive: {}", archive.display());
+        }
+    }
+
+    if !outcome.failed.is_empty() {
+        eprintln!("Failed to drop {} partition(s):", outcome.failed.len());
+        for failed in &outcome.failed {
+            eprintln!(
+                "  {}: {}",
+                failed.partition.as_deref().unwrap_or("<none>"),
+                failed.error
+            );
+        }
+        return Err(CliError::StagingError(format!(
+            "{} expired partition(s) were kept",
+            outcome.failed.len()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Handle the `staging vacuum` command
+pub fn handle_staging_vacuum(args: &StagingVacuumArgs) -> Result<(), CliError> {
+    let db = open_initialized(&args.database)?;
+
+    if !args.rewrite {
+        let stats = db
+            .vacuum()
+            .map_err(|e| CliError::StagingError(e.to_string()))?;
+        println!(
+            "Vacuumed {}: reclaimed {} bytes",
+            args.database.display(),
+            stats.reclaimed()
+        );
+        return Ok(());
+    }
+
+    let size_before = std::fs::metadata(&args.database).map(|m| m.len()).ok();
+    let rewritten = args.database.with_extension("vacuum.duckdb");
+    db.vacuum_into(&rewritten)
+        .map_err(|e| CliError::StagingError(e.to_string()))?;
+    drop(db);
+
+    // Remove the WAL of the old file so it is not replayed against the copy
+    let _ = std::fs::remove_file(format!("{}.wal", args.database.display()));
+    std::fs::rename(&rewritten, &args.database)
+        .map_err(|e| CliError::FileWriteError(args.database.clone(), e.to_string()))?;
+
+    let size_after = std::fs::metadata(&args.database).map(|m| m.len()).ok();
+    println!(
+        "Rewrote {}: reclaimed {} bytes",
+        args.database.display(),
+        size_before
+            .zip(size_after)
+            .map(|(before, after)| before.saturating_sub(after))
+            .unwrap_or(0)
+    );
+
+    Ok(())
+}
+
 /// Handle the `staging history` command
 pub fn handle_staging_history(args: &StagingHistoryArgs) -> Result<(), CliError> {
     #[cfg(feature = "iceberg")]
@@ -791,7 +1546,7 @@ pub fn handle_staging_export(args: &StagingExportArgs) -> Result<(), CliError> {
             } => {
                 println!("Target: {}:{}/{}", region, database, args.table);
             }
-            CatalogConfig::Rest { .. } => {}
+            CatalogConfig::Rest { .. } | CatalogConfig::Filesystem { .. } => {}
         }
 
         println!();
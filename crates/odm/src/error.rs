@@ -76,4 +76,10 @@ pub enum CliError {
 
     #[error("Pipeline error: {0}")]
     PipelineError(String),
+
+    #[error("PII error: {0}")]
+    PiiError(String),
+
+    #[error("LLM error: {0}")]
+    LlmError(String),
 }
@@ -11,25 +11,46 @@ use commands::db::{
     DbExportArgs, DbInitArgs, DbStatusArgs, DbSyncArgs, handle_db_export, handle_db_init,
     handle_db_status, handle_db_sync,
 };
+use commands::decision::{
+    DecisionExportArgs, DecisionIndexArgs, DecisionListArgs, DecisionNewArgs, DecisionShowArgs,
+    DecisionStatusArgs, DecisionSupersedeArgs, DecisionTransitionArgs, handle_decision_accept,
+    handle_decision_export, handle_decision_index, handle_decision_list, handle_decision_new,
+    handle_decision_propose, handle_decision_show, handle_decision_status,
+    handle_decision_supersede,
+};
+#[cfg(feature = "llm")]
+use commands::docs::{DocsApplyArgs, DocsSuggestArgs, handle_docs_apply, handle_docs_suggest};
 use commands::export::{
     ExportArgs, ExportFormat, handle_export_avro, handle_export_branded_markdown,
-    handle_export_json_schema, handle_export_markdown, handle_export_odcs, handle_export_odps,
-    handle_export_pdf, handle_export_protobuf, handle_export_protobuf_descriptor,
+    handle_export_dbt, handle_export_json_schema, handle_export_markdown, handle_export_odcs,
+    handle_export_odps, handle_export_pdf, handle_export_protobuf,
+    handle_export_protobuf_descriptor,
 };
 #[cfg(feature = "odps-validation")]
 use commands::import::handle_import_odps;
 #[cfg(feature = "openapi")]
 use commands::import::handle_import_openapi;
 use commands::import::{
-    ImportArgs, ImportFormat, InputSource, handle_import_avro, handle_import_json_schema,
-    handle_import_odcl, handle_import_odcs, handle_import_protobuf, handle_import_sql,
+    ImportArgs, ImportFormat, InputSource, handle_import_avro, handle_import_iceberg,
+    handle_import_json_schema, handle_import_odcl, handle_import_odcs, handle_import_protobuf,
+    handle_import_sql,
 };
+#[cfg(feature = "lakehouse-import")]
+use commands::import::{handle_import_delta, handle_import_parquet};
 #[cfg(all(feature = "inference", feature = "staging"))]
 use commands::inference::{
     InferenceInferArgs, InferenceSchemasArgs, handle_inference_infer, handle_inference_schemas,
 };
+use commands::knowledge::{
+    KnowledgeExportArgs, KnowledgeListArgs, KnowledgeNewArgs, KnowledgePublishArgs,
+    KnowledgeReviewArgs, KnowledgeSearchArgs, KnowledgeShowArgs, KnowledgeStatusArgs,
+    handle_knowledge_export, handle_knowledge_list, handle_knowledge_new, handle_knowledge_publish,
+    handle_knowledge_review, handle_knowledge_search, handle_knowledge_show,
+    handle_knowledge_status,
+};
 #[cfg(feature = "mapping")]
 use commands::mapping::{MapArgs, handle_map};
+use commands::pii::{PiiScanArgs, handle_pii_scan};
 #[cfg(feature = "pipeline")]
 use commands::pipeline::{
     PipelineRunArgs, PipelineStatusArgs, handle_pipeline_run, handle_pipeline_status,
@@ -38,11 +59,20 @@ use commands::pipeline::{
 use commands::query::{QueryArgs, handle_query};
 #[cfg(feature = "staging")]
 use commands::staging::{
-    StagingBatchesArgs, StagingExportArgs, StagingHistoryArgs, StagingIngestArgs, StagingInitArgs,
-    StagingQueryArgs, StagingSampleArgs, StagingStatsArgs, StagingViewCreateArgs,
-    handle_staging_batches, handle_staging_export, handle_staging_history, handle_staging_ingest,
-    handle_staging_init, handle_staging_query, handle_staging_sample, handle_staging_stats,
-    handle_staging_view_create,
+    StagingBatchesArgs, StagingDeadLetterDiscardArgs, StagingDeadLetterListArgs,
+    StagingDeadLetterReingestArgs, StagingDeadLetterShowArgs, StagingExportArgs,
+    StagingHistoryArgs, StagingIngestArgs, StagingInitArgs, StagingPartitionArchiveArgs,
+    StagingPartitionCompactArgs, StagingPartitionDropArgs, StagingPartitionListArgs,
+    StagingPartitionMergeArgs, StagingPartitionRenameArgs, StagingPartitionRetainArgs,
+    StagingProfileArgs, StagingQueryArgs, StagingSampleArgs, StagingStatsArgs, StagingVacuumArgs,
+    StagingViewCreateArgs, handle_staging_batches, handle_staging_dead_letter_discard,
+    handle_staging_dead_letter_list, handle_staging_dead_letter_reingest,
+    handle_staging_dead_letter_show, handle_staging_export, handle_staging_history,
+    handle_staging_ingest, handle_staging_init, handle_staging_partition_archive,
+    handle_staging_partition_compact, handle_staging_partition_drop, handle_staging_partition_list,
+    handle_staging_partition_merge, handle_staging_partition_rename,
+    handle_staging_partition_retain, handle_staging_profile, handle_staging_query,
+    handle_staging_sample, handle_staging_stats, handle_staging_vacuum, handle_staging_view_create,
 };
 use commands::validate::handle_validate;
 #[cfg(feature = "staging")]
@@ -133,6 +163,9 @@ enum Commands {
         /// Include table of contents (branded-markdown format)
         #[arg(long)]
         include_toc: bool,
+        /// Export the contract as a dbt source with this name instead of a model (dbt format)
+        #[arg(long)]
+        dbt_source: Option<String>,
     },
     /// Validate a file against its schema
     Validate {
@@ -181,9 +214,9 @@ enum Commands {
     /// Map source schema to target schema
     #[cfg(feature = "mapping")]
     Map {
-        /// Source schema file (.json or .yaml)
+        /// Source schema file (JSON Schema or .odcs.yaml contract)
         source: PathBuf,
-        /// Target schema file (.json or .yaml)
+        /// Target schema file (JSON Schema or .odcs.yaml contract)
         target: PathBuf,
         /// Output file for mapping result
         #[arg(short, long)]
@@ -197,12 +230,42 @@ enum Commands {
         /// Enable case-insensitive matching
         #[arg(long)]
         case_insensitive: bool,
-        /// Transform format (sql, jq, python, pyspark)
+        /// Transform format (sql, jq, python, pyspark, dbt)
         #[arg(long, default_value = "sql")]
         transform_format: String,
         /// Output file for generated transform script
         #[arg(long)]
         transform_output: Option<PathBuf>,
+        /// dbt source name the staging model reads from (dbt format with contracts)
+        #[arg(long, default_value = "raw")]
+        dbt_source: String,
+        /// Mapping specification file to merge with and keep up to date (.mapping.yaml)
+        #[arg(long)]
+        spec: Option<PathBuf>,
+        /// Domain recorded in a newly created mapping specification
+        #[arg(long, requires = "spec")]
+        domain: Option<String>,
+        /// Write lineage (transformSourceObjects/transformLogic) into the target contract
+        #[arg(long)]
+        write_lineage: bool,
+        /// Execute the mapping against sample records (JSON array or JSON Lines)
+        #[arg(long)]
+        test_records: Option<PathBuf>,
+        /// Execute the mapping against records in a staging database
+        #[cfg(feature = "staging")]
+        #[arg(long, conflicts_with = "test_records")]
+        test_database: Option<PathBuf>,
+        /// Staging partition to execute against (with --test-database)
+        #[cfg(feature = "staging")]
+        #[arg(long, requires = "test_database")]
+        partition: Option<String>,
+        /// Maximum number of staged records to execute (0 = all)
+        #[cfg(feature = "staging")]
+        #[arg(long, default_value = "1000", requires = "test_database")]
+        test_limit: usize,
+        /// Output file for the execution report (JSON)
+        #[arg(long)]
+        test_report: Option<PathBuf>,
         /// Show verbose mapping details
         #[arg(short, long)]
         verbose: bool,
@@ -214,6 +277,346 @@ enum Commands {
         #[command(subcommand)]
         command: PipelineCommands,
     },
+
+    /// Detect and classify PII in workspace tables
+    Pii {
+        #[command(subcommand)]
+        command: PiiCommands,
+    },
+
+    /// Propose missing table and column documentation with an LLM
+    #[cfg(feature = "llm")]
+    Docs {
+        #[command(subcommand)]
+        command: DocsCommands,
+    },
+
+    /// Manage architecture decision records (MADR)
+    Decision {
+        #[command(subcommand)]
+        command: DecisionCommands,
+    },
+
+    /// Manage knowledge base articles
+    Knowledge {
+        #[command(subcommand)]
+        command: KnowledgeCommands,
+    },
+}
+
+#[derive(Subcommand)]
+enum DecisionCommands {
+    /// Create a new draft decision
+    New {
+        /// Decision title
+        title: String,
+        /// Decision category
+        #[arg(short, long, default_value = "architecture")]
+        category: String,
+        /// Domain this decision belongs to
+        #[arg(short, long)]
+        domain: Option<String>,
+        /// Workspace path (default: current directory)
+        #[arg(short, long, default_value = ".")]
+        workspace: PathBuf,
+        /// Also export as Markdown
+        #[arg(long)]
+        markdown: bool,
+    },
+    /// List decisions in the workspace
+    List {
+        /// Workspace path (default: current directory)
+        #[arg(short, long, default_value = ".")]
+        workspace: PathBuf,
+        /// Filter by status
+        #[arg(short, long)]
+        status: Option<String>,
+        /// Filter by category
+        #[arg(short, long)]
+        category: Option<String>,
+        /// Filter by domain
+        #[arg(short, long)]
+        domain: Option<String>,
+        /// Output format (table, json, csv)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+    },
+    /// Show a decision
+    Show {
+        /// Decision number (e.g., ADR-0001 or 1)
+        number: String,
+        /// Workspace path (default: current directory)
+        #[arg(short, long, default_value = ".")]
+        workspace: PathBuf,
+        /// Output format (yaml, markdown, json)
+        #[arg(short, long, default_value = "yaml")]
+        format: String,
+    },
+    /// Set a decision's status directly
+    Status {
+        /// Decision number
+        number: String,
+        /// New status (draft, proposed, accepted, rejected, deprecated, superseded)
+        status: String,
+        /// Workspace path (default: current directory)
+        #[arg(short, long, default_value = ".")]
+        workspace: PathBuf,
+    },
+    /// Propose a draft decision
+    Propose {
+        /// Decision number
+        number: String,
+        /// Workspace path (default: current directory)
+        #[arg(short, long, default_value = ".")]
+        workspace: PathBuf,
+    },
+    /// Accept a proposed decision
+    Accept {
+        /// Decision number
+        number: String,
+        /// Workspace path (default: current directory)
+        #[arg(short, long, default_value = ".")]
+        workspace: PathBuf,
+    },
+    /// Supersede an accepted decision with another one
+    Supersede {
+        /// Number of the decision being replaced
+        number: String,
+        /// Number of the decision replacing it
+        #[arg(long)]
+        by: String,
+        /// Workspace path (default: current directory)
+        #[arg(short, long, default_value = ".")]
+        workspace: PathBuf,
+    },
+    /// Regenerate decisions.yaml from the decision files
+    Index {
+        /// Workspace path (default: current directory)
+        #[arg(short, long, default_value = ".")]
+        workspace: PathBuf,
+    },
+    /// Export decisions as Markdown
+    Export {
+        /// Workspace path (default: current directory)
+        #[arg(short, long, default_value = ".")]
+        workspace: PathBuf,
+        /// Output directory (default: <workspace>/decisions)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Export only this decision number
+        #[arg(short, long)]
+        number: Option<String>,
+        /// Generate a README.md index
+        #[arg(long)]
+        index: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum KnowledgeCommands {
+    /// Create a new draft article
+    New {
+        /// Article title
+        title: String,
+        /// Article type (guide, standard, reference, howto, troubleshooting, policy, template, concept, runbook)
+        #[arg(short = 't', long = "type", default_value = "guide")]
+        article_type: String,
+        /// Domain this article belongs to
+        #[arg(short, long)]
+        domain: Option<String>,
+        /// Author name
+        #[arg(short, long)]
+        author: String,
+        /// Author email
+        #[arg(long)]
+        author_email: Option<String>,
+        /// Workspace path (default: current directory)
+        #[arg(short, long, default_value = ".")]
+        workspace: PathBuf,
+        /// Also export as Markdown
+        #[arg(long)]
+        markdown: bool,
+    },
+    /// List articles in the workspace
+    List {
+        /// Workspace path (default: current directory)
+        #[arg(short, long, default_value = ".")]
+        workspace: PathBuf,
+        /// Filter by status
+        #[arg(short, long)]
+        status: Option<String>,
+        /// Filter by type
+        #[arg(short = 't', long = "type")]
+        article_type: Option<String>,
+        /// Filter by domain
+        #[arg(short, long)]
+        domain: Option<String>,
+        /// Filter by author
+        #[arg(short, long)]
+        author: Option<String>,
+        /// Output format (table, json, csv)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+    },
+    /// Show an article
+    Show {
+        /// Article number (e.g., KB-0001 or 1)
+        number: String,
+        /// Workspace path (default: current directory)
+        #[arg(short, long, default_value = ".")]
+        workspace: PathBuf,
+        /// Output format (yaml, markdown, json)
+        #[arg(short, long, default_value = "yaml")]
+        format: String,
+    },
+    /// Set an article's status directly
+    Status {
+        /// Article number
+        number: String,
+        /// New status (draft, review, published, archived, deprecated)
+        status: String,
+        /// Workspace path (default: current directory)
+        #[arg(short, long, default_value = ".")]
+        workspace: PathBuf,
+    },
+    /// Submit a draft article for review
+    Review {
+        /// Article number
+        number: String,
+        /// Reviewer to assign
+        #[arg(short, long)]
+        reviewer: Option<String>,
+        /// Workspace path (default: current directory)
+        #[arg(short, long, default_value = ".")]
+        workspace: PathBuf,
+    },
+    /// Publish a reviewed article
+    Publish {
+        /// Article number
+        number: String,
+        /// Workspace path (default: current directory)
+        #[arg(short, long, default_value = ".")]
+        workspace: PathBuf,
+    },
+    /// Export articles as Markdown
+    Export {
+        /// Workspace path (default: current directory)
+        #[arg(short, long, default_value = ".")]
+        workspace: PathBuf,
+        /// Output directory (default: <workspace>/knowledge)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Export only this article number
+        #[arg(short, long)]
+        number: Option<String>,
+        /// Generate a README.md index
+        #[arg(long)]
+        index: bool,
+        /// Organise output into one directory per domain
+        #[arg(long)]
+        by_domain: bool,
+    },
+    /// Search article titles, summaries, content and tags
+    Search {
+        /// Search query
+        query: String,
+        /// Workspace path (default: current directory)
+        #[arg(short, long, default_value = ".")]
+        workspace: PathBuf,
+        /// Output format (table, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum PiiCommands {
+    /// Scan ODCS files for PII and report where it lives
+    Scan {
+        /// Workspace directory or ODCS file (default: current directory)
+        #[arg(default_value = ".")]
+        path: PathBuf,
+        /// Organisation PII rules file (YAML)
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+        /// Output format (table, json, markdown)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+        /// Output file for the report (stdout if not provided)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Write classification and critical data element flags back to the files
+        #[arg(long)]
+        apply: bool,
+    },
+}
+
+#[cfg(feature = "llm")]
+#[derive(Subcommand)]
+#[allow(clippy::large_enum_variant)]
+enum DocsCommands {
+    /// Propose descriptions and business names where ODCS tables lack them
+    Suggest {
+        /// Workspace directory or ODCS file (default: current directory)
+        #[arg(default_value = ".")]
+        path: PathBuf,
+        /// Directory of knowledge base articles; articles linked to a table add context
+        #[arg(short, long)]
+        knowledge: Option<PathBuf>,
+        /// Output file for the patch (stdout if not provided)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Output format (yaml, markdown)
+        #[arg(short, long, default_value = "yaml")]
+        format: String,
+        /// Only propose descriptions, not business names
+        #[arg(long)]
+        no_business_names: bool,
+        /// LLM mode: online, offline, openai
+        #[arg(long, default_value = "online")]
+        llm: String,
+        /// Ollama URL for online mode
+        #[arg(long, default_value = "http://localhost:11434")]
+        ollama_url: String,
+        /// Base URL of an OpenAI-compatible server for openai mode
+        #[arg(long, default_value = "http://localhost:8000/v1")]
+        openai_url: String,
+        /// Environment variable holding the API key for openai mode
+        #[arg(long)]
+        api_key_env: Option<String>,
+        /// Model name
+        #[arg(long, default_value = "llama3.2")]
+        model: String,
+        /// Path to GGUF model file for offline mode
+        #[arg(long)]
+        model_path: Option<PathBuf>,
+        /// Path to documentation file for context
+        #[arg(long)]
+        doc_path: Option<PathBuf>,
+        /// Embedding model used to rank documentation chunks alongside keywords
+        #[arg(long)]
+        embedding_model: Option<String>,
+        /// Temperature for LLM generation (0.0-2.0)
+        #[arg(long, default_value = "0.3")]
+        temperature: f32,
+        /// Cache LLM responses in this directory or `.duckdb` staging database
+        #[arg(long)]
+        llm_cache: Option<PathBuf>,
+        /// LLM cache mode: readwrite, record, replay (replay needs no model)
+        #[arg(long, default_value = "readwrite")]
+        llm_cache_mode: String,
+    },
+    /// Apply a reviewed documentation patch to ODCS files
+    Apply {
+        /// Documentation patch file (YAML)
+        patch: PathBuf,
+        /// Workspace directory or ODCS file (default: current directory)
+        #[arg(default_value = ".")]
+        path: PathBuf,
+        /// Show what would change without writing files
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 #[cfg(feature = "staging")]
@@ -224,7 +627,7 @@ enum StagingCommands {
         /// Path to the staging database file
         #[arg(default_value = "staging.duckdb")]
         database: PathBuf,
-        /// Catalog type for Iceberg backend (rest, s3-tables, unity, glue)
+        /// Catalog type for Iceberg backend (rest, s3-tables, unity, glue, filesystem)
         #[arg(long)]
         catalog: Option<String>,
         /// Catalog endpoint URL (for REST, Unity)
@@ -272,6 +675,24 @@ enum StagingCommands {
         /// Batch ID to resume (required with --resume)
         #[arg(long)]
         batch_id: Option<String>,
+        /// Fail the batch once more than this many records are rejected
+        #[arg(long)]
+        max_errors: Option<usize>,
+        /// Fail the batch when the share of rejected records exceeds this ratio (0.0-1.0)
+        #[arg(long)]
+        max_error_rate: Option<f64>,
+        /// Keep watching the source directory and ingest new and changed files
+        #[arg(short, long, conflicts_with = "resume")]
+        watch: bool,
+        /// Seconds between two polls in watch mode
+        #[arg(long, default_value = "5", requires = "watch")]
+        poll_interval: u64,
+        /// Partition watched files by: fixed (--partition), directory, date
+        #[arg(long, default_value = "fixed", requires = "watch")]
+        partition_by: String,
+        /// Write the schema inferred from the watched records to this file (requires inference)
+        #[arg(long, requires = "watch")]
+        infer_output: Option<PathBuf>,
     },
 
     /// Show staging database statistics
@@ -325,6 +746,34 @@ enum StagingCommands {
         partition: Option<String>,
     },
 
+    /// Profile staged records per field (completeness, distributions, formats)
+    Profile {
+        /// Path to the staging database file
+        #[arg(short, long, default_value = "staging.duckdb")]
+        database: PathBuf,
+        /// Partition to profile
+        #[arg(short = 'k', long)]
+        partition: Option<String>,
+        /// Number of most frequent values per field
+        #[arg(long, default_value = "10")]
+        top_k: usize,
+        /// Profile a random sample of this many records
+        #[arg(short, long)]
+        sample_size: Option<usize>,
+        /// Output format (markdown, json)
+        #[arg(short, long, default_value = "markdown")]
+        format: String,
+        /// Output file path (stdout if not provided)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Show a stored profile version instead of profiling
+        #[arg(long)]
+        version: Option<i32>,
+        /// Do not store the profile in the staging database
+        #[arg(long)]
+        no_save: bool,
+    },
+
     /// Show table version history (requires Iceberg)
     History {
         /// Path to the staging database file
@@ -377,6 +826,28 @@ enum StagingCommands {
         #[command(subcommand)]
         command: StagingViewCommands,
     },
+
+    /// Inspect, re-ingest or discard records rejected during ingestion
+    DeadLetters {
+        #[command(subcommand)]
+        command: StagingDeadLetterCommands,
+    },
+
+    /// List, drop, rename, merge, compact, archive and expire partitions
+    Partitions {
+        #[command(subcommand)]
+        command: StagingPartitionCommands,
+    },
+
+    /// Release space held by deleted records
+    Vacuum {
+        /// Path to the staging database file
+        #[arg(short, long, default_value = "staging.duckdb")]
+        database: PathBuf,
+        /// Rewrite the database file to reclaim all free space
+        #[arg(long)]
+        rewrite: bool,
+    },
 }
 
 #[cfg(feature = "staging")]
@@ -399,6 +870,137 @@ enum StagingViewCommands {
     },
 }
 
+#[cfg(feature = "staging")]
+#[derive(Subcommand)]
+enum StagingDeadLetterCommands {
+    /// List quarantined records
+    List {
+        /// Path to the staging database file
+        #[arg(short, long, default_value = "staging.duckdb")]
+        database: PathBuf,
+        /// Only show records rejected by this batch
+        #[arg(long)]
+        batch_id: Option<String>,
+        /// Only show records with this status (pending, reingested, discarded)
+        #[arg(short, long)]
+        status: Option<String>,
+        /// Maximum number of records to show
+        #[arg(short, long, default_value = "20")]
+        limit: usize,
+    },
+    /// Show a quarantined record with its raw data
+    Show {
+        /// Path to the staging database file
+        #[arg(short, long, default_value = "staging.duckdb")]
+        database: PathBuf,
+        /// Dead letter ID
+        id: String,
+    },
+    /// Ingest a quarantined record, optionally replacing its data with a fix
+    Reingest {
+        /// Path to the staging database file
+        #[arg(short, long, default_value = "staging.duckdb")]
+        database: PathBuf,
+        /// Dead letter ID
+        id: String,
+        /// Fixed JSON to ingest instead of the raw data
+        #[arg(long, conflicts_with = "file")]
+        data: Option<String>,
+        /// File containing fixed JSON to ingest instead of the raw data
+        #[arg(short, long)]
+        file: Option<PathBuf>,
+    },
+    /// Discard a quarantined record without ingesting it
+    Discard {
+        /// Path to the staging database file
+        #[arg(short, long, default_value = "staging.duckdb")]
+        database: PathBuf,
+        /// Dead letter ID
+        id: String,
+    },
+}
+
+#[cfg(feature = "staging")]
+#[derive(Subcommand)]
+enum StagingPartitionCommands {
+    /// List partitions with record counts and age
+    List {
+        /// Path to the staging database file
+        #[arg(short, long, default_value = "staging.duckdb")]
+        database: PathBuf,
+    },
+    /// Drop a partition with its dead letters and processing batches
+    Drop {
+        /// Path to the staging database file
+        #[arg(short, long, default_value = "staging.duckdb")]
+        database: PathBuf,
+        /// Partition to drop
+        partition: String,
+        /// Archive the partition to this Parquet file before dropping it
+        #[arg(short, long)]
+        archive: Option<PathBuf>,
+    },
+    /// Rename a partition
+    Rename {
+        /// Path to the staging database file
+        #[arg(short, long, default_value = "staging.duckdb")]
+        database: PathBuf,
+        /// Current partition name
+        from: String,
+        /// New partition name
+        to: String,
+    },
+    /// Merge partitions into one
+    Merge {
+        /// Path to the staging database file
+        #[arg(short, long, default_value = "staging.duckdb")]
+        database: PathBuf,
+        /// Partitions to merge
+        #[arg(required = true)]
+        sources: Vec<String>,
+        /// Target partition (created if it does not exist)
+        #[arg(long)]
+        into: String,
+    },
+    /// Remove byte-identical duplicate records from a partition
+    Compact {
+        /// Path to the staging database file
+        #[arg(short, long, default_value = "staging.duckdb")]
+        database: PathBuf,
+        /// Partition to compact
+        partition: String,
+    },
+    /// Archive a partition to a Parquet file
+    Archive {
+        /// Path to the staging database file
+        #[arg(short, long, default_value = "staging.duckdb")]
+        database: PathBuf,
+        /// Partition to archive
+        partition: String,
+        /// Output Parquet file
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+    /// Drop partitions that are too old or beyond a partition count
+    Retain {
+        /// Path to the staging database file
+        #[arg(short, long, default_value = "staging.duckdb")]
+        database: PathBuf,
+        /// Drop partitions whose last record is at least this many days old
+        #[arg(long)]
+        max_age_days: Option<i64>,
+        /// Keep only this many most recently ingested partitions
+        #[arg(long)]
+        keep: Option<usize>,
+        /// Archive dropped partitions to Parquet files in this directory
+        #[arg(long)]
+        archive_dir: Option<PathBuf>,
+        /// Show which partitions would be dropped without dropping them
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
 #[cfg(feature = "staging")]
 fn parse_dedup_strategy(s: &str) -> Result<DedupStrategy, String> {
     s.parse().map_err(|_| {
@@ -411,6 +1013,7 @@ fn parse_dedup_strategy(s: &str) -> Result<DedupStrategy, String> {
 
 #[cfg(all(feature = "inference", feature = "staging"))]
 #[derive(Subcommand)]
+#[allow(clippy::large_enum_variant)]
 enum InferenceCommands {
     /// Infer schema from staged JSON data
     Infer {
@@ -432,20 +1035,44 @@ enum InferenceCommands {
         /// Disable format detection
         #[arg(long)]
         no_formats: bool,
-        /// Output format (json, yaml, json-schema)
+        /// Output format (json, yaml, json-schema, odcs)
         #[arg(short, long, default_value = "json")]
         format: String,
         /// Output file path (stdout if not provided)
         #[arg(short, long)]
         output: Option<PathBuf>,
+        /// Field that discriminates record variants (default: detect automatically)
+        #[arg(long)]
+        discriminator: Option<String>,
+        /// Disable discriminated union detection
+        #[arg(long)]
+        no_variants: bool,
+        /// Incremental state file: fold only records staged since the last run
+        #[arg(long)]
+        state: Option<PathBuf>,
+        /// Maximum new records folded into the state per run (0 = all)
+        #[arg(long, default_value = "0", requires = "state")]
+        max_new_records: usize,
 
         // LLM refinement options
-        /// LLM mode: none, online, offline (requires llm feature)
+        /// LLM mode: none, online, offline, openai (requires llm feature)
         #[arg(long, default_value = "none")]
         llm: String,
         /// Ollama URL for online mode
         #[arg(long, default_value = "http://localhost:11434")]
         ollama_url: String,
+        /// Base URL of an OpenAI-compatible server for openai mode
+        #[arg(long, default_value = "http://localhost:8000/v1")]
+        openai_url: String,
+        /// Environment variable holding the API key for openai mode
+        #[arg(long)]
+        api_key_env: Option<String>,
+        /// Cache LLM responses in this directory or `.duckdb` staging database
+        #[arg(long)]
+        llm_cache: Option<PathBuf>,
+        /// LLM cache mode: readwrite, record, replay (replay needs no model)
+        #[arg(long, default_value = "readwrite")]
+        llm_cache_mode: String,
         /// Model name for LLM refinement
         #[arg(long, default_value = "llama3.2")]
         model: String,
@@ -455,6 +1082,9 @@ enum InferenceCommands {
         /// Path to documentation file for context
         #[arg(long)]
         doc_path: Option<PathBuf>,
+        /// Embedding model used to rank documentation chunks alongside keywords
+        #[arg(long)]
+        embedding_model: Option<String>,
         /// Skip LLM refinement even if configured
         #[arg(long)]
         no_refine: bool,
@@ -464,6 +1094,12 @@ enum InferenceCommands {
         /// Verbose LLM output
         #[arg(long)]
         verbose_llm: bool,
+        /// Organisation PII rules file used to classify ODCS output (YAML)
+        #[arg(long)]
+        pii_config: Option<PathBuf>,
+        /// Skip PII classification of ODCS output
+        #[arg(long)]
+        no_pii: bool,
     },
 
     /// Analyze and group schemas across partitions
@@ -506,12 +1142,24 @@ enum PipelineCommands {
         /// Stages to run (ingest, infer, map, export)
         #[arg(long, value_delimiter = ',')]
         stages: Vec<String>,
-        /// LLM mode for inference: none, online, offline
+        /// LLM mode for inference: none, online, offline, openai
         #[arg(long, default_value = "none")]
         llm_mode: String,
         /// Ollama URL for online LLM mode
         #[arg(long, default_value = "http://localhost:11434")]
         ollama_url: String,
+        /// Base URL of an OpenAI-compatible server for openai LLM mode
+        #[arg(long, default_value = "http://localhost:8000/v1")]
+        openai_url: String,
+        /// Environment variable holding the API key for openai LLM mode
+        #[arg(long)]
+        api_key_env: Option<String>,
+        /// Cache LLM responses in this directory or `.duckdb` staging database
+        #[arg(long)]
+        llm_cache: Option<PathBuf>,
+        /// LLM cache mode: readwrite, record, replay (replay needs no model)
+        #[arg(long, default_value = "readwrite")]
+        llm_cache_mode: String,
         /// LLM model name
         #[arg(long, default_value = "llama3.2")]
         model: String,
@@ -524,7 +1172,7 @@ enum PipelineCommands {
         /// Temperature for LLM generation (0.0-2.0)
         #[arg(long, default_value = "0.3")]
         temperature: f32,
-        /// Configuration file path
+        /// Pipeline definition file (YAML or TOML) with multiple sources and entities
         #[arg(long)]
         config_file: Option<PathBuf>,
         /// Dry run mode (don't write outputs)
@@ -600,6 +1248,12 @@ enum ImportFormatArg {
     Odcs,
     Odcl,
     Odps,
+    /// Parquet file footer (file or directory of Hive-partitioned files)
+    Parquet,
+    /// Iceberg table metadata (metadata.json, metadata directory or table directory)
+    Iceberg,
+    /// Delta Lake table (table directory or its _delta_log)
+    Delta,
 }
 
 #[derive(clap::ValueEnum, Clone, Debug)]
@@ -616,6 +1270,8 @@ enum ExportFormatArg {
     Markdown,
     /// Branded Markdown export with logo, header, footer
     BrandedMarkdown,
+    /// dbt project fragment (schema.yml or sources.yml plus tests)
+    Dbt,
 }
 
 #[derive(clap::ValueEnum, Clone, Debug)]
@@ -658,6 +1314,9 @@ fn convert_import_format(format: ImportFormatArg) -> ImportFormat {
         ImportFormatArg::Odcs => ImportFormat::Odcs,
         ImportFormatArg::Odcl => ImportFormat::Odcl,
         ImportFormatArg::Odps => ImportFormat::Odps,
+        ImportFormatArg::Parquet => ImportFormat::Parquet,
+        ImportFormatArg::Iceberg => ImportFormat::Iceberg,
+        ImportFormatArg::Delta => ImportFormat::Delta,
     }
 }
 
@@ -672,6 +1331,7 @@ fn convert_export_format(format: ExportFormatArg) -> ExportFormat {
         ExportFormatArg::Pdf => ExportFormat::Pdf,
         ExportFormatArg::Markdown => ExportFormat::BrandedMarkdown, // Use same handler, no branding
         ExportFormatArg::BrandedMarkdown => ExportFormat::BrandedMarkdown,
+        ExportFormatArg::Dbt => ExportFormat::Dbt,
     }
 }
 
@@ -755,6 +1415,33 @@ fn main() {
                         ))
                     }
                 }
+                ImportFormat::Iceberg => handle_import_iceberg(&args),
+                ImportFormat::Parquet => {
+                    #[cfg(feature = "lakehouse-import")]
+                    {
+                        handle_import_parquet(&args)
+                    }
+                    #[cfg(not(feature = "lakehouse-import"))]
+                    {
+                        Err(error::CliError::InvalidArgument(
+                            "Parquet support not enabled. Enable 'lakehouse-import' feature."
+                                .to_string(),
+                        ))
+                    }
+                }
+                ImportFormat::Delta => {
+                    #[cfg(feature = "lakehouse-import")]
+                    {
+                        handle_import_delta(&args)
+                    }
+                    #[cfg(not(feature = "lakehouse-import"))]
+                    {
+                        Err(error::CliError::InvalidArgument(
+                            "Delta Lake support not enabled. Enable 'lakehouse-import' feature."
+                                .to_string(),
+                        ))
+                    }
+                }
             }
         }
         Commands::Export {
@@ -770,6 +1457,7 @@ fn main() {
             brand_color,
             company_name,
             include_toc,
+            dbt_source,
         } => {
             let export_format = convert_export_format(format.clone());
 
@@ -786,6 +1474,7 @@ fn main() {
                 brand_color,
                 company_name,
                 include_toc,
+                dbt_source,
             };
 
             match args.format {
@@ -796,6 +1485,7 @@ fn main() {
                 ExportFormat::ProtobufDescriptor => handle_export_protobuf_descriptor(&args),
                 ExportFormat::Odps => handle_export_odps(&args),
                 ExportFormat::Pdf => handle_export_pdf(&args),
+                ExportFormat::Dbt => handle_export_dbt(&args),
                 ExportFormat::BrandedMarkdown => {
                     // If no branding options provided, use standard markdown export
                     if args.logo_url.is_none()
@@ -906,6 +1596,12 @@ fn main() {
                 batch_size,
                 resume,
                 batch_id,
+                max_errors,
+                max_error_rate,
+                watch,
+                poll_interval,
+                partition_by,
+                infer_output,
             } => {
                 let args = StagingIngestArgs {
                     database,
@@ -916,6 +1612,12 @@ fn main() {
                     batch_size,
                     resume,
                     batch_id,
+                    max_errors,
+                    max_error_rate,
+                    watch,
+                    poll_interval,
+                    partition_by,
+                    infer_output,
                 };
                 handle_staging_ingest(&args)
             }
@@ -961,6 +1663,28 @@ fn main() {
                 };
                 handle_staging_sample(&args)
             }
+            StagingCommands::Profile {
+                database,
+                partition,
+                top_k,
+                sample_size,
+                format,
+                output,
+                version,
+                no_save,
+            } => {
+                let args = StagingProfileArgs {
+                    database,
+                    partition,
+                    top_k,
+                    sample_size,
+                    format,
+                    output,
+                    version,
+                    no_save,
+                };
+                handle_staging_profile(&args)
+            }
             StagingCommands::History {
                 database,
                 table,
@@ -1015,6 +1739,120 @@ fn main() {
                     handle_staging_view_create(&args)
                 }
             },
+            StagingCommands::DeadLetters { command } => match command {
+                StagingDeadLetterCommands::List {
+                    database,
+                    batch_id,
+                    status,
+                    limit,
+                } => {
+                    let args = StagingDeadLetterListArgs {
+                        database,
+                        batch_id,
+                        status,
+                        limit,
+                    };
+                    handle_staging_dead_letter_list(&args)
+                }
+                StagingDeadLetterCommands::Show { database, id } => {
+                    let args = StagingDeadLetterShowArgs { database, id };
+                    handle_staging_dead_letter_show(&args)
+                }
+                StagingDeadLetterCommands::Reingest {
+                    database,
+                    id,
+                    data,
+                    file,
+                } => {
+                    let args = StagingDeadLetterReingestArgs {
+                        database,
+                        id,
+                        data,
+                        file,
+                    };
+                    handle_staging_dead_letter_reingest(&args)
+                }
+                StagingDeadLetterCommands::Discard { database, id } => {
+                    let args = StagingDeadLetterDiscardArgs { database, id };
+                    handle_staging_dead_letter_discard(&args)
+                }
+            },
+            StagingCommands::Partitions { command } => match command {
+                StagingPartitionCommands::List { database } => {
+                    let args = StagingPartitionListArgs { database };
+                    handle_staging_partition_list(&args)
+                }
+                StagingPartitionCommands::Drop {
+                    database,
+                    partition,
+                    archive,
+                } => {
+                    let args = StagingPartitionDropArgs {
+                        database,
+                        partition,
+                        archive,
+                    };
+                    handle_staging_partition_drop(&args)
+                }
+                StagingPartitionCommands::Rename { database, from, to } => {
+                    let args = StagingPartitionRenameArgs { database, from, to };
+                    handle_staging_partition_rename(&args)
+                }
+                StagingPartitionCommands::Merge {
+                    database,
+                    sources,
+                    into,
+                } => {
+                    let args = StagingPartitionMergeArgs {
+                        database,
+                        sources,
+                        into,
+                    };
+                    handle_staging_partition_merge(&args)
+                }
+                StagingPartitionCommands::Compact {
+                    database,
+                    partition,
+                } => {
+                    let args = StagingPartitionCompactArgs {
+                        database,
+                        partition,
+                    };
+                    handle_staging_partition_compact(&args)
+                }
+                StagingPartitionCommands::Archive {
+                    database,
+                    partition,
+                    output,
+                } => {
+                    let args = StagingPartitionArchiveArgs {
+                        database,
+                        partition,
+                        output,
+                    };
+                    handle_staging_partition_archive(&args)
+                }
+                StagingPartitionCommands::Retain {
+                    database,
+                    max_age_days,
+                    keep,
+                    archive_dir,
+                    dry_run,
+                } => {
+                    let args = StagingPartitionRetainArgs {
+                        database,
+                        max_age_days,
+                        keep,
+                        archive_dir,
+                        dry_run,
+                    };
+                    handle_staging_partition_retain(&args)
+                }
+            },
+            StagingCommands::Vacuum { database, rewrite } => {
+                let args = StagingVacuumArgs { database, rewrite };
+                handle_staging_vacuum(&args)
+            }
         },
 
         #[cfg(all(feature = "inference", feature = "staging"))]
@@ -1030,12 +1868,23 @@ fn main() {
                 output,
                 llm,
                 ollama_url,
+                openai_url,
+                api_key_env,
+                llm_cache,
+                llm_cache_mode,
                 model,
                 model_path,
                 doc_path,
+                embedding_model,
                 no_refine,
                 temperature,
                 verbose_llm,
+                pii_config,
+                no_pii,
+                discriminator,
+                no_variants,
+                state,
+                max_new_records,
             } => {
                 let args = InferenceInferArgs {
                     database,
@@ -1048,12 +1897,23 @@ fn main() {
                     output,
                     llm_mode: llm,
                     ollama_url,
+                    openai_url,
+                    api_key_env,
+                    llm_cache,
+                    llm_cache_mode,
                     model,
                     model_path,
                     doc_path,
+                    embedding_model,
                     no_refine,
                     temperature,
                     verbose_llm,
+                    pii_config,
+                    no_pii,
+                    discriminator,
+                    no_variants,
+                    state,
+                    max_new_records,
                 };
                 handle_inference_infer(&args)
             }
@@ -1081,6 +1941,18 @@ fn main() {
             case_insensitive,
             transform_format,
             transform_output,
+            dbt_source,
+            spec,
+            domain,
+            write_lineage,
+            test_records,
+            #[cfg(feature = "staging")]
+            test_database,
+            #[cfg(feature = "staging")]
+            partition,
+            #[cfg(feature = "staging")]
+            test_limit,
+            test_report,
             verbose,
         } => {
             let args = MapArgs {
@@ -1092,6 +1964,18 @@ fn main() {
                 case_insensitive,
                 transform_format,
                 transform_output,
+                dbt_source,
+                spec,
+                domain,
+                write_lineage,
+                test_records,
+                #[cfg(feature = "staging")]
+                test_database,
+                #[cfg(feature = "staging")]
+                partition,
+                #[cfg(feature = "staging")]
+                test_limit,
+                test_report,
                 verbose,
             };
             handle_map(&args)
@@ -1109,6 +1993,10 @@ fn main() {
                 stages,
                 llm_mode,
                 ollama_url,
+                openai_url,
+                api_key_env,
+                llm_cache,
+                llm_cache_mode,
                 model,
                 model_path,
                 doc_path,
@@ -1128,6 +2016,10 @@ fn main() {
                     stages,
                     llm_mode,
                     ollama_url,
+                    openai_url,
+                    api_key_env,
+                    llm_cache,
+                    llm_cache_mode,
                     model,
                     model_path,
                     doc_path,
@@ -1144,6 +2036,285 @@ fn main() {
                 handle_pipeline_status(&args)
             }
         },
+
+        Commands::Pii { command } => match command {
+            PiiCommands::Scan {
+                path,
+                config,
+                format,
+                output,
+                apply,
+            } => {
+                let args = PiiScanArgs {
+                    path,
+                    config,
+                    format,
+                    output,
+                    apply,
+                };
+                handle_pii_scan(&args)
+            }
+        },
+
+        #[cfg(feature = "llm")]
+        Commands::Docs { command } => match command {
+            DocsCommands::Suggest {
+                path,
+                knowledge,
+                output,
+                format,
+                no_business_names,
+                llm,
+                ollama_url,
+                openai_url,
+                api_key_env,
+                model,
+                model_path,
+                doc_path,
+                embedding_model,
+                temperature,
+                llm_cache,
+                llm_cache_mode,
+            } => {
+                let args = DocsSuggestArgs {
+                    path,
+                    knowledge,
+                    output,
+                    format,
+                    no_business_names,
+                    llm_mode: llm,
+                    ollama_url,
+                    openai_url,
+                    api_key_env,
+                    model,
+                    model_path,
+                    doc_path,
+                    embedding_model,
+                    temperature,
+                    llm_cache,
+                    llm_cache_mode,
+                };
+                handle_docs_suggest(&args)
+            }
+            DocsCommands::Apply {
+                patch,
+                path,
+                dry_run,
+            } => {
+                let args = DocsApplyArgs {
+                    patch,
+                    path,
+                    dry_run,
+                };
+                handle_docs_apply(&args)
+            }
+        },
+
+        Commands::Decision { command } => match command {
+            DecisionCommands::New {
+                title,
+                category,
+                domain,
+                workspace,
+                markdown,
+            } => {
+                let args = DecisionNewArgs {
+                    title,
+                    category,
+                    domain,
+                    workspace,
+                    export_markdown: markdown,
+                };
+                handle_decision_new(&args)
+            }
+            DecisionCommands::List {
+                workspace,
+                status,
+                category,
+                domain,
+                format,
+            } => {
+                let args = DecisionListArgs {
+                    workspace,
+                    status,
+                    category,
+                    domain,
+                    format,
+                };
+                handle_decision_list(&args)
+            }
+            DecisionCommands::Show {
+                number,
+                workspace,
+                format,
+            } => {
+                let args = DecisionShowArgs {
+                    number,
+                    workspace,
+                    format,
+                };
+                handle_decision_show(&args)
+            }
+            DecisionCommands::Status {
+                number,
+                status,
+                workspace,
+            } => {
+                let args = DecisionStatusArgs {
+                    number,
+                    status,
+                    workspace,
+                };
+                handle_decision_status(&args)
+            }
+            DecisionCommands::Propose { number, workspace } => {
+                let args = DecisionTransitionArgs { number, workspace };
+                handle_decision_propose(&args)
+            }
+            DecisionCommands::Accept { number, workspace } => {
+                let args = DecisionTransitionArgs { number, workspace };
+                handle_decision_accept(&args)
+            }
+            DecisionCommands::Supersede {
+                number,
+                by,
+                workspace,
+            } => {
+                let args = DecisionSupersedeArgs {
+                    number,
+                    by,
+                    workspace,
+                };
+                handle_decision_supersede(&args)
+            }
+            DecisionCommands::Index { workspace } => {
+                let args = DecisionIndexArgs { workspace };
+                handle_decision_index(&args)
+            }
+            DecisionCommands::Export {
+                workspace,
+                output,
+                number,
+                index,
+            } => {
+                let args = DecisionExportArgs {
+                    workspace,
+                    output,
+                    number,
+                    generate_index: index,
+                };
+                handle_decision_export(&args)
+            }
+        },
+
+        Commands::Knowledge { command } => match command {
+            KnowledgeCommands::New {
+                title,
+                article_type,
+                domain,
+                author,
+                author_email,
+                workspace,
+                markdown,
+            } => {
+                let args = KnowledgeNewArgs {
+                    title,
+                    article_type,
+                    domain,
+                    author,
+                    author_email,
+                    workspace,
+                    export_markdown: markdown,
+                };
+                handle_knowledge_new(&args)
+            }
+            KnowledgeCommands::List {
+                workspace,
+                status,
+                article_type,
+                domain,
+                author,
+                format,
+            } => {
+                let args = KnowledgeListArgs {
+                    workspace,
+                    status,
+                    article_type,
+                    domain,
+                    author,
+                    format,
+                };
+                handle_knowledge_list(&args)
+            }
+            KnowledgeCommands::Show {
+                number,
+                workspace,
+                format,
+            } => {
+                let args = KnowledgeShowArgs {
+                    number,
+                    workspace,
+                    format,
+                };
+                handle_knowledge_show(&args)
+            }
+            KnowledgeCommands::Status {
+                number,
+                status,
+                workspace,
+            } => {
+                let args = KnowledgeStatusArgs {
+                    number,
+                    status,
+                    workspace,
+                };
+                handle_knowledge_status(&args)
+            }
+            KnowledgeCommands::Review {
+                number,
+                reviewer,
+                workspace,
+            } => {
+                let args = KnowledgeReviewArgs {
+                    number,
+                    reviewer,
+                    workspace,
+                };
+                handle_knowledge_review(&args)
+            }
+            KnowledgeCommands::Publish { number, workspace } => {
+                let args = KnowledgePublishArgs { number, workspace };
+                handle_knowledge_publish(&args)
+            }
+            KnowledgeCommands::Export {
+                workspace,
+                output,
+                number,
+                index,
+                by_domain,
+            } => {
+                let args = KnowledgeExportArgs {
+                    workspace,
+                    output,
+                    number,
+                    generate_index: index,
+                    by_domain,
+                };
+                handle_knowledge_export(&args)
+            }
+            KnowledgeCommands::Search {
+                query,
+                workspace,
+                format,
+            } => {
+                let args = KnowledgeSearchArgs {
+                    query,
+                    workspace,
+                    format,
+                };
+                handle_knowledge_search(&args)
+            }
+        },
     };
 
     if let Err(e) = result {